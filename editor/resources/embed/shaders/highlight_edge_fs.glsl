@@ -0,0 +1,32 @@
+uniform sampler2D maskTexture;
+uniform vec4 color;
+uniform float thickness;
+
+in vec2 texCoord;
+
+out vec4 FragColor;
+
+void main()
+{
+    // Outline is drawn only around selected objects, not on top of them.
+    if (texture(maskTexture, texCoord).r > 0.5) {
+        discard;
+    }
+
+    vec2 texelSize = 1.0 / vec2(textureSize(maskTexture, 0));
+    int radius = int(ceil(thickness));
+    float coverage = 0.0;
+    for (int y = -radius; y <= radius; ++y) {
+        for (int x = -radius; x <= radius; ++x) {
+            if (float(x * x + y * y) <= thickness * thickness) {
+                coverage = max(coverage, texture(maskTexture, texCoord + vec2(x, y) * texelSize).r);
+            }
+        }
+    }
+
+    if (coverage < 0.5) {
+        discard;
+    }
+
+    FragColor = color;
+}
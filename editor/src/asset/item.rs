@@ -8,7 +8,7 @@ use fyrox::{
         draw::{CommandTexture, Draw, DrawingContext},
         grid::{Column, GridBuilder, Row},
         image::ImageBuilder,
-        message::{MessageDirection, UiMessage},
+        message::{MessageDirection, MouseButton, UiMessage},
         text::TextBuilder,
         utils::make_simple_tooltip,
         widget::{Widget, WidgetBuilder, WidgetMessage},
@@ -39,6 +39,13 @@ pub enum AssetKind {
     Texture,
     Sound,
     Shader,
+    Material,
+}
+
+impl AssetItem {
+    pub fn is_selected(&self) -> bool {
+        self.selected
+    }
 }
 
 impl Deref for AssetItem {
@@ -85,14 +92,20 @@ impl Control for AssetItem {
     fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
         self.widget.handle_routed_message(ui, message);
 
-        if let Some(WidgetMessage::MouseDown { .. }) = message.data::<WidgetMessage>() {
+        if let Some(WidgetMessage::MouseDown { button, .. }) = message.data::<WidgetMessage>() {
             if !message.handled() {
                 message.set_handled(true);
-                ui.send_message(AssetItemMessage::select(
-                    self.handle(),
-                    MessageDirection::ToWidget,
-                    true,
-                ));
+                // Right click on a selected item keeps current selection, so the context menu could
+                // be applied to all selected items.
+                if *button != MouseButton::Right || !self.selected {
+                    // Ctrl+Click toggles the item, keeping other items selected.
+                    let select = !ui.keyboard_modifiers().control || !self.selected;
+                    ui.send_message(AssetItemMessage::select(
+                        self.handle(),
+                        MessageDirection::ToWidget,
+                        select,
+                    ));
+                }
             }
         } else if let Some(AssetItemMessage::Select(select)) = message.data::<AssetItemMessage>() {
             if self.selected != *select && message.destination() == self.handle() {
@@ -166,6 +179,10 @@ impl AssetItemBuilder {
                         kind = AssetKind::Shader;
                         load_image(include_bytes!("../../resources/embed/shader.png"))
                     }
+                    "material" => {
+                        kind = AssetKind::Material;
+                        load_image(include_bytes!("../../resources/embed/shader.png"))
+                    }
                     _ => None,
                 });
 
@@ -14,12 +14,12 @@ use crate::{
     message::MessageSender,
     preview::PreviewPanel,
     utils::window_content,
-    AssetItem, AssetKind, Mode,
+    AssetItem, AssetKind, Message, Mode,
 };
 use fyrox::{
     asset::manager::ResourceManager,
     core::{
-        algebra::{UnitQuaternion, Vector3},
+        algebra::{Matrix4, UnitQuaternion, Vector3},
         color::Color,
         futures::executor::block_on,
         log::Log,
@@ -77,6 +77,7 @@ struct ContextMenu {
     delete: Handle<UiNode>,
     placement_target: Handle<UiNode>,
     dependencies: Handle<UiNode>,
+    instantiate: Handle<UiNode>,
 }
 
 fn execute_command(command: &mut Command) {
@@ -111,6 +112,7 @@ impl ContextMenu {
         let copy_path;
         let copy_file_name;
         let dependencies;
+        let instantiate;
         let menu = PopupBuilder::new(WidgetBuilder::new())
             .with_content(
                 StackPanelBuilder::new(
@@ -121,6 +123,12 @@ impl ContextMenu {
                                 .build(ctx);
                             open
                         })
+                        .with_child({
+                            instantiate = MenuItemBuilder::new(WidgetBuilder::new())
+                                .with_content(MenuItemContent::text("Instantiate In Scene"))
+                                .build(ctx);
+                            instantiate
+                        })
                         .with_child({
                             copy_path = MenuItemBuilder::new(WidgetBuilder::new())
                                 .with_content(MenuItemContent::text("Copy Full Path"))
@@ -166,10 +174,17 @@ impl ContextMenu {
             placement_target: Default::default(),
             copy_file_name,
             dependencies,
+            instantiate,
         }
     }
 
-    pub fn handle_ui_message(&mut self, message: &UiMessage, engine: &mut Engine) {
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        items: &[Handle<UiNode>],
+        engine: &mut Engine,
+        sender: &MessageSender,
+    ) {
         if let Some(PopupMessage::Placement(Placement::Cursor(target))) = message.data() {
             if message.destination() == *self.menu {
                 self.placement_target = *target;
@@ -196,6 +211,19 @@ impl ContextMenu {
                     if let Some(file_name) = item.path.clone().file_name() {
                         put_path_to_clipboard(engine, file_name)
                     }
+                } else if message.destination() == self.instantiate {
+                    // The command is applied to every selected model, if the menu was opened on
+                    // one of selected items.
+                    let paths = if item.is_selected() {
+                        selected_paths(items, &engine.user_interface, AssetKind::Model)
+                    } else if item.kind == AssetKind::Model {
+                        vec![item.path.clone()]
+                    } else {
+                        Vec::new()
+                    };
+                    if !paths.is_empty() {
+                        sender.send(Message::InstantiateModels(paths));
+                    }
                 }
             }
         }
@@ -217,11 +245,20 @@ pub struct AssetBrowser {
     dependency_viewer: DependencyViewer,
 }
 
+fn selected_paths(items: &[Handle<UiNode>], ui: &UserInterface, kind: AssetKind) -> Vec<PathBuf> {
+    items
+        .iter()
+        .filter_map(|item| ui.try_get_node(*item).and_then(|n| n.cast::<AssetItem>()))
+        .filter(|item| item.is_selected() && item.kind == kind)
+        .map(|item| item.path.clone())
+        .collect()
+}
+
 fn is_engine_resource(ext: &OsStr) -> bool {
     let ext = ext.to_string_lossy().to_lowercase();
     matches!(
         ext.as_str(),
-        "rgs" | "fbx" | "jpg" | "tga" | "png" | "bmp" | "ogg" | "wav" | "shader"
+        "rgs" | "fbx" | "jpg" | "tga" | "png" | "bmp" | "ogg" | "wav" | "shader" | "material"
     )
 }
 
@@ -447,20 +484,23 @@ impl AssetBrowser {
 
         self.inspector.handle_ui_message(message, engine);
         self.preview.handle_message(message, engine);
-        self.context_menu.handle_ui_message(message, engine);
+        self.context_menu
+            .handle_ui_message(message, &self.items, engine, &sender);
         self.dependency_viewer
             .handle_ui_message(message, &mut engine.user_interface);
 
         let ui = &mut engine.user_interface;
 
         if let Some(AssetItemMessage::Select(true)) = message.data::<AssetItemMessage>() {
-            // Deselect other items.
-            for &item in self.items.iter().filter(|i| **i != message.destination()) {
-                ui.send_message(AssetItemMessage::select(
-                    item,
-                    MessageDirection::ToWidget,
-                    false,
-                ))
+            // Deselect other items, unless the item is added to the selection with Ctrl+Click.
+            if !ui.keyboard_modifiers().control {
+                for &item in self.items.iter().filter(|i| **i != message.destination()) {
+                    ui.send_message(AssetItemMessage::select(
+                        item,
+                        MessageDirection::ToWidget,
+                        false,
+                    ))
+                }
             }
 
             let item = ui
@@ -536,6 +576,27 @@ impl AssetBrowser {
                 AssetKind::Shader => {
                     Log::warn("Implement me!");
                 }
+                AssetKind::Material => {
+                    let path = item.path.clone();
+                    match block_on(Material::from_file(&path, engine.resource_manager.clone())) {
+                        Ok(material) => {
+                            let graph = &mut engine.scenes[self.preview.scene()].graph;
+                            let sphere = MeshBuilder::new(BaseBuilder::new())
+                                .with_surfaces(vec![SurfaceBuilder::new(SurfaceSharedData::new(
+                                    SurfaceData::make_sphere(32, 32, 1.0, &Matrix4::identity()),
+                                ))
+                                .with_material(SharedMaterial::new(material))
+                                .build()])
+                                .build(graph);
+                            self.preview.set_model(sphere, engine);
+                        }
+                        Err(e) => Log::err(format!(
+                            "Unable to load material {}. Reason: {:?}",
+                            path.display(),
+                            e
+                        )),
+                    }
+                }
             }
         } else if let Some(FileBrowserMessage::Path(path)) = message.data::<FileBrowserMessage>() {
             if message.destination() == self.folder_browser
@@ -4,7 +4,7 @@ use crate::{
     gui::make_dropdown_list_option,
     scene::commands::{
         effect::{AddAudioBusCommand, LinkAudioBuses, RemoveAudioBusCommand},
-        sound_context::{SetDistanceModelCommand, SetRendererCommand},
+        sound_context::{SetDistanceModelCommand, SetOcclusionEnabledCommand, SetRendererCommand},
         CommandGroup,
     },
     send_sync_message,
@@ -17,6 +17,7 @@ use fyrox::{
     engine::Engine,
     gui::{
         button::{ButtonBuilder, ButtonMessage},
+        check_box::{CheckBoxBuilder, CheckBoxMessage},
         dropdown_list::{DropdownListBuilder, DropdownListMessage},
         file_browser::{FileSelectorFieldBuilder, FileSelectorFieldMessage},
         grid::{Column, Row},
@@ -62,6 +63,7 @@ pub struct AudioPanel {
     distance_model: Handle<UiNode>,
     renderer: Handle<UiNode>,
     hrir_sphere_path: Handle<UiNode>,
+    occlusion: Handle<UiNode>,
 }
 
 fn item_bus(item: Handle<UiNode>, ui: &UserInterface) -> Handle<AudioBus> {
@@ -101,6 +103,7 @@ impl AudioPanel {
         let distance_model;
         let renderer;
         let hrir_sphere_path;
+        let occlusion;
         let window = WindowBuilder::new(WidgetBuilder::new())
             .with_content(
                 GridBuilder::new(
@@ -177,6 +180,29 @@ impl AudioPanel {
                                         )
                                         .build(ctx);
                                         hrir_sphere_path
+                                    })
+                                    .with_child({
+                                        occlusion = CheckBoxBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_vertical_alignment(VerticalAlignment::Center)
+                                                .with_margin(Thickness::uniform(1.0))
+                                                .with_tooltip(make_simple_tooltip(
+                                                    ctx,
+                                                    "Muffle sounds that are blocked by colliders \
+                                                    between them and the listener.",
+                                                )),
+                                        )
+                                        .with_content(
+                                            TextBuilder::new(
+                                                WidgetBuilder::new()
+                                                    .with_margin(Thickness::uniform(1.0)),
+                                            )
+                                            .with_vertical_text_alignment(VerticalAlignment::Center)
+                                            .with_text("Occlusion")
+                                            .build(ctx),
+                                        )
+                                        .build(ctx);
+                                        occlusion
                                     }),
                             )
                             .with_orientation(Orientation::Horizontal)
@@ -239,6 +265,7 @@ impl AudioPanel {
             remove_bus,
             renderer,
             hrir_sphere_path,
+            occlusion,
         }
     }
 
@@ -326,6 +353,12 @@ impl AudioPanel {
                     sender.do_scene_command(SetDistanceModelCommand::new(distance_model));
                 }
             }
+        } else if let Some(CheckBoxMessage::Check(Some(value))) = message.data() {
+            if message.destination() == self.occlusion
+                && message.direction() == MessageDirection::FromWidget
+            {
+                sender.do_scene_command(SetOcclusionEnabledCommand::new(*value));
+            }
         } else if let Some(FileSelectorFieldMessage::Path(path)) = message.data() {
             if message.destination() == self.hrir_sphere_path
                 && message.direction() == MessageDirection::FromWidget
@@ -348,6 +381,10 @@ impl AudioPanel {
     }
 
     pub fn sync_to_model(&mut self, editor_scene: &EditorScene, engine: &mut Engine) {
+        let occlusion_enabled = engine.scenes[editor_scene.scene]
+            .graph
+            .sound_context
+            .is_occlusion_enabled();
         let context_state = engine.scenes[editor_scene.scene]
             .graph
             .sound_context
@@ -503,6 +540,15 @@ impl AudioPanel {
             ),
         );
 
+        send_sync_message(
+            ui,
+            CheckBoxMessage::checked(
+                self.occlusion,
+                MessageDirection::ToWidget,
+                Some(occlusion_enabled),
+            ),
+        );
+
         send_sync_message(
             ui,
             DropdownListMessage::selection(
@@ -0,0 +1,408 @@
+use crate::{scene::EditorScene, send_sync_message};
+use fyrox::{
+    core::{algebra::Vector3, log::Log, pool::Handle},
+    engine::Engine,
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        grid::{Column, GridBuilder, Row},
+        key::HotKey,
+        list_view::{ListViewBuilder, ListViewMessage},
+        message::{KeyCode, MessageDirection, UiMessage},
+        scroll_viewer::ScrollViewerBuilder,
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        text_box::TextBoxBuilder,
+        utils::make_simple_tooltip,
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowTitle},
+        BuildContext, Orientation, Thickness, UiNode, UserInterface, VerticalAlignment,
+    },
+};
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+};
+
+/// A named position and orientation of the editor camera.
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct CameraBookmark {
+    pub name: String,
+    pub position: Vector3<f32>,
+    pub yaw: f32,
+    pub pitch: f32,
+}
+
+/// A set of camera bookmarks of a scene. Bookmarks are editor-only data, so they're stored in a
+/// separate file (sidecar) next to the scene file and never end up in the scene itself.
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+pub struct CameraBookmarks {
+    pub bookmarks: Vec<CameraBookmark>,
+}
+
+impl CameraBookmarks {
+    /// Returns a path of the sidecar file with the bookmarks of the scene at the given path.
+    pub fn sidecar_path(scene_path: &Path) -> PathBuf {
+        scene_path.with_extension("bookmarks")
+    }
+
+    /// Loads bookmarks of the scene at the given path. Missing sidecar means that the scene has no
+    /// bookmarks.
+    pub fn load(scene_path: &Path) -> Self {
+        let path = Self::sidecar_path(scene_path);
+        if !path.exists() {
+            return Default::default();
+        }
+
+        match File::open(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|file| ron::de::from_reader(file).map_err(|e| e.to_string()))
+        {
+            Ok(bookmarks) => bookmarks,
+            Err(e) => {
+                Log::err(format!(
+                    "Unable to load camera bookmarks from {}. Reason: {}",
+                    path.display(),
+                    e
+                ));
+                Default::default()
+            }
+        }
+    }
+
+    /// Saves bookmarks next to the scene at the given path. Sidecar file is removed if there are no
+    /// bookmarks.
+    pub fn save(&self, scene_path: &Path) -> Result<(), String> {
+        let path = Self::sidecar_path(scene_path);
+        if self.is_empty() {
+            if path.exists() {
+                std::fs::remove_file(&path).map_err(|e| e.to_string())?;
+            }
+            Ok(())
+        } else {
+            let file = File::create(&path).map_err(|e| e.to_string())?;
+            ron::ser::to_writer_pretty(file, self, PrettyConfig::default())
+                .map_err(|e| e.to_string())
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Option<&CameraBookmark> {
+        self.bookmarks.get(index)
+    }
+
+    pub fn add(&mut self, bookmark: CameraBookmark) {
+        self.bookmarks.push(bookmark);
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<CameraBookmark> {
+        if index < self.bookmarks.len() {
+            Some(self.bookmarks.remove(index))
+        } else {
+            None
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bookmarks.is_empty()
+    }
+}
+
+/// Returns an index of a bookmark to jump to for the given hot key. First nine bookmarks could be
+/// reached using Ctrl+1..Ctrl+9.
+pub fn bookmark_index_from_hotkey(hot_key: &HotKey) -> Option<usize> {
+    const KEYS: [KeyCode; 9] = [
+        KeyCode::Key1,
+        KeyCode::Key2,
+        KeyCode::Key3,
+        KeyCode::Key4,
+        KeyCode::Key5,
+        KeyCode::Key6,
+        KeyCode::Key7,
+        KeyCode::Key8,
+        KeyCode::Key9,
+    ];
+
+    KEYS.iter()
+        .position(|key| *hot_key == HotKey::ctrl_key(*key))
+}
+
+pub struct CameraBookmarksPanel {
+    pub window: Handle<UiNode>,
+    list: Handle<UiNode>,
+    name: Handle<UiNode>,
+    add: Handle<UiNode>,
+    remove: Handle<UiNode>,
+    new_name: String,
+    selected: Option<usize>,
+    names: Vec<String>,
+}
+
+impl CameraBookmarksPanel {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let list;
+        let name;
+        let add;
+        let remove;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(250.0).with_height(300.0))
+            .with_title(WindowTitle::text("Camera Bookmarks"))
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(
+                            GridBuilder::new(
+                                WidgetBuilder::new()
+                                    .with_child({
+                                        name = TextBoxBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_margin(Thickness::uniform(1.0))
+                                                .with_tooltip(make_simple_tooltip(
+                                                    ctx,
+                                                    "Name of a new bookmark.",
+                                                )),
+                                        )
+                                        .with_vertical_text_alignment(VerticalAlignment::Center)
+                                        .with_text("Bookmark")
+                                        .build(ctx);
+                                        name
+                                    })
+                                    .with_child({
+                                        add = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .on_column(1)
+                                                .with_width(60.0)
+                                                .with_margin(Thickness::uniform(1.0))
+                                                .with_tooltip(make_simple_tooltip(
+                                                    ctx,
+                                                    "Bookmark current position and orientation \
+                                                    of the camera.",
+                                                )),
+                                        )
+                                        .with_text("Add")
+                                        .build(ctx);
+                                        add
+                                    }),
+                            )
+                            .add_row(Row::stretch())
+                            .add_column(Column::stretch())
+                            .add_column(Column::auto())
+                            .build(ctx),
+                        )
+                        .with_child(
+                            ScrollViewerBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .with_margin(Thickness::uniform(1.0))
+                                    .with_tooltip(make_simple_tooltip(
+                                        ctx,
+                                        "Click on a bookmark to move the camera to it. \
+                                        First nine bookmarks could also be reached \
+                                        using Ctrl+1..Ctrl+9.",
+                                    )),
+                            )
+                            .with_content({
+                                list = ListViewBuilder::new(WidgetBuilder::new()).build(ctx);
+                                list
+                            })
+                            .build(ctx),
+                        )
+                        .with_child(
+                            StackPanelBuilder::new(WidgetBuilder::new().on_row(2).with_child({
+                                remove = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_width(60.0)
+                                        .with_enabled(false)
+                                        .with_margin(Thickness::uniform(1.0)),
+                                )
+                                .with_text("Remove")
+                                .build(ctx);
+                                remove
+                            }))
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        ),
+                )
+                .add_column(Column::stretch())
+                .add_row(Row::strict(25.0))
+                .add_row(Row::stretch())
+                .add_row(Row::strict(25.0))
+                .build(ctx),
+            )
+            .open(false)
+            .build(ctx);
+
+        Self {
+            window,
+            list,
+            name,
+            add,
+            remove,
+            new_name: "Bookmark".to_string(),
+            selected: None,
+            names: Default::default(),
+        }
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &mut EditorScene,
+        engine: &mut Engine,
+    ) {
+        let graph = &mut engine.scenes[editor_scene.scene].graph;
+        let camera_controller = &mut editor_scene.camera_controller;
+
+        if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.add {
+                let bookmark = camera_controller.make_bookmark(graph, self.new_name.clone());
+                camera_controller.bookmarks.add(bookmark);
+                editor_scene.has_unsaved_changes = true;
+            } else if message.destination() == self.remove {
+                if let Some(index) = self.selected.take() {
+                    if camera_controller.bookmarks.remove(index).is_some() {
+                        editor_scene.has_unsaved_changes = true;
+                    }
+                }
+            }
+        } else if let Some(TextMessage::Text(text)) = message.data() {
+            if message.destination() == self.name
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.new_name = text.clone();
+            }
+        } else if let Some(ListViewMessage::SelectionChanged(selection)) = message.data() {
+            if message.destination() == self.list
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.selected = *selection;
+
+                if let Some(bookmark) = selection.and_then(|i| camera_controller.bookmarks.get(i)) {
+                    let bookmark = bookmark.clone();
+                    camera_controller.jump_to_bookmark(graph, &bookmark);
+                }
+
+                engine.user_interface.send_message(WidgetMessage::enabled(
+                    self.remove,
+                    MessageDirection::ToWidget,
+                    selection.is_some(),
+                ));
+            }
+        }
+    }
+
+    pub fn sync_to_model(&mut self, editor_scene: &EditorScene, ui: &mut UserInterface) {
+        let names = editor_scene
+            .camera_controller
+            .bookmarks
+            .bookmarks
+            .iter()
+            .map(|b| b.name.clone())
+            .collect::<Vec<_>>();
+
+        if names == self.names {
+            return;
+        }
+
+        let items = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let text = if i < 9 {
+                    format!("{} (Ctrl+{})", name, i + 1)
+                } else {
+                    name.clone()
+                };
+
+                TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(2.0)))
+                    .with_text(text)
+                    .build(&mut ui.build_ctx())
+            })
+            .collect();
+
+        self.selected = None;
+        send_sync_message(
+            ui,
+            ListViewMessage::selection(self.list, MessageDirection::ToWidget, None),
+        );
+        send_sync_message(
+            ui,
+            ListViewMessage::items(self.list, MessageDirection::ToWidget, items),
+        );
+        send_sync_message(
+            ui,
+            WidgetMessage::enabled(self.remove, MessageDirection::ToWidget, false),
+        );
+
+        self.names = names;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::camera::{
+        bookmark::{bookmark_index_from_hotkey, CameraBookmarks},
+        CameraController,
+    };
+    use fyrox::{
+        core::algebra::{UnitQuaternion, Vector3},
+        gui::{key::HotKey, message::KeyCode},
+        scene::Scene,
+    };
+
+    #[test]
+    fn test_jump_to_bookmark_restores_camera_transform() {
+        let mut scene = Scene::new();
+        let root = scene.graph.get_root();
+        let mut controller = CameraController::new(&mut scene.graph, root, None);
+
+        let position = Vector3::new(1.0, 2.0, 3.0);
+        scene.graph[controller.pivot]
+            .local_transform_mut()
+            .set_position(position);
+        controller.yaw = 0.5;
+        controller.pitch = -0.25;
+        let bookmark = controller.make_bookmark(&scene.graph, "Spot".to_string());
+        controller.bookmarks.add(bookmark);
+        assert_eq!(controller.bookmarks.bookmarks.len(), 1);
+
+        // Move the camera somewhere else.
+        scene.graph[controller.pivot]
+            .local_transform_mut()
+            .set_position(Vector3::new(-10.0, 0.0, 5.0));
+        controller.yaw = 2.0;
+        controller.pitch = 1.0;
+
+        let bookmark = controller.bookmarks.get(0).unwrap().clone();
+        controller.jump_to_bookmark(&mut scene.graph, &bookmark);
+        scene.graph.update_hierarchical_data();
+
+        assert_eq!(controller.yaw, 0.5);
+        assert_eq!(controller.pitch, -0.25);
+        assert_eq!(controller.position(&scene.graph), position);
+        assert_eq!(
+            **scene.graph[controller.pivot].local_transform().rotation(),
+            UnitQuaternion::from_axis_angle(&Vector3::y_axis(), 0.5)
+        );
+        assert_eq!(
+            **scene.graph[controller.camera].local_transform().rotation(),
+            UnitQuaternion::from_axis_angle(&Vector3::x_axis(), -0.25)
+        );
+    }
+
+    #[test]
+    fn test_bookmark_hotkeys() {
+        assert_eq!(
+            bookmark_index_from_hotkey(&HotKey::ctrl_key(KeyCode::Key1)),
+            Some(0)
+        );
+        assert_eq!(
+            bookmark_index_from_hotkey(&HotKey::ctrl_key(KeyCode::Key9)),
+            Some(8)
+        );
+        assert_eq!(
+            bookmark_index_from_hotkey(&HotKey::from_key_code(KeyCode::Key1)),
+            None
+        );
+        assert!(CameraBookmarks::default().get(0).is_none());
+    }
+}
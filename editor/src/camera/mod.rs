@@ -1,5 +1,10 @@
 use crate::settings::keys::KeyBindings;
-use crate::{settings::camera::CameraSettings, utils::built_in_skybox, SceneCameraSettings};
+use crate::{
+    camera::bookmark::{CameraBookmark, CameraBookmarks},
+    settings::camera::CameraSettings,
+    utils::built_in_skybox,
+    SceneCameraSettings,
+};
 use fyrox::{
     core::{
         algebra::{Matrix4, Point3, UnitQuaternion, Vector2, Vector3},
@@ -10,9 +15,12 @@ use fyrox::{
         pool::Handle,
     },
     gui::message::{KeyCode, MouseButton},
+    renderer::DebugView,
     scene::{
         base::BaseBuilder,
         camera::{Camera, CameraBuilder, Exposure, FitParameters, Projection},
+        collider::{Collider, ColliderShape},
+        dim2::collider::{Collider as Collider2D, ColliderShape as Collider2DShape},
         graph::Graph,
         mesh::{
             buffer::{VertexAttributeUsage, VertexReadTrait},
@@ -27,10 +35,12 @@ use fyrox::{
     },
 };
 use std::{
+    cmp::Ordering,
     collections::hash_map::DefaultHasher,
     hash::{Hash, Hasher},
 };
 
+pub mod bookmark;
 pub mod panel;
 
 pub const DEFAULT_Z_OFFSET: f32 = -3.0;
@@ -40,6 +50,7 @@ pub struct CameraController {
     pub camera: Handle<Node>,
     pub yaw: f32,
     pub pitch: f32,
+    pub bookmarks: CameraBookmarks,
     rotate: bool,
     drag_side: f32,
     drag_up: f32,
@@ -50,7 +61,8 @@ pub struct CameraController {
     move_backward: bool,
     move_up: bool,
     move_down: bool,
-    speed_factor: f32,
+    speed_up: bool,
+    slow_down: bool,
     stack: Vec<Handle<Node>>,
     editor_context: PickContext,
     scene_context: PickContext,
@@ -61,6 +73,8 @@ pub struct CameraPickResult {
     pub position: Vector3<f32>,
     pub node: Handle<Node>,
     pub toi: f32,
+    /// Index of a surface of a mesh, that was hit by the ray. It is `None` for hull-less objects.
+    pub surface: Option<usize>,
 }
 
 #[derive(Default)]
@@ -126,6 +140,7 @@ impl CameraController {
             camera,
             yaw: settings.yaw,
             pitch: settings.pitch,
+            bookmarks: Default::default(),
             rotate: false,
             drag_side: 0.0,
             drag_up: 0.0,
@@ -136,7 +151,8 @@ impl CameraController {
             move_backward: false,
             move_up: false,
             move_down: false,
-            speed_factor: 1.0,
+            speed_up: false,
+            slow_down: false,
             stack: Default::default(),
             editor_context: Default::default(),
             scene_context: Default::default(),
@@ -198,10 +214,17 @@ impl CameraController {
             .set_projection(projection);
     }
 
+    pub fn set_debug_view(&self, graph: &mut Graph, debug_view: DebugView) {
+        graph[self.camera]
+            .as_camera_mut()
+            .set_debug_view(debug_view);
+    }
+
     pub fn on_mouse_move(&mut self, delta: Vector2<f32>, settings: &CameraSettings) {
         if self.rotate {
-            self.yaw -= delta.x * 0.01;
-            self.pitch += delta.y * 0.01;
+            let sensitivity = 0.01 * settings.look_sensitivity;
+            self.yaw -= delta.x * sensitivity;
+            self.pitch += delta.y * sensitivity;
             if self.pitch > 90.0f32.to_radians() {
                 self.pitch = 90.0f32.to_radians();
             }
@@ -277,8 +300,11 @@ impl CameraController {
         } else if key_bindings.move_down == key {
             self.move_down = false;
             true
-        } else if key_bindings.slow_down == key || key_bindings.speed_up == key {
-            self.speed_factor = 1.0;
+        } else if key_bindings.speed_up == key {
+            self.speed_up = false;
+            true
+        } else if key_bindings.slow_down == key {
+            self.slow_down = false;
             true
         } else {
             false
@@ -310,20 +336,62 @@ impl CameraController {
             self.move_down = true;
             true
         } else if key_bindings.speed_up == key {
-            self.speed_factor = 2.0;
+            self.speed_up = true;
             true
         } else if key_bindings.slow_down == key {
-            self.speed_factor = 0.25;
+            self.slow_down = true;
             true
         } else {
             false
         }
     }
 
+    fn move_speed(&self, settings: &CameraSettings) -> f32 {
+        let factor = if self.speed_up {
+            settings.boost_multiplier
+        } else if self.slow_down {
+            0.25
+        } else {
+            1.0
+        };
+        factor * settings.speed
+    }
+
     pub fn position(&self, graph: &Graph) -> Vector3<f32> {
         graph[self.pivot].global_position()
     }
 
+    /// Creates a bookmark with current position and orientation of the camera.
+    pub fn make_bookmark(&self, graph: &Graph, name: String) -> CameraBookmark {
+        CameraBookmark {
+            name,
+            position: **graph[self.pivot].local_transform().position(),
+            yaw: self.yaw,
+            pitch: self.pitch,
+        }
+    }
+
+    /// Moves the camera to the bookmarked position and restores its orientation.
+    pub fn jump_to_bookmark(&mut self, graph: &mut Graph, bookmark: &CameraBookmark) {
+        self.yaw = bookmark.yaw;
+        self.pitch = bookmark.pitch;
+
+        graph[self.camera]
+            .local_transform_mut()
+            .set_rotation(UnitQuaternion::from_axis_angle(
+                &Vector3::x_axis(),
+                self.pitch,
+            ));
+
+        graph[self.pivot]
+            .local_transform_mut()
+            .set_rotation(UnitQuaternion::from_axis_angle(
+                &Vector3::y_axis(),
+                self.yaw,
+            ))
+            .set_position(bookmark.position);
+    }
+
     pub fn update(&mut self, graph: &mut Graph, settings: &CameraSettings, dt: f32) {
         let camera = graph[self.camera].as_camera_mut();
 
@@ -358,7 +426,7 @@ impl CameraController {
                 }
 
                 if let Some(v) = move_vec.try_normalize(std::f32::EPSILON) {
-                    move_vec = v.scale(self.speed_factor * settings.speed * dt);
+                    move_vec = v.scale(self.move_speed(settings) * dt);
                 }
 
                 move_vec += side * self.drag_side;
@@ -401,7 +469,7 @@ impl CameraController {
                 move_vec.y += self.drag_up;
 
                 if let Some(v) = move_vec.try_normalize(f32::EPSILON) {
-                    move_vec = v.scale(self.speed_factor * settings.speed * dt);
+                    move_vec = v.scale(self.move_speed(settings) * dt);
                 }
 
                 camera
@@ -476,17 +544,18 @@ impl CameraController {
                     let object_space_ray =
                         ray.transform(node.global_transform().try_inverse().unwrap_or_default());
 
-                    let aabb = node.local_bounding_box();
+                    let aabb = node_bounds(node);
                     // Do coarse, but fast, intersection test with bounding box first.
                     if let Some(points) = object_space_ray.aabb_intersection_points(&aabb) {
                         if has_hull(node) {
-                            if let Some((closest_distance, position)) =
+                            if let Some((closest_distance, position, surface)) =
                                 precise_ray_test(node, &ray, ignore_back_faces)
                             {
                                 context.pick_list.push(CameraPickResult {
                                     position,
                                     node: handle,
                                     toi: closest_distance,
+                                    surface: Some(surface),
                                 });
                             }
                         } else if !only_meshes {
@@ -502,16 +571,20 @@ impl CameraController {
                                 ),
                                 node: handle,
                                 toi: closest_distance,
+                                surface: None,
                             });
                         }
                     }
                 }
             }
 
-            // Make sure closest will be selected first.
-            context
-                .pick_list
-                .sort_by(|a, b| a.toi.partial_cmp(&b.toi).unwrap());
+            // Make sure closest will be selected first, but colliders always have priority over
+            // everything else.
+            context.pick_list.sort_by(|a, b| {
+                pick_priority(&graph[a.node])
+                    .cmp(&pick_priority(&graph[b.node]))
+                    .then_with(|| a.toi.partial_cmp(&b.toi).unwrap_or(Ordering::Equal))
+            });
 
             if use_picking_loop {
                 let mut hasher = DefaultHasher::new();
@@ -585,6 +658,75 @@ fn read_triangle(
     Some([a, b, c])
 }
 
+fn is_collider(node: &Node) -> bool {
+    node.cast::<Collider>().is_some() || node.cast::<Collider2D>().is_some()
+}
+
+/// Lower values are picked first. Gizmos are not listed here, because they're picked separately
+/// by interaction modes before any scene object.
+fn pick_priority(node: &Node) -> u8 {
+    if is_collider(node) {
+        0
+    } else {
+        1
+    }
+}
+
+fn collider_bounds(node: &Node) -> Option<AxisAlignedBoundingBox> {
+    // 2D colliders are flat, give them some thickness so they could be picked from any side.
+    const THICKNESS: f32 = 0.01;
+
+    let half_extents = if let Some(collider) = node.cast::<Collider>() {
+        match collider.shape() {
+            ColliderShape::Ball(ball) => Vector3::repeat(ball.radius),
+            ColliderShape::Cuboid(cuboid) => cuboid.half_extents,
+            ColliderShape::Cylinder(cylinder) => {
+                Vector3::new(cylinder.radius, cylinder.half_height, cylinder.radius)
+            }
+            ColliderShape::Cone(cone) => Vector3::new(cone.radius, cone.half_height, cone.radius),
+            ColliderShape::Capsule(capsule) => {
+                let mut aabb = AxisAlignedBoundingBox::from_points(&[capsule.begin, capsule.end]);
+                aabb.inflate(Vector3::repeat(capsule.radius * 2.0));
+                return Some(aabb);
+            }
+            _ => return None,
+        }
+    } else if let Some(collider) = node.cast::<Collider2D>() {
+        match collider.shape() {
+            Collider2DShape::Ball(ball) => Vector3::new(ball.radius, ball.radius, THICKNESS),
+            Collider2DShape::Cuboid(cuboid) => {
+                Vector3::new(cuboid.half_extents.x, cuboid.half_extents.y, THICKNESS)
+            }
+            Collider2DShape::Capsule(capsule) => {
+                let mut aabb = AxisAlignedBoundingBox::from_points(&[
+                    Vector3::new(capsule.begin.x, capsule.begin.y, 0.0),
+                    Vector3::new(capsule.end.x, capsule.end.y, 0.0),
+                ]);
+                aabb.inflate(Vector3::new(
+                    capsule.radius * 2.0,
+                    capsule.radius * 2.0,
+                    THICKNESS * 2.0,
+                ));
+                return Some(aabb);
+            }
+            _ => return None,
+        }
+    } else {
+        return None;
+    };
+
+    Some(AxisAlignedBoundingBox {
+        min: -half_extents,
+        max: half_extents,
+    })
+}
+
+/// Returns local bounds of the node, that are used for picking and selection highlighting. Colliders
+/// have no geometry, so their bounds are calculated from their shapes.
+pub fn node_bounds(node: &Node) -> AxisAlignedBoundingBox {
+    collider_bounds(node).unwrap_or_else(|| node.local_bounding_box())
+}
+
 fn has_hull(node: &Node) -> bool {
     node.query_component_ref::<Mesh>().is_some()
 }
@@ -593,14 +735,15 @@ fn precise_ray_test(
     node: &Node,
     ray: &Ray,
     ignore_back_faces: bool,
-) -> Option<(f32, Vector3<f32>)> {
+) -> Option<(f32, Vector3<f32>, usize)> {
     let mut closest_distance = f32::MAX;
     let mut closest_point = None;
+    let mut closest_surface = 0;
 
     if let Some(mesh) = node.query_component_ref::<Mesh>() {
         let transform = mesh.global_transform();
 
-        for surface in mesh.surfaces().iter() {
+        for (surface_index, surface) in mesh.surfaces().iter().enumerate() {
             let data = surface.data();
             let data = data.lock();
 
@@ -624,11 +767,68 @@ fn precise_ray_test(
                     if distance < closest_distance {
                         closest_distance = distance;
                         closest_point = Some(pt);
+                        closest_surface = surface_index;
                     }
                 }
             }
         }
     }
 
-    closest_point.map(|pt| (closest_distance, pt))
+    closest_point.map(|pt| (closest_distance, pt, closest_surface))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        camera::CameraController,
+        settings::{camera::CameraSettings, keys::KeyBindings},
+    };
+    use fyrox::{
+        core::algebra::Vector3,
+        gui::message::{KeyCode, MouseButton},
+        scene::graph::Graph,
+    };
+
+    fn translation_per_frame(settings: &CameraSettings, boost: bool) -> f32 {
+        let mut graph = Graph::new();
+        let root = graph.get_root();
+        let mut controller = CameraController::new(&mut graph, root, None);
+        graph.update_hierarchical_data();
+
+        let key_bindings = KeyBindings::default();
+        // Fly mode is active only while the right mouse button is held.
+        controller.on_mouse_button_down(MouseButton::Right);
+        assert!(controller.on_key_down(&key_bindings, KeyCode::W));
+        if boost {
+            assert!(controller.on_key_down(&key_bindings, KeyCode::LControl));
+        }
+
+        let before: Vector3<f32> = **graph[controller.pivot].local_transform().position();
+        controller.update(&mut graph, settings, 0.5);
+        let after: Vector3<f32> = **graph[controller.pivot].local_transform().position();
+
+        (after - before).norm()
+    }
+
+    #[test]
+    fn test_move_speed_setting_changes_translation() {
+        let slow = CameraSettings {
+            speed: 2.0,
+            ..Default::default()
+        };
+        let fast = CameraSettings {
+            speed: 8.0,
+            ..Default::default()
+        };
+
+        assert!((translation_per_frame(&slow, false) - 1.0).abs() < 0.001);
+        assert!((translation_per_frame(&fast, false) - 4.0).abs() < 0.001);
+
+        let boosted = CameraSettings {
+            speed: 2.0,
+            boost_multiplier: 3.0,
+            ..Default::default()
+        };
+        assert!((translation_per_frame(&boosted, true) - 3.0).abs() < 0.001);
+    }
 }
@@ -4,10 +4,11 @@ use crate::{
 };
 use fyrox::gui::Thickness;
 use fyrox::{
-    core::pool::Handle,
+    core::{algebra::Vector2, pool::Handle},
     engine::Engine,
     gui::{
         check_box::{CheckBoxBuilder, CheckBoxMessage},
+        image::ImageBuilder,
         message::{MessageDirection, UiMessage},
         stack_panel::StackPanelBuilder,
         text::TextBuilder,
@@ -15,18 +16,29 @@ use fyrox::{
         window::{WindowBuilder, WindowMessage, WindowTitle},
         BuildContext, Orientation, UiNode, VerticalAlignment,
     },
-    scene::{camera::Camera, node::Node},
+    resource::texture::{TextureResource, TextureResourceExtension},
+    scene::{camera::Camera, graph::Graph, node::Node},
+    utils::into_gui_texture,
 };
 
+const PREVIEW_WIDTH: u32 = 256;
+const PREVIEW_HEIGHT: u32 = 144;
+
 pub struct CameraPreviewControlPanel {
     pub window: Handle<UiNode>,
     preview: Handle<UiNode>,
+    preview_image: Handle<UiNode>,
     cameras_state: Vec<(Handle<Node>, Node)>,
+    // A texture the selected camera renders to, it is shown live in the panel.
+    render_target: TextureResource,
+    previewed_camera: Handle<Node>,
 }
 
 impl CameraPreviewControlPanel {
     pub fn new(ctx: &mut BuildContext) -> Self {
         let preview;
+        let preview_image;
+        let render_target = TextureResource::new_render_target(PREVIEW_WIDTH, PREVIEW_HEIGHT);
         let window = WindowBuilder::new(WidgetBuilder::new())
             .with_title(WindowTitle::text("Camera Preview"))
             .with_content(
@@ -45,6 +57,18 @@ impl CameraPreviewControlPanel {
                                 )
                                 .build(ctx);
                             preview
+                        })
+                        .with_child({
+                            preview_image = ImageBuilder::new(
+                                WidgetBuilder::new()
+                                    .with_margin(Thickness::uniform(1.0))
+                                    .with_width(PREVIEW_WIDTH as f32)
+                                    .with_height(PREVIEW_HEIGHT as f32),
+                            )
+                            .with_flip(true)
+                            .with_texture(into_gui_texture(render_target.clone()))
+                            .build(ctx);
+                            preview_image
                         }),
                 )
                 .with_orientation(Orientation::Vertical)
@@ -57,6 +81,51 @@ impl CameraPreviewControlPanel {
             window,
             cameras_state: Default::default(),
             preview,
+            preview_image,
+            render_target,
+            previewed_camera: Handle::NONE,
+        }
+    }
+
+    /// Returns a handle of the camera, that renders its view to the preview image.
+    pub fn previewed_camera(&self) -> Handle<Node> {
+        self.previewed_camera
+    }
+
+    /// Makes the first selected camera render to the preview image, the camera that was previewed
+    /// before stops rendering to it.
+    fn sync_previewed_camera(&mut self, selection: &Selection, graph: &mut Graph) {
+        if let Some(camera) = graph
+            .try_get_mut(self.previewed_camera)
+            .and_then(|node| node.cast_mut::<Camera>())
+        {
+            camera.set_render_target(None);
+        }
+        self.previewed_camera = Handle::NONE;
+
+        if let Selection::Graph(ref selection) = selection {
+            if let Some(&camera_handle) = selection
+                .nodes
+                .iter()
+                .find(|n| graph.try_get_of_type::<Camera>(**n).is_some())
+            {
+                graph[camera_handle]
+                    .as_camera_mut()
+                    .set_render_target(Some(self.render_target.clone()));
+                self.previewed_camera = camera_handle;
+            }
+        }
+    }
+
+    /// Updates the matrices of the previewed camera. Scene nodes are not updated while editing, so the
+    /// matrices must be calculated manually to keep the preview live.
+    pub fn update(&self, editor_scene: &EditorScene, engine: &mut Engine) {
+        let graph = &mut engine.scenes[editor_scene.scene].graph;
+        if let Some(camera) = graph
+            .try_get_mut(self.previewed_camera)
+            .and_then(|node| node.cast_mut::<Camera>())
+        {
+            camera.calculate_matrices(Vector2::new(PREVIEW_WIDTH as f32, PREVIEW_HEIGHT as f32));
         }
     }
 
@@ -73,7 +142,8 @@ impl CameraPreviewControlPanel {
         }
 
         if let Message::SelectionChanged { .. } = message {
-            let scene = &engine.scenes[editor_scene.scene];
+            let scene = &mut engine.scenes[editor_scene.scene];
+            self.sync_previewed_camera(&editor_scene.selection, &mut scene.graph);
             if let Selection::Graph(ref selection) = editor_scene.selection {
                 let any_camera = selection
                     .nodes
@@ -98,7 +168,7 @@ impl CameraPreviewControlPanel {
     fn enter_preview_mode(&mut self, editor_scene: &mut EditorScene, engine: &mut Engine) {
         assert!(self.cameras_state.is_empty());
 
-        let scene = &engine.scenes[editor_scene.scene];
+        let scene = &mut engine.scenes[editor_scene.scene];
         let node_overrides = editor_scene.graph_switches.node_overrides.as_mut().unwrap();
 
         if let Selection::Graph(ref new_graph_selection) = editor_scene.selection {
@@ -108,6 +178,11 @@ impl CameraPreviewControlPanel {
                     self.cameras_state
                         .push((node_handle, scene.graph[node_handle].clone_box()));
 
+                    // The camera is shown in the scene viewer now, the preview image is not needed.
+                    scene.graph[node_handle]
+                        .as_camera_mut()
+                        .set_render_target(None);
+
                     assert!(node_overrides.insert(node_handle));
 
                     editor_scene.preview_camera = node_handle;
@@ -153,3 +228,105 @@ impl CameraPreviewControlPanel {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        camera::panel::{CameraPreviewControlPanel, PREVIEW_HEIGHT, PREVIEW_WIDTH},
+        disable_scene_cameras,
+        scene::{EditorScene, Selection},
+        world::graph::selection::GraphSelection,
+        Message,
+    };
+    use fyrox::{
+        asset::manager::ResourceManager,
+        core::pool::Handle,
+        engine::{Engine, EngineInitParams, SerializationContext},
+        gui::image::Image,
+        scene::{base::BaseBuilder, camera::CameraBuilder, node::Node, pivot::PivotBuilder, Scene},
+    };
+    use std::sync::Arc;
+
+    fn select(
+        panel: &mut CameraPreviewControlPanel,
+        editor_scene: &mut EditorScene,
+        engine: &mut Engine,
+        node: Handle<Node>,
+    ) {
+        let old_selection = std::mem::replace(
+            &mut editor_scene.selection,
+            Selection::Graph(GraphSelection::single_or_empty(node)),
+        );
+        panel.handle_message(
+            &Message::SelectionChanged { old_selection },
+            editor_scene,
+            engine,
+        );
+    }
+
+    #[test]
+    fn test_selected_camera_renders_to_preview() {
+        let mut engine = Engine::new(EngineInitParams {
+            graphics_context_params: Default::default(),
+            serialization_context: Arc::new(SerializationContext::new()),
+            resource_manager: ResourceManager::new(),
+        })
+        .unwrap();
+        let mut panel = CameraPreviewControlPanel::new(&mut engine.user_interface.build_ctx());
+
+        let mut scene = Scene::new();
+        let camera = CameraBuilder::new(BaseBuilder::new()).build(&mut scene.graph);
+        let other_camera = CameraBuilder::new(BaseBuilder::new()).build(&mut scene.graph);
+        let pivot = PivotBuilder::new(BaseBuilder::new()).build(&mut scene.graph);
+        let mut editor_scene = EditorScene::new_test(&mut scene);
+        editor_scene.scene = engine.scenes.add(scene);
+
+        select(&mut panel, &mut editor_scene, &mut engine, camera);
+        assert_eq!(panel.previewed_camera(), camera);
+
+        // The same sequence as in the editor right before rendering.
+        panel.update(&editor_scene, &mut engine);
+        let graph = &mut engine.scenes[editor_scene.scene].graph;
+        let camera_state = disable_scene_cameras(&editor_scene, panel.previewed_camera(), graph);
+
+        // The previewed camera is the only scene camera that is rendered, and it renders to the preview
+        // texture, not to the screen.
+        let previewed = graph[camera].as_camera();
+        assert!(previewed.is_enabled());
+        assert_eq!(
+            previewed.render_target().map(|rt| rt.key()),
+            Some(panel.render_target.key())
+        );
+        assert!(!graph[other_camera].as_camera().is_enabled());
+        assert!(graph[editor_scene.camera_controller.camera]
+            .as_camera()
+            .is_enabled());
+        assert_eq!(camera_state.len(), 2);
+        // Its projection matches the preview size, so the image is not stretched.
+        let projection = previewed.projection_matrix();
+        let aspect = projection[(1, 1)] / projection[(0, 0)];
+        assert!((aspect - PREVIEW_WIDTH as f32 / PREVIEW_HEIGHT as f32).abs() < 1.0e-4);
+        // And the texture is shown in the panel.
+        assert!(engine
+            .user_interface
+            .node(panel.preview_image)
+            .cast::<Image>()
+            .unwrap()
+            .texture
+            .is_some());
+
+        // Non-camera selection stops the preview.
+        for (handle, enabled) in camera_state {
+            engine.scenes[editor_scene.scene].graph[handle]
+                .as_camera_mut()
+                .set_enabled(enabled);
+        }
+        select(&mut panel, &mut editor_scene, &mut engine, pivot);
+        assert!(panel.previewed_camera().is_none());
+        panel.update(&editor_scene, &mut engine);
+        let graph = &mut engine.scenes[editor_scene.scene].graph;
+        disable_scene_cameras(&editor_scene, panel.previewed_camera(), graph);
+        assert!(graph[camera].as_camera().render_target().is_none());
+        assert!(!graph[camera].as_camera().is_enabled());
+    }
+}
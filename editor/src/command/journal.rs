@@ -0,0 +1,469 @@
+//! Command journal allows to recover unsaved changes after a crash of the editor. Every executed
+//! command is written to a recovery file, and on the next launch the journal could be replayed on
+//! top of the last saved version of the scene.
+//!
+//! Only a few types of commands can be described in a serializable form (see [`JournalEntry`]),
+//! every other command makes the journal to store a snapshot of the entire scene (a checkpoint)
+//! and to continue from it.
+
+use crate::scene::commands::{
+    graph::{MoveNodeCommand, RotateNodeCommand, ScaleNodeCommand, SetNodeNameCommand},
+    SceneCommand, SceneContext, SetPropertyCommand,
+};
+use fyrox::{
+    core::{
+        algebra::{UnitQuaternion, Vector2, Vector3},
+        color::Color,
+        futures::executor::block_on,
+        log::Log,
+        pool::Handle,
+        reflect::prelude::*,
+        visitor::prelude::*,
+    },
+    scene::{graph::Graph, node::Node, Scene},
+};
+use std::{any::Any, path::PathBuf};
+
+macro_rules! define_journal_values {
+    ($($variant:ident($ty:ty)),*) => {
+        /// Serializable value of a property. Only the most commonly edited types are supported.
+        #[derive(Debug, Clone, PartialEq, Visit)]
+        pub enum JournalValue {
+            $($variant($ty)),*
+        }
+
+        impl JournalValue {
+            fn from_reflect(value: &dyn Reflect) -> Option<Self> {
+                let mut result = None;
+                value.as_any(&mut |any| {
+                    $(
+                        if let Some(value) = any.downcast_ref::<$ty>() {
+                            result = Some(Self::$variant(value.clone()));
+                        }
+                    )*
+                });
+                result
+            }
+
+            fn into_reflect(self) -> Box<dyn Reflect> {
+                match self {
+                    $(Self::$variant(value) => Box::new(value)),*
+                }
+            }
+        }
+    };
+}
+
+define_journal_values!(
+    Bool(bool),
+    F32(f32),
+    F64(f64),
+    U8(u8),
+    I32(i32),
+    U32(u32),
+    String(String),
+    Vector2(Vector2<f32>),
+    Vector3(Vector3<f32>),
+    UnitQuaternion(UnitQuaternion<f32>),
+    Color(Color)
+);
+
+impl Default for JournalValue {
+    fn default() -> Self {
+        Self::Bool(false)
+    }
+}
+
+/// A path to a node in form of child indices starting from the scene content root. Handles cannot
+/// be used here, because they are not preserved when a scene is saved.
+pub type NodePath = Vec<u32>;
+
+fn node_path(context: &SceneContext, node: Handle<Node>) -> Option<NodePath> {
+    let graph = &context.scene.graph;
+    let mut path = Vec::new();
+    let mut current = node;
+    while current != context.editor_scene.scene_content_root {
+        let parent = graph.try_get(current)?.parent();
+        let index = graph
+            .try_get(parent)?
+            .children()
+            .iter()
+            .position(|c| *c == current)?;
+        path.push(index as u32);
+        current = parent;
+    }
+    path.reverse();
+    Some(path)
+}
+
+fn resolve_node_path(
+    graph: &Graph,
+    content_root: Handle<Node>,
+    path: &[u32],
+) -> Option<Handle<Node>> {
+    let mut current = content_root;
+    for index in path {
+        current = *graph.try_get(current)?.children().get(*index as usize)?;
+    }
+    Some(current)
+}
+
+/// Serializable description of a command.
+#[derive(Debug, Clone, PartialEq, Visit)]
+pub enum JournalEntry {
+    SetNodeName {
+        node: NodePath,
+        name: String,
+    },
+    SetNodePosition {
+        node: NodePath,
+        position: Vector3<f32>,
+    },
+    SetNodeScale {
+        node: NodePath,
+        scale: Vector3<f32>,
+    },
+    SetNodeRotation {
+        node: NodePath,
+        rotation: UnitQuaternion<f32>,
+    },
+    SetNodeProperty {
+        node: NodePath,
+        path: String,
+        value: JournalValue,
+    },
+}
+
+impl Default for JournalEntry {
+    fn default() -> Self {
+        Self::SetNodeName {
+            node: Default::default(),
+            name: Default::default(),
+        }
+    }
+}
+
+impl JournalEntry {
+    pub fn set_node_name(context: &SceneContext, node: Handle<Node>, name: &str) -> Option<Self> {
+        Some(Self::SetNodeName {
+            node: node_path(context, node)?,
+            name: name.to_owned(),
+        })
+    }
+
+    pub fn set_node_position(
+        context: &SceneContext,
+        node: Handle<Node>,
+        position: Vector3<f32>,
+    ) -> Option<Self> {
+        Some(Self::SetNodePosition {
+            node: node_path(context, node)?,
+            position,
+        })
+    }
+
+    pub fn set_node_scale(
+        context: &SceneContext,
+        node: Handle<Node>,
+        scale: Vector3<f32>,
+    ) -> Option<Self> {
+        Some(Self::SetNodeScale {
+            node: node_path(context, node)?,
+            scale,
+        })
+    }
+
+    pub fn set_node_rotation(
+        context: &SceneContext,
+        node: Handle<Node>,
+        rotation: UnitQuaternion<f32>,
+    ) -> Option<Self> {
+        Some(Self::SetNodeRotation {
+            node: node_path(context, node)?,
+            rotation,
+        })
+    }
+
+    /// Tries to describe a property change. Universal property commands are shared across many
+    /// entities, but only scene nodes (identified by `Handle<Node>`) are supported.
+    pub fn set_property(
+        context: &SceneContext,
+        entity: &dyn Any,
+        path: &str,
+        value: &dyn Reflect,
+    ) -> Option<Self> {
+        let node = *entity.downcast_ref::<Handle<Node>>()?;
+        Some(Self::SetNodeProperty {
+            node: node_path(context, node)?,
+            path: path.to_owned(),
+            value: JournalValue::from_reflect(value)?,
+        })
+    }
+
+    /// Creates a command that performs the action described by the entry on the given scene. Returns
+    /// `None` if the scene does not have the node the entry refers to.
+    pub fn into_command(self, graph: &Graph, content_root: Handle<Node>) -> Option<SceneCommand> {
+        let command = match self {
+            JournalEntry::SetNodeName { node, name } => SceneCommand::new(SetNodeNameCommand::new(
+                resolve_node_path(graph, content_root, &node)?,
+                name,
+            )),
+            JournalEntry::SetNodePosition { node, position } => {
+                let node = resolve_node_path(graph, content_root, &node)?;
+                let old = **graph[node].local_transform().position();
+                SceneCommand::new(MoveNodeCommand::new(node, old, position))
+            }
+            JournalEntry::SetNodeScale { node, scale } => {
+                let node = resolve_node_path(graph, content_root, &node)?;
+                let old = **graph[node].local_transform().scale();
+                SceneCommand::new(ScaleNodeCommand::new(node, old, scale))
+            }
+            JournalEntry::SetNodeRotation { node, rotation } => {
+                let node = resolve_node_path(graph, content_root, &node)?;
+                let old = **graph[node].local_transform().rotation();
+                SceneCommand::new(RotateNodeCommand::new(node, old, rotation))
+            }
+            JournalEntry::SetNodeProperty { node, path, value } => {
+                SceneCommand::new(SetPropertyCommand::new(
+                    resolve_node_path(graph, content_root, &node)?,
+                    path,
+                    value.into_reflect(),
+                ))
+            }
+        };
+        Some(command)
+    }
+}
+
+/// A list of commands executed since the last save of a scene.
+#[derive(Default, Debug, Visit)]
+pub struct Journal {
+    /// Path of the scene the journal was started for, `None` means that the scene wasn't saved yet.
+    scene_path: Option<PathBuf>,
+    /// If set, the journal must be replayed on top of [`Journal::CHECKPOINT_FILE_NAME`] instead of
+    /// the scene path.
+    has_checkpoint: bool,
+    entries: Vec<JournalEntry>,
+}
+
+impl Journal {
+    const FILE_NAME: &'static str = "editor_journal.bin";
+    const CHECKPOINT_FILE_NAME: &'static str = "editor_journal_checkpoint.rgs";
+
+    pub fn new(scene_path: Option<PathBuf>) -> Self {
+        Self {
+            scene_path,
+            has_checkpoint: false,
+            entries: Default::default(),
+        }
+    }
+
+    /// Tries to load a journal left by the previous session. Returns `None` if there is nothing to
+    /// recover.
+    pub fn load() -> Option<Self> {
+        if !PathBuf::from(Self::FILE_NAME).exists() {
+            return None;
+        }
+
+        let mut journal = Journal::default();
+        match block_on(Visitor::load_binary(Self::FILE_NAME))
+            .and_then(|mut visitor| journal.visit("Journal", &mut visitor))
+        {
+            Ok(_) => Some(journal).filter(|j| j.has_checkpoint || !j.entries.is_empty()),
+            Err(e) => {
+                Log::err(format!("Unable to load command journal. Reason: {:?}", e));
+                None
+            }
+        }
+    }
+
+    /// Writes the journal to the recovery file.
+    pub fn save(&mut self) {
+        let mut visitor = Visitor::new();
+        Log::verify(self.visit("Journal", &mut visitor));
+        Log::verify(visitor.save_binary(Self::FILE_NAME));
+    }
+
+    /// Removes all recovery files, must be called when there are no unsaved changes anymore.
+    pub fn remove_files() {
+        for path in [Self::FILE_NAME, Self::CHECKPOINT_FILE_NAME] {
+            if PathBuf::from(path).exists() {
+                Log::verify(std::fs::remove_file(path));
+            }
+        }
+    }
+
+    pub fn record(&mut self, entry: JournalEntry) {
+        self.entries.push(entry);
+    }
+
+    /// Saves the given scene as a new starting point of the journal, all previous entries are
+    /// discarded.
+    pub fn checkpoint(&mut self, scene: &mut Scene) {
+        let mut visitor = Visitor::new();
+        Log::verify(scene.save("Scene", &mut visitor));
+        match visitor.save_binary(Self::CHECKPOINT_FILE_NAME) {
+            Ok(_) => {
+                self.has_checkpoint = true;
+                self.entries.clear();
+            }
+            Err(e) => Log::err(format!(
+                "Unable to save journal checkpoint. Reason: {:?}",
+                e
+            )),
+        }
+    }
+
+    /// Returns a path to the scene on top of which the journal must be replayed.
+    pub fn base_scene_path(&self) -> Option<PathBuf> {
+        if self.has_checkpoint {
+            Some(PathBuf::from(Self::CHECKPOINT_FILE_NAME))
+        } else {
+            self.scene_path.clone()
+        }
+    }
+
+    pub fn scene_path(&self) -> Option<&PathBuf> {
+        self.scene_path.as_ref()
+    }
+
+    pub fn entries(&self) -> &[JournalEntry] {
+        &self.entries
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        command::{
+            journal::{Journal, JournalEntry},
+            CommandStack,
+        },
+        message::MessageSender,
+        scene::{
+            commands::{
+                graph::{MoveNodeCommand, SetNodeNameCommand},
+                SceneCommand, SceneContext, SetPropertyCommand,
+            },
+            EditorScene,
+        },
+    };
+    use fyrox::{
+        core::{algebra::Vector3, pool::Handle, visitor::prelude::*},
+        scene::{base::BaseBuilder, node::Node, pivot::PivotBuilder, Scene},
+    };
+    use std::sync::mpsc::channel;
+
+    fn child(scene: &Scene, parent: Handle<Node>, index: usize) -> Handle<Node> {
+        scene.graph[parent].children()[index]
+    }
+
+    #[test]
+    fn test_journal_replay() {
+        // root
+        //   a
+        //     b
+        //   c
+        let mut scene = Scene::new();
+        let b = PivotBuilder::new(BaseBuilder::new().with_name("B")).build(&mut scene.graph);
+        let a = PivotBuilder::new(BaseBuilder::new().with_name("A").with_children(&[b]))
+            .build(&mut scene.graph);
+        let c = PivotBuilder::new(BaseBuilder::new().with_name("C")).build(&mut scene.graph);
+
+        // This is what was saved last time.
+        let (mut saved_scene, _) = scene.clone(scene.graph.get_root(), &mut |_, _| true);
+
+        let (sender, _receiver) = channel();
+        let sender = MessageSender(sender);
+
+        let mut editor_scene = EditorScene::new_test(&mut scene);
+        let mut command_stack = CommandStack::new(false);
+        let mut journal = Journal::new(None);
+
+        let commands = vec![
+            SceneCommand::new(SetNodeNameCommand::new(c, "Renamed".to_string())),
+            SceneCommand::new(MoveNodeCommand::new(
+                b,
+                Vector3::default(),
+                Vector3::new(1.0, 2.0, 3.0),
+            )),
+            SceneCommand::new(SetPropertyCommand::new(
+                a,
+                "base.visibility".to_string(),
+                Box::new(false),
+            )),
+            SceneCommand::new(MoveNodeCommand::new(
+                b,
+                Vector3::new(1.0, 2.0, 3.0),
+                Vector3::new(4.0, 5.0, 6.0),
+            )),
+        ];
+        for command in commands {
+            let context = SceneContext::new_test(&mut editor_scene, &mut scene, sender.clone());
+            for entry in command.journal_entries(&context).unwrap() {
+                journal.record(entry);
+            }
+            command_stack.do_command(command.into_inner(), context);
+        }
+
+        // Undo must be journaled too.
+        let undo_context = SceneContext::new_test(&mut editor_scene, &mut scene, sender.clone());
+        for entry in command_stack
+            .undo_command()
+            .unwrap()
+            .journal_entries(&undo_context)
+            .unwrap()
+        {
+            journal.record(entry);
+        }
+        command_stack.undo(undo_context);
+
+        assert_eq!(journal.entries().len(), 5);
+        assert_eq!(
+            journal.entries()[4],
+            JournalEntry::SetNodePosition {
+                node: vec![0, 0],
+                position: Vector3::new(1.0, 2.0, 3.0)
+            }
+        );
+
+        // Pretend that the journal was written to a file and then read back.
+        let mut visitor = Visitor::new();
+        journal.visit("Journal", &mut visitor).unwrap();
+        let data = visitor.save_binary_to_vec().unwrap();
+        let mut loaded_journal = Journal::default();
+        let mut visitor = Visitor::load_from_memory(data).unwrap();
+        loaded_journal.visit("Journal", &mut visitor).unwrap();
+        assert_eq!(loaded_journal.entries(), journal.entries());
+
+        // Replay the journal on top of the saved scene.
+        let mut saved_editor_scene = EditorScene::new_test(&mut saved_scene);
+        let mut replay_stack = CommandStack::new(false);
+        for entry in loaded_journal.entries().iter().cloned() {
+            let command = entry
+                .into_command(&saved_scene.graph, saved_editor_scene.scene_content_root)
+                .unwrap();
+            replay_stack.do_command(
+                command.into_inner(),
+                SceneContext::new_test(&mut saved_editor_scene, &mut saved_scene, sender.clone()),
+            );
+        }
+
+        let root = saved_scene.graph.get_root();
+        let saved_a = child(&saved_scene, root, 0);
+        let saved_b = child(&saved_scene, saved_a, 0);
+        let saved_c = child(&saved_scene, root, 1);
+
+        assert_eq!(saved_scene.graph[saved_a].name(), "A");
+        assert!(!saved_scene.graph[saved_a].visibility());
+        assert_eq!(
+            **saved_scene.graph[saved_b].local_transform().position(),
+            Vector3::new(1.0, 2.0, 3.0)
+        );
+        assert_eq!(
+            **saved_scene.graph[saved_b].local_transform().position(),
+            **scene.graph[b].local_transform().position()
+        );
+        assert_eq!(saved_scene.graph[saved_c].name(), "Renamed");
+    }
+}
@@ -1,6 +1,7 @@
 use crate::scene::commands::SceneContext;
 use std::fmt::Debug;
 
+pub mod journal;
 pub mod panel;
 pub mod universal;
 
@@ -12,6 +13,15 @@ macro_rules! define_command_stack {
             fn execute(&mut self, context: &mut $context);
             fn revert(&mut self, context: &mut $context);
             fn finalize(&mut self, _: &mut $context) {}
+            /// Returns serializable description of the actions that will be performed by the next
+            /// call of either `execute` or `revert`. Commands that cannot be described return `None`,
+            /// in this case the journal will store the entire scene instead.
+            fn journal_entries(
+                &self,
+                _: &$context,
+            ) -> Option<Vec<$crate::command::journal::JournalEntry>> {
+                None
+            }
         }
 
         pub struct $command_stack {
@@ -64,13 +74,21 @@ macro_rules! define_command_stack {
             }
 
             pub fn undo(&mut self, mut context: $context) {
+                self.undo_internal(&mut context);
+            }
+
+            pub fn redo(&mut self, mut context: $context) {
+                self.redo_internal(&mut context);
+            }
+
+            fn undo_internal(&mut self, context: &mut $context) {
                 if !self.commands.is_empty() {
                     if let Some(top) = self.top.as_mut() {
                         if let Some(command) = self.commands.get_mut(*top) {
                             if self.debug {
                                 println!("Undo command {:?}", command);
                             }
-                            command.revert(&mut context)
+                            command.revert(context)
                         }
                         if *top == 0 {
                             self.top = None;
@@ -81,7 +99,7 @@ macro_rules! define_command_stack {
                 }
             }
 
-            pub fn redo(&mut self, mut context: $context) {
+            fn redo_internal(&mut self, context: &mut $context) {
                 if !self.commands.is_empty() {
                     let command = match self.top.as_mut() {
                         None => {
@@ -103,7 +121,7 @@ macro_rules! define_command_stack {
                         if self.debug {
                             println!("Redo command {:?}", command);
                         }
-                        command.execute(&mut context)
+                        command.execute(context)
                     }
                 }
             }
@@ -115,9 +133,138 @@ macro_rules! define_command_stack {
                     }
                     dropped_command.finalize(&mut context);
                 }
+                self.top = None;
             }
         }
     };
 }
 
 define_command_stack!(Command, CommandStack, SceneContext);
+
+impl CommandStack {
+    /// Returns a command that will be reverted by the next `undo` call.
+    pub fn undo_command(&self) -> Option<&dyn Command> {
+        self.top
+            .and_then(|top| self.commands.get(top))
+            .map(|c| &**c)
+    }
+
+    /// Returns a command that will be executed by the next `redo` call.
+    pub fn redo_command(&self) -> Option<&dyn Command> {
+        self.commands
+            .get(self.top.map_or(0, |top| top + 1))
+            .map(|c| &**c)
+    }
+
+    /// Undoes or redoes commands one-by-one until the command with the given index becomes the top
+    /// of the stack. `None` means that every command will be undone.
+    pub fn jump_to(&mut self, target: Option<usize>, mut context: SceneContext) {
+        if self.commands.is_empty() {
+            return;
+        }
+        let target = target.map(|t| t.min(self.commands.len() - 1));
+        while self.top > target {
+            self.undo_internal(&mut context);
+        }
+        while self.top < target {
+            self.redo_internal(&mut context);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        command::CommandStack,
+        message::MessageSender,
+        scene::{
+            commands::{
+                graph::{SetChildIndexCommand, SetNodeNameCommand},
+                SceneContext,
+            },
+            EditorScene,
+        },
+    };
+    use fyrox::scene::{base::BaseBuilder, pivot::PivotBuilder, Scene};
+    use std::sync::mpsc::channel;
+
+    #[test]
+    fn test_jump_to_command() {
+        let mut scene = Scene::new();
+        let node = PivotBuilder::new(BaseBuilder::new().with_name("0")).build(&mut scene.graph);
+
+        let mut editor_scene = EditorScene::new_test(&mut scene);
+
+        let (sender, _receiver) = channel();
+        let sender = MessageSender(sender);
+
+        let mut stack = CommandStack::new(false);
+        for i in 1..=5 {
+            stack.do_command(
+                Box::new(SetNodeNameCommand::new(node, i.to_string())),
+                SceneContext::new_test(&mut editor_scene, &mut scene, sender.clone()),
+            );
+        }
+        assert_eq!(scene.graph[node].name(), "5");
+
+        // Jump back three commands at once.
+        stack.jump_to(
+            Some(1),
+            SceneContext::new_test(&mut editor_scene, &mut scene, sender.clone()),
+        );
+        assert_eq!(stack.top, Some(1));
+        assert_eq!(scene.graph[node].name(), "2");
+
+        // And forward again.
+        stack.jump_to(
+            Some(3),
+            SceneContext::new_test(&mut editor_scene, &mut scene, sender.clone()),
+        );
+        assert_eq!(stack.top, Some(3));
+        assert_eq!(scene.graph[node].name(), "4");
+
+        // Revert everything.
+        stack.jump_to(
+            None,
+            SceneContext::new_test(&mut editor_scene, &mut scene, sender.clone()),
+        );
+        assert_eq!(stack.top, None);
+        assert_eq!(scene.graph[node].name(), "0");
+    }
+
+    #[test]
+    fn test_set_child_index_command() {
+        let mut scene = Scene::new();
+        let a = PivotBuilder::new(BaseBuilder::new()).build(&mut scene.graph);
+        let b = PivotBuilder::new(BaseBuilder::new()).build(&mut scene.graph);
+        let c = PivotBuilder::new(BaseBuilder::new()).build(&mut scene.graph);
+        let parent =
+            PivotBuilder::new(BaseBuilder::new().with_children(&[a, b, c])).build(&mut scene.graph);
+
+        let mut editor_scene = EditorScene::new_test(&mut scene);
+
+        let (sender, _receiver) = channel();
+        let sender = MessageSender(sender);
+
+        let mut stack = CommandStack::new(false);
+        stack.do_command(
+            Box::new(SetChildIndexCommand::new(parent, a, 2)),
+            SceneContext::new_test(&mut editor_scene, &mut scene, sender.clone()),
+        );
+        assert_eq!(scene.graph[parent].children(), &[b, c, a]);
+
+        stack.undo(SceneContext::new_test(
+            &mut editor_scene,
+            &mut scene,
+            sender.clone(),
+        ));
+        assert_eq!(scene.graph[parent].children(), &[a, b, c]);
+
+        stack.redo(SceneContext::new_test(
+            &mut editor_scene,
+            &mut scene,
+            sender.clone(),
+        ));
+        assert_eq!(scene.graph[parent].children(), &[b, c, a]);
+    }
+}
@@ -27,6 +27,7 @@ pub struct CommandStackViewer {
     undo: Handle<UiNode>,
     redo: Handle<UiNode>,
     clear: Handle<UiNode>,
+    command_count: usize,
 }
 
 impl CommandStackViewer {
@@ -110,6 +111,7 @@ impl CommandStackViewer {
             undo,
             redo,
             clear,
+            command_count: 0,
         }
     }
 
@@ -124,6 +126,17 @@ impl CommandStackViewer {
             } else if message.destination() == self.clear {
                 self.sender.send(Message::ClearSceneCommandStack);
             }
+        } else if let Some(&ListViewMessage::SelectionChanged(Some(index))) =
+            message.data::<ListViewMessage>()
+        {
+            if message.destination() == self.list
+                && message.direction() == MessageDirection::FromWidget
+            {
+                // First command in list is last on stack.
+                if let Some(command) = self.command_count.checked_sub(index + 1) {
+                    self.sender.send(Message::JumpToSceneCommand(Some(command)));
+                }
+            }
         }
     }
 
@@ -167,10 +180,27 @@ impl CommandStackViewer {
             })
             .collect();
 
+        self.command_count = command_stack.commands.len();
+
+        // Reset selection first, otherwise the list will try to fix it and the fix will be treated
+        // as a click on an item.
+        send_sync_message(
+            ui,
+            ListViewMessage::selection(self.list, MessageDirection::ToWidget, None),
+        );
         send_sync_message(
             ui,
             ListViewMessage::items(self.list, MessageDirection::ToWidget, items),
         );
+        // Highlight current position in the stack.
+        send_sync_message(
+            ui,
+            ListViewMessage::selection(
+                self.list,
+                MessageDirection::ToWidget,
+                top.and_then(|top| self.command_count.checked_sub(top + 1)),
+            ),
+        );
     }
 
     pub fn on_mode_changed(&mut self, ui: &UserInterface, mode: &Mode) {
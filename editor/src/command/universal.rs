@@ -15,6 +15,9 @@ macro_rules! define_universal_commands {
                 fyrox::gui::inspector::PropertyAction::RemoveItem { index } => Some(<$command_wrapper>::new(
                     RemoveCollectionItemCommand::new($handle_ident, property_changed.path(), index, $($field_name),*)
                 )),
+                fyrox::gui::inspector::PropertyAction::MoveItem { from, to } => Some(<$command_wrapper>::new(
+                    MoveCollectionItemCommand::new($handle_ident, property_changed.path(), from, to, $($field_name),*)
+                )),
                 // Must be handled outside, there is not enough context and it near to impossible to create universal reversion
                 // for InheritableVariable<T>.
                 fyrox::gui::inspector::PropertyAction::Revert => None
@@ -99,6 +102,10 @@ macro_rules! define_universal_commands {
             fn revert(&mut $self, $ctx_ident: &mut $ctx) {
                 $self.swap($ctx_ident);
             }
+
+            fn journal_entries(&$self, $ctx_ident: &$ctx) -> Option<Vec<$crate::command::journal::JournalEntry>> {
+                $crate::command::journal::JournalEntry::set_property($ctx_ident, &$self.$handle_ident, &$self.path, $self.value.as_deref()?).map(|e| vec![e])
+            }
         }
 
         #[derive(Debug)]
@@ -221,5 +228,64 @@ macro_rules! define_universal_commands {
                 })
             }
         }
+
+        #[derive(Debug)]
+        pub struct MoveCollectionItemCommand {
+            #[allow(dead_code)]
+            $handle_ident: $handle,
+            path: String,
+            from: usize,
+            to: usize,
+            $($field_name: $field_type),*
+        }
+
+        impl MoveCollectionItemCommand {
+            pub fn new($handle_ident: $handle, path: String, from: usize, to: usize, $($field_name: $field_type),*) -> Self {
+                Self {
+                    $handle_ident,
+                    path,
+                    from,
+                    to,
+                    $($field_name),*
+                }
+            }
+
+            fn move_item(path: &str, from: usize, to: usize, field: &mut dyn fyrox::core::reflect::Reflect) {
+                field.as_list_mut(&mut |result| {
+                    if let Some(list) = result {
+                        if let Some(item) = list.reflect_remove(from) {
+                            if list.reflect_insert(to, item).is_err() {
+                                fyrox::core::log::Log::err(format!(
+                                    "Failed to insert item to {} collection. Type mismatch!",
+                                    path
+                                ))
+                            }
+                        } else {
+                            fyrox::core::log::Log::err(format!("There is no item {} in {} collection!", from, path))
+                        }
+                    } else {
+                        fyrox::core::log::Log::err(format!("Property {} is not a collection!", path))
+                    }
+                })
+            }
+        }
+
+        impl $command for MoveCollectionItemCommand {
+            fn name(&mut $self, _: &$ctx) -> String {
+                format!("Move collection {} item {} to {}", $self.path, $self.from, $self.to)
+            }
+
+            fn execute(&mut $self, $ctx_ident: &mut $ctx) {
+                try_modify_property($entity_getter, &$self.path, |field| {
+                    Self::move_item(&$self.path, $self.from, $self.to, field)
+                })
+            }
+
+            fn revert(&mut $self, $ctx_ident: &mut $ctx) {
+                try_modify_property($entity_getter, &$self.path, |field| {
+                    Self::move_item(&$self.path, $self.to, $self.from, field)
+                })
+            }
+        }
     };
 }
@@ -0,0 +1,335 @@
+//! Selection outline, that is drawn around selected objects in the scene viewer.
+
+use crate::scene::Selection;
+use fyrox::{
+    core::{
+        algebra::{Matrix4, Vector3},
+        color::Color,
+        math::Rect,
+        pool::Handle,
+        sstorage::ImmutableString,
+    },
+    fxhash::FxHashSet,
+    renderer::{
+        framework::{
+            error::FrameworkError,
+            framebuffer::{
+                Attachment, AttachmentKind, BlendParameters, DrawParameters, FrameBuffer,
+            },
+            geometry_buffer::{ElementRange, GeometryBuffer, GeometryBufferKind},
+            gpu_program::{GpuProgram, UniformLocation},
+            gpu_texture::{
+                GpuTexture, GpuTextureKind, MagnificationFilter, MinificationFilter, PixelKind,
+            },
+            state::{BlendFactor, BlendFunc, PipelineState},
+        },
+        RenderPassStatistics, SceneRenderPass, SceneRenderPassContext,
+    },
+    scene::{mesh::surface::SurfaceData, mesh::Mesh, node::Node, Scene},
+};
+use std::{cell::RefCell, rc::Rc};
+
+struct MaskShader {
+    program: GpuProgram,
+    wvp_matrix: UniformLocation,
+}
+
+impl MaskShader {
+    fn new(state: &mut PipelineState) -> Result<Self, FrameworkError> {
+        let fragment_source = include_str!("../resources/embed/shaders/highlight_mask_fs.glsl");
+        let vertex_source = include_str!("../resources/embed/shaders/highlight_vs.glsl");
+        let program =
+            GpuProgram::from_source(state, "HighlightMaskShader", vertex_source, fragment_source)?;
+        Ok(Self {
+            wvp_matrix: program
+                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
+            program,
+        })
+    }
+}
+
+struct EdgeShader {
+    program: GpuProgram,
+    wvp_matrix: UniformLocation,
+    mask_texture: UniformLocation,
+    color: UniformLocation,
+    thickness: UniformLocation,
+}
+
+impl EdgeShader {
+    fn new(state: &mut PipelineState) -> Result<Self, FrameworkError> {
+        let fragment_source = include_str!("../resources/embed/shaders/highlight_edge_fs.glsl");
+        let vertex_source = include_str!("../resources/embed/shaders/highlight_vs.glsl");
+        let program =
+            GpuProgram::from_source(state, "HighlightEdgeShader", vertex_source, fragment_source)?;
+        Ok(Self {
+            wvp_matrix: program
+                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
+            mask_texture: program.uniform_location(state, &ImmutableString::new("maskTexture"))?,
+            color: program.uniform_location(state, &ImmutableString::new("color"))?,
+            thickness: program.uniform_location(state, &ImmutableString::new("thickness"))?,
+            program,
+        })
+    }
+}
+
+/// A set of nodes of a scene, that should be outlined.
+#[derive(Default, Debug)]
+pub struct HighlightTargets {
+    scene: Handle<Scene>,
+    nodes: FxHashSet<Handle<Node>>,
+}
+
+impl HighlightTargets {
+    /// Replaces current targets with the nodes from the given selection. Any other kind of selection
+    /// clears the targets.
+    pub fn set_from_selection(&mut self, scene: Handle<Scene>, selection: &Selection) {
+        self.scene = scene;
+        self.nodes.clear();
+        if let Selection::Graph(selection) = selection {
+            self.nodes.extend(selection.nodes().iter().cloned());
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.scene = Handle::NONE;
+        self.nodes.clear();
+    }
+
+    pub fn scene(&self) -> Handle<Scene> {
+        self.scene
+    }
+
+    pub fn contains(&self, node: Handle<Node>) -> bool {
+        self.nodes.contains(&node)
+    }
+
+    pub fn nodes(&self) -> &FxHashSet<Handle<Node>> {
+        &self.nodes
+    }
+}
+
+/// Draws an outline around meshes of the target nodes and their descendants. At first, the meshes are
+/// rendered into an offscreen mask, and then every pixel outside of the mask, that has a masked pixel
+/// nearby, is painted with the outline color.
+pub struct HighlightRenderPass {
+    framebuffer: FrameBuffer,
+    quad: GeometryBuffer,
+    mask_shader: MaskShader,
+    edge_shader: EdgeShader,
+    width: usize,
+    height: usize,
+    pub targets: HighlightTargets,
+    pub color: Color,
+    pub thickness: f32,
+}
+
+fn make_framebuffer(
+    state: &mut PipelineState,
+    width: usize,
+    height: usize,
+) -> Result<FrameBuffer, FrameworkError> {
+    let mask = GpuTexture::new(
+        state,
+        GpuTextureKind::Rectangle { width, height },
+        PixelKind::RGBA8,
+        MinificationFilter::Nearest,
+        MagnificationFilter::Nearest,
+        1,
+        None,
+    )?;
+
+    FrameBuffer::new(
+        state,
+        None,
+        vec![Attachment {
+            kind: AttachmentKind::Color,
+            texture: Rc::new(RefCell::new(mask)),
+        }],
+    )
+}
+
+impl HighlightRenderPass {
+    pub fn new(state: &mut PipelineState, width: usize, height: usize) -> Rc<RefCell<Self>> {
+        Rc::new(RefCell::new(Self {
+            framebuffer: make_framebuffer(state, width, height).unwrap(),
+            quad: GeometryBuffer::from_surface_data(
+                &SurfaceData::make_unit_xy_quad(),
+                GeometryBufferKind::StaticDraw,
+                state,
+            ),
+            mask_shader: MaskShader::new(state).unwrap(),
+            edge_shader: EdgeShader::new(state).unwrap(),
+            width,
+            height,
+            targets: Default::default(),
+            color: Color::opaque(255, 140, 0),
+            thickness: 2.0,
+        }))
+    }
+}
+
+impl SceneRenderPass for HighlightRenderPass {
+    fn on_ldr_render(
+        &mut self,
+        ctx: SceneRenderPassContext,
+    ) -> Result<RenderPassStatistics, FrameworkError> {
+        let mut stats = RenderPassStatistics::default();
+
+        if ctx.scene_handle != self.targets.scene()
+            || self.targets.nodes().is_empty()
+            || self.thickness <= 0.0
+        {
+            return Ok(stats);
+        }
+
+        let width = ctx.viewport.w().max(1) as usize;
+        let height = ctx.viewport.h().max(1) as usize;
+        if width != self.width || height != self.height {
+            self.framebuffer = make_framebuffer(ctx.pipeline_state, width, height)?;
+            self.width = width;
+            self.height = height;
+        }
+
+        let mask_viewport = Rect::new(0, 0, width as i32, height as i32);
+        self.framebuffer.clear(
+            ctx.pipeline_state,
+            mask_viewport,
+            Some(Color::TRANSPARENT),
+            None,
+            None,
+        );
+
+        let graph = &ctx.scene.graph;
+        let view_projection = ctx.camera.view_projection_matrix();
+        for &target in self.targets.nodes() {
+            if graph.try_get(target).is_none() {
+                continue;
+            }
+
+            for handle in graph.traverse_handle_iter(target) {
+                let node = &graph[handle];
+                if !node.global_visibility() {
+                    continue;
+                }
+
+                if let Some(mesh) = node.cast::<Mesh>() {
+                    let wvp = view_projection * mesh.global_transform();
+                    for surface in mesh.surfaces() {
+                        let geometry = ctx.geometry_cache.get(ctx.pipeline_state, &surface.data());
+                        stats += self.framebuffer.draw(
+                            geometry,
+                            ctx.pipeline_state,
+                            mask_viewport,
+                            &self.mask_shader.program,
+                            &DrawParameters {
+                                cull_face: None,
+                                color_write: Default::default(),
+                                depth_write: false,
+                                stencil_test: None,
+                                depth_test: false,
+                                blend: None,
+                                stencil_op: Default::default(),
+                            },
+                            ElementRange::Full,
+                            |mut program_binding| {
+                                program_binding.set_matrix4(&self.mask_shader.wvp_matrix, &wvp);
+                            },
+                        )?;
+                    }
+                }
+            }
+        }
+
+        let frame_matrix = Matrix4::new_orthographic(
+            0.0,
+            ctx.viewport.w() as f32,
+            ctx.viewport.h() as f32,
+            0.0,
+            -1.0,
+            1.0,
+        ) * Matrix4::new_nonuniform_scaling(&Vector3::new(
+            ctx.viewport.w() as f32,
+            ctx.viewport.h() as f32,
+            0.0,
+        ));
+        let mask = self.framebuffer.color_attachments()[0].texture.clone();
+        let shader = &self.edge_shader;
+        stats += ctx.framebuffer.draw(
+            &self.quad,
+            ctx.pipeline_state,
+            ctx.viewport,
+            &shader.program,
+            &DrawParameters {
+                cull_face: None,
+                color_write: Default::default(),
+                depth_write: false,
+                stencil_test: None,
+                depth_test: false,
+                blend: Some(BlendParameters {
+                    func: BlendFunc::new(BlendFactor::SrcAlpha, BlendFactor::OneMinusSrcAlpha),
+                    ..Default::default()
+                }),
+                stencil_op: Default::default(),
+            },
+            ElementRange::Full,
+            |mut program_binding| {
+                program_binding
+                    .set_matrix4(&shader.wvp_matrix, &frame_matrix)
+                    .set_texture(&shader.mask_texture, &mask)
+                    .set_srgb_color(&shader.color, &self.color)
+                    .set_f32(&shader.thickness, self.thickness.min(10.0));
+            },
+        )?;
+
+        Ok(stats)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        highlight::HighlightTargets, scene::Selection, world::graph::selection::GraphSelection,
+    };
+    use fyrox::{
+        core::pool::Handle,
+        scene::{base::BaseBuilder, pivot::PivotBuilder, Scene},
+    };
+
+    #[test]
+    fn test_selection_is_added_to_outline_targets() {
+        let mut scene = Scene::new();
+        let a = PivotBuilder::new(BaseBuilder::new()).build(&mut scene.graph);
+        let b = PivotBuilder::new(BaseBuilder::new()).build(&mut scene.graph);
+        let scene_handle = Handle::new(1, 1);
+
+        let mut targets = HighlightTargets::default();
+
+        targets.set_from_selection(
+            scene_handle,
+            &Selection::Graph(GraphSelection::single_or_empty(a)),
+        );
+        assert_eq!(targets.scene(), scene_handle);
+        assert!(targets.contains(a));
+        assert!(!targets.contains(b));
+
+        // Multi-selection highlights all selected nodes.
+        targets.set_from_selection(
+            scene_handle,
+            &Selection::Graph(GraphSelection::from_list(vec![a, b])),
+        );
+        assert!(targets.contains(a));
+        assert!(targets.contains(b));
+
+        // Deselection removes the node from the targets.
+        targets.set_from_selection(
+            scene_handle,
+            &Selection::Graph(GraphSelection::single_or_empty(b)),
+        );
+        assert!(!targets.contains(a));
+        assert!(targets.contains(b));
+
+        targets.set_from_selection(scene_handle, &Selection::None);
+        assert!(targets.nodes().is_empty());
+    }
+}
@@ -29,6 +29,7 @@ use fyrox::{
         AnimationContainer,
     },
     core::{
+        algebra::Vector3,
         futures::executor::block_on,
         parking_lot::Mutex,
         pool::{ErasedHandle, Handle},
@@ -53,7 +54,8 @@ use fyrox::{
     },
     scene::{
         base::{
-            Base, LevelOfDetail, LodControlledObject, LodGroup, Mobility, Property, PropertyValue,
+            Base, BillboardMode, LevelOfDetail, LodControlledObject, LodGroup, Mobility, Property,
+            PropertyValue, UnresolvedScript,
         },
         camera::{
             ColorGradingLut, Exposure, OrthographicProjection, PerspectiveProjection, Projection,
@@ -91,11 +93,12 @@ use fyrox::{
                 HighShelfFilterEffect, LowPassFilterEffect, LowShelfFilterEffect,
             },
             reverb::Reverb,
-            Attenuate, AudioBus, Biquad, DistanceModel, Effect, EffectWrapper, SoundBuffer,
-            SoundBufferResource, Status,
+            Attenuate, AudioBus, Biquad, DistanceModel, Effect, EffectWrapper, LoopRegion,
+            PitchRange, SoundBuffer, SoundBufferResource, Status,
         },
         terrain::{Chunk, Layer},
         transform::Transform,
+        trigger::TriggerShape,
     },
 };
 use std::rc::Rc;
@@ -153,6 +156,7 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.register_inheritable_vec_collection::<Property>();
     container.register_inheritable_vec_collection::<LodControlledObject>();
     container.register_inheritable_vec_collection::<GeometrySource>();
+    container.register_inheritable_vec_collection::<Vector3<f32>>();
 
     container.insert(make_status_enum_editor_definition());
 
@@ -163,6 +167,10 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
 
     container.register_inheritable_inspectable::<LodGroup>();
 
+    // Unresolved scripts are shown as read-only placeholders.
+    container.insert(EnumPropertyEditorDefinition::<UnresolvedScript>::new_optional());
+    container.insert(InspectablePropertyEditorDefinition::<UnresolvedScript>::new());
+
     container
         .register_inheritable_inspectable::<fyrox::animation::spritesheet::SpriteSheetAnimation>();
     container
@@ -216,6 +224,8 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.register_inheritable_inspectable::<PrismaticJoint>();
     container.register_inheritable_inspectable::<dim2::joint::PrismaticJoint>();
 
+    container.register_inheritable_inspectable::<dim2::rectangle::Rectangle>();
+
     container.register_inheritable_inspectable::<Base>();
     container.register_inheritable_inspectable::<BaseLight>();
 
@@ -235,6 +245,8 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
 
     container.register_inheritable_inspectable::<Biquad>();
     container.register_inheritable_inspectable::<AudioBus>();
+    container.register_inheritable_inspectable::<LoopRegion>();
+    container.register_inheritable_inspectable::<PitchRange>();
     container.register_inheritable_inspectable::<BaseEmitter>();
     container.register_inheritable_inspectable::<SphereEmitter>();
     container.register_inheritable_inspectable::<CylinderEmitter>();
@@ -252,6 +264,8 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.register_inheritable_option::<ColorGradingLut>();
     container.register_inheritable_option::<Biquad>();
     container.register_inheritable_option::<SkyBox>();
+    container.register_inheritable_option::<LoopRegion>();
+    container.register_inheritable_option::<PitchRange>();
 
     container.register_inheritable_inspectable::<SkyBox>();
 
@@ -263,8 +277,10 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.register_inheritable_enum::<TextureMinificationFilter, _>();
     container.register_inheritable_enum::<Projection, _>();
     container.register_inheritable_enum::<ColliderShape, _>();
+    container.register_inheritable_enum::<TriggerShape, _>();
     container.register_inheritable_enum::<PropertyValue, _>();
     container.register_inheritable_enum::<Mobility, _>();
+    container.register_inheritable_enum::<BillboardMode, _>();
     container.register_inheritable_enum::<RigidBodyType, _>();
     container.register_inheritable_enum::<Exposure, _>();
     container.register_inheritable_enum::<FrustumSplitOptions, _>();
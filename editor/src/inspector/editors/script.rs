@@ -4,7 +4,7 @@ use crate::{
 };
 use fyrox::gui::inspector::PropertyFilter;
 use fyrox::{
-    core::{pool::Handle, uuid::Uuid},
+    core::{pool::Handle, reflect::Reflect, uuid::Uuid},
     engine::SerializationContext,
     gui::{
         define_constructor,
@@ -151,7 +151,7 @@ impl ScriptPropertyEditorBuilder {
         ctx: &mut BuildContext,
     ) -> Handle<UiNode> {
         let context = script.as_ref().map(|script| {
-            InspectorContext::from_object(
+            make_script_inspector_context(
                 script,
                 ctx,
                 definition_container,
@@ -181,6 +181,43 @@ impl ScriptPropertyEditorBuilder {
     }
 }
 
+/// Creates inspector context for the given script. Properties of the script could be reset to the
+/// values of a default instance of the script.
+fn make_script_inspector_context(
+    script: &Script,
+    ctx: &mut BuildContext,
+    definition_container: Rc<PropertyEditorDefinitionContainer>,
+    environment: Option<Rc<dyn InspectorEnvironment>>,
+    sync_flag: u64,
+    layer_index: usize,
+    generate_property_string_values: bool,
+    filter: PropertyFilter,
+) -> InspectorContext {
+    let default_script = EditorEnvironment::try_get_from(&environment).and_then(|env| {
+        env.serialization_context
+            .script_constructors
+            .try_create(&script.id())
+    });
+
+    let context = InspectorContext::from_object(
+        script,
+        ctx,
+        definition_container,
+        environment,
+        sync_flag,
+        layer_index,
+        generate_property_string_values,
+        filter,
+    );
+
+    match default_script {
+        Some(default_script) => {
+            context.with_default_object(Rc::new(default_script) as Rc<dyn Reflect>)
+        }
+        None => context,
+    }
+}
+
 fn create_items(
     serialization_context: Arc<SerializationContext>,
     ctx: &mut BuildContext,
@@ -367,7 +404,7 @@ impl PropertyEditorDefinition for ScriptPropertyEditorDefinition {
             let context = value
                 .as_ref()
                 .map(|script| {
-                    InspectorContext::from_object(
+                    make_script_inspector_context(
                         script,
                         &mut ctx.ui.build_ctx(),
                         ctx.definition_container.clone(),
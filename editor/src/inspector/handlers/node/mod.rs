@@ -32,7 +32,7 @@ impl SceneNodePropertyChangedHandler {
                     CollectionChanged::Remove(index) => Some(SceneCommand::new(
                         DeleteTerrainLayerCommand::new(handle, index),
                     )),
-                    CollectionChanged::ItemChanged { .. } => None,
+                    CollectionChanged::Move { .. } | CollectionChanged::ItemChanged { .. } => None,
                 },
                 _ => None,
             }
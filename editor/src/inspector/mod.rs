@@ -229,6 +229,15 @@ impl Inspector {
         }
     }
 
+    /// Brings the inspector window to front, so the properties of the newly selected object are visible
+    /// even if the window was covered by some other window.
+    pub fn focus(&self, ui: &UserInterface) {
+        ui.send_message(WidgetMessage::topmost(
+            self.window,
+            MessageDirection::ToWidget,
+        ));
+    }
+
     fn sync_to(&mut self, obj: &dyn Reflect, ui: &mut UserInterface) {
         let ctx = ui
             .node(self.inspector)
@@ -1,3 +1,52 @@
+use fyrox::core::{algebra::UnitQuaternion, reflect::prelude::*};
+use serde::{Deserialize, Serialize};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
 pub mod move_gizmo;
 pub mod rotate_gizmo;
 pub mod scale_gizmo;
+
+/// Defines orientation of the move and rotation gizmos and thus the axes along which drags are applied.
+#[derive(
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Debug,
+    Serialize,
+    Deserialize,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum GizmoSpace {
+    /// Gizmo is aligned with the local axes of the selected node.
+    Local,
+    /// Gizmo is aligned with the world axes, regardless of the orientation of the selected node.
+    World,
+}
+
+impl Default for GizmoSpace {
+    fn default() -> Self {
+        Self::Local
+    }
+}
+
+impl GizmoSpace {
+    /// Returns the other space.
+    pub fn toggled(self) -> Self {
+        match self {
+            GizmoSpace::Local => GizmoSpace::World,
+            GizmoSpace::World => GizmoSpace::Local,
+        }
+    }
+
+    /// Returns global rotation of a gizmo for the given global rotation of a selection.
+    pub fn gizmo_rotation(self, selection_rotation: UnitQuaternion<f32>) -> UnitQuaternion<f32> {
+        match self {
+            GizmoSpace::Local => selection_rotation,
+            GizmoSpace::World => UnitQuaternion::identity(),
+        }
+    }
+}
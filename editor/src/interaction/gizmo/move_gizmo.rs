@@ -1,5 +1,5 @@
 use crate::{
-    interaction::plane::PlaneKind,
+    interaction::{gizmo::GizmoSpace, pivot::SnapPivot, plane::PlaneKind},
     make_color_material,
     scene::{EditorScene, Selection},
     set_mesh_diffuse_color, Engine,
@@ -140,9 +140,14 @@ fn create_quad_plane(
 
 impl MoveGizmo {
     pub fn new(editor_scene: &EditorScene, engine: &mut Engine) -> Self {
-        let scene = &mut engine.scenes[editor_scene.scene];
-        let graph = &mut scene.graph;
+        Self::new_in_graph(
+            &mut engine.scenes[editor_scene.scene].graph,
+            editor_scene.editor_objects_root,
+        )
+    }
 
+    /// Creates the gizmo in the given graph and links it to the given editor objects root.
+    pub fn new_in_graph(graph: &mut Graph, editor_objects_root: Handle<Node>) -> Self {
         let origin = PivotBuilder::new(
             BaseBuilder::new()
                 .with_name("Origin")
@@ -150,7 +155,7 @@ impl MoveGizmo {
         )
         .build(graph);
 
-        graph.link_nodes(origin, editor_scene.editor_objects_root);
+        graph.link_nodes(origin, editor_objects_root);
 
         let smart_dot = make_smart_dot(graph);
         graph.link_nodes(smart_dot, origin);
@@ -334,14 +339,24 @@ impl MoveGizmo {
         Vector3::default()
     }
 
-    pub fn sync_transform(&self, scene: &mut Scene, selection: &Selection, scale: Vector3<f32>) {
+    pub fn sync_transform(
+        &self,
+        scene: &mut Scene,
+        selection: &Selection,
+        scale: Vector3<f32>,
+        pivot: SnapPivot,
+        space: GizmoSpace,
+    ) {
         let graph = &mut scene.graph;
         if let Selection::Graph(selection) = selection {
-            if let Some((rotation, position)) = selection.global_rotation_position(graph) {
+            if let Some((rotation, mut position)) = selection.global_rotation_position(graph) {
+                if selection.is_single_selection() {
+                    position = pivot.world_position(graph, selection.nodes()[0]);
+                }
                 let node = &mut graph[self.origin];
                 node.set_visibility(true);
                 node.local_transform_mut()
-                    .set_rotation(rotation)
+                    .set_rotation(space.gizmo_rotation(rotation))
                     .set_position(position)
                     .set_scale(scale);
             }
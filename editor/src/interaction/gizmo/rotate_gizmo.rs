@@ -1,6 +1,6 @@
 use crate::{
-    make_color_material, scene::EditorScene, set_mesh_diffuse_color,
-    world::graph::selection::GraphSelection, Engine,
+    interaction::gizmo::GizmoSpace, make_color_material, scene::EditorScene,
+    set_mesh_diffuse_color, world::graph::selection::GraphSelection, Engine,
 };
 use fyrox::{
     core::{
@@ -223,12 +223,13 @@ impl RotationGizmo {
         graph: &mut Graph,
         selection: &GraphSelection,
         scale: Vector3<f32>,
+        space: GizmoSpace,
     ) {
         if let Some((rotation, position)) = selection.global_rotation_position(graph) {
             let node = &mut graph[self.origin];
             node.set_visibility(true);
             node.local_transform_mut()
-                .set_rotation(rotation)
+                .set_rotation(space.gizmo_rotation(rotation))
                 .set_position(position)
                 .set_scale(scale);
         }
@@ -0,0 +1,310 @@
+//! Measure mode allows you to measure distances between two points on scene geometry. It is an
+//! edit-only tool, it does not modify the scene.
+
+use crate::{
+    camera::PickingOptions, interaction::InteractionMode, scene::EditorScene, settings::Settings,
+    Engine,
+};
+use fyrox::{
+    core::{
+        algebra::{Vector2, Vector3},
+        color::Color,
+        pool::Handle,
+    },
+    gui::{
+        message::{KeyCode, MessageDirection},
+        text::TextMessage,
+        widget::WidgetMessage,
+        UiNode,
+    },
+    scene::{debug::Line, graph::Graph, node::Node},
+};
+
+/// A distance between two points in world space.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Measurement {
+    pub begin: Vector3<f32>,
+    pub end: Vector3<f32>,
+}
+
+impl Measurement {
+    /// Returns per-axis deltas between the points.
+    pub fn delta(&self) -> Vector3<f32> {
+        self.end - self.begin
+    }
+
+    /// Returns world-space distance between the points.
+    pub fn distance(&self) -> f32 {
+        self.delta().norm()
+    }
+
+    pub fn description(&self) -> String {
+        let delta = self.delta();
+        format!(
+            "Distance: {:.3}\nX: {:.3} Y: {:.3} Z: {:.3}",
+            self.distance(),
+            delta.x,
+            delta.y,
+            delta.z
+        )
+    }
+}
+
+/// Picks a point on scene geometry under the cursor.
+pub fn pick_point(
+    editor_scene: &mut EditorScene,
+    graph: &Graph,
+    mouse_pos: Vector2<f32>,
+    frame_size: Vector2<f32>,
+    ignore_back_faces: bool,
+) -> Option<Vector3<f32>> {
+    editor_scene
+        .camera_controller
+        .pick(PickingOptions {
+            cursor_pos: mouse_pos,
+            graph,
+            editor_objects_root: editor_scene.editor_objects_root,
+            scene_content_root: editor_scene.scene_content_root,
+            screen_size: frame_size,
+            editor_only: false,
+            filter: |_, _| true,
+            ignore_back_faces,
+            use_picking_loop: false,
+            only_meshes: true,
+        })
+        .map(|result| result.position)
+}
+
+pub struct MeasureInteractionMode {
+    display: Handle<UiNode>,
+    first_point: Option<Vector3<f32>>,
+    measurement: Option<Measurement>,
+}
+
+impl MeasureInteractionMode {
+    pub fn new(display: Handle<UiNode>) -> Self {
+        Self {
+            display,
+            first_point: None,
+            measurement: None,
+        }
+    }
+
+    /// Adds a new picked point. The first point starts a new measurement, the second one finishes it.
+    pub fn add_point(&mut self, point: Vector3<f32>) {
+        if let Some(begin) = self.first_point.take() {
+            self.measurement = Some(Measurement { begin, end: point });
+        } else {
+            self.first_point = Some(point);
+            self.measurement = None;
+        }
+    }
+
+    pub fn measurement(&self) -> Option<&Measurement> {
+        self.measurement.as_ref()
+    }
+
+    fn reset(&mut self, engine: &Engine) {
+        self.first_point = None;
+        self.measurement = None;
+        engine
+            .user_interface
+            .send_message(WidgetMessage::visibility(
+                self.display,
+                MessageDirection::ToWidget,
+                false,
+            ));
+    }
+}
+
+impl InteractionMode for MeasureInteractionMode {
+    fn on_left_mouse_button_down(
+        &mut self,
+        editor_scene: &mut EditorScene,
+        engine: &mut Engine,
+        mouse_pos: Vector2<f32>,
+        frame_size: Vector2<f32>,
+        settings: &Settings,
+    ) {
+        let graph = &engine.scenes[editor_scene.scene].graph;
+        if let Some(point) = pick_point(
+            editor_scene,
+            graph,
+            mouse_pos,
+            frame_size,
+            settings.selection.ignore_back_faces,
+        ) {
+            self.add_point(point);
+
+            let ui = &engine.user_interface;
+            if let Some(measurement) = self.measurement.as_ref() {
+                ui.send_message(TextMessage::text(
+                    self.display,
+                    MessageDirection::ToWidget,
+                    measurement.description(),
+                ));
+                ui.send_message(WidgetMessage::desired_position(
+                    self.display,
+                    MessageDirection::ToWidget,
+                    mouse_pos + Vector2::new(10.0, 10.0),
+                ));
+            }
+            ui.send_message(WidgetMessage::visibility(
+                self.display,
+                MessageDirection::ToWidget,
+                self.measurement.is_some(),
+            ));
+        }
+    }
+
+    fn on_left_mouse_button_up(
+        &mut self,
+        _editor_scene: &mut EditorScene,
+        _engine: &mut Engine,
+        _mouse_pos: Vector2<f32>,
+        _frame_size: Vector2<f32>,
+        _settings: &Settings,
+    ) {
+    }
+
+    fn on_mouse_move(
+        &mut self,
+        _mouse_offset: Vector2<f32>,
+        _mouse_position: Vector2<f32>,
+        _camera: Handle<Node>,
+        _editor_scene: &mut EditorScene,
+        _engine: &mut Engine,
+        _frame_size: Vector2<f32>,
+        _settings: &Settings,
+    ) {
+    }
+
+    fn update(
+        &mut self,
+        editor_scene: &mut EditorScene,
+        _camera: Handle<Node>,
+        engine: &mut Engine,
+        _settings: &Settings,
+    ) {
+        let ctx = &mut engine.scenes[editor_scene.scene].drawing_context;
+
+        if let Some(point) = self.first_point {
+            ctx.draw_sphere(point, 8, 8, 0.05, Color::ORANGE);
+        }
+
+        if let Some(measurement) = self.measurement.as_ref() {
+            ctx.draw_sphere(measurement.begin, 8, 8, 0.05, Color::ORANGE);
+            ctx.draw_sphere(measurement.end, 8, 8, 0.05, Color::ORANGE);
+            ctx.add_line(Line {
+                begin: measurement.begin,
+                end: measurement.end,
+                color: Color::ORANGE,
+            });
+        }
+    }
+
+    fn deactivate(&mut self, _editor_scene: &EditorScene, engine: &mut Engine) {
+        self.reset(engine);
+    }
+
+    fn on_key_down(
+        &mut self,
+        key: KeyCode,
+        _editor_scene: &mut EditorScene,
+        engine: &mut Engine,
+    ) -> bool {
+        if key == KeyCode::Escape {
+            self.reset(engine);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        camera::CameraController,
+        interaction::measure::{pick_point, MeasureInteractionMode},
+        scene::EditorScene,
+    };
+    use fyrox::{
+        core::{
+            algebra::{Matrix4, Vector2, Vector3},
+            pool::Handle,
+        },
+        scene::{
+            base::BaseBuilder,
+            mesh::{
+                surface::{SurfaceBuilder, SurfaceData, SurfaceSharedData},
+                MeshBuilder,
+            },
+            pivot::PivotBuilder,
+            transform::TransformBuilder,
+            Scene,
+        },
+    };
+
+    #[test]
+    fn test_measure_distance() {
+        let mut scene = Scene::new();
+        let scene_content_root = scene.graph.get_root();
+
+        for x in [-2.0, 2.0] {
+            MeshBuilder::new(
+                BaseBuilder::new().with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(x, 0.0, 0.0))
+                        .build(),
+                ),
+            )
+            .with_surfaces(vec![SurfaceBuilder::new(SurfaceSharedData::new(
+                SurfaceData::make_cube(Matrix4::identity()),
+            ))
+            .build()])
+            .build(&mut scene.graph);
+        }
+
+        let editor_objects_root = PivotBuilder::new(BaseBuilder::new()).build(&mut scene.graph);
+        let camera_controller = CameraController::new(&mut scene.graph, editor_objects_root, None);
+        scene.graph[camera_controller.pivot]
+            .local_transform_mut()
+            .set_position(Vector3::new(0.0, 0.0, -5.0));
+
+        let frame_size = Vector2::new(200.0, 200.0);
+        scene.graph.update_hierarchical_data();
+        scene.graph[camera_controller.camera]
+            .as_camera_mut()
+            .calculate_matrices(frame_size);
+
+        let camera = camera_controller.camera;
+        let mut editor_scene =
+            EditorScene::new_test_with(editor_objects_root, scene_content_root, camera_controller);
+
+        let mut mode = MeasureInteractionMode::new(Handle::NONE);
+        for center in [Vector3::new(-2.0, 0.0, 0.0), Vector3::new(2.0, 0.0, 0.0)] {
+            let cursor = scene.graph[camera]
+                .as_camera()
+                .project(center, frame_size)
+                .unwrap();
+            let point = pick_point(&mut editor_scene, &scene.graph, cursor, frame_size, false)
+                .expect("The cube must be picked!");
+            // The point must be on the front face of the cube.
+            assert!((point.z + 0.5).abs() < 0.001);
+            mode.add_point(point);
+        }
+
+        // Rays from the camera hit the front faces of the cubes at x = ±1.8.
+        let measurement = mode.measurement().unwrap();
+        assert!((measurement.distance() - 3.6).abs() < 0.001);
+        let delta = measurement.delta();
+        assert!((delta.x - 3.6).abs() < 0.001);
+        assert!(delta.y.abs() < 0.001);
+        assert!(delta.z.abs() < 0.001);
+
+        // The next point starts a new measurement.
+        mode.add_point(Vector3::default());
+        assert!(mode.measurement().is_none());
+    }
+}
@@ -1,4 +1,10 @@
-use crate::{scene::EditorScene, settings::Settings, Engine};
+use crate::{
+    camera::PickingOptions,
+    scene::{EditorScene, Selection},
+    settings::Settings,
+    world::graph::selection::GraphSelection,
+    Engine,
+};
 use fyrox::scene::camera::Projection;
 use fyrox::{
     core::{
@@ -11,12 +17,15 @@ use fyrox::{
 use std::any::Any;
 
 pub mod gizmo;
+pub mod measure;
 pub mod move_mode;
 pub mod navmesh;
+pub mod pivot;
 pub mod plane;
 pub mod rotate_mode;
 pub mod scale_mode;
 pub mod select_mode;
+pub mod spline;
 pub mod terrain;
 
 pub trait BaseInteractionMode {
@@ -133,6 +142,45 @@ fn distance_scale_factor(fov: f32) -> f32 {
     fov.tan() * 0.1
 }
 
+/// Picks a scene node under the cursor and returns a new selection. Colliders are picked first, then
+/// meshes and other objects, nodes locked in the editor are skipped. If `toggle` is set, the picked node
+/// is added to (or removed from) the current selection. Clicking on empty space produces an empty
+/// selection.
+pub fn pick_selection(
+    editor_scene: &mut EditorScene,
+    graph: &Graph,
+    mouse_pos: Vector2<f32>,
+    frame_size: Vector2<f32>,
+    ignore_back_faces: bool,
+    toggle: bool,
+) -> Selection {
+    let node_flags = &editor_scene.node_flags;
+    editor_scene
+        .camera_controller
+        .pick(PickingOptions {
+            cursor_pos: mouse_pos,
+            graph,
+            editor_objects_root: editor_scene.editor_objects_root,
+            scene_content_root: editor_scene.scene_content_root,
+            screen_size: frame_size,
+            editor_only: false,
+            filter: |handle, _| !node_flags.is_locked(handle),
+            ignore_back_faces,
+            use_picking_loop: true,
+            only_meshes: false,
+        })
+        .map(|result| {
+            if let (Selection::Graph(selection), true) = (&editor_scene.selection, toggle) {
+                let mut selection = selection.clone();
+                selection.insert_or_exclude(result.node);
+                Selection::Graph(selection)
+            } else {
+                Selection::Graph(GraphSelection::single_or_empty(result.node))
+            }
+        })
+        .unwrap_or_else(|| Selection::Graph(GraphSelection::default()))
+}
+
 /// Helper enum to be able to access interaction modes in array directly.
 #[derive(Copy, Clone, PartialOrd, PartialEq, Hash, Debug, Eq)]
 #[repr(usize)]
@@ -143,4 +191,192 @@ pub enum InteractionModeKind {
     Rotate = 3,
     Navmesh = 4,
     Terrain = 5,
+    Measure = 6,
+    Spline = 7,
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        camera::CameraController,
+        interaction::pick_selection,
+        scene::{EditorScene, Selection},
+        world::graph::selection::GraphSelection,
+    };
+    use fyrox::{
+        core::algebra::{Matrix4, Vector2, Vector3},
+        scene::{
+            base::BaseBuilder,
+            collider::{ColliderBuilder, ColliderShape},
+            graph::Graph,
+            mesh::{
+                surface::{SurfaceBuilder, SurfaceData, SurfaceSharedData},
+                MeshBuilder,
+            },
+            pivot::PivotBuilder,
+            transform::TransformBuilder,
+            Scene,
+        },
+    };
+
+    #[test]
+    fn test_click_selects_node() {
+        let mut scene = Scene::new();
+        let scene_content_root = scene.graph.get_root();
+
+        let mesh = MeshBuilder::new(BaseBuilder::new())
+            .with_surfaces(vec![SurfaceBuilder::new(SurfaceSharedData::new(
+                SurfaceData::make_cube(Matrix4::identity()),
+            ))
+            .build()])
+            .build(&mut scene.graph);
+
+        // A collider, that is off the line of sight for now.
+        let collider = ColliderBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(3.0, 0.0, 10.0))
+                    .build(),
+            ),
+        )
+        .with_shape(ColliderShape::cuboid(0.5, 0.5, 0.5))
+        .build(&mut scene.graph);
+
+        let editor_objects_root = PivotBuilder::new(BaseBuilder::new()).build(&mut scene.graph);
+        let camera_controller = CameraController::new(&mut scene.graph, editor_objects_root, None);
+        // Put the camera in front of the mesh, looking at it.
+        scene.graph[camera_controller.pivot]
+            .local_transform_mut()
+            .set_position(Vector3::new(0.0, 0.0, -5.0));
+
+        let frame_size = Vector2::new(200.0, 200.0);
+        scene.graph.update_hierarchical_data();
+        scene.graph[camera_controller.camera]
+            .as_camera_mut()
+            .calculate_matrices(frame_size);
+
+        let mut editor_scene =
+            EditorScene::new_test_with(editor_objects_root, scene_content_root, camera_controller);
+
+        let center = frame_size.scale(0.5);
+        let selection = pick_selection(
+            &mut editor_scene,
+            &scene.graph,
+            center,
+            frame_size,
+            false,
+            false,
+        );
+        assert_eq!(
+            selection,
+            Selection::Graph(GraphSelection::single_or_empty(mesh))
+        );
+        editor_scene.selection = selection;
+
+        // Clicking on empty space clears selection.
+        let selection = pick_selection(
+            &mut editor_scene,
+            &scene.graph,
+            Vector2::new(1.0, 1.0),
+            frame_size,
+            false,
+            false,
+        );
+        assert_eq!(selection, Selection::Graph(GraphSelection::default()));
+
+        // Colliders have priority over meshes.
+        scene.graph[collider]
+            .local_transform_mut()
+            .set_position(Vector3::new(0.0, 0.0, 10.0));
+        scene.graph.update_hierarchical_data();
+        let selection = pick_selection(
+            &mut editor_scene,
+            &scene.graph,
+            center,
+            frame_size,
+            false,
+            false,
+        );
+        assert_eq!(
+            selection,
+            Selection::Graph(GraphSelection::single_or_empty(collider))
+        );
+    }
+
+    #[test]
+    fn test_locked_node_is_not_picked() {
+        let mut scene = Scene::new();
+        let scene_content_root = scene.graph.get_root();
+
+        let make_cube = |z: f32, graph: &mut Graph| {
+            MeshBuilder::new(
+                BaseBuilder::new().with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, 0.0, z))
+                        .build(),
+                ),
+            )
+            .with_surfaces(vec![SurfaceBuilder::new(SurfaceSharedData::new(
+                SurfaceData::make_cube(Matrix4::identity()),
+            ))
+            .build()])
+            .build(graph)
+        };
+        let near = make_cube(0.0, &mut scene.graph);
+        let far = make_cube(5.0, &mut scene.graph);
+
+        let editor_objects_root = PivotBuilder::new(BaseBuilder::new()).build(&mut scene.graph);
+        let camera_controller = CameraController::new(&mut scene.graph, editor_objects_root, None);
+        scene.graph[camera_controller.pivot]
+            .local_transform_mut()
+            .set_position(Vector3::new(0.0, 0.0, -5.0));
+
+        let frame_size = Vector2::new(200.0, 200.0);
+        scene.graph.update_hierarchical_data();
+        scene.graph[camera_controller.camera]
+            .as_camera_mut()
+            .calculate_matrices(frame_size);
+
+        let mut editor_scene =
+            EditorScene::new_test_with(editor_objects_root, scene_content_root, camera_controller);
+
+        let center = frame_size.scale(0.5);
+        let pick = |editor_scene: &mut EditorScene, graph: &Graph| {
+            pick_selection(editor_scene, graph, center, frame_size, false, false)
+        };
+
+        assert_eq!(
+            pick(&mut editor_scene, &scene.graph),
+            Selection::Graph(GraphSelection::single_or_empty(near))
+        );
+
+        // Locked node is skipped, the one behind it is picked instead.
+        editor_scene.node_flags.set_locked(near, true);
+        assert_eq!(
+            pick(&mut editor_scene, &scene.graph),
+            Selection::Graph(GraphSelection::single_or_empty(far))
+        );
+
+        // Hidden nodes cannot be picked too.
+        editor_scene
+            .node_flags
+            .set_hidden(&mut scene.graph, far, true);
+        scene.graph.update_hierarchical_data();
+        // Hiding is editor-only, visibility of the node is left as is.
+        assert!(scene.graph[far].visibility());
+        assert_eq!(
+            pick(&mut editor_scene, &scene.graph),
+            Selection::Graph(GraphSelection::default())
+        );
+
+        editor_scene.node_flags.set_locked(near, false);
+        editor_scene
+            .node_flags
+            .set_hidden(&mut scene.graph, far, false);
+        scene.graph.update_hierarchical_data();
+        assert_eq!(
+            pick(&mut editor_scene, &scene.graph),
+            Selection::Graph(GraphSelection::single_or_empty(near))
+        );
+    }
 }
@@ -2,8 +2,8 @@ use crate::message::MessageSender;
 use crate::{
     camera::{CameraController, PickingOptions},
     interaction::{
-        calculate_gizmo_distance_scaling, gizmo::move_gizmo::MoveGizmo, plane::PlaneKind,
-        InteractionMode,
+        calculate_gizmo_distance_scaling, gizmo::move_gizmo::MoveGizmo, pick_selection,
+        plane::PlaneKind, InteractionMode,
     },
     scene::{
         commands::{graph::MoveNodeCommand, ChangeSelectionCommand, CommandGroup, SceneCommand},
@@ -363,33 +363,14 @@ impl InteractionMode for MoveInteractionMode {
                     .send(Message::DoSceneCommand(SceneCommand::new(commands)));
             }
         } else {
-            let new_selection = editor_scene
-                .camera_controller
-                .pick(PickingOptions {
-                    cursor_pos: mouse_pos,
-                    graph: &scene.graph,
-                    editor_objects_root: editor_scene.editor_objects_root,
-                    scene_content_root: editor_scene.scene_content_root,
-                    screen_size: frame_size,
-                    editor_only: false,
-                    filter: |_, _| true,
-                    ignore_back_faces: settings.selection.ignore_back_faces,
-                    use_picking_loop: true,
-                    only_meshes: false,
-                })
-                .map(|result| {
-                    if let (Selection::Graph(selection), true) = (
-                        &editor_scene.selection,
-                        engine.user_interface.keyboard_modifiers().control,
-                    ) {
-                        let mut selection = selection.clone();
-                        selection.insert_or_exclude(result.node);
-                        Selection::Graph(selection)
-                    } else {
-                        Selection::Graph(GraphSelection::single_or_empty(result.node))
-                    }
-                })
-                .unwrap_or_else(|| Selection::Graph(GraphSelection::default()));
+            let new_selection = pick_selection(
+                editor_scene,
+                &scene.graph,
+                mouse_pos,
+                frame_size,
+                settings.selection.ignore_back_faces,
+                engine.user_interface.keyboard_modifiers().control,
+            );
 
             if new_selection != editor_scene.selection {
                 self.message_sender
@@ -430,7 +411,7 @@ impl InteractionMode for MoveInteractionMode {
         editor_scene: &mut EditorScene,
         camera: Handle<Node>,
         engine: &mut Engine,
-        _settings: &Settings,
+        settings: &Settings,
     ) {
         let scene = &mut engine.scenes[editor_scene.scene];
         let graph = &mut scene.graph;
@@ -439,8 +420,13 @@ impl InteractionMode for MoveInteractionMode {
         } else {
             let scale = calculate_gizmo_distance_scaling(graph, camera, self.move_gizmo.origin);
             self.move_gizmo.set_visible(graph, true);
-            self.move_gizmo
-                .sync_transform(scene, &editor_scene.selection, scale);
+            self.move_gizmo.sync_transform(
+                scene,
+                &editor_scene.selection,
+                scale,
+                settings.move_mode_settings.pivot,
+                settings.gizmo_space,
+            );
         }
     }
 
@@ -449,3 +435,96 @@ impl InteractionMode for MoveInteractionMode {
         self.move_gizmo.set_visible(graph, false);
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        camera::CameraController,
+        interaction::{
+            gizmo::{move_gizmo::MoveGizmo, GizmoSpace},
+            move_mode::MoveContext,
+            pivot::SnapPivot,
+            plane::PlaneKind,
+        },
+        scene::Selection,
+        settings::Settings,
+        world::graph::selection::GraphSelection,
+    };
+    use fyrox::{
+        core::algebra::{UnitQuaternion, Vector2, Vector3},
+        scene::{base::BaseBuilder, pivot::PivotBuilder, transform::TransformBuilder, Scene},
+    };
+
+    // Drags X axis of the move gizmo of a node, that is rotated by 90 degrees around Z axis, so its
+    // local X axis matches world Y axis. Returns resulting offset of the node.
+    fn drag_x_axis_of_rotated_node(space: GizmoSpace) -> Vector3<f32> {
+        let mut scene = Scene::new();
+        let node = PivotBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_rotation(UnitQuaternion::from_axis_angle(
+                        &Vector3::z_axis(),
+                        90.0f32.to_radians(),
+                    ))
+                    .build(),
+            ),
+        )
+        .build(&mut scene.graph);
+
+        let editor_objects_root = PivotBuilder::new(BaseBuilder::new()).build(&mut scene.graph);
+        let camera_controller = CameraController::new(&mut scene.graph, editor_objects_root, None);
+        scene.graph[camera_controller.pivot]
+            .local_transform_mut()
+            .set_position(Vector3::new(0.0, 0.0, -5.0));
+        let move_gizmo = MoveGizmo::new_in_graph(&mut scene.graph, editor_objects_root);
+
+        let selection = GraphSelection::single_or_empty(node);
+        move_gizmo.sync_transform(
+            &mut scene,
+            &Selection::Graph(selection.clone()),
+            Vector3::new(1.0, 1.0, 1.0),
+            SnapPivot::Origin,
+            space,
+        );
+
+        let frame_size = Vector2::new(200.0, 200.0);
+        scene.graph.update_hierarchical_data();
+        scene.graph[camera_controller.camera]
+            .as_camera_mut()
+            .calculate_matrices(frame_size);
+
+        let mouse_position = Vector2::new(100.0, 100.0);
+        let mut context = MoveContext::from_graph_selection(
+            &selection,
+            &scene,
+            &move_gizmo,
+            &camera_controller,
+            PlaneKind::X,
+            mouse_position,
+            frame_size,
+        );
+        context.update_plane_move(
+            &scene.graph,
+            &camera_controller,
+            &Settings::default(),
+            mouse_position + Vector2::new(20.0, -20.0),
+            frame_size,
+        );
+
+        let entry = &context.objects[0];
+        entry.new_local_position - entry.initial_local_position
+    }
+
+    #[test]
+    fn test_local_space_drag_follows_local_axis() {
+        let offset = drag_x_axis_of_rotated_node(GizmoSpace::Local);
+        assert!(offset.y.abs() > 0.01);
+        assert!(offset.x.abs() < 0.001);
+        assert!(offset.z.abs() < 0.001);
+
+        let offset = drag_x_axis_of_rotated_node(GizmoSpace::World);
+        assert!(offset.x.abs() > 0.01);
+        assert!(offset.y.abs() < 0.001);
+        assert!(offset.z.abs() < 0.001);
+    }
+}
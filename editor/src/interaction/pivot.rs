@@ -0,0 +1,154 @@
+use fyrox::{
+    core::{
+        algebra::{Point3, Vector3},
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
+        reflect::prelude::*,
+    },
+    scene::{graph::Graph, node::Node},
+};
+use serde::{Deserialize, Serialize};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Defines a point of a node, that is used for snapping and as a location of the move gizmo.
+#[derive(
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Debug,
+    Serialize,
+    Deserialize,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum SnapPivot {
+    /// Origin of the node.
+    Origin,
+    /// Center of the bounding box of the node and its descendants.
+    BoundsCenter,
+    /// Center of the bottom side of the bounding box of the node and its descendants. Useful for
+    /// characters, that should stand on a surface by their feet.
+    BoundsBottom,
+}
+
+impl Default for SnapPivot {
+    fn default() -> Self {
+        Self::Origin
+    }
+}
+
+/// Calculates world-space bounding box of the node and all its descendants. Nodes without geometry
+/// are ignored, `None` is returned if there's no geometry at all.
+pub fn hierarchy_world_bounds(graph: &Graph, root: Handle<Node>) -> Option<AxisAlignedBoundingBox> {
+    let mut bounds = AxisAlignedBoundingBox::default();
+    let mut has_geometry = false;
+    for node in graph.traverse_iter(root) {
+        let local_bounds = node.local_bounding_box();
+        if !local_bounds.is_invalid_or_degenerate() {
+            bounds.add_box(local_bounds.transform(&node.global_transform()));
+            has_geometry = true;
+        }
+    }
+    if has_geometry {
+        Some(bounds)
+    } else {
+        None
+    }
+}
+
+impl SnapPivot {
+    /// Returns world-space position of the pivot of the given node. If the node has no geometry, its
+    /// origin is used.
+    pub fn world_position(self, graph: &Graph, node: Handle<Node>) -> Vector3<f32> {
+        let origin = graph[node].global_position();
+        let bounds = match self {
+            SnapPivot::Origin => return origin,
+            SnapPivot::BoundsCenter | SnapPivot::BoundsBottom => {
+                match hierarchy_world_bounds(graph, node) {
+                    Some(bounds) => bounds,
+                    None => return origin,
+                }
+            }
+        };
+
+        let center = bounds.center();
+        if self == SnapPivot::BoundsBottom {
+            Vector3::new(center.x, bounds.min.y, center.z)
+        } else {
+            center
+        }
+    }
+}
+
+/// Moves the node so its pivot will be at the given world-space point (usually a point on some surface).
+/// Global transforms of the hierarchy must be up-to-date.
+pub fn snap_to_point(graph: &mut Graph, node: Handle<Node>, point: Vector3<f32>, pivot: SnapPivot) {
+    let origin = graph[node].global_position();
+    let offset = pivot.world_position(graph, node) - origin;
+    let target = point - offset;
+
+    let parent = graph[node].parent();
+    let local_target = graph
+        .try_get(parent)
+        .and_then(|parent| parent.global_transform().try_inverse())
+        .map_or(target, |inv| {
+            inv.transform_point(&Point3::from(target)).coords
+        });
+
+    graph[node].local_transform_mut().set_position(local_target);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::interaction::pivot::{snap_to_point, SnapPivot};
+    use fyrox::{
+        core::algebra::{Matrix4, Vector3},
+        scene::{
+            base::BaseBuilder,
+            graph::Graph,
+            mesh::{
+                surface::{SurfaceBuilder, SurfaceData, SurfaceSharedData},
+                MeshBuilder,
+            },
+            pivot::PivotBuilder,
+            transform::TransformBuilder,
+        },
+    };
+
+    #[test]
+    fn test_snap_with_bottom_pivot() {
+        let mut graph = Graph::new();
+
+        // A unit cube, that is lifted by 2 meters relative to the origin of its parent - just like a
+        // model, which origin is not at its feet.
+        let cube = MeshBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(0.0, 2.0, 0.0))
+                    .build(),
+            ),
+        )
+        .with_surfaces(vec![SurfaceBuilder::new(SurfaceSharedData::new(
+            SurfaceData::make_cube(Matrix4::identity()),
+        ))
+        .build()])
+        .build(&mut graph);
+        let model = PivotBuilder::new(BaseBuilder::new().with_children(&[cube])).build(&mut graph);
+
+        graph.update_hierarchical_data();
+
+        let surface_point = Vector3::new(3.0, 1.0, -4.0);
+        snap_to_point(&mut graph, model, surface_point, SnapPivot::BoundsBottom);
+        graph.update_hierarchical_data();
+
+        let bottom = SnapPivot::BoundsBottom.world_position(&graph, model);
+        assert!((bottom - surface_point).norm() < 0.0001);
+
+        // The bottom of the cube must touch the surface.
+        let cube_bottom = graph[cube].global_position().y - 0.5;
+        assert!((cube_bottom - surface_point.y).abs() < 0.0001);
+    }
+}
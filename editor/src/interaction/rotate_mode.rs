@@ -1,7 +1,9 @@
 use crate::{
     camera::PickingOptions,
     interaction::{
-        calculate_gizmo_distance_scaling, gizmo::rotate_gizmo::RotationGizmo, InteractionMode,
+        calculate_gizmo_distance_scaling,
+        gizmo::{rotate_gizmo::RotationGizmo, GizmoSpace},
+        pick_selection, InteractionMode,
     },
     message::MessageSender,
     scene::{
@@ -9,13 +11,11 @@ use crate::{
         EditorScene, Selection,
     },
     settings::Settings,
-    world::graph::selection::GraphSelection,
     Engine,
 };
 use fyrox::{
     core::{
         algebra::{UnitQuaternion, Vector2},
-        math::round_to_step,
         pool::Handle,
     },
     scene::node::Node,
@@ -23,6 +23,9 @@ use fyrox::{
 
 pub struct RotateInteractionMode {
     initial_rotations: Vec<UnitQuaternion<f32>>,
+    // Total rotation since the beginning of a drag. Snapping is applied to it, not to the rotation
+    // deltas of each mouse move, otherwise small deltas will be rounded to zero.
+    accumulated_rotation: UnitQuaternion<f32>,
     rotation_gizmo: RotationGizmo,
     interacting: bool,
     message_sender: MessageSender,
@@ -36,6 +39,7 @@ impl RotateInteractionMode {
     ) -> Self {
         Self {
             initial_rotations: Default::default(),
+            accumulated_rotation: Default::default(),
             rotation_gizmo: RotationGizmo::new(editor_scene, engine),
             interacting: false,
             message_sender,
@@ -79,6 +83,7 @@ impl InteractionMode for RotateInteractionMode {
                 if let Selection::Graph(selection) = &editor_scene.selection {
                     self.interacting = true;
                     self.initial_rotations = selection.local_rotations(graph);
+                    self.accumulated_rotation = UnitQuaternion::identity();
                 }
             }
         }
@@ -122,33 +127,14 @@ impl InteractionMode for RotateInteractionMode {
                 }
             }
         } else {
-            let new_selection = editor_scene
-                .camera_controller
-                .pick(PickingOptions {
-                    cursor_pos: mouse_pos,
-                    graph,
-                    editor_objects_root: editor_scene.editor_objects_root,
-                    scene_content_root: editor_scene.scene_content_root,
-                    screen_size: frame_size,
-                    editor_only: false,
-                    filter: |_, _| true,
-                    ignore_back_faces: settings.selection.ignore_back_faces,
-                    use_picking_loop: true,
-                    only_meshes: false,
-                })
-                .map(|result| {
-                    if let (Selection::Graph(selection), true) = (
-                        &editor_scene.selection,
-                        engine.user_interface.keyboard_modifiers().control,
-                    ) {
-                        let mut selection = selection.clone();
-                        selection.insert_or_exclude(result.node);
-                        Selection::Graph(selection)
-                    } else {
-                        Selection::Graph(GraphSelection::single_or_empty(result.node))
-                    }
-                })
-                .unwrap_or_else(|| Selection::Graph(GraphSelection::default()));
+            let new_selection = pick_selection(
+                editor_scene,
+                graph,
+                mouse_pos,
+                frame_size,
+                settings.selection.ignore_back_faces,
+                engine.user_interface.keyboard_modifiers().control,
+            );
 
             if new_selection != editor_scene.selection {
                 self.message_sender
@@ -180,27 +166,43 @@ impl InteractionMode for RotateInteractionMode {
                     engine,
                     frame_size,
                 );
-                for &node in selection.nodes().iter() {
-                    let transform =
-                        engine.scenes[editor_scene.scene].graph[node].local_transform_mut();
-                    let rotation = **transform.rotation();
-                    let final_rotation = rotation * rotation_delta;
-                    let (mut roll, mut pitch, mut yaw) = final_rotation.euler_angles();
-                    if settings.rotate_mode_settings.angle_snapping {
-                        pitch = round_to_step(
-                            pitch,
-                            settings.rotate_mode_settings.x_snap_step.to_radians(),
-                        );
-                        yaw = round_to_step(
-                            yaw,
-                            settings.rotate_mode_settings.y_snap_step.to_radians(),
-                        );
-                        roll = round_to_step(
-                            roll,
-                            settings.rotate_mode_settings.z_snap_step.to_radians(),
-                        );
-                    }
-                    transform.set_rotation(UnitQuaternion::from_euler_angles(roll, pitch, yaw));
+                self.accumulated_rotation = rotation_delta * self.accumulated_rotation;
+
+                let rotation_delta = if settings.rotate_mode_settings.angle_snapping
+                    || engine.user_interface.keyboard_modifiers().control
+                {
+                    settings
+                        .rotate_mode_settings
+                        .snap_rotation(self.accumulated_rotation)
+                } else {
+                    self.accumulated_rotation
+                };
+
+                let graph = &mut engine.scenes[editor_scene.scene].graph;
+                for (&node, &initial_rotation) in
+                    selection.nodes().iter().zip(self.initial_rotations.iter())
+                {
+                    // In world space the delta is defined around world axes, so it must be applied
+                    // in parent space of the node.
+                    let world_delta = match settings.gizmo_space {
+                        GizmoSpace::Local => None,
+                        GizmoSpace::World => {
+                            let parent = graph[node].parent();
+                            let parent_rotation = if parent.is_some() {
+                                graph.global_rotation(parent)
+                            } else {
+                                UnitQuaternion::identity()
+                            };
+                            Some(parent_rotation.inverse() * rotation_delta * parent_rotation)
+                        }
+                    };
+                    let final_rotation = match world_delta {
+                        Some(world_delta) => world_delta * initial_rotation,
+                        None => initial_rotation * rotation_delta,
+                    };
+                    graph[node]
+                        .local_transform_mut()
+                        .set_rotation(final_rotation);
                 }
             }
         }
@@ -211,7 +213,7 @@ impl InteractionMode for RotateInteractionMode {
         editor_scene: &mut EditorScene,
         camera: Handle<Node>,
         engine: &mut Engine,
-        _settings: &Settings,
+        settings: &Settings,
     ) {
         if let Selection::Graph(selection) = &editor_scene.selection {
             let graph = &mut engine.scenes[editor_scene.scene].graph;
@@ -220,7 +222,8 @@ impl InteractionMode for RotateInteractionMode {
             } else {
                 let scale =
                     calculate_gizmo_distance_scaling(graph, camera, self.rotation_gizmo.origin);
-                self.rotation_gizmo.sync_transform(graph, selection, scale);
+                self.rotation_gizmo
+                    .sync_transform(graph, selection, scale, settings.gizmo_space);
                 self.rotation_gizmo.set_visible(graph, true);
             }
         }
@@ -1,7 +1,8 @@
 use crate::{
     camera::PickingOptions,
     interaction::{
-        calculate_gizmo_distance_scaling, gizmo::scale_gizmo::ScaleGizmo, InteractionMode,
+        calculate_gizmo_distance_scaling, gizmo::scale_gizmo::ScaleGizmo, pick_selection,
+        InteractionMode,
     },
     message::MessageSender,
     scene::{
@@ -9,7 +10,6 @@ use crate::{
         EditorScene, Selection,
     },
     settings::Settings,
-    world::graph::selection::GraphSelection,
     Engine,
 };
 use fyrox::{
@@ -117,33 +117,14 @@ impl InteractionMode for ScaleInteractionMode {
                 }
             }
         } else {
-            let new_selection = editor_scene
-                .camera_controller
-                .pick(PickingOptions {
-                    cursor_pos: mouse_pos,
-                    graph,
-                    editor_objects_root: editor_scene.editor_objects_root,
-                    scene_content_root: editor_scene.scene_content_root,
-                    screen_size: frame_size,
-                    editor_only: false,
-                    filter: |_, _| true,
-                    ignore_back_faces: settings.selection.ignore_back_faces,
-                    use_picking_loop: true,
-                    only_meshes: false,
-                })
-                .map(|result| {
-                    if let (Selection::Graph(selection), true) = (
-                        &editor_scene.selection,
-                        engine.user_interface.keyboard_modifiers().control,
-                    ) {
-                        let mut selection = selection.clone();
-                        selection.insert_or_exclude(result.node);
-                        Selection::Graph(selection)
-                    } else {
-                        Selection::Graph(GraphSelection::single_or_empty(result.node))
-                    }
-                })
-                .unwrap_or_else(|| Selection::Graph(GraphSelection::default()));
+            let new_selection = pick_selection(
+                editor_scene,
+                graph,
+                mouse_pos,
+                frame_size,
+                settings.selection.ignore_back_faces,
+                engine.user_interface.keyboard_modifiers().control,
+            );
 
             if new_selection != editor_scene.selection {
                 self.message_sender
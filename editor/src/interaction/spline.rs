@@ -0,0 +1,309 @@
+use crate::{
+    camera::PickingOptions,
+    interaction::{
+        calculate_gizmo_distance_scaling, gizmo::move_gizmo::MoveGizmo, plane::PlaneKind,
+        InteractionMode,
+    },
+    message::MessageSender,
+    scene::{commands::spline::SetSplineControlPointsCommand, EditorScene, Selection},
+    settings::Settings,
+};
+use fyrox::{
+    core::{
+        algebra::{Matrix4, Vector2, Vector3},
+        color::Color,
+        pool::Handle,
+    },
+    engine::Engine,
+    gui::message::KeyCode,
+    scene::{camera::Camera, graph::Graph, node::Node, spline::Spline},
+};
+
+/// Radius of a control point handle in world units.
+const POINT_RADIUS: f32 = 0.1;
+
+struct DragContext {
+    plane_kind: PlaneKind,
+    initial_points: Vec<Vector3<f32>>,
+}
+
+/// Allows to edit control points of a spline directly in the scene viewer. A control point could be
+/// selected by clicking on it and then moved using the move gizmo. Insert key adds a new point right after
+/// the selected one, Delete key removes the selected point.
+pub struct EditSplineMode {
+    move_gizmo: MoveGizmo,
+    message_sender: MessageSender,
+    selected_point: Option<usize>,
+    drag_context: Option<DragContext>,
+}
+
+impl EditSplineMode {
+    pub fn new(
+        editor_scene: &EditorScene,
+        engine: &mut Engine,
+        message_sender: MessageSender,
+    ) -> Self {
+        Self {
+            move_gizmo: MoveGizmo::new(editor_scene, engine),
+            message_sender,
+            selected_point: None,
+            drag_context: None,
+        }
+    }
+
+    fn spline_node(editor_scene: &EditorScene, graph: &Graph) -> Handle<Node> {
+        if let Selection::Graph(ref selection) = editor_scene.selection {
+            if let Some(first) = selection.nodes.first() {
+                if graph.try_get_of_type::<Spline>(*first).is_some() {
+                    return *first;
+                }
+            }
+        }
+        Handle::NONE
+    }
+
+    fn commit_points(
+        &self,
+        spline: Handle<Node>,
+        old_points: Vec<Vector3<f32>>,
+        new_points: Vec<Vector3<f32>>,
+    ) {
+        if old_points != new_points {
+            self.message_sender
+                .do_scene_command(SetSplineControlPointsCommand::new(
+                    spline, old_points, new_points,
+                ));
+        }
+    }
+}
+
+impl InteractionMode for EditSplineMode {
+    fn on_left_mouse_button_down(
+        &mut self,
+        editor_scene: &mut EditorScene,
+        engine: &mut Engine,
+        mouse_pos: Vector2<f32>,
+        frame_size: Vector2<f32>,
+        settings: &Settings,
+    ) {
+        let graph = &mut engine.scenes[editor_scene.scene].graph;
+
+        let spline_handle = Self::spline_node(editor_scene, graph);
+        if spline_handle.is_none() {
+            return;
+        }
+
+        let camera = editor_scene.camera_controller.camera;
+        let camera_pivot = editor_scene.camera_controller.pivot;
+        let gizmo_origin = self.move_gizmo.origin;
+        let editor_node = editor_scene
+            .camera_controller
+            .pick(PickingOptions {
+                cursor_pos: mouse_pos,
+                graph,
+                editor_objects_root: editor_scene.editor_objects_root,
+                scene_content_root: editor_scene.scene_content_root,
+                screen_size: frame_size,
+                editor_only: true,
+                filter: |handle, _| {
+                    handle != camera && handle != camera_pivot && handle != gizmo_origin
+                },
+                ignore_back_faces: settings.selection.ignore_back_faces,
+                use_picking_loop: true,
+                only_meshes: false,
+            })
+            .map(|r| r.node)
+            .unwrap_or_default();
+
+        if let Some(plane_kind) = self.move_gizmo.handle_pick(editor_node, graph) {
+            if self.selected_point.is_some() {
+                let spline = graph[spline_handle].cast::<Spline>().unwrap();
+                self.drag_context = Some(DragContext {
+                    plane_kind,
+                    initial_points: spline.control_points().to_vec(),
+                });
+            }
+        } else {
+            let ray = graph[camera]
+                .cast::<Camera>()
+                .unwrap()
+                .make_ray(mouse_pos, frame_size);
+            let spline = graph[spline_handle].cast::<Spline>().unwrap();
+            let transform = spline.global_transform();
+            self.selected_point = spline.control_points().iter().position(|point| {
+                ray.sphere_intersection(
+                    &transform.transform_point(&(*point).into()).coords,
+                    POINT_RADIUS,
+                )
+                .is_some()
+            });
+        }
+    }
+
+    fn on_left_mouse_button_up(
+        &mut self,
+        editor_scene: &mut EditorScene,
+        engine: &mut Engine,
+        _mouse_pos: Vector2<f32>,
+        _frame_size: Vector2<f32>,
+        _settings: &Settings,
+    ) {
+        let graph = &mut engine.scenes[editor_scene.scene].graph;
+
+        self.move_gizmo.reset_state(graph);
+
+        if let Some(drag_context) = self.drag_context.take() {
+            let spline_handle = Self::spline_node(editor_scene, graph);
+            if let Some(spline) = graph.try_get_of_type::<Spline>(spline_handle) {
+                let new_points = spline.control_points().to_vec();
+                self.commit_points(spline_handle, drag_context.initial_points, new_points);
+            }
+        }
+    }
+
+    fn on_mouse_move(
+        &mut self,
+        mouse_offset: Vector2<f32>,
+        mouse_position: Vector2<f32>,
+        camera: Handle<Node>,
+        editor_scene: &mut EditorScene,
+        engine: &mut Engine,
+        frame_size: Vector2<f32>,
+        _settings: &Settings,
+    ) {
+        if let (Some(drag_context), Some(selected_point)) =
+            (self.drag_context.as_ref(), self.selected_point)
+        {
+            let offset = self.move_gizmo.calculate_offset(
+                editor_scene,
+                camera,
+                mouse_offset,
+                mouse_position,
+                engine,
+                frame_size,
+                drag_context.plane_kind,
+            );
+
+            let graph = &mut engine.scenes[editor_scene.scene].graph;
+            let spline_handle = Self::spline_node(editor_scene, graph);
+            if let Some(spline) = graph.try_get_mut_of_type::<Spline>(spline_handle) {
+                // Gizmo works in world space, but control points are defined in local space of the node.
+                let local_offset = spline
+                    .global_transform()
+                    .try_inverse()
+                    .unwrap_or_else(Matrix4::identity)
+                    .transform_vector(&offset);
+                let mut points = spline.control_points().to_vec();
+                if let Some(point) = points.get_mut(selected_point) {
+                    *point += local_offset;
+                    spline.set_control_points(points);
+                }
+            }
+        }
+    }
+
+    fn update(
+        &mut self,
+        editor_scene: &mut EditorScene,
+        camera: Handle<Node>,
+        engine: &mut Engine,
+        _settings: &Settings,
+    ) {
+        let scene = &mut engine.scenes[editor_scene.scene];
+        self.move_gizmo.set_visible(&mut scene.graph, false);
+
+        let spline_handle = Self::spline_node(editor_scene, &scene.graph);
+        let spline = match scene.graph.try_get_of_type::<Spline>(spline_handle) {
+            Some(spline) => spline,
+            None => {
+                self.selected_point = None;
+                return;
+            }
+        };
+
+        if self
+            .selected_point
+            .map_or(false, |i| i >= spline.control_points().len())
+        {
+            self.selected_point = None;
+        }
+
+        let transform = spline.global_transform();
+        let mut gizmo_position = None;
+        for (index, point) in spline.control_points().iter().enumerate() {
+            let position = transform.transform_point(&(*point).into()).coords;
+            let color = if Some(index) == self.selected_point {
+                gizmo_position = Some(position);
+                Color::RED
+            } else {
+                Color::GREEN
+            };
+            scene
+                .drawing_context
+                .draw_sphere(position, 10, 10, POINT_RADIUS, color);
+        }
+
+        if let Some(gizmo_position) = gizmo_position {
+            let scale =
+                calculate_gizmo_distance_scaling(&scene.graph, camera, self.move_gizmo.origin);
+            self.move_gizmo.set_visible(&mut scene.graph, true);
+            self.move_gizmo
+                .transform(&mut scene.graph)
+                .set_scale(scale)
+                .set_position(gizmo_position);
+        }
+    }
+
+    fn deactivate(&mut self, editor_scene: &EditorScene, engine: &mut Engine) {
+        let scene = &mut engine.scenes[editor_scene.scene];
+        self.move_gizmo.set_visible(&mut scene.graph, false);
+        self.drag_context = None;
+    }
+
+    fn on_key_down(
+        &mut self,
+        key: KeyCode,
+        editor_scene: &mut EditorScene,
+        engine: &mut Engine,
+    ) -> bool {
+        let graph = &engine.scenes[editor_scene.scene].graph;
+        let spline_handle = Self::spline_node(editor_scene, graph);
+        let spline = match graph.try_get_of_type::<Spline>(spline_handle) {
+            Some(spline) => spline,
+            None => return false,
+        };
+
+        let old_points = spline.control_points().to_vec();
+        let mut new_points = old_points.clone();
+
+        match key {
+            KeyCode::Insert => {
+                let index = self
+                    .selected_point
+                    .unwrap_or_else(|| new_points.len().saturating_sub(1));
+                let new_point = match (new_points.get(index), new_points.get(index + 1)) {
+                    (Some(current), Some(next)) => (current + next).scale(0.5),
+                    (Some(current), None) => match index.checked_sub(1) {
+                        Some(prev) => current + (current - new_points[prev]),
+                        None => current + Vector3::x(),
+                    },
+                    _ => Vector3::default(),
+                };
+                let new_index = (index + 1).min(new_points.len());
+                new_points.insert(new_index, new_point);
+                self.selected_point = Some(new_index);
+            }
+            KeyCode::Delete => match self.selected_point.take() {
+                Some(index) if index < new_points.len() => {
+                    new_points.remove(index);
+                }
+                _ => return false,
+            },
+            _ => return false,
+        }
+
+        self.commit_points(spline_handle, old_points, new_points);
+
+        true
+    }
+}
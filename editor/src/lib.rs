@@ -20,6 +20,7 @@ mod command;
 mod configurator;
 mod curve_editor;
 mod gui;
+mod highlight;
 mod inspector;
 mod interaction;
 mod light;
@@ -30,6 +31,7 @@ mod message;
 mod overlay;
 mod particle;
 mod preview;
+mod problems;
 mod scene;
 mod scene_viewer;
 mod settings;
@@ -42,17 +44,27 @@ use crate::{
     asset::{item::AssetItem, item::AssetKind, AssetBrowser},
     audio::{preview::AudioPreviewPanel, AudioPanel},
     build::BuildWindow,
-    camera::panel::CameraPreviewControlPanel,
-    command::{panel::CommandStackViewer, Command, CommandStack},
+    camera::{
+        bookmark::{bookmark_index_from_hotkey, CameraBookmarksPanel},
+        panel::CameraPreviewControlPanel,
+    },
+    command::{
+        journal::{Journal, JournalEntry},
+        panel::CommandStackViewer,
+        Command, CommandStack,
+    },
     configurator::Configurator,
     curve_editor::CurveEditorWindow,
+    highlight::HighlightRenderPass,
     inspector::{editors::handle::HandlePropertyEditorMessage, Inspector},
     interaction::{
+        measure::MeasureInteractionMode,
         move_mode::MoveInteractionMode,
         navmesh::{EditNavmeshMode, NavmeshPanel},
         rotate_mode::RotateInteractionMode,
         scale_mode::ScaleInteractionMode,
         select_mode::SelectInteractionMode,
+        spline::EditSplineMode,
         terrain::TerrainInteractionMode,
         InteractionMode, InteractionModeKind,
     },
@@ -63,12 +75,14 @@ use crate::{
     message::MessageSender,
     overlay::OverlayRenderPass,
     particle::ParticleSystemPreviewControlPanel,
+    problems::ProblemsPanel,
     scene::{
         commands::{
-            graph::AddModelCommand, make_delete_selection_command, mesh::SetMeshTextureCommand,
-            ChangeSelectionCommand, CommandGroup, PasteCommand, SceneCommand, SceneContext,
+            graph::AddModelCommand, make_delete_selection_command, make_instantiate_models_command,
+            mesh::SetMeshTextureCommand, ChangeSelectionCommand, CommandGroup, PasteCommand,
+            SceneCommand, SceneContext,
         },
-        is_scene_needs_to_be_saved,
+        is_scene_needs_to_be_saved, is_scene_revertible,
         settings::SceneSettingsWindow,
         EditorScene, Selection,
     },
@@ -87,7 +101,6 @@ use fyrox::{
         pool::{ErasedHandle, Handle},
         scope_profile,
         sstorage::ImmutableString,
-        visitor::Visitor,
         watcher::FileSystemWatcher,
     },
     dpi::{PhysicalPosition, PhysicalSize},
@@ -119,7 +132,9 @@ use fyrox::{
     resource::texture::{
         CompressionOptions, TextureKind, TextureResource, TextureResourceExtension,
     },
-    scene::{camera::Camera, mesh::Mesh, node::Node, Scene, SceneLoader},
+    scene::{
+        camera::Camera, graph::Graph, mesh::Mesh, node::Node, Scene, SceneLoader, SceneSnapshot,
+    },
     utils::{into_gui_texture, translate_cursor_icon, translate_event},
     window::{Icon, WindowAttributes},
 };
@@ -244,6 +259,13 @@ pub enum Mode {
         process: std::process::Child,
         active: Arc<AtomicBool>,
     },
+    /// The edited scene is simulated right in the editor - scripts are running and physics is
+    /// stepping. The scene is restored from the snapshot (along with the selection) when leaving
+    /// the mode, so no edit-time data is lost.
+    Simulation {
+        snapshot: SceneSnapshot,
+        selection: Selection,
+    },
 }
 
 impl Mode {
@@ -278,6 +300,8 @@ pub enum SaveSceneConfirmationDialogAction {
     MakeNewScene,
     /// Closes current scene.
     CloseScene,
+    /// Closes the editor.
+    Exit,
 }
 
 struct SaveSceneConfirmationDialog {
@@ -338,6 +362,9 @@ impl SaveSceneConfirmationDialog {
                         SaveSceneConfirmationDialogAction::LoadScene(ref path) => {
                             sender.send(Message::LoadScene(path.clone()))
                         }
+                        SaveSceneConfirmationDialogAction::Exit => {
+                            sender.send(Message::Exit { force: true })
+                        }
                     },
                     MessageBoxResult::Yes => {
                         if let Some(editor_scene) = editor_scene {
@@ -360,6 +387,9 @@ impl SaveSceneConfirmationDialog {
                                     SaveSceneConfirmationDialogAction::LoadScene(ref path) => {
                                         sender.send(Message::LoadScene(path.clone()))
                                     }
+                                    SaveSceneConfirmationDialogAction::Exit => {
+                                        sender.send(Message::Exit { force: true })
+                                    }
                                 }
 
                                 self.action = SaveSceneConfirmationDialogAction::None;
@@ -371,7 +401,8 @@ impl SaveSceneConfirmationDialog {
                                     SaveSceneConfirmationDialogAction::OpenLoadSceneDialog
                                     | SaveSceneConfirmationDialogAction::LoadScene(_)
                                     | SaveSceneConfirmationDialogAction::MakeNewScene
-                                    | SaveSceneConfirmationDialogAction::CloseScene => {
+                                    | SaveSceneConfirmationDialogAction::CloseScene
+                                    | SaveSceneConfirmationDialogAction::Exit => {
                                         sender.send(Message::OpenSaveSceneDialog)
                                     }
                                 }
@@ -398,11 +429,60 @@ impl SaveSceneConfirmationDialog {
                 SaveSceneConfirmationDialogAction::LoadScene(path) => {
                     sender.send(Message::LoadScene(path))
                 }
+                SaveSceneConfirmationDialogAction::Exit => {
+                    sender.send(Message::Exit { force: true })
+                }
             }
         }
     }
 }
 
+/// Checks whether the editor could be closed right now. If the current scene has unsaved changes
+/// and the exit is not forced, the save confirmation dialog is opened instead and the editor will be
+/// closed when the user makes a choice (unless the choice is "Cancel").
+fn can_exit_immediately(
+    force: bool,
+    editor_scene: Option<&EditorScene>,
+    save_scene_dialog: &mut SaveSceneConfirmationDialog,
+    ui: &UserInterface,
+) -> bool {
+    if force || !is_scene_needs_to_be_saved(editor_scene) {
+        true
+    } else {
+        save_scene_dialog.open(ui, SaveSceneConfirmationDialogAction::Exit);
+        false
+    }
+}
+
+/// Loads the last saved state of the scene from its file and drops all the commands, so the
+/// discarded changes could not be "redone". Returns `None` if the scene cannot be reverted (see
+/// [`is_scene_revertible`]) or if it failed to load.
+fn load_reverted_scene(
+    command_stack: &mut CommandStack,
+    context: SceneContext,
+) -> Option<(Scene, PathBuf)> {
+    if !is_scene_revertible(Some(&*context.editor_scene)) {
+        return None;
+    }
+
+    let path = context.editor_scene.path.clone()?;
+    match block_on(SceneLoader::from_file(
+        &path,
+        context.serialization_context.clone(),
+        context.resource_manager.clone(),
+    )) {
+        Ok(loader) => {
+            let scene = block_on(loader.finish());
+            command_stack.clear(context);
+            Some((scene, path))
+        }
+        Err(e) => {
+            Log::err(e.to_string());
+            None
+        }
+    }
+}
+
 pub struct Editor {
     game_loop_data: GameLoopData,
     engine: Engine,
@@ -416,7 +496,7 @@ pub struct Editor {
     root_grid: Handle<UiNode>,
     scene_viewer: SceneViewer,
     asset_browser: AssetBrowser,
-    exit_message_box: Handle<UiNode>,
+    recovery_message_box: Handle<UiNode>,
     save_file_selector: Handle<UiNode>,
     save_scene_dialog: SaveSceneConfirmationDialog,
     light_panel: LightPanel,
@@ -441,9 +521,16 @@ pub struct Editor {
     animation_editor: AnimationEditor,
     particle_system_control_panel: ParticleSystemPreviewControlPanel,
     camera_control_panel: CameraPreviewControlPanel,
+    camera_bookmarks_panel: CameraBookmarksPanel,
     overlay_pass: Rc<RefCell<OverlayRenderPass>>,
+    highlight_pass: Rc<RefCell<HighlightRenderPass>>,
     audio_preview_panel: AudioPreviewPanel,
     doc_window: DocWindow,
+    problems_panel: ProblemsPanel,
+    journal: Journal,
+    // A journal left by the previous session, it is waiting for the user to decide whether to
+    // recover it or not.
+    pending_recovery: Option<Journal>,
 }
 
 impl Editor {
@@ -530,6 +617,16 @@ impl Editor {
             .renderer
             .add_render_pass(overlay_pass.clone());
 
+        let window_size = graphics_context.window.inner_size();
+        let highlight_pass = HighlightRenderPass::new(
+            graphics_context.renderer.pipeline_state(),
+            window_size.width as usize,
+            window_size.height as usize,
+        );
+        graphics_context
+            .renderer
+            .add_render_pass(highlight_pass.clone());
+
         let (message_sender, message_receiver) = mpsc::channel();
         let message_sender = MessageSender(message_sender);
 
@@ -572,12 +669,15 @@ impl Editor {
         let command_stack_viewer = CommandStackViewer::new(ctx, message_sender.clone());
         let log = LogPanel::new(ctx, log_message_receiver);
         let inspector = Inspector::new(ctx, message_sender.clone());
+        settings.inspector.apply_to(&inspector.property_editors);
         let animation_editor = AnimationEditor::new(ctx);
         let absm_editor = AbsmEditor::new(ctx, message_sender.clone());
         let particle_system_control_panel = ParticleSystemPreviewControlPanel::new(ctx);
         let camera_control_panel = CameraPreviewControlPanel::new(ctx);
+        let camera_bookmarks_panel = CameraBookmarksPanel::new(ctx);
         let audio_preview_panel = AudioPreviewPanel::new(ctx);
         let doc_window = DocWindow::new(ctx);
+        let problems_panel = ProblemsPanel::new(ctx);
 
         let root_grid = GridBuilder::new(
             WidgetBuilder::new()
@@ -669,9 +769,11 @@ impl Editor {
                         absm_editor.window,
                         particle_system_control_panel.window,
                         camera_control_panel.window,
+                        camera_bookmarks_panel.window,
                         audio_preview_panel.window,
                         navmesh_panel.window,
                         doc_window.window,
+                        problems_panel.window,
                     ])
                     .build(ctx),
                 ),
@@ -683,15 +785,17 @@ impl Editor {
 
         let save_file_selector = make_save_file_selector(ctx);
 
-        let exit_message_box = MessageBoxBuilder::new(
-            WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(100.0))
+        let recovery_message_box = MessageBoxBuilder::new(
+            WindowBuilder::new(WidgetBuilder::new().with_width(350.0).with_height(100.0))
                 .can_close(false)
                 .can_minimize(false)
                 .open(false)
-                .with_title(WindowTitle::Text("Unsaved changes".to_owned())),
+                .with_title(WindowTitle::Text("Recover Unsaved Changes".to_owned())),
         )
-        .with_text("There are unsaved changes. Do you wish to save them before exit?")
-        .with_buttons(MessageBoxButtons::YesNoCancel)
+        .with_text(
+            "The editor was not closed properly last time. Do you wish to recover unsaved changes?",
+        )
+        .with_buttons(MessageBoxButtons::YesNo)
         .build(ctx);
 
         let validation_message_box = MessageBoxBuilder::new(
@@ -732,7 +836,7 @@ impl Editor {
             menu,
             exit: false,
             asset_browser,
-            exit_message_box,
+            recovery_message_box,
             save_file_selector,
             configurator,
             log,
@@ -757,9 +861,14 @@ impl Editor {
             scene_settings,
             particle_system_control_panel,
             camera_control_panel,
+            camera_bookmarks_panel,
             overlay_pass,
+            highlight_pass,
             audio_preview_panel,
             doc_window,
+            problems_panel,
+            journal: Journal::new(None),
+            pending_recovery: None,
         };
 
         editor.set_interaction_mode(Some(InteractionModeKind::Move));
@@ -812,6 +921,10 @@ impl Editor {
             .file_menu
             .update_recent_files_list(&mut self.engine.user_interface, &self.settings);
 
+        self.settings
+            .inspector
+            .apply_to(&self.inspector.property_editors);
+
         match self
             .engine
             .graphics_context
@@ -883,11 +996,29 @@ impl Editor {
                 &mut self.engine,
                 self.message_sender.clone(),
             )),
+            Box::new(MeasureInteractionMode::new(
+                self.scene_viewer.measure_display(),
+            )),
+            Box::new(EditSplineMode::new(
+                &editor_scene,
+                &mut self.engine,
+                self.message_sender.clone(),
+            )),
         ];
 
+        // Scripts, whose types are not registered, are not lost, but they do nothing, so the user must
+        // be notified about them.
+        self.problems_panel.report(
+            &self.engine.scenes[editor_scene.scene].graph,
+            &self.engine.user_interface,
+        );
+
         self.command_stack = CommandStack::new(false);
         self.scene = Some(editor_scene);
 
+        Journal::remove_files();
+        self.journal = Journal::new(path.clone());
+
         self.set_interaction_mode(Some(InteractionModeKind::Move));
 
         if let Some(path) = path.as_ref() {
@@ -911,6 +1042,8 @@ impl Editor {
         );
         self.scene_viewer
             .reset_camera_projection(&self.engine.user_interface);
+        self.scene_viewer
+            .reset_debug_view(&self.engine.user_interface);
         self.engine
             .graphics_context
             .as_initialized_mut()
@@ -955,7 +1088,11 @@ impl Editor {
             };
             let key_bindings = &self.settings.key_bindings;
 
-            if hot_key == key_bindings.redo {
+            if hot_key == key_bindings.toggle_simulation {
+                sender.send(Message::ToggleSimulation);
+            } else if !self.mode.is_edit() {
+                // Any changes made in non-edit modes will be lost, so scene editing is not allowed.
+            } else if hot_key == key_bindings.redo {
                 sender.send(Message::RedoSceneCommand);
             } else if hot_key == key_bindings.undo {
                 sender.send(Message::UndoSceneCommand);
@@ -971,6 +1108,10 @@ impl Editor {
                 sender.send(Message::SetInteractionMode(InteractionModeKind::Navmesh));
             } else if hot_key == key_bindings.enable_terrain_mode {
                 sender.send(Message::SetInteractionMode(InteractionModeKind::Terrain));
+            } else if hot_key == key_bindings.enable_measure_mode {
+                sender.send(Message::SetInteractionMode(InteractionModeKind::Measure));
+            } else if hot_key == key_bindings.enable_spline_mode {
+                sender.send(Message::SetInteractionMode(InteractionModeKind::Spline));
             } else if hot_key == key_bindings.load_scene {
                 sender.send(Message::OpenLoadSceneDialog);
             } else if hot_key == key_bindings.save_scene {
@@ -1027,6 +1168,21 @@ impl Editor {
                         }
                     }
                 }
+            } else if hot_key == key_bindings.isolate_selection {
+                sender.send(Message::ToggleSelectionIsolation);
+            } else if hot_key == key_bindings.toggle_gizmo_space {
+                self.settings.gizmo_space = self.settings.gizmo_space.toggled();
+                Log::verify(self.settings.save());
+            } else if let Some(index) = bookmark_index_from_hotkey(&hot_key) {
+                if let Some(editor_scene) = self.scene.as_mut() {
+                    let camera_controller = &mut editor_scene.camera_controller;
+                    if let Some(bookmark) = camera_controller.bookmarks.get(index).cloned() {
+                        camera_controller.jump_to_bookmark(
+                            &mut engine.scenes[editor_scene.scene].graph,
+                            &bookmark,
+                        );
+                    }
+                }
             }
         }
     }
@@ -1065,6 +1221,7 @@ impl Editor {
                     curve_editor: &self.curve_editor,
                     absm_editor: &self.absm_editor,
                     command_stack_panel: self.command_stack_viewer.window,
+                    camera_bookmarks_panel: self.camera_bookmarks_panel.window,
                     scene_settings: &self.scene_settings,
                     animation_editor: &self.animation_editor,
                 },
@@ -1106,6 +1263,8 @@ impl Editor {
                 .handle_ui_message(message, editor_scene, engine);
             self.camera_control_panel
                 .handle_ui_message(message, editor_scene, engine);
+            self.camera_bookmarks_panel
+                .handle_ui_message(message, editor_scene, engine);
             self.audio_preview_panel
                 .handle_ui_message(message, editor_scene, engine);
             self.absm_editor
@@ -1120,6 +1279,13 @@ impl Editor {
 
             self.inspector
                 .handle_ui_message(message, editor_scene, engine, &self.message_sender);
+            if self
+                .settings
+                .inspector
+                .handle_ui_message(message, &self.inspector.property_editors)
+            {
+                Log::verify(self.settings.save());
+            }
 
             if let Some(current_im) = self.current_interaction_mode {
                 self.interaction_modes[current_im as usize].handle_ui_message(
@@ -1138,38 +1304,20 @@ impl Editor {
             self.material_editor
                 .handle_ui_message(message, engine, &self.message_sender);
 
-            if let Some(MessageBoxMessage::Close(result)) = message.data::<MessageBoxMessage>() {
-                if message.destination() == self.exit_message_box {
-                    match result {
-                        MessageBoxResult::No => {
-                            self.message_sender.send(Message::Exit { force: true });
-                        }
-                        MessageBoxResult::Yes => {
-                            if let Some(scene) = self.scene.as_ref() {
-                                if let Some(path) = scene.path.as_ref() {
-                                    self.message_sender.send(Message::SaveScene(path.clone()));
-                                    self.message_sender.send(Message::Exit { force: true });
-                                } else {
-                                    // Scene wasn't saved yet, open Save As dialog.
-                                    engine
-                                        .user_interface
-                                        .send_message(WindowMessage::open_modal(
-                                            self.save_file_selector,
-                                            MessageDirection::ToWidget,
-                                            true,
-                                        ));
-                                }
-                            }
-                        }
-                        _ => {}
-                    }
-                }
-            } else if let Some(FileSelectorMessage::Commit(path)) =
-                message.data::<FileSelectorMessage>()
-            {
+            if let Some(FileSelectorMessage::Commit(path)) = message.data::<FileSelectorMessage>() {
                 if message.destination() == self.save_file_selector {
                     self.message_sender.send(Message::SaveScene(path.clone()));
-                    self.message_sender.send(Message::Exit { force: true });
+                }
+            }
+        }
+
+        if let Some(MessageBoxMessage::Close(result)) = message.data::<MessageBoxMessage>() {
+            if message.destination() == self.recovery_message_box {
+                if *result == MessageBoxResult::Yes {
+                    self.message_sender.send(Message::RecoverFromJournal);
+                } else if self.pending_recovery.take().is_some() && self.scene.is_none() {
+                    // If there is a scene, the files were already replaced by its own journal.
+                    Journal::remove_files();
                 }
             }
         }
@@ -1264,13 +1412,82 @@ impl Editor {
         }
     }
 
+    fn set_simulation_mode(&mut self) {
+        if !self.mode.is_edit() {
+            Log::err("Cannot enter simulation mode from non-Edit mode!");
+            return;
+        }
+
+        self.try_leave_preview_mode();
+
+        if let Some(editor_scene) = self.scene.as_mut() {
+            match editor_scene.begin_simulation(&mut self.engine.scenes[editor_scene.scene]) {
+                Ok((snapshot, selection)) => {
+                    self.engine.register_scripted_scene(editor_scene.scene);
+
+                    self.mode = Mode::Simulation {
+                        snapshot,
+                        selection,
+                    };
+                    self.on_mode_changed();
+                }
+                Err(e) => Log::err(format!("Failed to enter simulation mode: {}", e)),
+            }
+        } else {
+            Log::err("Cannot enter simulation mode when there is no scene!");
+        }
+    }
+
+    fn leave_simulation_mode(&mut self, snapshot: SceneSnapshot, selection: Selection) {
+        if let Some(editor_scene) = self.scene.as_mut() {
+            let engine = &mut self.engine;
+            let simulated_scene = editor_scene.scene;
+            match editor_scene.end_simulation(
+                &engine.scenes[simulated_scene],
+                &snapshot,
+                selection,
+                engine.serialization_context.clone(),
+                engine.resource_manager.clone(),
+            ) {
+                Ok(scene) => {
+                    // Simulated scene is destroyed along with its scripts, restored scene is not
+                    // registered for script processing.
+                    engine.scenes.remove(simulated_scene);
+                    editor_scene.scene = engine.scenes.add(scene);
+                }
+                Err(e) => Log::err(format!(
+                    "Failed to restore the scene after simulation! Reason: {}",
+                    e
+                )),
+            }
+        }
+    }
+
+    fn toggle_simulation_mode(&mut self) {
+        match self.mode {
+            Mode::Edit => self.set_simulation_mode(),
+            Mode::Simulation { .. } => self.set_editor_mode(),
+            _ => Log::err("Cannot enter simulation mode while the game is running!"),
+        }
+    }
+
     fn set_editor_mode(&mut self) {
-        if let Mode::Play { mut process, .. } | Mode::Build { mut process } =
-            std::mem::replace(&mut self.mode, Mode::Edit)
-        {
-            Log::verify(process.kill());
+        match std::mem::replace(&mut self.mode, Mode::Edit) {
+            Mode::Play { mut process, .. } | Mode::Build { mut process } => {
+                Log::verify(process.kill());
+
+                self.on_mode_changed();
+            }
+            Mode::Simulation {
+                snapshot,
+                selection,
+            } => {
+                self.leave_simulation_mode(snapshot, selection);
 
-            self.on_mode_changed();
+                self.on_mode_changed();
+                self.message_sender.send(Message::ForceSync);
+            }
+            Mode::Edit => (),
         }
     }
 
@@ -1306,6 +1523,8 @@ impl Editor {
             self.material_editor
                 .sync_to_model(&mut engine.user_interface);
             self.audio_panel.sync_to_model(editor_scene, engine);
+            self.camera_bookmarks_panel
+                .sync_to_model(editor_scene, &mut engine.user_interface);
             self.navmesh_panel.sync_to_model(engine, editor_scene);
             self.command_stack_viewer.sync_to_model(
                 &mut self.command_stack,
@@ -1356,19 +1575,20 @@ impl Editor {
     fn do_scene_command(&mut self, command: SceneCommand) -> bool {
         let engine = &mut self.engine;
         if let Some(editor_scene) = self.scene.as_mut() {
-            self.command_stack.do_command(
-                command.into_inner(),
-                SceneContext {
-                    scene: &mut engine.scenes[editor_scene.scene],
-                    message_sender: self.message_sender.clone(),
-                    editor_scene,
-                    resource_manager: engine.resource_manager.clone(),
-                    serialization_context: engine.serialization_context.clone(),
-                },
-            );
+            let context = SceneContext {
+                scene: &mut engine.scenes[editor_scene.scene],
+                message_sender: self.message_sender.clone(),
+                editor_scene,
+                resource_manager: engine.resource_manager.clone(),
+                serialization_context: engine.serialization_context.clone(),
+            };
+            let journal_entries = command.journal_entries(&context);
+            self.command_stack.do_command(command.into_inner(), context);
 
             editor_scene.has_unsaved_changes = true;
 
+            self.write_journal(journal_entries);
+
             true
         } else {
             false
@@ -1378,16 +1598,29 @@ impl Editor {
     fn undo_scene_command(&mut self) -> bool {
         let engine = &mut self.engine;
         if let Some(editor_scene) = self.scene.as_mut() {
-            self.command_stack.undo(SceneContext {
+            let context = SceneContext {
                 scene: &mut engine.scenes[editor_scene.scene],
                 message_sender: self.message_sender.clone(),
                 editor_scene,
                 resource_manager: engine.resource_manager.clone(),
                 serialization_context: engine.serialization_context.clone(),
-            });
+            };
+            let journal_entries = self
+                .command_stack
+                .undo_command()
+                .map(|command| command.journal_entries(&context));
+            self.command_stack.undo(context);
 
             editor_scene.has_unsaved_changes = true;
 
+            if let Some(mut journal_entries) = journal_entries {
+                // Revert performs the actions in reverse order.
+                if let Some(entries) = journal_entries.as_mut() {
+                    entries.reverse();
+                }
+                self.write_journal(journal_entries);
+            }
+
             true
         } else {
             false
@@ -1397,22 +1630,130 @@ impl Editor {
     fn redo_scene_command(&mut self) -> bool {
         let engine = &mut self.engine;
         if let Some(editor_scene) = self.scene.as_mut() {
-            self.command_stack.redo(SceneContext {
+            let context = SceneContext {
                 scene: &mut engine.scenes[editor_scene.scene],
                 message_sender: self.message_sender.clone(),
                 editor_scene,
                 resource_manager: engine.resource_manager.clone(),
                 serialization_context: engine.serialization_context.clone(),
-            });
+            };
+            let journal_entries = self
+                .command_stack
+                .redo_command()
+                .map(|command| command.journal_entries(&context));
+            self.command_stack.redo(context);
+
+            editor_scene.has_unsaved_changes = true;
+
+            if let Some(journal_entries) = journal_entries {
+                self.write_journal(journal_entries);
+            }
+
+            true
+        } else {
+            false
+        }
+    }
+
+    fn jump_to_scene_command(&mut self, target: Option<usize>) -> bool {
+        let engine = &mut self.engine;
+        if let Some(editor_scene) = self.scene.as_mut() {
+            self.command_stack.jump_to(
+                target,
+                SceneContext {
+                    scene: &mut engine.scenes[editor_scene.scene],
+                    message_sender: self.message_sender.clone(),
+                    editor_scene,
+                    resource_manager: engine.resource_manager.clone(),
+                    serialization_context: engine.serialization_context.clone(),
+                },
+            );
 
             editor_scene.has_unsaved_changes = true;
 
+            // There could be any number of commands between the current and the target positions,
+            // it is easier to store the entire scene.
+            self.write_journal(None);
+
             true
         } else {
             false
         }
     }
 
+    /// Writes the actions performed by a command to the journal. `None` means that the actions
+    /// cannot be described, and the entire scene will be saved instead.
+    fn write_journal(&mut self, entries: Option<Vec<JournalEntry>>) {
+        if let Some(editor_scene) = self.scene.as_ref() {
+            match entries {
+                Some(entries) if entries.is_empty() => return,
+                Some(entries) => {
+                    for entry in entries {
+                        self.journal.record(entry);
+                    }
+                }
+                None => {
+                    let mut scene = editor_scene.make_purified_scene(&mut self.engine);
+                    self.journal.checkpoint(&mut scene);
+                }
+            }
+            self.journal.save();
+        }
+    }
+
+    fn recover_from_journal(&mut self, journal: Journal) {
+        let scene = match journal.base_scene_path() {
+            Some(path) => {
+                match block_on(SceneLoader::from_file(
+                    &path,
+                    self.engine.serialization_context.clone(),
+                    self.engine.resource_manager.clone(),
+                )) {
+                    Ok(loader) => block_on(loader.finish()),
+                    Err(e) => {
+                        Log::err(format!(
+                            "Unable to recover unsaved changes, failed to load {}. Reason: {}",
+                            path.display(),
+                            e
+                        ));
+                        return;
+                    }
+                }
+            }
+            None => {
+                let mut scene = Scene::new();
+                scene.ambient_lighting_color = Color::opaque(200, 200, 200);
+                scene
+            }
+        };
+
+        self.set_scene(scene, journal.scene_path().cloned());
+
+        for entry in journal.entries().iter().cloned() {
+            let command = self.scene.as_ref().and_then(|editor_scene| {
+                entry.into_command(
+                    &self.engine.scenes[editor_scene.scene].graph,
+                    editor_scene.scene_content_root,
+                )
+            });
+            match command {
+                Some(command) => {
+                    self.do_scene_command(command);
+                }
+                None => Log::warn("Unable to replay a journal entry, the node does not exist!"),
+            }
+        }
+
+        if let Some(editor_scene) = self.scene.as_mut() {
+            // Recovered scene is always unsaved, even if there were no entries on top of a checkpoint.
+            editor_scene.has_unsaved_changes = true;
+        }
+        // Make sure that the recovered state will survive yet another crash.
+        self.write_journal(None);
+
+        Log::info("Unsaved changes were recovered successfully!");
+    }
+
     fn clear_scene_command_stack(&mut self) -> bool {
         let engine = &mut self.engine;
         if let Some(editor_scene) = self.scene.as_mut() {
@@ -1466,6 +1807,9 @@ impl Editor {
                     Log::info(message);
 
                     editor_scene.has_unsaved_changes = false;
+
+                    Journal::remove_files();
+                    self.journal = Journal::new(Some(path));
                 }
                 Err(message) => {
                     Log::err(message.clone());
@@ -1501,19 +1845,37 @@ impl Editor {
         }
     }
 
-    fn exit(&mut self, force: bool) {
+    fn revert_current_scene(&mut self) -> bool {
         let engine = &mut self.engine;
-        if force {
-            self.exit = true;
-        } else if is_scene_needs_to_be_saved(self.scene.as_ref()) {
-            engine.user_interface.send_message(MessageBoxMessage::open(
-                self.exit_message_box,
-                MessageDirection::ToWidget,
-                None,
-                None,
-            ));
-        } else {
+        if let Some(editor_scene) = self.scene.as_mut() {
+            if let Some((scene, path)) = load_reverted_scene(
+                &mut self.command_stack,
+                SceneContext {
+                    scene: &mut engine.scenes[editor_scene.scene],
+                    message_sender: self.message_sender.clone(),
+                    editor_scene,
+                    resource_manager: engine.resource_manager.clone(),
+                    serialization_context: engine.serialization_context.clone(),
+                },
+            ) {
+                self.set_scene(scene, Some(path));
+                return true;
+            }
+        }
+        false
+    }
+
+    fn exit(&mut self, force: bool) {
+        if can_exit_immediately(
+            force,
+            self.scene.as_ref(),
+            &mut self.save_scene_dialog,
+            &self.engine.user_interface,
+        ) {
             self.exit = true;
+
+            // Clean exit, there is nothing to recover.
+            Journal::remove_files();
         }
     }
 
@@ -1524,6 +1886,9 @@ impl Editor {
         if let Some(editor_scene) = self.scene.take() {
             engine.scenes.remove(editor_scene.scene);
 
+            Journal::remove_files();
+            self.journal = Journal::new(None);
+
             // Preview frame has scene frame texture assigned, it must be cleared explicitly,
             // otherwise it will show last rendered frame in preview which is not what we want.
             self.scene_viewer
@@ -1587,6 +1952,18 @@ impl Editor {
             "New working directory was successfully set: {:?}",
             working_directory
         ));
+
+        // Journal is stored in the working directory, check if there is something left by the
+        // previous session.
+        if let Some(journal) = Journal::load() {
+            self.pending_recovery = Some(journal);
+            engine.user_interface.send_message(MessageBoxMessage::open(
+                self.recovery_message_box,
+                MessageDirection::ToWidget,
+                None,
+                None,
+            ));
+        }
     }
 
     fn select_object(&mut self, type_id: TypeId, handle: ErasedHandle) {
@@ -1687,7 +2064,20 @@ impl Editor {
                     Err(err) => Log::err(format!("Failed to wait for game process: {:?}", err)),
                 }
             }
-            _ => {}
+            Mode::Simulation { .. } => {
+                // Every scene node must be updated while simulating, including the nodes spawned
+                // by scripts.
+                if let Some(editor_scene) = self.scene.as_mut() {
+                    let graph = &self.engine.scenes[editor_scene.scene].graph;
+                    if let Some(node_overrides) =
+                        editor_scene.graph_switches.node_overrides.as_mut()
+                    {
+                        node_overrides
+                            .extend(graph.traverse_handle_iter(editor_scene.scene_content_root));
+                    }
+                }
+            }
+            Mode::Edit => {}
         }
 
         self.log.update(&mut self.engine);
@@ -1701,6 +2091,37 @@ impl Editor {
 
         self.overlay_pass.borrow_mut().pictogram_size = self.settings.debugging.pictogram_size;
 
+        {
+            let mut highlight_pass = self.highlight_pass.borrow_mut();
+            highlight_pass.color = self.settings.selection.outline_color;
+            highlight_pass.thickness = self.settings.selection.outline_thickness;
+            if let Some(editor_scene) = self.scene.as_ref() {
+                highlight_pass
+                    .targets
+                    .set_from_selection(editor_scene.scene, &editor_scene.selection);
+            } else {
+                highlight_pass.targets.clear();
+            }
+        }
+
+        let display_settings = self.settings.units.display_settings();
+        if self.inspector.property_editors.numeric_display_settings() != display_settings {
+            self.inspector
+                .property_editors
+                .set_numeric_display_settings(display_settings);
+            // Re-create property editors to show the values in the new units.
+            if let Some(editor_scene) = self.scene.as_ref() {
+                self.inspector.handle_message(
+                    &Message::SelectionChanged {
+                        old_selection: editor_scene.selection.clone(),
+                    },
+                    editor_scene,
+                    &mut self.engine,
+                    &self.message_sender,
+                );
+            }
+        }
+
         let mut iterations = 1;
         while iterations > 0 {
             iterations -= 1;
@@ -1761,17 +2182,35 @@ impl Editor {
                     Message::RedoSceneCommand => {
                         needs_sync |= self.redo_scene_command();
                     }
+                    Message::JumpToSceneCommand(target) => {
+                        needs_sync |= self.jump_to_scene_command(target);
+                    }
                     Message::ClearSceneCommandStack => {
                         needs_sync |= self.clear_scene_command_stack();
                     }
                     Message::SelectionChanged { .. } => {
                         self.world_viewer.sync_selection = true;
+                        if self.settings.selection.focus_follows_selection {
+                            self.inspector.focus(&self.engine.user_interface);
+                        }
+                    }
+                    Message::SaveScene(path) => {
+                        self.save_current_scene(path);
+                        needs_sync = true;
                     }
-                    Message::SaveScene(path) => self.save_current_scene(path),
                     Message::LoadScene(scene_path) => {
                         self.load_scene(scene_path);
                         needs_sync = true;
                     }
+                    Message::RevertScene => {
+                        needs_sync |= self.revert_current_scene();
+                    }
+                    Message::RecoverFromJournal => {
+                        if let Some(journal) = self.pending_recovery.take() {
+                            self.recover_from_journal(journal);
+                            needs_sync = true;
+                        }
+                    }
                     Message::SetInteractionMode(mode_kind) => {
                         self.set_interaction_mode(Some(mode_kind))
                     }
@@ -1815,6 +2254,23 @@ impl Editor {
                             editor_scene.camera_controller.fit_object(scene, handle);
                         }
                     }
+                    Message::ToggleNodesHidden(nodes) => {
+                        if let Some(editor_scene) = self.scene.as_mut() {
+                            let graph = &mut self.engine.scenes[editor_scene.scene].graph;
+                            for node in nodes {
+                                let hidden = editor_scene.node_flags.is_hidden(node);
+                                editor_scene.node_flags.set_hidden(graph, node, !hidden);
+                            }
+                        }
+                    }
+                    Message::ToggleSelectionIsolation => {
+                        if let Some(editor_scene) = self.scene.as_mut() {
+                            editor_scene.toggle_isolation(
+                                &mut self.engine.scenes[editor_scene.scene].graph,
+                                self.settings.selection.isolation_keeps_lights,
+                            );
+                        }
+                    }
                     Message::SetEditorCameraProjection(projection) => {
                         if let Some(editor_scene) = self.scene.as_ref() {
                             editor_scene.camera_controller.set_projection(
@@ -1823,12 +2279,21 @@ impl Editor {
                             );
                         }
                     }
+                    Message::SetDebugView(debug_view) => {
+                        if let Some(editor_scene) = self.scene.as_ref() {
+                            editor_scene.camera_controller.set_debug_view(
+                                &mut self.engine.scenes[editor_scene.scene].graph,
+                                debug_view,
+                            );
+                        }
+                    }
                     Message::SwitchMode => match self.mode {
                         Mode::Edit => self.set_build_mode(),
                         _ => self.set_editor_mode(),
                     },
                     Message::SwitchToBuildMode => self.set_build_mode(),
                     Message::SwitchToEditMode => self.set_editor_mode(),
+                    Message::ToggleSimulation => self.toggle_simulation_mode(),
                     Message::OpenLoadSceneDialog => {
                         self.menu
                             .open_load_file_selector(&mut self.engine.user_interface);
@@ -1847,6 +2312,19 @@ impl Editor {
                     Message::SaveSelectionAsPrefab(path) => {
                         self.try_save_selection_as_prefab(path);
                     }
+                    Message::InstantiateModels(paths) => {
+                        if let Some(editor_scene) = self.scene.as_ref() {
+                            if let Some(command) = make_instantiate_models_command(
+                                &paths,
+                                editor_scene,
+                                &mut self.engine.scenes[editor_scene.scene],
+                                &self.engine.resource_manager,
+                                self.settings.model.instantiation_scale,
+                            ) {
+                                self.message_sender.send(Message::DoSceneCommand(command));
+                            }
+                        }
+                    }
                     Message::SyncNodeHandleName { view, handle } => {
                         if let Some(editor_scene) = self.scene.as_ref() {
                             let scene = &self.engine.scenes[editor_scene.scene];
@@ -1924,39 +2402,11 @@ impl Editor {
 
     fn try_save_selection_as_prefab(&self, path: PathBuf) {
         if let Some(editor_scene) = self.scene.as_ref() {
-            let source_scene = &self.engine.scenes[editor_scene.scene];
-            let mut dest_scene = Scene::new();
-            if let Selection::Graph(ref graph_selection) = editor_scene.selection {
-                for root_node in graph_selection.root_nodes(&source_scene.graph) {
-                    source_scene
-                        .graph
-                        .copy_node(root_node, &mut dest_scene.graph, &mut |_, _| true);
-                }
-
-                let mut visitor = Visitor::new();
-                match dest_scene.save("Scene", &mut visitor) {
-                    Err(e) => Log::err(format!(
-                        "Failed to save selection as prefab! Reason: {:?}",
-                        e
-                    )),
-                    Ok(_) => {
-                        if let Err(e) = visitor.save_binary(&path) {
-                            Log::err(format!(
-                                "Failed to save selection as prefab! Reason: {:?}",
-                                e
-                            ));
-                        } else {
-                            Log::info(format!(
-                                "Selection was successfully saved as prefab to {:?}!",
-                                path
-                            ))
-                        }
-                    }
-                }
-            } else {
-                Log::warn(
-                    "Unable to selection to prefab, because selection is not scene selection!",
-                );
+            match editor_scene
+                .save_selection_as_prefab(&self.engine.scenes[editor_scene.scene], &path)
+            {
+                Ok(message) => Log::info(message),
+                Err(message) => Log::err(message),
             }
         } else {
             Log::warn("Unable to save selection to prefab, because there is no scene loaded!");
@@ -1980,7 +2430,7 @@ impl Editor {
 
                     // Kill any active child process on exit.
                     match self.mode {
-                        Mode::Edit => {}
+                        Mode::Edit | Mode::Simulation { .. } => {}
                         Mode::Build { ref mut process }
                         | Mode::Play {
                             ref mut process, ..
@@ -1995,21 +2445,14 @@ impl Editor {
                 // scene camera to interfere with the editor camera.
                 let mut camera_state = Vec::new();
                 if let Some(editor_scene) = self.scene.as_ref() {
-                    let scene = &mut self.engine.scenes[editor_scene.scene];
-                    let has_preview_camera =
-                        scene.graph.is_valid_handle(editor_scene.preview_camera);
-                    for (handle, camera) in scene.graph.pair_iter_mut().filter_map(|(h, n)| {
-                        if has_preview_camera && h != editor_scene.preview_camera
-                            || !has_preview_camera && h != editor_scene.camera_controller.camera
-                        {
-                            n.cast_mut::<Camera>().map(|c| (h, c))
-                        } else {
-                            None
-                        }
-                    }) {
-                        camera_state.push((handle, camera.is_enabled()));
-                        camera.set_enabled(false);
-                    }
+                    self.camera_control_panel
+                        .update(editor_scene, &mut self.engine);
+
+                    camera_state = disable_scene_cameras(
+                        editor_scene,
+                        self.camera_control_panel.previewed_camera(),
+                        &mut self.engine.scenes[editor_scene.scene].graph,
+                    );
                 }
 
                 self.engine.render().unwrap();
@@ -2024,6 +2467,8 @@ impl Editor {
                 }
             }
             Event::WindowEvent { ref event, .. } => {
+                self.engine.window_info.handle_event(event);
+
                 match event {
                     WindowEvent::CloseRequested => {
                         self.message_sender.send(Message::Exit { force: false });
@@ -2085,6 +2530,33 @@ impl Editor {
     }
 }
 
+/// Disables every camera of the scene except the one the scene is viewed from (either the editor camera
+/// or the preview camera) and the camera that renders to the camera preview panel. Returns previous state
+/// of the disabled cameras, so it could be reverted after rendering.
+fn disable_scene_cameras(
+    editor_scene: &EditorScene,
+    previewed_camera: Handle<Node>,
+    graph: &mut Graph,
+) -> Vec<(Handle<Node>, bool)> {
+    let mut camera_state = Vec::new();
+    let has_preview_camera = graph.is_valid_handle(editor_scene.preview_camera);
+    for (handle, camera) in graph.pair_iter_mut().filter_map(|(h, n)| {
+        if has_preview_camera && h != editor_scene.preview_camera
+            || !has_preview_camera && h != editor_scene.camera_controller.camera
+        {
+            n.cast_mut::<Camera>().map(|c| (h, c))
+        } else {
+            None
+        }
+    }) {
+        camera_state.push((handle, camera.is_enabled()));
+        // The camera selected in the camera preview panel renders to its own texture,
+        // so it does not interfere with the editor camera.
+        camera.set_enabled(handle == previewed_camera && camera.render_target().is_some());
+    }
+    camera_state
+}
+
 fn set_ui_scaling(ui: &UserInterface, scale: f32) {
     // High-DPI screen support
     ui.send_message(WidgetMessage::render_transform(
@@ -2131,3 +2603,125 @@ fn update(editor: &mut Editor, control_flow: &mut ControlFlow) {
     window.set_cursor_icon(translate_cursor_icon(editor.engine.user_interface.cursor()));
     window.request_redraw();
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        can_exit_immediately,
+        command::CommandStack,
+        load_reverted_scene,
+        message::{Message, MessageSender},
+        scene::{
+            commands::{graph::SetNodeNameCommand, SceneContext},
+            EditorScene,
+        },
+        SaveSceneConfirmationDialog,
+    };
+    use fyrox::{
+        core::{algebra::Vector2, visitor::prelude::*},
+        gui::{
+            message::MessageDirection,
+            messagebox::{MessageBoxMessage, MessageBoxResult},
+            UserInterface,
+        },
+        scene::{base::BaseBuilder, pivot::PivotBuilder, Scene},
+    };
+    use std::{path::PathBuf, sync::mpsc::channel};
+
+    #[test]
+    fn test_exit_with_unsaved_changes_asks_for_confirmation() {
+        let mut ui = UserInterface::new(Vector2::new(800.0, 600.0));
+        let mut dialog = SaveSceneConfirmationDialog::new(&mut ui.build_ctx());
+        while ui.poll_message().is_some() {}
+
+        let mut scene = Scene::new();
+        let mut editor_scene = EditorScene::new_test(&mut scene);
+        editor_scene.has_unsaved_changes = true;
+        editor_scene.path = Some(PathBuf::from("test.rgs"));
+
+        assert!(!can_exit_immediately(
+            false,
+            Some(&editor_scene),
+            &mut dialog,
+            &ui
+        ));
+        let message = ui.poll_message().unwrap();
+        assert_eq!(message.destination(), dialog.save_message_box);
+        assert!(matches!(
+            message.data(),
+            Some(MessageBoxMessage::Open { .. })
+        ));
+
+        // Forced exit and a scene without changes must not ask anything.
+        assert!(can_exit_immediately(
+            true,
+            Some(&editor_scene),
+            &mut dialog,
+            &ui
+        ));
+        assert!(can_exit_immediately(false, None, &mut dialog, &ui));
+        assert!(ui.poll_message().is_none());
+
+        // "Don't Save" closes the editor.
+        let (sender, receiver) = channel();
+        let sender = MessageSender(sender);
+        dialog.handle_ui_message(
+            &MessageBoxMessage::close(
+                dialog.save_message_box,
+                MessageDirection::FromWidget,
+                MessageBoxResult::No,
+            ),
+            &sender,
+            Some(&editor_scene),
+        );
+        assert!(matches!(
+            receiver.try_recv(),
+            Ok(Message::Exit { force: true })
+        ));
+    }
+
+    #[test]
+    fn test_revert_restores_saved_scene() {
+        let mut scene = Scene::new();
+        let node = PivotBuilder::new(BaseBuilder::new().with_name("Saved")).build(&mut scene.graph);
+
+        let path = std::env::temp_dir().join("fyroxed_revert_scene.rgs");
+        let mut visitor = Visitor::new();
+        scene.save("Scene", &mut visitor).unwrap();
+        visitor.save_binary(&path).unwrap();
+
+        let mut editor_scene = EditorScene::new_test(&mut scene);
+        editor_scene.path = Some(path.clone());
+
+        let (sender, _receiver) = channel();
+        let sender = MessageSender(sender);
+        let mut stack = CommandStack::new(false);
+
+        // Nothing to revert, until there are unsaved changes.
+        assert!(load_reverted_scene(
+            &mut stack,
+            SceneContext::new_test(&mut editor_scene, &mut scene, sender.clone()),
+        )
+        .is_none());
+
+        stack.do_command(
+            Box::new(SetNodeNameCommand::new(node, "Modified".to_owned())),
+            SceneContext::new_test(&mut editor_scene, &mut scene, sender.clone()),
+        );
+        editor_scene.has_unsaved_changes = true;
+        assert_eq!(scene.graph[node].name(), "Modified");
+        assert!(stack.undo_command().is_some());
+
+        let (reverted, reverted_path) = load_reverted_scene(
+            &mut stack,
+            SceneContext::new_test(&mut editor_scene, &mut scene, sender),
+        )
+        .unwrap();
+        assert_eq!(reverted_path, path);
+        assert_eq!(reverted.graph[node].name(), "Saved");
+        assert!(stack.undo_command().is_none());
+        assert!(stack.redo_command().is_none());
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
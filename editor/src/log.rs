@@ -15,11 +15,12 @@ use fyrox::{
         dropdown_list::DropdownListMessage,
         formatted_text::WrapMode,
         grid::{Column, GridBuilder, Row},
-        list_view::{ListView, ListViewBuilder, ListViewMessage},
+        list_view::{ListViewBuilder, ListViewMessage},
         menu::{MenuItemBuilder, MenuItemContent, MenuItemMessage},
         message::{MessageDirection, UiMessage},
         popup::{Placement, PopupBuilder, PopupMessage},
         scroll_viewer::ScrollViewerBuilder,
+        searchbar::{SearchBarBuilder, SearchBarMessage},
         stack_panel::StackPanelBuilder,
         text::{Text, TextBuilder},
         widget::WidgetBuilder,
@@ -27,7 +28,102 @@ use fyrox::{
         BuildContext, HorizontalAlignment, Orientation, RcUiNodeHandle, Thickness, UiNode,
     },
 };
-use std::sync::mpsc::Receiver;
+use std::{collections::VecDeque, sync::mpsc::Receiver};
+
+/// Maximum amount of messages stored in the log. Oldest messages will be discarded when the limit
+/// is reached.
+pub const MAX_LOG_MESSAGES: usize = 1024;
+
+/// A capped storage for log messages. It keeps every message regardless of its severity, so the
+/// view could be re-filtered at any time.
+pub struct LogBuffer {
+    messages: VecDeque<(u64, LogMessage)>,
+    capacity: usize,
+    next_id: u64,
+}
+
+impl Default for LogBuffer {
+    fn default() -> Self {
+        Self::new(MAX_LOG_MESSAGES)
+    }
+}
+
+impl LogBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            messages: Default::default(),
+            capacity: capacity.max(1),
+            next_id: 0,
+        }
+    }
+
+    /// Adds a new message to the buffer and returns its unique id together with the id of a message
+    /// that was discarded to keep the buffer within its capacity (if any).
+    pub fn push(&mut self, message: LogMessage) -> (u64, Option<u64>) {
+        let discarded = if self.messages.len() >= self.capacity {
+            self.messages.pop_front().map(|(id, _)| id)
+        } else {
+            None
+        };
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.messages.push_back((id, message));
+
+        (id, discarded)
+    }
+
+    /// Moves every pending message from the receiver into the buffer. Returns ids of the discarded
+    /// messages.
+    pub fn receive(&mut self, receiver: &Receiver<LogMessage>) -> Vec<u64> {
+        let mut discarded = Vec::new();
+        while let Ok(message) = receiver.try_recv() {
+            discarded.extend(self.push(message).1);
+        }
+        discarded
+    }
+
+    pub fn get(&self, id: u64) -> Option<&LogMessage> {
+        self.messages
+            .iter()
+            .find(|(message_id, _)| *message_id == id)
+            .map(|(_, message)| message)
+    }
+
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.messages.clear();
+    }
+
+    /// Returns every message with severity not less than the given one and whose content contains
+    /// the search string (case-insensitive). Empty search string matches everything.
+    pub fn filtered<'a>(
+        &'a self,
+        severity: MessageKind,
+        search: &'a str,
+    ) -> impl Iterator<Item = (u64, &'a LogMessage)> + 'a {
+        self.messages
+            .iter()
+            .filter(move |(_, message)| passes_filter(message, severity, search))
+            .map(|(id, message)| (*id, message))
+    }
+}
+
+fn passes_filter(message: &LogMessage, severity: MessageKind, search: &str) -> bool {
+    message.kind >= severity
+        && (search.is_empty()
+            || message
+                .content
+                .to_lowercase()
+                .contains(&search.to_lowercase()))
+}
 
 struct ContextMenu {
     menu: RcUiNodeHandle,
@@ -85,8 +181,13 @@ pub struct LogPanel {
     messages: Handle<UiNode>,
     clear: Handle<UiNode>,
     receiver: Receiver<LogMessage>,
+    buffer: LogBuffer,
+    // Pairs of message id and respective list item, in the same order as in the list.
+    visible: VecDeque<(u64, Handle<UiNode>)>,
     severity: MessageKind,
     severity_list: Handle<UiNode>,
+    search_bar: Handle<UiNode>,
+    search_text: String,
     context_menu: ContextMenu,
 }
 
@@ -95,6 +196,7 @@ impl LogPanel {
         let messages;
         let clear;
         let severity_list;
+        let search_bar;
         let window = WindowBuilder::new(WidgetBuilder::new())
             .can_minimize(false)
             .with_title(WindowTitle::Text("Message Log".to_owned()))
@@ -134,6 +236,15 @@ impl LogPanel {
                                         .with_selected(1)
                                         .build(ctx);
                                         severity_list
+                                    })
+                                    .with_child({
+                                        search_bar = SearchBarBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(200.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .build(ctx);
+                                        search_bar
                                     }),
                             )
                             .with_orientation(Orientation::Horizontal)
@@ -172,8 +283,12 @@ impl LogPanel {
             messages,
             clear,
             receiver: message_receiver,
+            buffer: Default::default(),
+            visible: Default::default(),
             severity: MessageKind::Warning,
             severity_list,
+            search_bar,
+            search_text: Default::default(),
             context_menu,
         }
     }
@@ -183,6 +298,8 @@ impl LogPanel {
 
         if let Some(ButtonMessage::Click) = message.data::<ButtonMessage>() {
             if message.destination() == self.clear {
+                self.buffer.clear();
+                self.visible.clear();
                 engine.user_interface.send_message(ListViewMessage::items(
                     self.messages,
                     MessageDirection::ToWidget,
@@ -201,54 +318,108 @@ impl LogPanel {
                     2 => self.severity = MessageKind::Error,
                     _ => (),
                 };
+                self.rebuild_items(engine);
+            }
+        } else if let Some(SearchBarMessage::Text(text)) = message.data() {
+            if message.destination() == self.search_bar
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.search_text = text.clone();
+                self.rebuild_items(engine);
             }
         }
 
         self.context_menu.handle_ui_message(message, engine);
     }
 
-    pub fn update(&mut self, engine: &mut Engine) {
-        let mut count = engine
-            .user_interface
-            .node(self.messages)
-            .cast::<ListView>()
-            .map(|v| v.items().len())
-            .unwrap_or_default();
+    fn make_item(
+        &self,
+        message: &LogMessage,
+        index: usize,
+        ctx: &mut BuildContext,
+    ) -> Handle<UiNode> {
+        let text = format!("[{:.2}s] {}", message.time.as_secs_f32(), message.content);
+
+        BorderBuilder::new(
+            WidgetBuilder::new()
+                .with_background(Brush::Solid(if index % 2 == 0 {
+                    Color::opaque(70, 70, 70)
+                } else {
+                    Color::opaque(40, 40, 40)
+                }))
+                .with_child(
+                    TextBuilder::new(
+                        WidgetBuilder::new()
+                            .with_context_menu(self.context_menu.menu.clone())
+                            .with_margin(Thickness::uniform(1.0))
+                            .with_foreground(Brush::Solid(match message.kind {
+                                MessageKind::Information => Color::opaque(210, 210, 210),
+                                MessageKind::Warning => Color::ORANGE,
+                                MessageKind::Error => Color::RED,
+                            })),
+                    )
+                    .with_text(text)
+                    .with_wrap(WrapMode::Word)
+                    .build(ctx),
+                ),
+        )
+        .build(ctx)
+    }
+
+    /// Re-creates the list of messages using current severity and search filters.
+    fn rebuild_items(&mut self, engine: &mut Engine) {
+        let ctx = &mut engine.user_interface.build_ctx();
+
+        self.visible = self
+            .buffer
+            .filtered(self.severity, &self.search_text)
+            .enumerate()
+            .map(|(index, (id, message))| (id, self.make_item(message, index, ctx)))
+            .collect();
+
+        engine.user_interface.send_message(ListViewMessage::items(
+            self.messages,
+            MessageDirection::ToWidget,
+            self.visible.iter().map(|(_, item)| *item).collect(),
+        ));
+    }
 
+    pub fn update(&mut self, engine: &mut Engine) {
         let mut item_to_bring_into_view = Handle::NONE;
 
         while let Ok(msg) = self.receiver.try_recv() {
-            if msg.kind < self.severity {
+            let passes = passes_filter(&msg, self.severity, &self.search_text);
+
+            let (id, discarded) = self.buffer.push(msg);
+
+            // Keep the list in sync with the buffer, so it won't grow infinitely.
+            if let Some(discarded) = discarded {
+                if self
+                    .visible
+                    .front()
+                    .map_or(false, |(id, _)| *id == discarded)
+                {
+                    let (_, item) = self.visible.pop_front().unwrap();
+                    engine
+                        .user_interface
+                        .send_message(ListViewMessage::remove_item(
+                            self.messages,
+                            MessageDirection::ToWidget,
+                            item,
+                        ));
+                }
+            }
+
+            if !passes {
                 continue;
             }
 
-            let text = format!("[{:.2}s] {}", msg.time.as_secs_f32(), msg.content);
-
-            let ctx = &mut engine.user_interface.build_ctx();
-            let item = BorderBuilder::new(
-                WidgetBuilder::new()
-                    .with_background(Brush::Solid(if count % 2 == 0 {
-                        Color::opaque(70, 70, 70)
-                    } else {
-                        Color::opaque(40, 40, 40)
-                    }))
-                    .with_child(
-                        TextBuilder::new(
-                            WidgetBuilder::new()
-                                .with_context_menu(self.context_menu.menu.clone())
-                                .with_margin(Thickness::uniform(1.0))
-                                .with_foreground(Brush::Solid(match msg.kind {
-                                    MessageKind::Information => Color::opaque(210, 210, 210),
-                                    MessageKind::Warning => Color::ORANGE,
-                                    MessageKind::Error => Color::RED,
-                                })),
-                        )
-                        .with_text(text)
-                        .with_wrap(WrapMode::Word)
-                        .build(ctx),
-                    ),
-            )
-            .build(ctx);
+            let message = self.buffer.get(id).unwrap();
+            let item = self.make_item(
+                message,
+                self.visible.len(),
+                &mut engine.user_interface.build_ctx(),
+            );
 
             engine
                 .user_interface
@@ -258,9 +429,9 @@ impl LogPanel {
                     item,
                 ));
 
-            item_to_bring_into_view = item;
+            self.visible.push_back((id, item));
 
-            count += 1;
+            item_to_bring_into_view = item;
         }
 
         if item_to_bring_into_view.is_some() {
@@ -274,3 +445,58 @@ impl LogPanel {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::log::LogBuffer;
+    use fyrox::core::log::{Log, LogMessage, MessageKind};
+    use std::{sync::mpsc, time::Duration};
+
+    fn message(kind: MessageKind, content: &str) -> LogMessage {
+        LogMessage {
+            kind,
+            content: content.to_string(),
+            time: Duration::default(),
+        }
+    }
+
+    #[test]
+    fn test_logged_message_appears_in_buffer() {
+        let (sender, receiver) = mpsc::channel();
+        Log::add_listener(sender);
+
+        Log::warn("Unable to find the door_key node!");
+
+        let mut buffer = LogBuffer::default();
+        buffer.receive(&receiver);
+
+        // The logger is global and other tests could write to it as well.
+        let (_, message) = buffer
+            .filtered(MessageKind::Information, "door_key")
+            .next()
+            .expect("The message must be in the buffer!");
+        assert!(message.kind == MessageKind::Warning);
+
+        assert_eq!(buffer.filtered(MessageKind::Error, "door_key").count(), 0);
+        assert_eq!(buffer.filtered(MessageKind::Warning, "DOOR_KEY").count(), 1);
+    }
+
+    #[test]
+    fn test_log_buffer_is_capped() {
+        let mut buffer = LogBuffer::new(2);
+
+        assert_eq!(
+            buffer.push(message(MessageKind::Information, "a")),
+            (0, None)
+        );
+        assert_eq!(buffer.push(message(MessageKind::Error, "b")), (1, None));
+        assert_eq!(
+            buffer.push(message(MessageKind::Warning, "c")),
+            (2, Some(0))
+        );
+
+        assert_eq!(buffer.len(), 2);
+        assert!(buffer.get(0).is_none());
+        assert_eq!(buffer.get(2).unwrap().content, "c");
+    }
+}
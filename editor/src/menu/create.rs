@@ -38,8 +38,10 @@ use fyrox::{
         },
         pivot::PivotBuilder,
         sound::{listener::ListenerBuilder, SoundBuilder},
+        spline::SplineBuilder,
         sprite::SpriteBuilder,
         terrain::{Layer, TerrainBuilder},
+        water::WaterBuilder,
     },
     utils::navmesh::Navmesh,
 };
@@ -79,23 +81,25 @@ impl CreateEntityRootMenu {
 }
 
 pub struct CreateEntityMenu {
-    create_pivot: Handle<UiNode>,
-    create_cube: Handle<UiNode>,
-    create_cone: Handle<UiNode>,
-    create_sphere: Handle<UiNode>,
-    create_cylinder: Handle<UiNode>,
-    create_quad: Handle<UiNode>,
-    create_decal: Handle<UiNode>,
-    create_point_light: Handle<UiNode>,
-    create_spot_light: Handle<UiNode>,
-    create_directional_light: Handle<UiNode>,
-    create_navmesh: Handle<UiNode>,
-    create_terrain: Handle<UiNode>,
-    create_camera: Handle<UiNode>,
-    create_sprite: Handle<UiNode>,
-    create_particle_system: Handle<UiNode>,
-    create_listener: Handle<UiNode>,
-    create_sound_source: Handle<UiNode>,
+    pub create_pivot: Handle<UiNode>,
+    pub create_cube: Handle<UiNode>,
+    pub create_cone: Handle<UiNode>,
+    pub create_sphere: Handle<UiNode>,
+    pub create_cylinder: Handle<UiNode>,
+    pub create_quad: Handle<UiNode>,
+    pub create_decal: Handle<UiNode>,
+    pub create_point_light: Handle<UiNode>,
+    pub create_spot_light: Handle<UiNode>,
+    pub create_directional_light: Handle<UiNode>,
+    pub create_navmesh: Handle<UiNode>,
+    pub create_spline: Handle<UiNode>,
+    pub create_water: Handle<UiNode>,
+    pub create_terrain: Handle<UiNode>,
+    pub create_camera: Handle<UiNode>,
+    pub create_sprite: Handle<UiNode>,
+    pub create_particle_system: Handle<UiNode>,
+    pub create_listener: Handle<UiNode>,
+    pub create_sound_source: Handle<UiNode>,
     physics_menu: PhysicsMenu,
     physics2d_menu: Physics2dMenu,
     dim2_menu: Dim2Menu,
@@ -116,6 +120,8 @@ impl CreateEntityMenu {
         let create_sprite;
         let create_decal;
         let create_navmesh;
+        let create_spline;
+        let create_water;
         let create_particle_system;
         let create_terrain;
         let create_pivot;
@@ -218,6 +224,14 @@ impl CreateEntityMenu {
                 create_navmesh = create_menu_item("Navmesh", vec![], ctx);
                 create_navmesh
             },
+            {
+                create_spline = create_menu_item("Spline", vec![], ctx);
+                create_spline
+            },
+            {
+                create_water = create_menu_item("Water", vec![], ctx);
+                create_water
+            },
         ];
 
         (
@@ -238,6 +252,8 @@ impl CreateEntityMenu {
                 create_sound_source,
                 create_listener,
                 create_navmesh,
+                create_spline,
+                create_water,
                 create_decal,
                 physics_menu,
                 physics2d_menu,
@@ -348,6 +364,18 @@ impl CreateEntityMenu {
                                 .with_navmesh(navmesh)
                                 .build_node(),
                         )
+                    } else if message.destination() == self.create_spline {
+                        Some(
+                            SplineBuilder::new(BaseBuilder::new().with_name("Spline"))
+                                .with_control_points(vec![
+                                    Vector3::new(-1.0, 0.0, 0.0),
+                                    Vector3::new(0.0, 0.0, 1.0),
+                                    Vector3::new(1.0, 0.0, 0.0),
+                                ])
+                                .build_node(),
+                        )
+                    } else if message.destination() == self.create_water {
+                        Some(WaterBuilder::new(BaseBuilder::new().with_name("Water")).build_node())
                     } else if message.destination() == self.create_sprite {
                         Some(
                             SpriteBuilder::new(BaseBuilder::new().with_name("Sprite")).build_node(),
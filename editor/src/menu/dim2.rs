@@ -2,24 +2,36 @@ use crate::menu::create_menu_item;
 use fyrox::{
     core::pool::Handle,
     gui::{menu::MenuItemMessage, message::UiMessage, BuildContext, UiNode},
-    scene::{base::BaseBuilder, dim2::rectangle::RectangleBuilder, node::Node},
+    scene::{
+        base::BaseBuilder,
+        dim2::{flipbook::FlipbookBuilder, rectangle::RectangleBuilder},
+        node::Node,
+    },
 };
 
 pub struct Dim2Menu {
     pub menu: Handle<UiNode>,
     create_sprite: Handle<UiNode>,
+    create_flipbook: Handle<UiNode>,
 }
 
 impl Dim2Menu {
     pub fn new(ctx: &mut BuildContext) -> Self {
         let create_sprite;
+        let create_flipbook;
 
         let menu = create_menu_item(
             "2D",
-            vec![{
-                create_sprite = create_menu_item("Rectangle (2D Sprite)", vec![], ctx);
-                create_sprite
-            }],
+            vec![
+                {
+                    create_sprite = create_menu_item("Rectangle (2D Sprite)", vec![], ctx);
+                    create_sprite
+                },
+                {
+                    create_flipbook = create_menu_item("Flipbook (2D Sprite Sheet)", vec![], ctx);
+                    create_flipbook
+                },
+            ],
             ctx,
         );
 
@@ -27,6 +39,7 @@ impl Dim2Menu {
             menu,
 
             create_sprite,
+            create_flipbook,
         }
     }
 
@@ -36,6 +49,12 @@ impl Dim2Menu {
                 let node =
                     RectangleBuilder::new(BaseBuilder::new().with_name("Sprite (2D)")).build_node();
                 Some(node)
+            } else if message.destination() == self.create_flipbook {
+                let node = FlipbookBuilder::new(RectangleBuilder::new(
+                    BaseBuilder::new().with_name("Flipbook (2D)"),
+                ))
+                .build_node();
+                Some(node)
             } else {
                 None
             }
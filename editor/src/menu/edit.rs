@@ -1,7 +1,8 @@
 use crate::{
-    menu::{create_menu_item_shortcut, create_root_menu_item},
+    menu::{create_menu_item, create_menu_item_shortcut, create_root_menu_item},
     message::MessageSender,
     scene::{commands::PasteCommand, EditorScene, Selection},
+    world::graph::{align::AlignWindow, array::ArrayDuplicationWindow, rename::BatchRenameWindow},
     Engine, Message, Mode,
 };
 use fyrox::{
@@ -20,6 +21,12 @@ pub struct EditMenu {
     redo: Handle<UiNode>,
     copy: Handle<UiNode>,
     paste: Handle<UiNode>,
+    batch_rename: Handle<UiNode>,
+    batch_rename_window: BatchRenameWindow,
+    duplicate_as_array: Handle<UiNode>,
+    array_duplication_window: ArrayDuplicationWindow,
+    align: Handle<UiNode>,
+    align_window: AlignWindow,
 }
 
 impl EditMenu {
@@ -28,6 +35,9 @@ impl EditMenu {
         let undo;
         let copy;
         let paste;
+        let batch_rename;
+        let duplicate_as_array;
+        let align;
         let menu = create_root_menu_item(
             "Edit",
             vec![
@@ -47,6 +57,18 @@ impl EditMenu {
                     paste = create_menu_item_shortcut("Paste", "Ctrl+V", vec![], ctx);
                     paste
                 },
+                {
+                    batch_rename = create_menu_item("Batch Rename...", vec![], ctx);
+                    batch_rename
+                },
+                {
+                    duplicate_as_array = create_menu_item("Duplicate As Array...", vec![], ctx);
+                    duplicate_as_array
+                },
+                {
+                    align = create_menu_item("Align And Distribute...", vec![], ctx);
+                    align
+                },
             ],
             ctx,
         );
@@ -57,6 +79,12 @@ impl EditMenu {
             redo,
             copy,
             paste,
+            batch_rename,
+            batch_rename_window: BatchRenameWindow::new(ctx),
+            duplicate_as_array,
+            array_duplication_window: ArrayDuplicationWindow::new(ctx),
+            align,
+            align_window: AlignWindow::new(ctx),
         }
     }
 
@@ -67,6 +95,13 @@ impl EditMenu {
         editor_scene: &mut EditorScene,
         engine: &mut Engine,
     ) {
+        self.batch_rename_window
+            .handle_ui_message(message, sender, editor_scene, engine);
+        self.array_duplication_window
+            .handle_ui_message(message, sender, editor_scene, engine);
+        self.align_window
+            .handle_ui_message(message, sender, editor_scene, engine);
+
         if let Some(MenuItemMessage::Click) = message.data::<MenuItemMessage>() {
             if message.destination() == self.copy {
                 if let Selection::Graph(selection) = &editor_scene.selection {
@@ -80,6 +115,18 @@ impl EditMenu {
                 if !editor_scene.clipboard.is_empty() {
                     sender.do_scene_command(PasteCommand::new(editor_scene.scene_content_root));
                 }
+            } else if message.destination() == self.batch_rename {
+                if let Selection::Graph(_) = editor_scene.selection {
+                    self.batch_rename_window.open(&engine.user_interface);
+                }
+            } else if message.destination() == self.duplicate_as_array {
+                if let Selection::Graph(_) = editor_scene.selection {
+                    self.array_duplication_window.open(&engine.user_interface);
+                }
+            } else if message.destination() == self.align {
+                if let Selection::Graph(_) = editor_scene.selection {
+                    self.align_window.open(&engine.user_interface);
+                }
             } else if message.destination() == self.undo {
                 sender.send(Message::UndoSceneCommand);
             } else if message.destination() == self.redo {
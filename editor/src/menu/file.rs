@@ -2,7 +2,7 @@ use crate::message::MessageSender;
 use crate::{
     make_save_file_selector, make_scene_file_filter,
     menu::{create_menu_item, create_menu_item_shortcut, create_root_menu_item},
-    scene::{is_scene_needs_to_be_saved, EditorScene},
+    scene::{is_scene_needs_to_be_saved, is_scene_revertible, EditorScene},
     settings::{recent::RecentFiles, Settings, SettingsWindow},
     Engine, Message, Mode, Panels, SaveSceneConfirmationDialogAction,
 };
@@ -12,24 +12,104 @@ use fyrox::{
         file_browser::{FileSelectorBuilder, FileSelectorMessage},
         menu::MenuItemMessage,
         message::{MessageDirection, UiMessage},
-        messagebox::{MessageBoxBuilder, MessageBoxButtons, MessageBoxMessage},
+        messagebox::{MessageBoxBuilder, MessageBoxButtons, MessageBoxMessage, MessageBoxResult},
         widget::{WidgetBuilder, WidgetMessage},
         window::{WindowBuilder, WindowMessage, WindowTitle},
         BuildContext, UiNode, UserInterface,
     },
 };
+use std::path::{Path, PathBuf};
+
+/// Asks a user whether an existing file should be overwritten, when a scene is saved using the save file
+/// selector. Choosing "No" returns the user back to the selector.
+pub struct OverwriteConfirmation {
+    pub message_box: Handle<UiNode>,
+    path: Option<PathBuf>,
+}
+
+impl OverwriteConfirmation {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let message_box = MessageBoxBuilder::new(
+            WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(100.0))
+                .open(false)
+                .with_title(WindowTitle::Text("Overwrite?".to_owned())),
+        )
+        .with_buttons(MessageBoxButtons::YesNo)
+        .build(ctx);
+
+        Self {
+            message_box,
+            path: None,
+        }
+    }
+
+    /// Saves the scene at the given path, or asks for a confirmation first if there's a file at the path
+    /// already.
+    pub fn try_save(&mut self, path: &Path, ui: &UserInterface, sender: &MessageSender) {
+        if path.exists() {
+            self.path = Some(path.to_owned());
+            ui.send_message(MessageBoxMessage::open(
+                self.message_box,
+                MessageDirection::ToWidget,
+                None,
+                Some(format!(
+                    "File {} already exists. Do you want to overwrite it?",
+                    path.display()
+                )),
+            ));
+        } else {
+            sender.send(Message::SaveScene(path.to_owned()));
+        }
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        save_file_selector: Handle<UiNode>,
+        ui: &UserInterface,
+        sender: &MessageSender,
+    ) {
+        if let Some(MessageBoxMessage::Close(result)) = message.data() {
+            if message.destination() != self.message_box {
+                return;
+            }
+
+            if let Some(path) = self.path.take() {
+                if *result == MessageBoxResult::Yes {
+                    sender.send(Message::SaveScene(path));
+                } else {
+                    ui.send_message(WindowMessage::open_modal(
+                        save_file_selector,
+                        MessageDirection::ToWidget,
+                        true,
+                    ));
+                    ui.send_message(FileSelectorMessage::path(
+                        save_file_selector,
+                        MessageDirection::ToWidget,
+                        path,
+                    ));
+                }
+            }
+        }
+    }
+}
 
 pub struct FileMenu {
     pub menu: Handle<UiNode>,
     new_scene: Handle<UiNode>,
     pub save: Handle<UiNode>,
     pub save_as: Handle<UiNode>,
+    export_selection: Handle<UiNode>,
     load: Handle<UiNode>,
+    pub revert: Handle<UiNode>,
+    revert_message_box: Handle<UiNode>,
     pub close_scene: Handle<UiNode>,
     exit: Handle<UiNode>,
     pub open_settings: Handle<UiNode>,
     configure: Handle<UiNode>,
     pub save_file_selector: Handle<UiNode>,
+    export_selection_file_selector: Handle<UiNode>,
+    pub overwrite_confirmation: OverwriteConfirmation,
     pub load_file_selector: Handle<UiNode>,
     configure_message: Handle<UiNode>,
     pub settings: SettingsWindow,
@@ -54,8 +134,10 @@ impl FileMenu {
         let new_scene;
         let save;
         let save_as;
+        let export_selection;
         let close_scene;
         let load;
+        let revert;
         let open_settings;
         let open_scene_settings;
         let configure;
@@ -73,6 +155,15 @@ impl FileMenu {
         .with_buttons(MessageBoxButtons::Ok)
         .build(ctx);
 
+        let revert_message_box = MessageBoxBuilder::new(
+            WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(100.0))
+                .open(false)
+                .with_title(WindowTitle::Text("Revert Scene".to_owned())),
+        )
+        .with_text("Do you really want to discard all unsaved changes and reload the scene?")
+        .with_buttons(MessageBoxButtons::YesNo)
+        .build(ctx);
+
         let recent_files = make_recent_files_items(ctx, &settings.recent);
 
         let menu = create_root_menu_item(
@@ -91,10 +182,18 @@ impl FileMenu {
                         create_menu_item_shortcut("Save Scene As...", "Ctrl+Shift+S", vec![], ctx);
                     save_as
                 },
+                {
+                    export_selection = create_menu_item("Export Selection...", vec![], ctx);
+                    export_selection
+                },
                 {
                     load = create_menu_item_shortcut("Load Scene...", "Ctrl+L", vec![], ctx);
                     load
                 },
+                {
+                    revert = create_menu_item("Revert Scene", vec![], ctx);
+                    revert
+                },
                 {
                     close_scene = create_menu_item_shortcut("Close Scene", "Ctrl+Q", vec![], ctx);
                     close_scene
@@ -125,6 +224,8 @@ impl FileMenu {
         );
 
         let save_file_selector = make_save_file_selector(ctx);
+        let overwrite_confirmation = OverwriteConfirmation::new(ctx);
+        let export_selection_file_selector = make_save_file_selector(ctx);
 
         let load_file_selector = FileSelectorBuilder::new(
             WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
@@ -136,13 +237,18 @@ impl FileMenu {
 
         Self {
             save_file_selector,
+            export_selection_file_selector,
+            overwrite_confirmation,
             load_file_selector,
             menu,
             new_scene,
             save,
             save_as,
+            export_selection,
             close_scene,
             load,
+            revert,
+            revert_message_box,
             exit,
             open_settings,
             configure,
@@ -200,12 +306,26 @@ impl FileMenu {
     ) {
         self.settings
             .handle_message(message, engine, settings, sender);
+        self.overwrite_confirmation.handle_ui_message(
+            message,
+            self.save_file_selector,
+            &engine.user_interface,
+            sender,
+        );
 
         if let Some(FileSelectorMessage::Commit(path)) = message.data::<FileSelectorMessage>() {
             if message.destination() == self.save_file_selector {
-                sender.send(Message::SaveScene(path.to_owned()));
+                self.overwrite_confirmation
+                    .try_save(path, &engine.user_interface, sender);
             } else if message.destination() == self.load_file_selector {
                 sender.send(Message::LoadScene(path.to_owned()));
+            } else if message.destination() == self.export_selection_file_selector {
+                sender.send(Message::SaveSelectionAsPrefab(path.to_owned()));
+            }
+        } else if let Some(MessageBoxMessage::Close(result)) = message.data() {
+            if message.destination() == self.revert_message_box && *result == MessageBoxResult::Yes
+            {
+                sender.send(Message::RevertScene);
             }
         } else if let Some(MenuItemMessage::Click) = message.data::<MenuItemMessage>() {
             if message.destination() == self.save {
@@ -243,6 +363,21 @@ impl FileMenu {
                         MessageDirection::ToWidget,
                         std::env::current_dir().unwrap(),
                     ));
+            } else if message.destination() == self.export_selection {
+                engine
+                    .user_interface
+                    .send_message(WindowMessage::open_modal(
+                        self.export_selection_file_selector,
+                        MessageDirection::ToWidget,
+                        true,
+                    ));
+                engine
+                    .user_interface
+                    .send_message(FileSelectorMessage::path(
+                        self.export_selection_file_selector,
+                        MessageDirection::ToWidget,
+                        std::env::current_dir().unwrap(),
+                    ));
             } else if message.destination() == self.load {
                 if is_scene_needs_to_be_saved(editor_scene.as_deref()) {
                     sender.send(Message::OpenSaveSceneConfirmationDialog(
@@ -251,6 +386,15 @@ impl FileMenu {
                 } else {
                     self.open_load_file_selector(&mut engine.user_interface);
                 }
+            } else if message.destination() == self.revert {
+                if is_scene_revertible(editor_scene.as_deref()) {
+                    engine.user_interface.send_message(MessageBoxMessage::open(
+                        self.revert_message_box,
+                        MessageDirection::ToWidget,
+                        None,
+                        None,
+                    ));
+                }
             } else if message.destination() == self.close_scene {
                 if is_scene_needs_to_be_saved(editor_scene.as_deref()) {
                     sender.send(Message::OpenSaveSceneConfirmationDialog(
@@ -317,3 +461,91 @@ impl FileMenu {
         ));
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        menu::file::OverwriteConfirmation,
+        message::{Message, MessageSender},
+    };
+    use fyrox::{
+        core::{algebra::Vector2, pool::Handle},
+        gui::{
+            file_browser::FileSelectorMessage,
+            message::MessageDirection,
+            messagebox::{MessageBoxMessage, MessageBoxResult},
+            window::WindowMessage,
+            UserInterface,
+        },
+    };
+    use std::sync::mpsc::channel;
+
+    #[test]
+    fn test_save_onto_existing_file_asks_for_confirmation() {
+        let mut ui = UserInterface::new(Vector2::new(800.0, 600.0));
+        let mut confirmation = OverwriteConfirmation::new(&mut ui.build_ctx());
+        while ui.poll_message().is_some() {}
+
+        let (sender, receiver) = channel();
+        let sender = MessageSender(sender);
+        let selector = Handle::new(123, 1);
+
+        let existing = std::env::temp_dir().join("fyroxed_overwrite_test.rgs");
+        std::fs::write(&existing, "data").unwrap();
+
+        confirmation.try_save(&existing, &ui, &sender);
+        assert!(receiver.try_recv().is_err());
+        let message = ui.poll_message().unwrap();
+        assert_eq!(message.destination(), confirmation.message_box);
+        assert!(matches!(
+            message.data(),
+            Some(MessageBoxMessage::Open { .. })
+        ));
+
+        // "No" returns back to the selector without saving.
+        confirmation.handle_ui_message(
+            &MessageBoxMessage::close(
+                confirmation.message_box,
+                MessageDirection::ToWidget,
+                MessageBoxResult::No,
+            ),
+            selector,
+            &ui,
+            &sender,
+        );
+        assert!(receiver.try_recv().is_err());
+        let message = ui.poll_message().unwrap();
+        assert_eq!(message.destination(), selector);
+        assert!(matches!(
+            message.data(),
+            Some(WindowMessage::OpenModal { .. })
+        ));
+        let message = ui.poll_message().unwrap();
+        assert_eq!(
+            message.data(),
+            Some(&FileSelectorMessage::Path(existing.clone()))
+        );
+
+        // "Yes" saves the scene.
+        confirmation.try_save(&existing, &ui, &sender);
+        confirmation.handle_ui_message(
+            &MessageBoxMessage::close(
+                confirmation.message_box,
+                MessageDirection::ToWidget,
+                MessageBoxResult::Yes,
+            ),
+            selector,
+            &ui,
+            &sender,
+        );
+        assert!(matches!(receiver.try_recv(), Ok(Message::SaveScene(path)) if path == existing));
+
+        // New files are saved immediately.
+        let new = std::env::temp_dir().join("fyroxed_overwrite_test_new.rgs");
+        let _ = std::fs::remove_file(&new);
+        confirmation.try_save(&new, &ui, &sender);
+        assert!(matches!(receiver.try_recv(), Ok(Message::SaveScene(path)) if path == new));
+
+        let _ = std::fs::remove_file(existing);
+    }
+}
@@ -5,7 +5,7 @@ use crate::{
         utils::UtilsMenu, view::ViewMenu,
     },
     message::MessageSender,
-    scene::EditorScene,
+    scene::{is_scene_revertible, EditorScene},
     send_sync_message,
     settings::Settings,
     AbsmEditor, CurveEditorWindow, Engine, Mode, SceneSettingsWindow,
@@ -30,6 +30,7 @@ pub mod physics;
 pub mod physics2d;
 pub mod utils;
 pub mod view;
+pub mod viewport;
 
 pub struct Menu {
     pub menu: Handle<UiNode>,
@@ -48,6 +49,7 @@ pub struct Panels<'b> {
     pub navmesh_panel: Handle<UiNode>,
     pub audio_panel: Handle<UiNode>,
     pub command_stack_panel: Handle<UiNode>,
+    pub camera_bookmarks_panel: Handle<UiNode>,
     pub inspector_window: Handle<UiNode>,
     pub world_outliner_window: Handle<UiNode>,
     pub asset_window: Handle<UiNode>,
@@ -159,6 +161,15 @@ impl Menu {
                 WidgetMessage::enabled(widget, MessageDirection::ToWidget, editor_scene.is_some()),
             );
         }
+
+        send_sync_message(
+            ui,
+            WidgetMessage::enabled(
+                self.file_menu.revert,
+                MessageDirection::ToWidget,
+                is_scene_revertible(editor_scene),
+            ),
+        );
     }
 
     pub fn handle_ui_message(&mut self, message: &UiMessage, mut ctx: MenuContext) {
@@ -19,6 +19,7 @@ pub struct ViewMenu {
     nav_mesh: Handle<UiNode>,
     audio: Handle<UiNode>,
     command_stack: Handle<UiNode>,
+    camera_bookmarks: Handle<UiNode>,
 }
 
 fn switch_window_state(window: Handle<UiNode>, ui: &UserInterface, center: bool) {
@@ -40,6 +41,7 @@ impl ViewMenu {
         let nav_mesh;
         let audio;
         let command_stack;
+        let camera_bookmarks;
         let menu = create_root_menu_item(
             "View",
             vec![
@@ -75,6 +77,10 @@ impl ViewMenu {
                     command_stack = create_menu_item("Command Stack Panel", vec![], ctx);
                     command_stack
                 },
+                {
+                    camera_bookmarks = create_menu_item("Camera Bookmarks", vec![], ctx);
+                    camera_bookmarks
+                },
             ],
             ctx,
         );
@@ -89,6 +95,7 @@ impl ViewMenu {
             nav_mesh,
             audio,
             command_stack,
+            camera_bookmarks,
         }
     }
 
@@ -110,6 +117,8 @@ impl ViewMenu {
                 switch_window_state(panels.audio_panel, ui, false);
             } else if message.destination() == self.command_stack {
                 switch_window_state(panels.command_stack_panel, ui, false);
+            } else if message.destination() == self.camera_bookmarks {
+                switch_window_state(panels.camera_bookmarks_panel, ui, true);
             }
         }
     }
@@ -0,0 +1,151 @@
+use crate::{
+    menu::create::CreateEntityMenu,
+    message::MessageSender,
+    scene::{commands::graph::AddNodeCommand, EditorScene},
+};
+use fyrox::{
+    core::{
+        algebra::{Vector2, Vector3},
+        pool::Handle,
+        scope_profile,
+    },
+    gui::{
+        menu::{MenuItemBuilder, MenuItemContent},
+        message::{MessageDirection, UiMessage},
+        popup::{Placement, PopupBuilder, PopupMessage},
+        stack_panel::StackPanelBuilder,
+        widget::WidgetBuilder,
+        BuildContext, UiNode, UserInterface,
+    },
+};
+
+/// A context menu of the scene viewer, that allows to quickly add new nodes at the clicked position.
+pub struct ViewportContextMenu {
+    pub menu: Handle<UiNode>,
+    create_entity_menu: CreateEntityMenu,
+    position: Vector3<f32>,
+}
+
+impl ViewportContextMenu {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let (create_entity_menu, create_entity_menu_root_items) = CreateEntityMenu::new(ctx);
+
+        let menu = PopupBuilder::new(WidgetBuilder::new().with_visibility(false))
+            .with_content(
+                StackPanelBuilder::new(
+                    WidgetBuilder::new().with_child(
+                        MenuItemBuilder::new(
+                            WidgetBuilder::new().with_min_size(Vector2::new(120.0, 22.0)),
+                        )
+                        .with_content(MenuItemContent::text("Add"))
+                        .with_items(create_entity_menu_root_items)
+                        .build(ctx),
+                    ),
+                )
+                .build(ctx),
+            )
+            .build(ctx);
+
+        Self {
+            menu,
+            create_entity_menu,
+            position: Default::default(),
+        }
+    }
+
+    /// Opens the menu at the cursor position. New nodes will be placed at the given world position.
+    pub fn open(&mut self, ui: &UserInterface, position: Vector3<f32>) {
+        self.position = position;
+
+        ui.send_message(PopupMessage::placement(
+            self.menu,
+            MessageDirection::ToWidget,
+            Placement::Cursor(Handle::NONE),
+        ));
+        ui.send_message(PopupMessage::open(self.menu, MessageDirection::ToWidget));
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &EditorScene,
+        sender: &MessageSender,
+    ) {
+        scope_profile!();
+
+        if let Some(mut node) = self.create_entity_menu.handle_ui_message(message) {
+            node.local_transform_mut().set_position(self.position);
+
+            sender.do_scene_command(AddNodeCommand::new(
+                node,
+                editor_scene.scene_content_root,
+                true,
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        command::CommandStack,
+        menu::viewport::ViewportContextMenu,
+        message::{Message, MessageSender},
+        scene::{commands::SceneContext, EditorScene},
+    };
+    use fyrox::{
+        core::algebra::{Vector2, Vector3},
+        gui::{menu::MenuItemMessage, message::MessageDirection, UserInterface},
+        scene::{camera::Camera, Scene},
+    };
+    use std::sync::mpsc::channel;
+
+    #[test]
+    fn test_add_node_at_clicked_position() {
+        let mut ui = UserInterface::new(Vector2::new(800.0, 600.0));
+        let mut menu = ViewportContextMenu::new(&mut ui.build_ctx());
+
+        let mut scene = Scene::new();
+        let root = scene.graph.get_root();
+        let mut editor_scene = EditorScene::new_test(&mut scene);
+
+        let (sender, receiver) = channel();
+        let sender = MessageSender(sender);
+
+        let position = Vector3::new(1.0, 0.0, 2.0);
+        menu.open(&ui, position);
+        menu.handle_ui_message(
+            &MenuItemMessage::click(
+                menu.create_entity_menu.create_camera,
+                MessageDirection::FromWidget,
+            ),
+            &editor_scene,
+            &sender,
+        );
+
+        let command = match receiver.try_recv() {
+            Ok(Message::DoSceneCommand(command)) => command,
+            _ => panic!("no command was sent"),
+        };
+
+        let mut stack = CommandStack::new(false);
+        let node_count = scene.graph.node_count();
+        stack.do_command(
+            command.into_inner(),
+            SceneContext::new_test(&mut editor_scene, &mut scene, sender.clone()),
+        );
+        assert_eq!(scene.graph.node_count(), node_count + 1);
+
+        let camera = *scene.graph[root].children().last().unwrap();
+        assert!(scene.graph[camera].cast::<Camera>().is_some());
+        assert_eq!(**scene.graph[camera].local_transform().position(), position);
+
+        stack.undo(SceneContext::new_test(
+            &mut editor_scene,
+            &mut scene,
+            sender.clone(),
+        ));
+        assert_eq!(scene.graph.node_count(), node_count);
+        assert!(!scene.graph.is_valid_handle(camera));
+    }
+}
@@ -9,6 +9,7 @@ use fyrox::{
     },
     gui::UiNode,
     material::SharedMaterial,
+    renderer::DebugView,
     scene::{camera::Projection, node::Node},
 };
 use std::{any::TypeId, path::PathBuf, sync::mpsc::Sender};
@@ -18,12 +19,19 @@ pub enum Message {
     DoSceneCommand(SceneCommand),
     UndoSceneCommand,
     RedoSceneCommand,
+    /// Undoes or redoes commands until the command with the given index becomes the top of the
+    /// command stack. `None` reverts every command.
+    JumpToSceneCommand(Option<usize>),
     ClearSceneCommandStack,
     SelectionChanged {
         old_selection: Selection,
     },
     SaveScene(PathBuf),
     LoadScene(PathBuf),
+    /// Discards all unsaved changes of the current scene and reloads it from its file.
+    RevertScene,
+    /// Replays the command journal left by the previous session, which was terminated abnormally.
+    RecoverFromJournal,
     CloseScene,
     SetInteractionMode(InteractionModeKind),
     Configure {
@@ -48,15 +56,28 @@ pub enum Message {
         handle: ErasedHandle,
     },
     FocusObject(Handle<Node>),
+    /// Hides the nodes in the viewport (or shows them back, if they're hidden already). This is an
+    /// editor-only state, it does not affect saved scene.
+    ToggleNodesHidden(Vec<Handle<Node>>),
+    /// Renders only selected nodes in the viewport (or renders everything back, if isolation is active
+    /// already). This is an editor-only state, it does not affect saved scene.
+    ToggleSelectionIsolation,
     SetEditorCameraProjection(Projection),
+    /// Changes debug view of the editor camera. This is an editor-only state, it does not affect saved
+    /// scene and other cameras.
+    SetDebugView(DebugView),
     SwitchToBuildMode,
     SwitchToEditMode,
+    /// Enters simulation mode (see [`crate::Mode::Simulation`]) or leaves it, if it is active already.
+    ToggleSimulation,
     SwitchMode,
     OpenLoadSceneDialog,
     OpenSaveSceneDialog,
     OpenSaveSceneConfirmationDialog(SaveSceneConfirmationDialogAction),
     SetBuildProfile(BuildProfile),
     SaveSelectionAsPrefab(PathBuf),
+    /// Adds instances of the models with the given paths to the current scene in one undoable step.
+    InstantiateModels(Vec<PathBuf>),
     SyncNodeHandleName {
         view: Handle<UiNode>,
         handle: Handle<Node>,
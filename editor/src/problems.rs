@@ -0,0 +1,213 @@
+use fyrox::{
+    core::{log::Log, pool::Handle, uuid::Uuid},
+    fxhash::FxHashMap,
+    gui::{
+        formatted_text::WrapMode,
+        message::MessageDirection,
+        scroll_viewer::ScrollViewerBuilder,
+        text::TextMessage,
+        text_box::TextBoxBuilder,
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, Thickness, UiNode, UserInterface,
+    },
+    scene::graph::Graph,
+};
+use std::fmt::Write;
+
+/// A script type that is referenced by scene nodes, but is not registered in the serialization context
+/// (usually because the plugin with the script is not loaded). Data of such scripts is preserved, but
+/// the scripts do nothing until their type is registered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingScript {
+    pub type_uuid: Uuid,
+    pub node_count: usize,
+}
+
+/// Returns every missing script type referenced in the graph with the amount of nodes that use it. The
+/// types are sorted by their uuids, so the order is stable.
+pub fn find_missing_scripts(graph: &Graph) -> Vec<MissingScript> {
+    let mut counters = FxHashMap::<Uuid, usize>::default();
+    for node in graph.linear_iter() {
+        if let Some(unresolved) = node.unresolved_script() {
+            *counters.entry(unresolved.type_uuid()).or_default() += 1;
+        }
+    }
+
+    let mut missing = counters
+        .into_iter()
+        .map(|(type_uuid, node_count)| MissingScript {
+            type_uuid,
+            node_count,
+        })
+        .collect::<Vec<_>>();
+    missing.sort_by_key(|script| script.type_uuid);
+    missing
+}
+
+/// Makes a human-readable warning for the given set of missing scripts. Returns `None` if there's no
+/// missing scripts.
+pub fn missing_scripts_warning(missing: &[MissingScript]) -> Option<String> {
+    if missing.is_empty() {
+        return None;
+    }
+
+    let node_count = missing
+        .iter()
+        .map(|script| script.node_count)
+        .sum::<usize>();
+    let mut warning = format!(
+        "The scene references {} script type(s) that are not registered, {} node(s) are affected. \
+        Make sure that the game plugin is loaded, the data of the scripts is preserved until then.\n",
+        missing.len(),
+        node_count
+    );
+    for script in missing {
+        writeln!(
+            warning,
+            "- {}: {} node(s)",
+            script.type_uuid, script.node_count
+        )
+        .unwrap();
+    }
+
+    Some(warning)
+}
+
+/// A window that lists problems of the current scene, that were found when the scene was opened.
+pub struct ProblemsPanel {
+    pub window: Handle<UiNode>,
+    text: Handle<UiNode>,
+}
+
+impl ProblemsPanel {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let text;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(400.0).with_height(200.0))
+            .open(false)
+            .with_content(
+                ScrollViewerBuilder::new(WidgetBuilder::new())
+                    .with_content({
+                        text = TextBoxBuilder::new(
+                            WidgetBuilder::new().with_margin(Thickness::uniform(3.0)),
+                        )
+                        .with_editable(false)
+                        .with_wrap(WrapMode::Word)
+                        .build(ctx);
+                        text
+                    })
+                    .build(ctx),
+            )
+            .with_title(WindowTitle::text("Problems"))
+            .build(ctx);
+        Self { window, text }
+    }
+
+    /// Checks the graph for problems and shows them in the panel. The panel is closed if there's no
+    /// problems.
+    pub fn report(&self, graph: &Graph, ui: &UserInterface) {
+        match missing_scripts_warning(&find_missing_scripts(graph)) {
+            Some(warning) => {
+                Log::warn(&warning);
+
+                ui.send_message(TextMessage::text(
+                    self.text,
+                    MessageDirection::ToWidget,
+                    warning,
+                ));
+                ui.send_message(WindowMessage::open(
+                    self.window,
+                    MessageDirection::ToWidget,
+                    true,
+                ));
+            }
+            None => {
+                ui.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::problems::{find_missing_scripts, missing_scripts_warning, MissingScript};
+    use fyrox::{
+        asset::manager::ResourceManager,
+        core::{
+            reflect::prelude::*,
+            uuid::{uuid, Uuid},
+            visitor::prelude::*,
+            TypeUuidProvider,
+        },
+        engine::SerializationContext,
+        impl_component_provider,
+        scene::{base::BaseBuilder, pivot::PivotBuilder, Scene},
+        script::{Script, ScriptTrait},
+    };
+    use std::sync::Arc;
+
+    #[derive(Reflect, Visit, Default, Debug, Clone)]
+    struct Enemy {
+        health: f32,
+    }
+
+    impl_component_provider!(Enemy);
+
+    impl TypeUuidProvider for Enemy {
+        fn type_uuid() -> Uuid {
+            uuid!("0f6c2a8e-4d1b-4f3a-9b7e-2c5d8a1e6f40")
+        }
+    }
+
+    impl ScriptTrait for Enemy {
+        fn id(&self) -> Uuid {
+            Self::type_uuid()
+        }
+    }
+
+    #[test]
+    fn test_missing_scripts_are_reported() {
+        let mut scene = Scene::new();
+        for _ in 0..2 {
+            PivotBuilder::new(BaseBuilder::new().with_script(Script::new(Enemy { health: 100.0 })))
+                .build(&mut scene.graph);
+        }
+        PivotBuilder::new(BaseBuilder::new()).build(&mut scene.graph);
+
+        // The plugin with the script is not loaded, so its type is not registered.
+        let mut scene = scene
+            .snapshot()
+            .unwrap()
+            .restore(
+                Arc::new(SerializationContext::new()),
+                ResourceManager::new(),
+            )
+            .unwrap();
+
+        let missing = find_missing_scripts(&scene.graph);
+        assert_eq!(
+            missing,
+            vec![MissingScript {
+                type_uuid: Enemy::type_uuid(),
+                node_count: 2
+            }]
+        );
+
+        let warning = missing_scripts_warning(&missing).unwrap();
+        assert!(warning.contains(&Enemy::type_uuid().to_string()));
+        assert!(warning.contains("2 node(s)"));
+
+        // Nothing to warn about, when the type is registered.
+        let context = Arc::new(SerializationContext::new());
+        context.script_constructors.add::<Enemy>("Enemy");
+        let scene = scene
+            .snapshot()
+            .unwrap()
+            .restore(context, ResourceManager::new())
+            .unwrap();
+        assert!(missing_scripts_warning(&find_missing_scripts(&scene.graph)).is_none());
+    }
+}
@@ -1,6 +1,9 @@
 use crate::{
-    command::Command, scene::commands::SceneContext, scene::Selection,
-    world::graph::selection::GraphSelection, Message,
+    command::{journal::JournalEntry, Command},
+    scene::commands::SceneContext,
+    scene::Selection,
+    world::graph::selection::GraphSelection,
+    Message,
 };
 use fyrox::{
     core::{
@@ -57,6 +60,46 @@ impl Command for MoveNodeCommand {
         let position = self.swap();
         self.set_position(&mut context.scene.graph, position);
     }
+
+    fn journal_entries(&self, context: &SceneContext) -> Option<Vec<JournalEntry>> {
+        JournalEntry::set_node_position(context, self.node, self.new_position).map(|e| vec![e])
+    }
+}
+
+#[derive(Debug)]
+pub struct SetNodeNameCommand {
+    node: Handle<Node>,
+    name: String,
+}
+
+impl SetNodeNameCommand {
+    pub fn new(node: Handle<Node>, name: String) -> Self {
+        Self { node, name }
+    }
+
+    fn swap(&mut self, graph: &mut Graph) {
+        let node = &mut graph[self.node];
+        let old = node.name_owned();
+        node.set_name(std::mem::replace(&mut self.name, old));
+    }
+}
+
+impl Command for SetNodeNameCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Set Node Name".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.swap(&mut context.scene.graph);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(&mut context.scene.graph);
+    }
+
+    fn journal_entries(&self, context: &SceneContext) -> Option<Vec<JournalEntry>> {
+        JournalEntry::set_node_name(context, self.node, &self.name).map(|e| vec![e])
+    }
 }
 
 #[derive(Debug)]
@@ -100,6 +143,10 @@ impl Command for ScaleNodeCommand {
         let scale = self.swap();
         self.set_scale(&mut context.scene.graph, scale);
     }
+
+    fn journal_entries(&self, context: &SceneContext) -> Option<Vec<JournalEntry>> {
+        JournalEntry::set_node_scale(context, self.node, self.new_scale).map(|e| vec![e])
+    }
 }
 
 #[derive(Debug)]
@@ -149,23 +196,47 @@ impl Command for RotateNodeCommand {
         let rotation = self.swap();
         self.set_rotation(&mut context.scene.graph, rotation);
     }
+
+    fn journal_entries(&self, context: &SceneContext) -> Option<Vec<JournalEntry>> {
+        JournalEntry::set_node_rotation(context, self.node, self.new_rotation).map(|e| vec![e])
+    }
 }
 
+/// Attaches a node to a new parent, keeping its global position and rotation.
 #[derive(Debug)]
 pub struct LinkNodesCommand {
     child: Handle<Node>,
     parent: Handle<Node>,
+    // Local position and rotation of the child that must be restored on the next link. It is
+    // empty until the first execution, which calculates the new local transform from the global one.
+    transform: Option<(Vector3<f32>, UnitQuaternion<f32>)>,
 }
 
 impl LinkNodesCommand {
     pub fn new(child: Handle<Node>, parent: Handle<Node>) -> Self {
-        Self { child, parent }
+        Self {
+            child,
+            parent,
+            transform: None,
+        }
     }
 
     fn link(&mut self, graph: &mut Graph) {
         let old_parent = graph[self.child].parent();
-        graph.link_nodes(self.child, self.parent);
+        let local_transform = graph[self.child].local_transform();
+        let old_transform = (**local_transform.position(), **local_transform.rotation());
+        match self.transform.take() {
+            Some((position, rotation)) => {
+                graph[self.child]
+                    .local_transform_mut()
+                    .set_position(position)
+                    .set_rotation(rotation);
+                graph.link_nodes(self.child, self.parent);
+            }
+            None => graph.link_nodes_keep_global_position_rotation(self.child, self.parent),
+        }
         self.parent = old_parent;
+        self.transform = Some(old_transform);
     }
 }
 
@@ -183,6 +254,43 @@ impl Command for LinkNodesCommand {
     }
 }
 
+#[derive(Debug)]
+pub struct SetChildIndexCommand {
+    parent: Handle<Node>,
+    child: Handle<Node>,
+    index: usize,
+}
+
+impl SetChildIndexCommand {
+    pub fn new(parent: Handle<Node>, child: Handle<Node>, index: usize) -> Self {
+        Self {
+            parent,
+            child,
+            index,
+        }
+    }
+
+    fn swap(&mut self, graph: &mut Graph) {
+        if let Some(old_index) = graph.set_child_index(self.parent, self.child, self.index) {
+            self.index = old_index;
+        }
+    }
+}
+
+impl Command for SetChildIndexCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Set Child Index".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.swap(&mut context.scene.graph);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(&mut context.scene.graph);
+    }
+}
+
 #[derive(Debug)]
 pub struct DeleteNodeCommand {
     handle: Handle<Node>,
@@ -311,6 +419,84 @@ impl Command for DeleteSubGraphCommand {
     }
 }
 
+#[derive(Debug)]
+enum DuplicateNodeCommandState {
+    Undefined,
+    NonExecuted,
+    Executed { copy: Handle<Node> },
+    Reverted { sub_graph: SubGraph },
+}
+
+/// Creates a deep copy of a node hierarchy (including scripts) next to the source node and places
+/// the copy at the given local position and rotation.
+#[derive(Debug)]
+pub struct DuplicateNodeCommand {
+    source: Handle<Node>,
+    position: Vector3<f32>,
+    rotation: UnitQuaternion<f32>,
+    state: DuplicateNodeCommandState,
+}
+
+impl DuplicateNodeCommand {
+    pub fn new(
+        source: Handle<Node>,
+        position: Vector3<f32>,
+        rotation: UnitQuaternion<f32>,
+    ) -> Self {
+        Self {
+            source,
+            position,
+            rotation,
+            state: DuplicateNodeCommandState::NonExecuted,
+        }
+    }
+}
+
+impl Command for DuplicateNodeCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Duplicate Node".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        let graph = &mut context.scene.graph;
+        match std::mem::replace(&mut self.state, DuplicateNodeCommandState::Undefined) {
+            DuplicateNodeCommandState::NonExecuted => {
+                let (copy, _) = graph.copy_node_inplace(self.source, &mut |_, _| true);
+                let parent = graph[self.source].parent();
+                graph.link_nodes(copy, parent);
+                graph[copy]
+                    .local_transform_mut()
+                    .set_position(self.position)
+                    .set_rotation(self.rotation);
+                self.state = DuplicateNodeCommandState::Executed { copy };
+            }
+            DuplicateNodeCommandState::Reverted { sub_graph } => {
+                let copy = graph.put_sub_graph_back(sub_graph);
+                self.state = DuplicateNodeCommandState::Executed { copy };
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        if let DuplicateNodeCommandState::Executed { copy } =
+            std::mem::replace(&mut self.state, DuplicateNodeCommandState::Undefined)
+        {
+            self.state = DuplicateNodeCommandState::Reverted {
+                sub_graph: context.scene.graph.take_reserve_sub_graph(copy),
+            };
+        }
+    }
+
+    fn finalize(&mut self, context: &mut SceneContext) {
+        if let DuplicateNodeCommandState::Reverted { sub_graph } =
+            std::mem::replace(&mut self.state, DuplicateNodeCommandState::Undefined)
+        {
+            context.scene.graph.forget_sub_graph(sub_graph);
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct AddNodeCommand {
     ticket: Option<Ticket<Node>>,
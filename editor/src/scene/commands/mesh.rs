@@ -1,7 +1,7 @@
 use crate::{command::Command, scene::commands::SceneContext};
 use fyrox::{
     core::{pool::Handle, sstorage::ImmutableString},
-    material::{shader::SamplerFallback, PropertyValue},
+    material::{shader::SamplerFallback, PropertyValue, SharedMaterial},
     resource::texture::TextureResource,
     scene::{mesh::Mesh, node::Node},
 };
@@ -105,3 +105,49 @@ impl Command for SetMeshTextureCommand {
         }
     }
 }
+
+#[derive(Debug)]
+pub struct SetMeshSurfaceMaterialCommand {
+    node: Handle<Node>,
+    surface_index: usize,
+    material: SharedMaterial,
+}
+
+impl SetMeshSurfaceMaterialCommand {
+    pub fn new(node: Handle<Node>, surface_index: usize, material: SharedMaterial) -> Self {
+        Self {
+            node,
+            surface_index,
+            material,
+        }
+    }
+
+    pub fn node(&self) -> Handle<Node> {
+        self.node
+    }
+
+    pub fn surface_index(&self) -> usize {
+        self.surface_index
+    }
+
+    fn swap(&mut self, context: &mut SceneContext) {
+        let mesh: &mut Mesh = context.scene.graph[self.node].as_mesh_mut();
+        let surface = &mut mesh.surfaces_mut()[self.surface_index];
+        let old_material = surface.material().clone();
+        surface.set_material(std::mem::replace(&mut self.material, old_material));
+    }
+}
+
+impl Command for SetMeshSurfaceMaterialCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Set Surface Material".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+}
@@ -1,22 +1,28 @@
 use crate::message::MessageSender;
 use crate::{
-    command::Command,
+    command::{journal::JournalEntry, Command},
     define_universal_commands,
     scene::{
-        clipboard::DeepCloneResult, commands::graph::DeleteSubGraphCommand, EditorScene,
-        GraphSelection, Selection,
+        clipboard::DeepCloneResult,
+        commands::graph::{AddModelCommand, DeleteSubGraphCommand},
+        EditorScene, GraphSelection, Selection,
     },
     Engine, Message,
 };
 use fyrox::core::variable::mark_inheritable_properties_non_modified;
 use fyrox::{
     asset::manager::ResourceManager,
-    core::{log::Log, pool::Handle, reflect::prelude::*},
+    core::{
+        algebra::Vector3, futures::executor::block_on, log::Log,
+        math::aabb::AxisAlignedBoundingBox, pool::Handle, reflect::prelude::*,
+    },
     engine::SerializationContext,
+    resource::model::{Model, ModelResourceExtension},
     scene::{graph::SubGraph, node::Node, Scene},
 };
 use std::{
     ops::{Deref, DerefMut},
+    path::PathBuf,
     sync::Arc,
 };
 
@@ -25,7 +31,9 @@ pub mod graph;
 pub mod material;
 pub mod mesh;
 pub mod navmesh;
+pub mod post_processing;
 pub mod sound_context;
+pub mod spline;
 pub mod terrain;
 
 #[macro_export]
@@ -49,6 +57,24 @@ pub struct SceneContext<'a> {
     pub serialization_context: Arc<SerializationContext>,
 }
 
+#[cfg(test)]
+impl<'a> SceneContext<'a> {
+    /// Creates a context for tests with a fresh resource manager and serialization context.
+    pub(crate) fn new_test(
+        editor_scene: &'a mut EditorScene,
+        scene: &'a mut Scene,
+        message_sender: MessageSender,
+    ) -> Self {
+        Self {
+            editor_scene,
+            scene,
+            message_sender,
+            resource_manager: ResourceManager::new(),
+            serialization_context: Arc::new(SerializationContext::new()),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct SceneCommand(pub Box<dyn Command>);
 
@@ -91,6 +117,14 @@ impl CommandGroup {
     pub fn push(&mut self, command: SceneCommand) {
         self.commands.push(command)
     }
+
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
 }
 
 impl Command for CommandGroup {
@@ -121,6 +155,14 @@ impl Command for CommandGroup {
             cmd.finalize(context);
         }
     }
+
+    fn journal_entries(&self, context: &SceneContext) -> Option<Vec<JournalEntry>> {
+        let mut entries = Vec::new();
+        for cmd in self.commands.iter() {
+            entries.extend(cmd.journal_entries(context)?);
+        }
+        Some(entries)
+    }
 }
 
 /// Creates scene command (command group) which removes current selection in editor's scene.
@@ -168,6 +210,84 @@ pub fn make_delete_selection_command(editor_scene: &EditorScene, engine: &Engine
     SceneCommand::new(command_group)
 }
 
+/// Loads models from the given paths and creates scene command (command group) which adds instances
+/// of the models to editor's scene and selects them, so the whole batch could be undone in one step.
+/// Instances are placed in a row along X axis, so they won't overlap each other. A model that failed
+/// to load is reported to the log and skipped, the rest of the batch is still instantiated. Returns
+/// `None` if none of the models were loaded.
+pub fn make_instantiate_models_command(
+    paths: &[PathBuf],
+    editor_scene: &EditorScene,
+    scene: &mut Scene,
+    resource_manager: &ResourceManager,
+    instantiation_scale: Vector3<f32>,
+) -> Option<SceneCommand> {
+    // Gap between neighbouring instances.
+    const SPACING: f32 = 1.0;
+
+    let mut commands = Vec::new();
+    let mut instances = Vec::new();
+    let mut offset = 0.0;
+    for path in paths {
+        let model = match block_on(resource_manager.request::<Model, _>(path)) {
+            Ok(model) => model,
+            Err(err) => {
+                Log::err(format!(
+                    "Unable to instantiate {} model. Reason: {:?}",
+                    path.display(),
+                    err
+                ));
+                continue;
+            }
+        };
+
+        let instance = model.instantiate(scene);
+        scene
+            .graph
+            .link_nodes(instance, editor_scene.scene_content_root);
+        scene.graph[instance]
+            .local_transform_mut()
+            .set_scale(instantiation_scale);
+        scene
+            .graph
+            .update_hierarchical_data_for_descendants(instance);
+
+        let mut aabb = AxisAlignedBoundingBox::default();
+        for descendant in scene.graph.traverse_iter(instance) {
+            let descendant_aabb = descendant.local_bounding_box();
+            if !descendant_aabb.is_invalid_or_degenerate() {
+                aabb.add_box(descendant_aabb.transform(&descendant.global_transform()))
+            }
+        }
+        let (min_x, width) = if aabb.is_invalid_or_degenerate() {
+            (0.0, 0.0)
+        } else {
+            (aabb.min.x, aabb.max.x - aabb.min.x)
+        };
+        scene.graph[instance]
+            .local_transform_mut()
+            .set_position(Vector3::new(offset - min_x, 0.0, 0.0));
+        offset += width + SPACING;
+
+        // Extract the instance from the scene, the command will put it back. This is required to not
+        // violate the rule of one place of execution, only commands allowed to modify the scene.
+        let sub_graph = scene.graph.take_reserve_sub_graph(instance);
+        commands.push(SceneCommand::new(AddModelCommand::new(sub_graph)));
+        instances.push(instance);
+    }
+
+    if instances.is_empty() {
+        return None;
+    }
+
+    commands.push(SceneCommand::new(ChangeSelectionCommand::new(
+        Selection::Graph(GraphSelection::from_list(instances)),
+        editor_scene.selection.clone(),
+    )));
+
+    Some(SceneCommand::new(CommandGroup::from(commands)))
+}
+
 #[derive(Debug)]
 pub struct ChangeSelectionCommand {
     new_selection: Selection,
@@ -225,6 +345,10 @@ impl Command for ChangeSelectionCommand {
                 .send(Message::SelectionChanged { old_selection });
         }
     }
+    fn journal_entries(&self, _context: &SceneContext) -> Option<Vec<JournalEntry>> {
+        // Selection is not a part of the scene.
+        Some(Vec::new())
+    }
 }
 
 #[derive(Debug)]
@@ -479,3 +603,107 @@ define_universal_commands!(
     self,
     { &mut ctx.scene.graph[self.handle] as &mut dyn Reflect },
 );
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        message::MessageSender,
+        scene::{
+            commands::{make_instantiate_models_command, SceneContext},
+            EditorScene, Selection,
+        },
+    };
+    use fyrox::{
+        asset::manager::ResourceManager,
+        core::{algebra::Vector3, visitor::Visitor},
+        engine::SerializationContext,
+        resource::model::{loader::ModelLoader, Model},
+        scene::{base::BaseBuilder, pivot::PivotBuilder, Scene},
+    };
+    use std::sync::{mpsc::channel, Arc};
+
+    fn save_model(name: &str) -> std::path::PathBuf {
+        let mut scene = Scene::new();
+        PivotBuilder::new(BaseBuilder::new().with_name(name)).build(&mut scene.graph);
+        let path = std::env::temp_dir().join(format!("fyroxed_batch_import_{}.rgs", name));
+        let mut visitor = Visitor::new();
+        scene.save("Scene", &mut visitor).unwrap();
+        visitor.save_binary(&path).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_batch_model_import_is_undone_in_one_step() {
+        let serialization_context = Arc::new(SerializationContext::new());
+        let resource_manager = ResourceManager::new();
+        {
+            let mut state = resource_manager.state();
+            state.constructors_container.add::<Model>();
+            state.loaders.set(ModelLoader {
+                resource_manager: resource_manager.clone(),
+                serialization_context: serialization_context.clone(),
+                default_import_options: Default::default(),
+            });
+        }
+
+        let mut scene = Scene::new();
+        let root = scene.graph.get_root();
+        let mut editor_scene = EditorScene::new_test(&mut scene);
+        let node_count = scene.graph.node_count();
+
+        // A file that fails to load does not abort the batch.
+        let paths = vec![
+            save_model("A"),
+            std::env::temp_dir().join("fyroxed_batch_import_missing.rgs"),
+            save_model("B"),
+        ];
+        let mut command = make_instantiate_models_command(
+            &paths,
+            &editor_scene,
+            &mut scene,
+            &resource_manager,
+            Vector3::new(1.0, 1.0, 1.0),
+        )
+        .unwrap();
+
+        let (sender, _receiver) = channel();
+        let mut context = SceneContext {
+            editor_scene: &mut editor_scene,
+            scene: &mut scene,
+            message_sender: MessageSender(sender),
+            resource_manager: resource_manager.clone(),
+            serialization_context,
+        };
+
+        command.execute(&mut context);
+        let instances = if let Selection::Graph(selection) = &context.editor_scene.selection {
+            selection.nodes().to_vec()
+        } else {
+            panic!("Instances must be selected")
+        };
+        assert_eq!(instances.len(), 2);
+        let added = instances
+            .iter()
+            .map(|instance| context.scene.graph.traverse_handle_iter(*instance).count())
+            .sum::<usize>();
+        assert_eq!(context.scene.graph.node_count(), node_count + added);
+        let positions = instances
+            .iter()
+            .map(|instance| {
+                assert_eq!(context.scene.graph[*instance].parent(), root);
+                **context.scene.graph[*instance].local_transform().position()
+            })
+            .collect::<Vec<_>>();
+        // The instances do not overlap.
+        assert_ne!(positions[0], positions[1]);
+
+        // Single undo removes the whole batch.
+        command.revert(&mut context);
+        assert_eq!(context.scene.graph.node_count(), node_count);
+        assert!(matches!(context.editor_scene.selection, Selection::None));
+
+        for path in paths {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{Command, SceneContext};
+
+macro_rules! define_post_processing_command {
+    ($($name:ident($human_readable_name:expr, $value_type:ty, $($field:ident).+); )*) => {
+        $(
+            #[derive(Debug)]
+            pub struct $name {
+                value: $value_type,
+            }
+
+            impl $name {
+                pub fn new(value: $value_type) -> Self {
+                    Self { value }
+                }
+
+                fn swap(&mut self, context: &mut SceneContext) {
+                    std::mem::swap(
+                        &mut context.scene.post_processing.$($field).+,
+                        &mut self.value,
+                    );
+                }
+            }
+
+            impl Command for $name {
+                fn name(&mut self, _context: &SceneContext) -> String {
+                    $human_readable_name.to_owned()
+                }
+
+                fn execute(&mut self, context: &mut SceneContext) {
+                    self.swap(context);
+                }
+
+                fn revert(&mut self, context: &mut SceneContext) {
+                    self.swap(context);
+                }
+            }
+        )*
+    };
+}
+
+define_post_processing_command! {
+    SetSsaoEnabledCommand("Set SSAO Enabled", bool, ssao.enabled);
+    SetSsaoRadiusCommand("Set SSAO Radius", Option<f32>, ssao.radius);
+    SetBloomEnabledCommand("Set Bloom Enabled", bool, bloom.enabled);
+    SetBloomThresholdCommand("Set Bloom Threshold", f32, bloom.threshold);
+    SetToneMappingEnabledCommand("Set Tone Mapping Enabled", bool, tone_mapping.enabled);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        command::Command,
+        message::MessageSender,
+        scene::{
+            commands::{
+                post_processing::{SetBloomEnabledCommand, SetSsaoRadiusCommand},
+                SceneContext,
+            },
+            EditorScene,
+        },
+    };
+    use fyrox::{renderer::QualitySettings, scene::Scene};
+    use std::sync::mpsc::channel;
+
+    #[test]
+    fn test_bloom_toggle_undo_redo() {
+        let mut scene = Scene::new();
+        let mut editor_scene = EditorScene::new_test(&mut scene);
+
+        let (sender, _receiver) = channel();
+        let mut context =
+            SceneContext::new_test(&mut editor_scene, &mut scene, MessageSender(sender));
+
+        // Settings, that will be used by the renderer to draw the scene.
+        let renderer_uses_bloom = |context: &SceneContext| {
+            QualitySettings::ultra()
+                .with_post_processing(&context.scene.post_processing)
+                .use_bloom
+        };
+
+        assert!(renderer_uses_bloom(&context));
+
+        let mut command = SetBloomEnabledCommand::new(false);
+        command.execute(&mut context);
+        assert!(!context.scene.post_processing.bloom.enabled);
+        assert!(!renderer_uses_bloom(&context));
+
+        // Undo
+        command.revert(&mut context);
+        assert!(context.scene.post_processing.bloom.enabled);
+        assert!(renderer_uses_bloom(&context));
+
+        // Redo
+        command.execute(&mut context);
+        assert!(!renderer_uses_bloom(&context));
+    }
+
+    #[test]
+    fn test_ssao_radius_overrides_quality_settings_only_if_set() {
+        let mut scene = Scene::new();
+        let mut editor_scene = EditorScene::new_test(&mut scene);
+
+        let (sender, _receiver) = channel();
+        let mut context =
+            SceneContext::new_test(&mut editor_scene, &mut scene, MessageSender(sender));
+
+        let quality_settings = QualitySettings {
+            ssao_radius: 0.25,
+            ..QualitySettings::ultra()
+        };
+        let ssao_radius = |context: &SceneContext| {
+            quality_settings
+                .with_post_processing(&context.scene.post_processing)
+                .ssao_radius
+        };
+
+        assert_eq!(ssao_radius(&context), 0.25);
+
+        let mut command = SetSsaoRadiusCommand::new(Some(1.5));
+        command.execute(&mut context);
+        assert_eq!(ssao_radius(&context), 1.5);
+
+        command.revert(&mut context);
+        assert_eq!(ssao_radius(&context), 0.25);
+    }
+}
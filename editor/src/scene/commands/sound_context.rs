@@ -1,5 +1,8 @@
 use crate::{Command, SceneContext};
-use fyrox::scene::sound::{context::SoundContext, DistanceModel, Renderer};
+use fyrox::{
+    core::{log::Log, pool::Handle},
+    scene::sound::{context::SoundContext, AudioBus, DistanceModel, Effect, Renderer},
+};
 
 macro_rules! define_sound_context_command {
     ($($name:ident($human_readable_name:expr, $value_type:ty, $get:ident, $set:ident); )*) => {
@@ -42,3 +45,258 @@ define_sound_context_command! {
     SetDistanceModelCommand("Set Distance Model", DistanceModel, distance_model, set_distance_model);
     SetRendererCommand("Set Renderer", Renderer, renderer, set_renderer);
 }
+
+#[derive(Debug)]
+pub struct SetOcclusionEnabledCommand {
+    enabled: bool,
+}
+
+impl SetOcclusionEnabledCommand {
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled }
+    }
+
+    fn swap(&mut self, sound_context: &mut SoundContext) {
+        let old = sound_context.is_occlusion_enabled();
+        sound_context.set_occlusion_enabled(self.enabled);
+        self.enabled = old;
+    }
+}
+
+impl Command for SetOcclusionEnabledCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Set Occlusion Enabled".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.swap(&mut context.scene.graph.sound_context);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(&mut context.scene.graph.sound_context);
+    }
+}
+
+#[derive(Debug)]
+pub struct SetBusGainCommand {
+    handle: Handle<AudioBus>,
+    gain: f32,
+}
+
+impl SetBusGainCommand {
+    pub fn new(handle: Handle<AudioBus>, gain: f32) -> Self {
+        Self {
+            handle,
+            // Negative gain makes no sense and will invert the phase of the signal.
+            gain: gain.max(0.0),
+        }
+    }
+
+    fn swap(&mut self, sound_context: &mut SoundContext) {
+        let mut state = sound_context.state();
+        // The bus could be removed by the user after the command was created.
+        if let Some(bus) = state.bus_graph_mut().try_get_bus_mut(self.handle) {
+            let old = bus.gain();
+            bus.set_gain(self.gain);
+            self.gain = old;
+        } else {
+            Log::warn(format!(
+                "Unable to set gain of audio bus {}, because it does not exist!",
+                self.handle
+            ));
+        }
+    }
+}
+
+impl Command for SetBusGainCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Set Bus Gain".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.swap(&mut context.scene.graph.sound_context);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(&mut context.scene.graph.sound_context);
+    }
+}
+
+macro_rules! define_reverb_command {
+    ($($name:ident($human_readable_name:expr, $get:ident, $set:ident); )*) => {
+        $(
+            #[derive(Debug)]
+            pub struct $name {
+                bus: Handle<AudioBus>,
+                effect_index: usize,
+                value: f32,
+            }
+
+            impl $name {
+                pub fn new(bus: Handle<AudioBus>, effect_index: usize, value: f32) -> Self {
+                    Self {
+                        bus,
+                        effect_index,
+                        value,
+                    }
+                }
+
+                fn swap(&mut self, sound_context: &mut SoundContext) {
+                    let mut state = sound_context.state();
+                    if let Some(Effect::Reverb(reverb)) = state
+                        .bus_graph_mut()
+                        .try_get_bus_mut(self.bus)
+                        .and_then(|bus| bus.effect_mut(self.effect_index))
+                    {
+                        let old = reverb.$get();
+                        // Setter clamps the value to the valid range.
+                        reverb.$set(self.value);
+                        self.value = old;
+                    }
+                }
+            }
+
+            impl Command for $name {
+                fn name(&mut self, _context: &SceneContext) -> String {
+                    $human_readable_name.to_owned()
+                }
+
+                fn execute(&mut self, context: &mut SceneContext) {
+                    self.swap(&mut context.scene.graph.sound_context);
+                }
+
+                fn revert(&mut self, context: &mut SceneContext) {
+                    self.swap(&mut context.scene.graph.sound_context);
+                }
+            }
+        )*
+    };
+}
+
+define_reverb_command! {
+    SetReverbDecayCommand("Set Reverb Decay Time", decay_time, set_decay_time);
+    SetReverbDryCommand("Set Reverb Dry", get_dry, set_dry);
+    SetReverbWetCommand("Set Reverb Wet", get_wet, set_wet);
+    SetReverbCutoffFrequencyCommand("Set Reverb Cutoff Frequency", fc, set_fc);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        command::Command,
+        message::MessageSender,
+        scene::{
+            commands::{
+                sound_context::{SetBusGainCommand, SetReverbDecayCommand},
+                SceneContext,
+            },
+            EditorScene,
+        },
+    };
+    use fyrox::{
+        core::pool::Handle,
+        scene::{
+            sound::{context::SoundContext, reverb::Reverb, AudioBus, Effect},
+            Scene,
+        },
+    };
+    use std::sync::mpsc::channel;
+
+    fn decay_time(context: &SoundContext, bus: Handle<AudioBus>) -> f32 {
+        match context
+            .state()
+            .bus_graph_ref()
+            .try_get_bus_ref(bus)
+            .and_then(|bus| bus.effect(0))
+        {
+            Some(Effect::Reverb(reverb)) => reverb.decay_time(),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_reverb_decay_undo_redo() {
+        let mut context = SoundContext::default();
+
+        let mut bus = AudioBus::new("Reverb".to_string());
+        bus.add_effect(Effect::Reverb(Reverb::new()));
+        let bus = {
+            let mut state = context.state();
+            let graph = state.bus_graph_mut();
+            let primary = graph.primary_bus_handle();
+            graph.add_bus(bus, primary)
+        };
+        assert_eq!(decay_time(&context, bus), 2.0);
+
+        let mut command = SetReverbDecayCommand::new(bus, 0, 5.0);
+
+        // Do
+        command.swap(&mut context);
+        assert_eq!(decay_time(&context, bus), 5.0);
+
+        // Undo
+        command.swap(&mut context);
+        assert_eq!(decay_time(&context, bus), 2.0);
+
+        // Redo
+        command.swap(&mut context);
+        assert_eq!(decay_time(&context, bus), 5.0);
+
+        // Negative decay time makes no sense and must be clamped.
+        let mut command = SetReverbDecayCommand::new(bus, 0, -1.0);
+        command.swap(&mut context);
+        assert_eq!(decay_time(&context, bus), 0.0);
+        command.swap(&mut context);
+        assert_eq!(decay_time(&context, bus), 5.0);
+    }
+
+    fn bus_gain(scene: &Scene, bus: Handle<AudioBus>) -> f32 {
+        scene
+            .graph
+            .sound_context
+            .state()
+            .bus_graph_ref()
+            .try_get_bus_ref(bus)
+            .unwrap()
+            .gain()
+    }
+
+    #[test]
+    fn test_bus_gain_undo_redo() {
+        let mut scene = Scene::new();
+        let mut editor_scene = EditorScene::new_test(&mut scene);
+
+        let bus = {
+            let mut state = scene.graph.sound_context.state();
+            let graph = state.bus_graph_mut();
+            let primary = graph.primary_bus_handle();
+            graph.add_bus(AudioBus::new("Sfx".to_string()), primary)
+        };
+        assert_eq!(bus_gain(&scene, bus), 1.0);
+
+        let (sender, _receiver) = channel();
+        let mut context =
+            SceneContext::new_test(&mut editor_scene, &mut scene, MessageSender(sender));
+        let mut command = SetBusGainCommand::new(bus, 0.5);
+
+        command.execute(&mut context);
+        assert_eq!(bus_gain(context.scene, bus), 0.5);
+
+        command.revert(&mut context);
+        assert_eq!(bus_gain(context.scene, bus), 1.0);
+
+        command.execute(&mut context);
+        assert_eq!(bus_gain(context.scene, bus), 0.5);
+
+        // The command must not panic if the bus was removed.
+        context
+            .scene
+            .graph
+            .sound_context
+            .state()
+            .bus_graph_mut()
+            .remove_bus(bus);
+        command.revert(&mut context);
+        command.execute(&mut context);
+    }
+}
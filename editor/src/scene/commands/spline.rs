@@ -0,0 +1,49 @@
+use crate::{command::Command, scene::commands::SceneContext};
+use fyrox::{
+    core::{algebra::Vector3, pool::Handle},
+    scene::{node::Node, spline::Spline},
+};
+
+#[derive(Debug)]
+pub struct SetSplineControlPointsCommand {
+    node: Handle<Node>,
+    old_points: Vec<Vector3<f32>>,
+    new_points: Vec<Vector3<f32>>,
+}
+
+impl SetSplineControlPointsCommand {
+    pub fn new(
+        node: Handle<Node>,
+        old_points: Vec<Vector3<f32>>,
+        new_points: Vec<Vector3<f32>>,
+    ) -> Self {
+        Self {
+            node,
+            old_points,
+            new_points,
+        }
+    }
+
+    fn swap(&mut self, context: &mut SceneContext) {
+        let points = self.new_points.clone();
+        std::mem::swap(&mut self.new_points, &mut self.old_points);
+        context.scene.graph[self.node]
+            .cast_mut::<Spline>()
+            .unwrap()
+            .set_control_points(points);
+    }
+}
+
+impl Command for SetSplineControlPointsCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Set Spline Control Points".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+}
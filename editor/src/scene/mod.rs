@@ -1,30 +1,44 @@
 use crate::{
-    absm::selection::AbsmSelection, animation::selection::AnimationSelection,
-    audio::AudioBusSelection, camera::CameraController,
-    interaction::navmesh::selection::NavmeshSelection, scene::clipboard::Clipboard,
-    world::graph::selection::GraphSelection, Settings,
+    absm::selection::AbsmSelection,
+    animation::selection::AnimationSelection,
+    audio::AudioBusSelection,
+    camera::{bookmark::CameraBookmarks, CameraController},
+    interaction::navmesh::selection::NavmeshSelection,
+    scene::clipboard::Clipboard,
+    settings::debugging::DebuggingSettings,
+    world::graph::selection::GraphSelection,
+    Settings,
 };
 use fyrox::core::log::Log;
 use fyrox::{
-    core::{color::Color, math::aabb::AxisAlignedBoundingBox, pool::Handle, visitor::Visitor},
-    engine::Engine,
+    asset::manager::ResourceManager,
+    core::{
+        algebra::{Matrix4, Vector3},
+        color::Color,
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
+        visitor::{VisitError, Visitor},
+    },
+    engine::{Engine, SerializationContext},
+    fxhash::FxHashSet,
     scene::{
         base::BaseBuilder,
         camera::Camera,
         debug::{Line, SceneDrawingContext},
         graph::{Graph, GraphUpdateSwitches},
-        light::{point::PointLight, spot::SpotLight},
+        light::{point::PointLight, spot::SpotLight, BaseLight},
         mesh::Mesh,
         navmesh::NavigationalMesh,
         node::Node,
         pivot::PivotBuilder,
         terrain::Terrain,
-        Scene,
+        Scene, SceneSnapshot,
     },
 };
 use std::fs::File;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 pub mod clipboard;
 pub mod property;
@@ -46,6 +60,163 @@ pub struct EditorScene {
     pub camera_controller: CameraController,
     pub preview_camera: Handle<Node>,
     pub graph_switches: GraphUpdateSwitches,
+    pub node_flags: EditorNodeFlags,
+}
+
+/// Editor-only flags of scene nodes. The flags are never saved with the scene, so they don't change
+/// anything in the game.
+#[derive(Default, Debug, Clone)]
+pub struct EditorNodeFlags {
+    hidden: FxHashSet<Handle<Node>>,
+    locked: FxHashSet<Handle<Node>>,
+    // Nodes hidden by isolation.
+    isolated: Option<FxHashSet<Handle<Node>>>,
+}
+
+impl EditorNodeFlags {
+    /// Hides the node (with its descendants) in the viewport or shows it back. Hidden nodes cannot be
+    /// picked in the viewport either. Visibility of the node itself is left untouched, the node is put
+    /// in the [hidden set](Graph::hidden_nodes) of the graph instead.
+    pub fn set_hidden(&mut self, graph: &mut Graph, node: Handle<Node>, hidden: bool) {
+        if hidden {
+            self.hidden.insert(node);
+        } else {
+            self.hidden.remove(&node);
+        }
+        self.apply_hidden(graph);
+    }
+
+    /// Copies the set of hidden (and isolated) nodes to the graph. It must be called when the graph is
+    /// replaced with another one (for example, when simulation ends).
+    pub fn apply_hidden(&self, graph: &mut Graph) {
+        graph.hidden_nodes = self
+            .hidden
+            .iter()
+            .chain(self.isolated.iter().flatten())
+            .cloned()
+            .collect();
+    }
+
+    pub fn is_hidden(&self, node: Handle<Node>) -> bool {
+        self.hidden.contains(&node)
+    }
+
+    /// Locks the node from being picked in the viewport or unlocks it.
+    pub fn set_locked(&mut self, node: Handle<Node>, locked: bool) {
+        if locked {
+            self.locked.insert(node);
+        } else {
+            self.locked.remove(&node);
+        }
+    }
+
+    pub fn is_locked(&self, node: Handle<Node>) -> bool {
+        self.locked.contains(&node)
+    }
+
+    /// Isolates the given nodes: every other node of the hierarchy starting from `root` is hidden in the
+    /// viewport, so only the subtrees of the given nodes (and, optionally, light sources) are rendered.
+    /// Ancestors of the isolated nodes stay visible, because visibility is inherited by descendants.
+    /// Previous isolation (if any) is discarded first. Just like [`Self::set_hidden`], isolation touches
+    /// neither visibility nor `enabled` state of the nodes.
+    pub fn isolate(
+        &mut self,
+        graph: &mut Graph,
+        root: Handle<Node>,
+        nodes: &[Handle<Node>],
+        keep_lights: bool,
+    ) {
+        self.exit_isolation(graph);
+
+        if nodes.is_empty() {
+            return;
+        }
+
+        let mut kept = nodes.iter().cloned().collect::<FxHashSet<_>>();
+        if keep_lights {
+            kept.extend(
+                graph
+                    .pair_iter()
+                    .filter(|(_, node)| node.query_component_ref::<BaseLight>().is_some())
+                    .map(|(handle, _)| handle),
+            );
+        }
+
+        let mut ancestors = FxHashSet::default();
+        for &node in kept.iter() {
+            let mut parent = graph.try_get(node).map(|n| n.parent()).unwrap_or_default();
+            while parent.is_some() && ancestors.insert(parent) {
+                parent = graph[parent].parent();
+            }
+        }
+
+        let mut isolated = FxHashSet::default();
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            if kept.contains(&node) {
+                continue;
+            }
+
+            if ancestors.contains(&node) {
+                stack.extend_from_slice(graph[node].children());
+            } else {
+                // The descendants are hidden too, because visibility is inherited.
+                isolated.insert(node);
+            }
+        }
+
+        self.isolated = Some(isolated);
+        self.apply_hidden(graph);
+    }
+
+    /// Shows every node, that was hidden by [`Self::isolate`].
+    pub fn exit_isolation(&mut self, graph: &mut Graph) {
+        if self.isolated.take().is_some() {
+            self.apply_hidden(graph);
+        }
+    }
+
+    pub fn is_isolated(&self) -> bool {
+        self.isolated.is_some()
+    }
+}
+
+/// Draws edit-only overlays (ground grid and world axes) using the given drawing context. The overlays
+/// are not a part of the scene and they're never saved.
+pub fn draw_overlays(ctx: &mut SceneDrawingContext, settings: &DebuggingSettings) {
+    let extent = settings.grid_size as f32 * settings.grid_spacing;
+
+    if settings.show_grid && settings.grid_spacing > 0.0 {
+        let color = Color::opaque(90, 90, 90);
+        let count = settings.grid_size as i32;
+        for i in -count..=count {
+            let offset = i as f32 * settings.grid_spacing;
+            ctx.add_line(Line {
+                begin: Vector3::new(offset, 0.0, -extent),
+                end: Vector3::new(offset, 0.0, extent),
+                color,
+            });
+            ctx.add_line(Line {
+                begin: Vector3::new(-extent, 0.0, offset),
+                end: Vector3::new(extent, 0.0, offset),
+                color,
+            });
+        }
+    }
+
+    if settings.show_world_axes {
+        for (axis, color) in [
+            (Vector3::x(), Color::RED),
+            (Vector3::y(), Color::GREEN),
+            (Vector3::z(), Color::BLUE),
+        ] {
+            ctx.add_line(Line {
+                begin: Vector3::default(),
+                end: axis.scale(extent.max(1.0)),
+                color,
+            });
+        }
+    }
 }
 
 pub fn is_scene_needs_to_be_saved(editor_scene: Option<&EditorScene>) -> bool {
@@ -54,6 +225,14 @@ pub fn is_scene_needs_to_be_saved(editor_scene: Option<&EditorScene>) -> bool {
         .map_or(false, |s| s.has_unsaved_changes || s.path.is_none())
 }
 
+/// Checks whether the scene could be reverted to its last saved state. It is possible only if the scene
+/// has unsaved changes and was saved at least once.
+pub fn is_scene_revertible(editor_scene: Option<&EditorScene>) -> bool {
+    editor_scene
+        .as_ref()
+        .map_or(false, |s| s.has_unsaved_changes && s.path.is_some())
+}
+
 impl EditorScene {
     pub fn from_native_scene(
         mut scene: Scene,
@@ -68,12 +247,15 @@ impl EditorScene {
             .change_root(PivotBuilder::new(BaseBuilder::new()).build_node());
 
         let editor_objects_root = PivotBuilder::new(BaseBuilder::new()).build(&mut scene.graph);
-        let camera_controller = CameraController::new(
+        let mut camera_controller = CameraController::new(
             &mut scene.graph,
             editor_objects_root,
             path.as_ref()
                 .and_then(|p| settings.camera.camera_settings.get(p)),
         );
+        if let Some(path) = path.as_ref() {
+            camera_controller.bookmarks = CameraBookmarks::load(path);
+        }
 
         // Freeze physics simulation in while editing scene by setting time step to zero.
         scene.graph.physics.integration_parameters.dt = Some(0.0);
@@ -98,9 +280,66 @@ impl EditorScene {
                 // Update only editor's camera.
                 node_overrides: Some(Default::default()),
             },
+            node_flags: Default::default(),
         }
     }
 
+    /// Isolates selected nodes in the viewport or exits isolation, if it is active already. See
+    /// [`EditorNodeFlags::isolate`] for more info.
+    pub fn toggle_isolation(&mut self, graph: &mut Graph, keep_lights: bool) {
+        if self.node_flags.is_isolated() {
+            self.node_flags.exit_isolation(graph);
+        } else if let Selection::Graph(ref selection) = self.selection {
+            // Editor objects (camera, gizmos, etc.) must stay visible.
+            let mut nodes = selection.nodes.clone();
+            nodes.push(self.editor_objects_root);
+            self.node_flags
+                .isolate(graph, self.scene_content_root, &nodes, keep_lights);
+        }
+    }
+
+    /// Prepares the scene for simulation: makes a snapshot of its current state, unfreezes physics
+    /// and resets the selection (scripts may delete selected nodes). Returns the snapshot and the
+    /// selection, they must be passed to [`Self::end_simulation`] to bring back edit-time state.
+    pub fn begin_simulation(
+        &mut self,
+        scene: &mut Scene,
+    ) -> Result<(SceneSnapshot, Selection), VisitError> {
+        let snapshot = scene.snapshot()?;
+
+        // Unfreeze physics and let the scene to remove "dead" nodes.
+        scene.graph.physics.integration_parameters.dt = None;
+        scene.graph.physics2d.integration_parameters.dt = None;
+        self.graph_switches.delete_dead_nodes = true;
+
+        Ok((snapshot, std::mem::take(&mut self.selection)))
+    }
+
+    /// Restores edit-time state of the scene from the snapshot made by [`Self::begin_simulation`].
+    /// Returns the restored scene, it must replace the simulated one.
+    pub fn end_simulation(
+        &mut self,
+        simulated_scene: &Scene,
+        snapshot: &SceneSnapshot,
+        selection: Selection,
+        serialization_context: Arc<SerializationContext>,
+        resource_manager: ResourceManager,
+    ) -> Result<Scene, VisitError> {
+        let mut scene = snapshot.restore(serialization_context, resource_manager)?;
+        scene.render_target = simulated_scene.render_target.clone();
+
+        // Freeze physics back.
+        scene.graph.physics.integration_parameters.dt = Some(0.0);
+        scene.graph.physics2d.integration_parameters.dt = Some(0.0);
+
+        self.graph_switches.delete_dead_nodes = false;
+        self.graph_switches.node_overrides = Some(Default::default());
+        self.selection = selection;
+        self.node_flags.apply_hidden(&mut scene.graph);
+
+        Ok(scene)
+    }
+
     pub fn make_purified_scene(&self, engine: &mut Engine) -> Scene {
         let scene = &mut engine.scenes[self.scene];
 
@@ -111,6 +350,50 @@ impl EditorScene {
         pure_scene
     }
 
+    /// Makes a new scene that contains copies of the selected nodes with their descendants. The scene could
+    /// be saved and used as a prefab later on. Returns `None` if there is no selected scene nodes.
+    pub fn make_prefab_from_selection(&self, scene: &Scene) -> Option<Scene> {
+        if let Selection::Graph(ref selection) = self.selection {
+            if selection.is_empty() {
+                return None;
+            }
+
+            let mut prefab = Scene::new();
+            let editor_root = self.editor_objects_root;
+            for root_node in selection.root_nodes(&scene.graph) {
+                scene
+                    .graph
+                    .copy_node(root_node, &mut prefab.graph, &mut |node, _| {
+                        node != editor_root
+                    });
+            }
+
+            Some(prefab)
+        } else {
+            None
+        }
+    }
+
+    /// Saves selected nodes (with their descendants) as a prefab at the given path. The scene itself is left
+    /// untouched.
+    pub fn save_selection_as_prefab(&self, scene: &Scene, path: &Path) -> Result<String, String> {
+        let mut prefab = self.make_prefab_from_selection(scene).ok_or_else(|| {
+            "Unable to save selection as prefab, because there is no selected scene nodes!"
+                .to_owned()
+        })?;
+
+        let mut visitor = Visitor::new();
+        prefab
+            .save("Scene", &mut visitor)
+            .and_then(|_| visitor.save_binary(path))
+            .map_err(|e| format!("Failed to save selection as prefab! Reason: {:?}", e))?;
+
+        Ok(format!(
+            "Selection was successfully saved as prefab to {}!",
+            path.display()
+        ))
+    }
+
     #[allow(clippy::redundant_clone)] // false positive
     pub fn save(
         &mut self,
@@ -132,6 +415,10 @@ impl EditorScene {
             if let Err(e) = visitor.save_binary(&path) {
                 Err(format!("Failed to save scene! Reason: {}", e))
             } else {
+                if let Err(e) = self.camera_controller.bookmarks.save(&path) {
+                    Log::err(format!("Failed to save camera bookmarks! Reason: {}", e));
+                }
+
                 if settings.debugging.save_scene_in_text_form {
                     let text = visitor.save_text();
                     let mut path = path.to_path_buf();
@@ -176,11 +463,13 @@ impl EditorScene {
 
         scene.drawing_context.clear_lines();
 
+        draw_overlays(&mut scene.drawing_context, debug_settings);
+
         if let Selection::Graph(selection) = &self.selection {
             for &node in selection.nodes() {
                 let node = &scene.graph[node];
                 scene.drawing_context.draw_oob(
-                    &node.local_bounding_box(),
+                    &node_bounds(node),
                     node.global_transform(),
                     Color::GREEN,
                 );
@@ -206,6 +495,13 @@ impl EditorScene {
 
             let node = &graph[node];
 
+            if settings.debugging.show_pivots {
+                let size = settings.debugging.pictogram_size;
+                ctx.draw_transform(
+                    node.global_transform() * Matrix4::new_scaling(size.max(f32::EPSILON)),
+                );
+            }
+
             if settings.debugging.show_bounds {
                 ctx.draw_oob(
                     &AxisAlignedBoundingBox::unit(),
@@ -293,6 +589,38 @@ impl EditorScene {
     }
 }
 
+#[cfg(test)]
+impl EditorScene {
+    /// Creates a bare editor scene for tests, that uses the root of the given scene both for
+    /// editor objects and for scene content.
+    pub(crate) fn new_test(scene: &mut Scene) -> Self {
+        let root = scene.graph.get_root();
+        let camera_controller = CameraController::new(&mut scene.graph, root, None);
+        Self::new_test_with(root, root, camera_controller)
+    }
+
+    /// Same as [`Self::new_test`], but allows to specify the roots and the camera controller.
+    pub(crate) fn new_test_with(
+        editor_objects_root: Handle<Node>,
+        scene_content_root: Handle<Node>,
+        camera_controller: CameraController,
+    ) -> Self {
+        Self {
+            has_unsaved_changes: false,
+            path: None,
+            scene: Default::default(),
+            editor_objects_root,
+            scene_content_root,
+            selection: Default::default(),
+            clipboard: Default::default(),
+            camera_controller,
+            preview_camera: Default::default(),
+            graph_switches: GraphUpdateSwitches::default(),
+            node_flags: Default::default(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Selection {
     None,
@@ -336,3 +664,260 @@ impl Selection {
         self.len() == 1
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        scene::{draw_overlays, EditorNodeFlags, EditorScene, Selection},
+        settings::debugging::DebuggingSettings,
+        world::graph::selection::GraphSelection,
+    };
+    use fyrox::{
+        asset::manager::ResourceManager,
+        core::{
+            algebra::Vector3,
+            futures::executor::block_on,
+            reflect::prelude::*,
+            uuid::{uuid, Uuid},
+            visitor::prelude::*,
+            TypeUuidProvider,
+        },
+        engine::SerializationContext,
+        impl_component_provider,
+        resource::model::{loader::ModelLoader, Model, ModelResourceExtension},
+        scene::{
+            base::BaseBuilder,
+            camera::Camera,
+            debug::SceneDrawingContext,
+            graph::Graph,
+            light::{point::PointLightBuilder, BaseLightBuilder},
+            pivot::PivotBuilder,
+            transform::TransformBuilder,
+            Scene,
+        },
+        script::{Script, ScriptTrait},
+    };
+    use std::sync::Arc;
+
+    #[derive(Reflect, Visit, Default, Debug, Clone, PartialEq)]
+    struct Turret {
+        range: f32,
+        armed: bool,
+    }
+
+    impl_component_provider!(Turret);
+
+    impl TypeUuidProvider for Turret {
+        fn type_uuid() -> Uuid {
+            uuid!("b3f1d0a2-7c4e-4a59-9e21-6d8f0c3b5a17")
+        }
+    }
+
+    impl ScriptTrait for Turret {
+        fn id(&self) -> Uuid {
+            Self::type_uuid()
+        }
+    }
+
+    #[test]
+    fn test_grid_toggle() {
+        let mut settings = DebuggingSettings {
+            show_world_axes: false,
+            grid_size: 2,
+            ..Default::default()
+        };
+
+        let mut ctx = SceneDrawingContext::default();
+        draw_overlays(&mut ctx, &settings);
+        // 5 lines along each axis.
+        assert_eq!(ctx.lines.len(), 10);
+
+        settings.show_grid = false;
+        let mut ctx = SceneDrawingContext::default();
+        draw_overlays(&mut ctx, &settings);
+        assert!(ctx.lines.is_empty());
+
+        settings.show_world_axes = true;
+        let mut ctx = SceneDrawingContext::default();
+        draw_overlays(&mut ctx, &settings);
+        assert_eq!(ctx.lines.len(), 3);
+    }
+
+    #[test]
+    fn test_isolation() {
+        let mut graph = Graph::new();
+        let child = PivotBuilder::new(BaseBuilder::new()).build(&mut graph);
+        let isolated =
+            PivotBuilder::new(BaseBuilder::new().with_children(&[child])).build(&mut graph);
+        let sibling = PivotBuilder::new(BaseBuilder::new()).build(&mut graph);
+        let light =
+            PointLightBuilder::new(BaseLightBuilder::new(BaseBuilder::new())).build(&mut graph);
+        let root = graph.get_root();
+
+        let mut flags = EditorNodeFlags::default();
+        flags.isolate(&mut graph, root, &[isolated], false);
+        graph.update_hierarchical_data();
+        assert!(flags.is_isolated());
+        assert!(graph[isolated].global_visibility());
+        assert!(graph[child].global_visibility());
+        assert!(graph[root].global_visibility());
+        assert!(!graph[sibling].global_visibility());
+        assert!(!graph[light].global_visibility());
+        // Isolation neither disables nodes nor changes their visibility.
+        assert!(graph[sibling].is_enabled());
+        assert!(graph[sibling].visibility());
+        assert!(graph[light].visibility());
+
+        flags.exit_isolation(&mut graph);
+        graph.update_hierarchical_data();
+        assert!(!flags.is_isolated());
+        assert!(graph[sibling].global_visibility());
+        assert!(graph[light].global_visibility());
+
+        // Lights could be kept to see the isolated nodes lit properly.
+        flags.isolate(&mut graph, root, &[isolated], true);
+        graph.update_hierarchical_data();
+        assert!(!graph[sibling].global_visibility());
+        assert!(graph[light].global_visibility());
+
+        // A node, that was hidden during isolation, stays hidden.
+        flags.set_hidden(&mut graph, sibling, true);
+        flags.exit_isolation(&mut graph);
+        graph.update_hierarchical_data();
+        assert!(!graph[sibling].global_visibility());
+        flags.set_hidden(&mut graph, sibling, false);
+        graph.update_hierarchical_data();
+        assert!(graph[sibling].global_visibility());
+    }
+
+    #[test]
+    fn test_export_selection_as_prefab() {
+        let mut scene = Scene::new();
+        let barrel =
+            PivotBuilder::new(BaseBuilder::new().with_name("Barrel")).build(&mut scene.graph);
+        let sight =
+            PivotBuilder::new(BaseBuilder::new().with_name("Sight")).build(&mut scene.graph);
+        let turret_script = Turret {
+            range: 25.0,
+            armed: true,
+        };
+        let turret = PivotBuilder::new(
+            BaseBuilder::new()
+                .with_name("Turret")
+                .with_script(Script::new(turret_script.clone()))
+                .with_children(&[barrel, sight]),
+        )
+        .build(&mut scene.graph);
+        PivotBuilder::new(BaseBuilder::new().with_name("Other")).build(&mut scene.graph);
+
+        let mut editor_scene = EditorScene::new_test(&mut scene);
+        editor_scene.selection = Selection::Graph(GraphSelection::single_or_empty(turret));
+        let node_count = scene.graph.node_count();
+
+        let path = std::env::temp_dir().join("fyroxed_export_selection.rgs");
+        editor_scene
+            .save_selection_as_prefab(&scene, &path)
+            .unwrap();
+
+        // The selection stays in the scene.
+        assert_eq!(scene.graph.node_count(), node_count);
+        assert_eq!(scene.graph[turret].name(), "Turret");
+
+        let serialization_context = Arc::new(SerializationContext::new());
+        serialization_context
+            .script_constructors
+            .add::<Turret>("Turret");
+        let resource_manager = ResourceManager::new();
+        {
+            let mut state = resource_manager.state();
+            state.constructors_container.add::<Model>();
+            state.loaders.set(ModelLoader {
+                resource_manager: resource_manager.clone(),
+                serialization_context,
+                default_import_options: Default::default(),
+            });
+        }
+
+        let model = block_on(resource_manager.request::<Model, _>(&path)).unwrap();
+        let mut dest_scene = Scene::new();
+        model.instantiate(&mut dest_scene);
+
+        let (instance, instance_ref) = dest_scene.graph.find_by_name_from_root("Turret").unwrap();
+        assert_eq!(
+            instance_ref.try_get_script::<Turret>(),
+            Some(&turret_script)
+        );
+        let children = instance_ref
+            .children()
+            .iter()
+            .map(|child| dest_scene.graph[*child].name().to_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(children, vec!["Barrel".to_owned(), "Sight".to_owned()]);
+        assert_eq!(dest_scene.graph.traverse_handle_iter(instance).count(), 3);
+        // Only the selected sub-graph is exported.
+        assert!(dest_scene.graph.find_by_name_from_root("Other").is_none());
+        assert!(dest_scene
+            .graph
+            .linear_iter()
+            .all(|node| node.cast::<Camera>().is_none()));
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_simulation_restores_pre_play_state() {
+        let mut scene = Scene::new();
+        let mut editor_scene = EditorScene::new_test(&mut scene);
+
+        let position = Vector3::new(1.0, 2.0, 3.0);
+        let moved = PivotBuilder::new(
+            BaseBuilder::new().with_name("Moved").with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(position)
+                    .build(),
+            ),
+        )
+        .build(&mut scene.graph);
+        let removed =
+            PivotBuilder::new(BaseBuilder::new().with_name("Removed")).build(&mut scene.graph);
+        let node_count = scene.graph.node_count();
+        let selection = Selection::Graph(GraphSelection::single_or_empty(removed));
+        editor_scene.selection = selection.clone();
+        scene.graph.physics.integration_parameters.dt = Some(0.0);
+
+        // Enter simulation.
+        let (snapshot, saved_selection) = editor_scene.begin_simulation(&mut scene).unwrap();
+        assert_eq!(editor_scene.selection, Selection::None);
+        assert_eq!(scene.graph.physics.integration_parameters.dt, None);
+        assert!(editor_scene.graph_switches.delete_dead_nodes);
+
+        // Simulate some gameplay changes.
+        scene.graph[moved]
+            .local_transform_mut()
+            .set_position(Vector3::new(10.0, 0.0, 0.0));
+        scene.graph.remove_node(removed);
+        PivotBuilder::new(BaseBuilder::new().with_name("Spawned")).build(&mut scene.graph);
+
+        // Leave simulation.
+        let scene = editor_scene
+            .end_simulation(
+                &scene,
+                &snapshot,
+                saved_selection,
+                Arc::new(SerializationContext::new()),
+                ResourceManager::new(),
+            )
+            .unwrap();
+
+        assert_eq!(scene.graph.node_count(), node_count);
+        assert_eq!(**scene.graph[moved].local_transform().position(), position);
+        assert_eq!(scene.graph[removed].name(), "Removed");
+        assert!(scene
+            .graph
+            .linear_iter()
+            .all(|node| node.name() != "Spawned"));
+        assert_eq!(scene.graph.physics.integration_parameters.dt, Some(0.0));
+        assert_eq!(editor_scene.selection, selection);
+        assert!(!editor_scene.graph_switches.delete_dead_nodes);
+    }
+}
@@ -23,10 +23,15 @@ use fyrox::{
     resource::texture::TextureResource,
     scene::{
         dim2,
+        environment::EnvironmentLighting,
+        fog::FogSettings,
         graph::{
             physics::{IntegrationParameters, PhysicsWorld},
             Graph, NodePool,
         },
+        post_processing::{
+            BloomSettings, PostProcessingSettings, SsaoSettings, ToneMappingSettings,
+        },
     },
     utils::lightmap::Lightmap,
 };
@@ -65,6 +70,12 @@ impl SceneSettingsWindow {
         container.insert(InspectablePropertyEditorDefinition::<
             dim2::physics::PhysicsWorld,
         >::new());
+        container.insert(InspectablePropertyEditorDefinition::<PostProcessingSettings>::new());
+        container.insert(InspectablePropertyEditorDefinition::<SsaoSettings>::new());
+        container.insert(InspectablePropertyEditorDefinition::<BloomSettings>::new());
+        container.insert(InspectablePropertyEditorDefinition::<ToneMappingSettings>::new());
+        container.insert(InspectablePropertyEditorDefinition::<FogSettings>::new());
+        container.insert(InspectablePropertyEditorDefinition::<EnvironmentLighting>::new());
 
         Self {
             window,
@@ -135,3 +146,183 @@ impl SceneSettingsWindow {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        message::MessageSender,
+        scene::{
+            commands::{SceneCommand, SceneContext},
+            settings::command::make_set_scene_property_command,
+            EditorScene,
+        },
+    };
+    use fyrox::{
+        core::visitor::prelude::*,
+        gui::inspector::{FieldKind, PropertyChanged, Value},
+        renderer::FogParameters,
+        scene::{graph::physics::IntegrationParameters, Scene},
+    };
+    use std::{any::TypeId, sync::mpsc::channel};
+
+    // Makes a command the same way as the settings inspector does, when a property at the given path
+    // is changed. Owner types are not used by the commands.
+    fn set_scene_property<T: Value>(path: &str, value: T) -> SceneCommand {
+        let mut names = path.rsplit('.');
+        let mut property_changed = PropertyChanged {
+            name: names.next().unwrap().to_owned(),
+            owner_type_id: TypeId::of::<Scene>(),
+            value: FieldKind::object(value),
+        };
+        for name in names {
+            property_changed = PropertyChanged {
+                name: name.to_owned(),
+                owner_type_id: TypeId::of::<Scene>(),
+                value: FieldKind::Inspectable(Box::new(property_changed)),
+            };
+        }
+        make_set_scene_property_command((), &property_changed).unwrap()
+    }
+
+    #[test]
+    fn test_fog_undo_redo() {
+        let mut scene = Scene::new();
+        let mut editor_scene = EditorScene::new_test(&mut scene);
+
+        let (sender, _receiver) = channel();
+        let mut context =
+            SceneContext::new_test(&mut editor_scene, &mut scene, MessageSender(sender));
+
+        // Parameters, that will be used by the renderer to draw the fog.
+        let renderer_fog =
+            |context: &SceneContext| FogParameters::from_settings(&context.scene.fog);
+
+        // Disabled by default.
+        assert_eq!(renderer_fog(&context), None);
+
+        let mut enable = set_scene_property("fog.enabled", true);
+        enable.execute(&mut context);
+        assert!(context.scene.fog.enabled);
+        let default_density = context.scene.fog.density;
+        assert_eq!(
+            renderer_fog(&context).map(|fog| fog.density),
+            Some(default_density)
+        );
+
+        let mut set_density = set_scene_property("fog.density", 0.25f32);
+        set_density.execute(&mut context);
+        assert_eq!(renderer_fog(&context).map(|fog| fog.density), Some(0.25));
+
+        // Undo
+        set_density.revert(&mut context);
+        assert_eq!(
+            renderer_fog(&context).map(|fog| fog.density),
+            Some(default_density)
+        );
+        enable.revert(&mut context);
+        assert_eq!(renderer_fog(&context), None);
+
+        // Redo
+        enable.execute(&mut context);
+        set_density.execute(&mut context);
+        assert_eq!(renderer_fog(&context).map(|fog| fog.density), Some(0.25));
+    }
+
+    #[test]
+    fn test_solver_iterations_undo_redo() {
+        let mut scene = Scene::new();
+        let mut editor_scene = EditorScene::new_test(&mut scene);
+
+        let (sender, _receiver) = channel();
+        let mut context =
+            SceneContext::new_test(&mut editor_scene, &mut scene, MessageSender(sender));
+
+        let dt = 1.0 / 60.0;
+        let default_params = context
+            .scene
+            .graph
+            .physics
+            .rapier_integration_parameters(dt);
+        let default_velocity_iterations = default_params.max_velocity_iterations;
+        let default_stabilization_iterations = default_params.max_stabilization_iterations;
+
+        let mut set_velocity_iterations = set_scene_property(
+            "graph.physics.integration_parameters.max_velocity_iterations",
+            16u32,
+        );
+        let mut set_stabilization_iterations = set_scene_property(
+            "graph.physics.integration_parameters.max_stabilization_iterations",
+            6u32,
+        );
+        let mut set_stabilization_iterations_2d = set_scene_property(
+            "graph.physics2d.integration_parameters.max_stabilization_iterations",
+            3u32,
+        );
+        set_velocity_iterations.execute(&mut context);
+        set_stabilization_iterations.execute(&mut context);
+        set_stabilization_iterations_2d.execute(&mut context);
+
+        let params = context
+            .scene
+            .graph
+            .physics
+            .rapier_integration_parameters(dt);
+        assert_eq!(params.max_velocity_iterations, 16);
+        assert_eq!(params.max_stabilization_iterations, 6);
+        assert_eq!(
+            context
+                .scene
+                .graph
+                .physics2d
+                .rapier_integration_parameters(dt)
+                .max_stabilization_iterations,
+            3
+        );
+
+        // Undo
+        set_stabilization_iterations_2d.revert(&mut context);
+        set_stabilization_iterations.revert(&mut context);
+        set_velocity_iterations.revert(&mut context);
+        let params = context
+            .scene
+            .graph
+            .physics
+            .rapier_integration_parameters(dt);
+        assert_eq!(params.max_velocity_iterations, default_velocity_iterations);
+        assert_eq!(
+            params.max_stabilization_iterations,
+            default_stabilization_iterations
+        );
+
+        // Redo
+        set_velocity_iterations.execute(&mut context);
+        set_stabilization_iterations.execute(&mut context);
+        let params = context
+            .scene
+            .graph
+            .physics
+            .rapier_integration_parameters(dt);
+        assert_eq!(params.max_velocity_iterations, 16);
+        assert_eq!(params.max_stabilization_iterations, 6);
+
+        // The iterations are saved with the scene.
+        let mut visitor = Visitor::new();
+        context
+            .scene
+            .graph
+            .physics
+            .integration_parameters
+            .visit("IntegrationParameters", &mut visitor)
+            .unwrap();
+        let data = visitor.save_binary_to_vec().unwrap();
+        let mut visitor = Visitor::load_from_memory(data).unwrap();
+        let mut loaded = IntegrationParameters {
+            max_velocity_iterations: 0,
+            max_stabilization_iterations: 0,
+            ..Default::default()
+        };
+        loaded.visit("IntegrationParameters", &mut visitor).unwrap();
+        assert_eq!(loaded.max_velocity_iterations, 16);
+        assert_eq!(loaded.max_stabilization_iterations, 6);
+    }
+}
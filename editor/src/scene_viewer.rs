@@ -1,10 +1,12 @@
 use crate::message::MessageSender;
+use crate::scene::commands::mesh::SetMeshSurfaceMaterialCommand;
 use crate::{
     camera::PickingOptions, gui::make_dropdown_list_option,
-    gui::make_dropdown_list_option_with_height, load_image, send_sync_message,
-    settings::keys::KeyBindings, utils::enable_widget, AddModelCommand, AssetItem, AssetKind,
-    BuildProfile, ChangeSelectionCommand, CommandGroup, DropdownListBuilder, EditorScene,
-    GraphSelection, InteractionMode, InteractionModeKind, Message, Mode, SceneCommand, Selection,
+    gui::make_dropdown_list_option_with_height, interaction::pivot::snap_to_point, load_image,
+    menu::viewport::ViewportContextMenu, send_sync_message, settings::keys::KeyBindings,
+    utils::enable_widget, AddModelCommand, AssetItem, AssetKind, BuildProfile,
+    ChangeSelectionCommand, CommandGroup, DropdownListBuilder, EditorScene, GraphSelection,
+    InteractionMode, InteractionModeKind, Message, Mode, SceneCommand, Selection,
     SetMeshTextureCommand, Settings,
 };
 use fyrox::{
@@ -12,6 +14,8 @@ use fyrox::{
     core::{
         algebra::{Vector2, Vector3},
         color::Color,
+        futures::executor::block_on,
+        log::Log,
         make_relative_path,
         math::{plane::Plane, Rect},
         pool::Handle,
@@ -39,12 +43,15 @@ use fyrox::{
         VerticalAlignment, BRUSH_BRIGHT_BLUE, BRUSH_DARKER, BRUSH_DARKEST, BRUSH_LIGHT,
         BRUSH_LIGHTER, BRUSH_LIGHTEST,
     },
+    material::{Material, SharedMaterial},
+    renderer::DebugView,
     resource::{
         model::{Model, ModelResourceExtension},
         texture::{Texture, TextureResource},
     },
     scene::{
         camera::{Camera, Projection},
+        graph::Graph,
         node::Node,
     },
     utils::into_gui_texture,
@@ -55,6 +62,128 @@ struct PreviewInstance {
     nodes: FxHashSet<Handle<Node>>,
 }
 
+/// Returns world position of a point under the cursor. At first it tries to find the closest
+/// intersection with scene objects, and if there is none, it checks intersection with oXZ plane (3D)
+/// or oXY (2D).
+fn pick_world_position<F>(
+    editor_scene: &mut EditorScene,
+    graph: &Graph,
+    rel_pos: Vector2<f32>,
+    frame_size: Vector2<f32>,
+    settings: &Settings,
+    filter: F,
+) -> Option<Vector3<f32>>
+where
+    F: FnMut(Handle<Node>, &Node) -> bool,
+{
+    if let Some(result) = editor_scene.camera_controller.pick(PickingOptions {
+        cursor_pos: rel_pos,
+        graph,
+        editor_objects_root: editor_scene.editor_objects_root,
+        scene_content_root: editor_scene.scene_content_root,
+        screen_size: frame_size,
+        editor_only: false,
+        filter,
+        ignore_back_faces: settings.selection.ignore_back_faces,
+        // We need info only about closest intersection.
+        use_picking_loop: false,
+        only_meshes: false,
+    }) {
+        Some(result.position)
+    } else {
+        let camera = graph[editor_scene.camera_controller.camera]
+            .query_component_ref::<Camera>()
+            .unwrap();
+
+        let normal = match camera.projection() {
+            Projection::Perspective(_) => Vector3::new(0.0, 1.0, 0.0),
+            Projection::Orthographic(_) => Vector3::new(0.0, 0.0, 1.0),
+        };
+
+        let plane = Plane::from_normal_and_point(&normal, &Default::default()).unwrap_or_default();
+
+        camera
+            .make_ray(rel_pos, frame_size)
+            .plane_intersection_point(&plane)
+    }
+}
+
+/// Creates a command, that assigns the material to a surface of a mesh under the cursor. Returns `None`
+/// if there is no mesh under the cursor.
+fn make_surface_material_command(
+    editor_scene: &mut EditorScene,
+    graph: &Graph,
+    rel_pos: Vector2<f32>,
+    frame_size: Vector2<f32>,
+    settings: &Settings,
+    material: SharedMaterial,
+) -> Option<SetMeshSurfaceMaterialCommand> {
+    let result = editor_scene.camera_controller.pick(PickingOptions {
+        cursor_pos: rel_pos,
+        graph,
+        editor_objects_root: editor_scene.editor_objects_root,
+        scene_content_root: editor_scene.scene_content_root,
+        screen_size: frame_size,
+        editor_only: false,
+        filter: |_, _| true,
+        ignore_back_faces: settings.selection.ignore_back_faces,
+        use_picking_loop: false,
+        only_meshes: true,
+    })?;
+
+    Some(SetMeshSurfaceMaterialCommand::new(
+        result.node,
+        result.surface?,
+        material,
+    ))
+}
+
+/// Debug views of the renderer, in the same order as the items of the debug view dropdown list.
+const DEBUG_VIEWS: [(DebugView, &str); 5] = [
+    (DebugView::Shaded, "Shaded"),
+    (DebugView::Wireframe, "Wireframe"),
+    (DebugView::Normals, "Normals"),
+    (DebugView::Overdraw, "Overdraw"),
+    (DebugView::LightingOnly, "Lighting Only"),
+];
+
+fn make_debug_view_dropdown(ctx: &mut BuildContext) -> Handle<UiNode> {
+    DropdownListBuilder::new(
+        WidgetBuilder::new()
+            .with_tooltip(make_simple_tooltip(
+                ctx,
+                "Debug View\nDefines what the renderer shows in the viewport. \
+                It affects only the editor, the scene is not changed.",
+            ))
+            .with_margin(Thickness::uniform(1.0))
+            .with_width(100.0),
+    )
+    .with_items(
+        DEBUG_VIEWS
+            .iter()
+            .map(|(_, name)| make_dropdown_list_option_with_height(ctx, name, 22.0))
+            .collect(),
+    )
+    .with_selected(0)
+    .build(ctx)
+}
+
+/// Sends a message to change debug view of the renderer, if the given message is a selection
+/// change of the debug view dropdown list.
+fn handle_debug_view_selection(
+    message: &UiMessage,
+    dropdown: Handle<UiNode>,
+    sender: &MessageSender,
+) {
+    if let Some(DropdownListMessage::SelectionChanged(Some(index))) = message.data() {
+        if message.destination() == dropdown && message.direction == MessageDirection::FromWidget {
+            if let Some((debug_view, _)) = DEBUG_VIEWS.get(*index) {
+                sender.send(Message::SetDebugView(*debug_view));
+            }
+        }
+    }
+}
+
 pub struct SceneViewer {
     frame: Handle<UiNode>,
     window: Handle<UiNode>,
@@ -68,8 +197,13 @@ pub struct SceneViewer {
     scale_mode: Handle<UiNode>,
     navmesh_mode: Handle<UiNode>,
     terrain_mode: Handle<UiNode>,
+    measure_mode: Handle<UiNode>,
+    spline_mode: Handle<UiNode>,
+    measure_display: Handle<UiNode>,
     camera_projection: Handle<UiNode>,
+    debug_view: Handle<UiNode>,
     play: Handle<UiNode>,
+    simulate: Handle<UiNode>,
     stop: Handle<UiNode>,
     build_profile: Handle<UiNode>,
     sender: MessageSender,
@@ -78,6 +212,8 @@ pub struct SceneViewer {
     global_position_display: Handle<UiNode>,
     preview_instance: Option<PreviewInstance>,
     no_scene_reminder: Handle<UiNode>,
+    context_menu: ViewportContextMenu,
+    right_click_pos: Option<Vector2<f32>>,
 }
 
 fn make_interaction_mode_button(
@@ -153,6 +289,15 @@ impl SceneViewer {
             "Edit Terrain\n\nTerrain edit mode allows you to modify selected \
         terrain.";
 
+        let measure_mode_tooltip = "Measure - Shortcut: [7]\n\nMeasure mode allows you to measure \
+        a distance between two points on scene geometry. Click twice to pick the points, press Escape \
+        to reset the measurement.";
+
+        let spline_mode_tooltip = "Edit Spline - Shortcut: [8]\n\nSpline edit mode allows you to modify \
+        control points of selected spline. Click on a control point to select it and drag it using the \
+        gizmo. Press Insert to add a new point after the selected one, press Delete to remove the selected \
+        point.";
+
         let frame;
         let select_mode;
         let move_mode;
@@ -160,9 +305,13 @@ impl SceneViewer {
         let scale_mode;
         let navmesh_mode;
         let terrain_mode;
+        let measure_mode;
+        let spline_mode;
+        let measure_display;
         let selection_frame;
         let camera_projection;
         let play;
+        let simulate;
         let stop;
         let build_profile;
 
@@ -224,15 +373,38 @@ impl SceneViewer {
                         false,
                     );
                     terrain_mode
+                })
+                .with_child({
+                    measure_mode = make_interaction_mode_button(
+                        ctx,
+                        include_bytes!("../resources/embed/locate.png"),
+                        measure_mode_tooltip,
+                        false,
+                    );
+                    measure_mode
+                })
+                .with_child({
+                    spline_mode = make_interaction_mode_button(
+                        ctx,
+                        include_bytes!("../resources/embed/position_track.png"),
+                        spline_mode_tooltip,
+                        false,
+                    );
+                    spline_mode
                 }),
         )
         .build(ctx);
 
         let global_position_display;
+        let debug_view;
         let contextual_actions = StackPanelBuilder::new(
             WidgetBuilder::new()
                 .on_column(1)
                 .with_horizontal_alignment(HorizontalAlignment::Right)
+                .with_child({
+                    debug_view = make_debug_view_dropdown(ctx);
+                    debug_view
+                })
                 .with_child({
                     camera_projection = DropdownListBuilder::new(
                         WidgetBuilder::new()
@@ -314,6 +486,36 @@ impl SceneViewer {
                                 .build(ctx);
                                 play
                             })
+                            .with_child({
+                                simulate = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_width(26.0)
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Simulate\nRuns scripts and physics of the scene \
+                                            right in the editor. The scene is restored when \
+                                            the simulation is stopped. Hotkey: F5",
+                                        )),
+                                )
+                                .with_content(
+                                    ImageBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(16.0)
+                                            .with_height(16.0)
+                                            .with_margin(Thickness::uniform(4.0))
+                                            .with_background(Brush::Solid(Color::opaque(
+                                                0, 120, 220,
+                                            ))),
+                                    )
+                                    .with_opt_texture(load_image(include_bytes!(
+                                        "../resources/embed/play.png"
+                                    )))
+                                    .build(ctx),
+                                )
+                                .build(ctx);
+                                simulate
+                            })
                             .with_child({
                                 stop = ButtonBuilder::new(
                                     WidgetBuilder::new()
@@ -361,6 +563,8 @@ impl SceneViewer {
         .with_wrap(WrapMode::Word)
         .build(ctx);
 
+        let context_menu = ViewportContextMenu::new(ctx);
+
         let window = WindowBuilder::new(WidgetBuilder::new())
             .can_close(false)
             .can_minimize(false)
@@ -385,21 +589,35 @@ impl SceneViewer {
                                         frame
                                     })
                                     .with_child(
-                                        CanvasBuilder::new(WidgetBuilder::new().with_child({
-                                            selection_frame = BorderBuilder::new(
-                                                WidgetBuilder::new()
-                                                    .with_visibility(false)
-                                                    .with_background(Brush::Solid(
-                                                        Color::from_rgba(255, 255, 255, 40),
-                                                    ))
-                                                    .with_foreground(Brush::Solid(Color::opaque(
-                                                        0, 255, 0,
-                                                    ))),
-                                            )
-                                            .with_stroke_thickness(Thickness::uniform(1.0))
-                                            .build(ctx);
-                                            selection_frame
-                                        }))
+                                        CanvasBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_child({
+                                                    measure_display = TextBuilder::new(
+                                                        WidgetBuilder::new()
+                                                            .with_visibility(false)
+                                                            .with_foreground(Brush::Solid(
+                                                                Color::ORANGE,
+                                                            )),
+                                                    )
+                                                    .build(ctx);
+                                                    measure_display
+                                                })
+                                                .with_child({
+                                                    selection_frame = BorderBuilder::new(
+                                                        WidgetBuilder::new()
+                                                            .with_visibility(false)
+                                                            .with_background(Brush::Solid(
+                                                                Color::from_rgba(255, 255, 255, 40),
+                                                            ))
+                                                            .with_foreground(Brush::Solid(
+                                                                Color::opaque(0, 255, 0),
+                                                            )),
+                                                    )
+                                                    .with_stroke_thickness(Thickness::uniform(1.0))
+                                                    .build(ctx);
+                                                    selection_frame
+                                                }),
+                                        )
                                         .build(ctx),
                                     ),
                             )
@@ -428,9 +646,14 @@ impl SceneViewer {
             select_mode,
             navmesh_mode,
             terrain_mode,
+            measure_mode,
+            spline_mode,
+            measure_display,
             camera_projection,
+            debug_view,
             click_mouse_pos: None,
             play,
+            simulate,
             interaction_mode_panel,
             contextual_actions,
             global_position_display,
@@ -438,6 +661,8 @@ impl SceneViewer {
             preview_instance: None,
             stop,
             no_scene_reminder,
+            context_menu,
+            right_click_pos: None,
         }
     }
 }
@@ -455,6 +680,10 @@ impl SceneViewer {
         self.selection_frame
     }
 
+    pub fn measure_display(&self) -> Handle<UiNode> {
+        self.measure_display
+    }
+
     pub fn handle_message(&mut self, message: &Message, engine: &mut Engine) {
         if let Message::SetInteractionMode(mode) = message {
             let active_button = match mode {
@@ -464,6 +693,8 @@ impl SceneViewer {
                 InteractionModeKind::Rotate => self.rotate_mode,
                 InteractionModeKind::Navmesh => self.navmesh_mode,
                 InteractionModeKind::Terrain => self.terrain_mode,
+                InteractionModeKind::Measure => self.measure_mode,
+                InteractionModeKind::Spline => self.spline_mode,
             };
 
             for mode_button in [
@@ -473,6 +704,8 @@ impl SceneViewer {
                 self.rotate_mode,
                 self.navmesh_mode,
                 self.terrain_mode,
+                self.measure_mode,
+                self.spline_mode,
             ] {
                 let decorator = engine
                     .user_interface
@@ -520,8 +753,16 @@ impl SceneViewer {
             } else if message.destination() == self.terrain_mode {
                 self.sender
                     .send(Message::SetInteractionMode(InteractionModeKind::Terrain));
+            } else if message.destination() == self.measure_mode {
+                self.sender
+                    .send(Message::SetInteractionMode(InteractionModeKind::Measure));
+            } else if message.destination() == self.spline_mode {
+                self.sender
+                    .send(Message::SetInteractionMode(InteractionModeKind::Spline));
             } else if message.destination() == self.play {
                 self.sender.send(Message::SwitchToBuildMode);
+            } else if message.destination() == self.simulate {
+                self.sender.send(Message::ToggleSimulation);
             } else if message.destination() == self.stop {
                 self.sender.send(Message::SwitchToEditMode);
             }
@@ -557,6 +798,13 @@ impl SceneViewer {
             }
         }
 
+        handle_debug_view_selection(message, self.debug_view, &self.sender);
+
+        if let Some(editor_scene) = editor_scene.as_deref() {
+            self.context_menu
+                .handle_ui_message(message, editor_scene, &self.sender);
+        }
+
         if let (Some(editor_scene), Some(msg), Mode::Edit) =
             (editor_scene, message.data::<WidgetMessage>(), mode)
         {
@@ -667,46 +915,25 @@ impl SceneViewer {
                                 let rel_pos = cursor_pos - screen_bounds.position;
                                 let graph = &mut engine.scenes[editor_scene.scene].graph;
 
-                                let position = if let Some(result) =
-                                    editor_scene.camera_controller.pick(PickingOptions {
-                                        cursor_pos: rel_pos,
-                                        graph,
-                                        editor_objects_root: editor_scene.editor_objects_root,
-                                        scene_content_root: editor_scene.scene_content_root,
-                                        screen_size: frame_size,
-                                        editor_only: false,
-                                        filter: |handle, _| !preview.nodes.contains(&handle),
-                                        ignore_back_faces: settings.selection.ignore_back_faces,
-                                        // We need info only about closest intersection.
-                                        use_picking_loop: false,
-                                        only_meshes: false,
-                                    }) {
-                                    Some(result.position)
-                                } else {
-                                    // In case of empty space, check intersection with oXZ plane (3D) or oXY (2D).
-                                    let camera = graph[editor_scene.camera_controller.camera]
-                                        .query_component_ref::<Camera>()
-                                        .unwrap();
-
-                                    let normal = match camera.projection() {
-                                        Projection::Perspective(_) => Vector3::new(0.0, 1.0, 0.0),
-                                        Projection::Orthographic(_) => Vector3::new(0.0, 0.0, 1.0),
-                                    };
-
-                                    let plane =
-                                        Plane::from_normal_and_point(&normal, &Default::default())
-                                            .unwrap_or_default();
-
-                                    let ray = camera.make_ray(rel_pos, frame_size);
-
-                                    ray.plane_intersection_point(&plane)
-                                };
+                                let position = pick_world_position(
+                                    editor_scene,
+                                    graph,
+                                    rel_pos,
+                                    frame_size,
+                                    settings,
+                                    |handle, _| !preview.nodes.contains(&handle),
+                                );
 
                                 if let Some(position) = position {
-                                    graph[preview.instance].local_transform_mut().set_position(
+                                    graph
+                                        .update_hierarchical_data_for_descendants(preview.instance);
+                                    snap_to_point(
+                                        graph,
+                                        preview.instance,
                                         settings
                                             .move_mode_settings
                                             .try_snap_vector_to_grid(position),
+                                        settings.move_mode_settings.pivot,
                                     );
                                 }
                             }
@@ -756,6 +983,11 @@ impl SceneViewer {
             MessageDirection::ToWidget,
             mode.is_edit(),
         ));
+        ui.send_message(WidgetMessage::enabled(
+            self.simulate,
+            MessageDirection::ToWidget,
+            mode.is_edit(),
+        ));
         ui.send_message(WidgetMessage::enabled(
             self.stop,
             MessageDirection::ToWidget,
@@ -788,6 +1020,15 @@ impl SceneViewer {
         ));
     }
 
+    pub fn reset_debug_view(&self, ui: &UserInterface) {
+        // Default debug view is Shaded.
+        ui.send_message(DropdownListMessage::selection(
+            self.debug_view,
+            MessageDirection::ToWidget,
+            Some(0),
+        ));
+    }
+
     pub fn frame_bounds(&self, ui: &UserInterface) -> Rect<f32> {
         ui.node(self.frame).screen_bounds()
     }
@@ -896,6 +1137,28 @@ impl SceneViewer {
                     settings,
                 );
             }
+        } else if button == MouseButton::Right {
+            // Open the context menu only on a click, the right mouse button is also used to rotate
+            // the camera.
+            if let Some(click_pos) = self.right_click_pos.take() {
+                if (pos - click_pos).norm() < 3.0 {
+                    let rel_pos = pos - screen_bounds.position;
+                    let graph = &engine.scenes[editor_scene.scene].graph;
+                    if let Some(position) = pick_world_position(
+                        editor_scene,
+                        graph,
+                        rel_pos,
+                        screen_bounds.size,
+                        settings,
+                        |_, _| true,
+                    ) {
+                        let position = settings
+                            .move_mode_settings
+                            .try_snap_vector_to_grid(position);
+                        self.context_menu.open(&engine.user_interface, position);
+                    }
+                }
+            }
         }
 
         editor_scene.camera_controller.on_mouse_button_up(button);
@@ -928,6 +1191,8 @@ impl SceneViewer {
                     settings,
                 );
             }
+        } else if button == MouseButton::Right {
+            self.right_click_pos = Some(pos);
         }
 
         editor_scene.camera_controller.on_mouse_button_down(button);
@@ -1006,9 +1271,220 @@ impl SceneViewer {
                             }
                         }
                     }
+                    AssetKind::Material => {
+                        match block_on(Material::from_file(
+                            &relative_path,
+                            engine.resource_manager.clone(),
+                        )) {
+                            Ok(material) => {
+                                let cursor_pos = engine.user_interface.cursor_position();
+                                let rel_pos = cursor_pos - screen_bounds.position;
+                                if let Some(command) = make_surface_material_command(
+                                    editor_scene,
+                                    &engine.scenes[editor_scene.scene].graph,
+                                    rel_pos,
+                                    frame_size,
+                                    settings,
+                                    SharedMaterial::new(material),
+                                ) {
+                                    self.sender.do_scene_command(command);
+                                }
+                            }
+                            Err(e) => Log::err(format!(
+                                "Unable to load material {}. Reason: {:?}",
+                                relative_path.display(),
+                                e
+                            )),
+                        }
+                    }
                     _ => {}
                 }
             }
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        camera::CameraController,
+        command::Command,
+        message::Message,
+        message::MessageSender,
+        scene::{commands::SceneContext, EditorScene},
+        scene_viewer::{
+            handle_debug_view_selection, make_debug_view_dropdown, make_surface_material_command,
+        },
+        Settings,
+    };
+    use fyrox::{
+        core::{
+            algebra::{Matrix4, Vector2, Vector3},
+            pool::Handle,
+        },
+        gui::{dropdown_list::DropdownListMessage, message::MessageDirection, UserInterface},
+        material::{Material, SharedMaterial},
+        renderer::{framework::state::PolygonFillMode, DebugView},
+        scene::{
+            base::BaseBuilder,
+            camera::CameraBuilder,
+            mesh::{
+                surface::{SurfaceBuilder, SurfaceData, SurfaceSharedData},
+                MeshBuilder,
+            },
+            node::Node,
+            pivot::PivotBuilder,
+            transform::TransformBuilder,
+            Scene,
+        },
+    };
+    use std::sync::mpsc::channel;
+
+    #[test]
+    fn test_material_drop_assigns_surface_material() {
+        let mut scene = Scene::new();
+        let root = scene.graph.get_root();
+        let editor_objects_root = PivotBuilder::new(BaseBuilder::new()).build(&mut scene.graph);
+        let camera_controller = CameraController::new(&mut scene.graph, editor_objects_root, None);
+
+        // A cube right in front of the editor camera.
+        let original_material = SharedMaterial::new(Material::standard());
+        let mesh = MeshBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(0.0, 1.0, 0.0))
+                    .build(),
+            ),
+        )
+        .with_surfaces(vec![SurfaceBuilder::new(SurfaceSharedData::new(
+            SurfaceData::make_cube(Matrix4::identity()),
+        ))
+        .with_material(original_material.clone())
+        .build()])
+        .build(&mut scene.graph);
+
+        let frame_size = Vector2::new(200.0, 200.0);
+        scene.graph.update(frame_size, 0.0, Default::default());
+
+        let mut editor_scene =
+            EditorScene::new_test_with(editor_objects_root, root, camera_controller);
+
+        let dropped_material = SharedMaterial::new(Material::standard());
+
+        // Nothing to assign the material to, when the material is dropped onto an empty spot.
+        assert!(make_surface_material_command(
+            &mut editor_scene,
+            &scene.graph,
+            Vector2::new(0.0, 0.0),
+            frame_size,
+            &Settings::default(),
+            dropped_material.clone(),
+        )
+        .is_none());
+
+        let mut command = make_surface_material_command(
+            &mut editor_scene,
+            &scene.graph,
+            frame_size.scale(0.5),
+            frame_size,
+            &Settings::default(),
+            dropped_material.clone(),
+        )
+        .unwrap();
+        assert_eq!(command.node(), mesh);
+        assert_eq!(command.surface_index(), 0);
+
+        let (sender, _receiver) = channel();
+        let mut context =
+            SceneContext::new_test(&mut editor_scene, &mut scene, MessageSender(sender));
+        let surface_material = |context: &SceneContext| {
+            context.scene.graph[mesh].as_mesh().surfaces()[0]
+                .material()
+                .clone()
+        };
+
+        command.execute(&mut context);
+        assert!(surface_material(&context) == dropped_material);
+
+        // Undo
+        command.revert(&mut context);
+        assert!(surface_material(&context) == original_material);
+
+        // Redo
+        command.execute(&mut context);
+        assert!(surface_material(&context) == dropped_material);
+    }
+
+    #[test]
+    fn test_debug_view_selection() {
+        let mut ui = UserInterface::new(Vector2::new(800.0, 600.0));
+        let dropdown = make_debug_view_dropdown(&mut ui.build_ctx());
+
+        let mut scene = Scene::new();
+        let root = scene.graph.get_root();
+        let camera_controller = CameraController::new(&mut scene.graph, root, None);
+        let editor_camera = camera_controller.camera;
+        let scene_camera = CameraBuilder::new(BaseBuilder::new()).build(&mut scene.graph);
+
+        let (sender, receiver) = channel();
+        let sender = MessageSender(sender);
+
+        // The same sequence as in the editor: the dropdown list sends a message, then the editor
+        // applies it to the editor camera.
+        let select = |index: usize, scene: &mut Scene| {
+            handle_debug_view_selection(
+                &DropdownListMessage::selection(
+                    dropdown,
+                    MessageDirection::FromWidget,
+                    Some(index),
+                ),
+                dropdown,
+                &sender,
+            );
+            match receiver.try_recv() {
+                Ok(Message::SetDebugView(debug_view)) => {
+                    camera_controller.set_debug_view(&mut scene.graph, debug_view)
+                }
+                _ => panic!("no debug view message was sent"),
+            }
+        };
+        // This is what the renderer uses to rasterize the scene for the given camera.
+        let fill_mode = |scene: &Scene, camera: Handle<Node>| {
+            scene.graph[camera]
+                .as_camera()
+                .debug_view()
+                .polygon_fill_mode(scene.polygon_rasterization_mode)
+        };
+
+        // Wireframe overrides rasterization mode of the editor camera only, the scene and its own
+        // cameras are not affected.
+        select(1, &mut scene);
+        assert_eq!(
+            scene.graph[editor_camera].as_camera().debug_view(),
+            DebugView::Wireframe
+        );
+        assert_eq!(fill_mode(&scene, editor_camera), PolygonFillMode::Line);
+        assert_eq!(
+            scene.graph[scene_camera].as_camera().debug_view(),
+            DebugView::Shaded
+        );
+        assert_eq!(fill_mode(&scene, scene_camera), PolygonFillMode::Fill);
+        assert_eq!(scene.polygon_rasterization_mode, PolygonFillMode::Fill);
+
+        // Shaded view resets it back to the mode of the scene.
+        select(0, &mut scene);
+        assert_eq!(
+            scene.graph[editor_camera].as_camera().debug_view(),
+            DebugView::Shaded
+        );
+        assert_eq!(fill_mode(&scene, editor_camera), PolygonFillMode::Fill);
+
+        // Messages sent to the dropdown list (not from it) are ignored.
+        handle_debug_view_selection(
+            &DropdownListMessage::selection(dropdown, MessageDirection::ToWidget, Some(1)),
+            dropdown,
+            &sender,
+        );
+        assert!(receiver.try_recv().is_err());
+    }
+}
@@ -24,10 +24,31 @@ fn default_zoom_speed() -> f32 {
     1.0
 }
 
+fn default_boost_multiplier() -> f32 {
+    2.0
+}
+
+fn default_look_sensitivity() -> f32 {
+    1.0
+}
+
 #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Reflect)]
 pub struct CameraSettings {
     #[serde(default)]
+    #[reflect(description = "Fly speed of the camera in units per second.")]
     pub speed: f32,
+    #[serde(default = "default_boost_multiplier")]
+    #[reflect(
+        description = "Multiplier of the fly speed, that is used while speed up key is pressed.",
+        min_value = 1.0
+    )]
+    pub boost_multiplier: f32,
+    #[serde(default = "default_look_sensitivity")]
+    #[reflect(
+        description = "Defines how fast the camera rotates when the mouse is moved.",
+        min_value = 0.0
+    )]
+    pub look_sensitivity: f32,
     #[serde(default)]
     pub invert_dragging: bool,
     #[serde(default)]
@@ -42,6 +63,8 @@ impl Default for CameraSettings {
     fn default() -> Self {
         Self {
             speed: 10.0,
+            boost_multiplier: default_boost_multiplier(),
+            look_sensitivity: default_look_sensitivity(),
             invert_dragging: false,
             drag_speed: 0.01,
             camera_settings: Default::default(),
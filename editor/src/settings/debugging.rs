@@ -1,6 +1,18 @@
 use fyrox::core::reflect::prelude::*;
 use serde::{Deserialize, Serialize};
 
+fn default_true() -> bool {
+    true
+}
+
+fn default_grid_size() -> u32 {
+    20
+}
+
+fn default_grid_spacing() -> f32 {
+    1.0
+}
+
 #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Reflect)]
 pub struct DebuggingSettings {
     pub show_physics: bool,
@@ -15,6 +27,27 @@ pub struct DebuggingSettings {
     #[reflect(description = "Size of pictograms in meters. It is used for objects like lights.")]
     #[serde(default)]
     pub pictogram_size: f32,
+    #[reflect(description = "Shows the ground grid in the scene viewer.")]
+    #[serde(default = "default_true")]
+    pub show_grid: bool,
+    #[reflect(
+        description = "Amount of grid cells in each direction from the origin.",
+        min_value = 1.0
+    )]
+    #[serde(default = "default_grid_size")]
+    pub grid_size: u32,
+    #[reflect(
+        description = "Distance between grid lines in meters.",
+        min_value = 0.01
+    )]
+    #[serde(default = "default_grid_spacing")]
+    pub grid_spacing: f32,
+    #[reflect(description = "Shows world X (red), Y (green) and Z (blue) axes.")]
+    #[serde(default = "default_true")]
+    pub show_world_axes: bool,
+    #[reflect(description = "Shows local coordinate system of every scene node.")]
+    #[serde(default)]
+    pub show_pivots: bool,
     #[reflect(
         description = "Forces the editor to save the scene in text form as well as standard binary."
     )]
@@ -32,6 +65,11 @@ impl Default for DebuggingSettings {
             show_light_bounds: true,
             show_camera_bounds: true,
             pictogram_size: 0.33,
+            show_grid: default_true(),
+            grid_size: default_grid_size(),
+            grid_spacing: default_grid_spacing(),
+            show_world_axes: default_true(),
+            show_pivots: false,
             save_scene_in_text_form: false,
         }
     }
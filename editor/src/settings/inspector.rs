@@ -0,0 +1,160 @@
+use fyrox::{
+    fxhash::FxHashMap,
+    gui::{
+        inspector::{editors::PropertyEditorDefinitionContainer, InspectorMessage},
+        message::{MessageDirection, UiMessage},
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default, Eq)]
+pub struct InspectorSettings {
+    /// Type names of inspected objects (scripts, nodes, etc.) mapped to the names of their pinned
+    /// properties.
+    #[serde(default)]
+    pub pinned_properties: BTreeMap<String, Vec<String>>,
+}
+
+impl InspectorSettings {
+    /// Applies pinned properties to the given property editors.
+    pub fn apply_to(&self, property_editors: &PropertyEditorDefinitionContainer) {
+        property_editors.set_pinned_properties(
+            self.pinned_properties
+                .iter()
+                .map(|(type_name, names)| (type_name.clone(), names.clone()))
+                .collect::<FxHashMap<_, _>>(),
+        );
+    }
+
+    /// Remembers pinned properties when a property is pinned or unpinned in any inspector. Returns
+    /// `true` if the settings were changed and must be saved.
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        property_editors: &PropertyEditorDefinitionContainer,
+    ) -> bool {
+        if message.direction() != MessageDirection::FromWidget {
+            return false;
+        }
+
+        if let Some(InspectorMessage::PinProperty { .. }) = message.data::<InspectorMessage>() {
+            let pinned_properties = property_editors
+                .pinned_properties()
+                .iter()
+                .map(|(type_name, names)| (type_name.clone(), names.clone()))
+                .collect::<BTreeMap<_, _>>();
+
+            if pinned_properties != self.pinned_properties {
+                self.pinned_properties = pinned_properties;
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::settings::Settings;
+    use fyrox::{
+        core::{algebra::Vector2, reflect::prelude::*},
+        gui::{
+            inspector::{
+                editors::PropertyEditorDefinitionContainer, Inspector, InspectorBuilder,
+                InspectorContext, InspectorMessage, PropertyFilter,
+            },
+            message::MessageDirection,
+            widget::WidgetBuilder,
+            UserInterface,
+        },
+    };
+    use std::rc::Rc;
+
+    #[derive(Reflect, Debug, Default)]
+    struct Player {
+        speed: f32,
+        yaw: f32,
+    }
+
+    #[test]
+    fn test_pinned_property_is_recorded_in_settings() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        let mut settings = Settings::default();
+        let property_editors = Rc::new(PropertyEditorDefinitionContainer::new());
+
+        let context = InspectorContext::from_object(
+            &Player::default(),
+            &mut ui.build_ctx(),
+            property_editors.clone(),
+            None,
+            1,
+            0,
+            false,
+            PropertyFilter::default(),
+        );
+        let inspector = InspectorBuilder::new(WidgetBuilder::new())
+            .with_context(context)
+            .build(&mut ui.build_ctx());
+
+        while ui.poll_message().is_some() {}
+
+        ui.send_message(InspectorMessage::pin_property(
+            inspector,
+            MessageDirection::ToWidget,
+            "yaw".to_string(),
+            true,
+        ));
+
+        let mut changed = false;
+        while let Some(message) = ui.poll_message() {
+            changed |= settings
+                .inspector
+                .handle_ui_message(&message, &property_editors);
+        }
+        assert!(changed);
+
+        let type_name = std::any::type_name::<Player>();
+        assert_eq!(
+            settings.inspector.pinned_properties.get(type_name),
+            Some(&vec!["yaw".to_string()])
+        );
+
+        let context = ui
+            .node(inspector)
+            .query_component::<Inspector>()
+            .unwrap()
+            .context()
+            .clone();
+        let yaw = context.find_property_editor("yaw").unwrap();
+        assert_eq!(
+            ui.node(yaw.property_container).parent(),
+            context.pinned_section
+        );
+
+        // Pins are restored from the settings for new inspectors.
+        let property_editors = Rc::new(PropertyEditorDefinitionContainer::new());
+        settings.inspector.apply_to(&property_editors);
+        let context = InspectorContext::from_object(
+            &Player::default(),
+            &mut ui.build_ctx(),
+            property_editors,
+            None,
+            1,
+            0,
+            false,
+            PropertyFilter::default(),
+        );
+        let yaw = context.find_property_editor("yaw").unwrap();
+        assert_eq!(
+            ui.node(yaw.property_container).parent(),
+            context.pinned_section
+        );
+        let speed = context.find_property_editor("speed").unwrap();
+        assert_eq!(
+            ui.node(speed.property_container).parent(),
+            context.stack_panel
+        );
+    }
+}
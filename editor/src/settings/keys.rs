@@ -26,6 +26,10 @@ pub struct KeyBindings {
     pub enable_scale_mode: HotKey,
     pub enable_navmesh_mode: HotKey,
     pub enable_terrain_mode: HotKey,
+    #[serde(default = "default_measure_mode_hotkey")]
+    pub enable_measure_mode: HotKey,
+    #[serde(default = "default_spline_mode_hotkey")]
+    pub enable_spline_mode: HotKey,
     pub save_scene: HotKey,
     pub load_scene: HotKey,
     pub copy_selection: HotKey,
@@ -35,12 +39,38 @@ pub struct KeyBindings {
     pub remove_selection: HotKey,
     #[serde(default = "default_focus_hotkey")]
     pub focus: HotKey,
+    #[serde(default = "default_toggle_gizmo_space_hotkey")]
+    pub toggle_gizmo_space: HotKey,
+    #[serde(default = "default_isolate_selection_hotkey")]
+    pub isolate_selection: HotKey,
+    #[serde(default = "default_toggle_simulation_hotkey")]
+    pub toggle_simulation: HotKey,
 }
 
 fn default_focus_hotkey() -> HotKey {
     HotKey::from_key_code(KeyCode::F)
 }
 
+fn default_measure_mode_hotkey() -> HotKey {
+    HotKey::from_key_code(KeyCode::Key7)
+}
+
+fn default_spline_mode_hotkey() -> HotKey {
+    HotKey::from_key_code(KeyCode::Key8)
+}
+
+fn default_toggle_gizmo_space_hotkey() -> HotKey {
+    HotKey::from_key_code(KeyCode::X)
+}
+
+fn default_isolate_selection_hotkey() -> HotKey {
+    HotKey::from_key_code(KeyCode::I)
+}
+
+fn default_toggle_simulation_hotkey() -> HotKey {
+    HotKey::from_key_code(KeyCode::F5)
+}
+
 impl Default for KeyBindings {
     fn default() -> Self {
         Self {
@@ -61,6 +91,8 @@ impl Default for KeyBindings {
             enable_scale_mode: HotKey::from_key_code(KeyCode::Key4),
             enable_navmesh_mode: HotKey::from_key_code(KeyCode::Key5),
             enable_terrain_mode: HotKey::from_key_code(KeyCode::Key6),
+            enable_measure_mode: default_measure_mode_hotkey(),
+            enable_spline_mode: default_spline_mode_hotkey(),
             save_scene: HotKey::ctrl_key(KeyCode::S),
             load_scene: HotKey::ctrl_key(KeyCode::L),
             copy_selection: HotKey::ctrl_key(KeyCode::C),
@@ -69,6 +101,9 @@ impl Default for KeyBindings {
             close_scene: HotKey::ctrl_key(KeyCode::Q),
             remove_selection: HotKey::from_key_code(KeyCode::Delete),
             focus: default_focus_hotkey(),
+            toggle_gizmo_space: default_toggle_gizmo_space_hotkey(),
+            isolate_selection: default_isolate_selection_hotkey(),
+            toggle_simulation: default_toggle_simulation_hotkey(),
         }
     }
 }
@@ -1,11 +1,13 @@
 use crate::{
     inspector::editors::make_property_editors_container,
+    interaction::{gizmo::GizmoSpace, pivot::SnapPivot},
     message::MessageSender,
     settings::{
         camera::CameraSettings, debugging::DebuggingSettings, graphics::GraphicsSettings,
-        keys::KeyBindings, model::ModelSettings, move_mode::MoveInteractionModeSettings,
-        navmesh::NavmeshSettings, recent::RecentFiles, rotate_mode::RotateInteractionModeSettings,
-        selection::SelectionSettings, windows::WindowsSettings,
+        inspector::InspectorSettings, keys::KeyBindings, model::ModelSettings,
+        move_mode::MoveInteractionModeSettings, navmesh::NavmeshSettings, recent::RecentFiles,
+        rotate_mode::RotateInteractionModeSettings, selection::SelectionSettings,
+        units::UnitSettings, windows::WindowsSettings,
     },
     Engine, MSG_SYNC_FLAG,
 };
@@ -19,6 +21,7 @@ use fyrox::{
                 enumeration::EnumPropertyEditorDefinition,
                 inspectable::InspectablePropertyEditorDefinition,
                 key::{HotKeyPropertyEditorDefinition, KeyBindingPropertyEditorDefinition},
+                numeric::AngleUnit,
                 PropertyEditorDefinitionContainer,
             },
             InspectorBuilder, InspectorContext, InspectorMessage, PropertyAction, PropertyChanged,
@@ -32,13 +35,18 @@ use fyrox::{
     },
     renderer::{CsmSettings, QualitySettings, ShadowMapPrecision},
 };
-use ron::ser::PrettyConfig;
+use ron::{extensions::Extensions, ser::PrettyConfig, Options};
 use serde::{Deserialize, Serialize};
-use std::{fs::File, path::PathBuf, rc::Rc};
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
 
 pub mod camera;
 pub mod debugging;
 pub mod graphics;
+pub mod inspector;
 pub mod keys;
 pub mod model;
 pub mod move_mode;
@@ -46,12 +54,14 @@ pub mod navmesh;
 pub mod recent;
 pub mod rotate_mode;
 pub mod selection;
+pub mod units;
 pub mod windows;
 
 pub struct SettingsWindow {
     window: Handle<UiNode>,
     ok: Handle<UiNode>,
     default: Handle<UiNode>,
+    save_as_global: Handle<UiNode>,
     inspector: Handle<UiNode>,
 }
 
@@ -62,15 +72,140 @@ pub struct Settings {
     pub debugging: DebuggingSettings,
     pub move_mode_settings: MoveInteractionModeSettings,
     pub rotate_mode_settings: RotateInteractionModeSettings,
+    #[serde(default)]
+    #[reflect(
+        description = "Orientation of move and rotation gizmos - either local axes of the selected node or world axes."
+    )]
+    pub gizmo_space: GizmoSpace,
     pub model: ModelSettings,
     pub camera: CameraSettings,
     pub navmesh: NavmeshSettings,
     pub key_bindings: KeyBindings,
+    #[serde(default)]
+    pub units: UnitSettings,
     #[reflect(hidden)]
     pub recent: RecentFiles,
     #[serde(default)]
     #[reflect(hidden)]
     pub windows: WindowsSettings,
+    #[serde(default)]
+    #[reflect(hidden)]
+    pub inspector: InspectorSettings,
+}
+
+/// Per-project overrides of the global editor settings. Settings are split in sections (top-level fields of
+/// [`Settings`]), every section that is present in the overrides replaces the same section of the global
+/// settings. Project settings file contains only the sections that differ from the global settings.
+#[derive(Deserialize, Serialize, PartialEq, Clone, Default, Debug)]
+#[serde(default)]
+pub struct SettingsOverrides {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selection: Option<SelectionSettings>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub graphics: Option<GraphicsSettings>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub debugging: Option<DebuggingSettings>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub move_mode_settings: Option<MoveInteractionModeSettings>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rotate_mode_settings: Option<RotateInteractionModeSettings>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gizmo_space: Option<GizmoSpace>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<ModelSettings>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera: Option<CameraSettings>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub navmesh: Option<NavmeshSettings>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_bindings: Option<KeyBindings>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub units: Option<UnitSettings>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recent: Option<RecentFiles>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub windows: Option<WindowsSettings>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inspector: Option<InspectorSettings>,
+}
+
+fn override_of<T: PartialEq + Clone>(value: &T, global: &T) -> Option<T> {
+    if value != global {
+        Some(value.clone())
+    } else {
+        None
+    }
+}
+
+impl SettingsOverrides {
+    /// Creates overrides with every section of the `settings`, that differs from the `global` settings.
+    pub fn diff(settings: &Settings, global: &Settings) -> Self {
+        Self {
+            selection: override_of(&settings.selection, &global.selection),
+            graphics: override_of(&settings.graphics, &global.graphics),
+            debugging: override_of(&settings.debugging, &global.debugging),
+            move_mode_settings: override_of(
+                &settings.move_mode_settings,
+                &global.move_mode_settings,
+            ),
+            rotate_mode_settings: override_of(
+                &settings.rotate_mode_settings,
+                &global.rotate_mode_settings,
+            ),
+            gizmo_space: override_of(&settings.gizmo_space, &global.gizmo_space),
+            model: override_of(&settings.model, &global.model),
+            camera: override_of(&settings.camera, &global.camera),
+            navmesh: override_of(&settings.navmesh, &global.navmesh),
+            key_bindings: override_of(&settings.key_bindings, &global.key_bindings),
+            units: override_of(&settings.units, &global.units),
+            recent: override_of(&settings.recent, &global.recent),
+            windows: override_of(&settings.windows, &global.windows),
+            inspector: override_of(&settings.inspector, &global.inspector),
+        }
+    }
+
+    /// Applies the overrides on top of the `global` settings and returns effective settings.
+    pub fn apply(self, global: Settings) -> Settings {
+        Settings {
+            selection: self.selection.unwrap_or(global.selection),
+            graphics: self.graphics.unwrap_or(global.graphics),
+            debugging: self.debugging.unwrap_or(global.debugging),
+            move_mode_settings: self.move_mode_settings.unwrap_or(global.move_mode_settings),
+            rotate_mode_settings: self
+                .rotate_mode_settings
+                .unwrap_or(global.rotate_mode_settings),
+            gizmo_space: self.gizmo_space.unwrap_or(global.gizmo_space),
+            model: self.model.unwrap_or(global.model),
+            camera: self.camera.unwrap_or(global.camera),
+            navmesh: self.navmesh.unwrap_or(global.navmesh),
+            key_bindings: self.key_bindings.unwrap_or(global.key_bindings),
+            units: self.units.unwrap_or(global.units),
+            recent: self.recent.unwrap_or(global.recent),
+            windows: self.windows.unwrap_or(global.windows),
+            inspector: self.inspector.unwrap_or(global.inspector),
+        }
+    }
+
+    /// Returns `true` if there are no overrides, `false` - otherwise.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    // Implicit `Some` allows to read the full settings of older versions as overrides of every section.
+    fn options() -> Options {
+        Options::default().with_default_extension(Extensions::IMPLICIT_SOME)
+    }
+
+    fn load(path: &Path) -> Result<Self, SettingsError> {
+        let file = File::open(path)?;
+        Ok(Self::options().from_reader(file)?)
+    }
+
+    fn save(&self, path: &Path) -> Result<(), SettingsError> {
+        let file = File::create(path)?;
+        Self::options().to_writer_pretty(file, self, PrettyConfig::default())?;
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
@@ -101,20 +236,114 @@ impl From<ron::Error> for SettingsError {
 impl Settings {
     const FILE_NAME: &'static str = "settings.ron";
 
-    fn full_path() -> PathBuf {
+    /// Returns a path of the global settings file, that is shared by every project. Returns `None` if there
+    /// is no configuration directory of the current user.
+    pub fn global_path() -> Option<PathBuf> {
+        let config_dir = if cfg!(target_os = "windows") {
+            std::env::var_os("APPDATA").map(PathBuf::from)
+        } else {
+            std::env::var_os("XDG_CONFIG_HOME")
+                .map(PathBuf::from)
+                .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+        };
+
+        config_dir.map(|dir| dir.join("fyroxed").join(Self::FILE_NAME))
+    }
+
+    /// Returns a path of the settings file of the current project. The file contains only overrides of
+    /// the global settings, see [`SettingsOverrides`] for more info.
+    pub fn project_path() -> PathBuf {
         Self::FILE_NAME.into()
     }
 
+    fn load_global(global_path: Option<&Path>) -> Result<Self, SettingsError> {
+        match global_path {
+            Some(path) if path.exists() => {
+                let file = File::open(path)?;
+                Ok(ron::de::from_reader(file)?)
+            }
+            _ => Ok(Default::default()),
+        }
+    }
+
+    /// Loads effective settings - global settings with the overrides of the current project applied on top.
     pub fn load() -> Result<Self, SettingsError> {
-        let file = File::open(Self::full_path())?;
-        Ok(ron::de::from_reader(file)?)
+        Self::load_layered(Self::global_path().as_deref(), &Self::project_path())
+    }
+
+    /// Loads global settings from the given file and applies project overrides from the other file on top.
+    /// Missing files are treated as default settings and empty overrides respectively.
+    pub fn load_layered(
+        global_path: Option<&Path>,
+        project_path: &Path,
+    ) -> Result<Self, SettingsError> {
+        let global = Self::load_global(global_path)?;
+        let overrides = if project_path.exists() {
+            SettingsOverrides::load(project_path)?
+        } else {
+            Default::default()
+        };
+        Ok(overrides.apply(global))
     }
 
+    /// Saves the settings in the project layer, only the sections that differ from the global settings are
+    /// written.
     pub fn save(&mut self) -> Result<(), SettingsError> {
-        let file = File::create(Self::full_path())?;
+        self.save_layered(Self::global_path().as_deref(), &Self::project_path())
+    }
+
+    /// Saves the settings as overrides (in the file at `project_path`) of the global settings from the file
+    /// at `global_path`. Project file is removed if there is nothing to override.
+    pub fn save_layered(
+        &mut self,
+        global_path: Option<&Path>,
+        project_path: &Path,
+    ) -> Result<(), SettingsError> {
         self.recent.deduplicate_and_refresh();
-        ron::ser::to_writer_pretty(file, self, PrettyConfig::default())?;
-        Ok(())
+
+        let global = Self::load_global(global_path)?;
+        let overrides = SettingsOverrides::diff(self, &global);
+        if overrides.is_empty() {
+            if project_path.exists() {
+                std::fs::remove_file(project_path)?;
+            }
+            Ok(())
+        } else {
+            overrides.save(project_path)
+        }
+    }
+
+    /// Makes current settings global defaults for every project. Overrides of the current project are
+    /// removed, except the list of recent files, which is always project-specific.
+    pub fn save_as_global(&mut self) -> Result<(), SettingsError> {
+        let global_path = Self::global_path().ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "There is no configuration directory for global settings!",
+            )
+        })?;
+        self.save_as_global_layered(&global_path, &Self::project_path())
+    }
+
+    /// Writes current settings to the global settings file and then saves the project overrides. See
+    /// [`Self::save_as_global`].
+    pub fn save_as_global_layered(
+        &mut self,
+        global_path: &Path,
+        project_path: &Path,
+    ) -> Result<(), SettingsError> {
+        if let Some(dir) = global_path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        let global = Settings {
+            recent: Default::default(),
+            ..self.clone()
+        };
+        let file = File::create(global_path)?;
+        ron::ser::to_writer_pretty(file, &global, PrettyConfig::default())?;
+
+        self.save_layered(Some(global_path), project_path)
     }
 
     fn make_property_editors_container(
@@ -125,6 +354,8 @@ impl Settings {
         container.insert(InspectablePropertyEditorDefinition::<GraphicsSettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<SelectionSettings>::new());
         container.insert(EnumPropertyEditorDefinition::<ShadowMapPrecision>::new());
+        container.insert(EnumPropertyEditorDefinition::<SnapPivot>::new());
+        container.insert(EnumPropertyEditorDefinition::<GizmoSpace>::new());
         container.insert(InspectablePropertyEditorDefinition::<DebuggingSettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<CsmSettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<QualitySettings>::new());
@@ -138,6 +369,8 @@ impl Settings {
         container.insert(InspectablePropertyEditorDefinition::<ModelSettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<NavmeshSettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<KeyBindings>::new());
+        container.insert(InspectablePropertyEditorDefinition::<UnitSettings>::new());
+        container.insert(EnumPropertyEditorDefinition::<AngleUnit>::new());
         container.insert(HotKeyPropertyEditorDefinition);
         container.insert(KeyBindingPropertyEditorDefinition);
 
@@ -157,6 +390,7 @@ impl SettingsWindow {
     pub fn new(engine: &mut Engine) -> Self {
         let ok;
         let default;
+        let save_as_global;
 
         let ctx = &mut engine.user_interface.build_ctx();
 
@@ -192,6 +426,16 @@ impl SettingsWindow {
                                         .build(ctx);
                                         default
                                     })
+                                    .with_child({
+                                        save_as_global = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(110.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Save As Global")
+                                        .build(ctx);
+                                        save_as_global
+                                    })
                                     .with_child({
                                         ok = ButtonBuilder::new(
                                             WidgetBuilder::new()
@@ -218,6 +462,7 @@ impl SettingsWindow {
             window,
             ok,
             default,
+            save_as_global,
             inspector,
         }
     }
@@ -271,6 +516,11 @@ impl SettingsWindow {
                 *settings = Default::default();
                 need_save = true;
                 self.sync_to_model(&mut engine.user_interface, settings, sender);
+            } else if message.destination() == self.save_as_global {
+                match settings.save_as_global() {
+                    Ok(_) => Log::info("Settings were saved as global settings!"),
+                    Err(e) => Log::err(format!("Unable to save global settings! Reason: {:?}!", e)),
+                }
             }
         } else if let Some(InspectorMessage::PropertyChanged(property_changed)) = message.data() {
             if message.destination() == self.inspector {
@@ -308,3 +558,57 @@ impl SettingsWindow {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        interaction::gizmo::GizmoSpace,
+        settings::{Settings, SettingsOverrides},
+    };
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("fyroxed_settings_test_{}", name));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn test_project_override_takes_precedence() {
+        let global_path = temp_path("global.ron");
+        let project_path = temp_path("project.ron");
+
+        let mut global = Settings::default();
+        global.camera.speed = 20.0;
+        global.gizmo_space = GizmoSpace::World;
+        global
+            .save_as_global_layered(&global_path, &project_path)
+            .unwrap();
+        // Nothing to override yet.
+        assert!(!project_path.exists());
+
+        // Edits are written to the project layer.
+        let mut settings = Settings::load_layered(Some(&global_path), &project_path).unwrap();
+        assert_eq!(settings, global);
+        settings.camera.speed = 5.0;
+        settings
+            .save_layered(Some(&global_path), &project_path)
+            .unwrap();
+
+        let settings = Settings::load_layered(Some(&global_path), &project_path).unwrap();
+        assert_eq!(settings.camera.speed, 5.0);
+        // Sections that weren't overridden come from the global settings.
+        assert_eq!(settings.gizmo_space, GizmoSpace::World);
+
+        // Global settings stay intact.
+        let global = Settings::load_layered(Some(&global_path), &temp_path("none.ron")).unwrap();
+        assert_eq!(global.camera.speed, 20.0);
+
+        let overrides = SettingsOverrides::diff(&settings, &global);
+        assert!(overrides.camera.is_some());
+        assert!(overrides.gizmo_space.is_none());
+
+        let _ = std::fs::remove_file(global_path);
+        let _ = std::fs::remove_file(project_path);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::interaction::pivot::SnapPivot;
 use fyrox::core::{algebra::Vector3, math, reflect::prelude::*};
 use serde::{Deserialize, Serialize};
 
@@ -7,6 +8,11 @@ pub struct MoveInteractionModeSettings {
     pub x_snap_step: f32,
     pub y_snap_step: f32,
     pub z_snap_step: f32,
+    #[serde(default)]
+    #[reflect(
+        description = "A point of a node, that is used for snapping to surfaces and as a location of the move gizmo."
+    )]
+    pub pivot: SnapPivot,
 }
 
 impl Default for MoveInteractionModeSettings {
@@ -16,6 +22,7 @@ impl Default for MoveInteractionModeSettings {
             x_snap_step: 0.05,
             y_snap_step: 0.05,
             z_snap_step: 0.05,
+            pivot: Default::default(),
         }
     }
 }
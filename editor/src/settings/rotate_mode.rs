@@ -1,11 +1,21 @@
-use fyrox::core::reflect::prelude::*;
+use fyrox::core::{
+    algebra::{UnitQuaternion, Vector3},
+    math,
+    reflect::prelude::*,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize, PartialEq, Debug, Clone, Reflect)]
 pub struct RotateInteractionModeSettings {
+    #[reflect(
+        description = "Always snap rotation to angle increments. Snapping could also be enabled temporarily by holding Ctrl while rotating."
+    )]
     pub angle_snapping: bool,
+    #[reflect(description = "Angle increment (in degrees) of rotation around X axis.")]
     pub x_snap_step: f32,
+    #[reflect(description = "Angle increment (in degrees) of rotation around Y axis.")]
     pub y_snap_step: f32,
+    #[reflect(description = "Angle increment (in degrees) of rotation around Z axis.")]
     pub z_snap_step: f32,
 }
 
@@ -19,3 +29,69 @@ impl Default for RotateInteractionModeSettings {
         }
     }
 }
+
+impl RotateInteractionModeSettings {
+    /// Rounds the angle of the given rotation to the angle increment of its axis. The rotation
+    /// must be around one of the coordinate axes (as any rotation made by the rotation gizmo).
+    pub fn snap_rotation(&self, rotation: UnitQuaternion<f32>) -> UnitQuaternion<f32> {
+        let scaled_axis = rotation.scaled_axis();
+
+        let (axis, step) = if scaled_axis.x.abs() >= scaled_axis.y.abs()
+            && scaled_axis.x.abs() >= scaled_axis.z.abs()
+        {
+            (0, self.x_snap_step)
+        } else if scaled_axis.y.abs() >= scaled_axis.z.abs() {
+            (1, self.y_snap_step)
+        } else {
+            (2, self.z_snap_step)
+        };
+
+        let mut snapped = Vector3::default();
+        snapped[axis] = math::round_to_step(scaled_axis[axis], step.to_radians());
+        UnitQuaternion::from_scaled_axis(snapped)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::settings::rotate_mode::RotateInteractionModeSettings;
+    use fyrox::core::algebra::{UnitQuaternion, Vector3};
+
+    #[test]
+    fn test_rotation_drag_snaps_to_increment() {
+        let settings = RotateInteractionModeSettings {
+            angle_snapping: true,
+            x_snap_step: 45.0,
+            y_snap_step: 15.0,
+            z_snap_step: 15.0,
+        };
+
+        // A drag is a sequence of small deltas, they must be accumulated before snapping,
+        // otherwise each of them is rounded to zero.
+        let mut accumulated = UnitQuaternion::identity();
+        for _ in 0..12 {
+            accumulated = UnitQuaternion::from_axis_angle(&Vector3::y_axis(), 2.0f32.to_radians())
+                * accumulated;
+        }
+
+        // 24 degrees is rounded to 30.
+        let snapped = settings.snap_rotation(accumulated);
+        assert!((snapped.angle() - 30.0f32.to_radians()).abs() < 1.0e-5);
+        assert!((snapped.axis().unwrap().into_inner() - Vector3::y()).norm() < 1.0e-5);
+
+        // Negative angles and per-axis increments.
+        let snapped = settings.snap_rotation(UnitQuaternion::from_axis_angle(
+            &Vector3::x_axis(),
+            -(20.0f32.to_radians()),
+        ));
+        assert!((snapped.scaled_axis() - Vector3::new(0.0, 0.0, 0.0)).norm() < 1.0e-5);
+        let snapped = settings.snap_rotation(UnitQuaternion::from_axis_angle(
+            &Vector3::x_axis(),
+            -(30.0f32.to_radians()),
+        ));
+        assert!(
+            (snapped.scaled_axis() - Vector3::new(-(45.0f32.to_radians()), 0.0, 0.0)).norm()
+                < 1.0e-5
+        );
+    }
+}
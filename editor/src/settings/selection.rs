@@ -1,20 +1,68 @@
-use fyrox::core::reflect::prelude::*;
-use serde::{Deserialize, Serialize};
+use fyrox::core::{color::Color, reflect::prelude::*};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-#[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Reflect, Eq)]
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Reflect)]
 pub struct SelectionSettings {
     pub ignore_back_faces: bool,
 
+    /// When enabled, a new selection is revealed in every panel: selected nodes are expanded and
+    /// scrolled into view in the world viewer, the inspector is brought to front and selecting a node
+    /// in the world viewer focuses the scene camera on it.
     // Hidden because there's a separate switch in world viewer for this.
     #[reflect(hidden)]
-    pub track_selection: bool,
+    #[serde(alias = "track_selection")]
+    pub focus_follows_selection: bool,
+
+    /// Whether light sources should stay visible when selection is isolated in the viewport.
+    #[serde(default = "default_isolation_keeps_lights")]
+    pub isolation_keeps_lights: bool,
+
+    #[reflect(description = "Color of the outline around selected objects in the scene viewer.")]
+    #[serde(
+        default = "default_outline_color",
+        serialize_with = "serialize_color",
+        deserialize_with = "deserialize_color"
+    )]
+    pub outline_color: Color,
+
+    #[reflect(
+        description = "Thickness of the outline around selected objects in pixels. Zero disables the outline.",
+        min_value = 0.0,
+        max_value = 10.0
+    )]
+    #[serde(default = "default_outline_thickness")]
+    pub outline_thickness: f32,
+}
+
+fn default_isolation_keeps_lights() -> bool {
+    true
+}
+
+fn default_outline_color() -> Color {
+    Color::opaque(255, 140, 0)
+}
+
+fn default_outline_thickness() -> f32 {
+    2.0
+}
+
+fn serialize_color<S: Serializer>(color: &Color, serializer: S) -> Result<S::Ok, S::Error> {
+    [color.r, color.g, color.b, color.a].serialize(serializer)
+}
+
+fn deserialize_color<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+    let [r, g, b, a] = <[u8; 4]>::deserialize(deserializer)?;
+    Ok(Color::from_rgba(r, g, b, a))
 }
 
 impl Default for SelectionSettings {
     fn default() -> Self {
         Self {
             ignore_back_faces: false,
-            track_selection: true,
+            focus_follows_selection: true,
+            isolation_keeps_lights: default_isolation_keeps_lights(),
+            outline_color: default_outline_color(),
+            outline_thickness: default_outline_thickness(),
         }
     }
 }
@@ -0,0 +1,40 @@
+use fyrox::{
+    core::reflect::prelude::*,
+    gui::inspector::editors::numeric::{AngleUnit, NumericDisplaySettings},
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Reflect, Eq)]
+pub struct UnitSettings {
+    #[reflect(
+        description = "Units in which angle properties are shown in the inspector. Angles are always stored in radians."
+    )]
+    pub angle_unit: AngleUnit,
+
+    #[reflect(
+        description = "Amount of decimal places of numeric properties in the inspector. Some properties may define their own precision.",
+        min_value = 0.0,
+        max_value = 10.0,
+        step = 1.0
+    )]
+    pub precision: usize,
+}
+
+impl Default for UnitSettings {
+    fn default() -> Self {
+        let display_settings = NumericDisplaySettings::default();
+        Self {
+            angle_unit: display_settings.angle_unit,
+            precision: display_settings.precision,
+        }
+    }
+}
+
+impl UnitSettings {
+    pub fn display_settings(&self) -> NumericDisplaySettings {
+        NumericDisplaySettings {
+            angle_unit: self.angle_unit,
+            precision: self.precision,
+        }
+    }
+}
@@ -419,7 +419,9 @@ impl PathFixer {
                                                 ));
                                             }
                                         }
-                                    } else if let Some(rectangle) = node.cast::<Rectangle>() {
+                                    } else if let Some(rectangle) =
+                                        node.query_component_ref::<Rectangle>()
+                                    {
                                         if let Some(texture) = rectangle.texture() {
                                             scene_resources
                                                 .insert(SceneResource::Texture(texture.clone()));
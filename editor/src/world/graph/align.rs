@@ -0,0 +1,531 @@
+//! Alignment tools move multiple nodes at once, so they're lined up along an axis (align) or evenly spaced
+//! along an axis (distribute). It is useful for level dressing - rows of props, walls, etc.
+
+use crate::{
+    gui::make_dropdown_list_option,
+    message::MessageSender,
+    scene::{
+        commands::{graph::MoveNodeCommand, CommandGroup, SceneCommand},
+        EditorScene, Selection,
+    },
+    Engine,
+};
+use fyrox::{
+    core::{
+        algebra::{Vector2, Vector3},
+        pool::Handle,
+    },
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        dropdown_list::{DropdownListBuilder, DropdownListMessage},
+        grid::{Column, GridBuilder, Row},
+        message::{MessageDirection, UiMessage},
+        stack_panel::StackPanelBuilder,
+        text::TextBuilder,
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+        VerticalAlignment,
+    },
+    scene::{graph::Graph, node::Node},
+};
+
+/// Defines which part of nodes will be lined up.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AlignTarget {
+    /// Nodes are lined up by their lowest coordinate on the axis.
+    Min,
+    /// Nodes are lined up by their centers, at the center of the selection.
+    Center,
+    /// Nodes are lined up by their highest coordinate on the axis.
+    Max,
+}
+
+/// Defines what will be used as extents of nodes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AlignMode {
+    /// World positions of nodes, every node is treated as a point.
+    Position,
+    /// World-space bounding boxes of nodes. Nodes without bounds (pivots, for example) are treated as
+    /// points.
+    BoundingBox,
+}
+
+/// Defines how nodes will be aligned or distributed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Alignment {
+    /// An index of a world axis (0 - X, 1 - Y, 2 - Z).
+    pub axis: usize,
+    /// Extents of nodes.
+    pub mode: AlignMode,
+}
+
+impl Default for Alignment {
+    fn default() -> Self {
+        Self {
+            axis: 0,
+            mode: AlignMode::Position,
+        }
+    }
+}
+
+impl Alignment {
+    /// Returns world-space extents (min and max) of the node along the axis.
+    fn extents(&self, node: &Node) -> (f32, f32) {
+        let position = node.global_position()[self.axis];
+        match self.mode {
+            AlignMode::Position => (position, position),
+            AlignMode::BoundingBox => {
+                let bounds = node.world_bounding_box();
+                if bounds.is_valid() {
+                    (bounds.min[self.axis], bounds.max[self.axis])
+                } else {
+                    (position, position)
+                }
+            }
+        }
+    }
+
+    fn collect(&self, graph: &Graph, nodes: &[Handle<Node>]) -> Vec<(Handle<Node>, f32, f32)> {
+        nodes
+            .iter()
+            .filter_map(|&handle| {
+                let node = graph.try_get(handle)?;
+                // Root node cannot be moved.
+                if node.parent().is_none() {
+                    return None;
+                }
+                let (min, max) = self.extents(node);
+                Some((handle, min, max))
+            })
+            .collect()
+    }
+
+    /// Makes a command, that moves the node by the given world-space distance along the axis.
+    fn make_move_command(
+        &self,
+        graph: &Graph,
+        handle: Handle<Node>,
+        delta: f32,
+    ) -> Option<SceneCommand> {
+        if delta.abs() <= f32::EPSILON {
+            return None;
+        }
+
+        let node = &graph[handle];
+        let mut world_offset = Vector3::default();
+        world_offset[self.axis] = delta;
+        let local_offset = graph
+            .try_get(node.parent())
+            .and_then(|parent| parent.global_transform().try_inverse())
+            .map_or(world_offset, |inv| inv.transform_vector(&world_offset));
+
+        let old_position = **node.local_transform().position();
+        Some(SceneCommand::new(MoveNodeCommand::new(
+            handle,
+            old_position,
+            old_position + local_offset,
+        )))
+    }
+
+    /// Creates a single command, that lines up every given node. Returns `None` if there is nothing
+    /// to move. Nodes should not be descendants of each other, otherwise descendants will be moved twice.
+    pub fn make_align_command(
+        &self,
+        graph: &Graph,
+        nodes: &[Handle<Node>],
+        target: AlignTarget,
+    ) -> Option<CommandGroup> {
+        let extents = self.collect(graph, nodes);
+        if extents.len() < 2 {
+            return None;
+        }
+
+        let min = extents
+            .iter()
+            .map(|(_, min, _)| *min)
+            .fold(f32::MAX, f32::min);
+        let max = extents
+            .iter()
+            .map(|(_, _, max)| *max)
+            .fold(f32::MIN, f32::max);
+
+        let commands = extents
+            .iter()
+            .filter_map(|&(handle, node_min, node_max)| {
+                let delta = match target {
+                    AlignTarget::Min => min - node_min,
+                    AlignTarget::Center => (min + max) * 0.5 - (node_min + node_max) * 0.5,
+                    AlignTarget::Max => max - node_max,
+                };
+                self.make_move_command(graph, handle, delta)
+            })
+            .collect::<Vec<_>>();
+
+        if commands.is_empty() {
+            None
+        } else {
+            Some(CommandGroup::from(commands))
+        }
+    }
+
+    /// Creates a single command, that spaces every given node evenly between the outermost ones. Positions
+    /// are spaced evenly in [`AlignMode::Position`] mode, and gaps between bounding boxes are made equal in
+    /// [`AlignMode::BoundingBox`] mode. Returns `None` if there is nothing to move (distribution requires at
+    /// least three nodes).
+    pub fn make_distribute_command(
+        &self,
+        graph: &Graph,
+        nodes: &[Handle<Node>],
+    ) -> Option<CommandGroup> {
+        let mut extents = self.collect(graph, nodes);
+        if extents.len() < 3 {
+            return None;
+        }
+
+        extents.sort_by(|(_, a_min, a_max), (_, b_min, b_max)| {
+            (a_min + a_max)
+                .partial_cmp(&(b_min + b_max))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let (_, first_min, first_max) = extents[0];
+        let (_, last_min, last_max) = extents[extents.len() - 1];
+        let total_size = extents.iter().map(|(_, min, max)| max - min).sum::<f32>();
+        let gap = ((last_max - first_min) - total_size) / (extents.len() - 1) as f32;
+        let step =
+            ((last_min + last_max) - (first_min + first_max)) * 0.5 / (extents.len() - 1) as f32;
+
+        let mut cursor = first_max + gap;
+        let mut commands = Vec::new();
+        for (index, &(handle, min, max)) in
+            extents.iter().enumerate().take(extents.len() - 1).skip(1)
+        {
+            let delta = match self.mode {
+                AlignMode::Position => {
+                    (first_min + first_max) * 0.5 + step * index as f32 - (min + max) * 0.5
+                }
+                AlignMode::BoundingBox => cursor - min,
+            };
+            cursor += (max - min) + gap;
+
+            commands.extend(self.make_move_command(graph, handle, delta));
+        }
+
+        if commands.is_empty() {
+            None
+        } else {
+            Some(CommandGroup::from(commands))
+        }
+    }
+}
+
+pub struct AlignWindow {
+    pub window: Handle<UiNode>,
+    axis: Handle<UiNode>,
+    mode: Handle<UiNode>,
+    align_min: Handle<UiNode>,
+    align_center: Handle<UiNode>,
+    align_max: Handle<UiNode>,
+    distribute: Handle<UiNode>,
+    close: Handle<UiNode>,
+    settings: Alignment,
+}
+
+fn make_label(text: &str, row: usize, ctx: &mut BuildContext) -> Handle<UiNode> {
+    TextBuilder::new(
+        WidgetBuilder::new()
+            .on_row(row)
+            .on_column(0)
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .with_text(text)
+    .with_vertical_text_alignment(VerticalAlignment::Center)
+    .build(ctx)
+}
+
+fn make_button(text: &str, ctx: &mut BuildContext) -> Handle<UiNode> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .with_width(70.0)
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .with_text(text)
+    .build(ctx)
+}
+
+impl AlignWindow {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let axis = DropdownListBuilder::new(
+            WidgetBuilder::new()
+                .on_row(0)
+                .on_column(1)
+                .with_margin(Thickness::uniform(1.0)),
+        )
+        .with_items(vec![
+            make_dropdown_list_option(ctx, "X"),
+            make_dropdown_list_option(ctx, "Y"),
+            make_dropdown_list_option(ctx, "Z"),
+        ])
+        .with_selected(0)
+        .build(ctx);
+        let mode = DropdownListBuilder::new(
+            WidgetBuilder::new()
+                .on_row(1)
+                .on_column(1)
+                .with_margin(Thickness::uniform(1.0)),
+        )
+        .with_items(vec![
+            make_dropdown_list_option(ctx, "Position"),
+            make_dropdown_list_option(ctx, "Bounding Box"),
+        ])
+        .with_selected(0)
+        .build(ctx);
+
+        let align_min = make_button("Min", ctx);
+        let align_center = make_button("Center", ctx);
+        let align_max = make_button("Max", ctx);
+        let distribute = make_button("Distribute", ctx);
+        let close = make_button("Close", ctx);
+
+        let window = WindowBuilder::new(
+            WidgetBuilder::new()
+                .with_width(300.0)
+                .with_height(145.0)
+                .with_min_size(Vector2::new(300.0, 145.0)),
+        )
+        .with_title(WindowTitle::text("Align And Distribute"))
+        .open(false)
+        .with_content(
+            GridBuilder::new(
+                WidgetBuilder::new()
+                    .with_child(
+                        GridBuilder::new(
+                            WidgetBuilder::new()
+                                .with_child(make_label("Axis", 0, ctx))
+                                .with_child(axis)
+                                .with_child(make_label("Mode", 1, ctx))
+                                .with_child(mode),
+                        )
+                        .add_column(Column::strict(80.0))
+                        .add_column(Column::stretch())
+                        .add_row(Row::strict(25.0))
+                        .add_row(Row::strict(25.0))
+                        .build(ctx),
+                    )
+                    .with_child(
+                        StackPanelBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(1)
+                                .with_horizontal_alignment(HorizontalAlignment::Center)
+                                .with_child(align_min)
+                                .with_child(align_center)
+                                .with_child(align_max)
+                                .with_child(distribute),
+                        )
+                        .with_orientation(Orientation::Horizontal)
+                        .build(ctx),
+                    )
+                    .with_child(
+                        StackPanelBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(2)
+                                .with_horizontal_alignment(HorizontalAlignment::Right)
+                                .with_child(close),
+                        )
+                        .with_orientation(Orientation::Horizontal)
+                        .build(ctx),
+                    ),
+            )
+            .add_row(Row::stretch())
+            .add_row(Row::strict(25.0))
+            .add_row(Row::strict(25.0))
+            .add_column(Column::stretch())
+            .build(ctx),
+        )
+        .build(ctx);
+
+        Self {
+            window,
+            axis,
+            mode,
+            align_min,
+            align_center,
+            align_max,
+            distribute,
+            close,
+            settings: Default::default(),
+        }
+    }
+
+    pub fn open(&self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::open(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        sender: &MessageSender,
+        editor_scene: &EditorScene,
+        engine: &Engine,
+    ) {
+        if message.direction() != MessageDirection::FromWidget {
+            return;
+        }
+
+        if let Some(DropdownListMessage::SelectionChanged(Some(index))) = message.data() {
+            if message.destination() == self.axis {
+                self.settings.axis = *index;
+            } else if message.destination() == self.mode {
+                self.settings.mode = if *index == 0 {
+                    AlignMode::Position
+                } else {
+                    AlignMode::BoundingBox
+                };
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.close {
+                engine.user_interface.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+                return;
+            }
+
+            if let Selection::Graph(selection) = &editor_scene.selection {
+                let graph = &engine.scenes[editor_scene.scene].graph;
+                let nodes = selection.root_nodes(graph);
+
+                let group = if message.destination() == self.align_min {
+                    self.settings
+                        .make_align_command(graph, &nodes, AlignTarget::Min)
+                } else if message.destination() == self.align_center {
+                    self.settings
+                        .make_align_command(graph, &nodes, AlignTarget::Center)
+                } else if message.destination() == self.align_max {
+                    self.settings
+                        .make_align_command(graph, &nodes, AlignTarget::Max)
+                } else if message.destination() == self.distribute {
+                    self.settings.make_distribute_command(graph, &nodes)
+                } else {
+                    None
+                };
+
+                if let Some(group) = group {
+                    sender.do_scene_command(group);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        command::Command,
+        message::MessageSender,
+        scene::{commands::SceneContext, EditorScene},
+        world::graph::align::{AlignMode, AlignTarget, Alignment},
+    };
+    use fyrox::{
+        core::{algebra::Vector3, pool::Handle},
+        scene::{
+            base::BaseBuilder, graph::Graph, node::Node, pivot::PivotBuilder,
+            transform::TransformBuilder, Scene,
+        },
+    };
+    use std::sync::mpsc::channel;
+
+    fn add_pivot(graph: &mut Graph, position: Vector3<f32>) -> Handle<Node> {
+        PivotBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(position)
+                    .build(),
+            ),
+        )
+        .build(graph)
+    }
+
+    fn global_x(graph: &mut Graph, node: Handle<Node>) -> f32 {
+        graph.update_hierarchical_data();
+        graph[node].global_position().x
+    }
+
+    #[test]
+    fn test_align_to_center_in_one_undo_step() {
+        let mut scene = Scene::new();
+        let a = add_pivot(&mut scene.graph, Vector3::new(-4.0, 1.0, 0.0));
+        let b = add_pivot(&mut scene.graph, Vector3::new(1.0, 2.0, 3.0));
+        // The last one is inside a scaled parent, so its local offset differs from the world one.
+        let c = add_pivot(&mut scene.graph, Vector3::new(3.0, 0.0, 0.0));
+        let parent = PivotBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_scale(Vector3::new(2.0, 2.0, 2.0))
+                    .build(),
+            ),
+        )
+        .build(&mut scene.graph);
+        scene.graph.link_nodes(c, parent);
+        scene.graph.update_hierarchical_data();
+
+        let mut editor_scene = EditorScene::new_test(&mut scene);
+
+        let (sender, _receiver) = channel();
+        let mut context =
+            SceneContext::new_test(&mut editor_scene, &mut scene, MessageSender(sender));
+
+        let alignment = Alignment {
+            axis: 0,
+            mode: AlignMode::Position,
+        };
+        let mut group = alignment
+            .make_align_command(&context.scene.graph, &[a, b, c], AlignTarget::Center)
+            .unwrap();
+        group.execute(&mut context);
+
+        // Center of [-4; 6] range.
+        let graph = &mut context.scene.graph;
+        for node in [a, b, c] {
+            assert!((global_x(graph, node) - 1.0).abs() < 1.0e-5);
+        }
+        // Other axes are untouched.
+        assert_eq!(graph[b].global_position().y, 2.0);
+        assert_eq!(graph[b].global_position().z, 3.0);
+
+        // Everything is reverted at once.
+        group.revert(&mut context);
+        let graph = &mut context.scene.graph;
+        assert_eq!(global_x(graph, a), -4.0);
+        assert_eq!(global_x(graph, b), 1.0);
+        assert_eq!(global_x(graph, c), 6.0);
+
+        group.finalize(&mut context);
+    }
+
+    #[test]
+    fn test_distribute_evenly() {
+        let mut graph = Graph::new();
+        let a = add_pivot(&mut graph, Vector3::new(0.0, 0.0, 0.0));
+        let b = add_pivot(&mut graph, Vector3::new(0.0, 0.0, 9.0));
+        let c = add_pivot(&mut graph, Vector3::new(0.0, 0.0, 1.0));
+        let d = add_pivot(&mut graph, Vector3::new(0.0, 0.0, 2.0));
+        graph.update_hierarchical_data();
+
+        let alignment = Alignment {
+            axis: 2,
+            mode: AlignMode::Position,
+        };
+        // The outermost nodes are not moved, so only two commands.
+        let group = alignment
+            .make_distribute_command(&graph, &[a, b, c, d])
+            .unwrap();
+        assert_eq!(group.len(), 2);
+        assert!(alignment.make_distribute_command(&graph, &[a, b]).is_none());
+    }
+}
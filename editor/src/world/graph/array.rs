@@ -0,0 +1,432 @@
+//! Array duplication creates multiple copies of nodes at once, placing them along a line (linear array)
+//! or around a center (radial array). It is useful for level dressing - fences, columns, etc.
+
+use crate::{
+    gui::make_dropdown_list_option,
+    message::MessageSender,
+    scene::{
+        commands::{graph::DuplicateNodeCommand, CommandGroup, SceneCommand},
+        EditorScene, Selection,
+    },
+    Engine,
+};
+use fyrox::{
+    core::{
+        algebra::{UnitQuaternion, Vector2, Vector3},
+        pool::Handle,
+    },
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        dropdown_list::{DropdownListBuilder, DropdownListMessage},
+        grid::{Column, GridBuilder, Row},
+        message::{MessageDirection, UiMessage},
+        numeric::{NumericUpDownBuilder, NumericUpDownMessage},
+        stack_panel::StackPanelBuilder,
+        text::TextBuilder,
+        vec::{Vec3EditorBuilder, Vec3EditorMessage},
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+        VerticalAlignment,
+    },
+    scene::{graph::Graph, node::Node},
+};
+
+/// Defines how copies will be placed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ArrayKind {
+    /// Copies are placed along a line, every next copy is shifted by [`ArrayDuplication::offset`]
+    /// from the previous one.
+    Linear,
+    /// Copies are placed around [`ArrayDuplication::center`], every next copy is rotated around
+    /// vertical axis by [`ArrayDuplication::angle`] relative to the previous one.
+    Radial,
+}
+
+/// Defines how nodes will be duplicated.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArrayDuplication {
+    /// A way of placing copies.
+    pub kind: ArrayKind,
+    /// Amount of copies of every node.
+    pub count: usize,
+    /// A distance between copies in a linear array, in the local space of the parent node.
+    pub offset: Vector3<f32>,
+    /// A center of a radial array, in the local space of the parent node.
+    pub center: Vector3<f32>,
+    /// An angle (in degrees) between copies in a radial array.
+    pub angle: f32,
+}
+
+impl Default for ArrayDuplication {
+    fn default() -> Self {
+        Self {
+            kind: ArrayKind::Linear,
+            count: 3,
+            offset: Vector3::new(1.0, 0.0, 0.0),
+            center: Default::default(),
+            angle: 45.0,
+        }
+    }
+}
+
+impl ArrayDuplication {
+    /// Calculates local position and rotation of a copy with the given index (starting from 1),
+    /// the index of the original node is 0.
+    pub fn make_transform(
+        &self,
+        position: Vector3<f32>,
+        rotation: UnitQuaternion<f32>,
+        index: usize,
+    ) -> (Vector3<f32>, UnitQuaternion<f32>) {
+        match self.kind {
+            ArrayKind::Linear => (position + self.offset.scale(index as f32), rotation),
+            ArrayKind::Radial => {
+                let delta = UnitQuaternion::from_axis_angle(
+                    &Vector3::y_axis(),
+                    (self.angle * index as f32).to_radians(),
+                );
+                (
+                    self.center + delta * (position - self.center),
+                    delta * rotation,
+                )
+            }
+        }
+    }
+
+    /// Creates a single command, that duplicates every given node. Returns `None` if there is
+    /// nothing to duplicate.
+    pub fn make_command(&self, graph: &Graph, nodes: &[Handle<Node>]) -> Option<CommandGroup> {
+        let mut commands = Vec::new();
+        for &handle in nodes {
+            if let Some(node) = graph.try_get(handle) {
+                // Root node cannot be duplicated, because copies must have the same parent.
+                if node.parent().is_none() {
+                    continue;
+                }
+
+                let position = **node.local_transform().position();
+                let rotation = **node.local_transform().rotation();
+                for index in 1..=self.count {
+                    let (position, rotation) = self.make_transform(position, rotation, index);
+                    commands.push(SceneCommand::new(DuplicateNodeCommand::new(
+                        handle, position, rotation,
+                    )));
+                }
+            }
+        }
+
+        if commands.is_empty() {
+            None
+        } else {
+            Some(CommandGroup::from(commands))
+        }
+    }
+}
+
+pub struct ArrayDuplicationWindow {
+    pub window: Handle<UiNode>,
+    kind: Handle<UiNode>,
+    count: Handle<UiNode>,
+    offset: Handle<UiNode>,
+    center: Handle<UiNode>,
+    angle: Handle<UiNode>,
+    duplicate: Handle<UiNode>,
+    cancel: Handle<UiNode>,
+    settings: ArrayDuplication,
+}
+
+fn make_label(text: &str, row: usize, ctx: &mut BuildContext) -> Handle<UiNode> {
+    TextBuilder::new(
+        WidgetBuilder::new()
+            .on_row(row)
+            .on_column(0)
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .with_text(text)
+    .with_vertical_text_alignment(VerticalAlignment::Center)
+    .build(ctx)
+}
+
+fn make_field_widget(row: usize) -> WidgetBuilder {
+    WidgetBuilder::new()
+        .on_row(row)
+        .on_column(1)
+        .with_margin(Thickness::uniform(1.0))
+}
+
+impl ArrayDuplicationWindow {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let settings = ArrayDuplication::default();
+
+        let kind = DropdownListBuilder::new(make_field_widget(0))
+            .with_items(vec![
+                make_dropdown_list_option(ctx, "Linear"),
+                make_dropdown_list_option(ctx, "Radial"),
+            ])
+            .with_selected(0)
+            .build(ctx);
+        let count = NumericUpDownBuilder::<u32>::new(make_field_widget(1))
+            .with_value(settings.count as u32)
+            .with_min_value(1)
+            .with_precision(0)
+            .build(ctx);
+        let offset = Vec3EditorBuilder::<f32>::new(make_field_widget(2))
+            .with_value(settings.offset)
+            .build(ctx);
+        let center = Vec3EditorBuilder::<f32>::new(make_field_widget(3))
+            .with_value(settings.center)
+            .build(ctx);
+        let angle = NumericUpDownBuilder::<f32>::new(make_field_widget(4))
+            .with_value(settings.angle)
+            .build(ctx);
+
+        let duplicate;
+        let cancel;
+        let window = WindowBuilder::new(
+            WidgetBuilder::new()
+                .with_width(300.0)
+                .with_height(195.0)
+                .with_min_size(Vector2::new(300.0, 195.0)),
+        )
+        .with_title(WindowTitle::text("Duplicate As Array"))
+        .open(false)
+        .with_content(
+            GridBuilder::new(
+                WidgetBuilder::new()
+                    .with_child(
+                        GridBuilder::new(
+                            WidgetBuilder::new()
+                                .with_child(make_label("Kind", 0, ctx))
+                                .with_child(kind)
+                                .with_child(make_label("Count", 1, ctx))
+                                .with_child(count)
+                                .with_child(make_label("Offset", 2, ctx))
+                                .with_child(offset)
+                                .with_child(make_label("Center", 3, ctx))
+                                .with_child(center)
+                                .with_child(make_label("Angle", 4, ctx))
+                                .with_child(angle),
+                        )
+                        .add_column(Column::strict(80.0))
+                        .add_column(Column::stretch())
+                        .add_row(Row::strict(25.0))
+                        .add_row(Row::strict(25.0))
+                        .add_row(Row::strict(25.0))
+                        .add_row(Row::strict(25.0))
+                        .add_row(Row::strict(25.0))
+                        .build(ctx),
+                    )
+                    .with_child(
+                        StackPanelBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(1)
+                                .with_horizontal_alignment(HorizontalAlignment::Right)
+                                .with_child({
+                                    duplicate = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(80.0)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("Duplicate")
+                                    .build(ctx);
+                                    duplicate
+                                })
+                                .with_child({
+                                    cancel = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(80.0)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("Cancel")
+                                    .build(ctx);
+                                    cancel
+                                }),
+                        )
+                        .with_orientation(Orientation::Horizontal)
+                        .build(ctx),
+                    ),
+            )
+            .add_row(Row::stretch())
+            .add_row(Row::strict(25.0))
+            .add_column(Column::stretch())
+            .build(ctx),
+        )
+        .build(ctx);
+
+        Self {
+            window,
+            kind,
+            count,
+            offset,
+            center,
+            angle,
+            duplicate,
+            cancel,
+            settings,
+        }
+    }
+
+    pub fn open(&self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::open_modal(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    fn close(&self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::close(
+            self.window,
+            MessageDirection::ToWidget,
+        ));
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        sender: &MessageSender,
+        editor_scene: &EditorScene,
+        engine: &Engine,
+    ) {
+        if message.direction() != MessageDirection::FromWidget {
+            return;
+        }
+
+        if let Some(DropdownListMessage::SelectionChanged(Some(index))) = message.data() {
+            if message.destination() == self.kind {
+                self.settings.kind = if *index == 0 {
+                    ArrayKind::Linear
+                } else {
+                    ArrayKind::Radial
+                };
+            }
+        } else if let Some(NumericUpDownMessage::Value(value)) =
+            message.data::<NumericUpDownMessage<u32>>()
+        {
+            if message.destination() == self.count {
+                self.settings.count = *value as usize;
+            }
+        } else if let Some(NumericUpDownMessage::Value(value)) =
+            message.data::<NumericUpDownMessage<f32>>()
+        {
+            if message.destination() == self.angle {
+                self.settings.angle = *value;
+            }
+        } else if let Some(Vec3EditorMessage::Value(value)) =
+            message.data::<Vec3EditorMessage<f32>>()
+        {
+            if message.destination() == self.offset {
+                self.settings.offset = *value;
+            } else if message.destination() == self.center {
+                self.settings.center = *value;
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.duplicate {
+                if let Selection::Graph(selection) = &editor_scene.selection {
+                    let graph = &engine.scenes[editor_scene.scene].graph;
+                    if let Some(group) = self.settings.make_command(graph, selection.nodes()) {
+                        sender.do_scene_command(group);
+                    }
+                }
+                self.close(&engine.user_interface);
+            } else if message.destination() == self.cancel {
+                self.close(&engine.user_interface);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        command::Command,
+        message::MessageSender,
+        scene::{commands::SceneContext, EditorScene},
+        world::graph::array::{ArrayDuplication, ArrayKind},
+    };
+    use fyrox::{
+        core::algebra::Vector3,
+        scene::{base::BaseBuilder, pivot::PivotBuilder, transform::TransformBuilder, Scene},
+    };
+    use std::sync::mpsc::channel;
+
+    #[test]
+    fn test_linear_array_in_one_undo_step() {
+        let mut scene = Scene::new();
+        let parent = PivotBuilder::new(BaseBuilder::new()).build(&mut scene.graph);
+        let node = PivotBuilder::new(
+            BaseBuilder::new()
+                .with_name("Column")
+                .with_children(&[PivotBuilder::new(BaseBuilder::new()).build(&mut scene.graph)])
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(1.0, 0.0, 0.0))
+                        .build(),
+                ),
+        )
+        .build(&mut scene.graph);
+        scene.graph.link_nodes(node, parent);
+
+        let mut editor_scene = EditorScene::new_test(&mut scene);
+
+        let (sender, _receiver) = channel();
+        let mut context =
+            SceneContext::new_test(&mut editor_scene, &mut scene, MessageSender(sender));
+
+        let node_count = context.scene.graph.node_count();
+
+        let array = ArrayDuplication {
+            kind: ArrayKind::Linear,
+            count: 3,
+            offset: Vector3::new(0.0, 0.0, 2.0),
+            ..Default::default()
+        };
+        let mut group = array.make_command(&context.scene.graph, &[node]).unwrap();
+        group.execute(&mut context);
+
+        // Every copy is a deep copy with its children.
+        assert_eq!(context.scene.graph.node_count(), node_count + 3 * 2);
+
+        let graph = &context.scene.graph;
+        let children = graph[parent].children();
+        assert_eq!(children.len(), 4);
+        let positions = children
+            .iter()
+            .map(|c| **graph[*c].local_transform().position())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            positions,
+            [
+                Vector3::new(1.0, 0.0, 0.0),
+                Vector3::new(1.0, 0.0, 2.0),
+                Vector3::new(1.0, 0.0, 4.0),
+                Vector3::new(1.0, 0.0, 6.0),
+            ]
+        );
+        for copy in &children[1..] {
+            assert_eq!(graph[*copy].name(), "Column");
+            assert_eq!(graph[*copy].children().len(), 1);
+        }
+
+        // Whole array is removed at once.
+        group.revert(&mut context);
+        assert_eq!(context.scene.graph.node_count(), node_count);
+        assert_eq!(context.scene.graph[parent].children(), &[node]);
+
+        group.finalize(&mut context);
+    }
+
+    #[test]
+    fn test_radial_array_transform() {
+        let array = ArrayDuplication {
+            kind: ArrayKind::Radial,
+            angle: 90.0,
+            ..Default::default()
+        };
+
+        let (position, _) =
+            array.make_transform(Vector3::new(1.0, 0.0, 0.0), Default::default(), 2);
+        assert!((position - Vector3::new(-1.0, 0.0, 0.0)).norm() < 1e-5);
+    }
+}
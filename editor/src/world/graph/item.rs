@@ -28,11 +28,13 @@ use std::{
 pub enum SceneItemMessage {
     Name(String),
     Validate(Result<(), String>),
+    Notes(String),
 }
 
 impl SceneItemMessage {
     define_constructor!(SceneItemMessage:Name => fn name(String), layout: false);
     define_constructor!(SceneItemMessage:Validate => fn validate(Result<(), String>), layout: false);
+    define_constructor!(SceneItemMessage:Notes => fn notes(String), layout: false);
 }
 
 pub struct SceneItem {
@@ -43,6 +45,9 @@ pub struct SceneItem {
     pub entity_handle: Handle<Node>,
     // Can be unassigned if there's no warning.
     pub warning_icon: Handle<UiNode>,
+    notes_value: String,
+    // Can be unassigned if there's no notes.
+    pub notes_icon: Handle<UiNode>,
     sender: MessageSender,
 }
 
@@ -50,6 +55,10 @@ impl SceneItem {
     pub fn name(&self) -> &str {
         &self.name_value
     }
+
+    pub fn notes(&self) -> &str {
+        &self.notes_value
+    }
 }
 
 impl Clone for SceneItem {
@@ -61,6 +70,8 @@ impl Clone for SceneItem {
             grid: self.grid,
             entity_handle: self.entity_handle,
             warning_icon: self.warning_icon,
+            notes_value: self.notes_value.clone(),
+            notes_icon: self.notes_icon,
             sender: self.sender.clone(),
         }
     }
@@ -164,6 +175,40 @@ impl Control for SceneItem {
                     }
                 }
             }
+        } else if let Some(SceneItemMessage::Notes(notes)) = message.data() {
+            if message.destination() == self.handle() && &self.notes_value != notes {
+                self.notes_value = notes.clone();
+
+                if self.notes_icon.is_some() {
+                    ui.send_message(WidgetMessage::remove(
+                        self.notes_icon,
+                        MessageDirection::ToWidget,
+                    ));
+                    self.notes_icon = Handle::NONE;
+                }
+
+                if !notes.is_empty() {
+                    self.notes_icon = ImageBuilder::new(
+                        WidgetBuilder::new()
+                            .with_width(16.0)
+                            .with_height(16.0)
+                            .with_tooltip(make_simple_tooltip(&mut ui.build_ctx(), notes))
+                            .with_margin(Thickness::uniform(1.0))
+                            .on_row(0)
+                            .on_column(3),
+                    )
+                    .with_opt_texture(load_image(include_bytes!(
+                        "../../../resources/embed/doc.png"
+                    )))
+                    .build(&mut ui.build_ctx());
+
+                    ui.send_message(WidgetMessage::link(
+                        self.notes_icon,
+                        MessageDirection::ToWidget,
+                        self.grid,
+                    ));
+                }
+            }
         } else if let Some(WidgetMessage::DoubleClick { .. }) = message.data() {
             let flag = 0b0010;
             if message.flags & flag != flag {
@@ -267,6 +312,7 @@ impl SceneItemBuilder {
         .add_column(Column::auto())
         .add_column(Column::stretch())
         .add_column(Column::auto())
+        .add_column(Column::auto())
         .build(ctx);
 
         let tree = self.tree_builder.with_content(content).build_tree(ctx);
@@ -278,6 +324,8 @@ impl SceneItemBuilder {
             text_name,
             grid: content,
             warning_icon: Default::default(),
+            notes_value: Default::default(),
+            notes_icon: Default::default(),
             sender,
         };
 
@@ -4,7 +4,9 @@ use crate::{
     message::MessageSender,
     scene::{
         commands::{
-            graph::{AddNodeCommand, ReplaceNodeCommand, SetGraphRootCommand},
+            graph::{
+                AddNodeCommand, ReplaceNodeCommand, SetChildIndexCommand, SetGraphRootCommand,
+            },
             make_delete_selection_command,
         },
         EditorScene, Selection,
@@ -12,8 +14,9 @@ use crate::{
     Engine, Message, MessageDirection, PasteCommand,
 };
 use fyrox::{
-    core::{algebra::Vector2, pool::Handle, scope_profile},
+    core::{algebra::Vector2, log::Log, pool::Handle, scope_profile},
     gui::{
+        copypasta::ClipboardProvider,
         file_browser::FileSelectorMessage,
         menu::{MenuItemBuilder, MenuItemContent, MenuItemMessage},
         message::UiMessage,
@@ -36,6 +39,12 @@ pub struct ItemContextMenu {
     save_as_prefab_dialog: Handle<UiNode>,
     paste: Handle<UiNode>,
     make_root: Handle<UiNode>,
+    copy_path: Handle<UiNode>,
+    move_up: Handle<UiNode>,
+    move_down: Handle<UiNode>,
+    toggle_hidden: Handle<UiNode>,
+    toggle_locked: Handle<UiNode>,
+    toggle_isolation: Handle<UiNode>,
 }
 
 impl ItemContextMenu {
@@ -45,6 +54,12 @@ impl ItemContextMenu {
         let save_as_prefab;
         let paste;
         let make_root;
+        let copy_path;
+        let move_up;
+        let move_down;
+        let toggle_hidden;
+        let toggle_locked;
+        let toggle_isolation;
 
         let (create_entity_menu, create_entity_menu_root_items) = CreateEntityMenu::new(ctx);
         let (replace_with_menu, replace_with_menu_root_items) = CreateEntityMenu::new(ctx);
@@ -67,6 +82,10 @@ impl ItemContextMenu {
                             paste = create_menu_item("Paste As Child", vec![], ctx);
                             paste
                         })
+                        .with_child({
+                            copy_path = create_menu_item("Copy Path", vec![], ctx);
+                            copy_path
+                        })
                         .with_child({
                             save_as_prefab = create_menu_item("Save As Prefab...", vec![], ctx);
                             save_as_prefab
@@ -83,6 +102,29 @@ impl ItemContextMenu {
                             make_root = create_menu_item("Make Root", vec![], ctx);
                             make_root
                         })
+                        .with_child({
+                            move_up = create_menu_item("Move Up", vec![], ctx);
+                            move_up
+                        })
+                        .with_child({
+                            move_down = create_menu_item("Move Down", vec![], ctx);
+                            move_down
+                        })
+                        .with_child({
+                            toggle_hidden =
+                                create_menu_item("Toggle Hidden In Viewport", vec![], ctx);
+                            toggle_hidden
+                        })
+                        .with_child({
+                            toggle_locked =
+                                create_menu_item("Toggle Locked From Selection", vec![], ctx);
+                            toggle_locked
+                        })
+                        .with_child({
+                            toggle_isolation =
+                                create_menu_item_shortcut("Toggle Isolation", "I", vec![], ctx);
+                            toggle_isolation
+                        })
                         .with_child(
                             MenuItemBuilder::new(
                                 WidgetBuilder::new().with_min_size(Vector2::new(120.0, 22.0)),
@@ -111,6 +153,12 @@ impl ItemContextMenu {
             replace_with_menu,
             paste,
             make_root,
+            copy_path,
+            move_up,
+            move_down,
+            toggle_hidden,
+            toggle_locked,
+            toggle_isolation,
         }
     }
 
@@ -174,6 +222,52 @@ impl ItemContextMenu {
                         MessageDirection::ToWidget,
                         Some(std::env::current_dir().unwrap()),
                     ));
+            } else if message.destination() == self.copy_path {
+                if let Selection::Graph(graph_selection) = &editor_scene.selection {
+                    if let Some(first) = graph_selection.nodes.first() {
+                        let path = engine.scenes[editor_scene.scene].graph.path_of(*first);
+                        if let Some(mut clipboard) = engine.user_interface.clipboard_mut() {
+                            Log::verify(clipboard.set_contents(path));
+                        }
+                    }
+                }
+            } else if message.destination() == self.move_up
+                || message.destination() == self.move_down
+            {
+                if let Selection::Graph(graph_selection) = &editor_scene.selection {
+                    if let Some(first) = graph_selection.nodes.first() {
+                        let graph = &engine.scenes[editor_scene.scene].graph;
+                        let parent = graph[*first].parent();
+                        if let Some(index) = graph
+                            .try_get(parent)
+                            .and_then(|p| p.children().iter().position(|c| c == first))
+                        {
+                            let new_index = if message.destination() == self.move_up {
+                                index.checked_sub(1)
+                            } else {
+                                Some(index + 1).filter(|i| *i < graph[parent].children().len())
+                            };
+                            if let Some(new_index) = new_index {
+                                sender.do_scene_command(SetChildIndexCommand::new(
+                                    parent, *first, new_index,
+                                ));
+                            }
+                        }
+                    }
+                }
+            } else if message.destination() == self.toggle_hidden {
+                if let Selection::Graph(graph_selection) = &editor_scene.selection {
+                    sender.send(Message::ToggleNodesHidden(graph_selection.nodes.clone()));
+                }
+            } else if message.destination() == self.toggle_locked {
+                if let Selection::Graph(graph_selection) = &editor_scene.selection {
+                    for &node in graph_selection.nodes.iter() {
+                        let locked = editor_scene.node_flags.is_locked(node);
+                        editor_scene.node_flags.set_locked(node, !locked);
+                    }
+                }
+            } else if message.destination() == self.toggle_isolation {
+                sender.send(Message::ToggleSelectionIsolation);
             } else if message.destination() == self.make_root {
                 if let Selection::Graph(graph_selection) = &editor_scene.selection {
                     if let Some(first) = graph_selection.nodes.first() {
@@ -1,3 +1,6 @@
+pub mod align;
+pub mod array;
 pub mod item;
 pub mod menu;
+pub mod rename;
 pub mod selection;
@@ -0,0 +1,360 @@
+//! Batch rename allows you to rename multiple nodes at once using a name pattern with auto-incrementing
+//! indices and/or find-and-replace within existing names.
+
+use crate::{
+    message::MessageSender,
+    scene::{
+        commands::{graph::SetNodeNameCommand, CommandGroup, SceneCommand},
+        EditorScene, Selection,
+    },
+    Engine,
+};
+use fyrox::{
+    core::{algebra::Vector2, pool::Handle},
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        grid::{Column, GridBuilder, Row},
+        message::{MessageDirection, UiMessage},
+        numeric::{NumericUpDownBuilder, NumericUpDownMessage},
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        text_box::TextBoxBuilder,
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+        VerticalAlignment,
+    },
+    scene::{graph::Graph, node::Node},
+};
+
+/// Defines how names of nodes will be changed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BatchRename {
+    /// A pattern of new names. Every run of `#` characters is replaced with the index of a node,
+    /// padded with zeros to the length of the run - `Tree_###` gives `Tree_001`, `Tree_002`, etc.
+    /// Empty pattern keeps the existing names.
+    pub pattern: String,
+    /// An index of the first node.
+    pub start_index: usize,
+    /// A substring of names, that will be replaced with [`Self::replace`]. Empty string disables
+    /// find-and-replace.
+    pub find: String,
+    /// A replacement for [`Self::find`].
+    pub replace: String,
+}
+
+impl Default for BatchRename {
+    fn default() -> Self {
+        Self {
+            pattern: Default::default(),
+            start_index: 1,
+            find: Default::default(),
+            replace: Default::default(),
+        }
+    }
+}
+
+impl BatchRename {
+    /// Makes a new name for a node with the given current name and index.
+    pub fn make_name(&self, old_name: &str, index: usize) -> String {
+        let mut name = if self.pattern.is_empty() {
+            old_name.to_owned()
+        } else {
+            let mut name = String::with_capacity(self.pattern.len());
+            let mut chars = self.pattern.chars().peekable();
+            while let Some(c) = chars.next() {
+                if c == '#' {
+                    let mut width = 1;
+                    while chars.peek() == Some(&'#') {
+                        chars.next();
+                        width += 1;
+                    }
+                    name.push_str(&format!("{:0width$}", index, width = width));
+                } else {
+                    name.push(c);
+                }
+            }
+            name
+        };
+
+        if !self.find.is_empty() {
+            name = name.replace(&self.find, &self.replace);
+        }
+
+        name
+    }
+
+    /// Creates a single command, that renames every given node. Nodes are numbered in the given order.
+    /// Returns `None` if no name will be changed.
+    pub fn make_command(&self, graph: &Graph, nodes: &[Handle<Node>]) -> Option<CommandGroup> {
+        let commands = nodes
+            .iter()
+            .filter(|handle| graph.is_valid_handle(**handle))
+            .enumerate()
+            .filter_map(|(i, &handle)| {
+                let old_name = graph[handle].name();
+                let new_name = self.make_name(old_name, self.start_index + i);
+                if new_name != old_name {
+                    Some(SceneCommand::new(SetNodeNameCommand::new(handle, new_name)))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if commands.is_empty() {
+            None
+        } else {
+            Some(CommandGroup::from(commands))
+        }
+    }
+}
+
+pub struct BatchRenameWindow {
+    pub window: Handle<UiNode>,
+    pattern: Handle<UiNode>,
+    start_index: Handle<UiNode>,
+    find: Handle<UiNode>,
+    replace: Handle<UiNode>,
+    rename: Handle<UiNode>,
+    cancel: Handle<UiNode>,
+    settings: BatchRename,
+}
+
+fn make_label(text: &str, row: usize, ctx: &mut BuildContext) -> Handle<UiNode> {
+    TextBuilder::new(
+        WidgetBuilder::new()
+            .on_row(row)
+            .on_column(0)
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .with_text(text)
+    .with_vertical_text_alignment(VerticalAlignment::Center)
+    .build(ctx)
+}
+
+fn make_text_box(row: usize, ctx: &mut BuildContext) -> Handle<UiNode> {
+    TextBoxBuilder::new(
+        WidgetBuilder::new()
+            .on_row(row)
+            .on_column(1)
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .with_vertical_text_alignment(VerticalAlignment::Center)
+    .build(ctx)
+}
+
+impl BatchRenameWindow {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let settings = BatchRename::default();
+
+        let pattern = make_text_box(0, ctx);
+        let start_index = NumericUpDownBuilder::<u32>::new(
+            WidgetBuilder::new()
+                .on_row(1)
+                .on_column(1)
+                .with_margin(Thickness::uniform(1.0)),
+        )
+        .with_value(settings.start_index as u32)
+        .with_precision(0)
+        .build(ctx);
+        let find = make_text_box(2, ctx);
+        let replace = make_text_box(3, ctx);
+
+        let rename;
+        let cancel;
+        let window = WindowBuilder::new(
+            WidgetBuilder::new()
+                .with_width(300.0)
+                .with_height(170.0)
+                .with_min_size(Vector2::new(300.0, 170.0)),
+        )
+        .with_title(WindowTitle::text("Batch Rename"))
+        .open(false)
+        .with_content(
+            GridBuilder::new(
+                WidgetBuilder::new()
+                    .with_child(
+                        GridBuilder::new(
+                            WidgetBuilder::new()
+                                .with_child(make_label("Pattern", 0, ctx))
+                                .with_child(pattern)
+                                .with_child(make_label("Start Index", 1, ctx))
+                                .with_child(start_index)
+                                .with_child(make_label("Find", 2, ctx))
+                                .with_child(find)
+                                .with_child(make_label("Replace", 3, ctx))
+                                .with_child(replace),
+                        )
+                        .add_column(Column::strict(80.0))
+                        .add_column(Column::stretch())
+                        .add_row(Row::strict(25.0))
+                        .add_row(Row::strict(25.0))
+                        .add_row(Row::strict(25.0))
+                        .add_row(Row::strict(25.0))
+                        .build(ctx),
+                    )
+                    .with_child(
+                        StackPanelBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(1)
+                                .with_horizontal_alignment(HorizontalAlignment::Right)
+                                .with_child({
+                                    rename = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(80.0)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("Rename")
+                                    .build(ctx);
+                                    rename
+                                })
+                                .with_child({
+                                    cancel = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(80.0)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("Cancel")
+                                    .build(ctx);
+                                    cancel
+                                }),
+                        )
+                        .with_orientation(Orientation::Horizontal)
+                        .build(ctx),
+                    ),
+            )
+            .add_row(Row::stretch())
+            .add_row(Row::strict(25.0))
+            .add_column(Column::stretch())
+            .build(ctx),
+        )
+        .build(ctx);
+
+        Self {
+            window,
+            pattern,
+            start_index,
+            find,
+            replace,
+            rename,
+            cancel,
+            settings,
+        }
+    }
+
+    pub fn open(&self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::open_modal(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    fn close(&self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::close(
+            self.window,
+            MessageDirection::ToWidget,
+        ));
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        sender: &MessageSender,
+        editor_scene: &EditorScene,
+        engine: &Engine,
+    ) {
+        if message.direction() != MessageDirection::FromWidget {
+            return;
+        }
+
+        if let Some(TextMessage::Text(text)) = message.data() {
+            if message.destination() == self.pattern {
+                self.settings.pattern = text.clone();
+            } else if message.destination() == self.find {
+                self.settings.find = text.clone();
+            } else if message.destination() == self.replace {
+                self.settings.replace = text.clone();
+            }
+        } else if let Some(NumericUpDownMessage::Value(value)) =
+            message.data::<NumericUpDownMessage<u32>>()
+        {
+            if message.destination() == self.start_index {
+                self.settings.start_index = *value as usize;
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.rename {
+                if let Selection::Graph(selection) = &editor_scene.selection {
+                    let graph = &engine.scenes[editor_scene.scene].graph;
+                    if let Some(group) = self.settings.make_command(graph, selection.nodes()) {
+                        sender.do_scene_command(group);
+                    }
+                }
+                self.close(&engine.user_interface);
+            } else if message.destination() == self.cancel {
+                self.close(&engine.user_interface);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        command::Command,
+        message::MessageSender,
+        scene::{commands::SceneContext, EditorScene},
+        world::graph::rename::BatchRename,
+    };
+    use fyrox::{
+        core::pool::Handle,
+        scene::{base::BaseBuilder, node::Node, pivot::PivotBuilder, Scene},
+    };
+    use std::sync::mpsc::channel;
+
+    #[test]
+    fn test_batch_rename_in_one_undo_step() {
+        let mut scene = Scene::new();
+        let nodes = ["Oak", "Pine", "Birch"]
+            .iter()
+            .map(|name| {
+                PivotBuilder::new(BaseBuilder::new().with_name(*name)).build(&mut scene.graph)
+            })
+            .collect::<Vec<Handle<Node>>>();
+
+        let mut editor_scene = EditorScene::new_test(&mut scene);
+
+        let (sender, _receiver) = channel();
+        let mut context =
+            SceneContext::new_test(&mut editor_scene, &mut scene, MessageSender(sender));
+
+        let names = |context: &SceneContext| {
+            nodes
+                .iter()
+                .map(|n| context.scene.graph[*n].name_owned())
+                .collect::<Vec<_>>()
+        };
+
+        let rename = BatchRename {
+            pattern: "Tree_###".to_string(),
+            ..Default::default()
+        };
+        let mut group = rename.make_command(&context.scene.graph, &nodes).unwrap();
+        group.execute(&mut context);
+        assert_eq!(names(&context), ["Tree_001", "Tree_002", "Tree_003"]);
+
+        // Whole batch is reverted at once.
+        group.revert(&mut context);
+        assert_eq!(names(&context), ["Oak", "Pine", "Birch"]);
+
+        let rename = BatchRename {
+            find: "i".to_string(),
+            replace: "y".to_string(),
+            ..Default::default()
+        };
+        let mut group = rename.make_command(&context.scene.graph, &nodes).unwrap();
+        group.execute(&mut context);
+        assert_eq!(names(&context), ["Oak", "Pyne", "Byrch"]);
+    }
+}
@@ -3,7 +3,10 @@ use crate::{
     gui::make_image_button_with_tooltip,
     load_image,
     scene::{
-        commands::{graph::LinkNodesCommand, ChangeSelectionCommand, CommandGroup, SceneCommand},
+        commands::{
+            graph::{LinkNodesCommand, SetChildIndexCommand},
+            make_delete_selection_command, ChangeSelectionCommand, CommandGroup, SceneCommand,
+        },
         EditorScene, Selection,
     },
     send_sync_message,
@@ -19,18 +22,19 @@ use fyrox::{
     core::{
         color::Color,
         log::Log,
+        math::Rect,
         pool::{ErasedHandle, Handle},
         scope_profile,
     },
     engine::Engine,
     gui::{
-        border::BorderBuilder,
+        border::{BorderBuilder, BorderMessage},
         brush::Brush,
         button::{ButtonBuilder, ButtonMessage},
         check_box::{CheckBoxBuilder, CheckBoxMessage},
         decorator::{Decorator, DecoratorBuilder, DecoratorMessage},
         grid::{Column, GridBuilder, Row},
-        message::{MessageDirection, UiMessage},
+        message::{KeyCode, MessageDirection, UiMessage},
         scroll_viewer::{ScrollViewerBuilder, ScrollViewerMessage},
         searchbar::{SearchBarBuilder, SearchBarMessage},
         stack_panel::StackPanelBuilder,
@@ -56,7 +60,7 @@ pub struct WorldViewer {
     pub window: Handle<UiNode>,
     tree_root: Handle<UiNode>,
     sender: MessageSender,
-    track_selection: Handle<UiNode>,
+    focus_follows_selection: Handle<UiNode>,
     search_bar: Handle<UiNode>,
     filter: String,
     stack: Vec<(Handle<UiNode>, Handle<Node>)>,
@@ -73,6 +77,7 @@ pub struct WorldViewer {
     item_context_menu: ItemContextMenu,
     node_to_view_map: HashMap<Handle<Node>, Handle<UiNode>>,
     small_font: SharedFont,
+    drop_indicator: Option<(Handle<UiNode>, DropPosition)>,
 }
 
 fn make_graph_node_item(
@@ -124,6 +129,216 @@ fn tree_node(ui: &UserInterface, tree: Handle<UiNode>) -> Handle<Node> {
         .entity_handle
 }
 
+/// Collects every item of the tree, that is not hidden inside a collapsed parent, in order of appearance.
+fn collect_visible_items(
+    items: &[Handle<UiNode>],
+    ui: &UserInterface,
+    out: &mut Vec<Handle<UiNode>>,
+) {
+    for &item in items {
+        if let Some(scene_item) = ui.node(item).cast::<SceneItem>() {
+            out.push(item);
+            if scene_item.tree.is_expanded {
+                collect_visible_items(&scene_item.tree.items, ui, out);
+            }
+        }
+    }
+}
+
+/// An action of the world viewer in response to a key press.
+#[derive(Debug, PartialEq, Eq)]
+enum KeyAction {
+    Select(Handle<Node>),
+    Expand(Handle<UiNode>, bool),
+    Delete,
+}
+
+/// Maps a key press to an action in the tree: up/down moves selection, left collapses an item (or moves
+/// selection to its parent), right expands an item (or moves selection to its first child), delete removes
+/// selection.
+fn key_action(
+    key: KeyCode,
+    selection: &Selection,
+    tree_root: Handle<UiNode>,
+    ui: &UserInterface,
+) -> Option<KeyAction> {
+    let current = match selection {
+        Selection::Graph(selection) => selection.nodes().last().cloned(),
+        _ => None,
+    }
+    .and_then(|node| map_selection(&[node], tree_root, ui).first().cloned());
+
+    let mut visible = Vec::new();
+    collect_visible_items(
+        &ui.node(tree_root).cast::<TreeRoot>()?.items,
+        ui,
+        &mut visible,
+    );
+    let position = current.and_then(|current| visible.iter().position(|i| *i == current));
+
+    let select = |item: Handle<UiNode>| Some(KeyAction::Select(tree_node(ui, item)));
+
+    match key {
+        KeyCode::Up => match position {
+            Some(position) => select(*visible.get(position.checked_sub(1)?)?),
+            None => select(*visible.last()?),
+        },
+        KeyCode::Down => match position {
+            Some(position) => select(*visible.get(position + 1)?),
+            None => select(*visible.first()?),
+        },
+        KeyCode::Left => {
+            let current = current?;
+            let item = ui.node(current).cast::<SceneItem>()?;
+            if item.tree.is_expanded && !item.tree.items.is_empty() {
+                Some(KeyAction::Expand(current, false))
+            } else {
+                let parent = ui.node(current).find_by_criteria_up(ui, |n| {
+                    n.cast::<SceneItem>().is_some() || n.cast::<TreeRoot>().is_some()
+                });
+                if ui.node(parent).cast::<SceneItem>().is_some() {
+                    select(parent)
+                } else {
+                    None
+                }
+            }
+        }
+        KeyCode::Right => {
+            let current = current?;
+            let item = ui.node(current).cast::<SceneItem>()?;
+            if !item.tree.is_expanded {
+                Some(KeyAction::Expand(current, true))
+            } else {
+                select(*item.tree.items.first()?)
+            }
+        }
+        KeyCode::Delete if !selection.is_empty() => Some(KeyAction::Delete),
+        _ => None,
+    }
+}
+
+/// Defines where dropped nodes will be placed relative to an item of the tree.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DropPosition {
+    /// Dropped nodes will be placed right before the target node in the list of children of its parent.
+    Before,
+    /// Dropped nodes will become children of the target node.
+    Into,
+    /// Dropped nodes will be placed right after the target node in the list of children of its parent.
+    After,
+}
+
+impl DropPosition {
+    /// Picks a drop position by vertical position of the cursor over the header of an item: top and
+    /// bottom quarters of the header mean "before" and "after", the rest means "into".
+    pub fn from_cursor(header_bounds: Rect<f32>, cursor_y: f32) -> Self {
+        let t = (cursor_y - header_bounds.y()) / header_bounds.h().max(f32::EPSILON);
+        if t < 0.25 {
+            Self::Before
+        } else if t > 0.75 {
+            Self::After
+        } else {
+            Self::Into
+        }
+    }
+
+    fn indicator_thickness(self) -> Thickness {
+        match self {
+            Self::Before => Thickness {
+                top: 2.0,
+                ..Thickness::zero()
+            },
+            Self::Into => Thickness::uniform(1.0),
+            Self::After => Thickness {
+                bottom: 2.0,
+                ..Thickness::zero()
+            },
+        }
+    }
+}
+
+/// Creates a command that moves given nodes to a new place in the hierarchy, defined by the target node
+/// and drop position. Moved nodes keep their global position and rotation. Nodes that cannot be moved,
+/// because it would create a cycle in the hierarchy, are skipped. Returns `None` if there is nothing to do.
+pub fn make_drop_command(
+    graph: &Graph,
+    nodes: &[Handle<Node>],
+    target: Handle<Node>,
+    position: DropPosition,
+) -> Option<CommandGroup> {
+    let target_parent = graph[target].parent();
+    let (new_parent, position) = if position == DropPosition::Into || target_parent.is_none() {
+        (target, DropPosition::Into)
+    } else {
+        (target_parent, position)
+    };
+
+    let mut moved = Vec::new();
+    for &node_handle in nodes {
+        if node_handle == target || moved.contains(&node_handle) {
+            continue;
+        }
+
+        // Make sure we won't create any loops - child must not have parent in its descendants.
+        if graph.can_link_nodes(node_handle, new_parent) {
+            moved.push(node_handle);
+        } else {
+            Log::warn(format!(
+                "Unable to attach {} node to {} node, because the latter is \
+                a descendant of the former!",
+                graph[node_handle].name(),
+                graph[new_parent].name()
+            ));
+        }
+    }
+
+    let mut commands = Vec::new();
+    let mut children = graph[new_parent].children().to_vec();
+    for &node_handle in moved.iter() {
+        if graph[node_handle].parent() != new_parent {
+            commands.push(SceneCommand::new(LinkNodesCommand::new(
+                node_handle,
+                new_parent,
+            )));
+            // Linking puts a node at the end of the list of children.
+            children.push(node_handle);
+        }
+    }
+
+    if position != DropPosition::Into {
+        let mut desired = children
+            .iter()
+            .filter(|h| !moved.contains(*h))
+            .cloned()
+            .collect::<Vec<_>>();
+        let target_index = desired.iter().position(|h| *h == target)?;
+        let insertion_index = match position {
+            DropPosition::After => target_index + 1,
+            _ => target_index,
+        };
+        desired.splice(insertion_index..insertion_index, moved.iter().cloned());
+
+        // Reorder children one by one, each move is a separate command, so the whole sequence can be
+        // reverted in the backward order.
+        for (index, &child) in desired.iter().enumerate() {
+            if children[index] != child {
+                let current = children.iter().position(|h| *h == child)?;
+                children.remove(current);
+                children.insert(index, child);
+                commands.push(SceneCommand::new(SetChildIndexCommand::new(
+                    new_parent, child, index,
+                )));
+            }
+        }
+    }
+
+    if commands.is_empty() {
+        None
+    } else {
+        Some(CommandGroup::from(commands))
+    }
+}
+
 fn colorize(handle: Handle<UiNode>, ui: &UserInterface, index: &mut usize) {
     let node = ui.node(handle);
 
@@ -167,7 +382,7 @@ impl WorldViewer {
         let expand_all;
         let locate_selection;
         let scroll_view;
-        let track_selection;
+        let focus_follows_selection;
         let search_bar = SearchBarBuilder::new(
             WidgetBuilder::new()
                 .on_row(1)
@@ -223,7 +438,7 @@ impl WorldViewer {
                                         locate_selection
                                     })
                                     .with_child({
-                                        track_selection = CheckBoxBuilder::new(
+                                        focus_follows_selection = CheckBoxBuilder::new(
                                             WidgetBuilder::new()
                                                 .with_vertical_alignment(VerticalAlignment::Center)
                                                 .with_margin(Thickness::uniform(1.0)),
@@ -233,12 +448,12 @@ impl WorldViewer {
                                                 .with_vertical_text_alignment(
                                                     VerticalAlignment::Center,
                                                 )
-                                                .with_text("Track Selection")
+                                                .with_text("Focus Follows Selection")
                                                 .build(ctx),
                                         )
-                                        .checked(Some(settings.selection.track_selection))
+                                        .checked(Some(settings.selection.focus_follows_selection))
                                         .build(ctx);
-                                        track_selection
+                                        focus_follows_selection
                                     }),
                             )
                             .with_orientation(Orientation::Horizontal)
@@ -279,7 +494,7 @@ impl WorldViewer {
 
         Self {
             search_bar,
-            track_selection,
+            focus_follows_selection,
             window,
             sender,
             tree_root,
@@ -293,6 +508,7 @@ impl WorldViewer {
             scroll_view,
             item_context_menu,
             node_to_view_map: Default::default(),
+            drop_indicator: None,
             filter: Default::default(),
             small_font,
         }
@@ -422,6 +638,28 @@ impl WorldViewer {
                         }
                     }
                     Ordering::Equal => {
+                        // Children could be reordered, in this case reorder views too.
+                        let in_order = items
+                            .iter()
+                            .map(|&view| tree_node(ui, view))
+                            .eq(node.children().iter().cloned());
+                        if !in_order {
+                            for child in node.children() {
+                                if let Some(&view) =
+                                    items.iter().find(|&&view| tree_node(ui, view) == *child)
+                                {
+                                    send_sync_message(
+                                        ui,
+                                        TreeMessage::add_item(
+                                            tree_handle,
+                                            MessageDirection::ToWidget,
+                                            view,
+                                        ),
+                                    );
+                                }
+                            }
+                        }
+
                         for &tree in items.iter() {
                             let child = tree_node(ui, tree);
                             self.stack.push((tree, child));
@@ -505,6 +743,17 @@ impl WorldViewer {
                         );
                     }
 
+                    if item.notes() != node.notes() {
+                        send_sync_message(
+                            ui,
+                            SceneItemMessage::notes(
+                                handle,
+                                MessageDirection::ToWidget,
+                                node.notes().to_owned(),
+                            ),
+                        );
+                    }
+
                     stack.extend_from_slice(&item.tree.items);
                 }
             } else if let Some(root) = ui_node.cast::<TreeRoot>() {
@@ -571,10 +820,25 @@ impl WorldViewer {
             if message.destination() == self.tree_root
                 && message.direction() == MessageDirection::FromWidget
             {
-                self.handle_selection(selection, editor_scene, engine);
+                self.handle_selection(selection, editor_scene, engine, settings);
             }
         } else if let Some(&WidgetMessage::Drop(node)) = message.data::<WidgetMessage>() {
+            self.set_drop_indicator(&engine.user_interface, None);
             self.handle_drop(engine, editor_scene, message.destination(), node);
+        } else if let Some(&WidgetMessage::DragOver(node)) = message.data::<WidgetMessage>() {
+            self.handle_drag_over(&engine.user_interface, message.destination(), node);
+        } else if let Some(WidgetMessage::MouseUp { .. }) = message.data::<WidgetMessage>() {
+            // Drag could end outside of the tree, so the indicator must be removed anyway.
+            self.set_drop_indicator(&engine.user_interface, None);
+        } else if let Some(&WidgetMessage::KeyDown(key)) = message.data::<WidgetMessage>() {
+            if !message.handled()
+                && (message.destination() == self.tree_root
+                    || engine
+                        .user_interface
+                        .is_node_child_of(message.destination(), self.tree_root))
+            {
+                self.handle_key_down(key, message, editor_scene, engine);
+            }
         } else if let Some(ButtonMessage::Click) = message.data::<ButtonMessage>() {
             if let Some(&view) = self.breadcrumbs.get(&message.destination()) {
                 if let Some(graph_node) = engine
@@ -606,15 +870,15 @@ impl WorldViewer {
                         MessageDirection::ToWidget,
                     ));
             } else if message.destination() == self.locate_selection {
-                self.locate_selection(&editor_scene.selection, engine)
+                self.locate_selection(&editor_scene.selection, &engine.user_interface)
             }
         } else if let Some(CheckBoxMessage::Check(Some(value))) = message.data::<CheckBoxMessage>()
         {
-            if message.destination() == self.track_selection {
-                settings.selection.track_selection = *value;
+            if message.destination() == self.focus_follows_selection {
+                settings.selection.focus_follows_selection = *value;
                 Log::verify(settings.save());
                 if *value {
-                    self.locate_selection(&editor_scene.selection, engine);
+                    self.locate_selection(&editor_scene.selection, &engine.user_interface);
                 }
             }
         } else if let Some(SearchBarMessage::Text(text)) = message.data() {
@@ -630,30 +894,31 @@ impl WorldViewer {
     pub fn try_locate_object(&self, type_id: TypeId, handle: ErasedHandle, engine: &Engine) {
         if type_id == TypeId::of::<Node>() {
             let selection = Selection::Graph(GraphSelection::single_or_empty(handle.into()));
-            self.locate_selection(&selection, engine)
+            self.locate_selection(&selection, &engine.user_interface)
         } else {
             // TODO: Add more types here.
         }
     }
 
-    fn locate_selection(&self, selection: &Selection, engine: &Engine) {
-        let tree_to_focus = self.map_selection(selection, engine);
+    /// Expands every selected item (and its ancestors) and scrolls the first one into view.
+    fn locate_selection(&self, selection: &Selection, ui: &UserInterface) {
+        let trees_to_focus = self.map_selection(selection, ui);
 
-        if let Some(tree_to_focus) = tree_to_focus.first() {
-            engine.user_interface.send_message(TreeMessage::expand(
-                *tree_to_focus,
+        for tree in trees_to_focus.iter() {
+            ui.send_message(TreeMessage::expand(
+                *tree,
                 MessageDirection::ToWidget,
                 true,
                 TreeExpansionStrategy::RecursiveAncestors,
             ));
+        }
 
-            engine
-                .user_interface
-                .send_message(ScrollViewerMessage::bring_into_view(
-                    self.scroll_view,
-                    MessageDirection::ToWidget,
-                    *tree_to_focus,
-                ));
+        if let Some(tree_to_focus) = trees_to_focus.first() {
+            ui.send_message(ScrollViewerMessage::bring_into_view(
+                self.scroll_view,
+                MessageDirection::ToWidget,
+                *tree_to_focus,
+            ));
         }
     }
 
@@ -662,6 +927,7 @@ impl WorldViewer {
         selection: &[Handle<UiNode>],
         editor_scene: &EditorScene,
         engine: &Engine,
+        settings: &Settings,
     ) {
         let mut new_selection = Selection::None;
         for selected_item in selection {
@@ -685,6 +951,14 @@ impl WorldViewer {
         }
 
         if new_selection != editor_scene.selection {
+            if settings.selection.focus_follows_selection {
+                if let Selection::Graph(ref graph_selection) = new_selection {
+                    if let Some(first) = graph_selection.nodes().first() {
+                        self.sender.send(Message::FocusObject(*first));
+                    }
+                }
+            }
+
             self.sender.do_scene_command(ChangeSelectionCommand::new(
                 new_selection,
                 editor_scene.selection.clone(),
@@ -692,6 +966,46 @@ impl WorldViewer {
         }
     }
 
+    fn handle_key_down(
+        &self,
+        key: KeyCode,
+        message: &UiMessage,
+        editor_scene: &EditorScene,
+        engine: &Engine,
+    ) {
+        let ui = &engine.user_interface;
+        let action = match key_action(key, &editor_scene.selection, self.tree_root, ui) {
+            Some(action) => action,
+            None => return,
+        };
+
+        match action {
+            KeyAction::Select(node) => {
+                self.sender.do_scene_command(ChangeSelectionCommand::new(
+                    Selection::Graph(GraphSelection::single_or_empty(node)),
+                    editor_scene.selection.clone(),
+                ));
+            }
+            KeyAction::Expand(item, expand) => {
+                ui.send_message(TreeMessage::expand(
+                    item,
+                    MessageDirection::ToWidget,
+                    expand,
+                    TreeExpansionStrategy::Direct,
+                ));
+            }
+            KeyAction::Delete => {
+                self.sender
+                    .send(Message::DoSceneCommand(make_delete_selection_command(
+                        editor_scene,
+                        engine,
+                    )));
+            }
+        }
+
+        message.set_handled(true);
+    }
+
     /// `target` - is a node at which `dropped` was dropped.
     /// `dropped` - is a node which was dropped at `target`.
     fn handle_drop(
@@ -713,32 +1027,18 @@ impl WorldViewer {
             ) {
                 if let Selection::Graph(ref selection) = editor_scene.selection {
                     if selection.nodes.contains(&child.entity_handle) {
-                        let mut commands = Vec::new();
-
-                        for &node_handle in selection.nodes.iter() {
-                            // Make sure we won't create any loops - child must not have parent in its
-                            // descendants.
-                            let mut attach = true;
-                            let graph = &engine.scenes[editor_scene.scene].graph;
-                            let mut p = parent.entity_handle;
-                            while p.is_some() {
-                                if p == node_handle {
-                                    attach = false;
-                                    break;
-                                }
-                                p = graph[p].parent();
-                            }
-
-                            if attach {
-                                commands.push(SceneCommand::new(LinkNodesCommand::new(
-                                    node_handle,
-                                    parent.entity_handle,
-                                )));
-                            }
-                        }
-
-                        if !commands.is_empty() {
-                            self.sender.do_scene_command(CommandGroup::from(commands));
+                        let graph = &engine.scenes[editor_scene.scene].graph;
+                        let position = DropPosition::from_cursor(
+                            ui.node(parent.tree.background).screen_bounds(),
+                            ui.cursor_position().y,
+                        );
+                        if let Some(command) = make_drop_command(
+                            graph,
+                            &selection.nodes,
+                            parent.entity_handle,
+                            position,
+                        ) {
+                            self.sender.do_scene_command(command);
                         }
                     }
                 }
@@ -746,11 +1046,82 @@ impl WorldViewer {
         }
     }
 
-    fn map_selection(&self, selection: &Selection, engine: &Engine) -> Vec<Handle<UiNode>> {
-        match selection {
-            Selection::Graph(selection) => {
-                map_selection(selection.nodes(), self.tree_root, &engine.user_interface)
+    /// Highlights the header of an item under the cursor while dragging a scene item over the tree,
+    /// the highlight shows where dragged nodes will be placed after drop.
+    fn handle_drag_over(
+        &mut self,
+        ui: &UserInterface,
+        destination: Handle<UiNode>,
+        dragged: Handle<UiNode>,
+    ) {
+        let mut indicator = None;
+
+        if ui
+            .try_get_node(dragged)
+            .and_then(|n| n.cast::<SceneItem>())
+            .is_some()
+            && ui.is_node_child_of(dragged, self.tree_root)
+            && (destination == self.tree_root || ui.is_node_child_of(destination, self.tree_root))
+        {
+            let item = ui
+                .node(destination)
+                .find_by_criteria_up(ui, |n| n.cast::<SceneItem>().is_some());
+            if let Some(scene_item) = ui.try_get_node(item).and_then(|n| n.cast::<SceneItem>()) {
+                if item != dragged {
+                    let background = scene_item.tree.background;
+                    let position = DropPosition::from_cursor(
+                        ui.node(background).screen_bounds(),
+                        ui.cursor_position().y,
+                    );
+                    indicator = Some((background, position));
+                }
             }
+        }
+
+        self.set_drop_indicator(ui, indicator);
+    }
+
+    fn set_drop_indicator(
+        &mut self,
+        ui: &UserInterface,
+        indicator: Option<(Handle<UiNode>, DropPosition)>,
+    ) {
+        if self.drop_indicator == indicator {
+            return;
+        }
+
+        if let Some((background, _)) = self.drop_indicator.take() {
+            ui.send_message(WidgetMessage::foreground(
+                background,
+                MessageDirection::ToWidget,
+                Brush::Solid(Color::TRANSPARENT),
+            ));
+            ui.send_message(BorderMessage::stroke_thickness(
+                background,
+                MessageDirection::ToWidget,
+                Thickness::uniform(1.0),
+            ));
+        }
+
+        if let Some((background, position)) = indicator {
+            ui.send_message(WidgetMessage::foreground(
+                background,
+                MessageDirection::ToWidget,
+                BRUSH_BRIGHT_BLUE,
+            ));
+            ui.send_message(BorderMessage::stroke_thickness(
+                background,
+                MessageDirection::ToWidget,
+                position.indicator_thickness(),
+            ));
+        }
+
+        self.drop_indicator = indicator;
+    }
+
+    fn map_selection(&self, selection: &Selection, ui: &UserInterface) -> Vec<Handle<UiNode>> {
+        match selection {
+            Selection::Graph(selection) => map_selection(selection.nodes(), self.tree_root, ui),
             _ => Default::default(),
         }
     }
@@ -763,7 +1134,7 @@ impl WorldViewer {
     ) {
         // Hack. See `self.sync_selection` for details.
         if self.sync_selection {
-            let trees = self.map_selection(&editor_scene.selection, engine);
+            let trees = self.map_selection(&editor_scene.selection, &engine.user_interface);
 
             let ui = &mut engine.user_interface;
             send_sync_message(
@@ -772,8 +1143,8 @@ impl WorldViewer {
             );
 
             self.update_breadcrumbs(ui, editor_scene, &engine.scenes[editor_scene.scene]);
-            if settings.selection.track_selection {
-                self.locate_selection(&editor_scene.selection, engine);
+            if settings.selection.focus_follows_selection {
+                self.locate_selection(&editor_scene.selection, &engine.user_interface);
             }
 
             self.sync_selection = false;
@@ -792,9 +1163,9 @@ impl WorldViewer {
 
     pub fn on_configure(&self, ui: &UserInterface, settings: &Settings) {
         ui.send_message(CheckBoxMessage::checked(
-            self.track_selection,
+            self.focus_follows_selection,
             MessageDirection::ToWidget,
-            Some(settings.selection.track_selection),
+            Some(settings.selection.focus_follows_selection),
         ));
     }
 
@@ -853,3 +1224,271 @@ fn map_selection(
         })
         .collect()
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        command::CommandStack,
+        message::MessageSender,
+        scene::{commands::SceneContext, EditorScene, Selection},
+        settings::Settings,
+        world::{
+            graph::{
+                item::{SceneItem, SceneItemBuilder},
+                selection::GraphSelection,
+            },
+            key_action, make_drop_command, DropPosition, KeyAction, WorldViewer,
+        },
+    };
+    use fyrox::{
+        core::{
+            algebra::{Vector2, Vector3},
+            math::Rect,
+            pool::Handle,
+        },
+        gui::{
+            message::{KeyCode, MessageDirection},
+            tree::{TreeBuilder, TreeExpansionStrategy, TreeMessage, TreeRootMessage},
+            widget::WidgetBuilder,
+            BuildContext, UiNode, UserInterface,
+        },
+        scene::{
+            base::BaseBuilder, graph::Graph, node::Node, pivot::PivotBuilder,
+            transform::TransformBuilder, Scene,
+        },
+    };
+    use std::sync::mpsc::channel;
+
+    fn make_item(
+        index: u32,
+        items: Vec<Handle<UiNode>>,
+        ctx: &mut BuildContext,
+        sender: &MessageSender,
+    ) -> Handle<UiNode> {
+        SceneItemBuilder::new(
+            TreeBuilder::new(WidgetBuilder::new())
+                .with_items(items)
+                .with_expanded(false),
+        )
+        .with_entity_handle(Handle::<Node>::new(index, 1))
+        .build(ctx, sender.clone())
+    }
+
+    fn is_expanded(ui: &UserInterface, item: Handle<UiNode>) -> bool {
+        ui.node(item).cast::<SceneItem>().unwrap().tree.is_expanded
+    }
+
+    #[test]
+    fn test_selection_is_revealed_in_world_viewer() {
+        let mut ui = UserInterface::new(Vector2::new(200.0, 200.0));
+        let (sender, _receiver) = channel();
+        let sender = MessageSender(sender);
+        let world_viewer =
+            WorldViewer::new(&mut ui.build_ctx(), sender.clone(), &Settings::default());
+
+        // root
+        //   child
+        //     grandchild
+        //   other_child
+        let ctx = &mut ui.build_ctx();
+        let grandchild = make_item(3, vec![], ctx, &sender);
+        let child = make_item(2, vec![grandchild], ctx, &sender);
+        let other_child = make_item(4, vec![], ctx, &sender);
+        let root = make_item(1, vec![child, other_child], ctx, &sender);
+        ui.send_message(TreeRootMessage::items(
+            world_viewer.tree_root,
+            MessageDirection::ToWidget,
+            vec![root],
+        ));
+        while ui.poll_message().is_some() {}
+
+        assert!(!is_expanded(&ui, root));
+        assert!(!is_expanded(&ui, child));
+
+        // Multi-selection made in the scene viewer must reveal every selected node.
+        let selection = Selection::Graph(GraphSelection::from_list(vec![
+            Handle::new(3, 1),
+            Handle::new(4, 1),
+        ]));
+        world_viewer.locate_selection(&selection, &ui);
+        while ui.poll_message().is_some() {}
+
+        assert!(is_expanded(&ui, root));
+        assert!(is_expanded(&ui, child));
+    }
+
+    #[test]
+    fn test_keyboard_navigation() {
+        let mut ui = UserInterface::new(Vector2::new(200.0, 200.0));
+        let (sender, _receiver) = channel();
+        let sender = MessageSender(sender);
+        let world_viewer =
+            WorldViewer::new(&mut ui.build_ctx(), sender.clone(), &Settings::default());
+
+        let ctx = &mut ui.build_ctx();
+        let grandchild = make_item(3, vec![], ctx, &sender);
+        let child = make_item(2, vec![grandchild], ctx, &sender);
+        let other_child = make_item(4, vec![], ctx, &sender);
+        let root = make_item(1, vec![child, other_child], ctx, &sender);
+        ui.send_message(TreeRootMessage::items(
+            world_viewer.tree_root,
+            MessageDirection::ToWidget,
+            vec![root],
+        ));
+        while ui.poll_message().is_some() {}
+
+        let node = |index| Handle::<Node>::new(index, 1);
+        let selected = |index| Selection::Graph(GraphSelection::single_or_empty(node(index)));
+        let tree_root = world_viewer.tree_root;
+
+        // Nothing is selected - selection starts from the first item.
+        assert_eq!(
+            key_action(KeyCode::Down, &Selection::None, tree_root, &ui),
+            Some(KeyAction::Select(node(1)))
+        );
+
+        // Children of a collapsed item are not reachable.
+        assert_eq!(
+            key_action(KeyCode::Down, &selected(1), tree_root, &ui),
+            None
+        );
+        assert_eq!(
+            key_action(KeyCode::Right, &selected(1), tree_root, &ui),
+            Some(KeyAction::Expand(root, true))
+        );
+        ui.send_message(TreeMessage::expand(
+            root,
+            MessageDirection::ToWidget,
+            true,
+            TreeExpansionStrategy::Direct,
+        ));
+        while ui.poll_message().is_some() {}
+
+        assert_eq!(
+            key_action(KeyCode::Down, &selected(1), tree_root, &ui),
+            Some(KeyAction::Select(node(2)))
+        );
+        assert_eq!(
+            key_action(KeyCode::Down, &selected(2), tree_root, &ui),
+            Some(KeyAction::Select(node(4)))
+        );
+        assert_eq!(
+            key_action(KeyCode::Up, &selected(4), tree_root, &ui),
+            Some(KeyAction::Select(node(2)))
+        );
+        assert_eq!(key_action(KeyCode::Up, &selected(1), tree_root, &ui), None);
+
+        // Right on an expanded item moves to its first child, left moves back to the parent.
+        assert_eq!(
+            key_action(KeyCode::Right, &selected(1), tree_root, &ui),
+            Some(KeyAction::Select(node(2)))
+        );
+        assert_eq!(
+            key_action(KeyCode::Left, &selected(4), tree_root, &ui),
+            Some(KeyAction::Select(node(1)))
+        );
+        assert_eq!(
+            key_action(KeyCode::Left, &selected(1), tree_root, &ui),
+            Some(KeyAction::Expand(root, false))
+        );
+
+        assert_eq!(
+            key_action(KeyCode::Delete, &selected(2), tree_root, &ui),
+            Some(KeyAction::Delete)
+        );
+        assert_eq!(
+            key_action(KeyCode::Delete, &Selection::None, tree_root, &ui),
+            None
+        );
+    }
+
+    #[test]
+    fn test_drop_position() {
+        let header = Rect::new(0.0, 100.0, 200.0, 20.0);
+        assert_eq!(
+            DropPosition::from_cursor(header, 102.0),
+            DropPosition::Before
+        );
+        assert_eq!(DropPosition::from_cursor(header, 110.0), DropPosition::Into);
+        assert_eq!(
+            DropPosition::from_cursor(header, 118.0),
+            DropPosition::After
+        );
+    }
+
+    #[test]
+    fn test_drag_drop_reparent_is_undoable() {
+        let mut scene = Scene::new();
+        let root = scene.graph.get_root();
+        let mut editor_scene = EditorScene::new_test(&mut scene);
+
+        fn make_pivot(position: Vector3<f32>, graph: &mut Graph) -> Handle<Node> {
+            PivotBuilder::new(
+                BaseBuilder::new().with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(position)
+                        .build(),
+                ),
+            )
+            .build(graph)
+        }
+
+        let parent = make_pivot(Vector3::new(1.0, 0.0, 0.0), &mut scene.graph);
+        let child = make_pivot(Vector3::new(0.0, 2.0, 0.0), &mut scene.graph);
+        let sibling = make_pivot(Vector3::new(0.0, 0.0, 3.0), &mut scene.graph);
+        scene.graph.update_hierarchical_data();
+
+        let (sender, _receiver) = channel();
+        let sender = MessageSender(sender);
+        let mut stack = CommandStack::new(false);
+
+        // Drop the child into the parent.
+        let command =
+            make_drop_command(&scene.graph, &[child], parent, DropPosition::Into).unwrap();
+        stack.do_command(
+            Box::new(command),
+            SceneContext::new_test(&mut editor_scene, &mut scene, sender.clone()),
+        );
+        scene.graph.update_hierarchical_data();
+        assert_eq!(scene.graph[child].parent(), parent);
+        assert!(
+            scene.graph[child]
+                .global_position()
+                .metric_distance(&Vector3::new(0.0, 2.0, 0.0))
+                < 1.0e-4
+        );
+
+        // Parent cannot be dropped into its own child.
+        assert!(make_drop_command(&scene.graph, &[parent], child, DropPosition::Into).is_none());
+
+        stack.undo(SceneContext::new_test(
+            &mut editor_scene,
+            &mut scene,
+            sender.clone(),
+        ));
+        assert_eq!(scene.graph[child].parent(), root);
+        assert_eq!(
+            **scene.graph[child].local_transform().position(),
+            Vector3::new(0.0, 2.0, 0.0)
+        );
+
+        // Drop the sibling before the parent.
+        let command =
+            make_drop_command(&scene.graph, &[sibling], parent, DropPosition::Before).unwrap();
+        let old_order = scene.graph[root].children().to_vec();
+        stack.do_command(
+            Box::new(command),
+            SceneContext::new_test(&mut editor_scene, &mut scene, sender.clone()),
+        );
+        let children = scene.graph[root].children();
+        let position = |h| children.iter().position(|c| *c == h).unwrap();
+        assert_eq!(position(sibling) + 1, position(parent));
+
+        stack.undo(SceneContext::new_test(
+            &mut editor_scene,
+            &mut scene,
+            sender,
+        ));
+        assert_eq!(scene.graph[root].children(), old_order.as_slice());
+    }
+}
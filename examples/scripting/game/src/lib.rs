@@ -21,6 +21,7 @@ use fyrox::{
         Scene, SceneLoader,
     },
     script::{ScriptContext, ScriptTrait},
+    utils::{camera_shake::CameraShake, input::InputActions},
 };
 
 mod bot;
@@ -98,25 +99,37 @@ impl Plugin for GamePlugin {
     }
 }
 
-#[derive(Default, Debug, Clone)]
-pub struct InputController {
-    walk_forward: bool,
-    walk_backward: bool,
-    walk_left: bool,
-    walk_right: bool,
-    jump: bool,
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+enum Action {
+    WalkForward,
+    WalkBackward,
+    WalkLeft,
+    WalkRight,
+    Jump,
 }
 
 #[derive(Visit, Reflect, Debug, Clone)]
 struct Player {
     speed: f32,
+    #[reflect(angle)]
     yaw: f32,
+    #[reflect(angle)]
     pitch: f32,
     camera: Handle<Node>,
+    #[reflect(
+        description = "Max distance to an interactable object (a node with `Interactable` tag)."
+    )]
+    #[visit(optional)]
+    interaction_radius: f32,
 
     #[visit(skip)]
     #[reflect(hidden)]
-    controller: InputController,
+    controller: InputActions<Action>,
+
+    /// The nearest interactable object within reach.
+    #[visit(skip)]
+    #[reflect(read_only)]
+    target: Handle<Node>,
 }
 
 impl_component_provider!(Player);
@@ -128,7 +141,9 @@ impl Default for Player {
             yaw: 0.0,
             pitch: 0.0,
             camera: Default::default(),
+            interaction_radius: 2.0,
             controller: Default::default(),
+            target: Default::default(),
         }
     }
 }
@@ -140,6 +155,14 @@ impl TypeUuidProvider for Player {
 }
 
 impl ScriptTrait for Player {
+    fn on_init(&mut self, ctx: &mut ScriptContext) {
+        if let Some(body) = ctx.scene.graph[ctx.handle].cast_mut::<RigidBody>() {
+            // Orientation of the player is fully controlled by the mouse, so physics must not rotate
+            // the capsule - otherwise it will tip over on collisions.
+            body.set_rotation_locked(true, true, true);
+        }
+    }
+
     fn remap_handles(&mut self, old_new_mapping: &NodeHandleMap) {
         old_new_mapping.map(&mut self.camera);
     }
@@ -164,16 +187,16 @@ impl ScriptTrait for Player {
 
             let mut velocity = Vector3::default();
 
-            if self.controller.walk_right {
+            if self.controller.is_down(Action::WalkRight) {
                 velocity -= side_vector;
             }
-            if self.controller.walk_left {
+            if self.controller.is_down(Action::WalkLeft) {
                 velocity += side_vector;
             }
-            if self.controller.walk_forward {
+            if self.controller.is_down(Action::WalkForward) {
                 velocity += look_vector;
             }
-            if self.controller.walk_backward {
+            if self.controller.is_down(Action::WalkBackward) {
                 velocity -= look_vector;
             }
 
@@ -183,14 +206,32 @@ impl ScriptTrait for Player {
                 .map(|v| v.scale(speed))
                 .unwrap_or_default();
 
-            body.set_ang_vel(Default::default());
+            // Jump only once per key press, holding the key must not make the player bounce.
+            let vertical_velocity = if self.controller.just_pressed(Action::Jump) {
+                4.0
+            } else {
+                body.lin_vel().y
+            };
+
             body.set_lin_vel(Vector3::new(
                 velocity.x / ctx.dt,
-                body.lin_vel().y,
+                vertical_velocity,
                 velocity.z / ctx.dt,
             ));
         }
 
+        self.controller.update();
+
+        let position = ctx.scene.graph[ctx.handle].global_position();
+        let interaction_radius = self.interaction_radius;
+        self.target = ctx
+            .scene
+            .graph
+            .nearest_node(position, &mut |_, node| node.tag() == "Interactable")
+            .filter(|(_, distance)| *distance <= interaction_radius)
+            .map(|(handle, _)| handle)
+            .unwrap_or_default();
+
         if let Some(camera) = ctx.scene.graph.try_get_mut(self.camera) {
             camera
                 .local_transform_mut()
@@ -217,24 +258,16 @@ impl ScriptTrait for Player {
             Event::WindowEvent { event, .. } => {
                 if let WindowEvent::KeyboardInput { input, .. } = event {
                     if let Some(key_code) = input.virtual_keycode {
-                        match key_code {
-                            VirtualKeyCode::W => {
-                                self.controller.walk_forward = input.state == ElementState::Pressed
-                            }
-                            VirtualKeyCode::S => {
-                                self.controller.walk_backward = input.state == ElementState::Pressed
-                            }
-                            VirtualKeyCode::A => {
-                                self.controller.walk_left = input.state == ElementState::Pressed
-                            }
-                            VirtualKeyCode::D => {
-                                self.controller.walk_right = input.state == ElementState::Pressed
-                            }
-                            VirtualKeyCode::Space => {
-                                self.controller.jump = input.state == ElementState::Pressed
-                            }
-                            _ => (),
-                        }
+                        let action = match key_code {
+                            VirtualKeyCode::W => Action::WalkForward,
+                            VirtualKeyCode::S => Action::WalkBackward,
+                            VirtualKeyCode::A => Action::WalkLeft,
+                            VirtualKeyCode::D => Action::WalkRight,
+                            VirtualKeyCode::Space => Action::Jump,
+                            _ => return,
+                        };
+                        self.controller
+                            .set_down(action, input.state == ElementState::Pressed);
                     }
                 }
             }
@@ -251,6 +284,19 @@ impl ScriptTrait for Player {
 struct Jumper {
     timer: f32,
     period: f32,
+    #[reflect(description = "A camera to shake when the jumper lands.")]
+    #[visit(optional)]
+    camera: Handle<Node>,
+    #[visit(optional)]
+    shake_intensity: f32,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    prev_vertical_velocity: f32,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    shake: CameraShake,
 }
 
 impl_component_provider!(Jumper);
@@ -260,6 +306,10 @@ impl Default for Jumper {
         Self {
             timer: 0.0,
             period: 0.5,
+            camera: Default::default(),
+            shake_intensity: 0.1,
+            prev_vertical_velocity: 0.0,
+            shake: Default::default(),
         }
     }
 }
@@ -273,8 +323,19 @@ impl TypeUuidProvider for Jumper {
 impl ScriptTrait for Jumper {
     fn on_init(&mut self, _context: &mut ScriptContext) {}
 
+    fn remap_handles(&mut self, old_new_mapping: &NodeHandleMap) {
+        old_new_mapping.map(&mut self.camera);
+    }
+
     fn on_update(&mut self, ctx: &mut ScriptContext) {
         if let Some(rigid_body) = ctx.scene.graph[ctx.handle].cast_mut::<RigidBody>() {
+            // Falling body that suddenly stopped has just landed.
+            let vertical_velocity = rigid_body.lin_vel().y;
+            if self.prev_vertical_velocity < -1.0 && vertical_velocity > -0.1 {
+                self.shake.shake(self.shake_intensity, 0.3);
+            }
+            self.prev_vertical_velocity = vertical_velocity;
+
             if self.timer > self.period {
                 rigid_body.apply_force(Vector3::new(0.0, 200.0, 0.0));
                 self.timer = 0.0;
@@ -282,6 +343,8 @@ impl ScriptTrait for Jumper {
 
             self.timer += ctx.dt;
         }
+
+        self.shake.update(self.camera, &mut ctx.scene.graph, ctx.dt);
     }
 
     fn id(&self) -> Uuid {
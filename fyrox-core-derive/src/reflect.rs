@@ -115,6 +115,8 @@ fn quote_field_prop(
 
     let immutable_collection = field.immutable_collection;
 
+    let is_angle = field.angle;
+
     let description = field.description.clone().unwrap_or_default();
 
     quote! {
@@ -131,6 +133,7 @@ fn quote_field_prop(
             reflect_value: #field_getter,
             step: #step,
             precision: #precision,
+            is_angle: #is_angle,
             description: #description,
             type_name: std::any::type_name::<#ty>()
         }
@@ -205,6 +205,13 @@ pub struct FieldArgs {
     #[darling(default)]
     pub precision: Option<usize>,
 
+    /// `#[reflect(angle)]`
+    ///
+    /// The field is an angle in radians, editors may show it in other units (degrees, for example).
+    /// Works only for numeric fields!
+    #[darling(default)]
+    pub angle: bool,
+
     /// `#[reflect(description = "This is a property description.")]`
     ///
     /// Description of the property.
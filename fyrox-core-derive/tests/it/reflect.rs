@@ -323,6 +323,7 @@ fn default_prop() -> FieldInfo<'static> {
         max_value: None,
         step: None,
         precision: None,
+        is_angle: false,
         description: "",
         type_name: "",
         doc: "",
@@ -383,6 +384,8 @@ fn inspect_attributes() {
             description = "This is a property description."
         )]
         y: f32,
+        #[reflect(angle)]
+        z: f32,
     }
 
     let data = Data::default();
@@ -408,13 +411,23 @@ fn inspect_attributes() {
             max_value: Some(1.1),
             step: Some(0.1),
             precision: Some(3),
+            is_angle: false,
             description: "This is a property description.",
             type_name: std::any::type_name::<f32>(),
             doc: "",
         },
+        FieldInfo {
+            owner_type_id: TypeId::of::<Data>(),
+            name: "z",
+            display_name: "Z",
+            value: &data.z,
+            type_name: std::any::type_name::<f32>(),
+            is_angle: true,
+            ..default_prop()
+        },
     ];
 
-    data.fields_info(&mut |fields_info| assert_eq!(fields_info[0..2], expected));
+    data.fields_info(&mut |fields_info| assert_eq!(fields_info[0..3], expected));
 }
 
 #[test]
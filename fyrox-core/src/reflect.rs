@@ -90,6 +90,10 @@ pub struct FieldInfo<'a> {
 
     /// Maximum amount of decimal places for a numeric property.
     pub precision: Option<usize>,
+
+    /// A property is an angle in radians. Editors may show it in other units. Works only with
+    /// numeric properties!
+    pub is_angle: bool,
 }
 
 impl<'a> FieldInfo<'a> {
@@ -118,6 +122,7 @@ impl<'a> fmt::Debug for FieldInfo<'a> {
             .field("max_value", &self.max_value)
             .field("step", &self.step)
             .field("precision", &self.precision)
+            .field("is_angle", &self.is_angle)
             .field("description", &self.description)
             .finish()
     }
@@ -137,6 +142,7 @@ impl<'a> PartialEq<Self> for FieldInfo<'a> {
             && self.max_value == other.max_value
             && self.step == other.step
             && self.precision == other.precision
+            && self.is_angle == other.is_angle
             && self.description == other.description
     }
 }
@@ -46,6 +46,7 @@ use std::{
 };
 use uuid::Uuid;
 
+#[derive(Clone)]
 pub enum FieldKind {
     Bool(bool),
     U8(u8),
@@ -244,6 +245,24 @@ impl<'a, T: Pod> Visit for PodVecView<'a, T> {
 }
 
 impl FieldKind {
+    fn is_compatible_with(&self, other: &FieldKind) -> bool {
+        match (self, other) {
+            (
+                FieldKind::PodArray {
+                    type_id: a_type_id,
+                    element_size: a_element_size,
+                    ..
+                },
+                FieldKind::PodArray {
+                    type_id: b_type_id,
+                    element_size: b_element_size,
+                    ..
+                },
+            ) => a_type_id == b_type_id && a_element_size == b_element_size,
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+
     fn as_string(&self) -> String {
         match self {
             Self::Bool(data) => format!("<bool = {}>, ", data),
@@ -1186,6 +1205,113 @@ impl Visitor {
         }
     }
 
+    /// Patches the data of the visitor with the data from the `source` visitor. A field is taken from
+    /// the source only if there is a field with the same name and the same type in the visitor, regions
+    /// with the same names are merged recursively, everything else that is missing in the visitor is
+    /// copied from the source as-is. It could be used to restore the state of an object whose layout was
+    /// changed: write the default state of the new object in a visitor, merge the old state in it and
+    /// then read the object back. Fields that changed their types will keep their new (default) values.
+    pub fn merge_compatible(&mut self, source: &Visitor) {
+        let (dest_root, source_root) = (self.root, source.root);
+        self.merge_node(dest_root, source, source_root);
+    }
+
+    fn merge_node(
+        &mut self,
+        dest: Handle<VisitorNode>,
+        source: &Visitor,
+        source_node: Handle<VisitorNode>,
+    ) {
+        let source_node = source.nodes.borrow(source_node);
+
+        let dest_node = self.nodes.borrow_mut(dest);
+        for source_field in source_node.fields.iter() {
+            if let Some(dest_field) = dest_node
+                .fields
+                .iter_mut()
+                .find(|f| f.name == source_field.name)
+            {
+                if dest_field.kind.is_compatible_with(&source_field.kind) {
+                    dest_field.kind = source_field.kind.clone();
+                }
+            } else {
+                dest_node
+                    .fields
+                    .push(Field::new(&source_field.name, source_field.kind.clone()));
+            }
+        }
+
+        for &source_child in source_node.children.iter() {
+            let source_child_name = &source.nodes.borrow(source_child).name;
+
+            let dest_child = self
+                .nodes
+                .borrow(dest)
+                .children
+                .iter()
+                .cloned()
+                .find(|c| &self.nodes.borrow(*c).name == source_child_name);
+
+            let dest_child = if let Some(dest_child) = dest_child {
+                dest_child
+            } else {
+                let dest_child = self.nodes.spawn(VisitorNode::new(source_child_name, dest));
+                self.nodes.borrow_mut(dest).children.push(dest_child);
+                dest_child
+            };
+
+            self.merge_node(dest_child, source, source_child);
+        }
+    }
+
+    /// Copies a child region with the given name of the current region (with all its content) into a new
+    /// standalone visitor. The region will be a child of the root region of the new visitor. It could be
+    /// used to keep the data that cannot be read right now (for example, because its type is unknown) as an
+    /// opaque blob, see [`Self::insert_regions`] for the opposite operation.
+    pub fn extract_region(&self, name: &str) -> Result<Visitor, VisitError> {
+        let source_region = self
+            .nodes
+            .borrow(self.current_node)
+            .children
+            .iter()
+            .cloned()
+            .find(|c| self.nodes.borrow(*c).name == name)
+            .ok_or_else(|| VisitError::RegionDoesNotExist(name.to_owned()))?;
+
+        let mut visitor = Visitor::new();
+        let root = visitor.root;
+        let region = visitor.nodes.spawn(VisitorNode::new(name, root));
+        visitor.nodes.borrow_mut(root).children.push(region);
+        visitor.merge_node(region, self, source_region);
+
+        Ok(visitor)
+    }
+
+    /// Copies every child region of the root region of the `source` visitor (with all their content) into
+    /// the current region. Fails if the current region already has a child region with the same name.
+    pub fn insert_regions(&mut self, source: &Visitor) -> VisitResult {
+        for &source_child in source.nodes.borrow(source.root).children.iter() {
+            let name = &source.nodes.borrow(source_child).name;
+
+            if self
+                .nodes
+                .borrow(self.current_node)
+                .children
+                .iter()
+                .any(|c| &self.nodes.borrow(*c).name == name)
+            {
+                return Err(VisitError::RegionAlreadyExists(name.clone()));
+            }
+
+            let current = self.current_node;
+            let region = self.nodes.spawn(VisitorNode::new(name, current));
+            self.nodes.borrow_mut(current).children.push(region);
+            self.merge_node(region, source, source_child);
+        }
+
+        Ok(())
+    }
+
     pub fn current_region(&self) -> Option<&str> {
         self.nodes
             .try_borrow(self.current_node)
@@ -1945,4 +2071,95 @@ mod test {
             objects.visit("Objects", &mut visitor).unwrap();
         }
     }
+
+    #[test]
+    fn test_merge_compatible() {
+        #[derive(Visit, Default)]
+        struct Old {
+            speed: f32,
+            yaw: f32,
+            items: Vec<u32>,
+        }
+
+        #[derive(Visit)]
+        struct New {
+            speed: f32,
+            yaw: String,
+            items: Vec<u32>,
+            pitch: f32,
+        }
+
+        let mut old = Old {
+            speed: 1.5,
+            yaw: 2.0,
+            items: vec![1, 2, 3],
+        };
+        let mut old_visitor = Visitor::new();
+        old.visit("Data", &mut old_visitor).unwrap();
+
+        let mut new = New {
+            speed: 0.0,
+            yaw: "yaw".to_string(),
+            items: vec![],
+            pitch: 3.0,
+        };
+        let mut new_visitor = Visitor::new();
+        new.visit("Data", &mut new_visitor).unwrap();
+        new_visitor.merge_compatible(&old_visitor);
+
+        let mut new_visitor =
+            Visitor::load_from_memory(new_visitor.save_binary_to_vec().unwrap()).unwrap();
+        new.visit("Data", &mut new_visitor).unwrap();
+
+        assert_eq!(new.speed, 1.5);
+        assert_eq!(new.yaw, "yaw");
+        assert_eq!(new.items, vec![1, 2, 3]);
+        assert_eq!(new.pitch, 3.0);
+    }
+
+    #[test]
+    fn test_extract_and_insert_region() {
+        #[derive(Visit, Default, PartialEq, Debug)]
+        struct Unknown {
+            health: f32,
+            items: Vec<u32>,
+        }
+
+        let mut unknown = Unknown {
+            health: 10.0,
+            items: vec![1, 2],
+        };
+        let mut visitor = Visitor::new();
+        {
+            let mut region = visitor.enter_region("Node").unwrap();
+            unknown.visit("Unknown", &mut region).unwrap();
+        }
+
+        // Keep the region as an opaque blob.
+        let mut visitor = Visitor::load_from_memory(visitor.save_binary_to_vec().unwrap()).unwrap();
+        let blob = {
+            let region = visitor.enter_region("Node").unwrap();
+            assert!(region.extract_region("Missing").is_err());
+            region
+                .extract_region("Unknown")
+                .unwrap()
+                .save_binary_to_vec()
+                .unwrap()
+        };
+
+        // Write the blob back into another visitor.
+        let mut visitor = Visitor::new();
+        {
+            let mut region = visitor.enter_region("Node").unwrap();
+            let blob = Visitor::load_from_memory(blob).unwrap();
+            region.insert_regions(&blob).unwrap();
+            assert!(region.insert_regions(&blob).is_err());
+        }
+
+        let mut visitor = Visitor::load_from_memory(visitor.save_binary_to_vec().unwrap()).unwrap();
+        let mut region = visitor.enter_region("Node").unwrap();
+        let mut loaded = Unknown::default();
+        loaded.visit("Unknown", &mut region).unwrap();
+        assert_eq!(loaded, unknown);
+    }
 }
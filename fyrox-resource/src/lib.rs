@@ -5,13 +5,14 @@
 
 use crate::{
     core::{
+        log::Log,
         parking_lot::MutexGuard,
         reflect::prelude::*,
         uuid::{uuid, Uuid},
         visitor::prelude::*,
         TypeUuidProvider,
     },
-    manager::ResourceManager,
+    manager::{make_portable_path, ResourceManager},
     state::ResourceState,
     untyped::UntypedResource,
 };
@@ -220,7 +221,37 @@ where
     fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
         let mut region = visitor.enter_region(name)?;
 
-        self.state.visit("State", &mut region)?;
+        if region.is_reading() {
+            self.state.visit("State", &mut region)?;
+        } else {
+            // Store the path in a portable form, so the reference stays valid if the project is moved
+            // to some other place.
+            let project_root =
+                region
+                    .blackboard
+                    .get::<ResourceManager>()
+                    .and_then(|resource_manager| {
+                        resource_manager
+                            .state()
+                            .project_root()
+                            .map(|p| p.to_path_buf())
+                    });
+            let mut portable_reference = self.state.as_ref().and_then(|state| {
+                let path = state.path();
+                let portable_path = make_portable_path(&path, project_root.as_deref());
+                (portable_path != path)
+                    .then(|| UntypedResource::new_pending(portable_path, state.type_uuid()))
+            });
+
+            if portable_reference.is_some() {
+                // The state is shared with other resource handles (and possibly other threads), so it
+                // must not be modified. Write a separate reference to the file instead, the actual data
+                // is loaded by its path anyway.
+                portable_reference.visit("State", &mut region)?;
+            } else {
+                self.state.visit("State", &mut region)?;
+            }
+        }
 
         if region.is_reading() {
             // Try to restore the shallow handle.
@@ -230,12 +261,14 @@ where
                 .expect("Resource manager must be available when deserializing resources!");
 
             let path = self.state.as_ref().unwrap().path();
+            let resolved_path = resource_manager.state().resolve_path(&path);
 
             // Procedural resources usually have path empty or use it as an id, in this case we need to
             // check if the file actually exists to not mess up procedural resources.
-            if path.exists() {
+            if resolved_path.exists() {
                 self.state = Some(
-                    resource_manager.request_untyped(path, <T as TypeUuidProvider>::type_uuid()),
+                    resource_manager
+                        .request_untyped(resolved_path, <T as TypeUuidProvider>::type_uuid()),
                 );
             } else {
                 // There might be a built-in resource, in this case we must restore the "reference" to it.
@@ -244,6 +277,21 @@ where
                     if built_in_resource.type_uuid() == self.state.as_ref().unwrap().type_uuid() {
                         self.state = Some(built_in_resource.clone());
                     }
+                } else if !path.as_os_str().is_empty() && state.is_loadable(&path) {
+                    // The path points to a file, that could be loaded, but the file does not exist - the
+                    // reference will never be resolved, so it is better to tell about it instead of
+                    // waiting forever.
+                    let message = format!(
+                        "Unable to load resource {}: the file {} is missing!",
+                        path.display(),
+                        resolved_path.display()
+                    );
+                    Log::err(&message);
+                    self.state = Some(UntypedResource::new_load_error(
+                        path.clone(),
+                        Some(Arc::new(message)),
+                        <T as TypeUuidProvider>::type_uuid(),
+                    ));
                 }
             }
         }
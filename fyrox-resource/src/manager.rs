@@ -4,10 +4,10 @@ use crate::{
     constructor::ResourceConstructorContainer,
     entry::{TimedEntry, DEFAULT_RESOURCE_LIFETIME},
     event::{ResourceEvent, ResourceEventBroadcaster},
-    loader::ResourceLoadersContainer,
+    loader::{ResourceLoader, ResourceLoadersContainer},
     state::ResourceState,
     task::TaskPool,
-    Resource, ResourceData, UntypedResource,
+    Resource, ResourceData, ResourceLoadError, UntypedResource,
 };
 use fxhash::FxHashMap;
 use fyrox_core::{
@@ -15,6 +15,7 @@ use fyrox_core::{
     log::Log,
     make_relative_path, notify,
     parking_lot::{Mutex, MutexGuard},
+    replace_slashes,
     uuid::Uuid,
     watcher::FileSystemWatcher,
     TypeUuidProvider, VecExtensions,
@@ -23,6 +24,7 @@ use std::path::PathBuf;
 use std::{
     ffi::OsStr,
     fmt::{Debug, Display, Formatter},
+    future::Future,
     marker::PhantomData,
     path::Path,
     sync::Arc,
@@ -49,6 +51,30 @@ impl ResourceWaitContext {
     }
 }
 
+/// Result of preloading of a single resource. See [`ResourceManager::preload`] for more info.
+pub struct PreloadResult {
+    /// A path of the resource.
+    pub path: PathBuf,
+    /// The resource, if it was loaded successfully, or an error otherwise.
+    pub result: Result<UntypedResource, Option<Arc<dyn ResourceLoadError>>>,
+}
+
+impl Debug for PreloadResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.result {
+            Ok(_) => write!(f, "{:?}: Ok", self.path),
+            Err(ref err) => write!(f, "{:?}: {:?}", self.path, err),
+        }
+    }
+}
+
+impl PreloadResult {
+    /// Returns `true` if the resource was loaded successfully.
+    pub fn is_ok(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
 /// See module docs.
 pub struct ResourceManagerState {
     /// A set of resource loaders. Use this field to register your own resource loader.
@@ -62,6 +88,7 @@ pub struct ResourceManagerState {
     resources: Vec<TimedEntry<UntypedResource>>,
     task_pool: Arc<TaskPool>,
     watcher: Option<FileSystemWatcher>,
+    project_root: Option<PathBuf>,
 }
 
 /// See module docs.
@@ -199,6 +226,56 @@ impl ResourceManager {
         }
     }
 
+    /// Requests every resource from the given list and returns a future, that resolves when every resource
+    /// is either loaded or failed to load. The future yields a result for each resource (in the same order
+    /// as the paths), so a loading screen can report which resources have failed. The method can be used
+    /// to make every resource of a scene resident before the scene is shown, this way there will be no
+    /// "pop-in" of textures, sounds, etc.
+    ///
+    /// Resources are requested without knowing their actual type, the type is resolved either when the
+    /// resource is loaded or when it is requested by [`Self::request`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use fyrox_resource::manager::ResourceManager;
+    ///
+    /// async fn load_level(resource_manager: ResourceManager) {
+    ///     for result in resource_manager
+    ///         .preload(&["data/level.rgs", "data/music.ogg"])
+    ///         .await
+    ///     {
+    ///         if !result.is_ok() {
+    ///             println!("Unable to preload {:?}", result.path);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn preload<P>(&self, paths: &[P]) -> impl Future<Output = Vec<PreloadResult>>
+    where
+        P: AsRef<Path>,
+    {
+        let resources = {
+            let mut state = self.state();
+            paths
+                .iter()
+                .map(|path| {
+                    (
+                        path.as_ref().to_path_buf(),
+                        state.request(path, Uuid::default()),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        join_all(resources.into_iter().map(|(path, resource)| async move {
+            PreloadResult {
+                path,
+                result: resource.await,
+            }
+        }))
+    }
+
     /// Reloads all loaded resources. Normally it should never be called, because it is **very** heavy
     /// method! This method is asynchronous, it uses all available CPU power to reload resources as
     /// fast as possible.
@@ -208,6 +285,29 @@ impl ResourceManager {
     }
 }
 
+/// Makes the given path of a resource relative to the given project root (or to the current working
+/// directory, if there's no root), so the path will stay valid if the whole project is moved to some
+/// other place. Relative paths and paths outside of the project are returned as is.
+pub fn make_portable_path(path: &Path, project_root: Option<&Path>) -> PathBuf {
+    if path.is_relative() {
+        return path.to_path_buf();
+    }
+
+    match project_root {
+        Some(root) => path
+            .strip_prefix(root)
+            .ok()
+            .map(replace_slashes)
+            .or_else(|| {
+                let root = root.canonicalize().ok()?;
+                let path = path.canonicalize().ok()?;
+                path.strip_prefix(root).ok().map(replace_slashes)
+            })
+            .unwrap_or_else(|| path.to_path_buf()),
+        None => make_relative_path(path).unwrap_or_else(|_| path.to_path_buf()),
+    }
+}
+
 impl ResourceManagerState {
     pub(crate) fn new() -> Self {
         Self {
@@ -218,6 +318,28 @@ impl ResourceManagerState {
             constructors_container: Default::default(),
             watcher: None,
             built_in_resources: Default::default(),
+            project_root: None,
+        }
+    }
+
+    /// Sets a directory, against which relative paths of resources will be resolved on deserialization,
+    /// and to which absolute paths of resources will be made relative on serialization. `None` means
+    /// the current working directory, which is the default.
+    pub fn set_project_root(&mut self, project_root: Option<PathBuf>) {
+        self.project_root = project_root;
+    }
+
+    /// Returns the directory, that is used to store paths of resources in a portable form. See
+    /// [`Self::set_project_root`] for more info.
+    pub fn project_root(&self) -> Option<&Path> {
+        self.project_root.as_deref()
+    }
+
+    /// Resolves a relative (portable) path of a resource against the project root.
+    pub fn resolve_path(&self, path: &Path) -> PathBuf {
+        match self.project_root.as_ref() {
+            Some(root) if path.is_relative() && !path.as_os_str().is_empty() => root.join(path),
+            _ => path.to_path_buf(),
         }
     }
 
@@ -385,7 +507,24 @@ impl ResourceManagerState {
         P: AsRef<Path>,
     {
         match self.find(path.as_ref()) {
-            Some(existing) => existing.clone(),
+            Some(existing) => {
+                // The resource could be requested without knowing its type (see `ResourceManager::preload`),
+                // in this case its type will be defined by the first typed request.
+                if let ResourceState::Pending {
+                    type_uuid: existing_type_uuid,
+                    ..
+                }
+                | ResourceState::LoadError {
+                    type_uuid: existing_type_uuid,
+                    ..
+                } = &mut *existing.0.lock()
+                {
+                    if existing_type_uuid.is_nil() {
+                        *existing_type_uuid = type_uuid;
+                    }
+                }
+                existing.clone()
+            }
             None => {
                 let resource = UntypedResource::new_pending(path.as_ref().to_owned(), type_uuid);
 
@@ -398,26 +537,31 @@ impl ResourceManagerState {
         }
     }
 
+    fn find_loader(&self, path: &Path) -> Option<&dyn ResourceLoader> {
+        let ext_lowercase = path.extension()?.to_ascii_lowercase();
+        self.loaders.iter().find(|loader| {
+            loader
+                .extensions()
+                .iter()
+                .any(|ext| OsStr::new(ext) == ext_lowercase.as_os_str())
+        })
+    }
+
+    /// Returns `true` if there's a loader, that is able to load a resource from the given path.
+    pub fn is_loadable(&self, path: &Path) -> bool {
+        self.find_loader(path).is_some()
+    }
+
     fn try_spawn_loading_task(&mut self, path: &Path, resource: UntypedResource, reload: bool) {
-        if let Some(loader) = path.extension() {
-            let ext_lowercase = loader.to_ascii_lowercase();
-            if let Some(loader) = self.loaders.iter().find(|loader| {
-                loader
-                    .extensions()
-                    .iter()
-                    .any(|ext| OsStr::new(ext) == ext_lowercase.as_os_str())
-            }) {
-                self.task_pool.spawn_task(loader.load(
-                    resource,
-                    self.event_broadcaster.clone(),
-                    reload,
-                ));
-
-                return;
-            }
+        if let Some(loader) = self.find_loader(path) {
+            self.task_pool.spawn_task(loader.load(
+                resource,
+                self.event_broadcaster.clone(),
+                reload,
+            ));
+        } else {
+            Log::err(format!("There's no loader registered for {:?}!", path));
         }
-
-        Log::err(format!("There's no loader registered for {:?}!", path));
     }
 
     /// Reloads a single resource.
@@ -470,3 +614,198 @@ impl ResourceManagerState {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            futures::executor::block_on, reflect::prelude::*, uuid::Uuid, visitor::prelude::*,
+            TypeUuidProvider,
+        },
+        event::ResourceEventBroadcaster,
+        loader::{BoxedLoaderFuture, ResourceLoader},
+        manager::ResourceManager,
+        state::ResourceState,
+        Resource, ResourceData, UntypedResource,
+    };
+    use std::{
+        any::Any,
+        borrow::Cow,
+        cell::Cell,
+        fs,
+        path::{Path, PathBuf},
+        sync::Arc,
+        time::Duration,
+    };
+
+    thread_local! {
+        static SET_PATH_CALLS: Cell<usize> = Cell::new(0);
+    }
+
+    #[derive(Debug, Default, Visit, Reflect)]
+    struct MyData {
+        path: PathBuf,
+    }
+
+    impl ResourceData for MyData {
+        fn path(&self) -> Cow<Path> {
+            Cow::Borrowed(&self.path)
+        }
+
+        fn set_path(&mut self, path: PathBuf) {
+            SET_PATH_CALLS.with(|calls| calls.set(calls.get() + 1));
+            self.path = path;
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+
+        fn type_uuid(&self) -> Uuid {
+            <Self as TypeUuidProvider>::type_uuid()
+        }
+    }
+
+    impl TypeUuidProvider for MyData {
+        fn type_uuid() -> Uuid {
+            Uuid::from_u128(0x5a1e_a5e5)
+        }
+    }
+
+    struct MyDataLoader;
+
+    impl ResourceLoader for MyDataLoader {
+        fn extensions(&self) -> &[&str] {
+            &["my_data", "broken"]
+        }
+
+        fn into_any(self: Box<Self>) -> Box<dyn Any> {
+            self
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+
+        fn load(
+            &self,
+            resource: UntypedResource,
+            _event_broadcaster: ResourceEventBroadcaster,
+            _reload: bool,
+        ) -> BoxedLoaderFuture {
+            Box::pin(async move {
+                // Simulate some work.
+                std::thread::sleep(Duration::from_millis(20));
+
+                let path = resource.path();
+                if path.extension().map_or(false, |ext| ext == "broken") {
+                    resource.commit_error(path, "Broken resource");
+                } else {
+                    resource.commit_ok(MyData { path });
+                }
+            })
+        }
+    }
+
+    #[test]
+    fn test_preload() {
+        let resource_manager = ResourceManager::new();
+        resource_manager.state().loaders.set(MyDataLoader);
+
+        let results = block_on(resource_manager.preload(&["a.my_data", "b.my_data", "c.broken"]));
+
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_ok());
+        assert!(results[1].is_ok());
+        assert!(!results[2].is_ok());
+        assert_eq!(results[2].path, Path::new("c.broken"));
+
+        // Successfully preloaded resources must be resident.
+        for result in &results[0..2] {
+            let resource = result.result.as_ref().unwrap();
+            assert!(matches!(*resource.0.lock(), ResourceState::Ok(_)));
+            assert_eq!(
+                resource.type_uuid(),
+                <MyData as TypeUuidProvider>::type_uuid()
+            );
+        }
+
+        // Typed request must return the preloaded instance.
+        let a = resource_manager.request::<MyData, _>("a.my_data");
+        assert!(a.is_ok());
+    }
+
+    fn make_resource_manager(project_root: &Path) -> ResourceManager {
+        let resource_manager = ResourceManager::new();
+        {
+            let mut state = resource_manager.state();
+            state.loaders.set(MyDataLoader);
+            state.constructors_container.add::<MyData>();
+            state.set_project_root(Some(project_root.to_path_buf()));
+        }
+        resource_manager
+    }
+
+    fn load(data: Vec<u8>, resource_manager: &ResourceManager) -> Resource<MyData> {
+        let mut visitor = Visitor::load_from_memory(data).unwrap();
+        visitor
+            .blackboard
+            .register(Arc::new(resource_manager.clone()));
+        let mut resource = Resource::<MyData>::default();
+        resource.visit("Resource", &mut visitor).unwrap();
+        resource
+    }
+
+    #[test]
+    fn test_resource_paths_are_portable() {
+        let temp_dir = std::env::temp_dir().join("fyrox_resource_portable_paths");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let old_project_dir = temp_dir.join("old");
+        let new_project_dir = temp_dir.join("new");
+        fs::create_dir_all(old_project_dir.join("data")).unwrap();
+        fs::write(old_project_dir.join("data/res.my_data"), []).unwrap();
+
+        // Save a resource, that was requested by its absolute path.
+        let resource_manager = make_resource_manager(&old_project_dir);
+        let mut resource =
+            resource_manager.request::<MyData, _>(old_project_dir.join("data/res.my_data"));
+        block_on(resource.clone()).unwrap();
+        let mut visitor = Visitor::new();
+        visitor
+            .blackboard
+            .register(Arc::new(resource_manager.clone()));
+        SET_PATH_CALLS.with(|calls| calls.set(0));
+        resource.visit("Resource", &mut visitor).unwrap();
+        let data = visitor.save_binary_to_vec().unwrap();
+
+        // The shared state must not be touched while saving, in-memory path must stay intact.
+        assert_eq!(SET_PATH_CALLS.with(|calls| calls.get()), 0);
+        assert_eq!(resource.path(), old_project_dir.join("data/res.my_data"));
+        assert!(resource.is_ok());
+
+        // Move the project.
+        fs::rename(&old_project_dir, &new_project_dir).unwrap();
+
+        let resource_manager = make_resource_manager(&new_project_dir);
+        let loaded = load(data.clone(), &resource_manager);
+        block_on(loaded.clone()).unwrap();
+        assert!(loaded.is_ok());
+        assert_eq!(loaded.path(), new_project_dir.join("data/res.my_data"));
+
+        // Missing file must be reported as an error instead of endless loading.
+        fs::remove_file(new_project_dir.join("data/res.my_data")).unwrap();
+        let resource_manager = make_resource_manager(&new_project_dir);
+        let loaded = load(data, &resource_manager);
+        assert!(loaded.is_failed_to_load());
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+}
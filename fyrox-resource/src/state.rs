@@ -286,6 +286,16 @@ impl ResourceState {
         }
     }
 
+    /// Changes a path to the resource source.
+    #[inline]
+    pub fn set_path(&mut self, new_path: PathBuf) {
+        match self {
+            Self::Pending { path, .. } => *path = new_path,
+            Self::LoadError { path, .. } => *path = new_path,
+            Self::Ok(details) => details.set_path(new_path),
+        }
+    }
+
     /// Changes ResourceState::Pending state to ResourceState::Ok(data) with given `data`.
     /// Additionally it wakes all futures.
     #[inline]
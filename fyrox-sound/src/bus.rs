@@ -503,8 +503,11 @@ impl AudioBusGraph {
 #[cfg(test)]
 mod test {
     use crate::{
+        buffer::{DataSource, SoundBufferResource, SoundBufferResourceExtension},
         bus::{AudioBus, AudioBusGraph},
+        context::SoundContext,
         effects::{Attenuate, Effect},
+        source::{SoundSourceBuilder, Status},
     };
 
     #[test]
@@ -586,4 +589,48 @@ mod test {
 
         assert_eq!(output_buffer[0], (0.75, 0.75));
     }
+
+    fn render_two_sources_routed_to_bus(bus_gain: f32) -> Vec<(f32, f32)> {
+        let context = SoundContext::new();
+        let mut state = context.state();
+
+        let mut sfx = AudioBus::new("Sfx".to_string());
+        sfx.set_gain(bus_gain);
+        let primary_bus = state.bus_graph_ref().primary_bus_handle();
+        state.bus_graph_mut().add_bus(sfx, primary_bus);
+
+        let buffer = SoundBufferResource::new_generic(DataSource::Raw {
+            sample_rate: 44100,
+            channel_count: 1,
+            samples: vec![0.25; 44100],
+        })
+        .unwrap();
+
+        for _ in 0..2 {
+            let source = SoundSourceBuilder::new()
+                .with_buffer(buffer.clone())
+                .with_bus("Sfx")
+                .with_status(Status::Playing)
+                .build()
+                .unwrap();
+            state.add_source(source);
+        }
+
+        let mut output_buffer = vec![(0.0f32, 0.0f32); SoundContext::SAMPLES_PER_CHANNEL];
+        state.render(&mut output_buffer);
+        output_buffer
+    }
+
+    #[test]
+    fn test_bus_gain_scales_routed_sources() {
+        let full = render_two_sources_routed_to_bus(1.0);
+        let half = render_two_sources_routed_to_bus(0.5);
+
+        assert!(full.iter().any(|(l, r)| *l != 0.0 && *r != 0.0));
+
+        for ((full_left, full_right), (half_left, half_right)) in full.iter().zip(half.iter()) {
+            assert!((full_left * 0.5 - half_left).abs() <= f32::EPSILON);
+            assert!((full_right * 0.5 - half_right).abs() <= f32::EPSILON);
+        }
+    }
 }
@@ -212,8 +212,9 @@ impl Reverb {
     }
 
     /// Sets desired duration of reverberation, the more size your environment has,
-    /// the larger duration of reverberation should be.
+    /// the larger duration of reverberation should be. Negative values will be clamped to zero.
     pub fn set_decay_time(&mut self, decay_time: f32) {
+        let decay_time = decay_time.max(0.0);
         self.decay_time = decay_time;
         self.left.set_decay_time(decay_time);
         self.right.set_decay_time(decay_time)
@@ -234,8 +235,9 @@ impl Reverb {
     ///
     /// This method uses normalized frequency as input, this means that you should divide your desired
     /// frequency in hertz by sample rate of sound context. Context has `normalize_frequency` method
-    /// exactly for this purpose.
+    /// exactly for this purpose. The value will be clamped to `[0.0; 1.0]` range.
     pub fn set_fc(&mut self, fc: f32) {
+        let fc = fc.clamp(0.0, 1.0);
         self.fc = fc;
         self.left.set_fc(fc);
         self.right.set_fc(fc);
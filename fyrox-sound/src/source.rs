@@ -32,11 +32,13 @@ use crate::{
     buffer::{streaming::StreamingBuffer, SoundBuffer, SoundBufferResource},
     bus::AudioBusGraph,
     context::DistanceModel,
+    dsp::filters::OnePole,
     error::SoundError,
     listener::Listener,
 };
 use fyrox_core::{
     algebra::Vector3,
+    math::lerpf,
     reflect::prelude::*,
     visitor::{Visit, VisitResult, Visitor},
 };
@@ -59,6 +61,24 @@ pub enum Status {
     Paused = 2,
 }
 
+/// Gain of a fully occluded sound source, see [`SoundSource::set_occlusion`].
+pub const OCCLUDED_GAIN: f32 = 0.35;
+
+/// Normalized cutoff frequency of the low-pass filter, that is applied to a fully occluded sound
+/// source (~880 Hz at 44100 Hz sampling rate), see [`SoundSource::set_occlusion`].
+pub const OCCLUDED_CUTOFF: f32 = 0.02;
+
+/// A region of a sound buffer that will be played over and over again when looping is enabled.
+/// It allows to make sounds with an "intro" part that will be played only once, and then the
+/// rest of the sound will loop in the given region.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Reflect, Visit)]
+pub struct LoopRegion {
+    /// Beginning of the region.
+    pub start: Duration,
+    /// End of the region. Must be greater than `start`, otherwise the region will be ignored.
+    pub end: Duration,
+}
+
 /// See module info.
 #[derive(Debug, Clone, Reflect, Visit)]
 pub struct SoundSource {
@@ -81,6 +101,8 @@ pub struct SoundSource {
     #[reflect(min_value = 0.0, step = 0.05)]
     gain: f32,
     looping: bool,
+    #[visit(optional)]
+    loop_region: Option<LoopRegion>,
     #[reflect(min_value = 0.0, max_value = 1.0, step = 0.05)]
     spatial_blend: f32,
     // Important coefficient for runtime resampling. It is used to modify playback speed
@@ -136,6 +158,13 @@ pub struct SoundSource {
     #[reflect(hidden)]
     #[visit(skip)]
     pub(crate) prev_distance_gain: Option<f32>,
+    #[reflect(hidden)]
+    #[visit(skip)]
+    occlusion: f32,
+    // Low-pass filters (for left and right channels) that muffle the sound when it is occluded.
+    #[reflect(hidden)]
+    #[visit(skip)]
+    occlusion_filters: (OnePole, OnePole),
 }
 
 impl Default for SoundSource {
@@ -150,6 +179,7 @@ impl Default for SoundSource {
             gain: 1.0,
             spatial_blend: 1.0,
             looping: false,
+            loop_region: None,
             resampling_multiplier: 1.0,
             status: Status::Stopped,
             bus: "Master".to_string(),
@@ -166,6 +196,8 @@ impl Default for SoundSource {
             prev_right_samples: Default::default(),
             prev_sampling_vector: Vector3::new(0.0, 0.0, 1.0),
             prev_distance_gain: None,
+            occlusion: 0.0,
+            occlusion_filters: Default::default(),
         }
     }
 }
@@ -279,6 +311,21 @@ impl SoundSource {
         self.gain
     }
 
+    /// Sets occlusion factor of the source in `[0; 1]` range. Occlusion defines how much the sound is
+    /// blocked by obstacles between the source and the listener: `0.0` - the path is clear, `1.0` - the
+    /// sound is fully blocked. Occluded sources are attenuated (down to [`OCCLUDED_GAIN`]) and muffled
+    /// by a low-pass filter (down to [`OCCLUDED_CUTOFF`]). The sound context does not detect obstacles
+    /// by itself, the factor is usually set by a game engine using ray casting.
+    pub fn set_occlusion(&mut self, occlusion: f32) -> &mut Self {
+        self.occlusion = occlusion.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Returns current occlusion factor of the source. See [`Self::set_occlusion`] for more info.
+    pub fn occlusion(&self) -> f32 {
+        self.occlusion
+    }
+
     /// Sets panning coefficient. Value must be in -1..+1 range. Where -1 - only left channel will be audible,
     /// 0 - both, +1 - only right.
     pub fn set_panning(&mut self, panning: f32) -> &mut Self {
@@ -320,6 +367,26 @@ impl SoundSource {
         self.looping
     }
 
+    /// Sets a region of the buffer that will be played in a loop. The region is used only if looping
+    /// is enabled (see [`Self::set_looping`]). Playback will jump back to `start` every time when it
+    /// reaches `end` (or the end of the buffer, if `end` lies beyond it). The part of the buffer before
+    /// `start` will be played only once, which is useful for music with an intro.
+    pub fn set_loop_region(&mut self, start: Duration, end: Duration) -> &mut Self {
+        self.loop_region = Some(LoopRegion { start, end });
+        self
+    }
+
+    /// Removes the loop region, looping sources will repeat the entire buffer.
+    pub fn clear_loop_region(&mut self) -> &mut Self {
+        self.loop_region = None;
+        self
+    }
+
+    /// Returns current loop region (if any).
+    pub fn loop_region(&self) -> Option<LoopRegion> {
+        self.loop_region
+    }
+
     /// Sets sound pitch. Defines "tone" of sounds. Default value is 1.0
     pub fn set_pitch(&mut self, pitch: f64) -> &mut Self {
         self.pitch = pitch.abs();
@@ -419,7 +486,7 @@ impl SoundSource {
             .position
             .metric_distance(&listener.position())
             .clamp(self.radius, self.max_distance);
-        match distance_model {
+        let distance_gain = match distance_model {
             DistanceModel::None => 1.0,
             DistanceModel::InverseDistance => {
                 self.radius / (self.radius + self.rolloff_factor * (distance - self.radius))
@@ -428,7 +495,9 @@ impl SoundSource {
                 1.0 - self.radius * (distance - self.radius) / (self.max_distance - self.radius)
             }
             DistanceModel::ExponentDistance => (distance / self.radius).powf(-self.rolloff_factor),
-        }
+        };
+        // Obstacles between the source and the listener make the sound quieter, as if it was farther.
+        distance_gain * lerpf(1.0, OCCLUDED_GAIN, self.occlusion)
     }
 
     pub(crate) fn calculate_panning(&self, listener: &Listener) -> f32 {
@@ -462,31 +531,54 @@ impl SoundSource {
 
     /// Sets playback duration.
     pub fn set_playback_time(&mut self, time: Duration) {
-        if let Some(buffer) = self.buffer.as_ref() {
+        if let Some(buffer) = self.buffer.clone() {
             let mut buffer = buffer.data_ref();
-            if let SoundBuffer::Streaming(ref mut streaming) = *buffer {
-                // Make sure decoder is at right position.
-                streaming.time_seek(time);
-            }
-            // Set absolute position first.
-            self.playback_pos = time.as_secs_f64() * buffer.sample_rate as f64;
-            // Then adjust buffer read position.
-            self.buf_read_pos = match *buffer {
-                SoundBuffer::Streaming(ref mut streaming) => {
-                    // Make sure to load correct data into buffer from decoder.
-                    streaming.read_next_block();
-                    // Streaming sources has different buffer read position because
-                    // buffer contains only small portion of data.
-                    self.playback_pos % (StreamingBuffer::STREAM_SAMPLE_COUNT as f64)
-                }
-                SoundBuffer::Generic(_) => self.playback_pos,
-            };
+            self.seek(&mut *buffer, time);
             assert!(
                 self.buf_read_pos * (buffer.channel_count() as f64) < buffer.samples().len() as f64
             );
         }
     }
 
+    fn seek(&mut self, buffer: &mut SoundBuffer, time: Duration) {
+        if let SoundBuffer::Streaming(ref mut streaming) = *buffer {
+            // Make sure decoder is at right position.
+            streaming.time_seek(time);
+        }
+        // Set absolute position first.
+        self.playback_pos = time.as_secs_f64() * buffer.sample_rate as f64;
+        // Then adjust buffer read position.
+        self.buf_read_pos = match *buffer {
+            SoundBuffer::Streaming(ref mut streaming) => {
+                // Make sure to load correct data into buffer from decoder.
+                streaming.read_next_block();
+                // Streaming sources has different buffer read position because
+                // buffer contains only small portion of data.
+                self.playback_pos % (StreamingBuffer::STREAM_SAMPLE_COUNT as f64)
+            }
+            SoundBuffer::Generic(_) => self.playback_pos,
+        };
+    }
+
+    // Returns loop region bounds in samples, if the region is active and valid for the buffer.
+    fn loop_region_bounds(&self, buffer: &SoundBuffer) -> Option<(f64, f64)> {
+        let region = self.loop_region.filter(|_| self.looping)?;
+        let sample_rate = buffer.sample_rate() as f64;
+        let start = region.start.as_secs_f64() * sample_rate;
+        let end = region.end.as_secs_f64() * sample_rate;
+        let is_valid = match buffer {
+            SoundBuffer::Generic(_) => {
+                start < (buffer.samples().len() / buffer.channel_count()) as f64
+            }
+            SoundBuffer::Streaming(_) => true,
+        };
+        if is_valid && start < end {
+            Some((start, end))
+        } else {
+            None
+        }
+    }
+
     pub(crate) fn render(&mut self, amount: usize) {
         if self.frame_samples.capacity() < amount {
             self.frame_samples = Vec::with_capacity(amount);
@@ -504,16 +596,63 @@ impl SoundSource {
         }
         // Fill the remaining part of frame_samples.
         self.frame_samples.resize(amount, (0.0, 0.0));
+
+        if self.occlusion > 0.0 {
+            // Nyquist frequency (0.5) means that the filter is effectively disabled.
+            let fc = lerpf(0.5, OCCLUDED_CUTOFF, self.occlusion);
+            let (left_filter, right_filter) = &mut self.occlusion_filters;
+            left_filter.set_fc(fc);
+            right_filter.set_fc(fc);
+            for (left, right) in self.frame_samples.iter_mut() {
+                *left = left_filter.feed(*left);
+                *right = right_filter.feed(*right);
+            }
+        }
     }
 
     fn render_playing(&mut self, buffer: &mut SoundBuffer, amount: usize) {
+        let step = self.pitch * self.resampling_multiplier;
+        let loop_region = self.loop_region_bounds(buffer);
         let mut count = 0;
         loop {
-            count += self.render_until_block_end(buffer, amount - count);
+            let mut limit = amount - count;
+            let mut stops_at_loop_end = false;
+            if let Some((_, end)) = loop_region {
+                if self.playback_pos < end {
+                    // Do not render past the end of the loop region.
+                    let until_end = ((end - self.playback_pos) / step).ceil().max(1.0) as usize;
+                    if until_end < limit {
+                        limit = until_end;
+                        stops_at_loop_end = true;
+                    }
+                }
+            }
+
+            let rendered = self.render_until_block_end(buffer, limit);
+            count += rendered;
+
+            if let Some((start, end)) = loop_region {
+                if self.playback_pos >= end {
+                    self.seek(
+                        buffer,
+                        Duration::from_secs_f64(start / buffer.sample_rate() as f64),
+                    );
+                    if count == amount {
+                        break;
+                    }
+                    continue;
+                }
+            }
+
             if count == amount {
                 break;
             }
 
+            if stops_at_loop_end && rendered == limit {
+                // Stopped right before the loop region end, not at the end of the block.
+                continue;
+            }
+
             let channel_count = buffer.channel_count();
             let len = buffer.samples().len();
             let mut end_reached = true;
@@ -532,8 +671,15 @@ impl SoundSource {
                     self.status = Status::Stopped;
                     return;
                 }
-                self.buf_read_pos = 0.0;
-                self.playback_pos = 0.0;
+                if let Some((start, _)) = loop_region {
+                    self.seek(
+                        buffer,
+                        Duration::from_secs_f64(start / buffer.sample_rate() as f64),
+                    );
+                } else {
+                    self.buf_read_pos = 0.0;
+                    self.playback_pos = 0.0;
+                }
             } else {
                 self.buf_read_pos -= len as f64 / channel_count as f64;
             }
@@ -706,6 +852,7 @@ pub struct SoundSourceBuilder {
     name: String,
     panning: f32,
     looping: bool,
+    loop_region: Option<LoopRegion>,
     status: Status,
     play_once: bool,
     playback_time: Duration,
@@ -733,6 +880,7 @@ impl SoundSourceBuilder {
             name: Default::default(),
             panning: 0.0,
             looping: false,
+            loop_region: None,
             status: Status::Stopped,
             play_once: false,
             playback_time: Default::default(),
@@ -787,6 +935,18 @@ impl SoundSourceBuilder {
         self
     }
 
+    /// See [`SoundSource::set_loop_region`]
+    pub fn with_loop_region(mut self, start: Duration, end: Duration) -> Self {
+        self.loop_region = Some(LoopRegion { start, end });
+        self
+    }
+
+    /// Sets desired loop region, `None` means that the entire buffer will loop.
+    pub fn with_opt_loop_region(mut self, loop_region: Option<LoopRegion>) -> Self {
+        self.loop_region = loop_region;
+        self
+    }
+
     /// Sets desired status of source.
     pub fn with_status(mut self, status: Status) -> Self {
         self.status = status;
@@ -851,6 +1011,7 @@ impl SoundSourceBuilder {
             panning: self.panning,
             status: self.status,
             looping: self.looping,
+            loop_region: self.loop_region,
             name: self.name,
             frame_samples: Default::default(),
             radius: self.radius,
@@ -870,3 +1031,107 @@ impl SoundSourceBuilder {
         Ok(source)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        buffer::{DataSource, SoundBufferResource, SoundBufferResourceExtension},
+        context::{DistanceModel, SAMPLE_RATE},
+        listener::Listener,
+        source::{SoundSource, SoundSourceBuilder, Status, OCCLUDED_GAIN},
+    };
+    use std::time::Duration;
+
+    // Creates a playing source with one second long buffer, where each sample is equal to its index.
+    fn make_source() -> SoundSource {
+        let buffer = SoundBufferResource::new_generic(DataSource::Raw {
+            sample_rate: SAMPLE_RATE as usize,
+            channel_count: 1,
+            samples: (0..SAMPLE_RATE).map(|i| i as f32).collect(),
+        })
+        .unwrap();
+
+        SoundSourceBuilder::new()
+            .with_buffer(buffer)
+            .with_status(Status::Playing)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_seek() {
+        let mut source = make_source();
+
+        source.set_playback_time(Duration::from_millis(500));
+        assert_eq!(source.playback_time(), Duration::from_millis(500));
+
+        source.render(3);
+        assert_eq!(
+            source.frame_samples(),
+            &[(22050.0, 22050.0), (22051.0, 22051.0), (22052.0, 22052.0)]
+        );
+    }
+
+    #[test]
+    fn test_loop_region() {
+        let mut source = make_source();
+        source
+            .set_looping(true)
+            .set_loop_region(Duration::from_millis(250), Duration::from_millis(500));
+        source.set_playback_time(Duration::from_millis(400));
+
+        // 4410 samples until the end of the region, then it must wrap to its beginning.
+        source.render(4412);
+        let samples = source.frame_samples();
+        assert_eq!(samples[0], (17640.0, 17640.0));
+        assert_eq!(samples[4409], (22049.0, 22049.0));
+        assert_eq!(samples[4410], (11025.0, 11025.0));
+        assert_eq!(samples[4411], (11026.0, 11026.0));
+    }
+
+    #[test]
+    fn test_occlusion() {
+        // Highest possible frequency - every sample flips its sign.
+        let make_source = || {
+            let buffer = SoundBufferResource::new_generic(DataSource::Raw {
+                sample_rate: SAMPLE_RATE as usize,
+                channel_count: 1,
+                samples: (0..SAMPLE_RATE)
+                    .map(|i| if i % 2 == 0 { 1.0 } else { -1.0 })
+                    .collect(),
+            })
+            .unwrap();
+
+            SoundSourceBuilder::new()
+                .with_buffer(buffer)
+                .with_status(Status::Playing)
+                .build()
+                .unwrap()
+        };
+        let peak = |source: &SoundSource| {
+            source
+                .frame_samples()
+                .iter()
+                .skip(100)
+                .fold(0.0f32, |peak, (left, _)| peak.max(left.abs()))
+        };
+
+        let listener = Listener::new();
+
+        let mut clear = make_source();
+        clear.render(200);
+        assert_eq!(peak(&clear), 1.0);
+
+        let mut occluded = make_source();
+        occluded.set_occlusion(1.0);
+        occluded.render(200);
+        // The low-pass filter cuts high frequencies off.
+        assert!(peak(&occluded) < 0.1);
+
+        // And the sound is quieter.
+        let clear_gain = clear.calculate_distance_gain(&listener, DistanceModel::InverseDistance);
+        let occluded_gain =
+            occluded.calculate_distance_gain(&listener, DistanceModel::InverseDistance);
+        assert!((occluded_gain - clear_gain * OCCLUDED_GAIN).abs() < 0.0001);
+    }
+}
@@ -15,8 +15,9 @@ use crate::{
     },
     message::{MessageDirection, UiMessage},
     stack_panel::StackPanelBuilder,
+    text::TextBuilder,
     widget::{Widget, WidgetBuilder, WidgetMessage},
-    BuildContext, Control, HorizontalAlignment, Thickness, UiNode, UserInterface,
+    BuildContext, Control, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
     VerticalAlignment,
 };
 use fyrox_core::reflect::Reflect;
@@ -31,7 +32,10 @@ use std::{
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Item {
     inspector: Handle<UiNode>,
+    header: Handle<UiNode>,
     remove: Handle<UiNode>,
+    /// A grip that could be dragged onto other item to move the item to its position.
+    drag: Handle<UiNode>,
 }
 
 pub trait CollectionItem: Clone + Reflect + Debug + Default + 'static {}
@@ -121,6 +125,24 @@ impl<T: CollectionItem> Control for CollectionEditor<T> {
                     index,
                 ));
             }
+        } else if let Some(WidgetMessage::Drop(dropped)) = message.data::<WidgetMessage>() {
+            if let Some(from) = self.items.iter().position(|i| i.drag == *dropped) {
+                if let Some(to) = ui
+                    .node(self.panel)
+                    .children()
+                    .iter()
+                    .position(|view| *view == message.destination())
+                {
+                    if from != to {
+                        ui.send_message(CollectionChanged::move_item(
+                            self.handle,
+                            MessageDirection::FromWidget,
+                            from,
+                            to,
+                        ));
+                    }
+                }
+            }
         } else if let Some(msg) = message.data::<CollectionEditorMessage>() {
             if message.destination == self.handle {
                 match msg {
@@ -189,14 +211,17 @@ fn create_item_views(
         .iter()
         .enumerate()
         .map(|(n, item)| {
-            make_expander_container(
+            let view = make_expander_container(
                 layer_index,
                 &format!("Item {}", n),
                 &format!("Item {} of the collection", n),
-                item.remove,
+                item.header,
                 item.inspector,
                 ctx,
-            )
+            );
+            // Allow dropping other items on this one to reorder them.
+            ctx[view].allow_drop = true;
+            view
         })
         .collect::<Vec<_>>()
 }
@@ -233,20 +258,46 @@ where
                 .with_context(inspector_context)
                 .build(ctx);
 
+            let drag = TextBuilder::new(
+                WidgetBuilder::new()
+                    .with_visibility(!immutable_collection)
+                    .with_allow_drag(true)
+                    .with_margin(Thickness::uniform(1.0))
+                    .with_vertical_alignment(VerticalAlignment::Center)
+                    .with_width(16.0)
+                    .with_height(16.0),
+            )
+            .with_text("::")
+            .with_horizontal_text_alignment(HorizontalAlignment::Center)
+            .with_vertical_text_alignment(VerticalAlignment::Center)
+            .build(ctx);
+
             let remove = ButtonBuilder::new(
                 WidgetBuilder::new()
                     .with_visibility(!immutable_collection)
                     .with_margin(Thickness::uniform(1.0))
                     .with_vertical_alignment(VerticalAlignment::Center)
-                    .with_horizontal_alignment(HorizontalAlignment::Right)
-                    .on_column(1)
                     .with_width(16.0)
                     .with_height(16.0),
             )
             .with_text("-")
             .build(ctx);
 
-            Item { inspector, remove }
+            let header = StackPanelBuilder::new(
+                WidgetBuilder::new()
+                    .with_horizontal_alignment(HorizontalAlignment::Right)
+                    .with_child(drag)
+                    .with_child(remove),
+            )
+            .with_orientation(Orientation::Horizontal)
+            .build(ctx);
+
+            Item {
+                inspector,
+                header,
+                remove,
+                drag,
+            }
         })
         .collect::<Vec<_>>()
 }
@@ -533,3 +584,128 @@ where
         None
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        button::ButtonMessage,
+        core::{algebra::Vector2, pool::Handle, reflect::prelude::*},
+        inspector::{
+            editors::{
+                collection::{CollectionEditor, VecCollectionPropertyEditorDefinition},
+                PropertyEditorDefinitionContainer,
+            },
+            Inspector, InspectorBuilder, InspectorContext, InspectorMessage, PropertyAction,
+            PropertyFilter,
+        },
+        message::MessageDirection,
+        widget::{WidgetBuilder, WidgetMessage},
+        UiNode, UserInterface,
+    };
+    use std::rc::Rc;
+
+    #[derive(Reflect, Debug, Default)]
+    struct Path {
+        waypoints: Vec<u32>,
+    }
+
+    fn sync(ui: &mut UserInterface, inspector: Handle<UiNode>, path: &Path) {
+        let context = ui
+            .node(inspector)
+            .cast::<Inspector>()
+            .unwrap()
+            .context()
+            .clone();
+        context
+            .sync(path, ui, 0, false, PropertyFilter::default())
+            .unwrap();
+        while ui.poll_message().is_some() {}
+    }
+
+    fn apply_changes(ui: &mut UserInterface, inspector: Handle<UiNode>, path: &mut Path) {
+        while let Some(message) = ui.poll_message() {
+            if message.destination() == inspector {
+                if let Some(InspectorMessage::PropertyChanged(args)) =
+                    message.data::<InspectorMessage>()
+                {
+                    assert_eq!(args.path(), "waypoints");
+                    PropertyAction::from_field_kind(&args.value).apply(
+                        &args.path(),
+                        path,
+                        &mut |result| assert!(result.is_ok()),
+                    );
+                }
+            }
+        }
+        sync(ui, inspector, path);
+    }
+
+    #[test]
+    fn test_add_remove_and_move_items() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+
+        let mut path = Path {
+            waypoints: vec![1, 2],
+        };
+
+        let container = PropertyEditorDefinitionContainer::new();
+        container.insert(VecCollectionPropertyEditorDefinition::<u32>::new());
+        let context = InspectorContext::from_object(
+            &path,
+            &mut ui.build_ctx(),
+            Rc::new(container),
+            None,
+            1,
+            0,
+            false,
+            PropertyFilter::default(),
+        );
+        let inspector = InspectorBuilder::new(WidgetBuilder::new())
+            .with_context(context)
+            .build(&mut ui.build_ctx());
+
+        while ui.poll_message().is_some() {}
+
+        let editor = ui
+            .node(inspector)
+            .cast::<Inspector>()
+            .unwrap()
+            .context
+            .entries
+            .iter()
+            .find(|e| e.property_name == "waypoints")
+            .unwrap()
+            .property_editor;
+        let collection = |ui: &UserInterface| {
+            ui.node(editor)
+                .cast::<CollectionEditor<u32>>()
+                .unwrap()
+                .clone()
+        };
+
+        // Add a new default item.
+        let add = collection(&ui).add;
+        ui.send_message(ButtonMessage::click(add, MessageDirection::FromWidget));
+        apply_changes(&mut ui, inspector, &mut path);
+        assert_eq!(path.waypoints, vec![1, 2, 0]);
+        assert_eq!(collection(&ui).items.len(), 3);
+
+        // Remove the first item.
+        let remove = collection(&ui).items[0].remove;
+        ui.send_message(ButtonMessage::click(remove, MessageDirection::FromWidget));
+        apply_changes(&mut ui, inspector, &mut path);
+        assert_eq!(path.waypoints, vec![2, 0]);
+        assert_eq!(collection(&ui).items.len(), 2);
+
+        // Drag the first item onto the second one.
+        let editor_ref = collection(&ui);
+        let target = ui.node(editor_ref.panel).children()[1];
+        ui.send_message(WidgetMessage::drop(
+            target,
+            MessageDirection::FromWidget,
+            editor_ref.items[0].drag,
+        ));
+        apply_changes(&mut ui, inspector, &mut path);
+        assert_eq!(path.waypoints, vec![0, 2]);
+    }
+}
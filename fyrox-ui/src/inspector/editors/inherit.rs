@@ -15,6 +15,7 @@ use crate::{
     },
     inspector::{FieldKind, InheritableAction},
     message::UiMessage,
+    numeric::NumericScrubMessage,
     utils::make_simple_tooltip,
     widget::WidgetBuilder,
     BuildContext, Control, MessageDirection, Thickness, UiNode, UserInterface, VerticalAlignment,
@@ -87,10 +88,23 @@ impl Control for InheritablePropertyEditor {
                     *modified,
                 ));
             }
+        } else if let Some(NumericScrubMessage::Begin { start_mouse_pos }) = message.data() {
+            if message.destination() == self.handle
+                && message.direction() == MessageDirection::ToWidget
+            {
+                ui.send_message(NumericScrubMessage::begin(
+                    self.inner_editor,
+                    MessageDirection::ToWidget,
+                    *start_mouse_pos,
+                ));
+            }
         }
 
-        // Re-cast messages from inner editor as message from this editor.
-        if message.destination() == self.inner_editor {
+        // Re-cast messages from inner editor as message from this editor. Scrubbing requests are
+        // sent to the inner editor by this editor, so they must not be re-casted back.
+        if message.destination() == self.inner_editor
+            && message.data::<NumericScrubMessage>().is_none()
+        {
             let mut clone = message.clone();
             clone.destination = self.handle;
             ui.send_message(clone);
@@ -206,6 +220,7 @@ where
         max_value: property_info.max_value,
         step: property_info.step,
         precision: property_info.precision,
+        is_angle: property_info.is_angle,
         description: property_info.description,
         type_name: property_info.type_name,
         doc: property_info.doc,
@@ -329,6 +344,7 @@ where
                 environment: ctx.environment.clone(),
                 name: ctx.name,
                 owner_type_id: ctx.owner_type_id,
+                is_angle: ctx.is_angle,
                 message: ctx.message,
                 definition_container: ctx.definition_container.clone(),
             });
@@ -18,7 +18,7 @@ use crate::{
             enumeration::{EnumPropertyEditorDefinition, InspectableEnum},
             inherit::InheritablePropertyEditorDefinition,
             inspectable::InspectablePropertyEditorDefinition,
-            numeric::NumericPropertyEditorDefinition,
+            numeric::{NumericDisplaySettings, NumericPropertyEditorDefinition},
             quat::QuatPropertyEditorDefinition,
             range::RangePropertyEditorDefinition,
             rect::RectPropertyEditorDefinition,
@@ -37,7 +37,7 @@ use crate::{
 use fxhash::FxHashMap;
 use std::{
     any::TypeId,
-    cell::{Ref, RefCell},
+    cell::{Cell, Ref, RefCell},
     fmt::Debug,
     ops::Range,
     rc::Rc,
@@ -89,6 +89,8 @@ pub struct PropertyEditorTranslationContext<'b, 'c> {
     pub environment: Option<Rc<dyn InspectorEnvironment>>,
     pub name: &'b str,
     pub owner_type_id: TypeId,
+    /// Whether the property is an angle or not. See [`FieldInfo::is_angle`].
+    pub is_angle: bool,
     pub message: &'c UiMessage,
     pub definition_container: Rc<PropertyEditorDefinitionContainer>,
 }
@@ -127,6 +129,8 @@ pub trait PropertyEditorDefinition: Debug {
 #[derive(Clone, Default)]
 pub struct PropertyEditorDefinitionContainer {
     definitions: RefCell<FxHashMap<TypeId, Rc<dyn PropertyEditorDefinition>>>,
+    numeric_display_settings: Cell<NumericDisplaySettings>,
+    pinned_properties: RefCell<FxHashMap<String, Vec<String>>>,
 }
 
 macro_rules! reg_array_property_editor {
@@ -302,4 +306,52 @@ impl PropertyEditorDefinitionContainer {
     pub fn definitions(&self) -> Ref<FxHashMap<TypeId, Rc<dyn PropertyEditorDefinition>>> {
         self.definitions.borrow()
     }
+
+    /// Sets new display settings of numeric properties. The settings will be applied to property
+    /// editors created after the call.
+    pub fn set_numeric_display_settings(&self, settings: NumericDisplaySettings) {
+        self.numeric_display_settings.set(settings);
+    }
+
+    /// Returns current display settings of numeric properties.
+    pub fn numeric_display_settings(&self) -> NumericDisplaySettings {
+        self.numeric_display_settings.get()
+    }
+
+    /// Sets new pinned properties - a map of type names of inspected objects to the names of their pinned
+    /// properties. The pinned properties will be shown at the top of inspectors created after the call.
+    pub fn set_pinned_properties(&self, pinned_properties: FxHashMap<String, Vec<String>>) {
+        *self.pinned_properties.borrow_mut() = pinned_properties;
+    }
+
+    /// Returns a map of type names of inspected objects to the names of their pinned properties.
+    pub fn pinned_properties(&self) -> Ref<FxHashMap<String, Vec<String>>> {
+        self.pinned_properties.borrow()
+    }
+
+    /// Returns `true` if a property with the given name of an object with the given type name is pinned.
+    pub fn is_property_pinned(&self, type_name: &str, property_name: &str) -> bool {
+        self.pinned_properties
+            .borrow()
+            .get(type_name)
+            .map_or(false, |names| {
+                names.iter().any(|name| name == property_name)
+            })
+    }
+
+    /// Pins or unpins a property with the given name of an object with the given type name.
+    pub fn set_property_pinned(&self, type_name: &str, property_name: &str, pinned: bool) {
+        let mut pinned_properties = self.pinned_properties.borrow_mut();
+        if pinned {
+            let names = pinned_properties.entry(type_name.to_owned()).or_default();
+            if !names.iter().any(|name| name == property_name) {
+                names.push(property_name.to_owned());
+            }
+        } else if let Some(names) = pinned_properties.get_mut(type_name) {
+            names.retain(|name| name != property_name);
+            if names.is_empty() {
+                pinned_properties.remove(type_name);
+            }
+        }
+    }
 }
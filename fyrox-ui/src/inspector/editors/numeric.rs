@@ -1,5 +1,5 @@
 use crate::{
-    core::num_traits::NumCast,
+    core::{num_traits::NumCast, reflect::prelude::*},
     inspector::{
         editors::{
             PropertyEditorBuildContext, PropertyEditorDefinition, PropertyEditorInstance,
@@ -12,7 +12,91 @@ use crate::{
     widget::WidgetBuilder,
     Thickness,
 };
+use serde::{Deserialize, Serialize};
 use std::{any::TypeId, marker::PhantomData};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Units in which angle properties (marked with `#[reflect(angle)]`) are shown in the inspector. Angles
+/// are always stored in radians, the conversion is done only for display.
+#[derive(
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    Debug,
+    Serialize,
+    Deserialize,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum AngleUnit {
+    Radians,
+    Degrees,
+}
+
+impl Default for AngleUnit {
+    fn default() -> Self {
+        Self::Degrees
+    }
+}
+
+impl AngleUnit {
+    /// Returns a short label of the unit, that could be shown next to a value.
+    pub fn label(self) -> &'static str {
+        match self {
+            AngleUnit::Radians => "rad",
+            AngleUnit::Degrees => "deg",
+        }
+    }
+}
+
+/// Defines how numeric properties are shown in the inspector.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct NumericDisplaySettings {
+    /// Units of angle properties.
+    pub angle_unit: AngleUnit,
+    /// Amount of decimal places of numeric properties, that do not specify their own precision.
+    pub precision: usize,
+}
+
+impl Default for NumericDisplaySettings {
+    fn default() -> Self {
+        Self {
+            angle_unit: Default::default(),
+            precision: 3,
+        }
+    }
+}
+
+/// Returns `true` if a property is shown in degrees.
+pub(crate) fn is_shown_in_degrees(is_angle: bool, settings: &NumericDisplaySettings) -> bool {
+    is_angle && settings.angle_unit == AngleUnit::Degrees
+}
+
+fn to_display<T: NumericType>(value: T, in_degrees: bool) -> T {
+    if in_degrees {
+        value
+            .to_f64()
+            .and_then(|v| NumCast::from(v.to_degrees()))
+            .unwrap_or(value)
+    } else {
+        value
+    }
+}
+
+fn from_display<T: NumericType>(value: T, in_degrees: bool) -> T {
+    if in_degrees {
+        value
+            .to_f64()
+            .and_then(|v| NumCast::from(v.to_radians()))
+            .unwrap_or(value)
+    } else {
+        value
+    }
+}
 
 #[derive(Debug)]
 pub struct NumericPropertyEditorDefinition<T>
@@ -46,6 +130,8 @@ where
         ctx: PropertyEditorBuildContext,
     ) -> Result<PropertyEditorInstance, InspectorError> {
         let value = ctx.property_info.cast_value::<T>()?;
+        let settings = ctx.definition_container.numeric_display_settings();
+        let in_degrees = is_shown_in_degrees(ctx.property_info.is_angle, &settings);
         Ok(PropertyEditorInstance::Simple {
             editor: NumericUpDownBuilder::new(
                 WidgetBuilder::new().with_margin(Thickness::top_bottom(1.0)),
@@ -54,22 +140,25 @@ where
                 ctx.property_info
                     .min_value
                     .and_then(NumCast::from)
+                    .map(|v| to_display(v, in_degrees))
                     .unwrap_or_else(T::min_value),
             )
             .with_max_value(
                 ctx.property_info
                     .max_value
                     .and_then(NumCast::from)
+                    .map(|v| to_display(v, in_degrees))
                     .unwrap_or_else(T::max_value),
             )
             .with_step(
                 ctx.property_info
                     .step
                     .and_then(NumCast::from)
+                    .map(|v| to_display(v, in_degrees))
                     .unwrap_or_else(T::one),
             )
-            .with_precision(ctx.property_info.precision.unwrap_or(3))
-            .with_value(*value)
+            .with_precision(ctx.property_info.precision.unwrap_or(settings.precision))
+            .with_value(to_display(*value, in_degrees))
             .build(ctx.build_context),
         })
     }
@@ -79,10 +168,14 @@ where
         ctx: PropertyEditorMessageContext,
     ) -> Result<Option<UiMessage>, InspectorError> {
         let value = ctx.property_info.cast_value::<T>()?;
+        let in_degrees = is_shown_in_degrees(
+            ctx.property_info.is_angle,
+            &ctx.definition_container.numeric_display_settings(),
+        );
         Ok(Some(NumericUpDownMessage::value(
             ctx.instance,
             MessageDirection::ToWidget,
-            *value,
+            to_display(*value, in_degrees),
         )))
     }
 
@@ -91,10 +184,14 @@ where
             if let Some(NumericUpDownMessage::Value(value)) =
                 ctx.message.data::<NumericUpDownMessage<T>>()
             {
+                let in_degrees = is_shown_in_degrees(
+                    ctx.is_angle,
+                    &ctx.definition_container.numeric_display_settings(),
+                );
                 return Some(PropertyChanged {
                     name: ctx.name.to_string(),
                     owner_type_id: ctx.owner_type_id,
-                    value: FieldKind::object(*value),
+                    value: FieldKind::object(from_display(*value, in_degrees)),
                 });
             }
         }
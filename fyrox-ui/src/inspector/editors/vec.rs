@@ -100,3 +100,80 @@ pub type Vec3PropertyEditorDefinition<T> = VecPropertyEditorDefinition<T, 3>;
 pub type Vec4PropertyEditorDefinition<T> = VecPropertyEditorDefinition<T, 4>;
 pub type Vec5PropertyEditorDefinition<T> = VecPropertyEditorDefinition<T, 5>;
 pub type Vec6PropertyEditorDefinition<T> = VecPropertyEditorDefinition<T, 6>;
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            algebra::{Vector2, Vector3},
+            reflect::prelude::*,
+        },
+        inspector::{
+            editors::PropertyEditorDefinitionContainer, InspectorBuilder, InspectorContext,
+            InspectorMessage, PropertyAction, PropertyFilter,
+        },
+        message::MessageDirection,
+        numeric::NumericUpDownMessage,
+        vec::Vec3Editor,
+        widget::WidgetBuilder,
+        UserInterface,
+    };
+    use std::rc::Rc;
+
+    #[derive(Reflect, Debug)]
+    struct Foo {
+        position: Vector3<f32>,
+    }
+
+    #[test]
+    fn test_vec_component_change() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+
+        let mut foo = Foo {
+            position: Vector3::new(1.0, 2.0, 3.0),
+        };
+
+        let context = InspectorContext::from_object(
+            &foo,
+            &mut ui.build_ctx(),
+            Rc::new(PropertyEditorDefinitionContainer::new()),
+            None,
+            1,
+            0,
+            false,
+            PropertyFilter::default(),
+        );
+        let editor = context.find_property_editor_widget("position");
+        let inspector = InspectorBuilder::new(WidgetBuilder::new())
+            .with_context(context)
+            .build(&mut ui.build_ctx());
+
+        while ui.poll_message().is_some() {}
+
+        // Edit Y component only.
+        let field = ui.node(editor).cast::<Vec3Editor<f32>>().unwrap().fields[1];
+        ui.send_message(NumericUpDownMessage::value(
+            field,
+            MessageDirection::ToWidget,
+            5.0f32,
+        ));
+
+        let mut changes = Vec::new();
+        while let Some(message) = ui.poll_message() {
+            if message.destination() == inspector {
+                if let Some(InspectorMessage::PropertyChanged(args)) =
+                    message.data::<InspectorMessage>()
+                {
+                    changes.push((args.path(), PropertyAction::from_field_kind(&args.value)));
+                }
+            }
+        }
+
+        assert_eq!(changes.len(), 1);
+        for (path, action) in changes {
+            action.apply(&path, &mut foo, &mut |result| assert!(result.is_ok()));
+        }
+
+        assert_eq!(foo.position, Vector3::new(1.0, 5.0, 3.0));
+    }
+}
@@ -2,9 +2,11 @@ use crate::{
     border::BorderBuilder,
     check_box::CheckBoxBuilder,
     core::{
-        algebra::Vector2,
+        algebra::{UnitQuaternion, Vector2, Vector3, Vector4},
+        color::Color,
         pool::Handle,
         reflect::{CastError, Reflect, ResolvePath},
+        uuid::Uuid,
     },
     define_constructor,
     expander::ExpanderBuilder,
@@ -15,10 +17,11 @@ use crate::{
         PropertyEditorInstance, PropertyEditorMessageContext, PropertyEditorTranslationContext,
     },
     menu::{MenuItemBuilder, MenuItemContent, MenuItemMessage},
-    message::{MessageDirection, UiMessage},
+    message::{MessageDirection, MouseButton, UiMessage},
+    numeric::NumericScrubMessage,
     popup::PopupBuilder,
     stack_panel::StackPanelBuilder,
-    text::TextBuilder,
+    text::{TextBuilder, TextMessage},
     utils::{make_arrow, make_simple_tooltip, ArrowDirection},
     widget::{Widget, WidgetBuilder, WidgetMessage},
     BuildContext, Control, RcUiNodeHandle, Thickness, UiNode, UserInterface, VerticalAlignment,
@@ -41,6 +44,14 @@ pub enum CollectionChanged {
     Add(ObjectValue),
     /// An item in the collection should be removed.
     Remove(usize),
+    /// An item in the collection should be moved to a new position. The item is removed from the
+    /// `from` position first and then inserted at the `to` position.
+    Move {
+        /// Current index of the item.
+        from: usize,
+        /// New index of the item.
+        to: usize,
+    },
     /// An item in the collection has changed one of its properties.
     ItemChanged {
         /// Index of an item in the collection.
@@ -52,6 +63,7 @@ pub enum CollectionChanged {
 impl CollectionChanged {
     define_constructor!(CollectionChanged:Add => fn add(ObjectValue), layout: false);
     define_constructor!(CollectionChanged:Remove => fn remove(usize), layout: false);
+    define_constructor!(CollectionChanged:Move => fn move_item(from: usize, to: usize), layout: false);
     define_constructor!(CollectionChanged:ItemChanged => fn item_changed(index: usize, property: PropertyChanged), layout: false);
 }
 
@@ -86,6 +98,13 @@ pub enum PropertyAction {
         /// Index of an item.
         index: usize,
     },
+    /// An item of a collection property needs to be moved to a new position.
+    MoveItem {
+        /// Current index of the item.
+        from: usize,
+        /// New index of the item.
+        to: usize,
+    },
     /// Revert value to parent.
     Revert,
 }
@@ -104,6 +123,7 @@ impl PropertyAction {
                     value: value.clone().into_box_reflect(),
                 },
                 CollectionChanged::Remove(index) => Self::RemoveItem { index },
+                CollectionChanged::Move { from, to } => Self::MoveItem { from, to },
                 CollectionChanged::ItemChanged { ref property, .. } => {
                     Self::from_field_kind(&property.value)
                 }
@@ -179,6 +199,28 @@ impl PropertyAction {
                     result_callback(Err(Self::RemoveItem { index }))
                 }
             }),
+            PropertyAction::MoveItem { from, to } => {
+                target.resolve_path_mut(path, &mut |result| {
+                    if let Ok(field) = result {
+                        field.as_list_mut(&mut |result| {
+                            match result {
+                                Some(list)
+                                    if from < list.reflect_len() && to < list.reflect_len() =>
+                                {
+                                    if let Some(value) = list.reflect_remove(from) {
+                                        // Cannot fail, the item has the type of the collection items.
+                                        let _ = list.reflect_insert(to, value);
+                                    }
+                                    result_callback(Ok(None))
+                                }
+                                _ => result_callback(Err(Self::MoveItem { from, to })),
+                            }
+                        })
+                    } else {
+                        result_callback(Err(Self::MoveItem { from, to }))
+                    }
+                })
+            }
             PropertyAction::Revert => {
                 // Unsupported due to lack of context (a reference to parent entity).
                 result_callback(Err(Self::Revert))
@@ -303,6 +345,7 @@ impl PropertyChanged {
             FieldKind::Collection(ref collection_changed) => match **collection_changed {
                 CollectionChanged::Add(_) => false,
                 CollectionChanged::Remove(_) => false,
+                CollectionChanged::Move { .. } => false,
                 CollectionChanged::ItemChanged { ref property, .. } => property.is_inheritable(),
             },
             FieldKind::Inspectable(ref inspectable) => inspectable.is_inheritable(),
@@ -316,11 +359,25 @@ impl PropertyChanged {
 pub enum InspectorMessage {
     Context(InspectorContext),
     PropertyChanged(PropertyChanged),
+    /// Resets a property with the given name to its value in [`InspectorContext::default_object`].
+    /// The reset is done through the property editor, so the inspector will emit usual
+    /// [`InspectorMessage::PropertyChanged`] message with the default value.
+    ResetToDefault(String),
+    /// Pins or unpins a property with the given name. Pinned properties are shown in a separate section
+    /// at the top of the inspector, the pins are stored per type of inspected objects in
+    /// [`PropertyEditorDefinitionContainer`]. The inspector responds with the same message with
+    /// [`MessageDirection::FromWidget`] direction.
+    PinProperty {
+        name: String,
+        pinned: bool,
+    },
 }
 
 impl InspectorMessage {
     define_constructor!(InspectorMessage:Context => fn context(InspectorContext), layout: false);
     define_constructor!(InspectorMessage:PropertyChanged => fn property_changed(PropertyChanged), layout: false);
+    define_constructor!(InspectorMessage:ResetToDefault => fn reset_to_default(String), layout: false);
+    define_constructor!(InspectorMessage:PinProperty => fn pin_property(name: String, pinned: bool), layout: false);
 }
 
 pub trait InspectorEnvironment: Any {
@@ -339,6 +396,29 @@ impl Inspector {
     pub fn context(&self) -> &InspectorContext {
         &self.context
     }
+
+    /// Returns an entry of the property, for which the context menu was opened.
+    fn entry_under_menu(&self, ui: &UserInterface) -> Option<&ContextEntry> {
+        let menu_handle = self.context.menu.menu.as_ref().map(|h| **h)?;
+        let position = ui.node(menu_handle).screen_position();
+
+        let mut parent_handle = ui.hit_test_unrestricted(position - Vector2::new(1.0, 1.0));
+
+        while let Some(parent) = ui.try_get_node(parent_handle) {
+            if let Some(entry) = self
+                .context
+                .entries
+                .iter()
+                .find(|e| e.property_container == parent_handle)
+            {
+                return Some(entry);
+            }
+
+            parent_handle = parent.parent;
+        }
+
+        None
+    }
 }
 
 pub const NAME_COLUMN_WIDTH: f32 = 150.0;
@@ -371,6 +451,13 @@ pub struct ContextEntry {
     pub property_editor: Handle<UiNode>,
     pub property_debug_output: String,
     pub property_container: Handle<UiNode>,
+    /// A label with the name of the property, it is [`Handle::NONE`] for properties with custom containers.
+    pub property_label: Handle<UiNode>,
+    /// Whether the property is an angle or not. See [`crate::core::reflect::FieldInfo::is_angle`].
+    pub property_is_angle: bool,
+    // Last known result of InspectorContext::is_property_modified, it is shared between the copies of
+    // the context, so the label is updated only when the state changes.
+    property_modified: Rc<Cell<bool>>,
 }
 
 impl PartialEq for ContextEntry {
@@ -389,6 +476,8 @@ impl PartialEq for ContextEntry {
 #[derive(Default, Clone)]
 pub struct Menu {
     pub copy_value_as_string: Handle<UiNode>,
+    pub reset_to_default: Handle<UiNode>,
+    pub pin: Handle<UiNode>,
     pub menu: Option<RcUiNodeHandle>,
     pub target: Cell<Handle<UiNode>>,
 }
@@ -396,11 +485,18 @@ pub struct Menu {
 #[derive(Clone)]
 pub struct InspectorContext {
     pub stack_panel: Handle<UiNode>,
+    /// A panel at the top of [`Self::stack_panel`] with the containers of pinned properties.
+    pub pinned_section: Handle<UiNode>,
+    /// Type name of the inspected object, pinned properties are stored per this name.
+    pub type_name: String,
     pub menu: Menu,
     pub entries: Vec<ContextEntry>,
     pub property_definitions: Rc<PropertyEditorDefinitionContainer>,
     pub environment: Option<Rc<dyn InspectorEnvironment>>,
     pub sync_flag: u64,
+    /// An object of the same type as the inspected one, that holds default values of the properties.
+    /// It is used to reset properties to their defaults, if it is `None`, then resetting does nothing.
+    pub default_object: Option<Rc<dyn Reflect>>,
 }
 
 impl PartialEq for InspectorContext {
@@ -413,11 +509,14 @@ impl Default for InspectorContext {
     fn default() -> Self {
         Self {
             stack_panel: Default::default(),
+            pinned_section: Default::default(),
+            type_name: Default::default(),
             menu: Default::default(),
             entries: Default::default(),
             property_definitions: Rc::new(PropertyEditorDefinitionContainer::new()),
             environment: None,
             sync_flag: 0,
+            default_object: None,
         }
     }
 }
@@ -514,6 +613,123 @@ fn make_tooltip(ctx: &mut BuildContext, text: &str) -> Option<RcUiNodeHandle> {
     }
 }
 
+/// Compares values of the most common types using their [`PartialEq`] implementation. Returns `None`
+/// if the type of the values is unknown.
+fn primitive_eq(a: &dyn Any, b: &dyn Any) -> Option<bool> {
+    if a.type_id() != b.type_id() {
+        return Some(false);
+    }
+
+    macro_rules! try_eq {
+        ($($ty:ty),*) => {
+            $(
+                if let (Some(a), Some(b)) = (a.downcast_ref::<$ty>(), b.downcast_ref::<$ty>()) {
+                    return Some(a == b);
+                }
+            )*
+        };
+    }
+
+    try_eq!(
+        f32,
+        f64,
+        i8,
+        i16,
+        i32,
+        i64,
+        isize,
+        u8,
+        u16,
+        u32,
+        u64,
+        usize,
+        bool,
+        char,
+        String,
+        Vector2<f32>,
+        Vector3<f32>,
+        Vector4<f32>,
+        UnitQuaternion<f32>,
+        Color,
+        Uuid
+    );
+
+    None
+}
+
+/// Compares pairs of values, see [`reflect_eq`] for more info.
+fn all_eq<'a>(mut pairs: impl Iterator<Item = (&'a dyn Reflect, &'a dyn Reflect)>) -> bool {
+    pairs.all(|(a, b)| reflect_eq(a, b))
+}
+
+/// Compares two values of the same type through reflection. Inheritable variables are compared by
+/// their inner values, values of the most common types are compared directly, collections are
+/// compared element by element and everything else is compared field by field. Values, that have
+/// nothing to compare through reflection (such as unit variants of enums), are compared by their
+/// [`Debug`] representation.
+fn reflect_eq(a: &dyn Reflect, b: &dyn Reflect) -> bool {
+    let mut result = None;
+
+    a.as_inheritable_variable(&mut |a| {
+        if let Some(a) = a {
+            b.as_inheritable_variable(&mut |b| result = b.map(|b| a.value_equals(b)));
+        }
+    });
+    if let Some(result) = result {
+        return result;
+    }
+
+    a.as_any(&mut |a| b.as_any(&mut |b| result = primitive_eq(a, b)));
+    if let Some(result) = result {
+        return result;
+    }
+
+    a.as_array(&mut |a| {
+        if let Some(a) = a {
+            b.as_array(&mut |b| {
+                if let Some(b) = b {
+                    result =
+                        Some(
+                            a.reflect_len() == b.reflect_len()
+                                && all_eq((0..a.reflect_len()).filter_map(|i| {
+                                    Some((a.reflect_index(i)?, b.reflect_index(i)?))
+                                })),
+                        );
+                }
+            });
+        }
+    });
+    if let Some(result) = result {
+        return result;
+    }
+
+    a.fields_info(&mut |a_fields| {
+        b.fields_info(&mut |b_fields| {
+            result = if a_fields.is_empty() && b_fields.is_empty() {
+                None
+            } else if a_fields.len() != b_fields.len()
+                // Fields of different enum variants have different names.
+                || a_fields.iter().zip(b_fields.iter()).any(|(a, b)| a.name != b.name)
+            {
+                Some(false)
+            } else {
+                Some(all_eq(
+                    a_fields
+                        .iter()
+                        .zip(b_fields.iter())
+                        .map(|(a, b)| (a.reflect_value, b.reflect_value)),
+                ))
+            };
+        })
+    });
+    if let Some(result) = result {
+        return result;
+    }
+
+    // Unit variants of enums have no fields, but their names are a part of debug output.
+    format!("{:?}", a) == format!("{:?}", b)
+}
+
 fn make_simple_property_container(
     title: Handle<UiNode>,
     editor: Handle<UiNode>,
@@ -574,7 +790,9 @@ impl InspectorContext {
             }
         });
 
+        let type_name = object.type_name();
         let mut editors = Vec::new();
+        let mut pinned_editors = Vec::new();
         object.fields_info(&mut |fields_info| {
             for (i, (field_text, info)) in
                 fields_text.iter().zip(fields_info.into_iter()).enumerate()
@@ -589,68 +807,100 @@ impl InspectorContext {
                     format!("{}\n\n{}", info.display_name, info.description)
                 };
 
+                // Show units of angle properties next to their names.
+                let name = if info.is_angle {
+                    format!(
+                        "{} ({})",
+                        info.display_name,
+                        definition_container
+                            .numeric_display_settings()
+                            .angle_unit
+                            .label()
+                    )
+                } else {
+                    info.display_name.to_string()
+                };
+
                 if let Some(definition) = definition_container
                     .definitions()
                     .get(&info.value.type_id())
                 {
-                    let editor = match definition.create_instance(PropertyEditorBuildContext {
-                        build_context: ctx,
-                        property_info: &info,
-                        environment: environment.clone(),
-                        definition_container: definition_container.clone(),
-                        sync_flag,
-                        layer_index,
-                        generate_property_string_values,
-                        filter: filter.clone(),
-                    }) {
-                        Ok(instance) => {
-                            let (container, editor) = match instance {
-                                PropertyEditorInstance::Simple { editor } => (
-                                    make_simple_property_container(
-                                        create_header(ctx, info.display_name, layer_index),
-                                        editor,
-                                        &description,
-                                        ctx,
-                                    ),
-                                    editor,
-                                ),
-                                PropertyEditorInstance::Custom { container, editor } => {
-                                    (container, editor)
+                    let (editor, pinned) =
+                        match definition.create_instance(PropertyEditorBuildContext {
+                            build_context: ctx,
+                            property_info: &info,
+                            environment: environment.clone(),
+                            definition_container: definition_container.clone(),
+                            sync_flag,
+                            layer_index,
+                            generate_property_string_values,
+                            filter: filter.clone(),
+                        }) {
+                            Ok(instance) => {
+                                let (container, editor, label) = match instance {
+                                    PropertyEditorInstance::Simple { editor } => {
+                                        let label = create_header(ctx, &name, layer_index);
+                                        (
+                                            make_simple_property_container(
+                                                label,
+                                                editor,
+                                                &description,
+                                                ctx,
+                                            ),
+                                            editor,
+                                            label,
+                                        )
+                                    }
+                                    PropertyEditorInstance::Custom { container, editor } => {
+                                        (container, editor, Handle::NONE)
+                                    }
+                                };
+
+                                entries.push(ContextEntry {
+                                    property_editor: editor,
+                                    property_editor_definition: definition.clone(),
+                                    property_name: info.name.to_string(),
+                                    property_owner_type_id: info.owner_type_id,
+                                    property_debug_output: field_text.clone(),
+                                    property_container: container,
+                                    property_label: label,
+                                    property_is_angle: info.is_angle,
+                                    property_modified: Default::default(),
+                                });
+
+                                if info.read_only {
+                                    ctx[editor].set_enabled(false);
                                 }
-                            };
-
-                            entries.push(ContextEntry {
-                                property_editor: editor,
-                                property_editor_definition: definition.clone(),
-                                property_name: info.name.to_string(),
-                                property_owner_type_id: info.owner_type_id,
-                                property_debug_output: field_text.clone(),
-                                property_container: container,
-                            });
-
-                            if info.read_only {
-                                ctx[editor].set_enabled(false);
-                            }
 
-                            container
-                        }
-                        Err(e) => make_simple_property_container(
-                            create_header(ctx, info.display_name, layer_index),
-                            TextBuilder::new(WidgetBuilder::new().on_row(i).on_column(1))
-                                .with_wrap(WrapMode::Word)
-                                .with_vertical_text_alignment(VerticalAlignment::Center)
-                                .with_text(format!(
-                                    "Unable to create property \
+                                (
+                                    container,
+                                    definition_container.is_property_pinned(type_name, info.name),
+                                )
+                            }
+                            Err(e) => (
+                                make_simple_property_container(
+                                    create_header(ctx, info.display_name, layer_index),
+                                    TextBuilder::new(WidgetBuilder::new().on_row(i).on_column(1))
+                                        .with_wrap(WrapMode::Word)
+                                        .with_vertical_text_alignment(VerticalAlignment::Center)
+                                        .with_text(format!(
+                                            "Unable to create property \
                                                     editor instance: Reason {:?}",
-                                    e
-                                ))
-                                .build(ctx),
-                            &description,
-                            ctx,
-                        ),
-                    };
+                                            e
+                                        ))
+                                        .build(ctx),
+                                    &description,
+                                    ctx,
+                                ),
+                                false,
+                            ),
+                        };
 
-                    editors.push(editor);
+                    if pinned {
+                        pinned_editors.push(editor);
+                    } else {
+                        editors.push(editor);
+                    }
                 } else {
                     editors.push(make_simple_property_container(
                         create_header(ctx, info.display_name, layer_index),
@@ -670,30 +920,66 @@ impl InspectorContext {
         });
 
         let copy_value_as_string;
+        let reset_to_default;
+        let pin;
         let menu = PopupBuilder::new(WidgetBuilder::new().with_visibility(false))
             .with_content(
-                StackPanelBuilder::new(WidgetBuilder::new().with_child({
-                    copy_value_as_string = MenuItemBuilder::new(WidgetBuilder::new())
-                        .with_content(MenuItemContent::text("Copy Value as String"))
-                        .build(ctx);
-                    copy_value_as_string
-                }))
+                StackPanelBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child({
+                            copy_value_as_string = MenuItemBuilder::new(WidgetBuilder::new())
+                                .with_content(MenuItemContent::text("Copy Value as String"))
+                                .build(ctx);
+                            copy_value_as_string
+                        })
+                        .with_child({
+                            reset_to_default = MenuItemBuilder::new(WidgetBuilder::new())
+                                .with_content(MenuItemContent::text("Reset to Default"))
+                                .build(ctx);
+                            reset_to_default
+                        })
+                        .with_child({
+                            pin = MenuItemBuilder::new(WidgetBuilder::new())
+                                .with_content(MenuItemContent::text("Pin/Unpin Property"))
+                                .build(ctx);
+                            pin
+                        }),
+                )
                 .build(ctx),
             )
             .build(ctx);
         let menu = RcUiNodeHandle::new(menu, ctx.sender());
 
+        let pinned_section = StackPanelBuilder::new(
+            WidgetBuilder::new()
+                .with_visibility(!pinned_editors.is_empty())
+                .with_margin(Thickness {
+                    left: 0.0,
+                    top: 0.0,
+                    right: 0.0,
+                    bottom: 4.0,
+                })
+                .with_child(create_header(ctx, "Pinned", layer_index))
+                .with_children(pinned_editors),
+        )
+        .build(ctx);
+
         let stack_panel = StackPanelBuilder::new(
             WidgetBuilder::new()
                 .with_context_menu(menu.clone())
+                .with_child(pinned_section)
                 .with_children(editors),
         )
         .build(ctx);
 
         Self {
             stack_panel,
+            pinned_section,
+            type_name: type_name.to_owned(),
             menu: Menu {
                 copy_value_as_string,
+                reset_to_default,
+                pin,
                 menu: Some(menu),
                 target: Default::default(),
             },
@@ -701,9 +987,16 @@ impl InspectorContext {
             property_definitions: definition_container,
             sync_flag,
             environment,
+            default_object: None,
         }
     }
 
+    /// Sets an object with default values of the properties, see [`Self::default_object`] for more info.
+    pub fn with_default_object(mut self, default_object: Rc<dyn Reflect>) -> Self {
+        self.default_object = Some(default_object);
+        self
+    }
+
     pub fn sync(
         &self,
         object: &dyn Reflect,
@@ -752,6 +1045,8 @@ impl InspectorContext {
             }
         });
 
+        self.sync_modified_properties(object, ui);
+
         if sync_errors.is_empty() {
             Ok(())
         } else {
@@ -759,6 +1054,77 @@ impl InspectorContext {
         }
     }
 
+    /// Returns `true` if the value of a property with the given name differs from its value in
+    /// [`Self::default_object`]. Values are compared through reflection, see [`reflect_eq`] for more
+    /// info. Always returns `false` if there is no default object.
+    pub fn is_property_modified(&self, object: &dyn Reflect, name: &str) -> bool {
+        let mut modified = false;
+        if let Some(default_object) = self.default_object.as_ref() {
+            object.field(name, &mut |value| {
+                default_object.field(name, &mut |default_value| {
+                    if let (Some(value), Some(default_value)) = (value, default_value) {
+                        modified = !reflect_eq(value, default_value);
+                    }
+                })
+            });
+        }
+        modified
+    }
+
+    /// Makes names of the properties, that differ from their default values, bold. It is done by
+    /// drawing a dilated shadow of the same color under the text of the name. Labels are updated only
+    /// if the state of a property has changed since the last sync.
+    fn sync_modified_properties(&self, object: &dyn Reflect, ui: &UserInterface) {
+        let default_object = match self.default_object.as_ref() {
+            Some(default_object) => default_object,
+            None => return,
+        };
+
+        default_object.fields_info(&mut |default_fields| {
+            object.fields_info(&mut |fields| {
+                // Entries are stored in the order of the fields, so both could be walked at once.
+                let mut entries = self.entries.iter().peekable();
+                for (info, default_info) in fields.iter().zip(default_fields.iter()) {
+                    let entry = match entries.peek() {
+                        Some(entry) if entry.property_name == info.name => entries.next().unwrap(),
+                        _ => continue,
+                    };
+
+                    let modified = !reflect_eq(info.reflect_value, default_info.reflect_value);
+                    if entry.property_modified.replace(modified) != modified {
+                        Self::set_label_bold(entry.property_label, modified, ui);
+                    }
+                }
+            })
+        });
+    }
+
+    fn set_label_bold(label: Handle<UiNode>, bold: bool, ui: &UserInterface) {
+        let foreground = match ui.try_get_node(label) {
+            Some(label) => label.foreground(),
+            None => return,
+        };
+
+        if bold {
+            ui.send_message(TextMessage::shadow_brush(
+                label,
+                MessageDirection::ToWidget,
+                foreground,
+            ));
+            ui.send_message(TextMessage::shadow_dilation(
+                label,
+                MessageDirection::ToWidget,
+                0.5,
+            ));
+            ui.send_message(TextMessage::shadow_offset(
+                label,
+                MessageDirection::ToWidget,
+                Vector2::default(),
+            ));
+        }
+        ui.send_message(TextMessage::shadow(label, MessageDirection::ToWidget, bold));
+    }
+
     pub fn property_editors(&self) -> impl Iterator<Item = &ContextEntry> + '_ {
         self.entries.iter()
     }
@@ -772,6 +1138,75 @@ impl InspectorContext {
             .map(|e| e.property_editor)
             .unwrap_or_default()
     }
+
+    /// Returns `true` if a property with the given name is pinned, `false` - otherwise.
+    pub fn is_property_pinned(&self, name: &str) -> bool {
+        self.property_definitions
+            .is_property_pinned(&self.type_name, name)
+    }
+
+    fn set_property_pinned(&self, name: &str, pinned: bool, ui: &UserInterface) {
+        if self.find_property_editor(name).is_none() {
+            return;
+        }
+
+        self.property_definitions
+            .set_property_pinned(&self.type_name, name, pinned);
+
+        // Re-link every container to keep the order of the properties in both sections.
+        let mut any_pinned = false;
+        for entry in self.entries.iter() {
+            let parent = if self.is_property_pinned(&entry.property_name) {
+                any_pinned = true;
+                self.pinned_section
+            } else {
+                self.stack_panel
+            };
+            ui.send_message(WidgetMessage::link(
+                entry.property_container,
+                MessageDirection::ToWidget,
+                parent,
+            ));
+        }
+
+        ui.send_message(WidgetMessage::visibility(
+            self.pinned_section,
+            MessageDirection::ToWidget,
+            any_pinned,
+        ));
+    }
+
+    fn reset_to_default(&self, name: &str, ui: &mut UserInterface) {
+        let (default_object, entry) = match (
+            self.default_object.as_ref(),
+            self.find_property_editor(name),
+        ) {
+            (Some(default_object), Some(entry)) => (default_object, entry),
+            _ => return,
+        };
+
+        default_object.fields_info(&mut |fields_info| {
+            if let Some(info) = fields_info.iter().find(|info| info.name == name) {
+                let ctx = PropertyEditorMessageContext {
+                    sync_flag: self.sync_flag,
+                    instance: entry.property_editor,
+                    ui,
+                    property_info: info,
+                    definition_container: self.property_definitions.clone(),
+                    layer_index: 0,
+                    environment: self.environment.clone(),
+                    generate_property_string_values: false,
+                    filter: Default::default(),
+                };
+
+                // The message is not marked with the sync flag, so the editor will respond with
+                // a message that will be translated to PropertyChanged.
+                if let Ok(Some(message)) = entry.property_editor_definition.create_message(ctx) {
+                    ui.send_message(message);
+                }
+            }
+        });
+    }
 }
 
 impl Control for Inspector {
@@ -802,6 +1237,39 @@ impl Control for Inspector {
                 ));
 
                 self.context = ctx.clone();
+            } else if let Some(InspectorMessage::ResetToDefault(name)) =
+                message.data::<InspectorMessage>()
+            {
+                self.context.reset_to_default(name, ui);
+            } else if let Some(InspectorMessage::PinProperty { name, pinned }) =
+                message.data::<InspectorMessage>()
+            {
+                self.context.set_property_pinned(name, *pinned, ui);
+                ui.send_message(InspectorMessage::pin_property(
+                    self.handle,
+                    MessageDirection::FromWidget,
+                    name.clone(),
+                    *pinned,
+                ));
+            }
+        }
+
+        // Dragging a property label scrubs the value of the property, if its editor supports it.
+        if let Some(WidgetMessage::MouseDown {
+            pos,
+            button: MouseButton::Left,
+        }) = message.data()
+        {
+            if let Some(entry) =
+                self.context.entries.iter().find(|e| {
+                    e.property_label.is_some() && e.property_label == message.destination()
+                })
+            {
+                ui.send_message(NumericScrubMessage::begin(
+                    entry.property_editor,
+                    MessageDirection::ToWidget,
+                    pos.x,
+                ));
             }
         }
 
@@ -816,6 +1284,7 @@ impl Control for Inspector {
                             environment: env.clone(),
                             name: &entry.property_name,
                             owner_type_id: entry.property_owner_type_id,
+                            is_angle: entry.property_is_angle,
                             message,
                             definition_container: self.context.property_definitions.clone(),
                         },
@@ -855,6 +1324,27 @@ impl Control for Inspector {
                     }
                 }
             }
+        } else if message.destination() == self.context.menu.reset_to_default {
+            if let Some(MenuItemMessage::Click) = message.data() {
+                if let Some(entry) = self.entry_under_menu(ui) {
+                    ui.send_message(InspectorMessage::reset_to_default(
+                        self.handle,
+                        MessageDirection::ToWidget,
+                        entry.property_name.clone(),
+                    ));
+                }
+            }
+        } else if message.destination() == self.context.menu.pin {
+            if let Some(MenuItemMessage::Click) = message.data() {
+                if let Some(entry) = self.entry_under_menu(ui) {
+                    ui.send_message(InspectorMessage::pin_property(
+                        self.handle,
+                        MessageDirection::ToWidget,
+                        entry.property_name.clone(),
+                        !self.context.is_property_pinned(&entry.property_name),
+                    ));
+                }
+            }
         }
     }
 }
@@ -896,3 +1386,372 @@ impl InspectorBuilder {
         ctx.add_node(UiNode::new(canvas))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{algebra::Vector2, reflect::prelude::*, variable::InheritableVariable},
+        inspector::{
+            editors::{
+                numeric::{AngleUnit, NumericDisplaySettings},
+                PropertyEditorDefinitionContainer,
+            },
+            reflect_eq, Inspector, InspectorBuilder, InspectorContext, InspectorMessage,
+            PropertyAction, PropertyFilter,
+        },
+        message::MessageDirection,
+        numeric::{NumericUpDown, NumericUpDownMessage},
+        text::{Text, TextMessage},
+        widget::WidgetBuilder,
+        UserInterface,
+    };
+    use std::rc::Rc;
+
+    #[derive(Reflect, Debug)]
+    struct Player {
+        speed: f32,
+        #[reflect(angle)]
+        yaw: f32,
+    }
+
+    impl Default for Player {
+        fn default() -> Self {
+            Self {
+                speed: 0.1,
+                yaw: 0.0,
+            }
+        }
+    }
+
+    #[test]
+    fn test_reset_to_default() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+
+        let mut player = Player {
+            speed: 0.5,
+            yaw: 1.0,
+        };
+
+        let context = InspectorContext::from_object(
+            &player,
+            &mut ui.build_ctx(),
+            Rc::new(PropertyEditorDefinitionContainer::new()),
+            None,
+            1,
+            0,
+            false,
+            PropertyFilter::default(),
+        )
+        .with_default_object(Rc::new(Player::default()));
+        let inspector = InspectorBuilder::new(WidgetBuilder::new())
+            .with_context(context)
+            .build(&mut ui.build_ctx());
+
+        while ui.poll_message().is_some() {}
+
+        ui.send_message(InspectorMessage::reset_to_default(
+            inspector,
+            MessageDirection::ToWidget,
+            "speed".to_string(),
+        ));
+
+        let mut changes = Vec::new();
+        while let Some(message) = ui.poll_message() {
+            if message.destination() == inspector {
+                if let Some(InspectorMessage::PropertyChanged(args)) =
+                    message.data::<InspectorMessage>()
+                {
+                    changes.push((args.path(), PropertyAction::from_field_kind(&args.value)));
+                }
+            }
+        }
+
+        // Reset must be a single change, so it could be undone in one step.
+        assert_eq!(changes.len(), 1);
+        for (path, action) in changes {
+            assert_eq!(path, "speed");
+            action.apply(&path, &mut player, &mut |result| assert!(result.is_ok()));
+        }
+
+        assert_eq!(player.speed, 0.1);
+        assert_eq!(player.yaw, 1.0);
+    }
+
+    #[derive(Reflect, Debug, Clone)]
+    struct Weapon {
+        name: String,
+        ammo: Vec<u32>,
+    }
+
+    #[derive(Reflect, Debug, Clone)]
+    enum Mode {
+        Idle,
+        Run,
+        Walk(f32),
+    }
+
+    #[test]
+    fn test_reflect_eq() {
+        assert!(reflect_eq(&1.0f32, &1.0f32));
+        assert!(!reflect_eq(&1.0f32, &2.0f32));
+
+        // Flags of inheritable variables do not matter.
+        assert!(reflect_eq(
+            &InheritableVariable::new_modified(1u32),
+            &InheritableVariable::new_non_modified(1u32)
+        ));
+
+        let weapon = Weapon {
+            name: "Rifle".to_string(),
+            ammo: vec![1, 2, 3],
+        };
+        assert!(reflect_eq(&weapon, &weapon.clone()));
+        let mut other_weapon = weapon.clone();
+        other_weapon.ammo.push(4);
+        assert!(!reflect_eq(&weapon, &other_weapon));
+
+        assert!(reflect_eq(&Mode::Walk(1.0), &Mode::Walk(1.0)));
+        assert!(!reflect_eq(&Mode::Walk(1.0), &Mode::Walk(2.0)));
+        assert!(!reflect_eq(&Mode::Idle, &Mode::Walk(1.0)));
+        // Unit variants have nothing to compare through reflection.
+        assert!(reflect_eq(&Mode::Idle, &Mode::Idle));
+        assert!(!reflect_eq(&Mode::Idle, &Mode::Run));
+    }
+
+    #[test]
+    fn test_modified_properties_are_bold() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+
+        let mut player = Player {
+            speed: 0.5,
+            yaw: 0.0,
+        };
+
+        let context = InspectorContext::from_object(
+            &player,
+            &mut ui.build_ctx(),
+            Rc::new(PropertyEditorDefinitionContainer::new()),
+            None,
+            1,
+            0,
+            false,
+            PropertyFilter::default(),
+        )
+        .with_default_object(Rc::new(Player::default()));
+
+        assert!(context.is_property_modified(&player, "speed"));
+        assert!(!context.is_property_modified(&player, "yaw"));
+
+        InspectorBuilder::new(WidgetBuilder::new())
+            .with_context(context.clone())
+            .build(&mut ui.build_ctx());
+        while ui.poll_message().is_some() {}
+
+        context
+            .sync(&player, &mut ui, 0, false, PropertyFilter::default())
+            .unwrap();
+        while ui.poll_message().is_some() {}
+
+        let is_bold = |ui: &UserInterface, name: &str| {
+            let label = context.find_property_editor(name).unwrap().property_label;
+            let text = ui.node(label).cast::<Text>().unwrap();
+            let formatted_text = text.formatted_text.borrow();
+            formatted_text.shadow
+        };
+        assert!(is_bold(&ui, "speed"));
+        assert!(!is_bold(&ui, "yaw"));
+
+        // Labels are not touched if nothing has changed.
+        context
+            .sync(&player, &mut ui, 0, false, PropertyFilter::default())
+            .unwrap();
+        while let Some(message) = ui.poll_message() {
+            assert!(message.data::<TextMessage>().is_none());
+        }
+
+        // The value is back to default.
+        player.speed = 0.1;
+        context
+            .sync(&player, &mut ui, 0, false, PropertyFilter::default())
+            .unwrap();
+        while ui.poll_message().is_some() {}
+        assert!(!is_bold(&ui, "speed"));
+
+        // Without default object there is nothing to compare with.
+        let mut context = context;
+        context.default_object = None;
+        assert!(!context.is_property_modified(&player, "speed"));
+    }
+
+    #[test]
+    fn test_pin_property() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+
+        let player = Player::default();
+        let definitions = Rc::new(PropertyEditorDefinitionContainer::new());
+
+        let context = InspectorContext::from_object(
+            &player,
+            &mut ui.build_ctx(),
+            definitions.clone(),
+            None,
+            1,
+            0,
+            false,
+            PropertyFilter::default(),
+        );
+        let inspector = InspectorBuilder::new(WidgetBuilder::new())
+            .with_context(context)
+            .build(&mut ui.build_ctx());
+
+        while ui.poll_message().is_some() {}
+
+        ui.send_message(InspectorMessage::pin_property(
+            inspector,
+            MessageDirection::ToWidget,
+            "speed".to_string(),
+            true,
+        ));
+
+        let mut responded = false;
+        while let Some(message) = ui.poll_message() {
+            if message.destination() == inspector
+                && message.direction() == MessageDirection::FromWidget
+            {
+                if let Some(InspectorMessage::PinProperty { name, pinned }) =
+                    message.data::<InspectorMessage>()
+                {
+                    assert_eq!(name, "speed");
+                    assert!(*pinned);
+                    responded = true;
+                }
+            }
+        }
+        assert!(responded);
+
+        let context = ui
+            .node(inspector)
+            .query_component::<Inspector>()
+            .unwrap()
+            .context()
+            .clone();
+        let type_name = std::any::type_name::<Player>();
+        assert!(definitions.is_property_pinned(type_name, "speed"));
+        assert!(!definitions.is_property_pinned(type_name, "yaw"));
+        let speed = context.find_property_editor("speed").unwrap();
+        let yaw = context.find_property_editor("yaw").unwrap();
+        assert_eq!(
+            ui.node(speed.property_container).parent(),
+            context.pinned_section
+        );
+        assert_eq!(
+            ui.node(yaw.property_container).parent(),
+            context.stack_panel
+        );
+        assert!(ui.node(context.pinned_section).visibility());
+
+        // Pins are applied to newly created inspectors of the same type.
+        let context = InspectorContext::from_object(
+            &player,
+            &mut ui.build_ctx(),
+            definitions.clone(),
+            None,
+            1,
+            0,
+            false,
+            PropertyFilter::default(),
+        );
+        let speed = context.find_property_editor("speed").unwrap();
+        assert_eq!(
+            ui.node(speed.property_container).parent(),
+            context.pinned_section
+        );
+
+        ui.send_message(InspectorMessage::pin_property(
+            inspector,
+            MessageDirection::ToWidget,
+            "speed".to_string(),
+            false,
+        ));
+        while ui.poll_message().is_some() {}
+
+        assert!(definitions.pinned_properties().is_empty());
+    }
+
+    fn edit_yaw(angle_unit: AngleUnit, displayed: f32, new_value: f32) -> f32 {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+
+        let mut player = Player {
+            speed: 0.5,
+            yaw: std::f32::consts::FRAC_PI_2,
+        };
+
+        let container = PropertyEditorDefinitionContainer::new();
+        container.set_numeric_display_settings(NumericDisplaySettings {
+            angle_unit,
+            ..Default::default()
+        });
+        let context = InspectorContext::from_object(
+            &player,
+            &mut ui.build_ctx(),
+            Rc::new(container),
+            None,
+            1,
+            0,
+            false,
+            PropertyFilter::default(),
+        );
+        let inspector = InspectorBuilder::new(WidgetBuilder::new())
+            .with_context(context)
+            .build(&mut ui.build_ctx());
+
+        while ui.poll_message().is_some() {}
+
+        let editor = ui
+            .node(inspector)
+            .cast::<Inspector>()
+            .unwrap()
+            .context
+            .entries
+            .iter()
+            .find(|e| e.property_name == "yaw")
+            .unwrap()
+            .property_editor;
+        let value = ui.node(editor).cast::<NumericUpDown<f32>>().unwrap().value;
+        assert!((value - displayed).abs() < 1.0e-4);
+
+        ui.send_message(NumericUpDownMessage::value(
+            editor,
+            MessageDirection::ToWidget,
+            new_value,
+        ));
+
+        while let Some(message) = ui.poll_message() {
+            if message.destination() == inspector {
+                if let Some(InspectorMessage::PropertyChanged(args)) =
+                    message.data::<InspectorMessage>()
+                {
+                    PropertyAction::from_field_kind(&args.value).apply(
+                        &args.path(),
+                        &mut player,
+                        &mut |result| assert!(result.is_ok()),
+                    );
+                }
+            }
+        }
+
+        // Other properties are shown as is.
+        assert_eq!(player.speed, 0.5);
+
+        player.yaw
+    }
+
+    #[test]
+    fn test_angle_is_shown_in_degrees() {
+        let yaw = edit_yaw(AngleUnit::Degrees, 90.0, 45.0);
+        assert!((yaw - std::f32::consts::FRAC_PI_4).abs() < 1.0e-6);
+
+        let yaw = edit_yaw(AngleUnit::Radians, std::f32::consts::FRAC_PI_2, 0.5);
+        assert!((yaw - 0.5).abs() < 1.0e-6);
+    }
+}
@@ -97,6 +97,22 @@ impl<T: NumericType> NumericUpDownMessage<T> {
     }
 }
 
+/// Starts horizontal drag-scrubbing of a numeric field: the field captures the mouse and changes its
+/// value while the mouse moves horizontally, the value is committed when left mouse button is released.
+/// Holding Shift makes scrubbing finer. The message is not generic, so it can be sent to a field without
+/// knowing its value type (for example, when a property label is dragged in the inspector).
+#[derive(Debug, Clone, PartialEq)]
+pub enum NumericScrubMessage {
+    Begin { start_mouse_pos: f32 },
+}
+
+impl NumericScrubMessage {
+    define_constructor!(NumericScrubMessage:Begin => fn begin(start_mouse_pos: f32), layout: false);
+}
+
+/// Scaling of scrubbing speed when Shift is held.
+pub const FINE_SCRUB_SCALING: f32 = 0.1;
+
 #[derive(Clone)]
 pub enum DragContext<T: NumericType> {
     PreDrag {
@@ -106,6 +122,11 @@ pub enum DragContext<T: NumericType> {
         start_value: T,
         start_mouse_pos: f32,
     },
+    Scrubbing {
+        start_value: T,
+        last_mouse_pos: f32,
+        offset: f32,
+    },
 }
 
 #[derive(Clone)]
@@ -161,6 +182,16 @@ impl<T: NumericType> NumericUpDown<T> {
         }
     }
 
+    fn value_by_offset(&self, start_value: T, offset: f32) -> T {
+        calculate_value_by_offset(
+            start_value,
+            offset as i32,
+            self.step,
+            self.min_value,
+            self.max_value,
+        )
+    }
+
     fn try_parse_value(&mut self, ui: &mut UserInterface) {
         // Parse input only when focus is lost from text field.
         if let Some(field) = ui.node(self.field).cast::<TextBox>() {
@@ -287,6 +318,34 @@ impl<T: NumericType> Control for NumericUpDown<T> {
                                     });
                                 }
                             }
+                            DragContext::Scrubbing {
+                                start_value,
+                                last_mouse_pos,
+                                offset,
+                            } => {
+                                let mut scaling = self.drag_value_scaling;
+                                if ui.keyboard_modifiers().shift {
+                                    scaling *= FINE_SCRUB_SCALING;
+                                }
+                                let start_value = *start_value;
+                                let offset = *offset + (pos.x - *last_mouse_pos) * scaling;
+                                self.drag_context = Some(DragContext::Scrubbing {
+                                    start_value,
+                                    last_mouse_pos: pos.x,
+                                    offset,
+                                });
+
+                                // Just change visual value while scrubbing; do not touch actual value.
+                                ui.send_message(TextMessage::text(
+                                    self.field,
+                                    MessageDirection::ToWidget,
+                                    format!(
+                                        "{:.1$}",
+                                        self.value_by_offset(start_value, offset),
+                                        self.precision
+                                    ),
+                                ));
+                            }
                             DragContext::Dragging {
                                 start_value,
                                 start_mouse_pos,
@@ -312,8 +371,48 @@ impl<T: NumericType> Control for NumericUpDown<T> {
                         }
                     }
                 }
+                WidgetMessage::MouseUp {
+                    button: MouseButton::Left,
+                    ..
+                } => {
+                    if let Some(DragContext::Scrubbing {
+                        start_value,
+                        offset,
+                        ..
+                    }) = self.drag_context
+                    {
+                        self.drag_context = None;
+                        ui.release_mouse_capture();
+
+                        let value = self.value_by_offset(start_value, offset);
+                        if value != self.value {
+                            ui.send_message(NumericUpDownMessage::value(
+                                self.handle,
+                                MessageDirection::ToWidget,
+                                value,
+                            ));
+                        } else {
+                            self.sync_text_field(ui);
+                        }
+
+                        message.set_handled(true);
+                    }
+                }
                 _ => {}
             }
+        } else if let Some(NumericScrubMessage::Begin { start_mouse_pos }) = message.data() {
+            if message.destination() == self.handle
+                && message.direction() == MessageDirection::ToWidget
+                && self.enabled()
+                && self.drag_context.is_none()
+                && ui.capture_mouse(self.handle)
+            {
+                self.drag_context = Some(DragContext::Scrubbing {
+                    start_value: self.value,
+                    last_mouse_pos: *start_mouse_pos,
+                    offset: 0.0,
+                });
+            }
         } else if let Some(msg) = message.data::<NumericUpDownMessage<T>>() {
             if message.direction() == MessageDirection::ToWidget
                 && message.destination() == self.handle()
@@ -576,7 +675,123 @@ impl<T: NumericType> NumericUpDownBuilder<T> {
 
 #[cfg(test)]
 mod test {
-    use crate::numeric::{saturating_add, saturating_sub};
+    use crate::{
+        core::{algebra::Vector2, reflect::prelude::*},
+        inspector::{
+            editors::PropertyEditorDefinitionContainer, InspectorBuilder, InspectorContext,
+            InspectorMessage, PropertyAction, PropertyFilter,
+        },
+        message::{ButtonState, KeyboardModifiers, MouseButton, OsEvent},
+        numeric::{saturating_add, saturating_sub},
+        widget::WidgetBuilder,
+        UserInterface,
+    };
+    use std::rc::Rc;
+
+    #[derive(Reflect, Debug)]
+    struct Foo {
+        value: f32,
+    }
+
+    #[test]
+    fn test_label_scrubbing() {
+        let screen_size = Vector2::new(500.0, 500.0);
+        let mut ui = UserInterface::new(screen_size);
+
+        let mut foo = Foo { value: 1.0 };
+
+        let context = InspectorContext::from_object(
+            &foo,
+            &mut ui.build_ctx(),
+            Rc::new(PropertyEditorDefinitionContainer::new()),
+            None,
+            1,
+            0,
+            false,
+            PropertyFilter::default(),
+        );
+        let label = context.entries[0].property_label;
+        let inspector = InspectorBuilder::new(WidgetBuilder::new())
+            .with_context(context)
+            .build(&mut ui.build_ctx());
+
+        // Picking requires both layout and drawing commands.
+        ui.update(screen_size, 0.0);
+        while ui.poll_message().is_some() {}
+        ui.draw();
+
+        // Grab the label by its text.
+        let bounds = ui.node(label).screen_bounds();
+        let start = bounds.position + Vector2::new(4.0, bounds.size.y * 0.5);
+        let mut changes = Vec::new();
+        let mut process = |ui: &mut UserInterface, event: OsEvent| {
+            ui.process_os_event(&event);
+            while let Some(message) = ui.poll_message() {
+                if message.destination() == inspector {
+                    if let Some(InspectorMessage::PropertyChanged(args)) =
+                        message.data::<InspectorMessage>()
+                    {
+                        changes.push((args.path(), PropertyAction::from_field_kind(&args.value)));
+                    }
+                }
+            }
+        };
+
+        process(&mut ui, OsEvent::CursorMoved { position: start });
+        process(
+            &mut ui,
+            OsEvent::MouseInput {
+                button: MouseButton::Left,
+                state: ButtonState::Pressed,
+            },
+        );
+        // 50 pixels with default scaling are 5 steps.
+        process(
+            &mut ui,
+            OsEvent::CursorMoved {
+                position: start + Vector2::new(50.0, 0.0),
+            },
+        );
+        // Shift makes scrubbing 10 times finer, so this is just a half of a step.
+        process(
+            &mut ui,
+            OsEvent::KeyboardModifiers(KeyboardModifiers {
+                shift: true,
+                ..Default::default()
+            }),
+        );
+        process(
+            &mut ui,
+            OsEvent::CursorMoved {
+                position: start + Vector2::new(100.0, 0.0),
+            },
+        );
+        process(
+            &mut ui,
+            OsEvent::KeyboardModifiers(KeyboardModifiers::default()),
+        );
+        process(
+            &mut ui,
+            OsEvent::CursorMoved {
+                position: start + Vector2::new(105.0, 0.0),
+            },
+        );
+        process(
+            &mut ui,
+            OsEvent::MouseInput {
+                button: MouseButton::Left,
+                state: ButtonState::Released,
+            },
+        );
+
+        // Whole scrub must result in a single change.
+        assert_eq!(changes.len(), 1);
+        for (path, action) in changes {
+            action.apply(&path, &mut foo, &mut |result| assert!(result.is_ok()));
+        }
+        assert_eq!(foo.value, 7.0);
+        assert!(ui.captured_node().is_none());
+    }
 
     #[test]
     fn test_saturating_add() {
@@ -327,6 +327,8 @@ impl Control for Tree {
                             self.panel,
                         ));
 
+                        // Adding an existing item moves it to the end.
+                        self.items.retain(|i| *i != item);
                         self.items.push(item);
                     }
                     &TreeMessage::RemoveItem(item) => {
@@ -6,8 +6,10 @@ use crate::{
     grid::{Column, GridBuilder, Row},
     message::{MessageDirection, UiMessage},
     numeric::{NumericType, NumericUpDownBuilder, NumericUpDownMessage},
+    text::TextBuilder,
     widget::WidgetBuilder,
-    BuildContext, Control, NodeHandleMapping, Thickness, UiNode, UserInterface, Widget,
+    BuildContext, Control, HorizontalAlignment, NodeHandleMapping, Thickness, UiNode,
+    UserInterface, VerticalAlignment, Widget,
 };
 use std::{
     any::{Any, TypeId},
@@ -43,14 +45,30 @@ fn make_numeric_input<T: NumericType>(
     .build(ctx)
 }
 
-pub fn make_mark(ctx: &mut BuildContext, column: usize, color: Color) -> Handle<UiNode> {
+pub fn make_mark(
+    ctx: &mut BuildContext,
+    text: &str,
+    column: usize,
+    color: Color,
+) -> Handle<UiNode> {
     BorderBuilder::new(
         WidgetBuilder::new()
             .on_row(0)
             .on_column(column)
             .with_background(Brush::Solid(color))
             .with_foreground(Brush::Solid(Color::TRANSPARENT))
-            .with_width(4.0),
+            .with_child(
+                TextBuilder::new(WidgetBuilder::new().with_margin(Thickness {
+                    left: 2.0,
+                    top: 0.0,
+                    right: 2.0,
+                    bottom: 0.0,
+                }))
+                .with_text(text)
+                .with_vertical_text_alignment(VerticalAlignment::Center)
+                .with_horizontal_text_alignment(HorizontalAlignment::Center)
+                .build(ctx),
+            ),
     )
     .build(ctx)
 }
@@ -226,6 +244,8 @@ where
         let mut children = Vec::new();
         let mut columns = Vec::new();
 
+        let labels = ["X", "Y", "Z", "W"];
+
         let colors = [
             Color::opaque(120, 0, 0),
             Color::opaque(0, 120, 0),
@@ -236,8 +256,12 @@ where
         ];
 
         for i in 0..D {
+            let label = labels
+                .get(i)
+                .map_or_else(|| i.to_string(), |l| l.to_string());
             children.push(make_mark(
                 ctx,
+                &label,
                 i * 2,
                 colors.get(i).cloned().unwrap_or(Color::ORANGE),
             ));
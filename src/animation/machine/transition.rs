@@ -67,6 +67,7 @@ macro_rules! define_two_args_node {
                         max_value: None,
                         step: None,
                         precision: None,
+                        is_angle: false,
                         doc: "",
                     },
                     FieldInfo {
@@ -82,7 +83,7 @@ macro_rules! define_two_args_node {
                         min_value: None,
                         max_value: None,
                         step: None,
-                        precision: None,doc: "",
+                        precision: None,is_angle: false,doc: "",
                     },
                 ])
             }
@@ -208,6 +209,7 @@ impl Reflect for NotNode {
             max_value: None,
             step: None,
             precision: None,
+            is_angle: false,
             doc: "",
         }])
     }
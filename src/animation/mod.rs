@@ -32,6 +32,7 @@ pub use signal::{AnimationEvent, AnimationSignal};
 pub mod container;
 pub mod machine;
 pub mod pose;
+pub mod retarget;
 pub mod signal;
 pub mod spritesheet;
 pub mod track;
@@ -0,0 +1,331 @@
+//! Skeletal retargeting allows you to apply an animation made for one skeleton to another skeleton with
+//! different proportions and bind pose. See [`retarget_animation`] docs for more info.
+
+use crate::{
+    animation::{container::TrackDataContainer, track::Track, value::ValueBinding, Animation},
+    core::{
+        algebra::{UnitQuaternion, Vector3},
+        curve::{Curve, CurveKey, CurveKeyKind},
+        log::Log,
+        math::{quat_from_euler, RotationOrder},
+        pool::Handle,
+    },
+    scene::{graph::Graph, node::Node},
+};
+use std::cmp::Ordering;
+
+/// A hierarchy of bones, that starts from a root node in a graph. Bones of two different skeletons are
+/// matched by their names.
+#[derive(Copy, Clone)]
+pub struct Skeleton<'a> {
+    /// A graph that contains the skeleton.
+    pub graph: &'a Graph,
+    /// A root node of the skeleton.
+    pub root: Handle<Node>,
+}
+
+impl<'a> Skeleton<'a> {
+    /// Creates a new skeleton, that starts from the given root node.
+    pub fn new(graph: &'a Graph, root: Handle<Node>) -> Self {
+        Self { graph, root }
+    }
+
+    fn find_bone(&self, name: &str) -> Option<(Handle<Node>, &'a Node)> {
+        self.graph.find_by_name(self.root, name)
+    }
+}
+
+/// Bind pose of a bone - its local transform at the moment of retargeting.
+struct BindPose {
+    position: Vector3<f32>,
+    rotation: UnitQuaternion<f32>,
+    scale: Vector3<f32>,
+}
+
+impl BindPose {
+    fn from_node(node: &Node) -> Self {
+        let transform = node.local_transform();
+        Self {
+            position: **transform.position(),
+            rotation: **transform.rotation(),
+            scale: **transform.scale(),
+        }
+    }
+}
+
+fn map_curve<F>(curve: &Curve, mut func: F) -> Curve
+where
+    F: FnMut(f32) -> f32,
+{
+    // Both functions used for mapping are linear, so tangents could be transformed the same way by
+    // mapping the difference between the key value and the value with the tangent applied.
+    let keys = curve
+        .keys()
+        .iter()
+        .map(|key| {
+            let value = func(key.value);
+            let kind = match key.kind {
+                CurveKeyKind::Cubic {
+                    left_tangent,
+                    right_tangent,
+                } => CurveKeyKind::Cubic {
+                    left_tangent: func(key.value + left_tangent) - value,
+                    right_tangent: func(key.value + right_tangent) - value,
+                },
+                ref kind => kind.clone(),
+            };
+            CurveKey::new(key.location(), value, kind)
+        })
+        .collect::<Vec<_>>();
+
+    let mut new_curve = Curve::from(keys);
+    new_curve.set_name(curve.name());
+    new_curve
+}
+
+fn map_vector_container<F>(container: &TrackDataContainer, mut func: F) -> TrackDataContainer
+where
+    F: FnMut(usize, f32) -> f32,
+{
+    let mut result = TrackDataContainer::default();
+    result.set_value_kind(container.value_kind());
+    for (i, curve) in container.curves_ref().iter().enumerate() {
+        result.add_curve(map_curve(curve, |value| func(i, value)));
+    }
+    result
+}
+
+fn map_rotation_container<F>(container: &TrackDataContainer, func: F) -> TrackDataContainer
+where
+    F: Fn(UnitQuaternion<f32>) -> UnitQuaternion<f32>,
+{
+    // Rotations are stored as Euler angles, which cannot be adjusted per-curve. Instead, the container
+    // is re-sampled at every key location of every curve.
+    let mut locations = container
+        .curves_ref()
+        .iter()
+        .flat_map(|c| c.keys().iter().map(|k| k.location()))
+        .collect::<Vec<_>>();
+    locations.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    locations.dedup();
+
+    let mut curves = [Vec::new(), Vec::new(), Vec::new()];
+    for location in locations {
+        let euler = Vector3::new(
+            container.curve(0).map_or(0.0, |c| c.value_at(location)),
+            container.curve(1).map_or(0.0, |c| c.value_at(location)),
+            container.curve(2).map_or(0.0, |c| c.value_at(location)),
+        );
+
+        let (x, y, z) = func(quat_from_euler(euler, RotationOrder::XYZ)).euler_angles();
+
+        for (curve, value) in curves.iter_mut().zip([x, y, z]) {
+            curve.push(CurveKey::new(location, value, CurveKeyKind::Linear));
+        }
+    }
+
+    let mut result = TrackDataContainer::default();
+    result.set_value_kind(container.value_kind());
+    for (keys, source) in curves.into_iter().zip(container.curves_ref()) {
+        let mut curve = Curve::from(keys);
+        curve.set_name(source.name());
+        result.add_curve(curve);
+    }
+    result
+}
+
+fn retarget_track(track: &Track, source: &BindPose, target: &BindPose) -> TrackDataContainer {
+    let container = track.data_container();
+    match track.binding() {
+        ValueBinding::Position => {
+            // Bone offsets are scaled by the ratio of bone lengths of the skeletons, this preserves the
+            // proportions of the motion.
+            let source_length = source.position.norm();
+            let ratio = if source_length > f32::EPSILON {
+                target.position.norm() / source_length
+            } else {
+                1.0
+            };
+            map_vector_container(container, |i, value| {
+                target.position[i] + (value - source.position[i]) * ratio
+            })
+        }
+        ValueBinding::Scale => map_vector_container(container, |i, value| {
+            if source.scale[i].abs() > f32::EPSILON {
+                value * target.scale[i] / source.scale[i]
+            } else {
+                value
+            }
+        }),
+        ValueBinding::Rotation => {
+            // Take the rotation relative to the source bind pose and apply it on top of the target bind pose.
+            let correction = target.rotation * source.rotation.inverse();
+            map_rotation_container(container, |rotation| correction * rotation)
+        }
+        ValueBinding::Property { .. } => container.clone(),
+    }
+}
+
+/// Applies the given animation, that was made for the `source` skeleton, to the `target` skeleton. Tracks are
+/// mapped to the bones of the target skeleton by names of the bones. Transform tracks are adjusted to the bind
+/// pose (local transform of the bones at the moment of retargeting) of the target skeleton:
+///
+/// - Rotations are applied relative to the bind pose - `target_bind * source_bind⁻¹ * rotation`. Rotation
+/// tracks are re-sampled at their key frames using linear interpolation.
+/// - Position offsets from the bind pose are scaled by the ratio of the bone lengths.
+/// - Scales are scaled by the ratio of the bind scales.
+///
+/// Tracks of property bindings are copied as is. Tracks, that are bound to the bones that do not exist in
+/// the target skeleton will be disabled and will have no target.
+///
+/// Both skeletons could be in different graphs, this allows you to re-use a library of animations stored
+/// in a separate model resource across multiple models with different proportions.
+pub fn retarget_animation(animation: &Animation, source: Skeleton, target: Skeleton) -> Animation {
+    let mut retargeted = animation.clone();
+
+    for (track, source_track) in retargeted
+        .tracks_mut()
+        .iter_mut()
+        .zip(animation.tracks().iter())
+    {
+        let target_bone = source
+            .graph
+            .try_get(source_track.target())
+            .and_then(|source_bone| {
+                let target_bone = target.find_bone(source_bone.name());
+                if target_bone.is_none() {
+                    Log::warn(format!(
+                        "Unable to retarget a track of {} bone, because there's no such bone in the target skeleton.",
+                        source_bone.name()
+                    ));
+                }
+                target_bone.map(|target_bone| (source_bone, target_bone))
+            });
+
+        if let Some((source_bone, (target_bone_handle, target_bone))) = target_bone {
+            let container = retarget_track(
+                source_track,
+                &BindPose::from_node(source_bone),
+                &BindPose::from_node(target_bone),
+            );
+            track.set_data_container(container);
+            track.set_target(target_bone_handle);
+        } else {
+            track.set_target(Handle::NONE);
+            track.set_enabled(false);
+        }
+    }
+
+    retargeted
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        animation::{
+            container::{TrackDataContainer, TrackValueKind},
+            retarget::{retarget_animation, Skeleton},
+            track::Track,
+            value::{TrackValue, ValueBinding},
+            Animation,
+        },
+        core::{
+            algebra::{UnitQuaternion, Vector3},
+            curve::{Curve, CurveKey, CurveKeyKind},
+            pool::Handle,
+        },
+        scene::{
+            base::BaseBuilder, graph::Graph, node::Node, pivot::PivotBuilder,
+            transform::TransformBuilder,
+        },
+    };
+
+    fn make_skeleton(
+        graph: &mut Graph,
+        hand_position: Vector3<f32>,
+        hand_rotation: UnitQuaternion<f32>,
+    ) -> (Handle<Node>, Handle<Node>) {
+        let hand = PivotBuilder::new(
+            BaseBuilder::new().with_name("Hand").with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(hand_position)
+                    .with_local_rotation(hand_rotation)
+                    .build(),
+            ),
+        )
+        .build(graph);
+        let arm = PivotBuilder::new(BaseBuilder::new().with_name("Arm").with_children(&[hand]))
+            .build(graph);
+        (arm, hand)
+    }
+
+    fn linear_curve(from: f32, to: f32) -> Curve {
+        Curve::from(vec![
+            CurveKey::new(0.0, from, CurveKeyKind::Linear),
+            CurveKey::new(1.0, to, CurveKeyKind::Linear),
+        ])
+    }
+
+    #[test]
+    fn test_retarget_two_bone_animation() {
+        let mut source_graph = Graph::new();
+        let (source_arm, source_hand) = make_skeleton(
+            &mut source_graph,
+            Vector3::new(0.0, 1.0, 0.0),
+            Default::default(),
+        );
+
+        // The target skeleton has twice longer arm and the hand is rotated in the bind pose.
+        let target_bind_rotation = UnitQuaternion::from_axis_angle(&Vector3::y_axis(), 0.5);
+        let mut target_graph = Graph::new();
+        let (target_arm, target_hand) = make_skeleton(
+            &mut target_graph,
+            Vector3::new(0.0, 2.0, 0.0),
+            target_bind_rotation,
+        );
+
+        let mut position = TrackDataContainer::new(TrackValueKind::Vector3);
+        position.curves_mut()[0] = linear_curve(0.0, 0.0);
+        position.curves_mut()[1] = linear_curve(1.0, 1.5);
+        position.curves_mut()[2] = linear_curve(0.0, 0.0);
+
+        let mut rotation = TrackDataContainer::new(TrackValueKind::UnitQuaternion);
+        rotation.curves_mut()[0] = linear_curve(0.0, 0.3);
+        rotation.curves_mut()[1] = linear_curve(0.0, 0.0);
+        rotation.curves_mut()[2] = linear_curve(0.0, 0.0);
+
+        let mut animation = Animation::default();
+        animation.add_track(Track::new(position, ValueBinding::Position).with_target(source_hand));
+        animation.add_track(Track::new(rotation, ValueBinding::Rotation).with_target(source_hand));
+        animation.add_track(Track::new_rotation().with_target(source_arm));
+
+        let retargeted = retarget_animation(
+            &animation,
+            Skeleton::new(&source_graph, source_graph.get_root()),
+            Skeleton::new(&target_graph, target_graph.get_root()),
+        );
+
+        let tracks = retargeted.tracks();
+        assert_eq!(tracks[0].target(), target_hand);
+        assert_eq!(tracks[1].target(), target_hand);
+        assert_eq!(tracks[2].target(), target_arm);
+
+        // The offset from the bind pose is scaled by the ratio of bone lengths.
+        match tracks[0].fetch(1.0).unwrap().value {
+            TrackValue::Vector3(position) => {
+                assert!((position - Vector3::new(0.0, 3.0, 0.0)).norm() < 0.0001)
+            }
+            _ => unreachable!(),
+        }
+
+        // The rotation is applied on top of the target bind pose.
+        match tracks[1].fetch(1.0).unwrap().value {
+            TrackValue::UnitQuaternion(rotation) => {
+                let expected =
+                    target_bind_rotation * UnitQuaternion::from_axis_angle(&Vector3::x_axis(), 0.3);
+                assert!(rotation.angle_to(&expected) < 0.0001);
+            }
+            _ => unreachable!(),
+        }
+    }
+}
@@ -129,7 +129,7 @@ impl Default for SpriteSheetFramesContainer {
 
 /// Sprite sheet animation is an animation based on key frames, where each key frame is packed into single image. Usually, all key
 /// frames have the same size, but this is not mandatory.
-#[derive(Visit, Reflect, Clone, Debug)]
+#[derive(Visit, Reflect, Clone, Debug, PartialEq)]
 pub struct SpriteSheetAnimation {
     #[visit(rename = "Frames")]
     frames_container: SpriteSheetFramesContainer,
@@ -5,7 +5,7 @@ use crate::core::{reflect::prelude::*, visitor::prelude::*};
 
 /// Animation signal is used as a point at which to notify external observers that animation just
 /// started to play a specific frame.
-#[derive(Visit, Reflect, Debug, Clone)]
+#[derive(Visit, Reflect, Debug, Clone, PartialEq, Eq)]
 pub struct Signal {
     /// Signal id. It should be used to distinguish different signals. For example, `JUMP` signal
     /// can have `id = 0`, while `CROUCH` signal - `id = 1`, etc.
@@ -156,6 +156,11 @@ impl Executor {
         let mut lag = 0.0;
 
         event_loop.run(move |event, window_target, control_flow| {
+            // Update window info first, so plugins and scripts will see actual values.
+            if let Event::WindowEvent { ref event, .. } = event {
+                engine.window_info.handle_event(event);
+            }
+
             engine.handle_os_event_by_plugins(&event, fixed_time_step, control_flow, &mut lag);
 
             let scenes = engine
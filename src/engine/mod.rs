@@ -5,17 +5,21 @@
 
 pub mod error;
 pub mod executor;
+pub mod window_info;
 
 use crate::material::shader::{ShaderResource, ShaderResourceExtension};
 use crate::{
     asset::{manager::ResourceManager, manager::ResourceWaitContext},
     core::{algebra::Vector2, futures::executor::block_on, instant, log::Log, pool::Handle},
-    engine::error::EngineError,
+    engine::{error::EngineError, window_info::WindowInfo},
     event::Event,
     event_loop::ControlFlow,
     gui::UserInterface,
     material::shader::{loader::ShaderLoader, Shader},
-    plugin::{Plugin, PluginConstructor, PluginContext, PluginRegistrationContext},
+    plugin::{
+        service::ServiceRegistry, Plugin, PluginConstructor, PluginContext, PluginFixedTimeStep,
+        PluginRegistrationContext,
+    },
     renderer::{framework::error::FrameworkError, Renderer},
     resource::{
         curve::{loader::CurveLoader, CurveResourceState},
@@ -24,14 +28,15 @@ use crate::{
     },
     scene::{
         base::NodeScriptMessage,
-        graph::GraphUpdateSwitches,
+        graph::{Graph, GraphUpdateSwitches},
         node::{constructor::NodeConstructorContainer, Node},
         sound::SoundEngine,
+        trigger::TriggerVolume,
         Scene, SceneContainer,
     },
     script::{
-        constructor::ScriptConstructorContainer, RoutingStrategy, Script, ScriptContext,
-        ScriptDeinitContext, ScriptMessage, ScriptMessageContext, ScriptMessageKind,
+        constructor::ScriptConstructorContainer, task::ScriptTaskPool, RoutingStrategy, Script,
+        ScriptContext, ScriptDeinitContext, ScriptMessage, ScriptMessageContext, ScriptMessageKind,
         ScriptMessageSender,
     },
     window::{Window, WindowBuilder},
@@ -183,6 +188,46 @@ impl GraphicsContext {
     }
 }
 
+/// Calls [`Plugin::on_pre_update`], then [`Plugin::on_fixed_update`] for every fixed step that fits
+/// in `context.dt` and then calls [`Plugin::update`] once.
+fn update_plugins(
+    plugins: &mut [Box<dyn Plugin>],
+    fixed_time_step: &mut PluginFixedTimeStep,
+    context: &mut PluginContext,
+    control_flow: &mut ControlFlow,
+) {
+    for plugin in plugins.iter_mut() {
+        plugin.on_pre_update(context, control_flow);
+    }
+
+    let dt = context.dt;
+
+    let (fixed_steps, step_duration) = fixed_time_step.advance_frame(dt, context.scenes);
+
+    context.dt = step_duration;
+    for _ in 0..fixed_steps {
+        for plugin in plugins.iter_mut() {
+            plugin.on_fixed_update(context, control_flow);
+        }
+    }
+    context.dt = dt;
+
+    for plugin in plugins.iter_mut() {
+        plugin.update(context, control_flow);
+    }
+}
+
+/// Calls [`Plugin::on_post_update`] of every plugin, it must be done after scripts were updated.
+fn post_update_plugins(
+    plugins: &mut [Box<dyn Plugin>],
+    context: &mut PluginContext,
+    control_flow: &mut ControlFlow,
+) {
+    for plugin in plugins.iter_mut() {
+        plugin.on_post_update(context, control_flow);
+    }
+}
+
 /// See module docs.
 pub struct Engine {
     /// Graphics context of the engine. See [`GraphicsContext`] docs for more info.
@@ -200,6 +245,18 @@ pub struct Engine {
     /// All available scenes in the engine.
     pub scenes: SceneContainer,
 
+    /// Actual parameters of the main window. See [`WindowInfo`] docs for more info.
+    pub window_info: WindowInfo,
+
+    /// Time step of [`Plugin::on_fixed_update`]. It is 60 Hz by default and it could be driven by the
+    /// physics of a scene, see [`PluginFixedTimeStep`] docs for more info.
+    pub plugins_fixed_time_step: PluginFixedTimeStep,
+
+    /// A registry of shared services, that are accessible from both plugins and scripts. Plugins should
+    /// register their services on creation, the registry is cleared when plugins are disabled. See
+    /// [`ServiceRegistry`] docs for more info.
+    pub services: ServiceRegistry,
+
     performance_statistics: PerformanceStatistics,
 
     model_events_receiver: Receiver<ResourceEvent>,
@@ -218,6 +275,9 @@ pub struct Engine {
     // Amount of time (in seconds) that passed from creation of the engine.
     elapsed_time: f32,
 
+    // Upper limit of time delta of a single update.
+    max_dt: f32,
+
     /// A special container that is able to create nodes by their type UUID. Use a copy of this
     /// value whenever you need it as a parameter in other parts of the engine.
     pub serialization_context: Arc<SerializationContext>,
@@ -225,10 +285,24 @@ pub struct Engine {
     script_processor: ScriptProcessor,
 }
 
+struct QueuedMessage {
+    payload: Box<dyn ScriptMessagePayload>,
+    kind: ScriptMessageKind,
+    // Message is removed at the end of a frame if it was already queued at the end of the previous one.
+    expiring: bool,
+}
+
 /// Performs dispatch of script messages.
+///
+/// Messages of a type that has subscribers are passed to [`crate::script::ScriptTrait::on_message`] of the
+/// subscribed scripts. Messages of any other type are put in a queue, where they could be drained by the
+/// scripts they're addressed to using [`crate::script::ScriptContext::messages`]. Queued messages, that were
+/// not drained, live for one more frame and then expire. A message is either delivered to subscribers or
+/// queued, never both.
 pub struct ScriptMessageDispatcher {
     type_groups: FxHashMap<TypeId, FxHashSet<Handle<Node>>>,
     message_receiver: Receiver<ScriptMessage>,
+    queue: FxHashMap<TypeId, Vec<QueuedMessage>>,
 }
 
 impl ScriptMessageDispatcher {
@@ -236,7 +310,59 @@ impl ScriptMessageDispatcher {
         Self {
             type_groups: Default::default(),
             message_receiver,
+            queue: Default::default(),
+        }
+    }
+
+    /// Removes every queued message of the given type `T`, that is addressed to the `receiver` node, and
+    /// returns them in the order they were sent. Keep in mind, that the messages will not be available for
+    /// other scripts after this call.
+    ///
+    /// Messages of types with subscribers are never queued, so the method returns nothing and logs a warning
+    /// if there is a subscriber for messages of the type `T`.
+    pub fn drain<T: 'static>(
+        &mut self,
+        receiver: Handle<Node>,
+        graph: &Graph,
+    ) -> impl Iterator<Item = T> {
+        let has_subscribers = self
+            .type_groups
+            .get(&TypeId::of::<T>())
+            .map_or(false, |receivers| !receivers.is_empty());
+
+        let drained = match self.queue.get_mut(&TypeId::of::<T>()) {
+            _ if has_subscribers => {
+                Log::warn(format!(
+                    "Messages of type {} are delivered to subscribers and can't be drained!",
+                    std::any::type_name::<T>()
+                ));
+                Vec::new()
+            }
+            Some(messages) => {
+                let (drained, rest): (Vec<_>, Vec<_>) = std::mem::take(messages)
+                    .into_iter()
+                    .partition(|message| message.kind.is_routed_to(receiver, graph));
+                *messages = rest;
+                drained
+            }
+            None => Vec::new(),
+        };
+
+        drained
+            .into_iter()
+            .filter_map(|message| message.payload.into_any().downcast::<T>().ok())
+            .map(|payload| *payload)
+    }
+
+    fn expire_queued_messages(&mut self) {
+        for messages in self.queue.values_mut() {
+            messages.retain_mut(|message| {
+                let alive = !message.expiring;
+                message.expiring = true;
+                alive
+            });
         }
+        self.queue.retain(|_, messages| !messages.is_empty());
     }
 
     /// Subscribes a node to receive any message of the given type `T`. Subscription is automatically removed
@@ -265,7 +391,7 @@ impl ScriptMessageDispatcher {
     }
 
     fn dispatch_messages(
-        &self,
+        &mut self,
         scene: &mut Scene,
         plugins: &mut Vec<Box<dyn Plugin>>,
         resource_manager: &ResourceManager,
@@ -275,72 +401,67 @@ impl ScriptMessageDispatcher {
     ) {
         while let Ok(message) = self.message_receiver.try_recv() {
             let mut payload = message.payload;
-            if let Some(receivers) = self.type_groups.get(&payload.deref().type_id()) {
-                match message.kind {
-                    ScriptMessageKind::Targeted(target) => {
-                        if receivers.contains(&target) {
+            let type_id = payload.deref().type_id();
+            let receivers = if let Some(receivers) = self
+                .type_groups
+                .get(&type_id)
+                .filter(|receivers| !receivers.is_empty())
+            {
+                receivers
+            } else {
+                self.queue.entry(type_id).or_default().push(QueuedMessage {
+                    payload,
+                    kind: message.kind,
+                    expiring: false,
+                });
+                continue;
+            };
+
+            match message.kind {
+                ScriptMessageKind::Targeted(target) => {
+                    if receivers.contains(&target) {
+                        let mut context = ScriptMessageContext {
+                            dt,
+                            elapsed_time,
+                            plugins,
+                            handle: target,
+                            scene,
+                            resource_manager,
+                            message_sender,
+                        };
+
+                        process_node_message(&mut context, &mut |s, ctx| {
+                            s.on_message(&mut *payload, ctx)
+                        })
+                    }
+                }
+                ScriptMessageKind::Hierarchical { root, routing } => match routing {
+                    RoutingStrategy::Up => {
+                        let mut node = root;
+                        while let Some(node_ref) = scene.graph.try_get(node) {
+                            let parent = node_ref.parent();
+
                             let mut context = ScriptMessageContext {
                                 dt,
                                 elapsed_time,
                                 plugins,
-                                handle: target,
+                                handle: node,
                                 scene,
                                 resource_manager,
                                 message_sender,
                             };
 
-                            process_node_message(&mut context, &mut |s, ctx| {
-                                s.on_message(&mut *payload, ctx)
-                            })
-                        }
-                    }
-                    ScriptMessageKind::Hierarchical { root, routing } => match routing {
-                        RoutingStrategy::Up => {
-                            let mut node = root;
-                            while let Some(node_ref) = scene.graph.try_get(node) {
-                                let parent = node_ref.parent();
-
-                                let mut context = ScriptMessageContext {
-                                    dt,
-                                    elapsed_time,
-                                    plugins,
-                                    handle: node,
-                                    scene,
-                                    resource_manager,
-                                    message_sender,
-                                };
-
-                                if receivers.contains(&node) {
-                                    process_node_message(&mut context, &mut |s, ctx| {
-                                        s.on_message(&mut *payload, ctx)
-                                    });
-                                }
-
-                                node = parent;
-                            }
-                        }
-                        RoutingStrategy::Down => {
-                            for node in scene.graph.traverse_handle_iter(root).collect::<Vec<_>>() {
-                                let mut context = ScriptMessageContext {
-                                    dt,
-                                    elapsed_time,
-                                    plugins,
-                                    handle: node,
-                                    scene,
-                                    resource_manager,
-                                    message_sender,
-                                };
-
-                                if receivers.contains(&node) {
-                                    process_node_message(&mut context, &mut |s, ctx| {
-                                        s.on_message(&mut *payload, ctx)
-                                    });
-                                }
+                            if receivers.contains(&node) {
+                                process_node_message(&mut context, &mut |s, ctx| {
+                                    s.on_message(&mut *payload, ctx)
+                                });
                             }
+
+                            node = parent;
                         }
-                    },
-                    ScriptMessageKind::Global => {
-                        for &node in receivers {
+                    }
+                    RoutingStrategy::Down => {
+                        for node in scene.graph.traverse_handle_iter(root).collect::<Vec<_>>() {
                             let mut context = ScriptMessageContext {
                                 dt,
                                 elapsed_time,
@@ -351,11 +472,30 @@ impl ScriptMessageDispatcher {
                                 message_sender,
                             };
 
-                            process_node_message(&mut context, &mut |s, ctx| {
-                                s.on_message(&mut *payload, ctx)
-                            });
+                            if receivers.contains(&node) {
+                                process_node_message(&mut context, &mut |s, ctx| {
+                                    s.on_message(&mut *payload, ctx)
+                                });
+                            }
                         }
                     }
+                },
+                ScriptMessageKind::Global => {
+                    for &node in receivers {
+                        let mut context = ScriptMessageContext {
+                            dt,
+                            elapsed_time,
+                            plugins,
+                            handle: node,
+                            scene,
+                            resource_manager,
+                            message_sender,
+                        };
+
+                        process_node_message(&mut context, &mut |s, ctx| {
+                            s.on_message(&mut *payload, ctx)
+                        });
+                    }
                 }
             }
         }
@@ -366,6 +506,17 @@ pub(crate) struct ScriptedScene {
     handle: Handle<Scene>,
     message_sender: ScriptMessageSender,
     message_dispatcher: ScriptMessageDispatcher,
+    task_pool: ScriptTaskPool,
+}
+
+/// Default upper limit of time delta of a single engine update. See [`Engine::set_max_dt`] docs for
+/// more info.
+pub const DEFAULT_MAX_DT: f32 = 0.1;
+
+/// A frame spike (i.e. after loading a level or when the window was dragged) must not be passed to
+/// scenes, plugins and scripts as is, otherwise they could move objects too far in one frame.
+fn clamp_dt(dt: f32, max_dt: f32) -> f32 {
+    dt.max(0.0).min(max_dt)
 }
 
 #[derive(Default)]
@@ -393,6 +544,7 @@ impl ScriptProcessor {
             handle: scene,
             message_sender: ScriptMessageSender { sender: tx },
             message_dispatcher: ScriptMessageDispatcher::new(rx),
+            task_pool: Default::default(),
         });
 
         let graph = &mut scenes[scene].graph;
@@ -415,6 +567,8 @@ impl ScriptProcessor {
         scenes: &mut SceneContainer,
         plugins: &mut Vec<Box<dyn Plugin>>,
         resource_manager: &ResourceManager,
+        window_info: &WindowInfo,
+        services: &mut ServiceRegistry,
         dt: f32,
         elapsed_time: f32,
     ) {
@@ -436,6 +590,26 @@ impl ScriptProcessor {
                 continue 'scene_loop;
             }
 
+            // Scripts must run at the same pace as the scene itself.
+            let dt = dt * scene.time_scale();
+
+            // Fixed updates are aligned with the physics steps, that were performed by the scene on
+            // this frame.
+            let time_step = &scene.graph.physics_time_step;
+            let (fixed_steps, fixed_dt) = if time_step.enabled {
+                (time_step.last_step_count(), time_step.step_duration())
+            } else {
+                (1, dt)
+            };
+
+            // Deliver events of trigger volumes, they will be dispatched along with the other script
+            // messages.
+            for (handle, node) in scene.graph.pair_iter() {
+                if let Some(volume) = node.cast::<TriggerVolume>() {
+                    volume.send_events(handle, &scripted_scene.message_sender);
+                }
+            }
+
             // Fill in initial handles to nodes to update.
             let mut update_queue = VecDeque::new();
             for (handle, node) in scene.graph.pair_iter() {
@@ -455,13 +629,17 @@ impl ScriptProcessor {
             'update_loop: for update_loop_iteration in 0..max_iterations {
                 let mut context = ScriptContext {
                     dt,
+                    dt_f64: dt as f64,
                     elapsed_time,
                     plugins,
                     handle: Default::default(),
                     scene,
                     resource_manager,
+                    window_info,
                     message_sender: &scripted_scene.message_sender,
                     message_dispatcher: &mut scripted_scene.message_dispatcher,
+                    task_pool: &mut scripted_scene.task_pool,
+                    services,
                 };
 
                 'init_loop: for init_loop_iteration in 0..max_iterations {
@@ -528,6 +706,14 @@ impl ScriptProcessor {
                         context.handle = handle;
 
                         process_node(&mut context, &mut |script, context| {
+                            context.dt = fixed_dt;
+                            context.dt_f64 = fixed_dt as f64;
+                            for _ in 0..fixed_steps {
+                                script.on_fixed_update(context);
+                            }
+                            context.dt = dt;
+                            context.dt_f64 = dt as f64;
+
                             script.on_update(context);
                         });
                     }
@@ -553,6 +739,14 @@ impl ScriptProcessor {
                 }
             }
 
+            // Queued messages that were not drained on this frame will expire on the next one.
+            scripted_scene.message_dispatcher.expire_queued_messages();
+
+            // Resume tasks after every script was updated, so they'll see the most recent state.
+            scripted_scene
+                .task_pool
+                .update(&scene.graph, dt, elapsed_time);
+
             // As the last step, destroy queued scripts.
             let mut context = ScriptDeinitContext {
                 elapsed_time,
@@ -758,8 +952,11 @@ pub(crate) fn process_scripts<T>(
     scene: &mut Scene,
     plugins: &mut [Box<dyn Plugin>],
     resource_manager: &ResourceManager,
+    window_info: &WindowInfo,
+    services: &mut ServiceRegistry,
     message_sender: &ScriptMessageSender,
     message_dispatcher: &mut ScriptMessageDispatcher,
+    task_pool: &mut ScriptTaskPool,
     dt: f32,
     elapsed_time: f32,
     mut func: T,
@@ -768,13 +965,17 @@ pub(crate) fn process_scripts<T>(
 {
     let mut context = ScriptContext {
         dt,
+        dt_f64: dt as f64,
         elapsed_time,
         plugins,
         handle: Default::default(),
         scene,
         resource_manager,
+        window_info,
         message_sender,
         message_dispatcher,
+        task_pool,
+        services,
     };
 
     for node_index in 0..context.scene.graph.capacity() {
@@ -883,9 +1084,13 @@ impl Engine {
             plugins: Default::default(),
             serialization_context,
             script_processor: Default::default(),
+            window_info: Default::default(),
+            plugins_fixed_time_step: Default::default(),
             plugins_enabled: false,
+            services: Default::default(),
             plugin_constructors: Default::default(),
             elapsed_time: 0.0,
+            max_dt: DEFAULT_MAX_DT,
         })
     }
 
@@ -1024,6 +1229,8 @@ impl Engine {
                 (window, glow::Context::from_webgl2_context(webgl2_context))
             };
 
+            self.window_info = WindowInfo::from_window(&window);
+
             self.user_interface.set_screen_size(Vector2::new(
                 window.inner_size().width as f32,
                 window.inner_size().height as f32,
@@ -1110,6 +1317,8 @@ impl Engine {
     /// Adjust size of the frame to be rendered. Must be called after the window size changes.
     /// Will update the renderer and GL context frame size.
     pub fn set_frame_size(&mut self, new_size: (u32, u32)) -> Result<(), FrameworkError> {
+        self.window_info.size = Vector2::new(new_size.0 as f32, new_size.1 as f32);
+
         if let GraphicsContext::Initialized(ctx) = &mut self.graphics_context {
             ctx.renderer.set_frame_size(new_size)?;
 
@@ -1180,29 +1389,48 @@ impl Engine {
             let inner_size = ctx.window.inner_size();
             let window_size = Vector2::new(inner_size.width as f32, inner_size.height as f32);
 
-            self.resource_manager.state().update(dt);
             ctx.renderer.update_caches(dt);
-            self.handle_model_events();
 
-            for (handle, scene) in self.scenes.pair_iter_mut().filter(|(_, s)| s.enabled) {
-                let frame_size = scene.render_target.as_ref().map_or(window_size, |rt| {
-                    if let TextureKind::Rectangle { width, height } = rt.data_ref().kind() {
-                        Vector2::new(width as f32, height as f32)
-                    } else {
-                        panic!("only rectangle textures can be used as render target!");
-                    }
-                });
+            self.update_frame(dt, window_size, control_flow, lag, switches);
+        }
+    }
 
-                scene.update(
-                    frame_size,
-                    dt,
-                    switches.get(&handle).cloned().unwrap_or_default(),
-                );
-            }
+    // Performs every step of `pre_update`, that does not need graphics context, in the following order:
+    // resources, scenes (including physics), plugins (`on_pre_update`, `on_fixed_update`, `update` and
+    // UI messages), scripts and then `on_post_update` of plugins. Time delta is clamped to `max_dt`
+    // first, so every step sees the same value.
+    fn update_frame(
+        &mut self,
+        dt: f32,
+        window_size: Vector2<f32>,
+        control_flow: &mut ControlFlow,
+        lag: &mut f32,
+        switches: FxHashMap<Handle<Scene>, GraphUpdateSwitches>,
+    ) {
+        let dt = clamp_dt(dt, self.max_dt);
+
+        self.resource_manager.state().update(dt);
+        self.handle_model_events();
+
+        for (handle, scene) in self.scenes.pair_iter_mut().filter(|(_, s)| s.enabled) {
+            let frame_size = scene.render_target.as_ref().map_or(window_size, |rt| {
+                if let TextureKind::Rectangle { width, height } = rt.data_ref().kind() {
+                    Vector2::new(width as f32, height as f32)
+                } else {
+                    panic!("only rectangle textures can be used as render target!");
+                }
+            });
 
-            self.update_plugins(dt, control_flow, lag);
-            self.handle_scripts(dt);
+            scene.update(
+                frame_size,
+                dt,
+                switches.get(&handle).cloned().unwrap_or_default(),
+            );
         }
+
+        self.update_plugins(dt, control_flow, lag);
+        self.handle_scripts(dt);
+        self.post_update_plugins(dt, control_flow, lag);
     }
 
     /// Performs post update for the engine.
@@ -1221,6 +1449,21 @@ impl Engine {
         }
     }
 
+    /// Sets the upper limit of time delta (in seconds) of a single [`Self::pre_update`] call. A long
+    /// frame (a hitch caused by level loading, a window being dragged, a debugger breakpoint, etc.)
+    /// is clamped to this value once, before scenes (including physics), plugins and scripts are
+    /// updated, so gameplay code won't move objects too far or apply huge impulses in a single
+    /// update. Default value is [`DEFAULT_MAX_DT`].
+    pub fn set_max_dt(&mut self, max_dt: f32) {
+        self.max_dt = max_dt.max(0.0);
+    }
+
+    /// Returns the upper limit of time delta of a single update. See [`Self::set_max_dt`] docs for
+    /// more info.
+    pub fn max_dt(&self) -> f32 {
+        self.max_dt
+    }
+
     /// Returns true if the scene is registered for script processing.
     pub fn has_scripted_scene(&self, scene: Handle<Scene>) -> bool {
         self.script_processor.has_scripted_scene(scene)
@@ -1241,6 +1484,8 @@ impl Engine {
             &mut self.scenes,
             &mut self.plugins,
             &self.resource_manager,
+            &self.window_info,
+            &mut self.services,
             dt,
             self.elapsed_time,
         );
@@ -1260,11 +1505,16 @@ impl Engine {
                 user_interface: &mut self.user_interface,
                 serialization_context: &self.serialization_context,
                 performance_statistics: &self.performance_statistics,
+                window_info: &self.window_info,
+                services: &mut self.services,
             };
 
-            for plugin in self.plugins.iter_mut() {
-                plugin.update(&mut context, control_flow);
-            }
+            update_plugins(
+                &mut self.plugins,
+                &mut self.plugins_fixed_time_step,
+                &mut context,
+                control_flow,
+            );
 
             while let Some(message) = self.user_interface.poll_message() {
                 let mut context = PluginContext {
@@ -1276,6 +1526,8 @@ impl Engine {
                     user_interface: &mut self.user_interface,
                     serialization_context: &self.serialization_context,
                     performance_statistics: &self.performance_statistics,
+                    window_info: &self.window_info,
+                    services: &mut self.services,
                 };
 
                 for plugin in self.plugins.iter_mut() {
@@ -1287,6 +1539,31 @@ impl Engine {
         self.performance_statistics.plugins_time = instant::Instant::now() - time;
     }
 
+    fn post_update_plugins(&mut self, dt: f32, control_flow: &mut ControlFlow, lag: &mut f32) {
+        let time = instant::Instant::now();
+
+        if self.plugins_enabled {
+            post_update_plugins(
+                &mut self.plugins,
+                &mut PluginContext {
+                    scenes: &mut self.scenes,
+                    resource_manager: &self.resource_manager,
+                    graphics_context: &mut self.graphics_context,
+                    dt,
+                    lag,
+                    user_interface: &mut self.user_interface,
+                    serialization_context: &self.serialization_context,
+                    performance_statistics: &self.performance_statistics,
+                    window_info: &self.window_info,
+                    services: &mut self.services,
+                },
+                control_flow,
+            );
+        }
+
+        self.performance_statistics.plugins_time += instant::Instant::now() - time;
+    }
+
     pub(crate) fn handle_os_event_by_plugins(
         &mut self,
         event: &Event<()>,
@@ -1307,6 +1584,8 @@ impl Engine {
                         user_interface: &mut self.user_interface,
                         serialization_context: &self.serialization_context,
                         performance_statistics: &self.performance_statistics,
+                        window_info: &self.window_info,
+                        services: &mut self.services,
                     },
                     control_flow,
                 );
@@ -1332,6 +1611,8 @@ impl Engine {
                         user_interface: &mut self.user_interface,
                         serialization_context: &self.serialization_context,
                         performance_statistics: &self.performance_statistics,
+                        window_info: &self.window_info,
+                        services: &mut self.services,
                     },
                     control_flow,
                 );
@@ -1357,6 +1638,8 @@ impl Engine {
                         user_interface: &mut self.user_interface,
                         serialization_context: &self.serialization_context,
                         performance_statistics: &self.performance_statistics,
+                        window_info: &self.window_info,
+                        services: &mut self.services,
                     },
                     control_flow,
                 );
@@ -1382,6 +1665,8 @@ impl Engine {
                         user_interface: &mut self.user_interface,
                         serialization_context: &self.serialization_context,
                         performance_statistics: &self.performance_statistics,
+                        window_info: &self.window_info,
+                        services: &mut self.services,
                     },
                     control_flow,
                 );
@@ -1414,8 +1699,11 @@ impl Engine {
                     scene,
                     &mut self.plugins,
                     &self.resource_manager,
+                    &self.window_info,
+                    &mut self.services,
                     &scripted_scene.message_sender,
                     &mut scripted_scene.message_dispatcher,
+                    &mut scripted_scene.task_pool,
                     dt,
                     self.elapsed_time,
                     |script, context| {
@@ -1504,6 +1792,8 @@ impl Engine {
                             user_interface: &mut self.user_interface,
                             serialization_context: &self.serialization_context,
                             performance_statistics: &self.performance_statistics,
+                            window_info: &self.window_info,
+                            services: &mut self.services,
                         },
                     ));
                 }
@@ -1521,13 +1811,19 @@ impl Engine {
                         user_interface: &mut self.user_interface,
                         serialization_context: &self.serialization_context,
                         performance_statistics: &self.performance_statistics,
+                        window_info: &self.window_info,
+                        services: &mut self.services,
                     });
                 }
+
+                // Services could hold some state of the plugins, that must not outlive them.
+                self.services.clear();
             }
         }
     }
 
-    /// Adds new plugin plugin constructor.
+    /// Adds new plugin plugin constructor. Scripts of already loaded scenes, whose types were unknown at the
+    /// moment of loading, will be restored if the plugin registers their types.
     pub fn add_plugin_constructor<P>(&mut self, constructor: P)
     where
         P: PluginConstructor + 'static,
@@ -1536,8 +1832,46 @@ impl Engine {
             serialization_context: &self.serialization_context,
         });
 
+        for scene in self.scenes.iter_mut() {
+            let count = scene.graph.resolve_scripts(&self.serialization_context);
+            if count > 0 {
+                Log::info(format!(
+                    "{} previously unknown script(s) were restored.",
+                    count
+                ));
+            }
+        }
+
         self.plugin_constructors.push(Box::new(constructor));
     }
+
+    /// Replaces every registered script type with a new version and keeps the state of live scripts.
+    /// It is meant to be used when the code of scripts is reloaded (for example, when a game plugin
+    /// was recompiled): `register` must register new versions of the scripts in the given
+    /// serialization context. The state of every script instance is captured before old types are
+    /// unregistered and restored into an instance of the new type with the same type uuid, see
+    /// [`crate::scene::graph::Graph::restore_script_states`] for details. Fields, that were removed
+    /// or changed their types, get default values. Restored scripts will be initialized again.
+    pub fn reload_scripts<F>(&mut self, register: F)
+    where
+        F: FnOnce(&SerializationContext),
+    {
+        let states = self
+            .scenes
+            .pair_iter_mut()
+            .map(|(handle, scene)| (handle, scene.graph.capture_script_states()))
+            .collect::<Vec<_>>();
+
+        // Old versions of the scripts must not be used anymore.
+        self.serialization_context.script_constructors.map().clear();
+        register(&self.serialization_context);
+
+        for (handle, states) in states {
+            self.scenes[handle]
+                .graph
+                .restore_script_states(states, &self.serialization_context.script_constructors);
+        }
+    }
 }
 
 impl Drop for Engine {
@@ -1564,17 +1898,45 @@ impl Drop for Engine {
 mod test {
     use crate::{
         asset::manager::ResourceManager,
-        core::{pool::Handle, reflect::prelude::*, uuid::Uuid, visitor::prelude::*},
-        engine::ScriptProcessor,
+        core::{
+            algebra::{Vector2, Vector3},
+            pool::Handle,
+            reflect::prelude::*,
+            uuid::{uuid, Uuid},
+            visitor::prelude::*,
+            TypeUuidProvider,
+        },
+        engine::{
+            update_plugins, Engine, EngineInitParams, GraphicsContext, PerformanceStatistics,
+            ScriptMessageDispatcher, ScriptProcessor, SerializationContext, DEFAULT_MAX_DT,
+        },
+        event_loop::ControlFlow,
+        gui::UserInterface,
         impl_component_provider,
-        scene::{base::BaseBuilder, node::Node, pivot::PivotBuilder, Scene, SceneContainer},
+        plugin::{
+            service::ServiceRegistry, Plugin, PluginConstructor, PluginContext, PluginFixedTimeStep,
+        },
+        scene::{
+            base::BaseBuilder,
+            collider::{ColliderBuilder, ColliderShape},
+            graph::{fixed_step::FixedTimeStep, Graph},
+            node::Node,
+            pivot::PivotBuilder,
+            rigidbody::{RigidBodyBuilder, RigidBodyType},
+            transform::TransformBuilder,
+            trigger::{TriggerShape, TriggerVolumeBuilder},
+            Scene, SceneContainer,
+        },
         script::{
-            Script, ScriptContext, ScriptDeinitContext, ScriptMessageContext, ScriptMessagePayload,
-            ScriptTrait,
+            RayHit, Script, ScriptContext, ScriptDeinitContext, ScriptMessageContext,
+            ScriptMessagePayload, ScriptTrait,
         },
     };
 
-    use std::sync::mpsc::{self, Sender, TryRecvError};
+    use std::sync::{
+        mpsc::{self, Sender, TryRecvError},
+        Arc,
+    };
 
     #[derive(PartialEq, Eq, Clone, Debug)]
     enum Event {
@@ -1710,6 +2072,8 @@ mod test {
                 &mut scene_container,
                 &mut Default::default(),
                 &resource_manager,
+                &Default::default(),
+                &mut Default::default(),
                 0.0,
                 0.0,
             );
@@ -1877,6 +2241,8 @@ mod test {
                 &mut scene_container,
                 &mut Default::default(),
                 &resource_manager,
+                &Default::default(),
+                &mut Default::default(),
                 0.0,
                 0.0,
             );
@@ -1894,4 +2260,1260 @@ mod test {
             }
         }
     }
+
+    struct Hit(u32);
+
+    struct Miss;
+
+    #[derive(Debug, Clone, Reflect, Visit)]
+    struct ScriptPostingHits {
+        index: u32,
+    }
+
+    impl_component_provider!(ScriptPostingHits);
+
+    impl ScriptTrait for ScriptPostingHits {
+        fn on_update(&mut self, ctx: &mut ScriptContext) {
+            if self.index == 0 {
+                ctx.message_sender.send_global(Hit(1));
+                ctx.message_sender.send_to_target(ctx.handle, Hit(2));
+                ctx.message_sender.send_global(Hit(3));
+                ctx.message_sender.send_global(Miss);
+            }
+            self.index += 1;
+        }
+
+        fn id(&self) -> Uuid {
+            Uuid::new_v4()
+        }
+    }
+
+    #[derive(Debug, Clone, Reflect, Visit)]
+    struct ScriptDrainingHits {
+        index: u32,
+        #[reflect(hidden)]
+        #[visit(skip)]
+        sender: Sender<(u32, Vec<u32>, usize)>,
+    }
+
+    impl_component_provider!(ScriptDrainingHits);
+
+    impl ScriptTrait for ScriptDrainingHits {
+        fn on_update(&mut self, ctx: &mut ScriptContext) {
+            let hits = ctx.messages::<Hit>().map(|hit| hit.0).collect::<Vec<_>>();
+            // Misses are drained only on the third frame, when they should be already expired.
+            let misses = if self.index == 2 {
+                ctx.messages::<Miss>().count()
+            } else {
+                0
+            };
+            self.sender.send((self.index, hits, misses)).unwrap();
+            self.index += 1;
+        }
+
+        fn id(&self) -> Uuid {
+            Uuid::new_v4()
+        }
+    }
+
+    #[test]
+    fn test_drain_messages() {
+        let resource_manager = ResourceManager::new();
+        let mut scene = Scene::new();
+
+        let (tx, rx) = mpsc::channel();
+
+        PivotBuilder::new(
+            BaseBuilder::new().with_script(Script::new(ScriptPostingHits { index: 0 })),
+        )
+        .build(&mut scene.graph);
+
+        PivotBuilder::new(
+            BaseBuilder::new().with_script(Script::new(ScriptDrainingHits {
+                index: 0,
+                sender: tx,
+            })),
+        )
+        .build(&mut scene.graph);
+
+        let mut scene_container = SceneContainer::new(Default::default());
+
+        let scene_handle = scene_container.add(scene);
+
+        let mut script_processor = ScriptProcessor::default();
+
+        script_processor.register_scripted_scene(
+            scene_handle,
+            &mut scene_container,
+            &resource_manager,
+        );
+
+        for _ in 0..3 {
+            script_processor.handle_scripts(
+                &mut scene_container,
+                &mut Default::default(),
+                &resource_manager,
+                &Default::default(),
+                &mut Default::default(),
+                0.0,
+                0.0,
+            );
+        }
+
+        // Messages are queued after they were sent, so they're available on the next frame. `Hit(2)` is
+        // targeted to the node of the other script, so it can't be drained here.
+        assert_eq!(rx.try_recv(), Ok((0, vec![], 0)));
+        assert_eq!(rx.try_recv(), Ok((1, vec![1, 3], 0)));
+        assert_eq!(rx.try_recv(), Ok((2, vec![], 0)));
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+    }
+
+    #[test]
+    fn test_drain_of_subscribed_type_is_empty() {
+        let mut graph = Graph::new();
+        let node = PivotBuilder::new(BaseBuilder::new()).build(&mut graph);
+
+        let (_sender, receiver) = mpsc::channel();
+        let mut dispatcher = ScriptMessageDispatcher::new(receiver);
+        dispatcher.subscribe_to::<Hit>(node);
+
+        // Must not panic, the messages are delivered to the subscribers instead.
+        assert_eq!(dispatcher.drain::<Hit>(node, &graph).count(), 0);
+    }
+
+    #[test]
+    fn test_targeted_message_is_drained_only_by_target() {
+        let resource_manager = ResourceManager::new();
+        let mut scene = Scene::new();
+
+        let (other_tx, other_rx) = mpsc::channel();
+        let (target_tx, target_rx) = mpsc::channel();
+
+        // The other script is updated first, so it gets a chance to steal the message.
+        PivotBuilder::new(
+            BaseBuilder::new().with_script(Script::new(ScriptDrainingHits {
+                index: 0,
+                sender: other_tx,
+            })),
+        )
+        .build(&mut scene.graph);
+
+        let target = PivotBuilder::new(BaseBuilder::new().with_script(Script::new(
+            ScriptDrainingHits {
+                index: 0,
+                sender: target_tx,
+            },
+        )))
+        .build(&mut scene.graph);
+
+        let mut scene_container = SceneContainer::new(Default::default());
+
+        let scene_handle = scene_container.add(scene);
+
+        let mut script_processor = ScriptProcessor::default();
+
+        script_processor.register_scripted_scene(
+            scene_handle,
+            &mut scene_container,
+            &resource_manager,
+        );
+
+        script_processor.scripted_scenes[0]
+            .message_sender
+            .send_to_target(target, Hit(7));
+
+        for _ in 0..2 {
+            script_processor.handle_scripts(
+                &mut scene_container,
+                &mut Default::default(),
+                &resource_manager,
+                &Default::default(),
+                &mut Default::default(),
+                0.0,
+                0.0,
+            );
+        }
+
+        assert_eq!(other_rx.try_recv(), Ok((0, vec![], 0)));
+        assert_eq!(other_rx.try_recv(), Ok((1, vec![], 0)));
+        assert_eq!(target_rx.try_recv(), Ok((0, vec![], 0)));
+        assert_eq!(target_rx.try_recv(), Ok((1, vec![7], 0)));
+    }
+
+    #[derive(Visit, Default, Debug, Clone)]
+    struct GameState {
+        score: u32,
+    }
+
+    impl TypeUuidProvider for GameState {
+        fn type_uuid() -> Uuid {
+            uuid!("1f4c6a52-0d3e-4a8f-b0c2-7e9d5a3b2c10")
+        }
+    }
+
+    #[derive(Debug, Clone, Reflect, Visit)]
+    struct ScoreWriter;
+
+    impl_component_provider!(ScoreWriter);
+
+    impl ScriptTrait for ScoreWriter {
+        fn on_update(&mut self, ctx: &mut ScriptContext) {
+            ctx.scene_data::<GameState>().score += 10;
+        }
+
+        fn id(&self) -> Uuid {
+            Uuid::new_v4()
+        }
+    }
+
+    #[derive(Debug, Clone, Reflect, Visit)]
+    struct ScoreReader {
+        #[reflect(hidden)]
+        #[visit(skip)]
+        sender: Sender<u32>,
+    }
+
+    impl_component_provider!(ScoreReader);
+
+    impl ScriptTrait for ScoreReader {
+        fn on_update(&mut self, ctx: &mut ScriptContext) {
+            self.sender
+                .send(ctx.scene_data::<GameState>().score)
+                .unwrap();
+        }
+
+        fn id(&self) -> Uuid {
+            Uuid::new_v4()
+        }
+    }
+
+    #[test]
+    fn test_scene_data_is_shared_between_scripts() {
+        let resource_manager = ResourceManager::new();
+        let mut scene = Scene::new();
+
+        let (tx, rx) = mpsc::channel();
+
+        PivotBuilder::new(BaseBuilder::new().with_script(Script::new(ScoreWriter)))
+            .build(&mut scene.graph);
+        PivotBuilder::new(BaseBuilder::new().with_script(Script::new(ScoreReader { sender: tx })))
+            .build(&mut scene.graph);
+
+        let mut scene_container = SceneContainer::new(Default::default());
+
+        let scene_handle = scene_container.add(scene);
+
+        let mut script_processor = ScriptProcessor::default();
+
+        script_processor.register_scripted_scene(
+            scene_handle,
+            &mut scene_container,
+            &resource_manager,
+        );
+
+        for iteration in 1..=2 {
+            script_processor.handle_scripts(
+                &mut scene_container,
+                &mut Default::default(),
+                &resource_manager,
+                &Default::default(),
+                &mut Default::default(),
+                0.0,
+                0.0,
+            );
+
+            // The reader must see the value written by the writer in the same frame.
+            assert_eq!(rx.try_recv(), Ok(iteration * 10));
+            assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+        }
+
+        assert_eq!(
+            scene_container[scene_handle]
+                .blackboard
+                .get::<GameState>()
+                .unwrap()
+                .score,
+            20
+        );
+    }
+
+    #[derive(Debug, Clone, Reflect, Visit)]
+    struct ScriptWithTask {
+        frames: usize,
+        #[reflect(hidden)]
+        #[visit(skip)]
+        sender: Sender<usize>,
+    }
+
+    impl_component_provider!(ScriptWithTask);
+
+    impl ScriptTrait for ScriptWithTask {
+        fn on_start(&mut self, ctx: &mut ScriptContext) {
+            let frames = self.frames;
+            let sender = self.sender.clone();
+            ctx.spawn_task(move |task| async move {
+                for _ in 0..frames {
+                    task.next_frame().await;
+                }
+                sender.send(frames).unwrap();
+            });
+        }
+
+        fn id(&self) -> Uuid {
+            Uuid::new_v4()
+        }
+    }
+
+    #[test]
+    fn test_script_tasks() {
+        let resource_manager = ResourceManager::new();
+        let mut scene = Scene::new();
+
+        let (tx, rx) = mpsc::channel();
+        let (cancelled_tx, cancelled_rx) = mpsc::channel();
+
+        PivotBuilder::new(BaseBuilder::new().with_script(Script::new(ScriptWithTask {
+            frames: 3,
+            sender: tx,
+        })))
+        .build(&mut scene.graph);
+
+        let doomed =
+            PivotBuilder::new(BaseBuilder::new().with_script(Script::new(ScriptWithTask {
+                frames: 5,
+                sender: cancelled_tx,
+            })))
+            .build(&mut scene.graph);
+
+        let mut scene_container = SceneContainer::new(Default::default());
+
+        let scene_handle = scene_container.add(scene);
+
+        let mut script_processor = ScriptProcessor::default();
+
+        script_processor.register_scripted_scene(
+            scene_handle,
+            &mut scene_container,
+            &resource_manager,
+        );
+
+        for frame in 0..10 {
+            script_processor.handle_scripts(
+                &mut scene_container,
+                &mut Default::default(),
+                &resource_manager,
+                &Default::default(),
+                &mut Default::default(),
+                0.0,
+                0.0,
+            );
+
+            // The task is started on the first frame and then resumed once per frame.
+            if frame == 3 {
+                assert_eq!(rx.try_recv(), Ok(3));
+            } else {
+                assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+            }
+
+            if frame == 1 {
+                scene_container[scene_handle].graph.remove_node(doomed);
+            }
+        }
+
+        // The task of the removed node must be cancelled (dropped with its sender) and must never finish.
+        assert_eq!(cancelled_rx.try_recv(), Err(TryRecvError::Disconnected));
+        assert!(script_processor.scripted_scenes[0].task_pool.is_empty());
+    }
+
+    #[derive(Debug, Clone, Reflect, Visit)]
+    struct DtRecorder {
+        #[reflect(hidden)]
+        #[visit(skip)]
+        sender: Sender<(f32, f64)>,
+    }
+
+    impl_component_provider!(DtRecorder);
+
+    impl ScriptTrait for DtRecorder {
+        fn on_update(&mut self, ctx: &mut ScriptContext) {
+            self.sender.send((ctx.dt, ctx.dt_f64)).unwrap();
+        }
+
+        fn id(&self) -> Uuid {
+            Uuid::new_v4()
+        }
+    }
+
+    #[test]
+    fn test_dt_is_clamped() {
+        let mut engine = Engine::new(EngineInitParams {
+            graphics_context_params: Default::default(),
+            serialization_context: Arc::new(SerializationContext::new()),
+            resource_manager: ResourceManager::new(),
+        })
+        .unwrap();
+
+        let (tx, rx) = mpsc::channel();
+
+        let mut scene = Scene::new();
+        PivotBuilder::new(BaseBuilder::new().with_script(Script::new(DtRecorder { sender: tx })))
+            .build(&mut scene.graph);
+        let scene = engine.scenes.add(scene);
+        engine.register_scripted_scene(scene);
+
+        let mut update = |engine: &mut Engine, dt: f32| {
+            // The same sequence as in `Engine::pre_update`.
+            engine.update_frame(
+                dt,
+                Vector2::new(800.0, 600.0),
+                &mut ControlFlow::Poll,
+                &mut 0.0,
+                Default::default(),
+            );
+            rx.try_recv().unwrap()
+        };
+
+        // Normal frame is passed as is.
+        assert_eq!(update(&mut engine, 0.02), (0.02, 0.02f32 as f64));
+
+        // Simulate a 2 seconds hitch.
+        assert_eq!(
+            update(&mut engine, 2.0),
+            (DEFAULT_MAX_DT, DEFAULT_MAX_DT as f64)
+        );
+
+        engine.set_max_dt(0.05);
+        assert_eq!(update(&mut engine, 2.0), (0.05, 0.05f32 as f64));
+    }
+
+    #[derive(Debug, Clone, Default, Reflect, Visit)]
+    struct Timer {
+        timer: f32,
+    }
+
+    impl_component_provider!(Timer);
+
+    impl ScriptTrait for Timer {
+        fn on_update(&mut self, ctx: &mut ScriptContext) {
+            self.timer += ctx.dt;
+        }
+
+        fn id(&self) -> Uuid {
+            Uuid::new_v4()
+        }
+    }
+
+    #[test]
+    fn test_time_scale() {
+        let resource_manager = ResourceManager::new();
+        let mut scene = Scene::new();
+        assert_eq!(scene.time_scale(), 1.0);
+
+        let node = PivotBuilder::new(BaseBuilder::new().with_script(Script::new(Timer::default())))
+            .build(&mut scene.graph);
+
+        let mut scene_container = SceneContainer::new(Default::default());
+        let scene_handle = scene_container.add(scene);
+
+        let mut script_processor = ScriptProcessor::default();
+        script_processor.register_scripted_scene(
+            scene_handle,
+            &mut scene_container,
+            &resource_manager,
+        );
+
+        let dt = 0.02;
+        let mut timer_advance = |scene_container: &mut SceneContainer, frames: usize| {
+            let timer = |scene_container: &SceneContainer| {
+                scene_container[scene_handle].graph[node]
+                    .script()
+                    .unwrap()
+                    .cast::<Timer>()
+                    .unwrap()
+                    .timer
+            };
+
+            let start = timer(scene_container);
+            for _ in 0..frames {
+                script_processor.handle_scripts(
+                    scene_container,
+                    &mut Default::default(),
+                    &resource_manager,
+                    &Default::default(),
+                    &mut Default::default(),
+                    dt,
+                    0.0,
+                );
+            }
+            timer(scene_container) - start
+        };
+
+        // Initialization frame.
+        timer_advance(&mut scene_container, 1);
+
+        let normal = timer_advance(&mut scene_container, 10);
+        assert!((normal - 10.0 * dt).abs() < 1.0e-5);
+
+        // Slow motion.
+        assert_eq!(scene_container[scene_handle].set_time_scale(0.5), 1.0);
+        let slow = timer_advance(&mut scene_container, 10);
+        assert!((slow - normal * 0.5).abs() < 1.0e-5);
+
+        // Pause.
+        scene_container[scene_handle].set_time_scale(0.0);
+        assert_eq!(timer_advance(&mut scene_container, 10), 0.0);
+
+        // Negative scale is not allowed.
+        scene_container[scene_handle].set_time_scale(-1.0);
+        assert_eq!(scene_container[scene_handle].time_scale(), 0.0);
+    }
+
+    #[derive(Default)]
+    struct UpdateCounter {
+        fixed_updates: usize,
+        updates: usize,
+        fixed_dt: f32,
+    }
+
+    impl Plugin for UpdateCounter {
+        fn update(&mut self, _context: &mut PluginContext, _control_flow: &mut ControlFlow) {
+            self.updates += 1;
+        }
+
+        fn on_fixed_update(
+            &mut self,
+            context: &mut PluginContext,
+            _control_flow: &mut ControlFlow,
+        ) {
+            self.fixed_updates += 1;
+            self.fixed_dt = context.dt;
+        }
+    }
+
+    fn simulate_plugin(
+        fixed_time_step: &mut PluginFixedTimeStep,
+        frames: usize,
+        dt: f32,
+    ) -> (usize, usize, f32) {
+        let mut plugins: Vec<Box<dyn Plugin>> = vec![Box::new(UpdateCounter::default())];
+
+        let mut scenes = SceneContainer::new(Default::default());
+        let resource_manager = ResourceManager::new();
+        let mut user_interface = UserInterface::new(Default::default());
+        let mut graphics_context = GraphicsContext::Uninitialized(Default::default());
+        let serialization_context = Arc::new(SerializationContext::new());
+        let performance_statistics = PerformanceStatistics::default();
+        let window_info = Default::default();
+        let mut services = ServiceRegistry::default();
+        let mut lag = 0.0;
+        let mut control_flow = ControlFlow::Poll;
+
+        for _ in 0..frames {
+            let mut context = PluginContext {
+                scenes: &mut scenes,
+                resource_manager: &resource_manager,
+                user_interface: &mut user_interface,
+                graphics_context: &mut graphics_context,
+                dt,
+                lag: &mut lag,
+                serialization_context: &serialization_context,
+                performance_statistics: &performance_statistics,
+                window_info: &window_info,
+                services: &mut services,
+            };
+
+            update_plugins(
+                &mut plugins,
+                fixed_time_step,
+                &mut context,
+                &mut control_flow,
+            );
+        }
+
+        let counter = plugins[0].cast::<UpdateCounter>().unwrap();
+        (counter.updates, counter.fixed_updates, counter.fixed_dt)
+    }
+
+    #[test]
+    fn test_plugin_fixed_update() {
+        // Default rate is 60 Hz - two fixed updates per update at 30 FPS.
+        let (updates, fixed_updates, fixed_dt) =
+            simulate_plugin(&mut PluginFixedTimeStep::default(), 10, 1.0 / 30.0);
+        assert_eq!(updates, 10);
+        assert_eq!(fixed_updates, 20);
+        assert_eq!(fixed_dt, 1.0 / 60.0);
+
+        // One second of rendering at 144 FPS with 30 Hz fixed step.
+        let (updates, fixed_updates, fixed_dt) =
+            simulate_plugin(&mut PluginFixedTimeStep::new(30.0), 144, 1.0 / 144.0);
+        assert_eq!(updates, 144);
+        assert_eq!(fixed_updates, 30);
+        assert_eq!(fixed_dt, 1.0 / 30.0);
+    }
+
+    #[test]
+    fn test_plugin_fixed_update_driven_by_scene_physics() {
+        let mut engine = Engine::new(EngineInitParams {
+            graphics_context_params: Default::default(),
+            serialization_context: Arc::new(SerializationContext::new()),
+            resource_manager: ResourceManager::new(),
+        })
+        .unwrap();
+
+        engine.plugins.push(Box::new(UpdateCounter::default()));
+        engine.plugins_enabled = true;
+
+        // Physics is stepped at 50 Hz, but the scene is slowed down twice.
+        let mut scene = Scene::new();
+        scene.graph.physics_time_step = FixedTimeStep::new(50.0);
+        scene.set_time_scale(0.5);
+        let scene = engine.scenes.add(scene);
+        engine.plugins_fixed_time_step.set_scene(scene);
+
+        let mut physics_steps = 0;
+        for _ in 0..60 {
+            engine.update_frame(
+                1.0 / 60.0,
+                Vector2::new(800.0, 600.0),
+                &mut ControlFlow::Poll,
+                &mut 0.0,
+                Default::default(),
+            );
+            physics_steps += engine.scenes[scene]
+                .graph
+                .physics_time_step
+                .last_step_count() as usize;
+        }
+
+        let counter = engine.plugins[0].cast::<UpdateCounter>().unwrap();
+        assert_eq!(physics_steps, 25);
+        assert_eq!(counter.fixed_updates, physics_steps);
+        assert_eq!(counter.fixed_dt, 1.0 / 50.0);
+
+        // Own rate is used when the scene does not drive the fixed updates.
+        engine.plugins_fixed_time_step.set_scene(Handle::NONE);
+        engine.update_frame(
+            1.0 / 30.0,
+            Vector2::new(800.0, 600.0),
+            &mut ControlFlow::Poll,
+            &mut 0.0,
+            Default::default(),
+        );
+        let counter = engine.plugins[0].cast::<UpdateCounter>().unwrap();
+        assert_eq!(counter.fixed_updates, physics_steps + 2);
+        assert_eq!(counter.fixed_dt, 1.0 / 60.0);
+    }
+
+    #[derive(Debug, Clone, Reflect, Visit)]
+    struct FixedUpdateRecorder {
+        #[reflect(hidden)]
+        #[visit(skip)]
+        sender: Sender<(&'static str, f32)>,
+    }
+
+    impl_component_provider!(FixedUpdateRecorder);
+
+    impl ScriptTrait for FixedUpdateRecorder {
+        fn on_fixed_update(&mut self, ctx: &mut ScriptContext) {
+            self.sender.send(("fixed_update", ctx.dt)).unwrap();
+        }
+
+        fn on_update(&mut self, ctx: &mut ScriptContext) {
+            self.sender.send(("update", ctx.dt)).unwrap();
+        }
+
+        fn id(&self) -> Uuid {
+            Uuid::new_v4()
+        }
+    }
+
+    #[test]
+    fn test_script_fixed_update_is_aligned_with_physics() {
+        let mut engine = Engine::new(EngineInitParams {
+            graphics_context_params: Default::default(),
+            serialization_context: Arc::new(SerializationContext::new()),
+            resource_manager: ResourceManager::new(),
+        })
+        .unwrap();
+
+        let (tx, rx) = mpsc::channel();
+
+        let mut scene = Scene::new();
+        scene.graph.physics_time_step = FixedTimeStep::new(50.0);
+        PivotBuilder::new(
+            BaseBuilder::new().with_script(Script::new(FixedUpdateRecorder { sender: tx })),
+        )
+        .build(&mut scene.graph);
+        let scene = engine.scenes.add(scene);
+        engine.register_scripted_scene(scene);
+
+        let mut update = |engine: &mut Engine, dt: f32| {
+            engine.update_frame(
+                dt,
+                Vector2::new(800.0, 600.0),
+                &mut ControlFlow::Poll,
+                &mut 0.0,
+                Default::default(),
+            );
+            rx.try_iter().collect::<Vec<_>>()
+        };
+
+        let mut fixed_updates = 0;
+        for _ in 0..60 {
+            let calls = update(&mut engine, 1.0 / 60.0);
+            let physics_steps = engine.scenes[scene]
+                .graph
+                .physics_time_step
+                .last_step_count() as usize;
+
+            // Fixed updates go before the update and there's one per physics step of the frame.
+            let (last, fixed) = calls.split_last().unwrap();
+            assert_eq!(*last, ("update", 1.0 / 60.0));
+            assert_eq!(fixed.len(), physics_steps);
+            assert!(fixed
+                .iter()
+                .all(|call| *call == ("fixed_update", 1.0 / 50.0)));
+
+            fixed_updates += fixed.len();
+        }
+        assert_eq!(fixed_updates, 50);
+
+        // Without fixed time step, the physics is stepped once per frame with frame time step.
+        engine.scenes[scene].graph.physics_time_step.enabled = false;
+        assert_eq!(
+            update(&mut engine, 1.0 / 30.0),
+            vec![("fixed_update", 1.0 / 30.0), ("update", 1.0 / 30.0)]
+        );
+    }
+
+    #[derive(Debug, Clone, Reflect, Visit)]
+    struct RayCaster {
+        #[reflect(hidden)]
+        #[visit(skip)]
+        sender: Sender<Option<RayHit>>,
+    }
+
+    impl_component_provider!(RayCaster);
+
+    impl ScriptTrait for RayCaster {
+        fn on_update(&mut self, ctx: &mut ScriptContext) {
+            let origin = ctx.scene.graph[ctx.handle].global_position();
+            self.sender
+                .send(ctx.ray_cast(origin, Vector3::new(0.0, 0.0, 2.0), 100.0))
+                .unwrap();
+        }
+
+        fn id(&self) -> Uuid {
+            Uuid::new_v4()
+        }
+    }
+
+    #[test]
+    fn test_script_ray_cast() {
+        let resource_manager = ResourceManager::new();
+        let mut scene = Scene::new();
+
+        let (tx, rx) = mpsc::channel();
+
+        // The ray starts inside the collider of the body of the script, it must be ignored.
+        let own_collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::ball(0.5))
+            .build(&mut scene.graph);
+        RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_script(Script::new(RayCaster { sender: tx }))
+                .with_children(&[own_collider]),
+        )
+        .with_gravity_scale(0.0)
+        .build(&mut scene.graph);
+
+        // Sensors must be ignored too.
+        TriggerVolumeBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(0.0, 0.0, 5.0))
+                    .build(),
+            ),
+        )
+        .with_shape(TriggerShape::cuboid(1.0, 1.0, 1.0))
+        .build(&mut scene.graph);
+
+        let collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(0.5, 0.5, 0.5))
+            .build(&mut scene.graph);
+        RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, 0.0, 10.0))
+                        .build(),
+                )
+                .with_children(&[collider]),
+        )
+        .with_body_type(RigidBodyType::Static)
+        .build(&mut scene.graph);
+
+        // Sync the physics world with the scene graph.
+        scene
+            .graph
+            .update(Vector2::new(800.0, 600.0), 1.0 / 60.0, Default::default());
+
+        let mut scene_container = SceneContainer::new(Default::default());
+
+        let scene_handle = scene_container.add(scene);
+
+        let mut script_processor = ScriptProcessor::default();
+
+        script_processor.register_scripted_scene(
+            scene_handle,
+            &mut scene_container,
+            &resource_manager,
+        );
+
+        script_processor.handle_scripts(
+            &mut scene_container,
+            &mut Default::default(),
+            &resource_manager,
+            &Default::default(),
+            &mut Default::default(),
+            0.0,
+            0.0,
+        );
+
+        let hit = rx
+            .try_recv()
+            .unwrap()
+            .expect("the ray must hit the collider");
+        assert_eq!(hit.node, collider);
+        assert!((hit.distance - 9.5).abs() < 1.0e-4);
+        assert!((hit.position - Vector3::new(0.0, 0.0, 9.5)).norm() < 1.0e-4);
+        assert!((hit.normal - Vector3::new(0.0, 0.0, -1.0)).norm() < 1.0e-4);
+    }
+
+    #[derive(Debug, Clone, Reflect, Visit)]
+    struct NeighborFinder {
+        #[reflect(hidden)]
+        #[visit(skip)]
+        sender: Sender<Vec<Handle<Node>>>,
+    }
+
+    impl_component_provider!(NeighborFinder);
+
+    impl ScriptTrait for NeighborFinder {
+        fn on_update(&mut self, ctx: &mut ScriptContext) {
+            let neighbors = ctx.query_neighbors(2.0).collect();
+            self.sender.send(neighbors).unwrap();
+        }
+
+        fn id(&self) -> Uuid {
+            Uuid::new_v4()
+        }
+    }
+
+    #[test]
+    fn test_script_query_neighbors() {
+        let mut engine = Engine::new(EngineInitParams {
+            graphics_context_params: Default::default(),
+            serialization_context: Arc::new(SerializationContext::new()),
+            resource_manager: ResourceManager::new(),
+        })
+        .unwrap();
+
+        let (tx, rx) = mpsc::channel();
+
+        let mut scene = Scene::new();
+        let pivot_at = |position: Vector3<f32>| {
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(position)
+                    .build(),
+            )
+        };
+        let finder = PivotBuilder::new(
+            pivot_at(Vector3::new(1.0, 0.0, 0.0))
+                .with_script(Script::new(NeighborFinder { sender: tx })),
+        )
+        .build(&mut scene.graph);
+        let near = PivotBuilder::new(pivot_at(Vector3::new(2.5, 0.0, 0.0))).build(&mut scene.graph);
+        PivotBuilder::new(pivot_at(Vector3::new(5.0, 0.0, 0.0))).build(&mut scene.graph);
+        let scene = engine.scenes.add(scene);
+        engine.register_scripted_scene(scene);
+
+        engine.update_frame(
+            1.0 / 60.0,
+            Vector2::new(800.0, 600.0),
+            &mut ControlFlow::Poll,
+            &mut 0.0,
+            Default::default(),
+        );
+
+        // The script node is within the radius of itself, but it must not be reported, the far
+        // node is out of the radius.
+        let neighbors = rx.try_recv().unwrap();
+        assert!(!neighbors.contains(&finder));
+        assert_eq!(neighbors, vec![near]);
+    }
+
+    #[derive(Debug, Clone, Default, Reflect, Visit)]
+    struct Mover {
+        steps: u32,
+    }
+
+    impl_component_provider!(Mover);
+
+    impl TypeUuidProvider for Mover {
+        fn type_uuid() -> Uuid {
+            uuid!("5b0e8c9a-3f71-4d2e-9a6b-1c4d7e8f2a05")
+        }
+    }
+
+    impl ScriptTrait for Mover {
+        fn on_update(&mut self, ctx: &mut ScriptContext) {
+            self.steps += 1;
+            ctx.scene.graph[ctx.handle]
+                .local_transform_mut()
+                .offset(Vector3::new(1.0, 0.0, 0.0));
+        }
+
+        fn id(&self) -> Uuid {
+            Self::type_uuid()
+        }
+    }
+
+    #[test]
+    fn test_scene_snapshot_restores_script_state() {
+        let resource_manager = ResourceManager::new();
+        let serialization_context = Arc::new(SerializationContext::new());
+        serialization_context
+            .script_constructors
+            .add::<Mover>("Mover");
+
+        let mut scene = Scene::new();
+        let mover =
+            PivotBuilder::new(BaseBuilder::new().with_script(Script::new(Mover { steps: 5 })))
+                .build(&mut scene.graph);
+
+        let snapshot = scene.snapshot().unwrap();
+
+        let mut scene_container = SceneContainer::new(Default::default());
+        let scene_handle = scene_container.add(scene);
+
+        let mut script_processor = ScriptProcessor::default();
+        script_processor.register_scripted_scene(
+            scene_handle,
+            &mut scene_container,
+            &resource_manager,
+        );
+
+        for _ in 0..3 {
+            script_processor.handle_scripts(
+                &mut scene_container,
+                &mut Default::default(),
+                &resource_manager,
+                &Default::default(),
+                &mut Default::default(),
+                1.0 / 60.0,
+                0.0,
+            );
+        }
+
+        let played = &scene_container[scene_handle].graph[mover];
+        assert!(played.try_get_script::<Mover>().unwrap().steps > 5);
+        assert_ne!(**played.local_transform().position(), Vector3::default());
+
+        let mut restored = snapshot
+            .restore(serialization_context, resource_manager)
+            .unwrap();
+        let node = &restored.graph[mover];
+        assert_eq!(node.try_get_script::<Mover>().unwrap().steps, 5);
+        assert_eq!(**node.local_transform().position(), Vector3::default());
+        assert_eq!(restored.snapshot().unwrap(), snapshot);
+    }
+
+    struct Score(u32);
+
+    struct Game;
+
+    impl Plugin for Game {}
+
+    struct GameConstructor;
+
+    impl PluginConstructor for GameConstructor {
+        fn create_instance(
+            &self,
+            _override_scene: Handle<Scene>,
+            context: PluginContext,
+        ) -> Box<dyn Plugin> {
+            context.services.insert(Score(0));
+            Box::new(Game)
+        }
+    }
+
+    #[derive(Debug, Clone, Default, Reflect, Visit)]
+    struct Collector {
+        collected: u32,
+    }
+
+    impl_component_provider!(Collector);
+
+    impl TypeUuidProvider for Collector {
+        fn type_uuid() -> Uuid {
+            uuid!("c2a7d5e1-6b4f-4e3a-8d09-2f1b5c7a9e36")
+        }
+    }
+
+    impl ScriptTrait for Collector {
+        fn on_update(&mut self, ctx: &mut ScriptContext) {
+            self.collected += 1;
+            ctx.service_mut::<Score>().unwrap().0 += 1;
+        }
+
+        fn id(&self) -> Uuid {
+            Self::type_uuid()
+        }
+    }
+
+    #[test]
+    fn test_script_accesses_plugin_service() {
+        let resource_manager = ResourceManager::new();
+        let mut scene_container = SceneContainer::new(Default::default());
+        let mut services = ServiceRegistry::default();
+
+        let mut plugins = vec![GameConstructor.create_instance(
+            Handle::NONE,
+            PluginContext {
+                scenes: &mut scene_container,
+                resource_manager: &resource_manager,
+                user_interface: &mut UserInterface::new(Default::default()),
+                graphics_context: &mut GraphicsContext::Uninitialized(Default::default()),
+                dt: 0.0,
+                lag: &mut 0.0,
+                serialization_context: &Arc::new(SerializationContext::new()),
+                performance_statistics: &Default::default(),
+                window_info: &Default::default(),
+                services: &mut services,
+            },
+        )];
+        assert_eq!(services.get::<Score>().unwrap().0, 0);
+
+        let mut scene = Scene::new();
+        PivotBuilder::new(BaseBuilder::new().with_script(Script::new(Collector::default())))
+            .build(&mut scene.graph);
+        let scene_handle = scene_container.add(scene);
+
+        let mut script_processor = ScriptProcessor::default();
+        script_processor.register_scripted_scene(
+            scene_handle,
+            &mut scene_container,
+            &resource_manager,
+        );
+
+        for _ in 0..3 {
+            script_processor.handle_scripts(
+                &mut scene_container,
+                &mut plugins,
+                &resource_manager,
+                &Default::default(),
+                &mut services,
+                1.0 / 60.0,
+                0.0,
+            );
+        }
+
+        assert_eq!(services.get::<Score>().unwrap().0, 3);
+        assert!(services.remove::<Score>().is_some());
+        assert!(services.is_empty());
+    }
+
+    #[derive(Default)]
+    struct CallLog(Vec<&'static str>);
+
+    struct OrderedPlugin;
+
+    impl Plugin for OrderedPlugin {
+        fn on_pre_update(&mut self, context: &mut PluginContext, _control_flow: &mut ControlFlow) {
+            context
+                .services
+                .get_mut::<CallLog>()
+                .unwrap()
+                .0
+                .push("pre_update");
+        }
+
+        fn on_fixed_update(
+            &mut self,
+            context: &mut PluginContext,
+            _control_flow: &mut ControlFlow,
+        ) {
+            context
+                .services
+                .get_mut::<CallLog>()
+                .unwrap()
+                .0
+                .push("fixed_update");
+        }
+
+        fn update(&mut self, context: &mut PluginContext, _control_flow: &mut ControlFlow) {
+            context
+                .services
+                .get_mut::<CallLog>()
+                .unwrap()
+                .0
+                .push("update");
+        }
+
+        fn on_post_update(&mut self, context: &mut PluginContext, _control_flow: &mut ControlFlow) {
+            context
+                .services
+                .get_mut::<CallLog>()
+                .unwrap()
+                .0
+                .push("post_update");
+        }
+    }
+
+    #[derive(Debug, Clone, Default, Reflect, Visit)]
+    struct OrderedScript;
+
+    impl_component_provider!(OrderedScript);
+
+    impl TypeUuidProvider for OrderedScript {
+        fn type_uuid() -> Uuid {
+            uuid!("5d0e2c41-9a7b-4f18-b3e6-71c8a2d4f905")
+        }
+    }
+
+    impl ScriptTrait for OrderedScript {
+        fn on_update(&mut self, ctx: &mut ScriptContext) {
+            ctx.service_mut::<CallLog>().unwrap().0.push("script");
+        }
+
+        fn id(&self) -> Uuid {
+            Self::type_uuid()
+        }
+    }
+
+    #[test]
+    fn test_plugin_and_script_update_order() {
+        let mut engine = Engine::new(EngineInitParams {
+            graphics_context_params: Default::default(),
+            serialization_context: Arc::new(SerializationContext::new()),
+            resource_manager: ResourceManager::new(),
+        })
+        .unwrap();
+
+        engine.services.insert(CallLog::default());
+        engine.plugins.push(Box::new(OrderedPlugin));
+        engine.plugins_enabled = true;
+
+        let mut scene = Scene::new();
+        PivotBuilder::new(BaseBuilder::new().with_script(Script::new(OrderedScript)))
+            .build(&mut scene.graph);
+        let scene = engine.scenes.add(scene);
+        engine.register_scripted_scene(scene);
+
+        // The same sequence as in `Engine::pre_update`.
+        engine.update_frame(
+            1.0 / 60.0,
+            Vector2::new(800.0, 600.0),
+            &mut ControlFlow::Poll,
+            &mut 0.0,
+            Default::default(),
+        );
+
+        assert_eq!(
+            engine.services.get::<CallLog>().unwrap().0,
+            vec![
+                "pre_update",
+                "fixed_update",
+                "update",
+                "script",
+                "post_update"
+            ]
+        );
+    }
+
+    const PLAYER_UUID: Uuid = uuid!("0f6c3b1e-8d0a-4c55-a7e4-2b9f5e1d7c3a");
+
+    #[derive(Debug, Clone, Default, Reflect, Visit)]
+    struct Player {
+        speed: f32,
+    }
+
+    impl_component_provider!(Player);
+
+    impl TypeUuidProvider for Player {
+        fn type_uuid() -> Uuid {
+            PLAYER_UUID
+        }
+    }
+
+    impl ScriptTrait for Player {
+        fn id(&self) -> Uuid {
+            Self::type_uuid()
+        }
+    }
+
+    // New version of the player script after the code was reloaded.
+    #[derive(Debug, Clone, Reflect, Visit)]
+    struct ReloadedPlayer {
+        speed: f32,
+        jump_height: f32,
+    }
+
+    impl Default for ReloadedPlayer {
+        fn default() -> Self {
+            Self {
+                speed: 1.0,
+                jump_height: 2.0,
+            }
+        }
+    }
+
+    impl_component_provider!(ReloadedPlayer);
+
+    impl TypeUuidProvider for ReloadedPlayer {
+        fn type_uuid() -> Uuid {
+            PLAYER_UUID
+        }
+    }
+
+    impl ScriptTrait for ReloadedPlayer {
+        fn id(&self) -> Uuid {
+            Self::type_uuid()
+        }
+    }
+
+    #[test]
+    fn test_reload_scripts_keeps_script_state() {
+        let serialization_context = Arc::new(SerializationContext::new());
+        serialization_context
+            .script_constructors
+            .add::<Player>("Player");
+
+        let mut engine = Engine::new(EngineInitParams {
+            graphics_context_params: Default::default(),
+            serialization_context,
+            resource_manager: ResourceManager::new(),
+        })
+        .unwrap();
+
+        let mut scene = Scene::new();
+        let player =
+            PivotBuilder::new(BaseBuilder::new().with_script(Script::new(Player { speed: 0.75 })))
+                .build(&mut scene.graph);
+        let scene = engine.scenes.add(scene);
+
+        engine.reload_scripts(|serialization_context| {
+            serialization_context
+                .script_constructors
+                .add::<ReloadedPlayer>("Player");
+        });
+
+        let script = engine.scenes[scene].graph[player].script().unwrap();
+        assert!(!script.initialized);
+        let reloaded = script.cast::<ReloadedPlayer>().unwrap();
+        assert_eq!(reloaded.speed, 0.75);
+        assert_eq!(reloaded.jump_height, 2.0);
+    }
 }
@@ -0,0 +1,84 @@
+//! Contains information about the main window of the engine. See [`WindowInfo`] docs for more info.
+
+use crate::{core::algebra::Vector2, event::WindowEvent, window::Window};
+
+/// A snapshot of the main window parameters. It is kept up-to-date by the engine and is available to
+/// plugins and scripts via [`crate::plugin::PluginContext::window_info`] and
+/// [`crate::script::ScriptContext::window_info`], so they can adapt their layout and cameras to the
+/// window without having direct access to it.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct WindowInfo {
+    /// Size of the client area of the window in physical pixels.
+    pub size: Vector2<f32>,
+
+    /// DPI scale factor of the window. Divide [`Self::size`] by it to get the size in logical units.
+    pub scale_factor: f32,
+}
+
+impl Default for WindowInfo {
+    fn default() -> Self {
+        Self {
+            size: Vector2::new(1.0, 1.0),
+            scale_factor: 1.0,
+        }
+    }
+}
+
+impl WindowInfo {
+    /// Creates window info from the actual parameters of the given window.
+    pub fn from_window(window: &Window) -> Self {
+        let inner_size = window.inner_size();
+        Self {
+            size: Vector2::new(inner_size.width as f32, inner_size.height as f32),
+            scale_factor: window.scale_factor() as f32,
+        }
+    }
+
+    /// Updates the info using the given window event. Every event except resizing or changing of the
+    /// scale factor is ignored.
+    pub fn handle_event(&mut self, event: &WindowEvent) {
+        match event {
+            WindowEvent::Resized(size) => {
+                self.size = Vector2::new(size.width as f32, size.height as f32);
+            }
+            WindowEvent::ScaleFactorChanged {
+                scale_factor,
+                new_inner_size,
+            } => {
+                self.scale_factor = *scale_factor as f32;
+                self.size = Vector2::new(new_inner_size.width as f32, new_inner_size.height as f32);
+            }
+            _ => (),
+        }
+    }
+
+    /// Returns size of the client area of the window in logical units.
+    pub fn logical_size(&self) -> Vector2<f32> {
+        self.size / self.scale_factor
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector2, dpi::PhysicalSize, engine::window_info::WindowInfo,
+        event::WindowEvent,
+    };
+
+    #[test]
+    fn test_window_info_resize() {
+        let mut info = WindowInfo::default();
+
+        info.handle_event(&WindowEvent::Resized(PhysicalSize::new(800, 600)));
+        assert_eq!(info.size, Vector2::new(800.0, 600.0));
+
+        let mut new_inner_size = PhysicalSize::new(1600, 1200);
+        info.handle_event(&WindowEvent::ScaleFactorChanged {
+            scale_factor: 2.0,
+            new_inner_size: &mut new_inner_size,
+        });
+        assert_eq!(info.size, Vector2::new(1600.0, 1200.0));
+        assert_eq!(info.scale_factor, 2.0);
+        assert_eq!(info.logical_size(), Vector2::new(800.0, 600.0));
+    }
+}
@@ -23,6 +23,7 @@ use fxhash::FxHashMap;
 use std::{
     fmt::{Display, Formatter},
     ops::Deref,
+    path::Path,
     sync::Arc,
 };
 
@@ -665,6 +666,28 @@ impl Material {
     pub fn properties(&self) -> &FxHashMap<ImmutableString, PropertyValue> {
         &self.properties
     }
+
+    /// Loads a material from a file in native engine format (usually with `.material` extension),
+    /// see [`Self::save`] for more info.
+    pub async fn from_file<P: AsRef<Path>>(
+        path: P,
+        resource_manager: ResourceManager,
+    ) -> Result<Self, VisitError> {
+        let mut visitor = Visitor::load_binary(path).await?;
+        visitor.blackboard.register(Arc::new(resource_manager));
+        let mut material = Material::default();
+        material.visit("Material", &mut visitor)?;
+        Ok(material)
+    }
+
+    /// Saves the material to a file in native engine format. Shader and textures are saved as
+    /// references to their resources, so the material file is small and could be shared across
+    /// multiple scenes.
+    pub fn save<P: AsRef<Path>>(&mut self, path: P) -> VisitResult {
+        let mut visitor = Visitor::new();
+        self.visit("Material", &mut visitor)?;
+        visitor.save_binary(path)
+    }
 }
 
 /// Shared material is a material instance that can be used across multiple objects. It is useful
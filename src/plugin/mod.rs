@@ -2,14 +2,19 @@
 
 #![warn(missing_docs)]
 
+pub mod service;
+
 use crate::{
     asset::manager::ResourceManager,
-    core::pool::Handle,
-    engine::{GraphicsContext, PerformanceStatistics, SerializationContext},
+    core::{algebra::Vector2, pool::Handle},
+    engine::{
+        window_info::WindowInfo, GraphicsContext, PerformanceStatistics, SerializationContext,
+    },
     event::Event,
     event_loop::ControlFlow,
     gui::{message::UiMessage, UserInterface},
-    scene::{Scene, SceneContainer},
+    plugin::service::ServiceRegistry,
+    scene::{graph::fixed_step::accumulate_steps, Scene, SceneContainer},
 };
 use std::{any::Any, sync::Arc};
 
@@ -83,6 +88,125 @@ pub struct PluginContext<'a, 'b> {
 
     /// Performance statistics from the last frame.
     pub performance_statistics: &'a PerformanceStatistics,
+
+    /// Actual parameters of the main window. See [`WindowInfo`] docs for more info.
+    pub window_info: &'a WindowInfo,
+
+    /// A registry of shared services. Plugins should register their services here, so scripts could access
+    /// them. See [`ServiceRegistry`] docs for more info.
+    pub services: &'a mut ServiceRegistry,
+}
+
+impl<'a, 'b> PluginContext<'a, 'b> {
+    /// Returns size of the client area of the main window in physical pixels.
+    pub fn window_size(&self) -> Vector2<f32> {
+        self.window_info.size
+    }
+
+    /// Returns DPI scale factor of the main window.
+    pub fn scale_factor(&self) -> f32 {
+        self.window_info.scale_factor
+    }
+
+    /// Returns a reference to a service of the given type (if any). See [`ServiceRegistry`] docs for more info.
+    pub fn service<T: Any>(&self) -> Option<&T> {
+        self.services.get::<T>()
+    }
+
+    /// Returns a reference to a service of the given type (if any). See [`ServiceRegistry`] docs for more info.
+    pub fn service_mut<T: Any>(&mut self) -> Option<&mut T> {
+        self.services.get_mut::<T>()
+    }
+}
+
+/// Defines the rate of [`Plugin::on_fixed_update`] calls. The time, that passed since the last engine
+/// update, is split in equal steps and the time that is not enough for a full step is carried over to
+/// the next update, so the amount of fixed updates per second does not depend on the frame rate.
+///
+/// # Relation to physics
+///
+/// By default, the fixed time step of plugins has its own rate (60 Hz) and its own accumulator, that
+/// are independent of the fixed time step of scene physics (see
+/// [`crate::scene::graph::Graph::physics_time_step`]). Even if both rates are the same, the amount of
+/// fixed updates of plugins could differ from the amount of physics steps, because the scene could have
+/// a different time scale, could be paused, or its accumulator could be reset.
+///
+/// Use [`Self::set_scene`] to drive the fixed updates of plugins by the physics of a scene. In this
+/// case, plugins get exactly as many fixed updates per engine update as there were physics steps of the
+/// scene and the time step of the fixed updates is the time step of the physics. Keep in mind, that all
+/// physics steps of a frame are performed (in [`Scene::update`]) before the fixed updates of plugins.
+/// The own rate is used when the scene does not exist, is disabled or has its fixed time step disabled.
+#[derive(Clone, Debug)]
+pub struct PluginFixedTimeStep {
+    rate: f32,
+    accumulator: f64,
+    scene: Handle<Scene>,
+}
+
+impl Default for PluginFixedTimeStep {
+    fn default() -> Self {
+        Self::new(60.0)
+    }
+}
+
+impl PluginFixedTimeStep {
+    /// Creates a new fixed time step with the given amount of fixed updates per second.
+    pub fn new(rate: f32) -> Self {
+        Self {
+            rate,
+            accumulator: 0.0,
+            scene: Handle::NONE,
+        }
+    }
+
+    /// Sets a new amount of fixed updates per second.
+    pub fn set_rate(&mut self, rate: f32) {
+        self.rate = rate;
+    }
+
+    /// Returns the amount of fixed updates per second.
+    pub fn rate(&self) -> f32 {
+        self.rate
+    }
+
+    /// Sets a scene, whose physics steps will drive the fixed updates of plugins. Pass [`Handle::NONE`]
+    /// to use own rate of the fixed time step. See [relation to physics](Self#relation-to-physics) for
+    /// more info.
+    pub fn set_scene(&mut self, scene: Handle<Scene>) {
+        self.scene = scene;
+    }
+
+    /// Returns a scene, whose physics steps drive the fixed updates of plugins.
+    pub fn scene(&self) -> Handle<Scene> {
+        self.scene
+    }
+
+    /// Returns duration of a single fixed update in seconds.
+    pub fn step_duration(&self) -> f32 {
+        1.0 / self.rate.max(1.0)
+    }
+
+    /// Advances the accumulator by the given amount of time and returns the amount of fixed updates
+    /// that must be performed. The amount of steps is bounded, because the engine clamps the time delta
+    /// of its updates (see [`crate::engine::Engine::set_max_dt`]).
+    pub fn advance(&mut self, dt: f32) -> u32 {
+        accumulate_steps(&mut self.accumulator, self.step_duration(), dt, u32::MAX)
+    }
+
+    /// Returns the amount of fixed updates and their time step for the current engine update. The
+    /// scenes must be updated already.
+    pub(crate) fn advance_frame(&mut self, dt: f32, scenes: &SceneContainer) -> (u32, f32) {
+        if let Some(time_step) = scenes
+            .try_get(self.scene)
+            .filter(|scene| scene.enabled)
+            .map(|scene| &scene.graph.physics_time_step)
+            .filter(|time_step| time_step.enabled)
+        {
+            (time_step.last_step_count(), time_step.step_duration())
+        } else {
+            (self.advance(dt), self.step_duration())
+        }
+    }
 }
 
 /// Base plugin automatically implements type casting for plugins.
@@ -161,6 +285,11 @@ impl dyn Plugin {
 ///         // The implementation is optional.
 ///     }
 ///
+///     fn on_fixed_update(&mut self, context: &mut PluginContext, control_flow: &mut ControlFlow) {
+///         // The method is called at the rate of `Engine::plugins_fixed_time_step`, it could be called
+///         // multiple times (or not called at all) per frame. The implementation is optional.
+///     }
+///
 ///     fn on_os_event(&mut self, event: &Event<()>, context: PluginContext, control_flow: &mut ControlFlow) {
 ///         // The method is called when the main window receives an event from the OS.
 ///     }
@@ -171,8 +300,33 @@ pub trait Plugin: BasePlugin {
     /// additional actions.
     fn on_deinit(&mut self, #[allow(unused_variables)] context: PluginContext) {}
 
+    /// The method is called once per engine update, before any other update method of the plugin and
+    /// before scripts. It could be used to prepare some state, that will be used by scripts in the
+    /// current frame (read input, reset per-frame counters, etc.). See [`Self::update`] docs for the
+    /// complete order of calls.
+    fn on_pre_update(
+        &mut self,
+        #[allow(unused_variables)] context: &mut PluginContext,
+        #[allow(unused_variables)] control_flow: &mut ControlFlow,
+    ) {
+    }
+
     /// Updates the plugin internals at fixed rate (see [`PluginContext::dt`] parameter for more
     /// info).
+    ///
+    /// # Order of calls
+    ///
+    /// The engine updates plugins and scripts in the following order on every update:
+    ///
+    /// 1. Scene graphs are updated (physics, animations, etc.).
+    /// 2. [`Self::on_pre_update`] of every plugin.
+    /// 3. [`Self::on_fixed_update`] of every plugin (zero or more times, see its docs).
+    /// 4. [`Self::update`] of every plugin.
+    /// 5. [`Self::on_ui_message`] of every plugin for every pending UI message.
+    /// 6. Scripts ([`crate::script::ScriptTrait::on_update`] and the rest of script methods).
+    /// 7. [`Self::on_post_update`] of every plugin.
+    ///
+    /// Plugins are called in the order of their registration.
     fn update(
         &mut self,
         #[allow(unused_variables)] context: &mut PluginContext,
@@ -180,6 +334,33 @@ pub trait Plugin: BasePlugin {
     ) {
     }
 
+    /// The method is called once per engine update, after scripts were updated. It could be used to
+    /// finalize the frame, using the results of scripts (apply gathered commands, send network
+    /// packets, etc.). See [`Self::update`] docs for the complete order of calls.
+    fn on_post_update(
+        &mut self,
+        #[allow(unused_variables)] context: &mut PluginContext,
+        #[allow(unused_variables)] control_flow: &mut ControlFlow,
+    ) {
+    }
+
+    /// Updates the plugin internals at the rate of [`crate::engine::Engine::plugins_fixed_time_step`].
+    /// Unlike [`Self::update`], which is called exactly once per engine update, this method could be
+    /// called zero or multiple times per engine update, so the amount of calls per second is always
+    /// the same. It is useful for deterministic simulations (networking, replays, etc.), while
+    /// [`Self::update`] should be used for everything that depends on frame rate (camera, visual
+    /// effects, etc.). [`PluginContext::dt`] is equal to the duration of a fixed step in this method.
+    ///
+    /// All fixed updates of an engine update are performed before [`Self::update`], but after the scenes
+    /// (and their physics) were updated. See [`PluginFixedTimeStep`] docs for more info about the relation
+    /// to the physics time step.
+    fn on_fixed_update(
+        &mut self,
+        #[allow(unused_variables)] context: &mut PluginContext,
+        #[allow(unused_variables)] control_flow: &mut ControlFlow,
+    ) {
+    }
+
     /// The method is called when the main window receives an event from the OS. The main use of
     /// the method is to respond to some external events, for example an event from keyboard or
     /// gamepad. See [`Event`] docs for more info.
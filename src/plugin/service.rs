@@ -0,0 +1,108 @@
+//! Service registry is a typed storage for shared services, such as audio managers, save systems and so on.
+//! See [`ServiceRegistry`] docs for more info.
+
+use fxhash::FxHashMap;
+use std::any::{Any, TypeId};
+
+/// Service registry is a typed storage for objects (services), that should be accessible from plugins and scripts.
+/// It allows you to avoid global variables and downcasting of plugins to access some shared functionality. There
+/// could be only one service of a type.
+///
+/// # Example
+///
+/// A plugin registers a service when it is created, and then scripts can access it using
+/// [`crate::script::ScriptContext::service`]:
+///
+/// ```rust
+/// use fyrox::{
+///     core::pool::Handle,
+///     plugin::{Plugin, PluginConstructor, PluginContext},
+///     scene::Scene,
+///     script::ScriptContext,
+/// };
+///
+/// #[derive(Default)]
+/// struct AudioManager {
+///     volume: f32,
+/// }
+///
+/// struct Game;
+///
+/// impl Plugin for Game {}
+///
+/// struct GameConstructor;
+///
+/// impl PluginConstructor for GameConstructor {
+///     fn create_instance(&self, _: Handle<Scene>, context: PluginContext) -> Box<dyn Plugin> {
+///         context.services.insert(AudioManager { volume: 0.5 });
+///         Box::new(Game)
+///     }
+/// }
+///
+/// fn on_update(context: &mut ScriptContext) {
+///     if let Some(audio) = context.service_mut::<AudioManager>() {
+///         audio.volume = 1.0;
+///     }
+/// }
+/// ```
+///
+/// # Lifetime
+///
+/// The registry is owned by the engine. All services are dropped when plugins are disabled (for example, when
+/// the editor leaves play mode), so plugins should register their services every time they are created.
+#[derive(Default)]
+pub struct ServiceRegistry {
+    services: FxHashMap<TypeId, Box<dyn Any>>,
+}
+
+impl ServiceRegistry {
+    /// Adds a new service to the registry. Returns previous service of the same type (if any).
+    pub fn insert<T: Any>(&mut self, service: T) -> Option<T> {
+        self.services
+            .insert(TypeId::of::<T>(), Box::new(service))
+            .and_then(|previous| previous.downcast::<T>().ok())
+            .map(|previous| *previous)
+    }
+
+    /// Returns a reference to a service of the given type (if any).
+    pub fn get<T: Any>(&self) -> Option<&T> {
+        self.services
+            .get(&TypeId::of::<T>())
+            .and_then(|service| service.downcast_ref::<T>())
+    }
+
+    /// Returns a reference to a service of the given type (if any).
+    pub fn get_mut<T: Any>(&mut self) -> Option<&mut T> {
+        self.services
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|service| service.downcast_mut::<T>())
+    }
+
+    /// Removes a service of the given type from the registry and returns it (if any).
+    pub fn remove<T: Any>(&mut self) -> Option<T> {
+        self.services
+            .remove(&TypeId::of::<T>())
+            .and_then(|service| service.downcast::<T>().ok())
+            .map(|service| *service)
+    }
+
+    /// Returns `true` if the registry has a service of the given type, `false` - otherwise.
+    pub fn contains<T: Any>(&self) -> bool {
+        self.services.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Returns total amount of services in the registry.
+    pub fn len(&self) -> usize {
+        self.services.len()
+    }
+
+    /// Returns `true` if the registry has no services, `false` - otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.services.is_empty()
+    }
+
+    /// Removes every service from the registry.
+    pub fn clear(&mut self) {
+        self.services.clear()
+    }
+}
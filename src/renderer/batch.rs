@@ -4,7 +4,7 @@ use crate::scene::node::Node;
 use crate::{
     core::{
         algebra::{Matrix4, Vector3},
-        math::frustum::Frustum,
+        math::{aabb::AxisAlignedBoundingBox, frustum::Frustum, ray::Ray},
         sstorage::ImmutableString,
     },
     material::SharedMaterial,
@@ -36,6 +36,12 @@ pub struct ObserverInfo {
     pub view_matrix: Matrix4<f32>,
     /// Projection matrix of the observer.
     pub projection_matrix: Matrix4<f32>,
+    /// A mask of layers, that are visible to the observer. Nodes whose layer bit is not set in the mask
+    /// will be ignored. See [`crate::scene::base::Base::set_layer`] for more info.
+    pub render_mask: u32,
+    /// Whether the nodes that are fully hidden behind [occluders](crate::scene::base::Base::set_occluder)
+    /// should be skipped or not.
+    pub occlusion_culling: bool,
 }
 
 /// Render context is used to collect render data from the scene nodes. It provides all required information about
@@ -121,6 +127,16 @@ pub struct RenderDataBatch {
     sort_index: u64,
 }
 
+impl RenderDataBatch {
+    /// Returns `true` if the batch is shared by more than one instance. All instances of a batch share the
+    /// same vertex/index data and material, so the geometry is uploaded to GPU only once and the instances
+    /// differ only in their per-instance data (world transform, bone matrices, etc.). Every instance is still
+    /// drawn with a separate draw call.
+    pub fn is_shared(&self) -> bool {
+        self.instances.len() > 1
+    }
+}
+
 impl Debug for RenderDataBatch {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -139,12 +155,41 @@ pub struct RenderDataBatchStorage {
     batch_map: FxHashMap<u64, usize>,
     /// A sorted list of batches.
     pub batches: Vec<RenderDataBatch>,
+    instance_count: usize,
+    visible_node_count: usize,
+}
+
+/// Returns `true` if the given box is fully hidden behind the occluder box from the point of view of the
+/// observer. Every corner of the box must be behind the occluder, the boxes must not intersect and the
+/// observer must be outside of the occluder.
+fn is_occluded(
+    observer_position: &Vector3<f32>,
+    occluder: &AxisAlignedBoundingBox,
+    aabb: &AxisAlignedBoundingBox,
+) -> bool {
+    if occluder.intersect_aabb(aabb) {
+        return false;
+    }
+
+    aabb.corners().iter().all(|corner| {
+        Ray::from_two_points(*observer_position, *corner)
+            .aabb_intersection(occluder)
+            .map_or(false, |result| result.min > 0.0 && result.max < 1.0)
+    })
+}
+
+fn is_finite_box(aabb: &AxisAlignedBoundingBox) -> bool {
+    aabb.min
+        .iter()
+        .zip(aabb.max.iter())
+        .all(|(min, max)| min.is_finite() && max.is_finite() && min <= max)
 }
 
 impl RenderDataBatchStorage {
     /// Creates a new render batch storage from the given graph and observer info. It "asks" every node in the
     /// graph one-by-one to give render data which is then put in the storage, sorted and ready for rendering.
-    /// Frustum culling is done on scene node side ([`crate::scene::node::NodeTrait::collect_render_data`]).
+    /// Frustum culling is done on scene node side ([`crate::scene::node::NodeTrait::collect_render_data`]),
+    /// occlusion culling (if enabled) is done here, before asking the nodes for render data.
     pub fn from_graph(
         graph: &Graph,
         observer_info: ObserverInfo,
@@ -155,6 +200,8 @@ impl RenderDataBatchStorage {
         let mut storage = Self {
             batch_map: FxHashMap::with_capacity_and_hasher(capacity, FxBuildHasher::default()),
             batches: Vec::with_capacity(capacity),
+            instance_count: 0,
+            visible_node_count: 0,
         };
 
         let mut lod_filter = vec![true; graph.capacity() as usize];
@@ -182,6 +229,23 @@ impl RenderDataBatchStorage {
         )
         .unwrap_or_default();
 
+        let mut occluders = Vec::new();
+        if observer_info.occlusion_culling {
+            for (handle, node) in graph.pair_iter() {
+                if node.is_occluder()
+                    && node.global_visibility()
+                    && node.is_globally_enabled()
+                    && lod_filter[handle.index() as usize]
+                    && (node.layer_mask() & observer_info.render_mask) != 0
+                {
+                    let aabb = node.world_bounding_box();
+                    if aabb.is_valid() && frustum.is_intersects_aabb(&aabb) {
+                        occluders.push((handle, aabb));
+                    }
+                }
+            }
+        }
+
         let mut ctx = RenderContext {
             observer_position: &observer_info.observer_position,
             z_near: observer_info.z_near,
@@ -198,8 +262,26 @@ impl RenderDataBatchStorage {
         for (handle, node) in graph.pair_iter() {
             ctx.node_handle = handle;
 
-            if lod_filter[handle.index() as usize] {
+            if lod_filter[handle.index() as usize]
+                && (node.layer_mask() & observer_info.render_mask) != 0
+            {
+                if !occluders.is_empty() {
+                    let aabb = node.world_bounding_box();
+                    if is_finite_box(&aabb)
+                        && occluders.iter().any(|(occluder_handle, occluder)| {
+                            *occluder_handle != handle
+                                && is_occluded(&observer_info.observer_position, occluder, &aabb)
+                        })
+                    {
+                        continue;
+                    }
+                }
+
+                let instance_count = ctx.storage.instance_count;
                 node.collect_render_data(&mut ctx);
+                if ctx.storage.instance_count != instance_count {
+                    ctx.storage.visible_node_count += 1;
+                }
             }
         }
 
@@ -246,7 +328,21 @@ impl RenderDataBatchStorage {
             self.batches.last_mut().unwrap()
         };
 
-        batch.instances.push(instance_data)
+        batch.instances.push(instance_data);
+        self.instance_count += 1;
+    }
+
+    /// Returns total amount of batches that have more than one instance. Such batches are formed from the
+    /// nodes that share the same surface data and material (for example, a forest made of the same tree
+    /// model). See [`RenderDataBatch::is_shared`] for more info.
+    pub fn shared_batch_count(&self) -> usize {
+        self.batches.iter().filter(|b| b.is_shared()).count()
+    }
+
+    /// Returns total amount of nodes that passed culling and gave at least one surface instance for rendering.
+    /// Could be useful for debugging to check how many nodes were culled.
+    pub fn visible_node_count(&self) -> usize {
+        self.visible_node_count
     }
 
     /// Sorts the batches by their respective sort index.
@@ -26,6 +26,7 @@ struct Shader {
     program: GpuProgram,
     world_view_projection_matrix: UniformLocation,
     hdr_sampler: UniformLocation,
+    threshold: UniformLocation,
 }
 
 impl Shader {
@@ -39,6 +40,7 @@ impl Shader {
             world_view_projection_matrix: program
                 .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
             hdr_sampler: program.uniform_location(state, &ImmutableString::new("hdrSampler"))?,
+            threshold: program.uniform_location(state, &ImmutableString::new("threshold"))?,
             program,
         })
     }
@@ -105,6 +107,7 @@ impl BloomRenderer {
         state: &mut PipelineState,
         quad: &GeometryBuffer,
         hdr_scene_frame: Rc<RefCell<GpuTexture>>,
+        threshold: f32,
     ) -> Result<RenderPassStatistics, FrameworkError> {
         scope_profile!();
 
@@ -134,7 +137,8 @@ impl BloomRenderer {
                         &shader.world_view_projection_matrix,
                         &(make_viewport_matrix(viewport)),
                     )
-                    .set_texture(&shader.hdr_sampler, &hdr_scene_frame);
+                    .set_texture(&shader.hdr_sampler, &hdr_scene_frame)
+                    .set_f32(&shader.threshold, threshold);
             },
         )?;
 
@@ -143,3 +147,82 @@ impl BloomRenderer {
         Ok(stats)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{algebra::Vector3, sstorage::ImmutableString},
+        material::{
+            shader::{
+                STANDARD_SHADER_SRC, STANDARD_TERRAIN_SHADER_SRC, STANDARD_TWOSIDES_SHADER_SRC,
+            },
+            Material, PropertyValue,
+        },
+    };
+
+    // There is no graphics context in tests, so the passes are performed on the CPU. The sources of the
+    // shaders are checked to make sure that the CPU versions match them.
+    const SHARED_SRC: &str = include_str!("../framework/shaders/shared.glsl");
+    const AMBIENT_LIGHT_SRC: &str = include_str!("../shaders/ambient_light_fs.glsl");
+    const BRIGHT_PASS_SRC: &str = include_str!("../shaders/bloom_fs.glsl");
+
+    fn luminance(color: Vector3<f32>) -> f32 {
+        color.dot(&Vector3::new(0.299, 0.587, 0.114))
+    }
+
+    // HDR color of a surface with white albedo in a scene without ambient light, the emission is written
+    // to the ambient buffer of the G-Buffer and added to the HDR frame by the ambient light pass.
+    fn hdr_color(material: &Material, emission_texel: Vector3<f32>) -> Vector3<f32> {
+        let strength = material
+            .property_ref(&ImmutableString::new("emissionStrength"))
+            .and_then(|p| p.as_vector3())
+            .unwrap();
+        emission_texel.component_mul(&strength)
+    }
+
+    fn bright_pass(hdr_color: Vector3<f32>, threshold: f32) -> Vector3<f32> {
+        if luminance(hdr_color) > threshold {
+            hdr_color
+        } else {
+            Vector3::default()
+        }
+    }
+
+    #[test]
+    fn test_emissive_surface_contributes_to_bloom() {
+        assert!(SHARED_SRC.contains("return dot(x, vec3(0.299, 0.587, 0.114));"));
+        assert!(AMBIENT_LIGHT_SRC.contains("FragColor = (ambientColor + ambientPixel) * albedo;"));
+        assert!(BRIGHT_PASS_SRC.contains("if (S_Luminance(hdrPixel) > threshold) {"));
+        assert!(BRIGHT_PASS_SRC.contains("outBrightColor = vec4(hdrPixel, 0.0);"));
+
+        let threshold = 1.0;
+        let texel = Vector3::new(0.2, 0.3, 0.2);
+
+        for (source, mut material) in [
+            (STANDARD_SHADER_SRC, Material::standard()),
+            (STANDARD_TWOSIDES_SHADER_SRC, Material::standard_two_sides()),
+            (STANDARD_TERRAIN_SHADER_SRC, Material::standard_terrain()),
+        ] {
+            assert!(source.lines().any(|line| line.trim().starts_with(
+                "outAmbient.xyz = emissionStrength * texture(emissionTexture, tc).rgb"
+            )));
+
+            // Dim emission stays below the threshold.
+            assert_eq!(
+                bright_pass(hdr_color(&material, texel), threshold),
+                Vector3::default()
+            );
+
+            // Emission strength is not limited, so it is enough to boost it to make the surface glow.
+            material
+                .set_property(
+                    &ImmutableString::new("emissionStrength"),
+                    PropertyValue::Vector3(Vector3::repeat(8.0)),
+                )
+                .unwrap();
+
+            let glow = bright_pass(hdr_color(&material, texel), threshold);
+            assert!(luminance(glow) > threshold);
+        }
+    }
+}
@@ -110,7 +110,9 @@ impl ForwardRenderer {
                         state,
                         viewport,
                         &render_pass.program,
-                        &render_pass.draw_params,
+                        &render_pass
+                            .draw_params
+                            .for_transform(&instance.world_transform),
                         instance.element_range,
                         |mut program_binding| {
                             apply_material(MaterialContext {
@@ -1,5 +1,12 @@
 use crate::{
-    core::{color::Color, math::Rect, reflect::prelude::*, scope_profile, visitor::prelude::*},
+    core::{
+        algebra::Matrix4,
+        color::Color,
+        math::{Matrix4Ext, Rect},
+        reflect::prelude::*,
+        scope_profile,
+        visitor::prelude::*,
+    },
     renderer::framework::{
         error::FrameworkError,
         geometry_buffer::{DrawCallStatistics, ElementRange, GeometryBuffer},
@@ -10,7 +17,7 @@ use crate::{
 };
 use glow::HasContext;
 use serde::Deserialize;
-use std::{cell::RefCell, rc::Rc};
+use std::{borrow::Cow, cell::RefCell, rc::Rc};
 
 #[derive(Copy, Clone, PartialOrd, PartialEq, Hash, Debug, Eq)]
 pub enum AttachmentKind {
@@ -44,6 +51,22 @@ impl Default for CullFace {
     }
 }
 
+impl CullFace {
+    /// Returns the face, that should be culled for an object with the given world transform. Transforms
+    /// with negative determinant (mirroring, negative scale) flip winding order of triangles, so the
+    /// opposite face must be culled.
+    pub fn for_transform(self, world_transform: &Matrix4<f32>) -> Self {
+        if world_transform.basis().determinant() < 0.0 {
+            match self {
+                CullFace::Back => CullFace::Front,
+                CullFace::Front => CullFace::Back,
+            }
+        } else {
+            self
+        }
+    }
+}
+
 #[derive(Deserialize, Default, Visit, Debug, PartialEq, Clone, Eq, Reflect)]
 pub struct BlendParameters {
     pub func: BlendFunc,
@@ -61,6 +84,22 @@ pub struct DrawParameters {
     pub stencil_op: StencilOp,
 }
 
+impl DrawParameters {
+    /// Returns draw parameters adjusted for the given world transform. See [`CullFace::for_transform`]
+    /// for more info.
+    pub fn for_transform(&self, world_transform: &Matrix4<f32>) -> Cow<'_, Self> {
+        match self.cull_face {
+            Some(cull_face) if cull_face.for_transform(world_transform) != cull_face => {
+                Cow::Owned(Self {
+                    cull_face: Some(cull_face.for_transform(world_transform)),
+                    ..self.clone()
+                })
+            }
+            _ => Cow::Borrowed(self),
+        }
+    }
+}
+
 impl Default for DrawParameters {
     fn default() -> Self {
         Self {
@@ -43,6 +43,7 @@ use crate::{
         decal::Decal,
         graph::Graph,
         mesh::{surface::SurfaceData, RenderPath},
+        node::NodeTrait,
     },
 };
 use std::{cell::RefCell, rc::Rc};
@@ -352,7 +353,9 @@ impl GBuffer {
                         state,
                         viewport,
                         &render_pass.program,
-                        &render_pass.draw_params,
+                        &render_pass
+                            .draw_params
+                            .for_transform(&instance.world_transform),
                         instance.element_range,
                         apply_uniforms,
                     )?;
@@ -369,7 +372,12 @@ impl GBuffer {
         // for rendering. We'll render in the G-Buffer, but depth will be used from final frame, since
         // decals do not modify depth (only diffuse and normal maps).
         let unit_cube = &self.cube;
-        for decal in graph.linear_iter().filter_map(|n| n.cast::<Decal>()) {
+        let frustum = camera.frustum();
+        for decal in graph
+            .linear_iter()
+            .filter_map(|n| n.cast::<Decal>())
+            .filter(|d| frustum.is_intersects_aabb(&d.world_bounding_box()))
+        {
             let shader = &self.decal_shader;
             let program = &self.decal_shader.program;
 
@@ -417,7 +425,7 @@ impl GBuffer {
                         .set_texture(&shader.normal_texture, &normal_texture)
                         .set_texture(&shader.decal_mask, &decal_mask)
                         .set_u32(&shader.layer_index, decal.layer() as u32)
-                        .set_linear_color(&shader.color, &decal.color());
+                        .set_linear_color(&shader.color, &decal.faded_color());
                 },
             )?;
         }
@@ -18,6 +18,7 @@ pub struct MapShader {
     pub max_luminance: UniformLocation,
     pub auto_exposure: UniformLocation,
     pub fixed_exposure: UniformLocation,
+    pub use_tone_mapping: UniformLocation,
 }
 
 impl MapShader {
@@ -48,6 +49,8 @@ impl MapShader {
                 .uniform_location(state, &ImmutableString::new("autoExposure"))?,
             fixed_exposure: program
                 .uniform_location(state, &ImmutableString::new("fixedExposure"))?,
+            use_tone_mapping: program
+                .uniform_location(state, &ImmutableString::new("useToneMapping"))?,
             program,
         })
     }
@@ -261,6 +261,7 @@ impl HighDynamicRangeRenderer {
         exposure: Exposure,
         color_grading_lut: Option<&ColorGradingLut>,
         use_color_grading: bool,
+        use_tone_mapping: bool,
         texture_cache: &mut TextureCache,
     ) -> Result<DrawCallStatistics, FrameworkError> {
         let shader = &self.map_shader;
@@ -296,7 +297,8 @@ impl HighDynamicRangeRenderer {
                         &shader.use_color_grading,
                         use_color_grading && color_grading_lut.is_some(),
                     )
-                    .set_texture(&shader.color_map_sampler, &color_grading_lut_tex);
+                    .set_texture(&shader.color_map_sampler, &color_grading_lut_tex)
+                    .set_bool(&shader.use_tone_mapping, use_tone_mapping);
 
                 match exposure {
                     Exposure::Auto {
@@ -332,6 +334,7 @@ impl HighDynamicRangeRenderer {
         exposure: Exposure,
         color_grading_lut: Option<&ColorGradingLut>,
         use_color_grading: bool,
+        use_tone_mapping: bool,
         texture_cache: &mut TextureCache,
     ) -> Result<RenderPassStatistics, FrameworkError> {
         let mut stats = RenderPassStatistics::default();
@@ -348,6 +351,7 @@ impl HighDynamicRangeRenderer {
             exposure,
             color_grading_lut,
             use_color_grading,
+            use_tone_mapping,
             texture_cache,
         )?;
         Ok(stats)
@@ -12,6 +12,16 @@ pub struct AmbientLightShader {
     pub ambient_color: UniformLocation,
     pub ao_sampler: UniformLocation,
     pub ambient_texture: UniformLocation,
+    pub depth_texture: UniformLocation,
+    pub normal_texture: UniformLocation,
+    pub material_texture: UniformLocation,
+    pub irradiance_texture: UniformLocation,
+    pub specular_texture: UniformLocation,
+    pub environment_enabled: UniformLocation,
+    pub environment_intensity: UniformLocation,
+    pub specular_max_lod: UniformLocation,
+    pub inv_view_proj_matrix: UniformLocation,
+    pub camera_position: UniformLocation,
 }
 
 impl AmbientLightShader {
@@ -30,6 +40,26 @@ impl AmbientLightShader {
             ao_sampler: program.uniform_location(state, &ImmutableString::new("aoSampler"))?,
             ambient_texture: program
                 .uniform_location(state, &ImmutableString::new("ambientTexture"))?,
+            depth_texture: program
+                .uniform_location(state, &ImmutableString::new("depthTexture"))?,
+            normal_texture: program
+                .uniform_location(state, &ImmutableString::new("normalTexture"))?,
+            material_texture: program
+                .uniform_location(state, &ImmutableString::new("materialTexture"))?,
+            irradiance_texture: program
+                .uniform_location(state, &ImmutableString::new("irradianceTexture"))?,
+            specular_texture: program
+                .uniform_location(state, &ImmutableString::new("specularTexture"))?,
+            environment_enabled: program
+                .uniform_location(state, &ImmutableString::new("environmentEnabled"))?,
+            environment_intensity: program
+                .uniform_location(state, &ImmutableString::new("environmentIntensity"))?,
+            specular_max_lod: program
+                .uniform_location(state, &ImmutableString::new("specularMaxLod"))?,
+            inv_view_proj_matrix: program
+                .uniform_location(state, &ImmutableString::new("invViewProj"))?,
+            camera_position: program
+                .uniform_location(state, &ImmutableString::new("cameraPosition"))?,
             program,
         })
     }
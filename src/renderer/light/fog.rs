@@ -0,0 +1,41 @@
+use crate::core::sstorage::ImmutableString;
+use crate::renderer::framework::{
+    error::FrameworkError,
+    gpu_program::{GpuProgram, UniformLocation},
+    state::PipelineState,
+};
+
+pub struct FogShader {
+    pub program: GpuProgram,
+    pub wvp_matrix: UniformLocation,
+    pub depth_sampler: UniformLocation,
+    pub inv_view_proj_matrix: UniformLocation,
+    pub camera_position: UniformLocation,
+    pub fog_color: UniformLocation,
+    pub fog_density: UniformLocation,
+    pub fog_start: UniformLocation,
+    pub fog_end: UniformLocation,
+}
+
+impl FogShader {
+    pub fn new(state: &mut PipelineState) -> Result<Self, FrameworkError> {
+        let fragment_source = include_str!("../shaders/fog_fs.glsl");
+        let vertex_source = include_str!("../shaders/ambient_light_vs.glsl");
+        let program = GpuProgram::from_source(state, "FogShader", vertex_source, fragment_source)?;
+        Ok(Self {
+            wvp_matrix: program
+                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
+            depth_sampler: program
+                .uniform_location(state, &ImmutableString::new("depthTexture"))?,
+            inv_view_proj_matrix: program
+                .uniform_location(state, &ImmutableString::new("invViewProj"))?,
+            camera_position: program
+                .uniform_location(state, &ImmutableString::new("cameraPosition"))?,
+            fog_color: program.uniform_location(state, &ImmutableString::new("fogColor"))?,
+            fog_density: program.uniform_location(state, &ImmutableString::new("fogDensity"))?,
+            fog_start: program.uniform_location(state, &ImmutableString::new("fogStart"))?,
+            fog_end: program.uniform_location(state, &ImmutableString::new("fogEnd"))?,
+            program,
+        })
+    }
+}
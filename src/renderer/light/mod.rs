@@ -22,7 +22,7 @@ use crate::{
         },
         gbuffer::GBuffer,
         light::{
-            ambient::AmbientLightShader, directional::DirectionalLightShader,
+            ambient::AmbientLightShader, directional::DirectionalLightShader, fog::FogShader,
             point::PointLightShader, spot::SpotLightShader,
         },
         light_volume::LightVolumeRenderer,
@@ -33,10 +33,11 @@ use crate::{
         },
         skybox_shader::SkyboxShader,
         ssao::ScreenSpaceAmbientOcclusionRenderer,
-        GeometryCache, QualitySettings, RenderPassStatistics, TextureCache,
+        FogParameters, GeometryCache, QualitySettings, RenderPassStatistics, TextureCache,
     },
     scene::{
         camera::Camera,
+        environment::SPECULAR_MAP_MIP_COUNT,
         light::{directional::DirectionalLight, point::PointLight, spot::SpotLight},
         mesh::{
             buffer::{TriangleBuffer, VertexBuffer},
@@ -55,6 +56,7 @@ use std::{
 
 pub mod ambient;
 pub mod directional;
+pub mod fog;
 pub mod point;
 pub mod spot;
 
@@ -106,6 +108,7 @@ pub struct DeferredLightRenderer {
     point_light_shader: PointLightShader,
     directional_light_shader: DirectionalLightShader,
     ambient_light_shader: AmbientLightShader,
+    fog_shader: FogShader,
     quad: GeometryBuffer,
     sphere: GeometryBuffer,
     skybox: GeometryBuffer,
@@ -123,6 +126,7 @@ pub(crate) struct DeferredRendererContext<'a> {
     pub camera: &'a Camera,
     pub gbuffer: &'a mut GBuffer,
     pub ambient_color: Color,
+    pub fog: Option<FogParameters>,
     pub settings: &'a QualitySettings,
     pub textures: &'a mut TextureCache,
     pub geometry_cache: &'a mut GeometryCache,
@@ -133,6 +137,8 @@ pub(crate) struct DeferredRendererContext<'a> {
     pub black_dummy: Rc<RefCell<GpuTexture>>,
     pub volume_dummy: Rc<RefCell<GpuTexture>>,
     pub matrix_storage: &'a mut MatrixStorageCache,
+    /// Replaces albedo of every surface with white color, so only lighting is visible.
+    pub lighting_only: bool,
 }
 
 impl DeferredLightRenderer {
@@ -186,6 +192,7 @@ impl DeferredLightRenderer {
             point_light_shader: PointLightShader::new(state)?,
             directional_light_shader: DirectionalLightShader::new(state)?,
             ambient_light_shader: AmbientLightShader::new(state)?,
+            fog_shader: FogShader::new(state)?,
             quad: GeometryBuffer::from_surface_data(
                 &SurfaceData::make_unit_xy_quad(),
                 GeometryBufferKind::StaticDraw,
@@ -306,6 +313,7 @@ impl DeferredLightRenderer {
             normal_dummy,
             white_dummy,
             ambient_color,
+            fog,
             settings,
             textures,
             geometry_cache,
@@ -313,6 +321,7 @@ impl DeferredLightRenderer {
             black_dummy,
             volume_dummy,
             matrix_storage,
+            lighting_only,
         } = args;
 
         let viewport = Rect::new(0, 0, gbuffer.width, gbuffer.height);
@@ -388,12 +397,28 @@ impl DeferredLightRenderer {
 
         // Ambient light.
         let gbuffer_depth_map = gbuffer.depth();
-        let gbuffer_diffuse_map = gbuffer.diffuse_texture();
+        let gbuffer_diffuse_map = if lighting_only {
+            white_dummy.clone()
+        } else {
+            gbuffer.diffuse_texture()
+        };
         let gbuffer_normal_map = gbuffer.normal_texture();
         let gbuffer_material_map = gbuffer.material_texture();
         let gbuffer_ambient_map = gbuffer.ambient_texture();
         let ao_map = self.ssao_renderer.ao_map();
 
+        // Image-based lighting is used only when prefiltered maps of the environment are ready.
+        let environment_maps = if scene.environment.enabled {
+            scene.environment.prefiltered().and_then(|prefiltered| {
+                Some((
+                    textures.get(state, prefiltered.irradiance())?,
+                    textures.get(state, prefiltered.specular())?,
+                ))
+            })
+        } else {
+            None
+        };
+
         pass_stats += frame_buffer.draw(
             &self.quad,
             state,
@@ -431,6 +456,49 @@ impl DeferredLightRenderer {
                     .set_texture(
                         &self.ambient_light_shader.ambient_texture,
                         &gbuffer_ambient_map,
+                    )
+                    .set_texture(&self.ambient_light_shader.depth_texture, &gbuffer_depth_map)
+                    .set_texture(
+                        &self.ambient_light_shader.normal_texture,
+                        &gbuffer_normal_map,
+                    )
+                    .set_texture(
+                        &self.ambient_light_shader.material_texture,
+                        &gbuffer_material_map,
+                    )
+                    .set_texture(
+                        &self.ambient_light_shader.irradiance_texture,
+                        environment_maps
+                            .as_ref()
+                            .map(|(irradiance, _)| irradiance)
+                            .unwrap_or(&black_dummy),
+                    )
+                    .set_texture(
+                        &self.ambient_light_shader.specular_texture,
+                        environment_maps
+                            .as_ref()
+                            .map(|(_, specular)| specular)
+                            .unwrap_or(&black_dummy),
+                    )
+                    .set_bool(
+                        &self.ambient_light_shader.environment_enabled,
+                        environment_maps.is_some(),
+                    )
+                    .set_f32(
+                        &self.ambient_light_shader.environment_intensity,
+                        scene.environment.intensity,
+                    )
+                    .set_f32(
+                        &self.ambient_light_shader.specular_max_lod,
+                        (SPECULAR_MAP_MIP_COUNT - 1) as f32,
+                    )
+                    .set_matrix4(
+                        &self.ambient_light_shader.inv_view_proj_matrix,
+                        &inv_view_projection,
+                    )
+                    .set_vector3(
+                        &self.ambient_light_shader.camera_position,
+                        &camera_global_position,
                     );
             },
         )?;
@@ -873,6 +941,46 @@ impl DeferredLightRenderer {
             }
         }
 
+        // Fog is applied on top of lit geometry, destination alpha is kept intact.
+        if let Some(fog) = fog {
+            let shader = &self.fog_shader;
+            pass_stats += frame_buffer.draw(
+                &self.quad,
+                state,
+                viewport,
+                &shader.program,
+                &DrawParameters {
+                    cull_face: None,
+                    color_write: Default::default(),
+                    depth_write: false,
+                    stencil_test: None,
+                    depth_test: false,
+                    blend: Some(BlendParameters {
+                        func: BlendFunc::new_separate(
+                            BlendFactor::SrcAlpha,
+                            BlendFactor::OneMinusSrcAlpha,
+                            BlendFactor::Zero,
+                            BlendFactor::One,
+                        ),
+                        ..Default::default()
+                    }),
+                    stencil_op: Default::default(),
+                },
+                ElementRange::Full,
+                |mut program_binding| {
+                    program_binding
+                        .set_matrix4(&shader.wvp_matrix, &frame_matrix)
+                        .set_texture(&shader.depth_sampler, &gbuffer_depth_map)
+                        .set_matrix4(&shader.inv_view_proj_matrix, &inv_view_projection)
+                        .set_vector3(&shader.camera_position, &camera_global_position)
+                        .set_linear_color(&shader.fog_color, &fog.color)
+                        .set_f32(&shader.fog_density, fog.density)
+                        .set_f32(&shader.fog_start, fog.start)
+                        .set_f32(&shader.fog_end, fog.end);
+                },
+            )?;
+        }
+
         Ok((pass_stats, light_stats))
     }
 }
@@ -28,11 +28,13 @@ mod gbuffer;
 mod hdr;
 mod light;
 mod light_volume;
+mod overdraw;
 mod particle_system_renderer;
 mod shadow;
 mod skybox_shader;
 mod sprite_renderer;
 mod ssao;
+mod water_renderer;
 
 use crate::material::shader::{ShaderResource, ShaderResourceExtension};
 use crate::renderer::batch::PersistentIdentifier;
@@ -76,15 +78,20 @@ use crate::{
         gbuffer::{GBuffer, GBufferRenderContext},
         hdr::HighDynamicRangeRenderer,
         light::{DeferredLightRenderer, DeferredRendererContext, LightingStatistics},
+        overdraw::{OverdrawRenderContext, OverdrawRenderer},
         particle_system_renderer::{ParticleSystemRenderContext, ParticleSystemRenderer},
         renderer2d::Renderer2d,
         sprite_renderer::{SpriteRenderContext, SpriteRenderer},
         ui_renderer::{UiRenderContext, UiRenderer},
+        water_renderer::{WaterReflection, WaterRenderContext, WaterRenderer},
     },
     resource::texture::{Texture, TextureKind, TextureResource},
-    scene::{camera::Camera, mesh::surface::SurfaceData, Scene, SceneContainer},
+    scene::{
+        camera::Camera, fog::FogSettings, mesh::surface::SurfaceData,
+        post_processing::PostProcessingSettings, Scene, SceneContainer,
+    },
 };
-use fxhash::FxHashMap;
+use fxhash::{FxHashMap, FxHashSet};
 use glow::HasContext;
 #[cfg(not(target_arch = "wasm32"))]
 use glutin::{
@@ -168,6 +175,13 @@ pub struct RenderPassStatistics {
     pub draw_calls: usize,
     /// Amount of triangles per frame.
     pub triangles_rendered: usize,
+    /// Amount of batches with more than one instance of the same surface data and material. Keep in mind,
+    /// that each instance is still a separate draw call. See [`batch::RenderDataBatch::is_shared`] for
+    /// more info.
+    pub shared_batches: usize,
+    /// Amount of nodes that passed culling and were rendered. See
+    /// [`batch::RenderDataBatchStorage::visible_node_count`] for more info.
+    pub visible_nodes: usize,
 }
 
 impl Display for RenderPassStatistics {
@@ -175,8 +189,10 @@ impl Display for RenderPassStatistics {
         write!(
             f,
             "Draw Calls: {}\n\
-            Triangles Rendered: {}",
-            self.draw_calls, self.triangles_rendered
+            Triangles Rendered: {}\n\
+            Shared Batches: {}\n\
+            Visible Nodes: {}",
+            self.draw_calls, self.triangles_rendered, self.shared_batches, self.visible_nodes
         )
     }
 }
@@ -185,6 +201,8 @@ impl std::ops::AddAssign for RenderPassStatistics {
     fn add_assign(&mut self, rhs: Self) {
         self.draw_calls += rhs.draw_calls;
         self.triangles_rendered += rhs.triangles_rendered;
+        self.shared_batches += rhs.shared_batches;
+        self.visible_nodes += rhs.visible_nodes;
     }
 }
 
@@ -255,6 +273,72 @@ impl Default for CsmSettings {
     }
 }
 
+/// Parameters of the fog pass of the renderer. See [`FogSettings`] docs for more info.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct FogParameters {
+    /// Color of the fog.
+    pub color: Color,
+    /// Max opacity of the fog.
+    pub density: f32,
+    /// Distance from the camera at which the fog starts.
+    pub start: f32,
+    /// Distance from the camera at which the fog reaches its maximum opacity.
+    pub end: f32,
+}
+
+impl FogParameters {
+    /// Returns parameters of the fog pass for given fog settings of a scene, or `None` if the fog
+    /// is disabled and the pass must be skipped.
+    pub fn from_settings(settings: &FogSettings) -> Option<Self> {
+        if settings.enabled {
+            Some(Self {
+                color: settings.color,
+                density: settings.density,
+                start: settings.start,
+                end: settings.end,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// Debug view defines what the renderer outputs into the final frame of a camera. It is useful for
+/// debugging rendering issues, for example the editor uses it to show wireframe or normals of a
+/// scene. Debug view is set per camera (see [`Camera::set_debug_view`]) and it is not serialized.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DebugView {
+    /// Normal rendering with full lighting and post-processing.
+    Shaded,
+    /// Edges of polygons only.
+    Wireframe,
+    /// World-space normals from the G-Buffer.
+    Normals,
+    /// Every surface is drawn with additive blending and without depth test, brighter pixels are
+    /// drawn more times.
+    Overdraw,
+    /// Lighting with white albedo, shows only the contribution of the light sources.
+    LightingOnly,
+}
+
+impl Default for DebugView {
+    fn default() -> Self {
+        Self::Shaded
+    }
+}
+
+impl DebugView {
+    /// Returns polygon fill mode that should be used to render a scene with the given rasterization
+    /// mode.
+    pub fn polygon_fill_mode(self, scene_mode: PolygonFillMode) -> PolygonFillMode {
+        if self == DebugView::Wireframe {
+            PolygonFillMode::Line
+        } else {
+            scene_mode
+        }
+    }
+}
+
 /// Quality settings allows you to find optimal balance between performance and
 /// graphics quality.
 #[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize, Reflect)]
@@ -315,6 +399,18 @@ impl Default for QualitySettings {
 }
 
 impl QualitySettings {
+    /// Returns a copy of the settings, that takes post-processing settings of a scene into account. An effect
+    /// is used only if it is enabled in both, the quality settings and the scene settings. SSAO radius of the
+    /// scene overrides the radius of the quality settings only if it is set explicitly.
+    pub fn with_post_processing(&self, post_processing: &PostProcessingSettings) -> Self {
+        Self {
+            use_ssao: self.use_ssao && post_processing.ssao.enabled,
+            ssao_radius: post_processing.ssao.radius.unwrap_or(self.ssao_radius),
+            use_bloom: self.use_bloom && post_processing.bloom.enabled,
+            ..*self
+        }
+    }
+
     /// Highest possible graphics quality. Requires very powerful GPU.
     pub fn ultra() -> Self {
         Self {
@@ -718,12 +814,16 @@ pub struct Renderer {
     pub debug_renderer: DebugRenderer,
     /// A set of associated data for each scene that was rendered.
     pub scene_data_map: FxHashMap<Handle<Scene>, AssociatedSceneData>,
+    // TextureId -> AssociatedSceneData mapping for cameras with their own render targets.
+    camera_data_map: FxHashMap<usize, AssociatedSceneData>,
     backbuffer_clear_color: Color,
     /// Texture cache with GPU textures.
     pub texture_cache: TextureCache,
     shader_cache: ShaderCache,
     geometry_cache: GeometryCache,
     forward_renderer: ForwardRenderer,
+    water_renderer: WaterRenderer,
+    overdraw_renderer: OverdrawRenderer,
     fxaa_renderer: FxaaRenderer,
     renderer2d: Renderer2d,
     texture_event_receiver: Receiver<ResourceEvent>,
@@ -1232,10 +1332,13 @@ impl Renderer {
             quality_settings: settings,
             debug_renderer: DebugRenderer::new(&mut state)?,
             scene_data_map: Default::default(),
+            camera_data_map: Default::default(),
             backbuffer_clear_color: Color::BLACK,
             texture_cache: Default::default(),
             geometry_cache: Default::default(),
             forward_renderer: ForwardRenderer::new(),
+            water_renderer: WaterRenderer::new(&mut state)?,
+            overdraw_renderer: OverdrawRenderer::new(&mut state)?,
             ui_frame_buffers: Default::default(),
             fxaa_renderer: FxaaRenderer::new(&mut state)?,
             statistics: Statistics::default(),
@@ -1496,6 +1599,8 @@ impl Renderer {
         let dt = self.statistics.capped_frame_time;
         self.statistics.begin_frame();
 
+        self.water_renderer.begin_frame(scenes, dt);
+
         let window_viewport = Rect::new(0, 0, self.frame_size.0 as i32, self.frame_size.1 as i32);
         self.backbuffer.clear(
             &mut self.state,
@@ -1508,8 +1613,13 @@ impl Renderer {
         let backbuffer_width = self.frame_size.0 as f32;
         let backbuffer_height = self.frame_size.1 as f32;
 
+        let mut used_camera_targets = FxHashSet::default();
+
         for (scene_handle, scene) in scenes.pair_iter().filter(|(_, s)| s.enabled) {
             let graph = &scene.graph;
+            let quality_settings = self
+                .quality_settings
+                .with_post_processing(&scene.post_processing);
 
             let frame_size = scene
                 .render_target
@@ -1582,8 +1692,145 @@ impl Renderer {
                 .linear_iter()
                 .filter_map(|node| node.cast::<Camera>().filter(|&camera| camera.is_enabled()))
             {
+                // Cameras with their own render target use separate associated data, the size of which
+                // matches the size of the target.
+                let (scene_associated_data, frame_size) = if let Some(rt) = camera.render_target() {
+                    let rt_size =
+                        if let TextureKind::Rectangle { width, height } = rt.data_ref().kind() {
+                            Vector2::new(width as f32, height as f32).sup(&Vector2::new(1.0, 1.0))
+                        } else {
+                            // Only rectangle textures can be used as render target.
+                            continue;
+                        };
+
+                    let data = match self.camera_data_map.entry(rt.key()) {
+                        Entry::Occupied(entry) => {
+                            let data = entry.into_mut();
+                            if data.gbuffer.width != rt_size.x as i32
+                                || data.gbuffer.height != rt_size.y as i32
+                            {
+                                *data = AssociatedSceneData::new(
+                                    state,
+                                    rt_size.x as usize,
+                                    rt_size.y as usize,
+                                )?;
+                            }
+                            data
+                        }
+                        Entry::Vacant(entry) => entry.insert(AssociatedSceneData::new(
+                            state,
+                            rt_size.x as usize,
+                            rt_size.y as usize,
+                        )?),
+                    };
+
+                    self.texture_cache.map.insert(
+                        rt.key(),
+                        CacheEntry {
+                            value: data.ldr_scene_frame_texture(),
+                            time_to_live: f32::INFINITY,
+                            value_hash: 0,
+                        },
+                    );
+                    used_camera_targets.insert(rt.key());
+
+                    (data, rt_size)
+                } else {
+                    (&mut *scene_associated_data, frame_size)
+                };
+
                 let viewport = camera.viewport_pixels(frame_size);
 
+                // Billboards must face the camera that is currently rendering the graph.
+                graph.apply_billboards(camera.global_position());
+
+                // Render reflections of water surfaces first, they will be used later on when
+                // rendering the surfaces themselves.
+                let mut water_reflections = Vec::new();
+                for reflection in WaterReflection::collect(graph, camera) {
+                    let reflection_data = self.water_renderer.reflection_target(
+                        state,
+                        scene_handle,
+                        reflection.water,
+                        viewport,
+                    )?;
+                    let reflection_viewport = Rect::new(
+                        0,
+                        0,
+                        reflection_data.gbuffer.width,
+                        reflection_data.gbuffer.height,
+                    );
+                    let reflection_camera = &reflection.camera;
+
+                    let reflection_batch_storage = RenderDataBatchStorage::from_graph(
+                        graph,
+                        ObserverInfo {
+                            observer_position: reflection_camera.global_position(),
+                            z_near: reflection_camera.projection().z_near(),
+                            z_far: reflection_camera.projection().z_far(),
+                            view_matrix: reflection_camera.view_matrix(),
+                            projection_matrix: reflection_camera.projection_matrix(),
+                            render_mask: reflection_camera.render_mask().0,
+                            occlusion_culling: false,
+                        },
+                        GBUFFER_PASS_NAME.clone(),
+                    );
+
+                    self.statistics += reflection_data.gbuffer.fill(GBufferRenderContext {
+                        state,
+                        camera: reflection_camera,
+                        geom_cache: &mut self.geometry_cache,
+                        batch_storage: &reflection_batch_storage,
+                        texture_cache: &mut self.texture_cache,
+                        shader_cache: &mut self.shader_cache,
+                        environment_dummy: self.environment_dummy.clone(),
+                        use_parallax_mapping: quality_settings.use_parallax_mapping,
+                        normal_dummy: self.normal_dummy.clone(),
+                        white_dummy: self.white_dummy.clone(),
+                        black_dummy: self.black_dummy.clone(),
+                        volume_dummy: self.volume_dummy.clone(),
+                        graph,
+                        matrix_storage: &mut self.matrix_storage,
+                    })?;
+
+                    reflection_data.copy_depth_stencil_to_scene_framebuffer(state);
+
+                    reflection_data.hdr_scene_framebuffer.clear(
+                        state,
+                        reflection_viewport,
+                        Some(self.backbuffer_clear_color),
+                        None,
+                        Some(0),
+                    );
+
+                    let (pass_stats, light_stats) =
+                        self.deferred_light_renderer
+                            .render(DeferredRendererContext {
+                                state,
+                                scene,
+                                camera: reflection_camera,
+                                gbuffer: &mut reflection_data.gbuffer,
+                                white_dummy: self.white_dummy.clone(),
+                                ambient_color: scene.ambient_lighting_color,
+                                fog: FogParameters::from_settings(&scene.fog),
+                                settings: &quality_settings,
+                                textures: &mut self.texture_cache,
+                                geometry_cache: &mut self.geometry_cache,
+                                frame_buffer: &mut reflection_data.hdr_scene_framebuffer,
+                                shader_cache: &mut self.shader_cache,
+                                normal_dummy: self.normal_dummy.clone(),
+                                black_dummy: self.black_dummy.clone(),
+                                volume_dummy: self.volume_dummy.clone(),
+                                matrix_storage: &mut self.matrix_storage,
+                                lighting_only: camera.debug_view() == DebugView::LightingOnly,
+                            })?;
+
+                    self.statistics.lighting += light_stats;
+                    self.statistics.geometry += pass_stats;
+
+                    water_reflections.push(reflection.water);
+                }
+
                 let batch_storage = RenderDataBatchStorage::from_graph(
                     graph,
                     ObserverInfo {
@@ -1592,13 +1839,20 @@ impl Renderer {
                         z_far: camera.projection().z_far(),
                         view_matrix: camera.view_matrix(),
                         projection_matrix: camera.projection_matrix(),
+                        render_mask: camera.render_mask().0,
+                        occlusion_culling: camera.occlusion_culling(),
                     },
                     GBUFFER_PASS_NAME.clone(),
                 );
 
+                self.statistics.geometry.shared_batches += batch_storage.shared_batch_count();
+                self.statistics.geometry.visible_nodes += batch_storage.visible_node_count();
+
                 state.set_polygon_fill_mode(
                     PolygonFace::FrontAndBack,
-                    scene.polygon_rasterization_mode,
+                    camera
+                        .debug_view()
+                        .polygon_fill_mode(scene.polygon_rasterization_mode),
                 );
 
                 self.statistics += scene_associated_data.gbuffer.fill(GBufferRenderContext {
@@ -1609,7 +1863,7 @@ impl Renderer {
                     texture_cache: &mut self.texture_cache,
                     shader_cache: &mut self.shader_cache,
                     environment_dummy: self.environment_dummy.clone(),
-                    use_parallax_mapping: self.quality_settings.use_parallax_mapping,
+                    use_parallax_mapping: quality_settings.use_parallax_mapping,
                     normal_dummy: self.normal_dummy.clone(),
                     white_dummy: self.white_dummy.clone(),
                     black_dummy: self.black_dummy.clone(),
@@ -1639,7 +1893,8 @@ impl Renderer {
                             gbuffer: &mut scene_associated_data.gbuffer,
                             white_dummy: self.white_dummy.clone(),
                             ambient_color: scene.ambient_lighting_color,
-                            settings: &self.quality_settings,
+                            fog: FogParameters::from_settings(&scene.fog),
+                            settings: &quality_settings,
                             textures: &mut self.texture_cache,
                             geometry_cache: &mut self.geometry_cache,
                             frame_buffer: &mut scene_associated_data.hdr_scene_framebuffer,
@@ -1648,6 +1903,7 @@ impl Renderer {
                             black_dummy: self.black_dummy.clone(),
                             volume_dummy: self.volume_dummy.clone(),
                             matrix_storage: &mut self.matrix_storage,
+                            lighting_only: camera.debug_view() == DebugView::LightingOnly,
                         })?;
 
                 self.statistics.lighting += light_stats;
@@ -1700,7 +1956,7 @@ impl Renderer {
                     batch_storage: &batch_storage,
                     framebuffer: &mut scene_associated_data.hdr_scene_framebuffer,
                     viewport,
-                    quality_settings: &self.quality_settings,
+                    quality_settings: &quality_settings,
                     white_dummy: self.white_dummy.clone(),
                     normal_dummy: self.normal_dummy.clone(),
                     black_dummy: self.black_dummy.clone(),
@@ -1708,6 +1964,16 @@ impl Renderer {
                     matrix_storage: &mut self.matrix_storage,
                 })?;
 
+                self.statistics += self.water_renderer.render(WaterRenderContext {
+                    state,
+                    framebuffer: &mut scene_associated_data.hdr_scene_framebuffer,
+                    graph,
+                    camera,
+                    viewport,
+                    scene_handle,
+                    reflections: &water_reflections,
+                })?;
+
                 for render_pass in self.scene_render_passes.iter() {
                     self.statistics +=
                         render_pass
@@ -1716,7 +1982,7 @@ impl Renderer {
                                 pipeline_state: state,
                                 texture_cache: &mut self.texture_cache,
                                 geometry_cache: &mut self.geometry_cache,
-                                quality_settings: &self.quality_settings,
+                                quality_settings: &quality_settings,
                                 batch_storage: &batch_storage,
                                 viewport,
                                 scene,
@@ -1738,17 +2004,23 @@ impl Renderer {
                 let quad = &self.quad;
 
                 // Prepare glow map.
-                self.statistics.geometry += scene_associated_data.bloom_renderer.render(
-                    state,
-                    quad,
-                    scene_associated_data.hdr_scene_frame_texture(),
-                )?;
+                let bloom_texture = if quality_settings.use_bloom {
+                    self.statistics.geometry += scene_associated_data.bloom_renderer.render(
+                        state,
+                        quad,
+                        scene_associated_data.hdr_scene_frame_texture(),
+                        scene.post_processing.bloom.threshold,
+                    )?;
+                    scene_associated_data.bloom_renderer.result()
+                } else {
+                    self.black_dummy.clone()
+                };
 
                 // Convert high dynamic range frame to low dynamic range (sRGB) with tone mapping and gamma correction.
                 self.statistics.geometry += scene_associated_data.hdr_renderer.render(
                     state,
                     scene_associated_data.hdr_scene_frame_texture(),
-                    scene_associated_data.bloom_renderer.result(),
+                    bloom_texture,
                     &mut scene_associated_data.ldr_scene_framebuffer,
                     viewport,
                     quad,
@@ -1756,11 +2028,12 @@ impl Renderer {
                     camera.exposure(),
                     camera.color_grading_lut_ref(),
                     camera.color_grading_enabled(),
+                    scene.post_processing.tone_mapping.enabled,
                     &mut self.texture_cache,
                 )?;
 
                 // Apply FXAA if needed.
-                if self.quality_settings.fxaa {
+                if quality_settings.fxaa {
                     self.statistics.geometry += self.fxaa_renderer.render(
                         state,
                         viewport,
@@ -1780,6 +2053,33 @@ impl Renderer {
                     )?;
                 }
 
+                // Replace the frame with debug data if needed.
+                match camera.debug_view() {
+                    DebugView::Normals => {
+                        let quad = &self.quad;
+                        self.statistics.geometry += blit_pixels(
+                            state,
+                            &mut scene_associated_data.ldr_scene_framebuffer,
+                            scene_associated_data.gbuffer.normal_texture(),
+                            &self.flat_shader,
+                            viewport,
+                            quad,
+                        )?;
+                    }
+                    DebugView::Overdraw => {
+                        self.statistics +=
+                            self.overdraw_renderer.render(OverdrawRenderContext {
+                                state,
+                                camera,
+                                geom_cache: &mut self.geometry_cache,
+                                batch_storage: &batch_storage,
+                                framebuffer: &mut scene_associated_data.ldr_scene_framebuffer,
+                                viewport,
+                            })?;
+                    }
+                    DebugView::Shaded | DebugView::Wireframe | DebugView::LightingOnly => {}
+                }
+
                 // Render debug geometry in the LDR frame buffer.
                 self.statistics += self.debug_renderer.render(
                     state,
@@ -1797,7 +2097,7 @@ impl Renderer {
                                 pipeline_state: state,
                                 texture_cache: &mut self.texture_cache,
                                 geometry_cache: &mut self.geometry_cache,
-                                quality_settings: &self.quality_settings,
+                                quality_settings: &quality_settings,
                                 batch_storage: &batch_storage,
                                 viewport,
                                 scene,
@@ -1831,6 +2131,10 @@ impl Renderer {
             }
         }
 
+        // Make sure to drop associated data of the cameras, that do not render to their targets anymore.
+        self.camera_data_map
+            .retain(|key, _| used_camera_targets.contains(key));
+
         self.pipeline_state()
             .set_polygon_fill_mode(PolygonFace::FrontAndBack, PolygonFillMode::Fill);
 
@@ -0,0 +1,123 @@
+//! Overdraw renderer draws every visible surface with additive blending and without depth test, so
+//! brighter pixels of the frame are drawn more times than darker ones. It is used only by
+//! [`super::DebugView::Overdraw`].
+
+use crate::{
+    core::{algebra::Vector4, color::Color, math::Rect, scope_profile, sstorage::ImmutableString},
+    renderer::{
+        batch::RenderDataBatchStorage,
+        cache::geometry::GeometryCache,
+        framework::{
+            error::FrameworkError,
+            framebuffer::{BlendParameters, DrawParameters, FrameBuffer},
+            gpu_program::{GpuProgram, UniformLocation},
+            state::{BlendFactor, BlendFunc, PipelineState},
+        },
+        RenderPassStatistics,
+    },
+    scene::camera::Camera,
+};
+
+struct OverdrawShader {
+    program: GpuProgram,
+    wvp_matrix: UniformLocation,
+    color: UniformLocation,
+}
+
+impl OverdrawShader {
+    fn new(state: &mut PipelineState) -> Result<Self, FrameworkError> {
+        let fragment_source = include_str!("shaders/overdraw_fs.glsl");
+        let vertex_source = include_str!("shaders/overdraw_vs.glsl");
+        let program =
+            GpuProgram::from_source(state, "OverdrawShader", vertex_source, fragment_source)?;
+        Ok(Self {
+            wvp_matrix: program
+                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
+            color: program.uniform_location(state, &ImmutableString::new("color"))?,
+            program,
+        })
+    }
+}
+
+pub(crate) struct OverdrawRenderer {
+    shader: OverdrawShader,
+}
+
+pub(crate) struct OverdrawRenderContext<'a, 'b> {
+    pub state: &'a mut PipelineState,
+    pub camera: &'b Camera,
+    pub geom_cache: &'a mut GeometryCache,
+    pub batch_storage: &'a RenderDataBatchStorage,
+    pub framebuffer: &'a mut FrameBuffer,
+    pub viewport: Rect<i32>,
+}
+
+impl OverdrawRenderer {
+    pub(crate) fn new(state: &mut PipelineState) -> Result<Self, FrameworkError> {
+        Ok(Self {
+            shader: OverdrawShader::new(state)?,
+        })
+    }
+
+    pub(crate) fn render(
+        &self,
+        args: OverdrawRenderContext,
+    ) -> Result<RenderPassStatistics, FrameworkError> {
+        scope_profile!();
+
+        let mut statistics = RenderPassStatistics::default();
+
+        let OverdrawRenderContext {
+            state,
+            camera,
+            geom_cache,
+            batch_storage,
+            framebuffer,
+            viewport,
+        } = args;
+
+        framebuffer.clear(state, viewport, Some(Color::BLACK), None, None);
+
+        let params = DrawParameters {
+            cull_face: None,
+            color_write: Default::default(),
+            depth_write: false,
+            stencil_test: None,
+            depth_test: false,
+            blend: Some(BlendParameters {
+                func: BlendFunc::new(BlendFactor::One, BlendFactor::One),
+                ..Default::default()
+            }),
+            stencil_op: Default::default(),
+        };
+
+        // Every layer adds a bit of "heat", so 10 layers of geometry will saturate the pixel.
+        let color = Vector4::new(0.1, 0.04, 0.01, 1.0);
+        let view_projection = camera.view_projection_matrix();
+
+        for batch in batch_storage.batches.iter() {
+            let geometry = geom_cache.get(state, &batch.data);
+
+            for instance in batch.instances.iter() {
+                statistics += framebuffer.draw(
+                    geometry,
+                    state,
+                    viewport,
+                    &self.shader.program,
+                    &params,
+                    instance.element_range,
+                    |mut program_binding| {
+                        program_binding
+                            .set_matrix4(
+                                &self.shader.wvp_matrix,
+                                &(view_projection * instance.world_transform),
+                            )
+                            .set_vector4(&self.shader.color, &color);
+                    },
+                )?;
+            }
+        }
+
+        Ok(statistics)
+    }
+}
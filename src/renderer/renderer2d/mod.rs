@@ -100,7 +100,7 @@ impl SpriteBatchStorage {
 
         let mut batch_index = 0;
         for node in graph.linear_iter() {
-            if let Some(rectangle) = node.cast::<Rectangle>() {
+            if let Some(rectangle) = node.query_component_ref::<Rectangle>() {
                 if !rectangle.global_visibility() {
                     continue;
                 }
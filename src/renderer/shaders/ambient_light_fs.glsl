@@ -3,16 +3,54 @@ uniform sampler2D aoSampler;
 uniform sampler2D ambientTexture;
 uniform vec4 ambientColor;
 
+uniform sampler2D depthTexture;
+uniform sampler2D normalTexture;
+uniform sampler2D materialTexture;
+uniform sampler2D irradianceTexture;
+uniform sampler2D specularTexture;
+uniform bool environmentEnabled;
+uniform float environmentIntensity;
+uniform float specularMaxLod;
+uniform mat4 invViewProj;
+uniform vec3 cameraPosition;
+
 out vec4 FragColor;
 in vec2 texCoord;
 
+// Must match `texel_direction` in the environment lighting baker.
+vec2 EquirectangularUV(vec3 direction)
+{
+    return vec2(atan(direction.z, direction.x) / (2.0 * PI) + 0.5, acos(clamp(direction.y, -1.0, 1.0)) / PI);
+}
+
 void main()
 {
     float ambientOcclusion = texture(aoSampler, texCoord).r;
     vec4 ambientPixel = texture(ambientTexture, texCoord);
-    FragColor = (ambientColor + ambientPixel) * S_SRGBToLinear(texture(diffuseTexture, texCoord));
+    vec4 albedo = S_SRGBToLinear(texture(diffuseTexture, texCoord));
+    FragColor = (ambientColor + ambientPixel) * albedo;
+
+    if (environmentEnabled) {
+        vec3 material = texture(materialTexture, texCoord).rgb;
+        float metallic = material.x;
+        float roughness = material.y;
+
+        vec3 fragmentPosition = S_UnProject(vec3(texCoord, texture(depthTexture, texCoord).r), invViewProj);
+        vec3 normal = normalize(texture(normalTexture, texCoord).xyz * 2.0 - 1.0);
+        vec3 viewVector = normalize(cameraPosition - fragmentPosition);
+        vec3 reflection = reflect(-viewVector, normal);
+
+        // Fresnel-Schlick with roughness, it prevents overly bright edges of rough surfaces.
+        vec3 F0 = mix(vec3(0.04), albedo.rgb, metallic);
+        float cosTheta = clamp(dot(normal, viewVector), 0.0, 1.0);
+        vec3 F = F0 + (max(vec3(1.0 - roughness), F0) - F0) * pow(1.0 - cosTheta, 5.0);
+
+        vec3 diffuse = (1.0 - F) * (1.0 - metallic) * albedo.rgb * texture(irradianceTexture, EquirectangularUV(normal)).rgb;
+        vec3 specular = F * textureLod(specularTexture, EquirectangularUV(reflection), roughness * specularMaxLod).rgb;
+
+        FragColor.rgb += environmentIntensity * (diffuse + specular);
+    }
+
     FragColor.rgb *= ambientOcclusion;
     FragColor.a = ambientPixel.a;
-
-    // TODO: Implement IBL.
-}
\ No newline at end of file
+}
@@ -0,0 +1,26 @@
+uniform sampler2D depthTexture;
+uniform mat4 invViewProj;
+uniform vec3 cameraPosition;
+uniform vec4 fogColor;
+uniform float fogDensity;
+uniform float fogStart;
+uniform float fogEnd;
+
+in vec2 texCoord;
+out vec4 FragColor;
+
+void main()
+{
+    float depth = texture(depthTexture, texCoord).r;
+
+    // Pixels without geometry (sky) are not affected by the fog.
+    if (depth >= 1.0) {
+        discard;
+    }
+
+    vec3 fragmentPosition = S_UnProject(vec3(texCoord, depth), invViewProj);
+    float distance = length(fragmentPosition - cameraPosition);
+    float factor = clamp((distance - fogStart) / max(fogEnd - fogStart, 0.0001), 0.0, 1.0);
+
+    FragColor = vec4(fogColor.rgb, factor * clamp(fogDensity, 0.0, 1.0));
+}
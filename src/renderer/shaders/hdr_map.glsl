@@ -8,6 +8,7 @@ uniform float minLuminance;
 uniform float maxLuminance;
 uniform bool autoExposure;
 uniform float fixedExposure;
+uniform bool useToneMapping;
 
 in vec2 texCoord;
 
@@ -36,7 +37,12 @@ void main() {
         exposure = fixedExposure;
     }
 
-    vec4 ldrColor = vec4(vec3(1.0) - exp(-hdrColor.rgb * exposure), hdrColor.a);
+    vec4 ldrColor;
+    if (useToneMapping) {
+        ldrColor = vec4(vec3(1.0) - exp(-hdrColor.rgb * exposure), hdrColor.a);
+    } else {
+        ldrColor = vec4(clamp(hdrColor.rgb, 0.0, 1.0), hdrColor.a);
+    }
 
     if (useColorGrading) {
         outLdrColor = vec4(ColorGrading(S_LinearToSRGB(ldrColor).rgb), ldrColor.a);
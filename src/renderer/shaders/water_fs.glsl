@@ -0,0 +1,27 @@
+uniform sampler2D reflectionTexture;
+uniform vec4 color;
+uniform float distortion;
+uniform float phase;
+
+out vec4 FragColor;
+
+in vec4 clipPosition;
+in vec3 worldPosition;
+
+void main()
+{
+    vec2 screenPosition = (clipPosition.xy / clipPosition.w) * 0.5 + 0.5;
+
+    // Reflection is rendered with horizontally mirrored projection.
+    vec2 reflectionCoords = vec2(1.0 - screenPosition.x, screenPosition.y);
+
+    vec2 waves = vec2(
+        sin(worldPosition.x * 1.7 + phase) + sin(worldPosition.z * 2.3 + phase * 1.3),
+        cos(worldPosition.z * 1.9 + phase) + cos(worldPosition.x * 2.1 + phase * 0.7)
+    );
+    reflectionCoords = clamp(reflectionCoords + waves * 0.5 * distortion, vec2(0.0), vec2(1.0));
+
+    vec3 reflection = texture(reflectionTexture, reflectionCoords).rgb;
+
+    FragColor = vec4(mix(reflection, color.rgb, color.a), 1.0);
+}
@@ -0,0 +1,14 @@
+layout(location = 0) in vec3 vertexPosition;
+
+uniform mat4 worldViewProjection;
+uniform mat4 worldMatrix;
+
+out vec4 clipPosition;
+out vec3 worldPosition;
+
+void main()
+{
+    worldPosition = (worldMatrix * vec4(vertexPosition, 1.0)).xyz;
+    clipPosition = worldViewProjection * vec4(vertexPosition, 1.0);
+    gl_Position = clipPosition;
+}
@@ -217,6 +217,8 @@ impl CsmRenderer {
                     z_far,
                     view_matrix: light_view_matrix,
                     projection_matrix,
+                    render_mask: u32::MAX,
+                    occlusion_culling: false,
                 },
                 DIRECTIONAL_SHADOW_PASS_NAME.clone(),
             );
@@ -278,7 +280,9 @@ impl CsmRenderer {
                             viewport,
                             &render_pass.program,
                             &DrawParameters {
-                                cull_face: Some(CullFace::Back),
+                                cull_face: Some(
+                                    CullFace::Back.for_transform(&instance.world_transform),
+                                ),
                                 color_write: ColorMask::all(false),
                                 depth_write: true,
                                 stencil_test: None,
@@ -244,6 +244,8 @@ impl PointShadowMapRenderer {
                     z_far,
                     view_matrix: light_view_matrix,
                     projection_matrix: light_projection_matrix,
+                    render_mask: u32::MAX,
+                    occlusion_culling: false,
                 },
                 POINT_SHADOW_PASS_NAME.clone(),
             );
@@ -268,7 +270,9 @@ impl PointShadowMapRenderer {
                             state,
                             viewport,
                             &render_pass.program,
-                            &render_pass.draw_params,
+                            &render_pass
+                                .draw_params
+                                .for_transform(&instance.world_transform),
                             instance.element_range,
                             |mut program_binding| {
                                 apply_material(MaterialContext {
@@ -154,6 +154,8 @@ impl SpotShadowMapRenderer {
                 z_far,
                 view_matrix: light_view_matrix,
                 projection_matrix: light_projection_matrix,
+                render_mask: u32::MAX,
+                occlusion_culling: false,
             },
             SPOT_SHADOW_PASS_NAME.clone(),
         );
@@ -179,7 +181,9 @@ impl SpotShadowMapRenderer {
                         viewport,
                         &render_pass.program,
                         &DrawParameters {
-                            cull_face: Some(CullFace::Back),
+                            cull_face: Some(
+                                CullFace::Back.for_transform(&instance.world_transform),
+                            ),
                             color_write: ColorMask::all(false),
                             depth_write: true,
                             stencil_test: None,
@@ -0,0 +1,363 @@
+//! Renders water surfaces with planar reflections. See [`Water`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, UnitQuaternion, Vector3, Vector4},
+        math::Rect,
+        pool::Handle,
+        scope_profile,
+        sstorage::ImmutableString,
+    },
+    renderer::{
+        framework::{
+            error::FrameworkError,
+            framebuffer::{DrawParameters, FrameBuffer},
+            geometry_buffer::{ElementRange, GeometryBuffer, GeometryBufferKind},
+            gpu_program::{GpuProgram, UniformLocation},
+            state::PipelineState,
+        },
+        AssociatedSceneData, RenderPassStatistics,
+    },
+    scene::{
+        camera::Camera, graph::Graph, mesh::surface::SurfaceData, node::Node, water::Water, Scene,
+        SceneContainer,
+    },
+};
+use fxhash::FxHashMap;
+
+struct WaterShader {
+    program: GpuProgram,
+    world_view_projection: UniformLocation,
+    world_matrix: UniformLocation,
+    reflection_texture: UniformLocation,
+    color: UniformLocation,
+    distortion: UniformLocation,
+    phase: UniformLocation,
+}
+
+impl WaterShader {
+    fn new(state: &mut PipelineState) -> Result<Self, FrameworkError> {
+        let fragment_source = include_str!("shaders/water_fs.glsl");
+        let vertex_source = include_str!("shaders/water_vs.glsl");
+        let program =
+            GpuProgram::from_source(state, "WaterShader", vertex_source, fragment_source)?;
+        Ok(Self {
+            world_view_projection: program
+                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
+            world_matrix: program.uniform_location(state, &ImmutableString::new("worldMatrix"))?,
+            reflection_texture: program
+                .uniform_location(state, &ImmutableString::new("reflectionTexture"))?,
+            color: program.uniform_location(state, &ImmutableString::new("color"))?,
+            distortion: program.uniform_location(state, &ImmutableString::new("distortion"))?,
+            phase: program.uniform_location(state, &ImmutableString::new("phase"))?,
+            program,
+        })
+    }
+}
+
+/// Replaces near clipping plane of the given projection matrix with an arbitrary plane (in view space),
+/// the plane must face away from the camera. See "Oblique View Frustum Depth Projection and Clipping"
+/// by Eric Lengyel for more info.
+fn oblique_projection(projection: &Matrix4<f32>, clip_plane: &Vector4<f32>) -> Matrix4<f32> {
+    let q = projection.try_inverse().unwrap_or_default()
+        * Vector4::new(clip_plane.x.signum(), clip_plane.y.signum(), 1.0, 1.0);
+    let c = clip_plane.scale(2.0 / clip_plane.dot(&q));
+
+    let mut result = *projection;
+    for i in 0..4 {
+        result[(2, i)] = c[i] - projection[(3, i)];
+    }
+    result
+}
+
+/// An additional render pass, that renders the scene from a mirrored camera into the reflection texture
+/// of a water surface.
+///
+/// The camera of the pass uses mirrored view matrix, so its projection matrix is also mirrored along X
+/// axis to keep the winding of triangles (and face culling) intact. The resulting image is horizontally
+/// flipped, the water shader takes this into account. Everything below the surface is clipped by the
+/// near plane of the projection.
+pub(crate) struct WaterReflection {
+    /// A handle of the water surface.
+    pub water: Handle<Node>,
+    /// A mirrored camera, that should be used to render the reflection.
+    pub camera: Camera,
+}
+
+impl WaterReflection {
+    /// Returns reflection passes for every water surface visible from the given camera. Surfaces that are
+    /// seen from below do not reflect anything and do not need any pass.
+    pub(crate) fn collect(graph: &Graph, camera: &Camera) -> Vec<Self> {
+        let frustum = camera.frustum();
+        let camera_position = camera.global_position();
+
+        graph
+            .pair_iter()
+            .filter_map(|(handle, node)| {
+                let water = node.cast::<Water>()?;
+
+                if !water.global_visibility()
+                    || !water.is_globally_enabled()
+                    || water.plane().dot(&camera_position) <= 0.0
+                    || !frustum.is_intersects_aabb(&water.world_bounding_box())
+                {
+                    return None;
+                }
+
+                Some(Self {
+                    water: handle,
+                    camera: Self::make_camera(camera, water),
+                })
+            })
+            .collect()
+    }
+
+    fn make_camera(camera: &Camera, water: &Water) -> Camera {
+        let reflection = water.reflection_matrix();
+        let plane = water.plane();
+
+        let view_matrix = camera.view_matrix() * reflection;
+        let clip_plane = view_matrix.try_inverse().unwrap_or_default().transpose()
+            * Vector4::new(plane.normal.x, plane.normal.y, plane.normal.z, plane.d);
+        let projection_matrix = Matrix4::new_nonuniform_scaling(&Vector3::new(-1.0, 1.0, 1.0))
+            * oblique_projection(&camera.projection_matrix(), &clip_plane);
+
+        let mut reflected = camera.clone();
+        reflected.set_matrices(view_matrix, projection_matrix);
+        reflected
+            .global_transform
+            .set(reflection * camera.global_transform());
+        // Reflection is rendered in a separate frame buffer, that matches camera's viewport.
+        reflected.set_viewport(Rect::new(0.0, 0.0, 1.0, 1.0));
+        reflected
+    }
+}
+
+pub(crate) struct WaterRenderContext<'a, 'b, 'c> {
+    pub state: &'a mut PipelineState,
+    pub framebuffer: &'b mut FrameBuffer,
+    pub graph: &'c Graph,
+    pub camera: &'c Camera,
+    pub viewport: Rect<i32>,
+    pub scene_handle: Handle<Scene>,
+    /// Water surfaces with reflections rendered for the current camera.
+    pub reflections: &'c [Handle<Node>],
+}
+
+pub(crate) struct WaterRenderer {
+    shader: WaterShader,
+    quad: GeometryBuffer,
+    reflections: FxHashMap<(Handle<Scene>, Handle<Node>), AssociatedSceneData>,
+    time: f32,
+}
+
+impl WaterRenderer {
+    pub(crate) fn new(state: &mut PipelineState) -> Result<Self, FrameworkError> {
+        // Unit quad in XZ plane, facing towards +Y.
+        let quad = GeometryBuffer::from_surface_data(
+            &SurfaceData::make_quad(&Matrix4::from(UnitQuaternion::from_axis_angle(
+                &Vector3::x_axis(),
+                std::f32::consts::FRAC_PI_2,
+            ))),
+            GeometryBufferKind::StaticDraw,
+            state,
+        );
+
+        Ok(Self {
+            shader: WaterShader::new(state)?,
+            quad,
+            reflections: Default::default(),
+            time: 0.0,
+        })
+    }
+
+    /// Advances wave animation and drops reflections of destroyed water surfaces.
+    pub(crate) fn begin_frame(&mut self, scenes: &SceneContainer, dt: f32) {
+        self.time += dt;
+
+        self.reflections.retain(|(scene, water), _| {
+            scenes
+                .try_get(*scene)
+                .and_then(|scene| scene.graph.try_get(*water))
+                .map_or(false, |node| node.cast::<Water>().is_some())
+        });
+    }
+
+    /// Returns a set of frame buffers, that should be used to render reflection of the given water surface.
+    /// The reflection is rendered in half resolution of the given viewport.
+    pub(crate) fn reflection_target(
+        &mut self,
+        state: &mut PipelineState,
+        scene: Handle<Scene>,
+        water: Handle<Node>,
+        viewport: Rect<i32>,
+    ) -> Result<&mut AssociatedSceneData, FrameworkError> {
+        let width = (viewport.w() / 2).max(1);
+        let height = (viewport.h() / 2).max(1);
+
+        let data = match self.reflections.entry((scene, water)) {
+            std::collections::hash_map::Entry::Occupied(entry) => entry.into_mut(),
+            std::collections::hash_map::Entry::Vacant(entry) => entry.insert(
+                AssociatedSceneData::new(state, width as usize, height as usize)?,
+            ),
+        };
+
+        if data.gbuffer.width != width || data.gbuffer.height != height {
+            *data = AssociatedSceneData::new(state, width as usize, height as usize)?;
+        }
+
+        Ok(data)
+    }
+
+    pub(crate) fn render(
+        &mut self,
+        args: WaterRenderContext,
+    ) -> Result<RenderPassStatistics, FrameworkError> {
+        scope_profile!();
+
+        let mut statistics = RenderPassStatistics::default();
+
+        let WaterRenderContext {
+            state,
+            framebuffer,
+            graph,
+            camera,
+            viewport,
+            scene_handle,
+            reflections,
+        } = args;
+
+        let view_projection = camera.view_projection_matrix();
+
+        for &handle in reflections {
+            let (water, reflection) = match (
+                graph.try_get_of_type::<Water>(handle),
+                self.reflections.get(&(scene_handle, handle)),
+            ) {
+                (Some(water), Some(reflection)) => (water, reflection),
+                _ => continue,
+            };
+
+            let size = water.size();
+            let world_matrix = water.global_transform()
+                * Matrix4::new_nonuniform_scaling(&Vector3::new(size.x, 1.0, size.y));
+            let reflection_texture = reflection.hdr_scene_frame_texture();
+
+            statistics += framebuffer.draw(
+                &self.quad,
+                state,
+                viewport,
+                &self.shader.program,
+                &DrawParameters {
+                    cull_face: None,
+                    color_write: Default::default(),
+                    depth_write: true,
+                    stencil_test: None,
+                    depth_test: true,
+                    blend: None,
+                    stencil_op: Default::default(),
+                },
+                ElementRange::Full,
+                |mut program_binding| {
+                    program_binding
+                        .set_matrix4(
+                            &self.shader.world_view_projection,
+                            &(view_projection * world_matrix),
+                        )
+                        .set_matrix4(&self.shader.world_matrix, &world_matrix)
+                        .set_texture(&self.shader.reflection_texture, &reflection_texture)
+                        .set_linear_color(&self.shader.color, &water.color())
+                        .set_f32(&self.shader.distortion, water.distortion())
+                        .set_f32(&self.shader.phase, self.time * water.wave_speed());
+                },
+            )?;
+        }
+
+        Ok(statistics)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::{Point3, UnitQuaternion, Vector2, Vector3},
+        renderer::water_renderer::WaterReflection,
+        scene::{
+            base::BaseBuilder,
+            camera::{Camera, CameraBuilder},
+            graph::Graph,
+            transform::TransformBuilder,
+            water::{Water, WaterBuilder},
+        },
+    };
+
+    fn camera_at(graph: &mut Graph, position: Vector3<f32>, pitch: f32) -> Camera {
+        let camera = CameraBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(position)
+                    .with_local_rotation(UnitQuaternion::from_axis_angle(&Vector3::x_axis(), pitch))
+                    .build(),
+            ),
+        )
+        .build(graph);
+        graph.update_hierarchical_data();
+
+        let mut camera = graph[camera].cast::<Camera>().unwrap().clone();
+        camera.calculate_matrices(Vector2::new(800.0, 600.0));
+        camera
+    }
+
+    fn ndc(camera: &Camera, point: Vector3<f32>) -> Vector3<f32> {
+        camera
+            .view_projection_matrix()
+            .transform_point(&Point3::from(point))
+            .coords
+    }
+
+    #[test]
+    fn test_water_produces_reflection_pass() {
+        let mut graph = Graph::new();
+        WaterBuilder::new(BaseBuilder::new())
+            .with_size(Vector2::new(100.0, 100.0))
+            .build(&mut graph);
+        let camera = camera_at(&mut graph, Vector3::new(0.0, 5.0, -10.0), 0.4);
+
+        let reflections = WaterReflection::collect(&graph, &camera);
+        assert_eq!(reflections.len(), 1);
+        let reflection = &reflections[0];
+        assert!(graph[reflection.water].cast::<Water>().is_some());
+        assert!(
+            (reflection.camera.global_position() - Vector3::new(0.0, -5.0, -10.0)).norm() < 1.0e-4
+        );
+
+        // A point above the water must be seen by the reflection camera at the same place where
+        // its mirrored copy is seen by the main camera (horizontally flipped).
+        let point = Vector3::new(1.0, 2.0, 5.0);
+        let reflected = ndc(&reflection.camera, point);
+        let mirrored = ndc(&camera, Vector3::new(1.0, -2.0, 5.0));
+        assert!((reflected.x + mirrored.x).abs() < 1.0e-4);
+        assert!((reflected.y - mirrored.y).abs() < 1.0e-4);
+        assert!(reflected.z.abs() <= 1.0);
+
+        // Everything below the water must be clipped.
+        assert!(ndc(&reflection.camera, Vector3::new(0.0, -1.0, 5.0)).z < -1.0);
+    }
+
+    #[test]
+    fn test_no_reflection_pass_from_below() {
+        let mut graph = Graph::new();
+        let water = WaterBuilder::new(BaseBuilder::new())
+            .with_size(Vector2::new(100.0, 100.0))
+            .build(&mut graph);
+        let camera = camera_at(&mut graph, Vector3::new(0.0, -5.0, -10.0), -0.4);
+
+        assert!(WaterReflection::collect(&graph, &camera).is_empty());
+
+        // Invisible surfaces must not produce any passes either.
+        let camera = camera_at(&mut graph, Vector3::new(0.0, 5.0, -10.0), 0.4);
+        graph[water].set_visibility(false);
+        graph.update_hierarchical_data();
+        assert!(WaterReflection::collect(&graph, &camera).is_empty());
+    }
+}
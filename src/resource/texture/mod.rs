@@ -1486,6 +1486,36 @@ impl Texture {
         }
     }
 
+    /// Creates new texture instance with the given amount of mip levels. Data of the levels must be
+    /// stored one after another, starting from the largest one. It may fail only if size of data passed
+    /// in does not match with required.
+    pub fn from_bytes_with_mips(
+        kind: TextureKind,
+        pixel_kind: TexturePixelKind,
+        bytes: Vec<u8>,
+        mip_count: u32,
+        serialize_content: bool,
+    ) -> Option<Self> {
+        let mip_count = mip_count.max(1);
+        let required_size = (0..mip_count as usize)
+            .map(|mip| bytes_in_mip_level(kind, pixel_kind, mip) as usize)
+            .sum::<usize>();
+        if required_size != bytes.len() {
+            None
+        } else {
+            Some(Self {
+                path: Default::default(),
+                kind,
+                data_hash: data_hash(&bytes),
+                bytes: bytes.into(),
+                pixel_kind,
+                mip_count,
+                serialize_content,
+                ..Default::default()
+            })
+        }
+    }
+
     /// Sets new minification filter. It is used when texture becomes smaller.
     pub fn set_minification_filter(&mut self, filter: TextureMinificationFilter) {
         self.minification_filter = filter;
@@ -5,7 +5,7 @@
 use crate::{
     asset::ResourceStateRef,
     core::{
-        algebra::{Matrix4, Vector3},
+        algebra::{Matrix4, UnitQuaternion, Vector3},
         log::Log,
         math::{aabb::AxisAlignedBoundingBox, Matrix4Ext},
         pool::{ErasedHandle, Handle},
@@ -16,7 +16,11 @@ use crate::{
     },
     engine::SerializationContext,
     resource::model::ModelResource,
-    scene::{node::Node, transform::Transform},
+    scene::{
+        graph::event::{GraphEvent, GraphEventBroadcaster},
+        node::Node,
+        transform::Transform,
+    },
     script::{Script, ScriptTrait},
 };
 use std::{
@@ -126,6 +130,9 @@ pub struct LodGroup {
     pub levels: Vec<LevelOfDetail>,
 }
 
+/// Maximum index of a layer, that could be assigned to a scene node. See [`Base::set_layer`].
+pub const MAX_LAYER: u8 = 31;
+
 /// Mobility defines a group for scene node which has direct impact on performance
 /// and capabilities of nodes.
 #[derive(
@@ -202,6 +209,69 @@ impl Visit for Mobility {
     }
 }
 
+/// Defines whether a node should be rotated to face the camera. It is useful for sprites, icons,
+/// health bars, nameplates, etc.
+#[derive(
+    Copy, Clone, PartialEq, Eq, Debug, Visit, Reflect, AsRefStr, EnumString, EnumVariantNames,
+)]
+pub enum BillboardMode {
+    /// The node keeps its own orientation.
+    None,
+    /// The node is rotated so its look vector points at the camera.
+    Full,
+    /// The node is rotated only around world Y axis, so it faces the camera but stays upright.
+    YAxis,
+}
+
+impl Default for BillboardMode {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+impl BillboardMode {
+    /// Orients the given global transform towards the observer position. Position and scale of
+    /// the transform are preserved. Returns `None` if the mode is [`BillboardMode::None`] or the
+    /// orientation cannot be calculated (for example when the observer is at the same position).
+    pub fn apply(
+        self,
+        transform: &Matrix4<f32>,
+        observer_position: &Vector3<f32>,
+    ) -> Option<Matrix4<f32>> {
+        let position = transform.position();
+        let mut direction = observer_position - position;
+
+        let up = match self {
+            BillboardMode::None => return None,
+            BillboardMode::Full => Vector3::y(),
+            BillboardMode::YAxis => {
+                direction.y = 0.0;
+                Vector3::y()
+            }
+        };
+
+        let direction = direction.try_normalize(f32::EPSILON)?;
+        // Looking straight up or down makes Y axis useless as "up" vector.
+        let up = if direction.dot(&up).abs() > 0.999 {
+            Vector3::z()
+        } else {
+            up
+        };
+
+        let scale = Vector3::new(
+            transform.side().norm(),
+            transform.up().norm(),
+            transform.look().norm(),
+        );
+
+        Some(
+            Matrix4::new_translation(&position)
+                * UnitQuaternion::face_towards(&direction, &up).to_homogeneous()
+                * Matrix4::new_nonuniform_scaling(&scale),
+        )
+    }
+}
+
 /// A property value.
 #[derive(Debug, Visit, Reflect, PartialEq, Clone, AsRefStr, EnumString, EnumVariantNames)]
 pub enum PropertyValue {
@@ -290,6 +360,92 @@ impl Visit for InstanceId {
     }
 }
 
+/// Stable unique id of a node. Unlike [`Handle<Node>`], which could change when a scene is saved and loaded back
+/// (or when it is cloned), the id is assigned once when the node is created and then saved together with the node.
+/// It makes the id suitable for external references to nodes, for example save-game systems could store ids of
+/// nodes and then find them using [`crate::scene::graph::Graph::find_by_id`].
+///
+/// Every copy of a node (including instances of prefabs) gets a new id, the only exception is a copy of the entire
+/// graph (see [`crate::scene::graph::Graph::clone`]), that preserves ids of every node.
+#[derive(Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd, Default, Debug, Reflect)]
+#[repr(transparent)]
+#[reflect(hide_all)]
+pub struct NodeId(pub Uuid);
+
+impl NodeId {
+    /// Creates new random node id.
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl Visit for NodeId {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        self.0.visit(name, visitor)
+    }
+}
+
+/// Serialized state of a script, whose type was not registered in the [`SerializationContext`] at the moment when
+/// the node was loaded. It usually happens when a game was changed (or is being recompiled) and some script type
+/// was renamed or temporarily removed. Instead of dropping the script, the node keeps its data as an opaque blob
+/// and writes it back when saved, so the data is not lost. The script could be reattached later, when its type
+/// becomes available again, using [`Base::try_resolve_script`] (or [`crate::scene::graph::Graph::resolve_scripts`]
+/// for the entire graph).
+///
+/// The script is exposed via reflection as a read-only property, so the editor could show it as a placeholder.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Reflect)]
+pub struct UnresolvedScript {
+    #[reflect(
+        read_only,
+        description = "Type uuid of the script. The script will be reattached when its type is registered."
+    )]
+    type_uuid: Uuid,
+    #[reflect(hidden)]
+    data: Vec<u8>,
+}
+
+impl UnresolvedScript {
+    /// Returns type uuid of the script.
+    pub fn type_uuid(&self) -> Uuid {
+        self.type_uuid
+    }
+
+    /// Returns serialized data of the script.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Tries to create an instance of the script and restore its state from the serialized data. Returns `None`
+    /// if there is still no constructor for the script type, or if the data cannot be read by the script.
+    pub fn try_resolve(&self, serialization_context: &SerializationContext) -> Option<Script> {
+        let mut script = serialization_context
+            .script_constructors
+            .try_create(&self.type_uuid)?;
+
+        let mut visitor = match Visitor::load_from_memory(self.data.clone()) {
+            Ok(visitor) => visitor,
+            Err(e) => {
+                Log::err(format!(
+                    "Unable to read data of unresolved script {}. Reason: {:?}",
+                    self.type_uuid, e
+                ));
+                return None;
+            }
+        };
+
+        match script.visit("ScriptData", &mut visitor) {
+            Ok(_) => Some(script),
+            Err(e) => {
+                Log::err(format!(
+                    "Unable to restore data of script {}. Reason: {:?}",
+                    self.type_uuid, e
+                ));
+                None
+            }
+        }
+    }
+}
+
 /// Base scene graph node is a simplest possible node, it is used to build more complex ones using composition.
 /// It contains all fundamental properties for each scene graph nodes, like local and global transforms, name,
 /// lifetime, etc. Base node is a building block for all complex node hierarchies - it contains list of children
@@ -318,6 +474,9 @@ pub struct Base {
     #[reflect(hidden)]
     pub(crate) script_message_sender: Option<Sender<NodeScriptMessage>>,
 
+    #[reflect(hidden)]
+    pub(crate) event_broadcaster: Option<GraphEventBroadcaster>,
+
     // Name is not inheritable, because property inheritance works bad with external 3D models.
     // They use names to search "original" nodes.
     #[reflect(setter = "set_name_internal")]
@@ -349,6 +508,14 @@ pub struct Base {
     #[reflect(setter = "set_cast_shadows")]
     cast_shadows: InheritableVariable<bool>,
 
+    #[reflect(
+        description = "Index of a layer the node belongs to. Cameras render only the nodes whose layers are in their render mask.",
+        min_value = 0.0,
+        max_value = 31.0
+    )]
+    #[reflect(setter = "set_layer")]
+    pub(crate) layer: InheritableVariable<u8>,
+
     /// A set of custom properties that can hold almost any data. It can be used to set additional
     /// properties to scene nodes.
 
@@ -358,6 +525,24 @@ pub struct Base {
     #[reflect(setter = "set_frustum_culling")]
     frustum_culling: InheritableVariable<bool>,
 
+    #[reflect(
+        description = "Whether the node hides the nodes behind it when occlusion culling is enabled on a camera. Its bounding box is treated as a solid box."
+    )]
+    #[reflect(setter = "set_occluder")]
+    occluder: InheritableVariable<bool>,
+
+    #[reflect(
+        description = "Arbitrary notes for designers. They are shown in the editor and do not affect the game."
+    )]
+    #[reflect(setter = "set_notes")]
+    notes: InheritableVariable<String>,
+
+    #[reflect(
+        description = "Defines whether the node is rotated to face the camera. Useful for sprites, icons, nameplates, etc."
+    )]
+    #[reflect(setter = "set_billboard")]
+    billboard: InheritableVariable<BillboardMode>,
+
     #[reflect(hidden)]
     pub(crate) transform_modified: Cell<bool>,
 
@@ -379,6 +564,10 @@ pub struct Base {
     #[reflect(hidden)]
     pub(crate) global_transform: Cell<Matrix4<f32>>,
 
+    // Global transform at the previous update of the graph, `None` if the node wasn't updated yet.
+    #[reflect(hidden)]
+    pub(crate) previous_global_transform: Cell<Option<Matrix4<f32>>>,
+
     // Bone-specific matrix. Non-serializable.
     #[reflect(hidden)]
     pub(crate) inv_bind_pose_transform: Matrix4<f32>,
@@ -398,6 +587,10 @@ pub struct Base {
     #[reflect(hidden)]
     pub(crate) instance_id: InstanceId,
 
+    #[reflect(read_only)]
+    #[reflect(hidden)]
+    pub(crate) node_id: NodeId,
+
     // Current script of the scene node.
     //
     // # Important notes
@@ -407,6 +600,13 @@ pub struct Base {
     #[reflect(setter = "set_script_internal")]
     pub(crate) script: Option<Script>,
 
+    // Serialized state of a script, whose type was unknown at the moment of loading.
+    #[reflect(
+        read_only,
+        description = "A script, whose type is not registered. Its data is preserved until the type becomes available."
+    )]
+    pub(crate) unresolved_script: Option<UnresolvedScript>,
+
     enabled: InheritableVariable<bool>,
 
     #[reflect(hidden)]
@@ -528,6 +728,17 @@ impl Base {
         self.global_transform.get()
     }
 
+    /// Returns global transform matrix of the node at the previous frame (more precisely - the one that the
+    /// node had right before the last update of the graph). It could be used to calculate movement of the
+    /// node during the last frame, for example for velocity-based effects like trails. If the node was
+    /// created during the last frame, the method returns current global transform.
+    #[inline]
+    pub fn previous_global_transform(&self) -> Matrix4<f32> {
+        self.previous_global_transform
+            .get()
+            .unwrap_or_else(|| self.global_transform.get())
+    }
+
     /// Returns inverse of bind pose matrix. Bind pose matrix - is special matrix
     /// for bone nodes, it stores initial transform of bone node at the moment
     /// of "binding" vertices to bones.
@@ -708,6 +919,49 @@ impl Base {
             .set_value_and_mark_modified(frustum_culling)
     }
 
+    /// Returns notes of the node.
+    #[inline]
+    pub fn notes(&self) -> &str {
+        &self.notes
+    }
+
+    /// Sets new notes of the node. Notes are arbitrary text for designers (for example "TODO: fix
+    /// collision"), they're shown in the editor and have no effect on the game. Returns old notes.
+    #[inline]
+    pub fn set_notes(&mut self, notes: String) -> String {
+        self.notes.set_value_and_mark_modified(notes)
+    }
+
+    /// Returns current billboard mode of the node.
+    #[inline]
+    pub fn billboard(&self) -> BillboardMode {
+        *self.billboard
+    }
+
+    /// Sets new billboard mode of the node. Billboard nodes are rotated to face the camera on each
+    /// frame, only global transform of such nodes is changed, local transform stays the same. See
+    /// [`BillboardMode`] for more info. Returns old billboard mode.
+    #[inline]
+    pub fn set_billboard(&mut self, billboard: BillboardMode) -> BillboardMode {
+        self.billboard.set_value_and_mark_modified(billboard)
+    }
+
+    /// Returns `true` if the node is an occluder, `false` - otherwise. See [`Self::set_occluder`]
+    /// for more info.
+    #[inline]
+    pub fn is_occluder(&self) -> bool {
+        *self.occluder
+    }
+
+    /// Marks the node as an occluder. Occluders hide the nodes that are fully behind them from the
+    /// cameras with enabled occlusion culling (see [`crate::scene::camera::Camera::set_occlusion_culling`]).
+    /// World-space bounding box of an occluder is treated as a solid box, so only the nodes that are
+    /// (almost) box-shaped and opaque, such as walls or big buildings, should be marked as occluders.
+    #[inline]
+    pub fn set_occluder(&mut self, occluder: bool) -> bool {
+        self.occluder.set_value_and_mark_modified(occluder)
+    }
+
     /// Returns true if the node should cast shadows, false - otherwise.
     #[inline]
     pub fn cast_shadows(&self) -> bool {
@@ -720,6 +974,29 @@ impl Base {
         self.cast_shadows.set_value_and_mark_modified(cast_shadows)
     }
 
+    /// Returns index of the layer the node belongs to. See [`Self::set_layer`] for more info.
+    #[inline]
+    pub fn layer(&self) -> u8 {
+        *self.layer
+    }
+
+    /// Sets new layer index (`0..32`) of the node and returns previous one. Layers are used to
+    /// filter nodes for rendering: a camera renders a node only if its
+    /// [render mask](crate::scene::camera::Camera::render_mask) has the bit of the node's layer
+    /// set. The same layer could define collision groups of colliders, see
+    /// [`crate::scene::collider::Collider::set_use_layer_groups`]. Values larger than 31 will be
+    /// clamped.
+    #[inline]
+    pub fn set_layer(&mut self, layer: u8) -> u8 {
+        self.layer.set_value_and_mark_modified(layer.min(MAX_LAYER))
+    }
+
+    /// Returns a bit mask with a single bit set at the position of the node's layer.
+    #[inline]
+    pub fn layer_mask(&self) -> u32 {
+        1 << self.layer()
+    }
+
     /// Sets instance id of the node. See [`InstanceId`] for more info.
     ///
     /// ## Important notes
@@ -736,6 +1013,21 @@ impl Base {
         self.instance_id
     }
 
+    /// Sets stable id of the node. See [`NodeId`] for more info.
+    ///
+    /// ## Important notes
+    ///
+    /// Ids must be unique within a graph, otherwise [`crate::scene::graph::Graph::find_by_id`] will return
+    /// any of the nodes with the same id.
+    pub fn set_node_id(&mut self, id: NodeId) {
+        self.node_id = id;
+    }
+
+    /// Returns stable id of the node. See [`NodeId`] for more info.
+    pub fn node_id(&self) -> NodeId {
+        self.node_id
+    }
+
     fn remove_script(&mut self) {
         // Send script to the graph to destroy script instances correctly.
         if let Some(script) = self.script.take() {
@@ -754,10 +1046,30 @@ impl Base {
         }
     }
 
-    /// Sets new script for the scene node.
+    fn script_uuid(&self) -> Option<Uuid> {
+        self.script.as_ref().map(|script| script.id())
+    }
+
+    fn notify_script_changed(&self, old_uuid: Option<Uuid>) {
+        let new_uuid = self.script_uuid();
+        if old_uuid.is_some() || new_uuid.is_some() {
+            if let Some(event_broadcaster) = self.event_broadcaster.as_ref() {
+                event_broadcaster.broadcast(GraphEvent::ScriptChanged {
+                    node: self.self_handle,
+                    old_uuid,
+                    new_uuid,
+                });
+            }
+        }
+    }
+
+    /// Sets new script for the scene node. If the node belongs to a graph, the graph will emit
+    /// [`GraphEvent::ScriptChanged`] event.
     #[inline]
     pub fn set_script(&mut self, script: Option<Script>) {
+        let old_uuid = self.script_uuid();
         self.remove_script();
+        self.unresolved_script = None;
         self.script = script;
         if let Some(sender) = self.script_message_sender.as_ref() {
             if self.script.is_some() {
@@ -766,10 +1078,14 @@ impl Base {
                 }));
             }
         }
+        self.notify_script_changed(old_uuid);
     }
 
     fn set_script_internal(&mut self, script: Option<Script>) -> Option<Script> {
-        std::mem::replace(&mut self.script, script)
+        self.unresolved_script = None;
+        let old_script = std::mem::replace(&mut self.script, script);
+        self.notify_script_changed(old_script.as_ref().map(|script| script.id()));
+        old_script
     }
 
     /// Checks if the node has a script of a particular type. Returns `false` if there is no script
@@ -780,7 +1096,32 @@ impl Base {
     }
 
     /// Tries to cast current script instance (if any) to given type and returns a shared reference
-    /// to it on successful cast.
+    /// to it on successful cast. It is the easiest way to access a script of another node:
+    ///
+    /// ```rust
+    /// # use fyrox::{
+    /// #     core::{pool::Handle, reflect::prelude::*, uuid::Uuid, visitor::prelude::*},
+    /// #     impl_component_provider,
+    /// #     scene::{graph::Graph, node::Node},
+    /// #     script::ScriptTrait,
+    /// # };
+    /// #[derive(Reflect, Visit, Default, Debug, Clone)]
+    /// struct Player {
+    ///     health: f32,
+    /// }
+    ///
+    /// # impl_component_provider!(Player);
+    /// # impl ScriptTrait for Player {
+    /// #     fn id(&self) -> Uuid {
+    /// #         Default::default()
+    /// #     }
+    /// # }
+    /// fn player_health(graph: &Graph, player: Handle<Node>) -> Option<f32> {
+    ///     graph[player].try_get_script::<Player>().map(|p| p.health)
+    /// }
+    /// ```
+    ///
+    /// See also [`crate::scene::graph::Graph::try_get_script_of`], which also checks the handle.
     #[inline]
     pub fn try_get_script<T: ScriptTrait>(&self) -> Option<&T> {
         self.script.as_ref().and_then(|s| s.cast::<T>())
@@ -833,6 +1174,34 @@ impl Base {
         self.script.as_mut()
     }
 
+    /// Returns serialized state of a script, whose type was unknown at the moment when the node was loaded.
+    /// See [`UnresolvedScript`] docs for more info.
+    #[inline]
+    pub fn unresolved_script(&self) -> Option<&UnresolvedScript> {
+        self.unresolved_script.as_ref()
+    }
+
+    /// Tries to reattach a script, whose type was unknown at the moment when the node was loaded. It should
+    /// be called when new script types were registered in the serialization context. Returns `true` if the
+    /// script was restored, `false` - otherwise (if there was nothing to restore, or if the script type is
+    /// still unknown). Restored script will be initialized as any other new script of the node.
+    pub fn try_resolve_script(&mut self, serialization_context: &SerializationContext) -> bool {
+        if self.script.is_some() {
+            return false;
+        }
+
+        if let Some(script) = self
+            .unresolved_script
+            .as_ref()
+            .and_then(|unresolved| unresolved.try_resolve(serialization_context))
+        {
+            self.set_script(Some(script));
+            true
+        } else {
+            false
+        }
+    }
+
     /// Returns a copy of the current script.
     #[inline]
     pub fn script_cloned(&self) -> Option<Script> {
@@ -905,14 +1274,24 @@ impl Default for Base {
     }
 }
 
-// Serializes Option<Script> using given serializer.
-fn visit_opt_script(name: &str, script: &mut Option<Script>, visitor: &mut Visitor) -> VisitResult {
+// Serializes Option<Script> using given serializer. Scripts of unknown types are kept as opaque blobs.
+fn visit_opt_script(
+    name: &str,
+    script: &mut Option<Script>,
+    unresolved: &mut Option<UnresolvedScript>,
+    visitor: &mut Visitor,
+) -> VisitResult {
     let mut region = visitor.enter_region(name)?;
 
-    let mut script_type_uuid = script.as_ref().map(|s| s.id()).unwrap_or_default();
+    let mut script_type_uuid = script
+        .as_ref()
+        .map(|s| s.id())
+        .or_else(|| unresolved.as_ref().map(|u| u.type_uuid))
+        .unwrap_or_default();
     script_type_uuid.visit("TypeUuid", &mut region)?;
 
     if region.is_reading() {
+        *unresolved = None;
         *script = if script_type_uuid.is_nil() {
             None
         } else {
@@ -921,22 +1300,33 @@ fn visit_opt_script(name: &str, script: &mut Option<Script>, visitor: &mut Visit
                 .get::<SerializationContext>()
                 .expect("Visitor blackboard must contain serialization context!");
 
-            Some(
-                serialization_context
-                    .script_constructors
-                    .try_create(&script_type_uuid)
-                    .ok_or_else(|| {
-                        VisitError::User(format!(
-                            "There is no corresponding script constructor for {} type!",
-                            script_type_uuid
-                        ))
-                    })?,
-            )
+            let instance = serialization_context
+                .script_constructors
+                .try_create(&script_type_uuid);
+
+            if instance.is_none() {
+                Log::warn(format!(
+                    "There is no corresponding script constructor for {} type! The script data \
+                    will be preserved and the script will be restored once its type is registered.",
+                    script_type_uuid
+                ));
+
+                *unresolved = Some(UnresolvedScript {
+                    type_uuid: script_type_uuid,
+                    data: region.extract_region("ScriptData")?.save_binary_to_vec()?,
+                });
+            }
+
+            instance
         };
     }
 
     if let Some(script) = script {
         script.visit("ScriptData", &mut region)?;
+    } else if let Some(unresolved) = unresolved {
+        if !region.is_reading() {
+            region.insert_regions(&Visitor::load_from_memory(unresolved.data.clone())?)?;
+        }
     }
 
     Ok(())
@@ -971,7 +1361,12 @@ impl Visit for Base {
         let _ = self.properties.visit("Properties", &mut region);
         let _ = self.frustum_culling.visit("FrustumCulling", &mut region);
         let _ = self.cast_shadows.visit("CastShadows", &mut region);
+        let _ = self.occluder.visit("Occluder", &mut region);
+        let _ = self.notes.visit("Notes", &mut region);
+        let _ = self.billboard.visit("Billboard", &mut region);
+        let _ = self.layer.visit("Layer", &mut region);
         let _ = self.instance_id.visit("InstanceId", &mut region);
+        let _ = self.node_id.visit("NodeId", &mut region);
         let _ = self.enabled.visit("Enabled", &mut region);
 
         // Script visiting may fail for various reasons:
@@ -982,7 +1377,12 @@ impl Visit for Base {
         //
         // None of the reasons are fatal and we should still give an ability to load such node
         // to edit or remove it.
-        if let Err(e) = visit_opt_script("Script", &mut self.script, &mut region) {
+        if let Err(e) = visit_opt_script(
+            "Script",
+            &mut self.script,
+            &mut self.unresolved_script,
+            &mut region,
+        ) {
             // Do not spam with error messages if there is missing `Script` field. It is ok
             // for old scenes not to have script at all.
             if !matches!(e, VisitError::RegionDoesNotExist(_)) {
@@ -1007,7 +1407,11 @@ pub struct BaseBuilder {
     inv_bind_pose_transform: Matrix4<f32>,
     tag: String,
     frustum_culling: bool,
+    occluder: bool,
+    notes: String,
+    billboard: BillboardMode,
     cast_shadows: bool,
+    layer: u8,
     script: Option<Script>,
     instance_id: InstanceId,
     enabled: bool,
@@ -1035,7 +1439,11 @@ impl BaseBuilder {
             inv_bind_pose_transform: Matrix4::identity(),
             tag: Default::default(),
             frustum_culling: true,
+            occluder: false,
+            notes: Default::default(),
+            billboard: Default::default(),
             cast_shadows: true,
+            layer: 0,
             script: None,
             instance_id: InstanceId(Uuid::new_v4()),
             enabled: true,
@@ -1132,6 +1540,27 @@ impl BaseBuilder {
         self
     }
 
+    /// Sets desired notes. See [`Base::set_notes`] for more info.
+    #[inline]
+    pub fn with_notes(mut self, notes: String) -> Self {
+        self.notes = notes;
+        self
+    }
+
+    /// Sets desired billboard mode. See [`Base::set_billboard`] for more info.
+    #[inline]
+    pub fn with_billboard(mut self, billboard: BillboardMode) -> Self {
+        self.billboard = billboard;
+        self
+    }
+
+    /// Sets whether the node is an occluder or not. See [`Base::set_occluder`] for more info.
+    #[inline]
+    pub fn with_occluder(mut self, occluder: bool) -> Self {
+        self.occluder = occluder;
+        self
+    }
+
     /// Sets whether mesh should cast shadows or not.
     #[inline]
     pub fn with_cast_shadows(mut self, cast_shadows: bool) -> Self {
@@ -1139,6 +1568,13 @@ impl BaseBuilder {
         self
     }
 
+    /// Sets desired layer index of the node. See [`Base::set_layer`] for more info.
+    #[inline]
+    pub fn with_layer(mut self, layer: u8) -> Self {
+        self.layer = layer.min(MAX_LAYER);
+        self
+    }
+
     /// Sets desired script of the node.
     #[inline]
     pub fn with_script(mut self, script: Script) -> Self {
@@ -1158,6 +1594,7 @@ impl BaseBuilder {
         Base {
             self_handle: Default::default(),
             script_message_sender: None,
+            event_broadcaster: None,
             name: self.name,
             children: self.children,
             local_transform: self.local_transform,
@@ -1166,6 +1603,7 @@ impl BaseBuilder {
             global_visibility: Cell::new(true),
             parent: Handle::NONE,
             global_transform: Cell::new(Matrix4::identity()),
+            previous_global_transform: Cell::new(None),
             inv_bind_pose_transform: self.inv_bind_pose_transform,
             resource: None,
             original_handle_in_resource: Handle::NONE,
@@ -1177,11 +1615,95 @@ impl BaseBuilder {
             properties: Default::default(),
             transform_modified: Cell::new(false),
             frustum_culling: self.frustum_culling.into(),
+            occluder: self.occluder.into(),
+            notes: self.notes.into(),
+            billboard: self.billboard.into(),
             cast_shadows: self.cast_shadows.into(),
+            layer: self.layer.into(),
             script: self.script,
+            unresolved_script: None,
             instance_id: InstanceId(Uuid::new_v4()),
+            node_id: NodeId::new(),
             enabled: self.enabled.into(),
             global_enabled: Cell::new(true),
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{reflect::prelude::*, uuid::Uuid, visitor::prelude::*},
+        impl_component_provider,
+        scene::{base::BaseBuilder, graph::Graph, pivot::PivotBuilder},
+        script::{Script, ScriptTrait},
+    };
+
+    #[derive(Reflect, Visit, Default, Debug, Clone)]
+    struct Player {
+        health: f32,
+    }
+
+    impl_component_provider!(Player);
+
+    impl ScriptTrait for Player {
+        fn id(&self) -> Uuid {
+            Uuid::from_u128(0x7f1e_2d3c_4b5a_4968_8776_a5b4c3d2e1f0)
+        }
+    }
+
+    #[derive(Reflect, Visit, Default, Debug, Clone)]
+    struct Enemy {
+        damage: f32,
+    }
+
+    impl_component_provider!(Enemy);
+
+    impl ScriptTrait for Enemy {
+        fn id(&self) -> Uuid {
+            Uuid::from_u128(0x1a2b_3c4d_5e6f_4a0b_9c8d_7e6f5a4b3c2d)
+        }
+    }
+
+    #[test]
+    fn test_get_script_by_type() {
+        let mut graph = Graph::new();
+        let player = PivotBuilder::new(
+            BaseBuilder::new().with_script(Script::new(Player { health: 100.0 })),
+        )
+        .build(&mut graph);
+        let empty = PivotBuilder::new(BaseBuilder::new()).build(&mut graph);
+
+        assert_eq!(
+            graph[player].try_get_script::<Player>().unwrap().health,
+            100.0
+        );
+        assert!(graph[player].try_get_script::<Enemy>().is_none());
+        assert!(graph[empty].try_get_script::<Player>().is_none());
+
+        graph[player].try_get_script_mut::<Player>().unwrap().health -= 25.0;
+        assert_eq!(
+            graph.try_get_script_of::<Player>(player).unwrap().health,
+            75.0
+        );
+        assert!(graph.try_get_script_of_mut::<Enemy>(player).is_none());
+        assert!(graph[player].has_script::<Player>());
+    }
+
+    #[test]
+    fn test_notes_save_load() {
+        let mut base = BaseBuilder::new()
+            .with_notes("TODO: fix collision".to_string())
+            .build_base();
+
+        let mut visitor = Visitor::new();
+        base.visit("Base", &mut visitor).unwrap();
+        let data = visitor.save_binary_to_vec().unwrap();
+
+        let mut visitor = Visitor::load_from_memory(data).unwrap();
+        let mut loaded = BaseBuilder::new().build_base();
+        loaded.visit("Base", &mut visitor).unwrap();
+
+        assert_eq!(loaded.notes(), "TODO: fix collision");
+    }
+}
@@ -0,0 +1,329 @@
+//! Scene blackboard is a typed storage for arbitrary data shared between scripts of a scene. See
+//! [`SceneBlackboard`] docs for more info.
+
+use crate::core::{
+    log::Log,
+    uuid::Uuid,
+    visitor::{PodVecView, Visit, VisitResult, Visitor},
+    TypeUuidProvider,
+};
+use fxhash::FxHashMap;
+use std::{
+    any::Any,
+    fmt::{Debug, Formatter},
+};
+
+/// A value that could be stored in a [`SceneBlackboard`]. This trait is implemented automatically for
+/// every type that satisfies its bounds.
+pub trait BlackboardValue: Any + Visit + Debug + Send {
+    /// Creates a boxed copy of the value.
+    fn clone_box(&self) -> Box<dyn BlackboardValue>;
+
+    /// Casts self as `Any`.
+    fn as_any(&self) -> &dyn Any;
+
+    /// Casts self as `Any`.
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T> BlackboardValue for T
+where
+    T: Any + Visit + Debug + Send + Clone,
+{
+    fn clone_box(&self) -> Box<dyn BlackboardValue> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+enum Entry {
+    /// A value that was accessed at least once since the scene was created or loaded.
+    Live(Box<dyn BlackboardValue>),
+    /// Serialized value, that was loaded from a scene, but wasn't accessed yet. The actual type of the
+    /// value is unknown at loading stage, so it is deserialized lazily on first access. Untouched values
+    /// are written back as-is, so they're never lost.
+    Serialized(Vec<u8>),
+}
+
+impl Clone for Entry {
+    fn clone(&self) -> Self {
+        match self {
+            Entry::Live(value) => Entry::Live(value.clone_box()),
+            Entry::Serialized(bytes) => Entry::Serialized(bytes.clone()),
+        }
+    }
+}
+
+impl Debug for Entry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Entry::Live(value) => write!(f, "{value:?}"),
+            Entry::Serialized(bytes) => write!(f, "Serialized ({} bytes)", bytes.len()),
+        }
+    }
+}
+
+/// Scene blackboard is a typed storage for arbitrary data shared between scripts of a scene - for example
+/// game score, team data and so on. There could be only one value of a type at a time, values are identified
+/// by their type uuid. The blackboard is saved together with its scene.
+///
+/// Usually there's no need to use the blackboard directly, use [`crate::script::ScriptContext::scene_data`]
+/// instead.
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox::{
+///     core::{uuid::{uuid, Uuid}, visitor::prelude::*, TypeUuidProvider},
+///     scene::blackboard::SceneBlackboard,
+/// };
+///
+/// #[derive(Visit, Default, Debug, Clone)]
+/// struct GameState {
+///     score: u32,
+/// }
+///
+/// impl TypeUuidProvider for GameState {
+///     fn type_uuid() -> Uuid {
+///         uuid!("4d8e0a5e-3e2a-4d4b-9d61-2a8d6c5f1b7e")
+///     }
+/// }
+///
+/// let mut blackboard = SceneBlackboard::default();
+/// blackboard.get_or_default::<GameState>().score += 10;
+/// assert_eq!(blackboard.get::<GameState>().unwrap().score, 10);
+/// ```
+#[derive(Default, Clone, Debug)]
+pub struct SceneBlackboard {
+    entries: FxHashMap<Uuid, Entry>,
+}
+
+impl SceneBlackboard {
+    fn deserialize<T>(bytes: Vec<u8>) -> T
+    where
+        T: BlackboardValue + TypeUuidProvider + Default,
+    {
+        let mut value = T::default();
+        match Visitor::load_from_memory(bytes) {
+            Ok(mut visitor) => {
+                if let Err(err) = value.visit("Value", &mut visitor) {
+                    Log::warn(format!(
+                        "Unable to deserialize blackboard value {}. Reason: {:?}. Default value will be used.",
+                        T::type_uuid(),
+                        err
+                    ));
+                }
+            }
+            Err(err) => Log::warn(format!(
+                "Corrupted blackboard value {}. Reason: {:?}. Default value will be used.",
+                T::type_uuid(),
+                err
+            )),
+        }
+        value
+    }
+
+    fn make_live<T>(&mut self) -> Option<&mut Entry>
+    where
+        T: BlackboardValue + TypeUuidProvider + Default,
+    {
+        let entry = self.entries.get_mut(&T::type_uuid())?;
+        if let Entry::Serialized(bytes) = entry {
+            *entry = Entry::Live(Box::new(Self::deserialize::<T>(std::mem::take(bytes))));
+        }
+        Some(entry)
+    }
+
+    /// Returns a reference to a value of the given type, or `None` if there's no such value.
+    pub fn get<T>(&mut self) -> Option<&T>
+    where
+        T: BlackboardValue + TypeUuidProvider + Default,
+    {
+        self.get_mut::<T>().map(|value| &*value)
+    }
+
+    /// Returns a reference to a value of the given type, or `None` if there's no such value.
+    pub fn get_mut<T>(&mut self) -> Option<&mut T>
+    where
+        T: BlackboardValue + TypeUuidProvider + Default,
+    {
+        match self.make_live::<T>()? {
+            Entry::Live(value) => value.as_any_mut().downcast_mut::<T>(),
+            Entry::Serialized(_) => unreachable!(),
+        }
+    }
+
+    /// Returns a reference to a value of the given type. If there's no such value, the default one will
+    /// be created and put in the blackboard.
+    pub fn get_or_default<T>(&mut self) -> &mut T
+    where
+        T: BlackboardValue + TypeUuidProvider + Default,
+    {
+        if self.make_live::<T>().is_none() {
+            self.set(T::default());
+        }
+        self.get_mut::<T>()
+            .expect("Type uuid of the value must be unique!")
+    }
+
+    /// Puts a value in the blackboard, replacing the previous value of the same type.
+    pub fn set<T>(&mut self, value: T)
+    where
+        T: BlackboardValue + TypeUuidProvider,
+    {
+        self.entries
+            .insert(T::type_uuid(), Entry::Live(Box::new(value)));
+    }
+
+    /// Removes a value of the given type from the blackboard. Returns `true` if there was such value.
+    pub fn remove<T>(&mut self) -> bool
+    where
+        T: TypeUuidProvider,
+    {
+        self.entries.remove(&T::type_uuid()).is_some()
+    }
+
+    /// Returns `true` if the blackboard contains a value of the given type.
+    pub fn contains<T>(&self) -> bool
+    where
+        T: TypeUuidProvider,
+    {
+        self.entries.contains_key(&T::type_uuid())
+    }
+
+    /// Returns total amount of values in the blackboard.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the blackboard is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Removes every value from the blackboard.
+    pub fn clear(&mut self) {
+        self.entries.clear()
+    }
+}
+
+impl Visit for SceneBlackboard {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        let mut count = self.entries.len() as u32;
+        count.visit("Count", &mut region)?;
+
+        if region.is_reading() {
+            self.entries.clear();
+            for i in 0..count {
+                let mut entry_region = region.enter_region(&format!("Entry{i}"))?;
+
+                let mut type_uuid = Uuid::default();
+                type_uuid.visit("TypeUuid", &mut entry_region)?;
+
+                let mut bytes = Vec::<u8>::new();
+                PodVecView::from_pod_vec(&mut bytes).visit("Data", &mut entry_region)?;
+
+                self.entries.insert(type_uuid, Entry::Serialized(bytes));
+            }
+        } else {
+            for (i, (type_uuid, entry)) in self.entries.iter_mut().enumerate() {
+                let mut entry_region = region.enter_region(&format!("Entry{i}"))?;
+
+                let mut type_uuid = *type_uuid;
+                type_uuid.visit("TypeUuid", &mut entry_region)?;
+
+                let mut bytes = match entry {
+                    Entry::Live(value) => {
+                        let mut visitor = Visitor::new();
+                        value.visit("Value", &mut visitor)?;
+                        visitor.save_binary_to_vec()?
+                    }
+                    Entry::Serialized(bytes) => bytes.clone(),
+                };
+                PodVecView::from_pod_vec(&mut bytes).visit("Data", &mut entry_region)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            uuid::{uuid, Uuid},
+            visitor::prelude::*,
+            TypeUuidProvider,
+        },
+        scene::blackboard::SceneBlackboard,
+    };
+
+    #[derive(Visit, Default, Debug, Clone, PartialEq)]
+    struct Score {
+        value: u32,
+        team: String,
+    }
+
+    impl TypeUuidProvider for Score {
+        fn type_uuid() -> Uuid {
+            uuid!("b0f0d7a4-4a54-4d2d-8c53-7a51c9bdb1a3")
+        }
+    }
+
+    #[derive(Visit, Default, Debug, Clone, PartialEq)]
+    struct Unrelated {
+        flag: bool,
+    }
+
+    impl TypeUuidProvider for Unrelated {
+        fn type_uuid() -> Uuid {
+            uuid!("6a2f0e8b-1c55-4e1e-9a0c-3b7e2d4f9c11")
+        }
+    }
+
+    #[test]
+    fn test_blackboard_serialization() {
+        let mut blackboard = SceneBlackboard::default();
+        *blackboard.get_or_default::<Score>() = Score {
+            value: 42,
+            team: "Red".to_string(),
+        };
+        blackboard.set(Unrelated { flag: true });
+
+        let mut visitor = Visitor::new();
+        blackboard.visit("Blackboard", &mut visitor).unwrap();
+        let bytes = visitor.save_binary_to_vec().unwrap();
+
+        let mut visitor = Visitor::load_from_memory(bytes).unwrap();
+        let mut loaded = SceneBlackboard::default();
+        loaded.visit("Blackboard", &mut visitor).unwrap();
+        assert_eq!(loaded.len(), 2);
+
+        // Saving a loaded blackboard must preserve values that weren't accessed yet.
+        let mut visitor = Visitor::new();
+        loaded.visit("Blackboard", &mut visitor).unwrap();
+        let bytes = visitor.save_binary_to_vec().unwrap();
+        let mut visitor = Visitor::load_from_memory(bytes).unwrap();
+        let mut loaded = SceneBlackboard::default();
+        loaded.visit("Blackboard", &mut visitor).unwrap();
+
+        assert_eq!(
+            loaded.get::<Score>(),
+            Some(&Score {
+                value: 42,
+                team: "Red".to_string()
+            })
+        );
+        assert_eq!(loaded.get::<Unrelated>(), Some(&Unrelated { flag: true }));
+    }
+}
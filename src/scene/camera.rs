@@ -14,11 +14,13 @@ use crate::{
         visitor::{Visit, VisitResult, Visitor},
         TypeUuidProvider,
     },
+    renderer::DebugView,
     resource::texture::{
         TextureKind, TexturePixelKind, TextureResource, TextureResourceExtension, TextureWrapMode,
     },
     scene::{
-        base::{Base, BaseBuilder},
+        base::{Base, BaseBuilder, MAX_LAYER},
+        collider::BitMask,
         debug::SceneDrawingContext,
         graph::Graph,
         node::{Node, NodeTrait, UpdateContext},
@@ -327,6 +329,20 @@ pub struct Camera {
     #[reflect(setter = "set_color_grading_enabled")]
     color_grading_enabled: InheritableVariable<bool>,
 
+    #[reflect(
+        description = "A set of layers that will be rendered by the camera. Nodes whose layer is not in the mask are skipped."
+    )]
+    #[reflect(setter = "set_render_mask")]
+    #[visit(optional)]
+    render_mask: InheritableVariable<BitMask>,
+
+    #[reflect(
+        description = "Whether the camera skips the nodes that are fully hidden behind occluders."
+    )]
+    #[reflect(setter = "set_occlusion_culling")]
+    #[visit(optional)]
+    occlusion_culling: InheritableVariable<bool>,
+
     #[visit(skip)]
     #[reflect(hidden)]
     view_matrix: Matrix4<f32>,
@@ -334,6 +350,14 @@ pub struct Camera {
     #[visit(skip)]
     #[reflect(hidden)]
     projection_matrix: Matrix4<f32>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    render_target: Option<TextureResource>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    debug_view: DebugView,
 }
 
 impl Deref for Camera {
@@ -395,6 +419,18 @@ impl Camera {
         self.projection_matrix = self.projection.matrix(frame_size);
     }
 
+    /// Replaces view and projection matrices, calculated by [`Self::calculate_matrices`]. It is used by
+    /// the renderer to render the scene from "virtual" cameras (for example, mirrored camera for planar
+    /// reflections).
+    pub(crate) fn set_matrices(
+        &mut self,
+        view_matrix: Matrix4<f32>,
+        projection_matrix: Matrix4<f32>,
+    ) {
+        self.view_matrix = view_matrix;
+        self.projection_matrix = projection_matrix;
+    }
+
     /// Sets new viewport in resolution-independent format. In other words
     /// each parameter of viewport defines portion of your current resolution
     /// in percents. In example viewport (0.0, 0.0, 0.5, 1.0) will force camera
@@ -667,6 +703,38 @@ impl Camera {
         *self.color_grading_enabled
     }
 
+    /// Sets new render mask of the camera and returns previous one. Each bit of the mask corresponds
+    /// to a [layer](crate::scene::base::Base::set_layer), the camera renders only the nodes whose
+    /// layer bit is set in the mask. It could be used, for example, to exclude some objects from
+    /// a minimap camera.
+    pub fn set_render_mask(&mut self, mask: BitMask) -> BitMask {
+        self.render_mask.set_value_and_mark_modified(mask)
+    }
+
+    /// Returns current render mask of the camera. See [`Self::set_render_mask`] for more info.
+    pub fn render_mask(&self) -> BitMask {
+        *self.render_mask
+    }
+
+    /// Enables or disables occlusion culling and returns previous state. When enabled, the camera
+    /// skips the nodes that are fully hidden behind [occluders](crate::scene::base::Base::set_occluder).
+    /// Occlusion culling is done on CPU and it is disabled by default, it is worth enabling in big
+    /// levels with lots of large occluders (buildings, walls, etc.).
+    pub fn set_occlusion_culling(&mut self, enabled: bool) -> bool {
+        self.occlusion_culling.set_value_and_mark_modified(enabled)
+    }
+
+    /// Returns `true` if occlusion culling is enabled, `false` - otherwise. See
+    /// [`Self::set_occlusion_culling`] for more info.
+    pub fn occlusion_culling(&self) -> bool {
+        *self.occlusion_culling
+    }
+
+    /// Returns `true` if the camera renders the objects from the given layer, `false` - otherwise.
+    pub fn renders_layer(&self, layer: u8) -> bool {
+        layer <= MAX_LAYER && (self.render_mask.0 & (1 << layer)) != 0
+    }
+
     /// Sets new exposure. See `Exposure` struct docs for more info.
     pub fn set_exposure(&mut self, exposure: Exposure) -> Exposure {
         self.exposure.set_value_and_mark_modified(exposure)
@@ -676,6 +744,35 @@ impl Camera {
     pub fn exposure(&self) -> Exposure {
         *self.exposure
     }
+
+    /// Sets a texture the camera will render the scene to, instead of the frame of the scene, and returns
+    /// previous one. The texture must be a rectangle texture (see
+    /// [`TextureResourceExtension::new_render_target`]), the camera's viewport is calculated relative to
+    /// the texture size. This could be used for live monitors, security cameras, previews, etc. Render
+    /// target is not serialized.
+    pub fn set_render_target(
+        &mut self,
+        render_target: Option<TextureResource>,
+    ) -> Option<TextureResource> {
+        std::mem::replace(&mut self.render_target, render_target)
+    }
+
+    /// Returns current render target of the camera. See [`Self::set_render_target`] for more info.
+    pub fn render_target(&self) -> Option<&TextureResource> {
+        self.render_target.as_ref()
+    }
+
+    /// Sets new debug view of the camera and returns previous one. Debug view affects only the image
+    /// produced by this camera, other cameras (and the scene itself) are not changed. Debug view is not
+    /// serialized. See [`DebugView`] docs for more info.
+    pub fn set_debug_view(&mut self, debug_view: DebugView) -> DebugView {
+        std::mem::replace(&mut self.debug_view, debug_view)
+    }
+
+    /// Returns current debug view of the camera.
+    pub fn debug_view(&self) -> DebugView {
+        self.debug_view
+    }
 }
 
 impl NodeTrait for Camera {
@@ -698,7 +795,19 @@ impl NodeTrait for Camera {
     }
 
     fn update(&mut self, context: &mut UpdateContext) {
-        self.calculate_matrices(context.frame_size);
+        // Cameras with their own render target must use its size, otherwise the image will be stretched.
+        let frame_size = self
+            .render_target
+            .as_ref()
+            .and_then(|rt| {
+                if let TextureKind::Rectangle { width, height } = rt.data_ref().kind() {
+                    Some(Vector2::new(width as f32, height as f32))
+                } else {
+                    None
+                }
+            })
+            .unwrap_or(context.frame_size);
+        self.calculate_matrices(frame_size);
     }
 
     fn debug_draw(&self, ctx: &mut SceneDrawingContext) {
@@ -906,6 +1015,8 @@ pub struct CameraBuilder {
     color_grading_lut: Option<ColorGradingLut>,
     color_grading_enabled: bool,
     projection: Projection,
+    render_mask: BitMask,
+    occlusion_culling: bool,
 }
 
 impl CameraBuilder {
@@ -924,6 +1035,8 @@ impl CameraBuilder {
             color_grading_lut: None,
             color_grading_enabled: false,
             projection: Projection::default(),
+            render_mask: BitMask(u32::MAX),
+            occlusion_culling: false,
         }
     }
 
@@ -981,6 +1094,19 @@ impl CameraBuilder {
         self
     }
 
+    /// Sets desired render mask. See [`Camera::set_render_mask`] for more info.
+    pub fn with_render_mask(mut self, mask: BitMask) -> Self {
+        self.render_mask = mask;
+        self
+    }
+
+    /// Sets whether occlusion culling should be enabled or not. See [`Camera::set_occlusion_culling`]
+    /// for more info.
+    pub fn with_occlusion_culling(mut self, enabled: bool) -> Self {
+        self.occlusion_culling = enabled;
+        self
+    }
+
     /// Sets desired exposure options.
     pub fn with_exposure(mut self, exposure: Exposure) -> Self {
         self.exposure = exposure;
@@ -1009,6 +1135,10 @@ impl CameraBuilder {
             exposure: self.exposure.into(),
             color_grading_lut: self.color_grading_lut.into(),
             color_grading_enabled: self.color_grading_enabled.into(),
+            render_mask: self.render_mask.into(),
+            occlusion_culling: self.occlusion_culling.into(),
+            render_target: None,
+            debug_view: Default::default(),
         }
     }
 
@@ -15,7 +15,7 @@ use crate::{
         TypeUuidProvider,
     },
     scene::{
-        base::{Base, BaseBuilder},
+        base::{Base, BaseBuilder, MAX_LAYER},
         graph::{
             physics::{CoefficientCombineRule, ContactPair, IntersectionPair, PhysicsWorld},
             Graph,
@@ -315,6 +315,16 @@ impl InteractionGroups {
             filter,
         }
     }
+
+    /// Creates new interaction group for a collider that belongs only to the given
+    /// [layer](crate::scene::base::Base::set_layer) and interacts with every other group. Layer
+    /// indices larger than 31 will be clamped.
+    pub fn from_layer(layer: u8) -> Self {
+        Self {
+            memberships: BitMask(1 << layer.min(MAX_LAYER)),
+            filter: BitMask(u32::MAX),
+        }
+    }
 }
 
 impl Default for InteractionGroups {
@@ -468,6 +478,13 @@ pub struct Collider {
     #[reflect(setter = "set_collision_groups")]
     pub(crate) collision_groups: InheritableVariable<InteractionGroups>,
 
+    #[reflect(
+        description = "Whether the collider belongs to the layer of the node only. If set, the filter of collision groups defines a set of layers the collider interacts with.",
+        setter = "set_use_layer_groups"
+    )]
+    #[visit(optional)]
+    pub(crate) use_layer_groups: InheritableVariable<bool>,
+
     #[reflect(setter = "set_solver_groups")]
     pub(crate) solver_groups: InheritableVariable<InteractionGroups>,
 
@@ -492,6 +509,7 @@ impl Default for Collider {
             restitution: InheritableVariable::new_modified(0.0),
             is_sensor: InheritableVariable::new_modified(false),
             collision_groups: Default::default(),
+            use_layer_groups: Default::default(),
             solver_groups: Default::default(),
             friction_combine_rule: Default::default(),
             restitution_combine_rule: Default::default(),
@@ -524,6 +542,7 @@ impl Clone for Collider {
             restitution: self.restitution.clone(),
             is_sensor: self.is_sensor.clone(),
             collision_groups: self.collision_groups.clone(),
+            use_layer_groups: self.use_layer_groups.clone(),
             solver_groups: self.solver_groups.clone(),
             friction_combine_rule: self.friction_combine_rule.clone(),
             restitution_combine_rule: self.restitution_combine_rule.clone(),
@@ -647,6 +666,39 @@ impl Collider {
         *self.collision_groups
     }
 
+    /// Defines whether the collision groups of the collider are derived from the
+    /// [layer](crate::scene::base::Base::set_layer) of the node. If set, the collider is a member
+    /// of its layer only (memberships of [`Self::collision_groups`] are ignored) and the filter of
+    /// the collision groups defines a set of layers the collider interacts with. Changing the layer
+    /// of the node updates collision groups of the collider automatically.
+    ///
+    /// # Performance
+    ///
+    /// This is relatively expensive operation - it forces the physics engine to recalculate contacts,
+    /// perform collision response, etc. Try avoid calling this method each frame for better
+    /// performance.
+    pub fn set_use_layer_groups(&mut self, use_layer_groups: bool) -> bool {
+        self.use_layer_groups
+            .set_value_and_mark_modified(use_layer_groups)
+    }
+
+    /// Returns `true` if the collision groups of the collider are derived from the layer of the
+    /// node. See [`Self::set_use_layer_groups`] for more info.
+    pub fn is_using_layer_groups(&self) -> bool {
+        *self.use_layer_groups
+    }
+
+    /// Returns collision groups that are used by the physics engine. They're either the same as
+    /// [`Self::collision_groups`], or derived from the layer of the node, see
+    /// [`Self::set_use_layer_groups`].
+    pub fn effective_collision_groups(&self) -> InteractionGroups {
+        if *self.use_layer_groups {
+            InteractionGroups::new(BitMask(self.layer_mask()), self.collision_groups.filter)
+        } else {
+            *self.collision_groups
+        }
+    }
+
     /// Sets the new joint solver filtering options. See [`InteractionGroups`] docs for more info.
     ///
     /// # Performance
@@ -744,6 +796,8 @@ impl Collider {
             || self.restitution.need_sync()
             || self.is_sensor.need_sync()
             || self.collision_groups.need_sync()
+            || self.use_layer_groups.need_sync()
+            || self.layer.need_sync()
             || self.solver_groups.need_sync()
             || self.friction_combine_rule.need_sync()
             || self.restitution_combine_rule.need_sync()
@@ -808,6 +862,7 @@ pub struct ColliderBuilder {
     restitution: f32,
     is_sensor: bool,
     collision_groups: InteractionGroups,
+    use_layer_groups: bool,
     solver_groups: InteractionGroups,
     friction_combine_rule: CoefficientCombineRule,
     restitution_combine_rule: CoefficientCombineRule,
@@ -824,6 +879,7 @@ impl ColliderBuilder {
             restitution: 0.0,
             is_sensor: false,
             collision_groups: Default::default(),
+            use_layer_groups: false,
             solver_groups: Default::default(),
             friction_combine_rule: Default::default(),
             restitution_combine_rule: Default::default(),
@@ -872,6 +928,13 @@ impl ColliderBuilder {
         self
     }
 
+    /// Sets whether the collision groups must be derived from the layer of the node. See
+    /// [`Collider::set_use_layer_groups`] for more info.
+    pub fn with_use_layer_groups(mut self, use_layer_groups: bool) -> Self {
+        self.use_layer_groups = use_layer_groups;
+        self
+    }
+
     /// Sets desired friction combine rule.
     pub fn with_friction_combine_rule(mut self, rule: CoefficientCombineRule) -> Self {
         self.friction_combine_rule = rule;
@@ -894,6 +957,7 @@ impl ColliderBuilder {
             restitution: self.restitution.into(),
             is_sensor: self.is_sensor.into(),
             collision_groups: self.collision_groups.into(),
+            use_layer_groups: self.use_layer_groups.into(),
             solver_groups: self.solver_groups.into(),
             friction_combine_rule: self.friction_combine_rule.into(),
             restitution_combine_rule: self.restitution_combine_rule.into(),
@@ -917,7 +981,7 @@ mod test {
     use crate::core::algebra::Vector2;
     use crate::scene::{
         base::BaseBuilder,
-        collider::{ColliderBuilder, ColliderShape},
+        collider::{BitMask, ColliderBuilder, ColliderShape, InteractionGroups},
         graph::Graph,
         rigidbody::{RigidBodyBuilder, RigidBodyType},
     };
@@ -983,4 +1047,55 @@ mod test {
                 .count()
         );
     }
+
+    #[test]
+    fn test_layer_collision_groups() {
+        let mut graph = Graph::new();
+
+        // Both colliders interact with layer 1 only.
+        let mut create_rigid_body = |is_sensor, layer| {
+            let collider = ColliderBuilder::new(BaseBuilder::new().with_layer(layer))
+                .with_shape(ColliderShape::cuboid(0.5, 0.5, 0.5))
+                .with_sensor(is_sensor)
+                .with_collision_groups(InteractionGroups::new(BitMask(u32::MAX), BitMask(1 << 1)))
+                .with_use_layer_groups(true)
+                .build(&mut graph);
+
+            RigidBodyBuilder::new(BaseBuilder::new().with_children(&[collider]))
+                .with_body_type(RigidBodyType::Static)
+                .build(&mut graph);
+
+            collider
+        };
+
+        let sensor = create_rigid_body(true, 1);
+        let other = create_rigid_body(false, 2);
+
+        assert_eq!(
+            graph[other].as_collider().effective_collision_groups(),
+            InteractionGroups::new(BitMask(1 << 2), BitMask(1 << 1))
+        );
+
+        let intersections = |graph: &mut Graph| {
+            graph.update(Vector2::new(800.0, 600.0), 1.0, Default::default());
+            graph.update(Vector2::new(800.0, 600.0), 1.0, Default::default());
+            graph[sensor]
+                .as_collider()
+                .intersects(&graph.physics)
+                .filter(|i| i.has_any_active_contact)
+                .count()
+        };
+
+        // Layer 2 is filtered out by the sensor.
+        assert_eq!(intersections(&mut graph), 0);
+
+        // Moving the node to another layer changes collision groups automatically.
+        graph[other].set_layer(1);
+        assert_eq!(intersections(&mut graph), 1);
+
+        // Explicit collision groups are used when layer groups are disabled.
+        graph[other].as_collider_mut().set_use_layer_groups(false);
+        graph[other].set_layer(2);
+        assert_eq!(intersections(&mut graph), 1);
+    }
 }
@@ -4,6 +4,7 @@
 
 use crate::{
     core::{
+        algebra::{Point3, Vector3},
         color::Color,
         math::aabb::AxisAlignedBoundingBox,
         pool::Handle,
@@ -54,6 +55,12 @@ use std::ops::{Deref, DerefMut};
 /// It should be noted that decals are not cheap, keep amount (and size) of decals at reasonable values! This
 /// means that unused decals (bullet holes for example) must be removed after some time.
 ///
+/// # Expiration
+///
+/// Temporary decals could be removed automatically by setting a lifetime of the node (see
+/// [`Base::set_lifetime`]). Such decals could also fade out smoothly at the end of their lifetime, use
+/// [`Decal::set_fade_out_time`] to set the duration of the fading.
+///
 /// # Example
 ///
 /// ```
@@ -99,6 +106,14 @@ pub struct Decal {
     #[reflect(min_value = 0.0)]
     #[reflect(setter = "set_layer")]
     layer: InheritableVariable<u8>,
+
+    #[reflect(
+        min_value = 0.0,
+        description = "Time (in seconds) before the end of the lifetime of the node, during which the decal fades out."
+    )]
+    #[reflect(setter = "set_fade_out_time")]
+    #[visit(optional)]
+    fade_out_time: InheritableVariable<f32>,
 }
 
 impl Deref for Decal {
@@ -187,21 +202,64 @@ impl Decal {
     pub fn layer(&self) -> u8 {
         *self.layer
     }
+
+    /// Sets time (in seconds) before the end of the lifetime of the node, during which the decal fades
+    /// out. Has no effect on decals without a lifetime.
+    pub fn set_fade_out_time(&mut self, time: f32) -> f32 {
+        self.fade_out_time
+            .set_value_and_mark_modified(time.max(0.0))
+    }
+
+    /// Returns current fade out time.
+    pub fn fade_out_time(&self) -> f32 {
+        *self.fade_out_time
+    }
+
+    /// Returns opacity of the decal in `[0; 1]` range, it is less than 1.0 only when the decal is fading
+    /// out at the end of its lifetime.
+    pub fn fade_factor(&self) -> f32 {
+        match self.lifetime() {
+            Some(lifetime) if lifetime < *self.fade_out_time => {
+                (lifetime / *self.fade_out_time).clamp(0.0, 1.0)
+            }
+            _ => 1.0,
+        }
+    }
+
+    /// Returns color of the decal with applied fading.
+    pub fn faded_color(&self) -> Color {
+        let mut color = *self.color;
+        color.a = (color.a as f32 * self.fade_factor()) as u8;
+        color
+    }
+
+    /// Checks if the given **world-space** point is inside the volume of the decal, only such points are
+    /// affected by the decal.
+    pub fn is_point_inside(&self, point: Vector3<f32>) -> bool {
+        match self.global_transform().try_inverse() {
+            Some(inv_transform) => {
+                let local = inv_transform.transform_point(&Point3::from(point));
+                local.x.abs() <= 0.5 && local.y.abs() <= 0.5 && local.z.abs() <= 0.5
+            }
+            None => false,
+        }
+    }
 }
 
 impl NodeTrait for Decal {
     crate::impl_query_component!();
 
-    /// Returns current **local-space** bounding box.
+    /// Returns current **local-space** bounding box, it is a unit cube which is then scaled by the
+    /// transform of the decal.
     #[inline]
     fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
-        // TODO: Maybe calculate AABB using frustum corners?
-        self.base.local_bounding_box()
+        AxisAlignedBoundingBox::unit()
     }
 
     /// Returns current **world-space** bounding box.
     fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
-        self.base.world_bounding_box()
+        self.local_bounding_box()
+            .transform(&self.global_transform())
     }
 
     fn id(&self) -> Uuid {
@@ -216,6 +274,7 @@ pub struct DecalBuilder {
     normal_texture: Option<TextureResource>,
     color: Color,
     layer: u8,
+    fade_out_time: f32,
 }
 
 impl DecalBuilder {
@@ -227,6 +286,7 @@ impl DecalBuilder {
             normal_texture: None,
             color: Color::opaque(255, 255, 255),
             layer: 0,
+            fade_out_time: 0.0,
         }
     }
 
@@ -254,6 +314,12 @@ impl DecalBuilder {
         self
     }
 
+    /// Sets desired fade out time, see [`Decal::set_fade_out_time`] for more info.
+    pub fn with_fade_out_time(mut self, time: f32) -> Self {
+        self.fade_out_time = time.max(0.0);
+        self
+    }
+
     /// Creates new Decal node.
     pub fn build_decal(self) -> Decal {
         Decal {
@@ -262,6 +328,7 @@ impl DecalBuilder {
             normal_texture: self.normal_texture.into(),
             color: self.color.into(),
             layer: self.layer.into(),
+            fade_out_time: self.fade_out_time.into(),
         }
     }
 
@@ -275,3 +342,56 @@ impl DecalBuilder {
         graph.add_node(self.build_node())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::{Vector2, Vector3},
+        scene::{
+            base::BaseBuilder,
+            decal::{Decal, DecalBuilder},
+            graph::Graph,
+            node::NodeTrait,
+            transform::TransformBuilder,
+        },
+    };
+
+    #[test]
+    fn test_decal_volume_and_fading() {
+        let mut graph = Graph::new();
+
+        // A script would spawn a decal at a point of impact returned by a ray cast.
+        let impact_point = Vector3::new(1.0, 2.0, 3.0);
+        let decal = DecalBuilder::new(
+            BaseBuilder::new().with_lifetime(1.0).with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(impact_point)
+                    .with_local_scale(Vector3::new(2.0, 2.0, 0.2))
+                    .build(),
+            ),
+        )
+        .with_fade_out_time(0.5)
+        .build(&mut graph);
+
+        graph.update(Vector2::new(800.0, 600.0), 0.0, Default::default());
+
+        let decal_ref = graph[decal].cast::<Decal>().unwrap();
+        let bounds = decal_ref.world_bounding_box();
+        assert!((bounds.min - Vector3::new(0.0, 1.0, 2.9)).norm() < 1.0e-5);
+        assert!((bounds.max - Vector3::new(2.0, 3.0, 3.1)).norm() < 1.0e-5);
+        assert!(decal_ref.is_point_inside(impact_point));
+        assert!(decal_ref.is_point_inside(impact_point + Vector3::new(0.9, -0.9, 0.05)));
+        assert!(!decal_ref.is_point_inside(impact_point + Vector3::new(1.1, 0.0, 0.0)));
+        assert!(!decal_ref.is_point_inside(impact_point + Vector3::new(0.0, 0.0, 0.2)));
+        assert_eq!(decal_ref.fade_factor(), 1.0);
+
+        graph.update(Vector2::new(800.0, 600.0), 0.75, Default::default());
+        let decal_ref = graph[decal].cast::<Decal>().unwrap();
+        assert_eq!(decal_ref.fade_factor(), 0.5);
+        assert_eq!(decal_ref.faded_color().a, 127);
+
+        // The decal expires at the end of its lifetime.
+        graph.update(Vector2::new(800.0, 600.0), 0.3, Default::default());
+        assert!(!graph.is_valid_handle(decal));
+    }
+}
@@ -15,7 +15,7 @@ use crate::{
     },
     scene::{
         base::{Base, BaseBuilder},
-        collider::InteractionGroups,
+        collider::{BitMask, InteractionGroups},
         dim2::{
             physics::{ContactPair, IntersectionPair, PhysicsWorld},
             rigidbody::RigidBody,
@@ -250,6 +250,13 @@ pub struct Collider {
     #[reflect(setter = "set_collision_groups")]
     pub(crate) collision_groups: InheritableVariable<InteractionGroups>,
 
+    #[reflect(
+        description = "Whether the collider belongs to the layer of the node only. If set, the filter of collision groups defines a set of layers the collider interacts with.",
+        setter = "set_use_layer_groups"
+    )]
+    #[visit(optional)]
+    pub(crate) use_layer_groups: InheritableVariable<bool>,
+
     #[reflect(setter = "set_solver_groups")]
     pub(crate) solver_groups: InheritableVariable<InteractionGroups>,
 
@@ -274,6 +281,7 @@ impl Default for Collider {
             restitution: Default::default(),
             is_sensor: Default::default(),
             collision_groups: Default::default(),
+            use_layer_groups: Default::default(),
             solver_groups: Default::default(),
             friction_combine_rule: Default::default(),
             restitution_combine_rule: Default::default(),
@@ -306,6 +314,7 @@ impl Clone for Collider {
             restitution: self.restitution.clone(),
             is_sensor: self.is_sensor.clone(),
             collision_groups: self.collision_groups.clone(),
+            use_layer_groups: self.use_layer_groups.clone(),
             solver_groups: self.solver_groups.clone(),
             friction_combine_rule: self.friction_combine_rule.clone(),
             restitution_combine_rule: self.restitution_combine_rule.clone(),
@@ -429,6 +438,39 @@ impl Collider {
         *self.collision_groups
     }
 
+    /// Defines whether the collision groups of the collider are derived from the
+    /// [layer](crate::scene::base::Base::set_layer) of the node. If set, the collider is a member
+    /// of its layer only (memberships of [`Self::collision_groups`] are ignored) and the filter of
+    /// the collision groups defines a set of layers the collider interacts with. Changing the layer
+    /// of the node updates collision groups of the collider automatically.
+    ///
+    /// # Performance
+    ///
+    /// This is relatively expensive operation - it forces the physics engine to recalculate contacts,
+    /// perform collision response, etc. Try avoid calling this method each frame for better
+    /// performance.
+    pub fn set_use_layer_groups(&mut self, use_layer_groups: bool) -> bool {
+        self.use_layer_groups
+            .set_value_and_mark_modified(use_layer_groups)
+    }
+
+    /// Returns `true` if the collision groups of the collider are derived from the layer of the
+    /// node. See [`Self::set_use_layer_groups`] for more info.
+    pub fn is_using_layer_groups(&self) -> bool {
+        *self.use_layer_groups
+    }
+
+    /// Returns collision groups that are used by the physics engine. They're either the same as
+    /// [`Self::collision_groups`], or derived from the layer of the node, see
+    /// [`Self::set_use_layer_groups`].
+    pub fn effective_collision_groups(&self) -> InteractionGroups {
+        if *self.use_layer_groups {
+            InteractionGroups::new(BitMask(self.layer_mask()), self.collision_groups.filter)
+        } else {
+            *self.collision_groups
+        }
+    }
+
     /// Sets the new joint solver filtering options. See [`InteractionGroups`] docs for more info.
     ///
     /// # Performance
@@ -526,6 +568,8 @@ impl Collider {
             || self.restitution.need_sync()
             || self.is_sensor.need_sync()
             || self.collision_groups.need_sync()
+            || self.use_layer_groups.need_sync()
+            || self.layer.need_sync()
             || self.solver_groups.need_sync()
             || self.friction_combine_rule.need_sync()
             || self.restitution_combine_rule.need_sync()
@@ -590,6 +634,7 @@ pub struct ColliderBuilder {
     restitution: f32,
     is_sensor: bool,
     collision_groups: InteractionGroups,
+    use_layer_groups: bool,
     solver_groups: InteractionGroups,
     friction_combine_rule: CoefficientCombineRule,
     restitution_combine_rule: CoefficientCombineRule,
@@ -606,6 +651,7 @@ impl ColliderBuilder {
             restitution: 0.0,
             is_sensor: false,
             collision_groups: Default::default(),
+            use_layer_groups: false,
             solver_groups: Default::default(),
             friction_combine_rule: Default::default(),
             restitution_combine_rule: Default::default(),
@@ -654,6 +700,13 @@ impl ColliderBuilder {
         self
     }
 
+    /// Sets whether the collision groups must be derived from the layer of the node. See
+    /// [`Collider::set_use_layer_groups`] for more info.
+    pub fn with_use_layer_groups(mut self, use_layer_groups: bool) -> Self {
+        self.use_layer_groups = use_layer_groups;
+        self
+    }
+
     /// Sets desired friction combine rule.
     pub fn with_friction_combine_rule(mut self, rule: CoefficientCombineRule) -> Self {
         self.friction_combine_rule = rule;
@@ -676,6 +729,7 @@ impl ColliderBuilder {
             restitution: self.restitution.into(),
             is_sensor: self.is_sensor.into(),
             collision_groups: self.collision_groups.into(),
+            use_layer_groups: self.use_layer_groups.into(),
             solver_groups: self.solver_groups.into(),
             friction_combine_rule: self.friction_combine_rule.into(),
             restitution_combine_rule: self.restitution_combine_rule.into(),
@@ -0,0 +1,337 @@
+//! Flipbook is an animated "2D" sprite, that cycles through the frames of a sprite sheet.
+//!
+//! See [`Flipbook`] docs for more info.
+
+use crate::{
+    animation::spritesheet::SpriteSheetAnimation,
+    core::{
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        variable::InheritableVariable,
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    scene::{
+        base::Base,
+        dim2::rectangle::{Rectangle, RectangleBuilder},
+        graph::Graph,
+        node::{Node, NodeTrait, UpdateContext},
+    },
+};
+use std::ops::{Deref, DerefMut};
+
+/// Flipbook is a [`Rectangle`] (2D sprite), that shows the frames of a sprite sheet one after another
+/// with a configurable rate. It is used for simple 2D effects (explosions, smoke, etc.) and characters.
+///
+/// The node provides the inner rectangle as a component, so it is rendered exactly as a usual
+/// rectangle - the only difference is that its UV rectangle is driven by the animation.
+///
+/// # Example
+///
+/// ```rust
+/// use fyrox::{
+///     animation::spritesheet::{ImageParameters, SpriteSheetAnimation},
+///     core::pool::Handle,
+///     resource::texture::TextureResource,
+///     scene::{
+///         base::BaseBuilder,
+///         dim2::{flipbook::FlipbookBuilder, rectangle::RectangleBuilder},
+///         graph::Graph,
+///         node::Node,
+///     },
+/// };
+///
+/// fn create_explosion(graph: &mut Graph, texture: TextureResource) -> Handle<Node> {
+///     // 4x4 frames sprite sheet.
+///     let mut animation = SpriteSheetAnimation::new_from_image_parameters(ImageParameters {
+///         width: 256,
+///         height: 256,
+///         frame_width: 64,
+///         frame_height: 64,
+///         first_frame: 0,
+///         last_frame: 16,
+///         column_major: false,
+///     });
+///     animation.set_looping(false);
+///
+///     FlipbookBuilder::new(RectangleBuilder::new(BaseBuilder::new()).with_texture(texture))
+///         .with_animation(animation)
+///         .with_fps(24.0)
+///         .build(graph)
+/// }
+/// ```
+#[derive(Visit, Reflect, Debug, Clone, Default)]
+pub struct Flipbook {
+    sprite: Rectangle,
+
+    #[reflect(setter = "set_animation")]
+    animation: InheritableVariable<SpriteSheetAnimation>,
+}
+
+impl Deref for Flipbook {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.sprite
+    }
+}
+
+impl DerefMut for Flipbook {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.sprite
+    }
+}
+
+impl TypeUuidProvider for Flipbook {
+    fn type_uuid() -> Uuid {
+        uuid!("a2a57156-eb24-439f-ad1c-ad731123ca47")
+    }
+}
+
+impl Flipbook {
+    /// Returns a reference to the sprite, that is used to show the frames.
+    pub fn sprite(&self) -> &Rectangle {
+        &self.sprite
+    }
+
+    /// Returns a reference to the sprite, that is used to show the frames. Keep in mind, that UV
+    /// rectangle of the sprite is overwritten by the animation.
+    pub fn sprite_mut(&mut self) -> &mut Rectangle {
+        &mut self.sprite
+    }
+
+    /// Returns a reference to the sprite sheet animation of the flipbook.
+    pub fn animation(&self) -> &SpriteSheetAnimation {
+        &self.animation
+    }
+
+    /// Returns a reference to the sprite sheet animation of the flipbook.
+    pub fn animation_mut(&mut self) -> &mut SpriteSheetAnimation {
+        self.animation.get_value_mut_and_mark_modified()
+    }
+
+    /// Sets new sprite sheet animation of the flipbook and returns the previous one.
+    pub fn set_animation(&mut self, animation: SpriteSheetAnimation) -> SpriteSheetAnimation {
+        let prev = self.animation.set_value_and_mark_modified(animation);
+        self.sync_uv_rect();
+        prev
+    }
+
+    /// Starts (or resumes) the playback.
+    pub fn play(&mut self) {
+        self.animation.get_value_mut_silent().play();
+    }
+
+    /// Stops the playback and rewinds the flipbook to the first frame.
+    pub fn stop(&mut self) {
+        self.animation.get_value_mut_silent().stop();
+        self.sync_uv_rect();
+    }
+
+    /// Puts the playback on pause, the current frame is kept.
+    pub fn pause(&mut self) {
+        self.animation.get_value_mut_silent().pause();
+    }
+
+    /// Returns `true` if the flipbook is playing, `false` - otherwise.
+    pub fn is_playing(&self) -> bool {
+        self.animation.is_playing()
+    }
+
+    /// Returns index of the frame that is currently shown.
+    pub fn current_frame(&self) -> usize {
+        self.animation.current_frame()
+    }
+
+    /// Shows a frame with the given index. The index is clamped to the amount of frames.
+    pub fn set_frame(&mut self, frame: usize) {
+        let animation = self.animation.get_value_mut_silent();
+        let last = animation.frames().len().saturating_sub(1);
+        animation.set_current_frame(frame.min(last));
+        self.sync_uv_rect();
+    }
+
+    /// Returns playback rate in frames per second.
+    pub fn fps(&self) -> f32 {
+        self.animation.speed()
+    }
+
+    /// Sets playback rate in frames per second. Negative values play the frames in reverse.
+    pub fn set_fps(&mut self, fps: f32) {
+        self.animation
+            .get_value_mut_and_mark_modified()
+            .set_speed(fps);
+    }
+
+    fn sync_uv_rect(&mut self) {
+        if let Some(uv_rect) = self.animation.current_frame_uv_rect() {
+            self.sprite.set_uv_rect(uv_rect);
+        }
+    }
+}
+
+impl NodeTrait for Flipbook {
+    crate::impl_query_component!(sprite: Rectangle);
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.sprite.local_bounding_box()
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.sprite.world_bounding_box()
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn update(&mut self, context: &mut UpdateContext) {
+        if self.animation.is_playing() {
+            self.animation.get_value_mut_silent().update(context.dt);
+            self.sync_uv_rect();
+        }
+    }
+}
+
+/// Allows you to create flipbook in declarative manner.
+pub struct FlipbookBuilder {
+    sprite_builder: RectangleBuilder,
+    animation: SpriteSheetAnimation,
+    fps: Option<f32>,
+    play: bool,
+}
+
+impl FlipbookBuilder {
+    /// Creates new flipbook builder. The flipbook starts playing right after creation, use
+    /// [`Self::with_playing`] to change that.
+    pub fn new(sprite_builder: RectangleBuilder) -> Self {
+        Self {
+            sprite_builder,
+            animation: Default::default(),
+            fps: None,
+            play: true,
+        }
+    }
+
+    /// Sets desired sprite sheet animation.
+    pub fn with_animation(mut self, animation: SpriteSheetAnimation) -> Self {
+        self.animation = animation;
+        self
+    }
+
+    /// Sets desired playback rate in frames per second. By default, the rate of the animation is used.
+    pub fn with_fps(mut self, fps: f32) -> Self {
+        self.fps = Some(fps);
+        self
+    }
+
+    /// Sets whether the flipbook should play right after creation or not.
+    pub fn with_playing(mut self, play: bool) -> Self {
+        self.play = play;
+        self
+    }
+
+    /// Creates new [`Flipbook`] instance.
+    pub fn build_flipbook(self) -> Flipbook {
+        let mut animation = self.animation;
+        if let Some(fps) = self.fps {
+            animation.set_speed(fps);
+        }
+        if self.play {
+            animation.play();
+        }
+
+        let mut flipbook = Flipbook {
+            sprite: self.sprite_builder.build_rectangle(),
+            animation: animation.into(),
+        };
+        flipbook.sync_uv_rect();
+        flipbook
+    }
+
+    /// Creates new [`Flipbook`] instance.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_flipbook())
+    }
+
+    /// Creates new [`Flipbook`] instance and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        animation::spritesheet::{ImageParameters, SpriteSheetAnimation},
+        core::{algebra::Vector2, math::Rect},
+        scene::{
+            base::BaseBuilder,
+            dim2::{
+                flipbook::{Flipbook, FlipbookBuilder},
+                rectangle::{Rectangle, RectangleBuilder},
+            },
+            graph::Graph,
+        },
+    };
+
+    #[test]
+    fn test_flipbook_frame_rate() {
+        let mut graph = Graph::new();
+
+        let flipbook = FlipbookBuilder::new(RectangleBuilder::new(BaseBuilder::new()))
+            .with_animation(SpriteSheetAnimation::new_from_image_parameters(
+                ImageParameters {
+                    width: 128,
+                    height: 128,
+                    frame_width: 32,
+                    frame_height: 32,
+                    first_frame: 0,
+                    last_frame: 4,
+                    column_major: false,
+                },
+            ))
+            .with_fps(10.0)
+            .build(&mut graph);
+
+        let frame_size = Vector2::new(800.0, 600.0);
+        let current_frame =
+            |graph: &Graph| graph[flipbook].cast::<Flipbook>().unwrap().current_frame();
+
+        assert_eq!(current_frame(&graph), 0);
+
+        // 10 FPS - a new frame every 0.1 second.
+        let mut frames = Vec::new();
+        for _ in 0..5 {
+            graph.update(frame_size, 0.105, Default::default());
+            frames.push(current_frame(&graph));
+        }
+        // The animation loops by default.
+        assert_eq!(frames, vec![1, 2, 3, 0, 1]);
+
+        // The sprite shows the current frame.
+        assert_eq!(
+            graph[flipbook]
+                .query_component_ref::<Rectangle>()
+                .unwrap()
+                .uv_rect(),
+            Rect::new(0.25, 0.0, 0.25, 0.25)
+        );
+
+        let flipbook_ref = graph[flipbook].cast_mut::<Flipbook>().unwrap();
+        flipbook_ref.stop();
+        assert_eq!(flipbook_ref.current_frame(), 0);
+        graph.update(frame_size, 0.5, Default::default());
+        assert_eq!(current_frame(&graph), 0);
+
+        let flipbook_ref = graph[flipbook].cast_mut::<Flipbook>().unwrap();
+        flipbook_ref.set_frame(2);
+        flipbook_ref.set_fps(20.0);
+        flipbook_ref.play();
+        // 20 FPS - a new frame every 0.05 second.
+        graph.update(frame_size, 0.055, Default::default());
+        assert_eq!(current_frame(&graph), 3);
+    }
+}
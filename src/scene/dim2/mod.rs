@@ -2,6 +2,7 @@
 //! but physics simulation is in true 2D.
 
 pub mod collider;
+pub mod flipbook;
 pub mod joint;
 pub mod physics;
 pub mod rectangle;
@@ -9,7 +9,7 @@ use crate::{
         arrayvec::ArrayVec,
         instant,
         log::{Log, MessageKind},
-        math::Matrix4Ext,
+        math::{m4x4_approx_eq, Matrix4Ext},
         parking_lot::Mutex,
         pool::Handle,
         reflect::prelude::*,
@@ -29,6 +29,7 @@ use crate::{
         node::{Node, NodeTrait},
     },
 };
+use fxhash::FxHashMap;
 use rapier2d::{
     dynamics::{
         CCDSolver, GenericJoint, GenericJointBuilder, ImpulseJointHandle, ImpulseJointSet,
@@ -350,6 +351,24 @@ pub struct PhysicsWorld {
     #[visit(skip)]
     #[reflect(hidden)]
     debug_render_pipeline: Mutex<DebugRenderPipeline>,
+    // Interpolation factor between the previous and the current poses of dynamic rigid bodies. It is
+    // set by the graph when fixed time step is used.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) interpolation: Option<f32>,
+    // Poses of dynamic rigid bodies before the last simulation step.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    previous_poses: FxHashMap<RigidBodyHandle, Isometry2<f32>>,
+    // Interpolated poses that were written to the rigid body nodes. They're used only for rendering and
+    // must not be pushed back to the simulation.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    interpolated_poses: FxHashMap<RigidBodyHandle, Isometry2<f32>>,
+    // Linear speed limits of rigid bodies, only the bodies with a limit are stored here.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    max_linear_speeds: FxHashMap<RigidBodyHandle, f32>,
 }
 
 fn isometry_from_global_transform(transform: &Matrix4<f32>) -> Isometry2<f32> {
@@ -412,6 +431,41 @@ impl PhysicsWorld {
             query: RefCell::new(Default::default()),
             performance_statistics: Default::default(),
             debug_render_pipeline: Default::default(),
+            interpolation: None,
+            previous_poses: Default::default(),
+            interpolated_poses: Default::default(),
+            max_linear_speeds: Default::default(),
+        }
+    }
+
+    /// Returns integration parameters that will be passed to the physics engine for a simulation step with
+    /// the given time step. The time step is overridden by [`IntegrationParameters::dt`], if it is set.
+    pub fn rapier_integration_parameters(
+        &self,
+        dt: f32,
+    ) -> rapier2d::dynamics::IntegrationParameters {
+        rapier2d::dynamics::IntegrationParameters {
+            dt: self.integration_parameters.dt.unwrap_or(dt),
+            min_ccd_dt: self.integration_parameters.min_ccd_dt,
+            erp: self.integration_parameters.erp,
+            damping_ratio: self.integration_parameters.damping_ratio,
+            joint_erp: self.integration_parameters.joint_erp,
+            joint_damping_ratio: self.integration_parameters.joint_damping_ratio,
+            allowed_linear_error: self.integration_parameters.allowed_linear_error,
+            max_penetration_correction: self.integration_parameters.max_penetration_correction,
+            prediction_distance: self.integration_parameters.prediction_distance,
+            max_velocity_iterations: self.integration_parameters.max_velocity_iterations as usize,
+            max_velocity_friction_iterations: self
+                .integration_parameters
+                .max_velocity_friction_iterations
+                as usize,
+            max_stabilization_iterations: self.integration_parameters.max_stabilization_iterations
+                as usize,
+            interleave_restitution_and_friction_resolution: self
+                .integration_parameters
+                .interleave_restitution_and_friction_resolution,
+            min_island_size: self.integration_parameters.min_island_size as usize,
+            max_ccd_substeps: self.integration_parameters.max_ccd_substeps as usize,
         }
     }
 
@@ -419,32 +473,19 @@ impl PhysicsWorld {
         let time = instant::Instant::now();
 
         if self.enabled {
-            let integration_parameters = rapier2d::dynamics::IntegrationParameters {
-                dt: self.integration_parameters.dt.unwrap_or(dt),
-                min_ccd_dt: self.integration_parameters.min_ccd_dt,
-                erp: self.integration_parameters.erp,
-                damping_ratio: self.integration_parameters.damping_ratio,
-                joint_erp: self.integration_parameters.joint_erp,
-                joint_damping_ratio: self.integration_parameters.joint_damping_ratio,
-                allowed_linear_error: self.integration_parameters.allowed_linear_error,
-                max_penetration_correction: self.integration_parameters.max_penetration_correction,
-                prediction_distance: self.integration_parameters.prediction_distance,
-                max_velocity_iterations: self.integration_parameters.max_velocity_iterations
-                    as usize,
-                max_velocity_friction_iterations: self
-                    .integration_parameters
-                    .max_velocity_friction_iterations
-                    as usize,
-                max_stabilization_iterations: self
-                    .integration_parameters
-                    .max_stabilization_iterations
-                    as usize,
-                interleave_restitution_and_friction_resolution: self
-                    .integration_parameters
-                    .interleave_restitution_and_friction_resolution,
-                min_island_size: self.integration_parameters.min_island_size as usize,
-                max_ccd_substeps: self.integration_parameters.max_ccd_substeps as usize,
-            };
+            self.previous_poses.clear();
+            if self.interpolation.is_some() {
+                for (handle, body) in self.bodies.set.iter() {
+                    if body.body_type() == RigidBodyType::Dynamic {
+                        self.previous_poses.insert(handle, *body.position());
+                    }
+                }
+            }
+
+            // Velocity could be changed directly (for example, by an impulse) since the last step.
+            self.clamp_linear_speeds();
+
+            let integration_parameters = self.rapier_integration_parameters(dt);
 
             self.pipeline.step(
                 &self.gravity,
@@ -463,11 +504,40 @@ impl PhysicsWorld {
                 &(),
                 &*self.event_handler,
             );
+
+            self.clamp_linear_speeds();
         }
 
         self.performance_statistics.step_time += instant::Instant::now() - time;
     }
 
+    // Rapier has no speed limit, so the velocity is clamped manually before and after each step. `get_mut`
+    // is expensive, so use it only when the velocity is actually clamped.
+    fn clamp_linear_speeds(&mut self) {
+        for (&handle, &max_speed) in self.max_linear_speeds.iter() {
+            let clamped_lin_vel = self.bodies.set.get(handle).and_then(|body| {
+                let lin_vel = *body.linvel();
+                let speed = lin_vel.norm();
+                (body.body_type() == RigidBodyType::Dynamic && speed > max_speed)
+                    .then(|| lin_vel.scale(max_speed / speed))
+            });
+
+            if let Some(lin_vel) = clamped_lin_vel {
+                if let Some(body) = self.bodies.set.get_mut(handle) {
+                    body.set_linvel(lin_vel, false);
+                }
+            }
+        }
+    }
+
+    fn set_max_linear_speed(&mut self, handle: RigidBodyHandle, max_speed: f32) {
+        if max_speed > 0.0 {
+            self.max_linear_speeds.insert(handle, max_speed);
+        } else {
+            self.max_linear_speeds.remove(&handle);
+        }
+    }
+
     pub(crate) fn add_body(&mut self, owner: Handle<Node>, body: RigidBody) -> RigidBodyHandle {
         let handle = self.bodies.set.insert(body);
         self.bodies.map.insert(handle, owner);
@@ -490,6 +560,7 @@ impl PhysicsWorld {
         {
             assert!(self.bodies.map.remove_by_key(&handle).is_some());
         }
+        self.max_linear_speeds.remove(&handle);
     }
 
     pub(crate) fn add_collider(
@@ -614,9 +685,21 @@ impl PhysicsWorld {
         rigid_body: &scene::dim2::rigidbody::RigidBody,
         new_global_transform: &Matrix4<f32>,
     ) {
+        let new_position = isometry_from_global_transform(new_global_transform);
+
+        // Skip the interpolated pose, otherwise the simulation would be "rewound" to it every frame.
+        if let Some(interpolated) = self.interpolated_poses.get(&rigid_body.native.get()) {
+            if m4x4_approx_eq(
+                &isometry2_to_mat4(interpolated),
+                &isometry2_to_mat4(&new_position),
+            ) {
+                return;
+            }
+        }
+
         if let Some(native) = self.bodies.set.get_mut(rigid_body.native.get()) {
             native.set_position(
-                isometry_from_global_transform(new_global_transform),
+                new_position,
                 // Do not wake up body, it is too expensive and must be done **only** by explicit
                 // `wake_up` call!
                 false,
@@ -632,10 +715,26 @@ impl PhysicsWorld {
         if self.enabled {
             if let Some(native) = self.bodies.set.get(rigid_body.native.get()) {
                 if native.body_type() == RigidBodyType::Dynamic {
+                    let position = match (
+                        self.interpolation,
+                        self.previous_poses.get(&rigid_body.native.get()),
+                    ) {
+                        (Some(t), Some(previous)) => {
+                            let interpolated = previous.lerp_slerp(native.position(), t);
+                            self.interpolated_poses
+                                .insert(rigid_body.native.get(), interpolated);
+                            interpolated
+                        }
+                        _ => {
+                            self.interpolated_poses.remove(&rigid_body.native.get());
+                            *native.position()
+                        }
+                    };
+
                     let local_transform: Matrix4<f32> = parent_transform
                         .try_inverse()
                         .unwrap_or_else(Matrix4::identity)
-                        * isometry2_to_mat4(native.position());
+                        * isometry2_to_mat4(&position);
 
                     let local_rotation = UnitQuaternion::from_matrix_eps(
                         &local_transform.basis(),
@@ -678,6 +777,10 @@ impl PhysicsWorld {
         // 1) `get_mut` is **very** expensive because it forces physics engine to recalculate contacts
         //    and a lot of other stuff, this is why we need `anything_changed` flag.
         if rigid_body_node.native.get() != RigidBodyHandle::invalid() {
+            rigid_body_node
+                .max_linear_speed
+                .try_sync_model(|v| self.set_max_linear_speed(rigid_body_node.native.get(), v));
+
             let mut actions = rigid_body_node.actions.lock();
             if rigid_body_node.need_sync_model() || !actions.is_empty() {
                 if let Some(native) = self.bodies.set.get_mut(rigid_body_node.native.get()) {
@@ -793,6 +896,10 @@ impl PhysicsWorld {
             );
 
             rigid_body_node.native.set(self.add_body(handle, body));
+            self.set_max_linear_speed(
+                rigid_body_node.native.get(),
+                rigid_body_node.max_linear_speed(),
+            );
 
             Log::writeln(
                 MessageKind::Information,
@@ -846,12 +953,19 @@ impl PhysicsWorld {
                     collider_node
                         .restitution
                         .try_sync_model(|v| native.set_restitution(v));
-                    collider_node.collision_groups.try_sync_model(|v| {
+                    // Collision groups could be derived from the layer of the node, so every
+                    // property they depend on must be checked.
+                    let collision_groups_changed =
+                        collider_node.collision_groups.try_sync_model(|_| {})
+                            | collider_node.use_layer_groups.try_sync_model(|_| {})
+                            | collider_node.layer.try_sync_model(|_| {});
+                    if collision_groups_changed {
+                        let groups = collider_node.effective_collision_groups();
                         native.set_collision_groups(InteractionGroups::new(
-                            u32_to_group(v.memberships.0),
-                            u32_to_group(v.filter.0),
-                        ))
-                    });
+                            u32_to_group(groups.memberships.0),
+                            u32_to_group(groups.filter.0),
+                        ));
+                    }
                     collider_node.solver_groups.try_sync_model(|v| {
                         native.set_solver_groups(InteractionGroups::new(
                             u32_to_group(v.memberships.0),
@@ -891,8 +1005,8 @@ impl PhysicsWorld {
                         .friction(collider_node.friction())
                         .restitution(collider_node.restitution())
                         .collision_groups(InteractionGroups::new(
-                            u32_to_group(collider_node.collision_groups().memberships.0),
-                            u32_to_group(collider_node.collision_groups().filter.0),
+                            u32_to_group(collider_node.effective_collision_groups().memberships.0),
+                            u32_to_group(collider_node.effective_collision_groups().filter.0),
                         ))
                         .friction_combine_rule(collider_node.friction_combine_rule().into())
                         .restitution_combine_rule(collider_node.restitution_combine_rule().into())
@@ -1104,3 +1218,90 @@ impl Debug for PhysicsWorld {
         write!(f, "PhysicsWorld")
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::{Isometry2, Vector2},
+        scene::{
+            base::BaseBuilder,
+            dim2::{
+                collider::{ColliderBuilder, ColliderShape},
+                rigidbody::RigidBodyBuilder,
+            },
+            graph::{fixed_step::FixedTimeStep, Graph},
+        },
+    };
+
+    fn simulate(interpolate: bool) -> Isometry2<f32> {
+        let mut graph = Graph::new();
+        graph.physics_time_step = FixedTimeStep {
+            interpolate,
+            ..FixedTimeStep::new(60.0)
+        };
+
+        let collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::ball(0.5))
+            .build(&mut graph);
+        let body = RigidBodyBuilder::new(BaseBuilder::new().with_children(&[collider]))
+            .with_lin_vel(Vector2::new(2.0, 0.0))
+            .with_ang_vel(3.0)
+            .build(&mut graph);
+
+        // Frame rate is not a multiple of the physics rate, so almost every frame is interpolated.
+        for _ in 0..100 {
+            graph.update(Vector2::new(800.0, 600.0), 1.0 / 144.0, Default::default());
+        }
+
+        let native = graph[body].as_rigid_body2d().native.get();
+        *graph.physics2d.bodies.set.get(native).unwrap().position()
+    }
+
+    #[test]
+    fn test_interpolation_does_not_affect_simulation() {
+        let interpolated = simulate(true);
+        let reference = simulate(false);
+
+        assert!(reference.translation.vector.x > 1.0);
+        assert!(
+            (interpolated.translation.vector - reference.translation.vector).norm() < 1.0e-4,
+            "{:?} != {:?}",
+            interpolated,
+            reference
+        );
+        assert!(interpolated.rotation.angle_to(&reference.rotation).abs() < 1.0e-4);
+    }
+
+    #[test]
+    fn test_max_linear_speed_is_applied_after_each_step() {
+        let mut graph = Graph::new();
+        graph.physics_time_step = FixedTimeStep::new(60.0);
+
+        let collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::ball(0.5))
+            .build(&mut graph);
+        let body = RigidBodyBuilder::new(BaseBuilder::new().with_children(&[collider]))
+            .with_gravity_scale(0.0)
+            .with_max_linear_speed(20.0)
+            .build(&mut graph);
+
+        // The collider is built before its body, so the first update creates the native body and the
+        // second one creates the native collider, that gives the body its mass.
+        graph.update(Vector2::new(800.0, 600.0), 0.1, Default::default());
+        graph.update(Vector2::new(800.0, 600.0), 0.1, Default::default());
+
+        graph[body]
+            .as_rigid_body2d_mut()
+            .apply_impulse(Vector2::new(10000.0, 0.0));
+
+        // The frame is split in 6 physics steps, the body must not move faster than the limit on
+        // any of them.
+        graph.update(Vector2::new(800.0, 600.0), 0.1, Default::default());
+
+        let native = graph[body].as_rigid_body2d().native.get();
+        let native = graph.physics2d.bodies.set.get(native).unwrap();
+        assert!((native.linvel().norm() - 20.0).abs() < 1.0e-3);
+        assert!(native.translation().x <= 20.0 * 0.1 + 1.0e-3);
+        assert!((graph[body].as_rigid_body2d().lin_vel().norm() - 20.0).abs() < 1.0e-3);
+    }
+}
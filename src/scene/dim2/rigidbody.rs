@@ -62,6 +62,13 @@ pub(crate) enum ApplyAction {
 ///
 /// Rigid body that does not move for some time will go asleep. This means that the body will not
 /// move unless it is woken up by some other moving body. This feature allows to save CPU resources.
+///
+/// # Speed limit
+///
+/// Linear speed of a dynamic rigid body could be limited using [`RigidBody::set_max_linear_speed`].
+/// The velocity is clamped right before and after each physics step, so huge forces or impulses (for
+/// example, the ones produced by movement code on tiny time steps) cannot make the body fly away. Zero
+/// limit (default) means that the speed is not limited.
 #[derive(Visit, Reflect)]
 pub struct RigidBody {
     base: Base,
@@ -103,6 +110,11 @@ pub struct RigidBody {
     #[reflect(setter = "set_gravity_scale")]
     pub(crate) gravity_scale: InheritableVariable<f32>,
 
+    #[visit(optional)]
+    #[reflect(min_value = 0.0)]
+    #[reflect(setter = "set_max_linear_speed")]
+    pub(crate) max_linear_speed: InheritableVariable<f32>,
+
     #[visit(skip)]
     #[reflect(hidden)]
     pub(crate) sleeping: bool,
@@ -143,6 +155,7 @@ impl Default for RigidBody {
             can_sleep: InheritableVariable::new_modified(true),
             dominance: Default::default(),
             gravity_scale: InheritableVariable::new_modified(1.0),
+            max_linear_speed: Default::default(),
             native: Cell::new(RigidBodyHandle::invalid()),
             actions: Default::default(),
             reset_forces: Default::default(),
@@ -181,6 +194,7 @@ impl Clone for RigidBody {
             can_sleep: self.can_sleep.clone(),
             dominance: self.dominance.clone(),
             gravity_scale: self.gravity_scale.clone(),
+            max_linear_speed: self.max_linear_speed.clone(),
             // Do not copy. The copy will have its own native representation.
             native: Cell::new(RigidBodyHandle::invalid()),
             actions: Default::default(),
@@ -308,6 +322,18 @@ impl RigidBody {
         *self.gravity_scale
     }
 
+    /// Sets max linear speed of the rigid body. Linear velocity of the body is clamped to this value
+    /// before and after each physics step. Zero can be used to disable the limit. Returns previous value.
+    pub fn set_max_linear_speed(&mut self, max_speed: f32) -> f32 {
+        self.max_linear_speed
+            .set_value_and_mark_modified(max_speed.max(0.0))
+    }
+
+    /// Returns current max linear speed of the rigid body. Zero means that the speed is not limited.
+    pub fn max_linear_speed(&self) -> f32 {
+        *self.max_linear_speed
+    }
+
     /// Sets dominance group of the rigid body. A rigid body with higher dominance group will not
     /// be affected by an object with lower dominance group (it will behave like it has an infinite
     /// mass). This is very importance feature for character physics in games, you can set highest
@@ -479,6 +505,7 @@ pub struct RigidBodyBuilder {
     can_sleep: bool,
     dominance: i8,
     gravity_scale: f32,
+    max_linear_speed: f32,
 }
 
 impl RigidBodyBuilder {
@@ -499,6 +526,7 @@ impl RigidBodyBuilder {
             can_sleep: true,
             dominance: 0,
             gravity_scale: 1.0,
+            max_linear_speed: 0.0,
         }
     }
 
@@ -568,6 +596,12 @@ impl RigidBodyBuilder {
         self
     }
 
+    /// Sets desired max linear speed. Zero means that the speed is not limited.
+    pub fn with_max_linear_speed(mut self, max_linear_speed: f32) -> Self {
+        self.max_linear_speed = max_linear_speed;
+        self
+    }
+
     /// Sets initial state of the body (sleeping or not).
     pub fn with_sleeping(mut self, sleeping: bool) -> Self {
         self.sleeping = sleeping;
@@ -597,6 +631,7 @@ impl RigidBodyBuilder {
             can_sleep: self.can_sleep.into(),
             dominance: self.dominance.into(),
             gravity_scale: self.gravity_scale.into(),
+            max_linear_speed: self.max_linear_speed.into(),
             native: Cell::new(RigidBodyHandle::invalid()),
             actions: Default::default(),
             reset_forces: Default::default(),
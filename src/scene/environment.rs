@@ -0,0 +1,405 @@
+//! Image-based lighting of a scene. See [`EnvironmentLighting`] docs for more info.
+
+use crate::{
+    asset::{Resource, ResourceStateRef},
+    core::{algebra::Vector3, log::Log, reflect::prelude::*, visitor::prelude::*},
+    resource::texture::{Texture, TextureKind, TexturePixelKind, TextureResource, TextureWrapMode},
+};
+use std::f32::consts::PI;
+
+/// Size (width and height) of the diffuse irradiance map.
+pub const IRRADIANCE_MAP_SIZE: (u32, u32) = (32, 16);
+
+/// Size (width and height) of the first mip level of the prefiltered specular map.
+pub const SPECULAR_MAP_SIZE: (u32, u32) = (64, 32);
+
+/// Amount of mip levels of the prefiltered specular map. The first level is a sharp reflection, the last
+/// one is a reflection of a surface with maximum roughness.
+pub const SPECULAR_MAP_MIP_COUNT: u32 = 5;
+
+/// A set of maps, that is baked from a HDRI. Both maps use equirectangular projection and store linear
+/// colors in [`TexturePixelKind::RGB32F`] format.
+#[derive(Clone, Debug)]
+pub struct PrefilteredEnvironment {
+    irradiance: TextureResource,
+    specular: TextureResource,
+}
+
+impl PrefilteredEnvironment {
+    /// Returns diffuse irradiance map. Every pixel of the map stores cosine-weighted average of the light
+    /// coming to a surface, that faces in the direction of the pixel.
+    pub fn irradiance(&self) -> &TextureResource {
+        &self.irradiance
+    }
+
+    /// Returns prefiltered specular map. Its mip levels store reflections for linearly increasing surface
+    /// roughness, see [`SPECULAR_MAP_MIP_COUNT`].
+    pub fn specular(&self) -> &TextureResource {
+        &self.specular
+    }
+}
+
+/// Image-based lighting of a scene. It uses a high dynamic range image (HDRI) in equirectangular projection
+/// as a source of light that comes from every direction. When a HDRI is assigned, it is baked into
+/// prefiltered maps (see [`PrefilteredEnvironment`]), which are then used by the renderer for diffuse and
+/// specular lighting in addition to ambient lighting color of the scene.
+///
+/// Baking is done on CPU, when a HDRI is assigned or when it is fully loaded (for example, after the scene
+/// was loaded). Prefiltered maps are not saved together with the scene. Only uncompressed RGB(A) textures
+/// with 8 bits or 32-bit floats per channel are supported.
+///
+/// # Example
+///
+/// ```rust
+/// use fyrox::{asset::manager::ResourceManager, resource::texture::Texture, scene::Scene};
+///
+/// fn set_sky(scene: &mut Scene, resource_manager: &ResourceManager) {
+///     scene.environment.enabled = true;
+///     scene.environment.set_hdri(Some(resource_manager.request::<Texture, _>("data/sky.hdr")));
+/// }
+/// ```
+#[derive(Clone, Debug, Visit, Reflect)]
+pub struct EnvironmentLighting {
+    /// Whether the image-based lighting is enabled or not.
+    pub enabled: bool,
+
+    /// Multiplier of the light that comes from the environment.
+    #[reflect(min_value = 0.0, step = 0.05)]
+    pub intensity: f32,
+
+    #[reflect(setter = "set_hdri")]
+    hdri: Option<TextureResource>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    prefiltered: Option<PrefilteredEnvironment>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    needs_baking: bool,
+}
+
+impl Default for EnvironmentLighting {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            intensity: 1.0,
+            hdri: None,
+            prefiltered: None,
+            needs_baking: true,
+        }
+    }
+}
+
+impl EnvironmentLighting {
+    /// Sets new HDRI and bakes it into prefiltered maps. If the texture is not loaded yet, the maps will be
+    /// baked on the first [`Self::update`] call after it is loaded. Returns previous HDRI.
+    pub fn set_hdri(&mut self, hdri: Option<TextureResource>) -> Option<TextureResource> {
+        let prev = std::mem::replace(&mut self.hdri, hdri);
+        self.prefiltered = None;
+        self.needs_baking = true;
+        self.update();
+        prev
+    }
+
+    /// Returns current HDRI.
+    pub fn hdri(&self) -> Option<&TextureResource> {
+        self.hdri.as_ref()
+    }
+
+    /// Returns prefiltered maps baked from current HDRI (if any).
+    pub fn prefiltered(&self) -> Option<&PrefilteredEnvironment> {
+        self.prefiltered.as_ref()
+    }
+
+    /// Bakes prefiltered maps, if the HDRI has changed and it is loaded. It is called automatically by the
+    /// scene every frame.
+    pub fn update(&mut self) {
+        if !self.needs_baking {
+            return;
+        }
+
+        let hdri = if let Some(hdri) = self.hdri.as_ref() {
+            hdri
+        } else {
+            self.needs_baking = false;
+            return;
+        };
+
+        let state = hdri.state();
+        match state.get() {
+            ResourceStateRef::Pending { .. } => (),
+            ResourceStateRef::LoadError { .. } => {
+                self.needs_baking = false;
+            }
+            ResourceStateRef::Ok(texture) => {
+                self.prefiltered = bake(texture);
+                if self.prefiltered.is_none() {
+                    Log::warn(
+                        "Unable to bake environment lighting, only uncompressed rectangular \
+                        RGB(A) textures are supported as HDRI!",
+                    );
+                }
+                self.needs_baking = false;
+            }
+        }
+    }
+}
+
+struct RadianceMap {
+    width: u32,
+    height: u32,
+    pixels: Vec<Vector3<f32>>,
+}
+
+impl RadianceMap {
+    fn from_texture(texture: &Texture) -> Option<Self> {
+        let (width, height) = if let TextureKind::Rectangle { width, height } = texture.kind() {
+            (width, height)
+        } else {
+            return None;
+        };
+
+        fn to_linear(value: u8) -> f32 {
+            (value as f32 / 255.0).powf(2.2)
+        }
+
+        fn read_f32(bytes: &[u8]) -> f32 {
+            f32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+        }
+
+        let data = texture.mip_level_data(0);
+        let pixels = match texture.pixel_kind() {
+            TexturePixelKind::RGB8 => data
+                .chunks_exact(3)
+                .map(|p| Vector3::new(to_linear(p[0]), to_linear(p[1]), to_linear(p[2])))
+                .collect::<Vec<_>>(),
+            TexturePixelKind::RGBA8 => data
+                .chunks_exact(4)
+                .map(|p| Vector3::new(to_linear(p[0]), to_linear(p[1]), to_linear(p[2])))
+                .collect(),
+            TexturePixelKind::RGB32F => data
+                .chunks_exact(12)
+                .map(|p| Vector3::new(read_f32(&p[0..4]), read_f32(&p[4..8]), read_f32(&p[8..12])))
+                .collect(),
+            TexturePixelKind::RGBA32F => data
+                .chunks_exact(16)
+                .map(|p| Vector3::new(read_f32(&p[0..4]), read_f32(&p[4..8]), read_f32(&p[8..12])))
+                .collect(),
+            _ => return None,
+        };
+
+        if width == 0 || height == 0 || pixels.len() != (width * height) as usize {
+            return None;
+        }
+
+        Some(Self {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    fn downsample(&self, width: u32, height: u32) -> Self {
+        let mut pixels = Vec::with_capacity((width * height) as usize);
+        for y in 0..height {
+            let y_begin = y * self.height / height;
+            let y_end = ((y + 1) * self.height / height).max(y_begin + 1);
+            for x in 0..width {
+                let x_begin = x * self.width / width;
+                let x_end = ((x + 1) * self.width / width).max(x_begin + 1);
+
+                let mut sum = Vector3::default();
+                for sy in y_begin..y_end {
+                    for sx in x_begin..x_end {
+                        sum += self.pixels[(sy * self.width + sx) as usize];
+                    }
+                }
+                pixels.push(sum.scale(1.0 / ((y_end - y_begin) * (x_end - x_begin)) as f32));
+            }
+        }
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Returns direction and solid angle of every pixel of the map.
+    fn texels(&self) -> impl Iterator<Item = (Vector3<f32>, f32, Vector3<f32>)> + '_ {
+        let texel_angle = (PI / self.height as f32) * (2.0 * PI / self.width as f32);
+        self.pixels.iter().enumerate().map(move |(i, radiance)| {
+            let x = i as u32 % self.width;
+            let y = i as u32 / self.width;
+            let direction = texel_direction(x, y, self.width, self.height);
+            let solid_angle = texel_angle * (1.0 - direction.y * direction.y).max(0.0).sqrt();
+            (direction, solid_angle, *radiance)
+        })
+    }
+}
+
+/// Returns direction of a pixel center of equirectangular map. It must match `EquirectangularUV` in the
+/// ambient light shader.
+fn texel_direction(x: u32, y: u32, width: u32, height: u32) -> Vector3<f32> {
+    let phi = (x as f32 + 0.5) / width as f32 * 2.0 * PI - PI;
+    let theta = (y as f32 + 0.5) / height as f32 * PI;
+    Vector3::new(
+        theta.sin() * phi.cos(),
+        theta.cos(),
+        theta.sin() * phi.sin(),
+    )
+}
+
+fn convolve<F>(source: &RadianceMap, width: u32, height: u32, mut weight: F, bytes: &mut Vec<u8>)
+where
+    F: FnMut(&Vector3<f32>, &Vector3<f32>) -> f32,
+{
+    for y in 0..height {
+        for x in 0..width {
+            let direction = texel_direction(x, y, width, height);
+
+            let mut sum = Vector3::default();
+            let mut total_weight = 0.0;
+            for (source_direction, solid_angle, radiance) in source.texels() {
+                let w = weight(&direction, &source_direction) * solid_angle;
+                sum += radiance.scale(w);
+                total_weight += w;
+            }
+
+            let value = if total_weight > 0.0 {
+                sum.scale(1.0 / total_weight)
+            } else {
+                Vector3::default()
+            };
+
+            for component in value.iter() {
+                bytes.extend_from_slice(&component.to_ne_bytes());
+            }
+        }
+    }
+}
+
+fn make_texture(
+    width: u32,
+    height: u32,
+    bytes: Vec<u8>,
+    mip_count: u32,
+) -> Option<TextureResource> {
+    let mut texture = Texture::from_bytes_with_mips(
+        TextureKind::Rectangle { width, height },
+        TexturePixelKind::RGB32F,
+        bytes,
+        mip_count,
+        false,
+    )?;
+    texture.set_s_wrap_mode(TextureWrapMode::Repeat);
+    texture.set_t_wrap_mode(TextureWrapMode::ClampToEdge);
+    Some(Resource::new_ok(texture))
+}
+
+fn bake(hdri: &Texture) -> Option<PrefilteredEnvironment> {
+    let source =
+        RadianceMap::from_texture(hdri)?.downsample(SPECULAR_MAP_SIZE.0, SPECULAR_MAP_SIZE.1);
+
+    // Diffuse irradiance is a cosine-weighted average of the incoming light over the hemisphere. Normalization
+    // by the total weight also divides by PI, so a white environment produces white irradiance.
+    let (width, height) = IRRADIANCE_MAP_SIZE;
+    let mut bytes = Vec::new();
+    convolve(&source, width, height, |n, l| n.dot(l).max(0.0), &mut bytes);
+    let irradiance = make_texture(width, height, bytes, 1)?;
+
+    // Every mip level of the specular map is a convolution with a Phong lobe, that matches GGX distribution
+    // with the roughness of the level. The first level is the source itself.
+    let mut bytes = Vec::new();
+    for pixel in source.pixels.iter() {
+        for component in pixel.iter() {
+            bytes.extend_from_slice(&component.to_ne_bytes());
+        }
+    }
+    for mip in 1..SPECULAR_MAP_MIP_COUNT {
+        let roughness = mip as f32 / (SPECULAR_MAP_MIP_COUNT - 1) as f32;
+        let alpha = roughness * roughness;
+        let power = 2.0 / (alpha * alpha).max(f32::EPSILON) - 2.0;
+        convolve(
+            &source,
+            (SPECULAR_MAP_SIZE.0 >> mip).max(1),
+            (SPECULAR_MAP_SIZE.1 >> mip).max(1),
+            |r, l| r.dot(l).max(0.0).powf(power),
+            &mut bytes,
+        );
+    }
+    let specular = make_texture(
+        SPECULAR_MAP_SIZE.0,
+        SPECULAR_MAP_SIZE.1,
+        bytes,
+        SPECULAR_MAP_MIP_COUNT,
+    )?;
+
+    Some(PrefilteredEnvironment {
+        irradiance,
+        specular,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        resource::texture::{
+            TextureKind, TexturePixelKind, TextureResource, TextureResourceExtension,
+        },
+        scene::environment::{
+            EnvironmentLighting, IRRADIANCE_MAP_SIZE, SPECULAR_MAP_MIP_COUNT, SPECULAR_MAP_SIZE,
+        },
+    };
+
+    #[test]
+    fn test_hdri_is_baked_into_prefiltered_maps() {
+        let (width, height) = (16, 8);
+        let bytes = std::iter::repeat(2.0f32)
+            .take((width * height * 3) as usize)
+            .flat_map(|value| value.to_ne_bytes())
+            .collect::<Vec<_>>();
+        let hdri = TextureResource::from_bytes(
+            TextureKind::Rectangle { width, height },
+            TexturePixelKind::RGB32F,
+            bytes,
+            false,
+        )
+        .unwrap();
+
+        let mut environment = EnvironmentLighting::default();
+        assert!(environment.prefiltered().is_none());
+
+        environment.set_hdri(Some(hdri));
+
+        let prefiltered = environment.prefiltered().unwrap();
+
+        let irradiance = prefiltered.irradiance().data_ref();
+        assert_eq!(
+            irradiance.kind(),
+            TextureKind::Rectangle {
+                width: IRRADIANCE_MAP_SIZE.0,
+                height: IRRADIANCE_MAP_SIZE.1
+            }
+        );
+        assert_eq!(irradiance.pixel_kind(), TexturePixelKind::RGB32F);
+        // Uniformly lit environment gives the same irradiance in every direction.
+        for value in irradiance.data().chunks_exact(4) {
+            let value = f32::from_ne_bytes([value[0], value[1], value[2], value[3]]);
+            assert!((value - 2.0).abs() < 0.01);
+        }
+
+        let specular = prefiltered.specular().data_ref();
+        assert_eq!(
+            specular.kind(),
+            TextureKind::Rectangle {
+                width: SPECULAR_MAP_SIZE.0,
+                height: SPECULAR_MAP_SIZE.1
+            }
+        );
+        assert_eq!(specular.mip_count(), SPECULAR_MAP_MIP_COUNT);
+
+        environment.set_hdri(None);
+        assert!(environment.prefiltered().is_none());
+    }
+}
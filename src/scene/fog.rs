@@ -0,0 +1,71 @@
+//! Per-scene atmospheric fog. See [`FogSettings`] docs for more info.
+
+use crate::core::{color::Color, reflect::prelude::*, visitor::prelude::*};
+
+/// Atmospheric fog settings of a scene. Fog is absent closer than [`Self::start`] distance to the camera,
+/// then it grows linearly and reaches its maximum at [`Self::end`] distance. Density defines opacity of
+/// the fog at its maximum, `1.0` means that everything past the end distance is completely hidden.
+/// Fog is disabled by default.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct FogSettings {
+    /// Whether the fog is enabled or not.
+    pub enabled: bool,
+    /// Color of the fog.
+    pub color: Color,
+    /// Max opacity of the fog.
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    pub density: f32,
+    /// Distance from the camera at which the fog starts.
+    #[reflect(min_value = 0.0, step = 0.5)]
+    pub start: f32,
+    /// Distance from the camera at which the fog reaches its maximum opacity.
+    #[reflect(min_value = 0.0, step = 0.5)]
+    pub end: f32,
+}
+
+impl Default for FogSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            color: Color::opaque(128, 128, 128),
+            density: 1.0,
+            start: 10.0,
+            end: 100.0,
+        }
+    }
+}
+
+impl FogSettings {
+    /// Calculates opacity of the fog at given distance from the camera. Returns zero if the fog is disabled.
+    pub fn factor(&self, distance: f32) -> f32 {
+        if !self.enabled {
+            return 0.0;
+        }
+
+        let length = (self.end - self.start).max(f32::EPSILON);
+        ((distance - self.start) / length).clamp(0.0, 1.0) * self.density.clamp(0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::scene::fog::FogSettings;
+
+    #[test]
+    fn test_fog_factor() {
+        let mut fog = FogSettings {
+            enabled: true,
+            density: 0.5,
+            start: 10.0,
+            end: 20.0,
+            ..Default::default()
+        };
+
+        assert_eq!(fog.factor(5.0), 0.0);
+        assert_eq!(fog.factor(15.0), 0.25);
+        assert_eq!(fog.factor(100.0), 0.5);
+
+        fog.enabled = false;
+        assert_eq!(fog.factor(100.0), 0.0);
+    }
+}
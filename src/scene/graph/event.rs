@@ -2,12 +2,12 @@
 //! Check [GraphEventBroadcaster::subscribe] for examples.
 
 use crate::{
-    core::{pool::Handle, VecExtensions},
+    core::{parking_lot::Mutex, pool::Handle, uuid::Uuid, VecExtensions},
     scene::node::Node,
 };
 use std::{
     fmt::{Debug, Formatter},
-    sync::mpsc::Sender,
+    sync::{mpsc::Sender, Arc},
 };
 
 /// An event that happened in a graph.
@@ -17,13 +17,26 @@ pub enum GraphEvent {
     Added(Handle<Node>),
     /// A node was removed.
     Removed(Handle<Node>),
+    /// A script of a node was added, removed or replaced with some other script. Type UUIDs are
+    /// `None` if the node had no script before the change or has no script after it.
+    ScriptChanged {
+        /// A handle of the node.
+        node: Handle<Node>,
+        /// Type UUID of the previous script.
+        old_uuid: Option<Uuid>,
+        /// Type UUID of the new script.
+        new_uuid: Option<Uuid>,
+    },
 }
 
 /// Graph event broadcaster allows you to receive graph events such as node deletion or addition.
 /// Check [GraphEventBroadcaster::subscribe] for examples.
-#[derive(Default)]
+///
+/// The broadcaster is shared between the graph and its nodes (cloning creates a new reference to
+/// the same set of subscribers), so nodes are able to report their own changes.
+#[derive(Default, Clone)]
 pub struct GraphEventBroadcaster {
-    senders: Vec<Sender<GraphEvent>>,
+    senders: Arc<Mutex<Vec<Sender<GraphEvent>>>>,
 }
 
 impl Debug for GraphEventBroadcaster {
@@ -31,7 +44,7 @@ impl Debug for GraphEventBroadcaster {
         write!(
             f,
             "GraphEventBroadcaster has {} senders.",
-            self.senders.len()
+            self.senders.lock().len()
         )
     }
 }
@@ -64,11 +77,12 @@ impl GraphEventBroadcaster {
     ///
     /// ```
     pub fn subscribe(&mut self, sender: Sender<GraphEvent>) {
-        self.senders.push(sender);
+        self.senders.lock().push(sender);
     }
 
-    pub(crate) fn broadcast(&mut self, event: GraphEvent) {
+    pub(crate) fn broadcast(&self, event: GraphEvent) {
         self.senders
+            .lock()
             .retain_mut_ext(|sender| sender.send(event.clone()).is_ok());
     }
 }
@@ -0,0 +1,159 @@
+//! Fixed time step allows you to make physics simulation independent of the frame rate. See [`FixedTimeStep`]
+//! docs for more info.
+
+use crate::core::{reflect::prelude::*, visitor::prelude::*};
+
+/// Fixed time step splits the time, that passed since the last frame, in equal physics steps. The time that
+/// is not enough for a full step is accumulated and carried over to the next frame. This way the physics
+/// is always simulated with the same time step and the total amount of steps depends only on the simulated
+/// time, not on the frame rate - this makes gameplay deterministic across different machines.
+///
+/// Since the amount of physics steps could be different from frame to frame, the movement of rigid bodies
+/// could look jerky. To fix this, the transforms of dynamic rigid bodies could be interpolated between the
+/// two last physics steps (see [`Self::interpolate`]). Interpolated transforms are used only for rendering,
+/// the actual state of the simulation is left untouched.
+///
+/// Fixed time step is disabled by default, which means that the physics is stepped once per frame using
+/// frame's time step.
+#[derive(Clone, Debug, Visit, Reflect)]
+pub struct FixedTimeStep {
+    /// Whether the fixed time step is enabled or not.
+    pub enabled: bool,
+
+    /// Amount of physics steps per second.
+    #[reflect(min_value = 1.0, description = "Amount of physics steps per second.")]
+    pub rate: f32,
+
+    /// Max amount of physics steps per frame. If a frame took too much time, the excess time is discarded,
+    /// this prevents the physics from "falling behind" forever on slow machines.
+    #[reflect(
+        min_value = 1.0,
+        description = "Max amount of physics steps per frame. Excess time is discarded."
+    )]
+    pub max_steps_per_frame: u32,
+
+    /// Whether transforms of dynamic rigid bodies should be interpolated between two last physics steps.
+    #[reflect(
+        description = "Interpolate transforms of dynamic rigid bodies between two last physics steps."
+    )]
+    pub interpolate: bool,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    accumulator: f64,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    last_step_count: u32,
+}
+
+impl Default for FixedTimeStep {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            rate: 60.0,
+            max_steps_per_frame: 8,
+            interpolate: true,
+            accumulator: 0.0,
+            last_step_count: 0,
+        }
+    }
+}
+
+impl FixedTimeStep {
+    /// Creates a new fixed time step with the given amount of physics steps per second.
+    pub fn new(rate: f32) -> Self {
+        Self {
+            enabled: true,
+            rate,
+            ..Default::default()
+        }
+    }
+
+    /// Returns duration of a single physics step in seconds.
+    pub fn step_duration(&self) -> f32 {
+        1.0 / self.rate.max(1.0)
+    }
+
+    /// Advances the accumulator by the given amount of time and returns the amount of physics steps that
+    /// must be performed.
+    pub fn advance(&mut self, dt: f32) -> u32 {
+        self.last_step_count = accumulate_steps(
+            &mut self.accumulator,
+            self.step_duration(),
+            dt,
+            self.max_steps_per_frame,
+        );
+        self.last_step_count
+    }
+
+    /// Returns the amount of physics steps, that were performed by the last [`Self::advance`] call.
+    pub fn last_step_count(&self) -> u32 {
+        self.last_step_count
+    }
+
+    /// Returns a factor in `[0; 1]` range, that defines how far the current time is from the last physics
+    /// step towards the next one. It is used to interpolate transforms of rigid bodies.
+    pub fn interpolation_factor(&self) -> f32 {
+        (self.accumulator / self.step_duration() as f64).clamp(0.0, 1.0) as f32
+    }
+}
+
+/// Adds `dt` to the `accumulator` and returns the amount of whole steps of `step` duration that fit in
+/// it, but no more than `max_steps`. The time of the performed steps is subtracted from the accumulator.
+pub(crate) fn accumulate_steps(accumulator: &mut f64, step: f32, dt: f32, max_steps: u32) -> u32 {
+    let step = step as f64;
+    // Tolerance compensates rounding errors of the accumulation, otherwise a step could be "lost" when
+    // the frame rate is a multiple of the step rate.
+    let tolerance = step * 1.0e-4;
+
+    *accumulator += dt.max(0.0) as f64;
+
+    let mut steps = 0;
+    while *accumulator + tolerance >= step {
+        if steps == max_steps {
+            // Discard the excess time.
+            *accumulator = accumulator.min(step - tolerance);
+            break;
+        }
+        *accumulator -= step;
+        steps += 1;
+    }
+
+    steps
+}
+
+#[cfg(test)]
+mod test {
+    use crate::scene::graph::fixed_step::FixedTimeStep;
+
+    fn count_steps(frame_rate: f32, seconds: f32) -> u32 {
+        let mut step = FixedTimeStep::new(60.0);
+        let frames = (frame_rate * seconds).round() as u32;
+        (0..frames).map(|_| step.advance(1.0 / frame_rate)).sum()
+    }
+
+    #[test]
+    fn test_step_count_is_frame_rate_independent() {
+        for frame_rate in [24.0, 30.0, 60.0, 75.0, 120.0, 144.0, 240.0] {
+            assert_eq!(count_steps(frame_rate, 10.0), 600, "{} FPS", frame_rate);
+        }
+
+        // Irregular frame times.
+        let mut step = FixedTimeStep::new(60.0);
+        let mut total = 0;
+        for dt in [0.01, 0.03, 0.002, 0.025, 0.013].iter().cycle().take(125) {
+            total += step.advance(*dt);
+        }
+        // 125 frames are 2 seconds.
+        assert_eq!(total, 120);
+    }
+
+    #[test]
+    fn test_max_steps_per_frame() {
+        let mut step = FixedTimeStep::new(60.0);
+        assert_eq!(step.advance(1.0), step.max_steps_per_frame);
+        // The excess time must be discarded.
+        assert_eq!(step.advance(0.0), 0);
+    }
+}
@@ -34,17 +34,20 @@ use crate::{
         variable::try_inherit_properties,
         visitor::{Visit, VisitResult, Visitor},
     },
+    engine::SerializationContext,
     material::SharedMaterial,
     resource::model::{ModelResource, ModelResourceExtension, NodeMapping},
     scene::{
         self,
-        base::NodeScriptMessage,
+        base::{BillboardMode, NodeId, NodeScriptMessage},
         camera::Camera,
         dim2::{self},
         graph::{
             event::{GraphEvent, GraphEventBroadcaster},
+            fixed_step::FixedTimeStep,
             map::NodeHandleMap,
             physics::{PhysicsPerformanceStatistics, PhysicsWorld},
+            spatial_hash::SpatialHash,
         },
         mesh::Mesh,
         node::{container::NodeContainer, Node, NodeTrait, SyncContext, UpdateContext},
@@ -52,7 +55,7 @@ use crate::{
         sound::context::SoundContext,
         transform::TransformBuilder,
     },
-    script::ScriptTrait,
+    script::{constructor::ScriptConstructorContainer, ScriptState, ScriptTrait},
 };
 use fxhash::FxHashSet;
 use rapier3d::geometry::ColliderHandle;
@@ -65,8 +68,10 @@ use std::{
 };
 
 pub mod event;
+pub mod fixed_step;
 pub mod map;
 pub mod physics;
+pub mod spatial_hash;
 
 /// Graph performance statistics. Allows you to find out "hot" parts of the scene graph, which
 /// parts takes the most time to update.
@@ -121,6 +126,10 @@ pub struct Graph {
     /// Backing 2D physics "world". It is responsible for the 2D physics simulation.
     pub physics2d: dim2::physics::PhysicsWorld,
 
+    /// Defines how the physics (both 2D and 3D) is stepped. By default, the physics is stepped once per
+    /// frame, see [`FixedTimeStep`] docs for more info.
+    pub physics_time_step: FixedTimeStep,
+
     /// Backing sound context. It is responsible for sound rendering.
     #[reflect(hidden)]
     pub sound_context: SoundContext,
@@ -133,6 +142,17 @@ pub struct Graph {
     #[reflect(hidden)]
     pub event_broadcaster: GraphEventBroadcaster,
 
+    /// Spatial hash over world positions of the nodes, it is used for fast neighbor queries. Use
+    /// [`Graph::spatial_hash`] to query it.
+    #[reflect(hidden)]
+    pub spatial_hash: SpatialHash,
+
+    /// A set of nodes, that are hidden (with their descendants) without changing their own visibility
+    /// flag. Such nodes are not globally visible, so they're not rendered, but the flag is still saved
+    /// as is. It is used by the editor to hide nodes in its viewport. The set is never saved.
+    #[reflect(hidden)]
+    pub hidden_nodes: FxHashSet<Handle<Node>>,
+
     #[reflect(hidden)]
     pub(crate) script_message_sender: Sender<NodeScriptMessage>,
     #[reflect(hidden)]
@@ -146,12 +166,15 @@ impl Default for Graph {
         Self {
             physics: PhysicsWorld::new(),
             physics2d: dim2::physics::PhysicsWorld::new(),
+            physics_time_step: Default::default(),
             root: Handle::NONE,
             pool: Pool::new(),
             stack: Vec::new(),
             sound_context: Default::default(),
             performance_statistics: Default::default(),
             event_broadcaster: Default::default(),
+            spatial_hash: Default::default(),
+            hidden_nodes: Default::default(),
             script_message_receiver: rx,
             script_message_sender: tx,
         }
@@ -207,10 +230,14 @@ fn isometric_global_transform(nodes: &NodePool, node: Handle<Node>) -> Matrix4<f
 // Clears all information about parent-child relations of a given node. This is needed in some
 // cases (mostly when copying a node), because `Graph::add_node` uses children list to attach
 // children to the given node, and when copying a node it is important that this step is skipped.
-fn clear_links(mut node: Node) -> Node {
-    node.children.clear();
-    node.parent = Handle::NONE;
-    node
+/// Creates a copy of the node without any links to other nodes. The copy is a new node, so it gets
+/// its own id.
+fn make_copy(node: &Node) -> Node {
+    let mut copy = node.clone_box();
+    copy.children.clear();
+    copy.parent = Handle::NONE;
+    copy.node_id = NodeId::new();
+    copy
 }
 
 /// A set of switches that allows you to disable a particular step of graph update pipeline.
@@ -246,7 +273,9 @@ impl Graph {
 
         // Create root node.
         let mut root_node = Pivot::default();
+        let event_broadcaster = GraphEventBroadcaster::default();
         root_node.script_message_sender = Some(tx.clone());
+        root_node.event_broadcaster = Some(event_broadcaster.clone());
         root_node.set_name("__ROOT__");
 
         // Add it to the pool.
@@ -260,9 +289,12 @@ impl Graph {
             root,
             pool,
             physics2d: Default::default(),
+            physics_time_step: Default::default(),
             sound_context: SoundContext::new(),
             performance_statistics: Default::default(),
-            event_broadcaster: Default::default(),
+            event_broadcaster,
+            spatial_hash: Default::default(),
+            hidden_nodes: Default::default(),
             script_message_receiver: rx,
             script_message_sender: tx,
         }
@@ -324,6 +356,7 @@ impl Graph {
         }
 
         self.event_broadcaster.broadcast(GraphEvent::Added(handle));
+        self.spatial_hash.invalidate();
         if has_script {
             self.script_message_sender
                 .send(NodeScriptMessage::InitializeScript { handle })
@@ -331,9 +364,11 @@ impl Graph {
         }
 
         let sender = self.script_message_sender.clone();
+        let event_broadcaster = self.event_broadcaster.clone();
         let node = &mut self[handle];
         node.self_handle = handle;
         node.script_message_sender = Some(sender);
+        node.event_broadcaster = Some(event_broadcaster);
 
         handle
     }
@@ -466,6 +501,19 @@ impl Graph {
             self.event_broadcaster
                 .broadcast(GraphEvent::Removed(handle));
         }
+
+        self.spatial_hash.invalidate();
+    }
+
+    /// Returns spatial hash over world positions of the nodes, rebuilding it first if it is outdated.
+    /// The hash is marked as outdated on every [`Self::update`] call and when nodes are added or removed,
+    /// so it is rebuilt at most once per frame if the graph does not change.
+    #[inline]
+    pub fn spatial_hash(&mut self) -> &SpatialHash {
+        if self.spatial_hash.need_rebuild() {
+            self.spatial_hash.rebuild(&self.pool, self.root);
+        }
+        &self.spatial_hash
     }
 
     fn unlink_internal(&mut self, node_handle: Handle<Node>) {
@@ -495,14 +543,57 @@ impl Graph {
         }
     }
 
-    /// Links specified child with specified parent.
+    /// Checks whether the specified child can be attached to the specified parent. Attachment is
+    /// prohibited if the parent is the child itself or one of its descendants, because it would create
+    /// a cycle in the hierarchy.
+    #[inline]
+    pub fn can_link_nodes(&self, child: Handle<Node>, parent: Handle<Node>) -> bool {
+        let mut handle = parent;
+        while let Some(node) = self.pool.try_borrow(handle) {
+            if handle == child {
+                return false;
+            }
+            handle = node.parent;
+        }
+        true
+    }
+
+    /// Links specified child with specified parent. The link is ignored if it would create a cycle
+    /// in the hierarchy, see [`Self::can_link_nodes`].
     #[inline]
     pub fn link_nodes(&mut self, child: Handle<Node>, parent: Handle<Node>) {
+        if !self.can_link_nodes(child, parent) {
+            Log::err(format!(
+                "Unable to link {} node to {} node, because it would create a cycle!",
+                child, parent
+            ));
+            return;
+        }
+
         self.unlink_internal(child);
         self.pool[child].parent = parent;
         self.pool[parent].children.push(child);
     }
 
+    /// Moves specified child to a new position in the list of children of specified parent. The
+    /// index will be clamped to the count of children. Order of children affects iteration order
+    /// and is preserved on serialization. Returns previous index of the child, or `None` if the
+    /// child does not belong to the parent.
+    #[inline]
+    pub fn set_child_index(
+        &mut self,
+        parent: Handle<Node>,
+        child: Handle<Node>,
+        new_index: usize,
+    ) -> Option<usize> {
+        let children = &mut self.pool.try_borrow_mut(parent)?.children;
+        let old_index = children.iter().position(|h| *h == child)?;
+        children.remove(old_index);
+        let new_index = new_index.min(children.len());
+        children.insert(new_index, child);
+        Some(old_index)
+    }
+
     /// Links specified child with specified parent while keeping the
     /// child's global position and rotation.
     #[inline]
@@ -511,6 +602,14 @@ impl Graph {
         child: Handle<Node>,
         parent: Handle<Node>,
     ) {
+        if !self.can_link_nodes(child, parent) {
+            Log::err(format!(
+                "Unable to link {} node to {} node, because it would create a cycle!",
+                child, parent
+            ));
+            return;
+        }
+
         let parent_transform_inv = self.pool[parent]
             .global_transform()
             .try_inverse()
@@ -630,6 +729,33 @@ impl Graph {
         None
     }
 
+    /// Searches for a node, that is the closest (by world position) to the given point among the nodes that pass
+    /// the filter. Returns a tuple with a handle of the found node and the distance to it. If nothing is found, it
+    /// returns [`None`]. Root node is never considered. The method checks every node of the graph, so avoid calling
+    /// it too often for large graphs.
+    #[inline]
+    pub fn nearest_node<F>(
+        &self,
+        point: Vector3<f32>,
+        filter: &mut F,
+    ) -> Option<(Handle<Node>, f32)>
+    where
+        F: FnMut(Handle<Node>, &Node) -> bool,
+    {
+        let mut nearest: Option<(Handle<Node>, f32)> = None;
+        for (handle, node) in self.pair_iter() {
+            if handle == self.root || !filter(handle, node) {
+                continue;
+            }
+
+            let distance = node.global_position().metric_distance(&point);
+            if nearest.map_or(true, |(_, nearest_distance)| distance < nearest_distance) {
+                nearest = Some((handle, distance));
+            }
+        }
+        nearest
+    }
+
     /// Searches for a node with the specified name down the tree starting from the specified node. Returns a tuple with
     /// a handle and a reference to the found node. If nothing is found, it returns [`None`].
     #[inline]
@@ -659,6 +785,50 @@ impl Graph {
         self.find_by_name(self.root, name)
     }
 
+    /// Searches for a node with the given stable id in the entire graph. Returns a tuple with a handle and a
+    /// reference to the found node. If nothing is found, it returns [`None`]. Unlike handles, ids are preserved
+    /// when the graph is saved and loaded back, so they could be used to store references to nodes in save
+    /// files. See [`NodeId`] docs for more info.
+    pub fn find_by_id(&self, id: NodeId) -> Option<(Handle<Node>, &Node)> {
+        self.pair_iter().find(|(_, node)| node.node_id() == id)
+    }
+
+    /// Returns a path of the node in the hierarchy - names of every ancestor of the node (excluding the
+    /// graph root) and the node itself, separated by `/` (for example `Player/Body/Camera`). The path
+    /// of the graph root is an empty string. The path can be resolved back using [`Self::find_by_path`].
+    /// Keep in mind, that the path is ambiguous if there are siblings with the same name, in this case
+    /// [`Self::find_by_path`] returns the first one.
+    pub fn path_of(&self, handle: Handle<Node>) -> String {
+        let mut names = Vec::new();
+        let mut current = handle;
+        while let Some(node) = self.try_get(current) {
+            if current == self.root {
+                break;
+            }
+            names.push(node.name());
+            current = node.parent;
+        }
+        names.reverse();
+        names.join("/")
+    }
+
+    /// Searches for a node using its path from the graph root. See [`Self::path_of`] docs for
+    /// the path format. Returns a tuple with a handle and a reference to the found node. If nothing
+    /// is found, it returns [`None`].
+    pub fn find_by_path(&self, path: &str) -> Option<(Handle<Node>, &Node)> {
+        let mut current = self.root;
+        if !path.is_empty() {
+            for name in path.split('/') {
+                current = *self
+                    .try_get(current)?
+                    .children()
+                    .iter()
+                    .find(|child| self.try_get(**child).map_or(false, |c| c.name() == name))?;
+            }
+        }
+        self.try_get(current).map(|node| (current, node))
+    }
+
     /// Searches for a **first** node with a script of the given type `S` in the hierarchy starting from the
     /// given `root_node`.
     #[inline]
@@ -746,10 +916,19 @@ impl Graph {
         let mut root_handle = Handle::NONE;
 
         for (parent, children) in to_copy.iter() {
-            // Copy parent first.
-            let parent_copy = clear_links(self.pool[*parent].clone_box());
-            let parent_copy_handle = self.add_node(parent_copy);
-            old_new_mapping.map.insert(*parent, parent_copy_handle);
+            // Copy parent first. Descendant nodes were already copied at previous iterations
+            // as children of their parents, so reuse their copies. Descendants without a copy
+            // were filtered out.
+            let parent_copy_handle = match old_new_mapping.map.get(parent) {
+                Some(parent_copy_handle) => *parent_copy_handle,
+                None if *parent != node_handle => continue,
+                None => {
+                    let parent_copy = make_copy(&self.pool[*parent]);
+                    let parent_copy_handle = self.add_node(parent_copy);
+                    old_new_mapping.map.insert(*parent, parent_copy_handle);
+                    parent_copy_handle
+                }
+            };
 
             if root_handle.is_none() {
                 root_handle = parent_copy_handle;
@@ -758,7 +937,7 @@ impl Graph {
             // Copy children and link to new parent.
             for &child in children {
                 if filter(child, &self.pool[child]) {
-                    let child_copy = clear_links(self.pool[child].clone_box());
+                    let child_copy = make_copy(&self.pool[child]);
                     let child_copy_handle = self.add_node(child_copy);
                     old_new_mapping.map.insert(child, child_copy_handle);
                     self.link_nodes(child_copy_handle, parent_copy_handle);
@@ -779,7 +958,7 @@ impl Graph {
     #[inline]
     pub fn copy_single_node(&self, node_handle: Handle<Node>) -> Node {
         let node = &self.pool[node_handle];
-        let mut clone = clear_links(node.clone_box());
+        let mut clone = make_copy(node);
         if let Some(ref mut mesh) = clone.cast_mut::<Mesh>() {
             for surface in mesh.surfaces_mut() {
                 surface.bones.clear();
@@ -799,7 +978,7 @@ impl Graph {
         F: FnMut(Handle<Node>, &Node) -> bool,
     {
         let src_node = &self.pool[root_handle];
-        let dest_node = clear_links(src_node.clone_box());
+        let dest_node = make_copy(src_node);
         let dest_copy_handle = dest_graph.add_node(dest_node);
         old_new_mapping.map.insert(root_handle, dest_copy_handle);
         for &src_child_handle in src_node.children() {
@@ -1038,6 +1217,7 @@ impl Graph {
         for (handle, node) in self.pool.pair_iter_mut() {
             node.self_handle = handle;
             node.script_message_sender = Some(self.script_message_sender.clone());
+            node.event_broadcaster = Some(self.event_broadcaster.clone());
         }
     }
 
@@ -1078,6 +1258,7 @@ impl Graph {
         sound_context: &mut SoundContext,
         physics: &mut PhysicsWorld,
         physics2d: &mut dim2::physics::PhysicsWorld,
+        hidden_nodes: &FxHashSet<Handle<Node>>,
         node_handle: Handle<Node>,
     ) {
         let node = &nodes[node_handle];
@@ -1109,7 +1290,7 @@ impl Graph {
 
         node.global_transform.set(new_global_transform);
         node.global_visibility
-            .set(parent_visibility && node.visibility());
+            .set(parent_visibility && node.visibility() && !hidden_nodes.contains(&node_handle));
         node.global_enabled.set(parent_enabled && node.is_enabled());
 
         for &child in node.children() {
@@ -1118,6 +1299,7 @@ impl Graph {
                 sound_context,
                 physics,
                 physics2d,
+                hidden_nodes,
                 child,
             );
         }
@@ -1133,6 +1315,7 @@ impl Graph {
             &mut self.sound_context,
             &mut self.physics,
             &mut self.physics2d,
+            &self.hidden_nodes,
             node_handle,
         );
     }
@@ -1149,10 +1332,96 @@ impl Graph {
             &mut self.sound_context,
             &mut self.physics,
             &mut self.physics2d,
+            &self.hidden_nodes,
             self.root,
         );
     }
 
+    /// Returns global transform of the node blended between the two last physics states. The transform
+    /// is calculated using the closest dynamic rigid body up in the hierarchy (including the node
+    /// itself), see [`PhysicsWorld::interpolated_pose`] for more info. If there's no such body, or the
+    /// fixed time step is disabled, the global transform of the node is returned as is.
+    pub fn interpolated_global_transform(&self, handle: Handle<Node>) -> Matrix4<f32> {
+        let node_transform = self.pool[handle].global_transform();
+
+        let mut current = handle;
+        while let Some(node) = self.pool.try_borrow(current) {
+            if let Some(pose) = node
+                .cast::<scene::rigidbody::RigidBody>()
+                .and_then(|body| self.physics.interpolated_pose(body))
+            {
+                // Keep the transform of the node relative to the body.
+                let relative = node
+                    .global_transform()
+                    .try_inverse()
+                    .unwrap_or_else(Matrix4::identity)
+                    * node_transform;
+                let scale = Matrix4::new_nonuniform_scaling(&**node.local_transform().scale());
+                return pose.to_homogeneous() * scale * relative;
+            }
+            current = node.parent();
+        }
+
+        node_transform
+    }
+
+    fn update_global_transforms_recursively(&self, node_handle: Handle<Node>) {
+        let node = &self.pool[node_handle];
+        let parent_global_transform = self
+            .pool
+            .try_borrow(node.parent())
+            .map(|parent| parent.global_transform())
+            .unwrap_or_else(Matrix4::identity);
+        node.global_transform
+            .set(parent_global_transform * node.local_transform().matrix());
+        for &child in node.children() {
+            self.update_global_transforms_recursively(child);
+        }
+    }
+
+    /// Rotates every node with billboard mode (see [`BillboardMode`]) to face the given observer
+    /// position. Only global transforms of the nodes and their descendants are changed, local
+    /// transforms stay the same. Normally you don't need to call this method directly, the graph
+    /// orients billboards to the first enabled camera on each update and the renderer does the
+    /// same for every camera it renders the graph with.
+    pub fn apply_billboards(&self, observer_position: Vector3<f32>) {
+        let mut billboards = self
+            .pool
+            .pair_iter()
+            .filter(|(_, node)| node.billboard() != BillboardMode::None)
+            .map(|(handle, _)| handle)
+            .collect::<Vec<_>>();
+
+        if billboards.is_empty() {
+            return;
+        }
+
+        // Parents must be oriented first, otherwise they'll overwrite global transforms of nested
+        // billboards.
+        billboards.sort_by_cached_key(|handle| {
+            let mut depth = 0;
+            let mut parent = self.pool[*handle].parent();
+            while let Some(parent_ref) = self.pool.try_borrow(parent) {
+                depth += 1;
+                parent = parent_ref.parent();
+            }
+            depth
+        });
+
+        for handle in billboards {
+            let node = &self.pool[handle];
+            if let Some(transform) = node
+                .billboard()
+                .apply(&node.global_transform(), &observer_position)
+            {
+                node.global_transform.set(transform);
+                for &child in node.children() {
+                    self.update_global_transforms_recursively(child);
+                }
+            }
+        }
+    }
+
     /// Checks whether given node handle is valid or not.
     #[inline]
     pub fn is_valid_handle(&self, node_handle: Handle<Node>) -> bool {
@@ -1221,7 +1490,29 @@ impl Graph {
     /// where you need to have preview mode to update only specific set of nodes, etc.
     pub fn update(&mut self, frame_size: Vector2<f32>, dt: f32, switches: GraphUpdateSwitches) {
         let last_time = instant::Instant::now();
+        // Remember global transforms of the previous frame. New nodes do not have valid global transform
+        // yet, so they will get it right after the update of hierarchical data.
+        for node in self.pool.iter() {
+            if node.previous_global_transform.get().is_some() {
+                node.previous_global_transform
+                    .set(Some(node.global_transform.get()));
+            }
+        }
         self.update_hierarchical_data();
+        for node in self.pool.iter() {
+            if node.previous_global_transform.get().is_none() {
+                node.previous_global_transform
+                    .set(Some(node.global_transform.get()));
+            }
+        }
+        if let Some(camera) = self
+            .pool
+            .iter()
+            .find_map(|node| node.cast::<Camera>().filter(|camera| camera.is_enabled()))
+        {
+            self.apply_billboards(camera.global_position());
+        }
+        self.spatial_hash.invalidate();
         self.performance_statistics.hierarchical_properties_time =
             instant::Instant::now() - last_time;
 
@@ -1229,15 +1520,34 @@ impl Graph {
         self.sync_native(&switches);
         self.performance_statistics.sync_time = instant::Instant::now() - last_time;
 
+        let (physics_steps, physics_dt, fixed_step_alpha) = if self.physics_time_step.enabled {
+            let steps = self.physics_time_step.advance(dt);
+            (
+                steps,
+                self.physics_time_step.step_duration(),
+                Some(self.physics_time_step.interpolation_factor()),
+            )
+        } else {
+            (1, dt, None)
+        };
+        let interpolate = self.physics_time_step.interpolate;
+
         if switches.physics {
             self.physics.performance_statistics.reset();
-            self.physics.update(dt);
+            self.physics.fixed_step_alpha = fixed_step_alpha;
+            self.physics.interpolate = interpolate;
+            for _ in 0..physics_steps {
+                self.physics.update(physics_dt);
+            }
             self.performance_statistics.physics = self.physics.performance_statistics.clone();
         }
 
         if switches.physics2d {
             self.physics2d.performance_statistics.reset();
-            self.physics2d.update(dt);
+            self.physics2d.interpolation = fixed_step_alpha.filter(|_| interpolate);
+            for _ in 0..physics_steps {
+                self.physics2d.update(physics_dt);
+            }
             self.performance_statistics.physics2d = self.physics2d.performance_statistics.clone();
         }
 
@@ -1465,6 +1775,12 @@ impl Graph {
 
         let (copy_root, old_new_map) = self.copy_node(root, &mut copy, filter);
         assert_eq!(copy.root, copy_root);
+
+        // The copy is the same graph, so preserve ids of the nodes.
+        for (&original, &copy_handle) in old_new_map.map.iter() {
+            copy.pool[copy_handle].node_id = self.pool[original].node_id;
+        }
+
         (copy, old_new_map)
     }
 
@@ -1556,6 +1872,13 @@ impl Graph {
         )
     }
 
+    /// Returns global transform of a node at the previous frame. See
+    /// [`scene::base::Base::previous_global_transform`] for more info.
+    #[inline]
+    pub fn previous_global_transform(&self, node: Handle<Node>) -> Matrix4<f32> {
+        self[node].previous_global_transform()
+    }
+
     /// Returns global scale of a node.
     #[inline]
     pub fn global_scale(&self, node: Handle<Node>) -> Vector3<f32> {
@@ -1582,6 +1905,17 @@ impl Graph {
             .and_then(|node| node.try_get_script_mut())
     }
 
+    /// Tries to reattach scripts, whose types were unknown at the moment when the graph was loaded (see
+    /// [`scene::base::UnresolvedScript`] docs for more info). It should be called when new script types were
+    /// registered in the serialization context, for example after a game was recompiled. Returns the amount
+    /// of restored scripts.
+    pub fn resolve_scripts(&mut self, serialization_context: &SerializationContext) -> usize {
+        self.pool
+            .iter_mut()
+            .filter(|node| node.try_resolve_script(serialization_context))
+            .count()
+    }
+
     /// Tries to borrow a node using the given handle and fetch a reference to a component of the given type
     /// from the script of the node.
     #[inline]
@@ -1603,6 +1937,60 @@ impl Graph {
         self.try_get_mut(node)
             .and_then(|node| node.try_get_script_component_mut())
     }
+
+    /// Serializes the state of every script in the graph. It is meant to be used in pair with
+    /// [`Self::restore_script_states`] to keep the state of scripts when their code is reloaded. Scripts,
+    /// that cannot be serialized, are skipped and an error message is written to the log.
+    pub fn capture_script_states(&mut self) -> Vec<(Handle<Node>, ScriptState)> {
+        let mut states = Vec::new();
+        for (handle, node) in self.pool.pair_iter_mut() {
+            if let Some(script) = node.script_mut() {
+                match ScriptState::capture(script) {
+                    Ok(state) => states.push((handle, state)),
+                    Err(err) => Log::err(format!(
+                        "Unable to capture state of the script of {} node. Reason: {:?}",
+                        node.name(),
+                        err
+                    )),
+                }
+            }
+        }
+        states
+    }
+
+    /// Re-creates the scripts of the nodes using the given set of constructors and restores their state
+    /// captured by [`Self::capture_script_states`]. Fields, that have the same name and type as before, keep
+    /// their values, everything else is set to defaults. Restored scripts will be initialized again.
+    pub fn restore_script_states(
+        &mut self,
+        states: Vec<(Handle<Node>, ScriptState)>,
+        constructors: &ScriptConstructorContainer,
+    ) {
+        for (handle, state) in states {
+            let node = if let Some(node) = self.try_get_mut(handle) {
+                node
+            } else {
+                continue;
+            };
+
+            match state.restore(constructors) {
+                Ok(Some(script)) => node.set_script(Some(script)),
+                Ok(None) => {
+                    Log::warn(format!(
+                        "There is no constructor for script {} of {} node, the script is removed.",
+                        state.type_uuid(),
+                        node.name()
+                    ));
+                    node.set_script(None);
+                }
+                Err(err) => Log::err(format!(
+                    "Unable to restore state of the script of {} node. Reason: {:?}",
+                    node.name(),
+                    err
+                )),
+            }
+        }
+    }
 }
 
 impl Index<Handle<Node>> for Graph {
@@ -1722,6 +2110,7 @@ impl Visit for Graph {
         self.sound_context.visit("SoundContext", &mut region)?;
         self.physics.visit("PhysicsWorld", &mut region)?;
         self.physics2d.visit("PhysicsWorld2D", &mut region)?;
+        let _ = self.physics_time_step.visit("PhysicsTimeStep", &mut region);
 
         Ok(())
     }
@@ -1732,8 +2121,28 @@ mod test {
     use crate::scene::base::BaseBuilder;
     use crate::scene::pivot::PivotBuilder;
     use crate::{
-        core::pool::Handle,
-        scene::{graph::Graph, node::Node, pivot::Pivot},
+        core::{
+            algebra::{Matrix4, UnitQuaternion, Vector2, Vector3},
+            math::Matrix4Ext,
+            pool::Handle,
+            sstorage::ImmutableString,
+        },
+        material::{Material, SharedMaterial},
+        renderer::batch::{ObserverInfo, RenderDataBatchStorage},
+        scene::{
+            base::{BillboardMode, LevelOfDetail, LodControlledObject, LodGroup},
+            camera::CameraBuilder,
+            collider::BitMask,
+            graph::{fixed_step::FixedTimeStep, Graph},
+            mesh::{
+                surface::{SurfaceBuilder, SurfaceData, SurfaceSharedData},
+                MeshBuilder,
+            },
+            node::Node,
+            pivot::Pivot,
+            rigidbody::RigidBodyBuilder,
+            transform::TransformBuilder,
+        },
     };
 
     #[test]
@@ -1862,4 +2271,668 @@ mod test {
 
         assert!(graph[b].children.is_empty());
     }
+
+    #[test]
+    fn test_camera_render_mask() {
+        let mut graph = Graph::new();
+
+        let camera = CameraBuilder::new(BaseBuilder::new())
+            .with_render_mask(BitMask(!(1 << 3)))
+            .build(&mut graph);
+
+        let mut make_cube = |layer: u8| {
+            MeshBuilder::new(
+                BaseBuilder::new().with_layer(layer).with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, 0.0, 5.0))
+                        .build(),
+                ),
+            )
+            .with_surfaces(vec![SurfaceBuilder::new(SurfaceSharedData::new(
+                SurfaceData::make_cube(Matrix4::identity()),
+            ))
+            .build()])
+            .build(&mut graph)
+        };
+
+        let visible = make_cube(0);
+        let excluded = make_cube(3);
+
+        graph.update(Vector2::new(800.0, 600.0), 0.0, Default::default());
+
+        let camera_ref = graph[camera].as_camera();
+        assert!(camera_ref.renders_layer(0));
+        assert!(!camera_ref.renders_layer(3));
+
+        let storage = RenderDataBatchStorage::from_graph(
+            &graph,
+            ObserverInfo {
+                observer_position: camera_ref.global_position(),
+                z_near: camera_ref.projection().z_near(),
+                z_far: camera_ref.projection().z_far(),
+                view_matrix: camera_ref.view_matrix(),
+                projection_matrix: camera_ref.projection_matrix(),
+                render_mask: camera_ref.render_mask().0,
+                occlusion_culling: false,
+            },
+            ImmutableString::new("GBuffer"),
+        );
+
+        let surface_key =
+            |handle: Handle<Node>| graph[handle].as_mesh().surfaces()[0].data_ref().key();
+
+        assert_eq!(storage.batches.len(), 1);
+        assert_eq!(storage.batches[0].data.key(), surface_key(visible));
+        assert!(storage
+            .batches
+            .iter()
+            .all(|b| b.data.key() != surface_key(excluded)));
+    }
+
+    #[test]
+    fn test_hidden_nodes() {
+        let mut graph = Graph::new();
+
+        let camera = CameraBuilder::new(BaseBuilder::new()).build(&mut graph);
+        let mesh = MeshBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(0.0, 0.0, 5.0))
+                    .build(),
+            ),
+        )
+        .with_surfaces(vec![SurfaceBuilder::new(SurfaceSharedData::new(
+            SurfaceData::make_cube(Matrix4::identity()),
+        ))
+        .build()])
+        .build(&mut graph);
+        let parent = PivotBuilder::new(BaseBuilder::new().with_children(&[mesh])).build(&mut graph);
+
+        let batch_count = |graph: &Graph| {
+            let camera_ref = graph[camera].as_camera();
+            RenderDataBatchStorage::from_graph(
+                graph,
+                ObserverInfo {
+                    observer_position: camera_ref.global_position(),
+                    z_near: camera_ref.projection().z_near(),
+                    z_far: camera_ref.projection().z_far(),
+                    view_matrix: camera_ref.view_matrix(),
+                    projection_matrix: camera_ref.projection_matrix(),
+                    render_mask: camera_ref.render_mask().0,
+                    occlusion_culling: false,
+                },
+                ImmutableString::new("GBuffer"),
+            )
+            .batches
+            .len()
+        };
+
+        graph.hidden_nodes.insert(parent);
+        graph.update(Vector2::new(800.0, 600.0), 0.0, Default::default());
+
+        // Descendants are hidden too, but nobody's own visibility is changed.
+        assert!(!graph[parent].global_visibility());
+        assert!(!graph[mesh].global_visibility());
+        assert!(graph[parent].visibility());
+        assert!(graph[mesh].visibility());
+        assert_eq!(batch_count(&graph), 0);
+
+        graph.hidden_nodes.remove(&parent);
+        graph.update(Vector2::new(800.0, 600.0), 0.0, Default::default());
+
+        assert!(graph[mesh].global_visibility());
+        assert_eq!(batch_count(&graph), 1);
+    }
+
+    #[test]
+    fn test_lod_group_switching() {
+        let mut graph = Graph::new();
+
+        let camera = CameraBuilder::new(BaseBuilder::new()).build(&mut graph);
+
+        let mut make_cube = || {
+            MeshBuilder::new(
+                BaseBuilder::new().with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, 0.0, 5.0))
+                        .build(),
+                ),
+            )
+            .with_surfaces(vec![SurfaceBuilder::new(SurfaceSharedData::new(
+                SurfaceData::make_cube(Matrix4::identity()),
+            ))
+            .build()])
+            .build(&mut graph)
+        };
+
+        let high_detail = make_cube();
+        let low_detail = make_cube();
+
+        PivotBuilder::new(
+            BaseBuilder::new()
+                .with_children(&[high_detail, low_detail])
+                .with_lod_group(LodGroup {
+                    levels: vec![
+                        LevelOfDetail::new(0.0, 0.01, vec![LodControlledObject(high_detail)]),
+                        LevelOfDetail::new(0.01, 1.0, vec![LodControlledObject(low_detail)]),
+                    ],
+                }),
+        )
+        .build(&mut graph);
+
+        let mut rendered_surfaces = |camera_position: Vector3<f32>| {
+            graph[camera]
+                .local_transform_mut()
+                .set_position(camera_position);
+            graph.update(Vector2::new(800.0, 600.0), 0.0, Default::default());
+
+            let camera_ref = graph[camera].as_camera();
+            let storage = RenderDataBatchStorage::from_graph(
+                &graph,
+                ObserverInfo {
+                    observer_position: camera_ref.global_position(),
+                    z_near: camera_ref.projection().z_near(),
+                    z_far: camera_ref.projection().z_far(),
+                    view_matrix: camera_ref.view_matrix(),
+                    projection_matrix: camera_ref.projection_matrix(),
+                    render_mask: camera_ref.render_mask().0,
+                    occlusion_culling: false,
+                },
+                ImmutableString::new("GBuffer"),
+            );
+
+            [high_detail, low_detail]
+                .iter()
+                .map(|handle| {
+                    let key = graph[*handle].as_mesh().surfaces()[0].data_ref().key();
+                    storage.batches.iter().any(|b| b.data.key() == key)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Close to the camera - only high-detail mesh is rendered.
+        assert_eq!(rendered_surfaces(Vector3::default()), [true, false]);
+
+        // Move the camera past the threshold (0.01 of the distance range is ~20 meters).
+        assert_eq!(
+            rendered_surfaces(Vector3::new(0.0, 0.0, -50.0)),
+            [false, true]
+        );
+    }
+
+    #[test]
+    fn test_identical_meshes_form_single_shared_batch() {
+        let mut graph = Graph::new();
+
+        let camera = CameraBuilder::new(BaseBuilder::new()).build(&mut graph);
+
+        let data = SurfaceSharedData::new(SurfaceData::make_cube(Matrix4::identity()));
+        let material = SharedMaterial::new(Material::standard());
+
+        const COUNT: usize = 10;
+        for i in 0..COUNT {
+            MeshBuilder::new(
+                BaseBuilder::new().with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(i as f32 * 0.1, 0.0, 5.0))
+                        .build(),
+                ),
+            )
+            .with_surfaces(vec![SurfaceBuilder::new(data.clone())
+                .with_material(material.clone())
+                .build()])
+            .build(&mut graph);
+        }
+
+        graph.update(Vector2::new(800.0, 600.0), 0.0, Default::default());
+
+        let camera = graph[camera].as_camera();
+        let storage = RenderDataBatchStorage::from_graph(
+            &graph,
+            ObserverInfo {
+                observer_position: camera.global_position(),
+                z_near: camera.projection().z_near(),
+                z_far: camera.projection().z_far(),
+                view_matrix: camera.view_matrix(),
+                projection_matrix: camera.projection_matrix(),
+                render_mask: camera.render_mask().0,
+                occlusion_culling: false,
+            },
+            ImmutableString::new("GBuffer"),
+        );
+
+        assert_eq!(storage.batches.len(), 1);
+        assert_eq!(storage.batches[0].instances.len(), COUNT);
+        assert!(storage.batches[0].is_shared());
+        assert_eq!(storage.shared_batch_count(), 1);
+    }
+
+    #[test]
+    fn test_node_behind_occluder_is_culled() {
+        let mut graph = Graph::new();
+
+        let camera = CameraBuilder::new(BaseBuilder::new())
+            .with_occlusion_culling(true)
+            .build(&mut graph);
+
+        let data = SurfaceSharedData::new(SurfaceData::make_cube(Matrix4::identity()));
+        let mut make_cube = |position: Vector3<f32>, scale: Vector3<f32>, occluder: bool| {
+            MeshBuilder::new(
+                BaseBuilder::new()
+                    .with_occluder(occluder)
+                    .with_local_transform(
+                        TransformBuilder::new()
+                            .with_local_position(position)
+                            .with_local_scale(scale)
+                            .build(),
+                    ),
+            )
+            .with_surfaces(vec![SurfaceBuilder::new(data.clone()).build()])
+            .build(&mut graph)
+        };
+
+        // A wall in front of the camera.
+        let occluder = make_cube(
+            Vector3::new(0.0, 0.0, 5.0),
+            Vector3::new(4.0, 4.0, 0.2),
+            true,
+        );
+        // Fully behind the wall.
+        let hidden = make_cube(
+            Vector3::new(0.0, 0.0, 10.0),
+            Vector3::new(1.0, 1.0, 1.0),
+            false,
+        );
+        // Visible next to the wall.
+        let visible = make_cube(
+            Vector3::new(5.0, 0.0, 10.0),
+            Vector3::new(1.0, 1.0, 1.0),
+            false,
+        );
+
+        graph.update(Vector2::new(800.0, 600.0), 0.0, Default::default());
+
+        let rendered_nodes = |graph: &Graph, occlusion_culling: bool| {
+            let camera = graph[camera].as_camera();
+            let storage = RenderDataBatchStorage::from_graph(
+                graph,
+                ObserverInfo {
+                    observer_position: camera.global_position(),
+                    z_near: camera.projection().z_near(),
+                    z_far: camera.projection().z_far(),
+                    view_matrix: camera.view_matrix(),
+                    projection_matrix: camera.projection_matrix(),
+                    render_mask: camera.render_mask().0,
+                    occlusion_culling,
+                },
+                ImmutableString::new("GBuffer"),
+            );
+            let nodes = [occluder, hidden, visible]
+                .iter()
+                .map(|node| {
+                    let transform = graph[*node].global_transform();
+                    storage.batches.iter().any(|batch| {
+                        batch
+                            .instances
+                            .iter()
+                            .any(|instance| instance.world_transform == transform)
+                    })
+                })
+                .collect::<Vec<_>>();
+            (nodes, storage.visible_node_count())
+        };
+
+        assert_eq!(rendered_nodes(&graph, false), (vec![true, true, true], 3));
+        assert_eq!(
+            rendered_nodes(&graph, graph[camera].as_camera().occlusion_culling()),
+            (vec![true, false, true], 2)
+        );
+
+        // Disabled occluder hides nothing.
+        graph[occluder].set_occluder(false);
+        assert_eq!(rendered_nodes(&graph, true), (vec![true, true, true], 3));
+    }
+
+    #[test]
+    fn test_uniform_scale_propagation() {
+        let mut graph = Graph::new();
+
+        let child = PivotBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(1.0, 2.0, 3.0))
+                    .build(),
+            ),
+        )
+        .build(&mut graph);
+        let parent = PivotBuilder::new(
+            BaseBuilder::new()
+                .with_children(&[child])
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(10.0, 0.0, 0.0))
+                        .build(),
+                ),
+        )
+        .build(&mut graph);
+
+        graph[parent].local_transform_mut().set_uniform_scale(2.0);
+        graph.update_hierarchical_data();
+
+        assert_eq!(
+            **graph[parent].local_transform().scale(),
+            Vector3::repeat(2.0)
+        );
+        assert_eq!(graph.global_scale(child), Vector3::repeat(2.0));
+        assert_eq!(graph[child].global_position(), Vector3::new(12.0, 4.0, 6.0));
+
+        // Negative scale mirrors the hierarchy.
+        graph[parent].local_transform_mut().set_uniform_scale(-1.0);
+        graph.update_hierarchical_data();
+
+        assert_eq!(graph.global_scale(child), Vector3::repeat(-1.0));
+        assert_eq!(
+            graph[child].global_position(),
+            Vector3::new(9.0, -2.0, -3.0)
+        );
+    }
+
+    #[test]
+    fn test_path_of_and_find_by_path() {
+        let mut graph = Graph::new();
+
+        let camera = PivotBuilder::new(BaseBuilder::new().with_name("Camera")).build(&mut graph);
+        let body = PivotBuilder::new(
+            BaseBuilder::new()
+                .with_name("Body")
+                .with_children(&[camera]),
+        )
+        .build(&mut graph);
+        let player = PivotBuilder::new(
+            BaseBuilder::new()
+                .with_name("Player")
+                .with_children(&[body]),
+        )
+        .build(&mut graph);
+
+        let path = graph.path_of(camera);
+        assert_eq!(path, "Player/Body/Camera");
+        assert_eq!(graph.find_by_path(&path).map(|(h, _)| h), Some(camera));
+
+        assert_eq!(graph.path_of(player), "Player");
+        assert_eq!(graph.find_by_path("Player").map(|(h, _)| h), Some(player));
+
+        assert_eq!(graph.path_of(graph.get_root()), "");
+        assert_eq!(
+            graph.find_by_path("").map(|(h, _)| h),
+            Some(graph.get_root())
+        );
+
+        assert!(graph.find_by_path("Player/Camera").is_none());
+    }
+
+    #[test]
+    fn test_set_child_index() {
+        let mut graph = Graph::new();
+
+        let a = PivotBuilder::new(BaseBuilder::new().with_name("A")).build(&mut graph);
+        let b = PivotBuilder::new(BaseBuilder::new().with_name("B")).build(&mut graph);
+        let c = PivotBuilder::new(BaseBuilder::new().with_name("C")).build(&mut graph);
+        let parent =
+            PivotBuilder::new(BaseBuilder::new().with_children(&[a, b, c])).build(&mut graph);
+
+        assert_eq!(graph.set_child_index(parent, c, 0), Some(2));
+        assert_eq!(graph[parent].children(), &[c, a, b]);
+
+        // Out-of-bounds index must be clamped.
+        assert_eq!(graph.set_child_index(parent, c, 100), Some(0));
+        assert_eq!(graph[parent].children(), &[a, b, c]);
+
+        // Not a child of the parent.
+        assert_eq!(graph.set_child_index(a, b, 0), None);
+        assert_eq!(graph[parent].children(), &[a, b, c]);
+    }
+
+    #[test]
+    fn test_link_nodes_rejects_cycles() {
+        let mut graph = Graph::new();
+        let root = graph.get_root();
+        let parent = PivotBuilder::new(BaseBuilder::new()).build(&mut graph);
+        let child = PivotBuilder::new(BaseBuilder::new()).build(&mut graph);
+        graph.link_nodes(child, parent);
+
+        assert!(graph.can_link_nodes(child, root));
+        assert!(!graph.can_link_nodes(parent, child));
+        assert!(!graph.can_link_nodes(parent, parent));
+
+        graph.link_nodes(parent, child);
+        graph.link_nodes(parent, parent);
+        graph.link_nodes_keep_global_position_rotation(parent, child);
+
+        // Graph must stay unchanged.
+        assert_eq!(graph[parent].parent(), root);
+        assert_eq!(graph[parent].children(), &[child]);
+        assert_eq!(graph[child].parent(), parent);
+        assert!(graph[child].children().is_empty());
+        assert_eq!(graph[root].children(), &[parent]);
+    }
+
+    #[test]
+    fn test_copy_node_inplace_deep_hierarchy() {
+        let mut graph = Graph::new();
+        let grand_child =
+            PivotBuilder::new(BaseBuilder::new().with_name("GrandChild")).build(&mut graph);
+        let child = PivotBuilder::new(
+            BaseBuilder::new()
+                .with_name("Child")
+                .with_children(&[grand_child]),
+        )
+        .build(&mut graph);
+        let parent = PivotBuilder::new(
+            BaseBuilder::new()
+                .with_name("Parent")
+                .with_children(&[child]),
+        )
+        .build(&mut graph);
+
+        let node_count = graph.node_count();
+        let (copy, _) = graph.copy_node_inplace(parent, &mut |_, _| true);
+
+        // Every node must be copied exactly once.
+        assert_eq!(graph.node_count(), node_count + 3);
+        assert_eq!(graph[copy].name(), "Parent");
+        let child_copy = graph[copy].children()[0];
+        assert_eq!(graph[child_copy].name(), "Child");
+        let grand_child_copy = graph[child_copy].children()[0];
+        assert_eq!(graph[grand_child_copy].name(), "GrandChild");
+    }
+
+    #[test]
+    fn test_nearest_node() {
+        let mut graph = Graph::new();
+
+        let mut make_node = |tag: &str, position: Vector3<f32>| {
+            PivotBuilder::new(
+                BaseBuilder::new()
+                    .with_tag(tag.to_owned())
+                    .with_local_transform(
+                        TransformBuilder::new()
+                            .with_local_position(position)
+                            .build(),
+                    ),
+            )
+            .build(&mut graph)
+        };
+
+        let far = make_node("Interactable", Vector3::new(10.0, 0.0, 0.0));
+        let near = make_node("Interactable", Vector3::new(0.0, 3.0, 4.0));
+        let nearest_untagged = make_node("", Vector3::new(1.0, 0.0, 0.0));
+
+        graph.update(Vector2::new(800.0, 600.0), 0.0, Default::default());
+
+        let point = Vector3::new(0.0, 0.0, 0.0);
+        assert_eq!(
+            graph.nearest_node(point, &mut |_, node| node.tag() == "Interactable"),
+            Some((near, 5.0))
+        );
+        assert_eq!(
+            graph.nearest_node(point, &mut |_, _| true),
+            Some((nearest_untagged, 1.0))
+        );
+        assert_eq!(
+            graph.nearest_node(Vector3::new(9.0, 0.0, 0.0), &mut |handle, _| handle != near),
+            Some((far, 1.0))
+        );
+        assert_eq!(
+            graph.nearest_node(point, &mut |_, node| node.tag() == "Door"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_billboard_faces_camera() {
+        let mut graph = Graph::new();
+
+        let camera_position = Vector3::new(0.0, 5.0, -10.0);
+        CameraBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(camera_position)
+                    .build(),
+            ),
+        )
+        .build(&mut graph);
+
+        let position = Vector3::new(3.0, 1.0, 2.0);
+        fn make_billboard(
+            graph: &mut Graph,
+            position: Vector3<f32>,
+            mode: BillboardMode,
+        ) -> Handle<Node> {
+            PivotBuilder::new(
+                BaseBuilder::new()
+                    .with_billboard(mode)
+                    .with_local_transform(
+                        TransformBuilder::new()
+                            .with_local_position(position)
+                            .with_local_scale(Vector3::new(2.0, 2.0, 2.0))
+                            .build(),
+                    ),
+            )
+            .build(graph)
+        }
+        let full = make_billboard(&mut graph, position, BillboardMode::Full);
+        let y_axis = make_billboard(&mut graph, position, BillboardMode::YAxis);
+
+        graph.update(Vector2::new(800.0, 600.0), 0.0, Default::default());
+
+        let expected = (camera_position - position).normalize();
+        let look = graph[full].look_vector();
+        assert!((look.normalize() - expected).norm() < 1.0e-5);
+        // Scale and position must be preserved.
+        assert!((look.norm() - 2.0).abs() < 1.0e-5);
+        assert_eq!(graph[full].global_position(), position);
+
+        let mut expected = camera_position - position;
+        expected.y = 0.0;
+        let look = graph[y_axis].look_vector().normalize();
+        assert!((look - expected.normalize()).norm() < 1.0e-5);
+        assert!((graph[y_axis].up_vector().normalize() - Vector3::y()).norm() < 1.0e-5);
+
+        // Local transform stays untouched.
+        assert_eq!(
+            **graph[full].local_transform().rotation(),
+            UnitQuaternion::identity()
+        );
+    }
+
+    #[test]
+    fn test_previous_global_transform() {
+        let mut graph = Graph::new();
+        let parent = PivotBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(1.0, 0.0, 0.0))
+                    .build(),
+            ),
+        )
+        .build(&mut graph);
+        let child = PivotBuilder::new(BaseBuilder::new()).build(&mut graph);
+        graph.link_nodes(child, parent);
+
+        // New nodes did not move yet.
+        graph.update(Vector2::new(800.0, 600.0), 0.0, Default::default());
+        assert_eq!(
+            graph.previous_global_transform(child),
+            graph[child].global_transform()
+        );
+
+        let translation = Vector3::new(2.0, -3.0, 0.5);
+        graph[parent].local_transform_mut().offset(translation);
+        graph.update(Vector2::new(800.0, 600.0), 0.0, Default::default());
+
+        let delta =
+            graph[child].global_position() - graph.previous_global_transform(child).position();
+        assert_eq!(delta, translation);
+
+        // Movement is tracked per frame.
+        graph.update(Vector2::new(800.0, 600.0), 0.0, Default::default());
+        assert_eq!(
+            graph.previous_global_transform(child),
+            graph[child].global_transform()
+        );
+    }
+
+    #[test]
+    fn test_interpolated_transform_is_between_physics_states() {
+        let mut graph = Graph::new();
+        graph.physics_time_step = FixedTimeStep {
+            // Keep node transforms at the physics states, to be able to compare with them.
+            interpolate: false,
+            ..FixedTimeStep::new(60.0)
+        };
+
+        let body = RigidBodyBuilder::new(BaseBuilder::new())
+            .with_lin_vel(Vector3::new(6.0, 0.0, 0.0))
+            .with_gravity_scale(0.0)
+            .build(&mut graph);
+        let child = PivotBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(0.0, 1.0, 0.0))
+                    .build(),
+            ),
+        )
+        .build(&mut graph);
+        graph.link_nodes(child, body);
+
+        let frame_size = Vector2::new(800.0, 600.0);
+        let step = graph.physics_time_step.step_duration();
+
+        // A full step, no time is left in the accumulator.
+        graph.update(frame_size, step, Default::default());
+        let previous = graph[body].local_transform().position().x;
+
+        // One more step and a half of the next one.
+        graph.update(frame_size, 1.5 * step, Default::default());
+        let current = graph[body].local_transform().position().x;
+        assert!(current > previous);
+
+        let interpolated = graph.interpolated_global_transform(body);
+        assert!((interpolated[12] - (previous + current) * 0.5).abs() < 0.001);
+        assert!(interpolated[12] > previous && interpolated[12] < current);
+
+        // Descendants of the body are interpolated as well.
+        let interpolated_child = graph.interpolated_global_transform(child);
+        assert!((interpolated_child[12] - interpolated[12]).abs() < 0.001);
+        assert!((interpolated_child[13] - 1.0).abs() < 0.001);
+
+        // Without fixed time step the transform is left as is.
+        graph.physics_time_step.enabled = false;
+        graph.update(frame_size, step, Default::default());
+        assert_eq!(
+            graph.interpolated_global_transform(body),
+            graph[body].global_transform()
+        );
+    }
 }
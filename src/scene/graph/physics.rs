@@ -9,7 +9,7 @@ use crate::{
         arrayvec::ArrayVec,
         instant,
         log::{Log, MessageKind},
-        math::Matrix4Ext,
+        math::{m4x4_approx_eq, Matrix4Ext},
         parking_lot::Mutex,
         pool::Handle,
         reflect::prelude::*,
@@ -30,9 +30,11 @@ use crate::{
         node::{Node, NodeTrait},
         rigidbody::ApplyAction,
         terrain::Terrain,
+        trigger::{TriggerShape, TriggerVolume},
     },
     utils::raw_mesh::{RawMeshBuilder, RawVertex},
 };
+use fxhash::FxHashMap;
 use rapier3d::{
     dynamics::{
         CCDSolver, GenericJoint, GenericJointBuilder, ImpulseJointHandle, ImpulseJointSet,
@@ -40,14 +42,14 @@ use rapier3d::{
         RigidBodyActivation, RigidBodyBuilder, RigidBodyHandle, RigidBodySet, RigidBodyType,
     },
     geometry::{
-        BroadPhase, Collider, ColliderBuilder, ColliderHandle, ColliderSet, Cuboid,
-        InteractionGroups, NarrowPhase, Ray, SharedShape,
+        ActiveCollisionTypes, BroadPhase, Collider, ColliderBuilder, ColliderHandle, ColliderSet,
+        Cuboid, InteractionGroups, NarrowPhase, Ray, SharedShape,
     },
     pipeline::{DebugRenderPipeline, EventHandler, PhysicsPipeline, QueryFilter, QueryPipeline},
     prelude::JointAxis,
 };
 use std::{
-    cell::{Cell, RefCell},
+    cell::{Cell, RefCell, RefMut},
     cmp::Ordering,
     fmt::{Debug, Formatter},
     hash::Hash,
@@ -319,6 +321,24 @@ pub struct ContactPair {
     pub has_any_active_contact: bool,
 }
 
+/// A single contact point of a rigid body with some other collider. See [`crate::scene::rigidbody::RigidBody::contacts`]
+/// for more info.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ContactInfo {
+    /// World-space position of the contact point on the surface of the collider of the body.
+    pub point: Vector3<f32>,
+    /// World-space contact normal, it points from the other collider to the collider of the body. For
+    /// example, a body that stands on a floor will have a contact with the normal pointing up.
+    pub normal: Vector3<f32>,
+    /// A collider of the body, that is involved in the contact.
+    pub collider: Handle<Node>,
+    /// The other collider involved in the contact.
+    pub other_collider: Handle<Node>,
+    /// A rigid body to which the other collider is attached to. Could be [`Handle::NONE`] if the
+    /// collider is not attached to any body.
+    pub other_body: Handle<Node>,
+}
+
 /// Intersection info for pair of colliders.
 pub struct IntersectionPair {
     /// The first collider involved in the contact pair.
@@ -891,9 +911,48 @@ pub struct PhysicsWorld {
     #[visit(skip)]
     #[reflect(hidden)]
     query: RefCell<QueryPipeline>,
+    // Query pipeline is updated lazily before a scene query, only if bodies or colliders were changed since
+    // the last update. This way it is updated once per frame in most cases.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    query_outdated: Cell<bool>,
     #[visit(skip)]
     #[reflect(hidden)]
     debug_render_pipeline: Mutex<DebugRenderPipeline>,
+    // Interpolation factor between the previous and the current poses of dynamic rigid bodies. It is
+    // set by the graph when fixed time step is used.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) fixed_step_alpha: Option<f32>,
+    // Whether the transforms of rigid body nodes should be interpolated using `fixed_step_alpha` or not.
+    // [`Self::interpolated_pose`] works regardless of it.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) interpolate: bool,
+    // Poses of dynamic rigid bodies before the last simulation step.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    previous_poses: FxHashMap<RigidBodyHandle, Isometry3<f32>>,
+    // Interpolated poses that were written to the rigid body nodes. They're used only for rendering and
+    // must not be pushed back to the simulation.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    interpolated_poses: FxHashMap<RigidBodyHandle, Isometry3<f32>>,
+    // Linear speed limits of rigid bodies, only the bodies with a limit are stored here.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    max_linear_speeds: FxHashMap<RigidBodyHandle, f32>,
+}
+
+fn trigger_shape_into_native_shape(shape: &TriggerShape) -> SharedShape {
+    match shape {
+        TriggerShape::Ball(ball) => SharedShape::ball(ball.radius),
+        TriggerShape::Cuboid(cuboid) => SharedShape::cuboid(
+            cuboid.half_extents.x,
+            cuboid.half_extents.y,
+            cuboid.half_extents.z,
+        ),
+    }
 }
 
 fn isometry_from_global_transform(transform: &Matrix4<f32>) -> Isometry3<f32> {
@@ -955,8 +1014,59 @@ impl PhysicsWorld {
             },
             event_handler: Box::new(()),
             query: RefCell::new(Default::default()),
+            query_outdated: Cell::new(true),
             performance_statistics: Default::default(),
             debug_render_pipeline: Default::default(),
+            fixed_step_alpha: None,
+            interpolate: false,
+            previous_poses: Default::default(),
+            interpolated_poses: Default::default(),
+            max_linear_speeds: Default::default(),
+        }
+    }
+
+    /// Returns the pose of the given rigid body blended between the two last physics states using the
+    /// interpolation factor of the fixed time step (see [`super::fixed_step::FixedTimeStep`]). Use it to
+    /// get smooth motion when physics is stepped with fixed time step, but frames are not. Returns `None`
+    /// if the fixed time step is disabled, the body is not dynamic or it was not simulated yet.
+    pub fn interpolated_pose(
+        &self,
+        rigid_body: &scene::rigidbody::RigidBody,
+    ) -> Option<Isometry3<f32>> {
+        let alpha = self.fixed_step_alpha?;
+        let native = self.bodies.set.get(rigid_body.native.get())?;
+        let previous = self.previous_poses.get(&rigid_body.native.get())?;
+        Some(previous.lerp_slerp(native.position(), alpha))
+    }
+
+    /// Returns integration parameters that will be passed to the physics engine for a simulation step with
+    /// the given time step. The time step is overridden by [`IntegrationParameters::dt`], if it is set.
+    pub fn rapier_integration_parameters(
+        &self,
+        dt: f32,
+    ) -> rapier3d::dynamics::IntegrationParameters {
+        rapier3d::dynamics::IntegrationParameters {
+            dt: self.integration_parameters.dt.unwrap_or(dt),
+            min_ccd_dt: self.integration_parameters.min_ccd_dt,
+            erp: self.integration_parameters.erp,
+            damping_ratio: self.integration_parameters.damping_ratio,
+            joint_erp: self.integration_parameters.joint_erp,
+            joint_damping_ratio: self.integration_parameters.joint_damping_ratio,
+            allowed_linear_error: self.integration_parameters.allowed_linear_error,
+            max_penetration_correction: self.integration_parameters.max_penetration_correction,
+            prediction_distance: self.integration_parameters.prediction_distance,
+            max_velocity_iterations: self.integration_parameters.max_velocity_iterations as usize,
+            max_velocity_friction_iterations: self
+                .integration_parameters
+                .max_velocity_friction_iterations
+                as usize,
+            max_stabilization_iterations: self.integration_parameters.max_stabilization_iterations
+                as usize,
+            interleave_restitution_and_friction_resolution: self
+                .integration_parameters
+                .interleave_restitution_and_friction_resolution,
+            min_island_size: self.integration_parameters.min_island_size as usize,
+            max_ccd_substeps: self.integration_parameters.max_ccd_substeps as usize,
         }
     }
 
@@ -964,32 +1074,19 @@ impl PhysicsWorld {
         let time = instant::Instant::now();
 
         if self.enabled {
-            let integration_parameters = rapier3d::dynamics::IntegrationParameters {
-                dt: self.integration_parameters.dt.unwrap_or(dt),
-                min_ccd_dt: self.integration_parameters.min_ccd_dt,
-                erp: self.integration_parameters.erp,
-                damping_ratio: self.integration_parameters.damping_ratio,
-                joint_erp: self.integration_parameters.joint_erp,
-                joint_damping_ratio: self.integration_parameters.joint_damping_ratio,
-                allowed_linear_error: self.integration_parameters.allowed_linear_error,
-                max_penetration_correction: self.integration_parameters.max_penetration_correction,
-                prediction_distance: self.integration_parameters.prediction_distance,
-                max_velocity_iterations: self.integration_parameters.max_velocity_iterations
-                    as usize,
-                max_velocity_friction_iterations: self
-                    .integration_parameters
-                    .max_velocity_friction_iterations
-                    as usize,
-                max_stabilization_iterations: self
-                    .integration_parameters
-                    .max_stabilization_iterations
-                    as usize,
-                interleave_restitution_and_friction_resolution: self
-                    .integration_parameters
-                    .interleave_restitution_and_friction_resolution,
-                min_island_size: self.integration_parameters.min_island_size as usize,
-                max_ccd_substeps: self.integration_parameters.max_ccd_substeps as usize,
-            };
+            self.previous_poses.clear();
+            if self.fixed_step_alpha.is_some() {
+                for (handle, body) in self.bodies.set.iter() {
+                    if body.body_type() == RigidBodyType::Dynamic {
+                        self.previous_poses.insert(handle, *body.position());
+                    }
+                }
+            }
+
+            // Velocity could be changed directly (for example, by an impulse) since the last step.
+            self.clamp_linear_speeds();
+
+            let integration_parameters = self.rapier_integration_parameters(dt);
 
             self.pipeline.step(
                 &self.gravity,
@@ -1008,14 +1105,45 @@ impl PhysicsWorld {
                 &(),
                 &*self.event_handler,
             );
+
+            self.clamp_linear_speeds();
+            self.query_outdated.set(true);
         }
 
         self.performance_statistics.step_time += instant::Instant::now() - time;
     }
 
+    // Rapier has no speed limit, so the velocity is clamped manually before and after each step. `get_mut`
+    // is expensive, so use it only when the velocity is actually clamped.
+    fn clamp_linear_speeds(&mut self) {
+        for (&handle, &max_speed) in self.max_linear_speeds.iter() {
+            let clamped_lin_vel = self.bodies.set.get(handle).and_then(|body| {
+                let lin_vel = *body.linvel();
+                let speed = lin_vel.norm();
+                (body.body_type() == RigidBodyType::Dynamic && speed > max_speed)
+                    .then(|| lin_vel.scale(max_speed / speed))
+            });
+
+            if let Some(lin_vel) = clamped_lin_vel {
+                if let Some(body) = self.bodies.set.get_mut(handle) {
+                    body.set_linvel(lin_vel, false);
+                }
+            }
+        }
+    }
+
+    fn set_max_linear_speed(&mut self, handle: RigidBodyHandle, max_speed: f32) {
+        if max_speed > 0.0 {
+            self.max_linear_speeds.insert(handle, max_speed);
+        } else {
+            self.max_linear_speeds.remove(&handle);
+        }
+    }
+
     pub(super) fn add_body(&mut self, owner: Handle<Node>, body: RigidBody) -> RigidBodyHandle {
         let handle = self.bodies.set.insert(body);
         self.bodies.map.insert(handle, owner);
+        self.query_outdated.set(true);
         handle
     }
 
@@ -1034,7 +1162,9 @@ impl PhysicsWorld {
             .is_some()
         {
             assert!(self.bodies.map.remove_by_key(&handle).is_some());
+            self.query_outdated.set(true);
         }
+        self.max_linear_speeds.remove(&handle);
     }
 
     pub(super) fn add_collider(
@@ -1048,6 +1178,7 @@ impl PhysicsWorld {
                 .set
                 .insert_with_parent(collider, parent_body, &mut self.bodies.set);
         self.colliders.map.insert(handle, owner);
+        self.query_outdated.set(true);
         handle
     }
 
@@ -1059,6 +1190,7 @@ impl PhysicsWorld {
             .is_some()
         {
             assert!(self.colliders.map.remove_by_key(&handle).is_some());
+            self.query_outdated.set(true);
             true
         } else {
             false
@@ -1096,18 +1228,22 @@ impl PhysicsWorld {
         );
     }
 
+    // Returns the query pipeline, that is in sync with the current state of bodies and colliders. Bodies
+    // and colliders could be removed between consecutive queries, so the pipeline must not be used without
+    // the update, otherwise it could contain invalid handles.
+    fn updated_query_pipeline(&self) -> RefMut<QueryPipeline> {
+        let mut query = self.query.borrow_mut();
+        if self.query_outdated.replace(false) {
+            query.update(&self.bodies.set, &self.colliders.set);
+        }
+        query
+    }
+
     /// Casts a ray with given options.
     pub fn cast_ray<S: QueryResultsStorage>(&self, opts: RayCastOptions, query_buffer: &mut S) {
         let time = instant::Instant::now();
 
-        let mut query = self.query.borrow_mut();
-
-        // TODO: Ideally this must be called once per frame, but it seems to be impossible because
-        // a body can be deleted during the consecutive calls of this method which will most
-        // likely end up in panic because of invalid handle stored in internal acceleration
-        // structure. This could be fixed by delaying deleting of bodies/collider to the end
-        // of the frame.
-        query.update(&self.bodies.set, &self.colliders.set);
+        let query = self.updated_query_pipeline();
 
         query_buffer.clear();
         let ray = Ray::new(
@@ -1154,14 +1290,68 @@ impl PhysicsWorld {
         );
     }
 
+    /// Casts a ray and returns the closest hit with a solid collider. Sensors (including trigger volumes)
+    /// and colliders of `exclude_body` (if any) are ignored.
+    pub(crate) fn cast_ray_closest_solid(
+        &self,
+        ray_origin: Point3<f32>,
+        ray_direction: Vector3<f32>,
+        max_len: f32,
+        exclude_body: Option<RigidBodyHandle>,
+    ) -> Option<Intersection> {
+        let ray = Ray::new(
+            ray_origin,
+            ray_direction
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_default(),
+        );
+
+        let mut filter = QueryFilter::new().exclude_sensors();
+        if let Some(exclude_body) = exclude_body {
+            filter = filter.exclude_rigid_body(exclude_body);
+        }
+
+        self.updated_query_pipeline()
+            .cast_ray_and_get_normal(
+                &self.bodies.set,
+                &self.colliders.set,
+                &ray,
+                max_len,
+                true,
+                filter,
+            )
+            .and_then(|(handle, intersection)| {
+                Some(Intersection {
+                    collider: self.colliders.map.value_of(&handle).cloned()?,
+                    normal: intersection.normal,
+                    position: ray.point_at(intersection.toi),
+                    feature: intersection.feature.into(),
+                    toi: intersection.toi,
+                })
+            })
+    }
+
     pub(crate) fn set_rigid_body_position(
         &mut self,
         rigid_body: &scene::rigidbody::RigidBody,
         new_global_transform: &Matrix4<f32>,
     ) {
+        let new_position = isometry_from_global_transform(new_global_transform);
+
+        // Skip the interpolated pose, otherwise the simulation would be "rewound" to it every frame.
+        if let Some(interpolated) = self.interpolated_poses.get(&rigid_body.native.get()) {
+            if m4x4_approx_eq(
+                &interpolated.to_homogeneous(),
+                &new_position.to_homogeneous(),
+            ) {
+                return;
+            }
+        }
+
         if let Some(native) = self.bodies.set.get_mut(rigid_body.native.get()) {
+            self.query_outdated.set(true);
             native.set_position(
-                isometry_from_global_transform(new_global_transform),
+                new_position,
                 // Do not wake up body, it is too expensive and must be done **only** by explicit
                 // `wake_up` call!
                 false,
@@ -1177,10 +1367,26 @@ impl PhysicsWorld {
         if self.enabled {
             if let Some(native) = self.bodies.set.get(rigid_body.native.get()) {
                 if native.body_type() == RigidBodyType::Dynamic {
+                    let position = match (
+                        self.fixed_step_alpha.filter(|_| self.interpolate),
+                        self.previous_poses.get(&rigid_body.native.get()),
+                    ) {
+                        (Some(t), Some(previous)) => {
+                            let interpolated = previous.lerp_slerp(native.position(), t);
+                            self.interpolated_poses
+                                .insert(rigid_body.native.get(), interpolated);
+                            interpolated
+                        }
+                        _ => {
+                            self.interpolated_poses.remove(&rigid_body.native.get());
+                            *native.position()
+                        }
+                    };
+
                     let local_transform: Matrix4<f32> = parent_transform
                         .try_inverse()
                         .unwrap_or_else(Matrix4::identity)
-                        * native.position().to_homogeneous();
+                        * position.to_homogeneous();
 
                     let local_rotation = UnitQuaternion::from_matrix_eps(
                         &local_transform.basis(),
@@ -1225,9 +1431,15 @@ impl PhysicsWorld {
         // 1) `get_mut` is **very** expensive because it forces physics engine to recalculate contacts
         //    and a lot of other stuff, this is why we need `anything_changed` flag.
         if rigid_body_node.native.get() != RigidBodyHandle::invalid() {
+            rigid_body_node
+                .max_linear_speed
+                .try_sync_model(|v| self.set_max_linear_speed(rigid_body_node.native.get(), v));
+
             let mut actions = rigid_body_node.actions.lock();
             if rigid_body_node.need_sync_model() || !actions.is_empty() {
                 if let Some(native) = self.bodies.set.get_mut(rigid_body_node.native.get()) {
+                    self.query_outdated.set(true);
+
                     // Sync native rigid body's properties with scene node's in case if they
                     // were changed by user.
                     rigid_body_node
@@ -1264,9 +1476,16 @@ impl PhysicsWorld {
                             activation.angular_threshold = -1.0;
                         };
                     });
-                    rigid_body_node
-                        .translation_locked
-                        .try_sync_model(|v| native.lock_translations(v, false));
+                    // Every flag must be synced, so do not short-circuit here.
+                    let translation_locks_changed =
+                        rigid_body_node.translation_locked.try_sync_model(|_| ())
+                            | rigid_body_node.x_translation_locked.try_sync_model(|_| ())
+                            | rigid_body_node.y_translation_locked.try_sync_model(|_| ())
+                            | rigid_body_node.z_translation_locked.try_sync_model(|_| ());
+                    if translation_locks_changed {
+                        let [x, y, z] = rigid_body_node.translation_locks();
+                        native.set_enabled_translations(!x, !y, !z, false);
+                    }
                     rigid_body_node.x_rotation_locked.try_sync_model(|v| {
                         native.set_enabled_rotations(
                             !v,
@@ -1352,13 +1571,16 @@ impl PhysicsWorld {
                     !rigid_body_node.is_z_rotation_locked(),
                 );
 
-            if rigid_body_node.is_translation_locked() {
-                builder = builder.lock_translations();
-            }
+            let [x, y, z] = rigid_body_node.translation_locks();
+            builder = builder.enabled_translations(!x, !y, !z);
 
             rigid_body_node
                 .native
                 .set(self.add_body(handle, builder.build()));
+            self.set_max_linear_speed(
+                rigid_body_node.native.get(),
+                rigid_body_node.max_linear_speed(),
+            );
 
             Log::writeln(
                 MessageKind::Information,
@@ -1393,6 +1615,8 @@ impl PhysicsWorld {
         if collider_node.native.get() != ColliderHandle::invalid() {
             if anything_changed {
                 if let Some(native) = self.colliders.set.get_mut(collider_node.native.get()) {
+                    self.query_outdated.set(true);
+
                     if collider_node.transform_modified.get() {
                         native.set_position_wrt_parent(Isometry3 {
                             rotation: **collider_node.local_transform().rotation(),
@@ -1418,12 +1642,19 @@ impl PhysicsWorld {
                     collider_node
                         .restitution
                         .try_sync_model(|v| native.set_restitution(v));
-                    collider_node.collision_groups.try_sync_model(|v| {
+                    // Collision groups could be derived from the layer of the node, so every
+                    // property they depend on must be checked.
+                    let collision_groups_changed =
+                        collider_node.collision_groups.try_sync_model(|_| {})
+                            | collider_node.use_layer_groups.try_sync_model(|_| {})
+                            | collider_node.layer.try_sync_model(|_| {});
+                    if collision_groups_changed {
+                        let groups = collider_node.effective_collision_groups();
                         native.set_collision_groups(InteractionGroups::new(
-                            u32_to_group(v.memberships.0),
-                            u32_to_group(v.filter.0),
-                        ))
-                    });
+                            u32_to_group(groups.memberships.0),
+                            u32_to_group(groups.filter.0),
+                        ));
+                    }
                     collider_node.solver_groups.try_sync_model(|v| {
                         native.set_solver_groups(InteractionGroups::new(
                             u32_to_group(v.memberships.0),
@@ -1469,8 +1700,8 @@ impl PhysicsWorld {
                         .friction(collider_node.friction())
                         .restitution(collider_node.restitution())
                         .collision_groups(InteractionGroups::new(
-                            u32_to_group(collider_node.collision_groups().memberships.0),
-                            u32_to_group(collider_node.collision_groups().filter.0),
+                            u32_to_group(collider_node.effective_collision_groups().memberships.0),
+                            u32_to_group(collider_node.effective_collision_groups().filter.0),
                         ))
                         .friction_combine_rule(collider_node.friction_combine_rule().into())
                         .restitution_combine_rule(collider_node.restitution_combine_rule().into())
@@ -1501,6 +1732,65 @@ impl PhysicsWorld {
         }
     }
 
+    pub(crate) fn sync_to_trigger_volume_node(
+        &mut self,
+        nodes: &NodePool,
+        handle: Handle<Node>,
+        volume: &TriggerVolume,
+    ) {
+        if !volume.is_globally_enabled() {
+            self.remove_collider(volume.native.get());
+            volume.native.set(ColliderHandle::invalid());
+            return;
+        }
+
+        // Trigger volume is not attached to any rigid body, so its native collider is positioned in
+        // world space directly.
+        let position = isometry_from_global_transform(&isometric_global_transform(nodes, handle));
+
+        if let Some(native) = self.colliders.set.get(volume.native.get()) {
+            // `get_mut` is expensive, so use it only if something has actually changed.
+            if *native.position() != position || volume.shape.need_sync() {
+                if let Some(native) = self.colliders.set.get_mut(volume.native.get()) {
+                    self.query_outdated.set(true);
+
+                    native.set_position(position);
+                    volume
+                        .shape
+                        .try_sync_model(|v| native.set_shape(trigger_shape_into_native_shape(&v)));
+                }
+            }
+        } else {
+            let collider = ColliderBuilder::new(trigger_shape_into_native_shape(volume.shape()))
+                .position(position)
+                .sensor(true)
+                // Detect kinematic and static bodies too, character controllers are usually kinematic.
+                .active_collision_types(ActiveCollisionTypes::all())
+                .build();
+            let native_handle = self.colliders.set.insert(collider);
+            self.query_outdated.set(true);
+            self.colliders.map.insert(native_handle, handle);
+            volume.native.set(native_handle);
+            volume.shape.try_sync_model(|_| ());
+        }
+    }
+
+    /// Returns handles of collider nodes, that intersect the given sensor collider.
+    pub(crate) fn intersecting_colliders(&self, sensor: ColliderHandle) -> Vec<Handle<Node>> {
+        self.narrow_phase
+            .intersections_with(sensor)
+            .filter(|(_, _, intersecting)| *intersecting)
+            .filter_map(|(collider1, collider2, _)| {
+                let other = if collider1 == sensor {
+                    collider2
+                } else {
+                    collider1
+                };
+                self.colliders.map.value_of(&other).cloned()
+            })
+            .collect()
+    }
+
     pub(crate) fn sync_to_joint_node(
         &mut self,
         nodes: &NodePool,
@@ -1671,6 +1961,123 @@ impl PhysicsWorld {
                 has_any_active_contact: c.has_any_active_contact,
             })
     }
+    /// Collects active contact points of every collider of the given rigid body.
+    pub(crate) fn body_contacts(&self, body: RigidBodyHandle) -> Vec<ContactInfo> {
+        let mut contacts = Vec::new();
+
+        let body = match self.bodies.set.get(body) {
+            Some(body) => body,
+            None => return contacts,
+        };
+
+        for &collider in body.colliders() {
+            for pair in self.narrow_phase.contacts_with(collider) {
+                if !pair.has_any_active_contact {
+                    continue;
+                }
+
+                let is_first = pair.collider1 == collider;
+                let (other, position) = if is_first {
+                    (pair.collider2, self.colliders.set.get(pair.collider1))
+                } else {
+                    (pair.collider1, self.colliders.set.get(pair.collider2))
+                };
+                let position = match position {
+                    Some(own_collider) => *own_collider.position(),
+                    None => continue,
+                };
+
+                let other_body = self
+                    .colliders
+                    .set
+                    .get(other)
+                    .and_then(|c| c.parent())
+                    .and_then(|h| self.bodies.map.value_of(&h).cloned())
+                    .unwrap_or_default();
+
+                for manifold in pair.manifolds.iter() {
+                    // Manifold normal points from the first collider to the second one.
+                    let normal = if is_first {
+                        -manifold.data.normal
+                    } else {
+                        manifold.data.normal
+                    };
+
+                    for point in manifold.points.iter() {
+                        let local_point = if is_first {
+                            point.local_p1
+                        } else {
+                            point.local_p2
+                        };
+
+                        contacts.push(ContactInfo {
+                            point: position.transform_point(&local_point).coords,
+                            normal,
+                            collider: self
+                                .colliders
+                                .map
+                                .value_of(&collider)
+                                .cloned()
+                                .unwrap_or_default(),
+                            other_collider: self
+                                .colliders
+                                .map
+                                .value_of(&other)
+                                .cloned()
+                                .unwrap_or_default(),
+                            other_body,
+                        });
+                    }
+                }
+            }
+        }
+
+        contacts
+    }
+
+    /// Casts a ray straight down from the center of the given rigid body and returns the closest hit with a
+    /// solid collider that does not belong to the body, sensors (including trigger volumes) are ignored. The
+    /// distance of the hit (`toi`) is measured from the lowest
+    /// point of the colliders of the body, hits that are further than `max_distance` from it are ignored.
+    pub(crate) fn cast_ray_below_body(
+        &self,
+        body: RigidBodyHandle,
+        max_distance: f32,
+    ) -> Option<Intersection> {
+        let native_body = self.bodies.set.get(body)?;
+
+        let mut min = Vector3::repeat(f32::MAX);
+        let mut max = Vector3::repeat(-f32::MAX);
+        for collider in native_body
+            .colliders()
+            .iter()
+            .filter_map(|collider| self.colliders.set.get(*collider))
+        {
+            let aabb = collider.compute_aabb();
+            min = min.inf(&aabb.mins.coords);
+            max = max.sup(&aabb.maxs.coords);
+        }
+        if min.y > max.y {
+            // The body has no colliders.
+            return None;
+        }
+
+        // Start the ray in the middle of the body, so it won't start inside the ground if the body
+        // penetrates it a bit.
+        let center = (min + max).scale(0.5);
+        let half_height = center.y - min.y;
+
+        self.cast_ray_closest_solid(
+            Point3::from(center),
+            -Vector3::y(),
+            half_height + max_distance,
+            Some(body),
+        )
+        .map(|mut intersection| {
+            intersection.toi = (intersection.toi - half_height).max(0.0);
+            intersection
+        })
+    }
 }
 
 impl Default for PhysicsWorld {
@@ -1684,3 +2091,88 @@ impl Debug for PhysicsWorld {
         write!(f, "PhysicsWorld")
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::{Isometry3, Vector2, Vector3},
+        scene::{
+            base::BaseBuilder,
+            collider::{ColliderBuilder, ColliderShape},
+            graph::{fixed_step::FixedTimeStep, Graph},
+            rigidbody::RigidBodyBuilder,
+        },
+    };
+
+    fn simulate(interpolate: bool) -> Isometry3<f32> {
+        let mut graph = Graph::new();
+        graph.physics_time_step = FixedTimeStep {
+            interpolate,
+            ..FixedTimeStep::new(60.0)
+        };
+
+        let collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::ball(0.5))
+            .build(&mut graph);
+        let body = RigidBodyBuilder::new(BaseBuilder::new().with_children(&[collider]))
+            .with_lin_vel(Vector3::new(2.0, 0.0, 0.0))
+            .with_ang_vel(Vector3::new(0.0, 3.0, 0.0))
+            .build(&mut graph);
+
+        // Frame rate is not a multiple of the physics rate, so almost every frame is interpolated.
+        for _ in 0..100 {
+            graph.update(Vector2::new(800.0, 600.0), 1.0 / 144.0, Default::default());
+        }
+
+        let native = graph[body].as_rigid_body().native.get();
+        *graph.physics.bodies.set.get(native).unwrap().position()
+    }
+
+    #[test]
+    fn test_interpolation_does_not_affect_simulation() {
+        let interpolated = simulate(true);
+        let reference = simulate(false);
+
+        assert!(reference.translation.vector.x > 1.0);
+        assert!(
+            (interpolated.translation.vector - reference.translation.vector).norm() < 1.0e-4,
+            "{:?} != {:?}",
+            interpolated,
+            reference
+        );
+        assert!(interpolated.rotation.angle_to(&reference.rotation) < 1.0e-4);
+    }
+
+    #[test]
+    fn test_max_linear_speed_is_applied_after_each_step() {
+        let mut graph = Graph::new();
+        graph.physics_time_step = FixedTimeStep::new(60.0);
+
+        let collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::ball(0.5))
+            .build(&mut graph);
+        let body = RigidBodyBuilder::new(BaseBuilder::new().with_children(&[collider]))
+            .with_gravity_scale(0.0)
+            .with_max_linear_speed(20.0)
+            .build(&mut graph);
+
+        // The collider is built before its body, so the first update creates the native body and the
+        // second one creates the native collider, that gives the body its mass.
+        graph.update(Vector2::new(800.0, 600.0), 0.1, Default::default());
+        graph.update(Vector2::new(800.0, 600.0), 0.1, Default::default());
+
+        graph[body]
+            .as_rigid_body_mut()
+            .apply_impulse(Vector3::new(10000.0, 0.0, 0.0));
+
+        // The frame is split in 6 physics steps, the body must not move faster than the limit on
+        // any of them.
+        graph.update(Vector2::new(800.0, 600.0), 0.1, Default::default());
+
+        let native = graph[body].as_rigid_body().native.get();
+        let native = graph.physics.bodies.set.get(native).unwrap();
+        assert!((native.linvel().norm() - 20.0).abs() < 1.0e-3);
+        assert!(native.translation().x <= 20.0 * 0.1 + 1.0e-3);
+        assert!((graph[body].as_rigid_body().lin_vel().norm() - 20.0).abs() < 1.0e-3);
+    }
+}
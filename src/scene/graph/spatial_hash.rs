@@ -0,0 +1,217 @@
+//! Spatial hash over world positions of scene nodes. It allows to quickly find nodes that are located
+//! near some point, which is useful for proximity-based logic (flocking, crowds, AI perception, etc.).
+//!
+//! See [`SpatialHash`] docs for more info.
+
+use crate::{
+    core::{algebra::Vector3, pool::Handle},
+    scene::{graph::NodePool, node::Node},
+};
+use fxhash::FxHashMap;
+
+/// Spatial hash splits the space in a uniform grid of cubic cells and stores handles of the nodes in the
+/// cells that contain their world positions. Neighbor query then checks only the cells that intersect the
+/// query sphere, instead of checking every node of the graph.
+///
+/// The hash is rebuilt lazily - [`super::Graph::update`] marks it as outdated, and the first query after
+/// that rebuilds it using the current global positions of the nodes. This means that the hash does not
+/// cost anything if nobody queries it. Keep in mind that the positions are taken at the moment of the
+/// rebuild, so the nodes that were moved after it will be found at their old positions until the next frame.
+#[derive(Debug, Clone)]
+pub struct SpatialHash {
+    cell_size: f32,
+    cells: FxHashMap<[i32; 3], Vec<(Handle<Node>, Vector3<f32>)>>,
+    // Min and max occupied cells, used to limit the amount of cells checked by huge queries.
+    bounds: Option<([i32; 3], [i32; 3])>,
+    need_rebuild: bool,
+}
+
+impl Default for SpatialHash {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CELL_SIZE)
+    }
+}
+
+impl SpatialHash {
+    /// Default size of a cell (in meters).
+    pub const DEFAULT_CELL_SIZE: f32 = 4.0;
+
+    /// Creates new empty spatial hash with the given size of a cell. Size of a cell should be roughly the
+    /// same as typical query radius for best performance.
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size: cell_size.max(f32::EPSILON),
+            cells: Default::default(),
+            bounds: None,
+            need_rebuild: true,
+        }
+    }
+
+    /// Sets new size of a cell. The hash will be rebuilt on next query.
+    pub fn set_cell_size(&mut self, cell_size: f32) {
+        self.cell_size = cell_size.max(f32::EPSILON);
+        self.need_rebuild = true;
+    }
+
+    /// Returns current size of a cell.
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// Marks the hash as outdated, it will be rebuilt on next query.
+    pub fn invalidate(&mut self) {
+        self.need_rebuild = true;
+    }
+
+    /// Returns `true` if the hash must be rebuilt before it can be queried.
+    pub fn need_rebuild(&self) -> bool {
+        self.need_rebuild
+    }
+
+    /// Removes every node from the hash.
+    pub fn clear(&mut self) {
+        // Keep allocated memory of the cells, it will be most likely re-used on next rebuild.
+        for entries in self.cells.values_mut() {
+            entries.clear();
+        }
+        self.bounds = None;
+    }
+
+    fn cell_of(&self, position: Vector3<f32>) -> [i32; 3] {
+        [
+            (position.x / self.cell_size).floor() as i32,
+            (position.y / self.cell_size).floor() as i32,
+            (position.z / self.cell_size).floor() as i32,
+        ]
+    }
+
+    /// Puts a node with the given position in the hash.
+    pub fn insert(&mut self, handle: Handle<Node>, position: Vector3<f32>) {
+        let cell = self.cell_of(position);
+
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (
+                [
+                    min[0].min(cell[0]),
+                    min[1].min(cell[1]),
+                    min[2].min(cell[2]),
+                ],
+                [
+                    max[0].max(cell[0]),
+                    max[1].max(cell[1]),
+                    max[2].max(cell[2]),
+                ],
+            ),
+            None => (cell, cell),
+        });
+
+        self.cells
+            .entry(cell)
+            .or_insert_with(Default::default)
+            .push((handle, position));
+    }
+
+    pub(crate) fn rebuild(&mut self, pool: &NodePool, root: Handle<Node>) {
+        self.clear();
+        for (handle, node) in pool.pair_iter() {
+            if handle != root {
+                self.insert(handle, node.global_position());
+            }
+        }
+        self.need_rebuild = false;
+    }
+
+    /// Returns an iterator over handles of every node whose position is within the given radius
+    /// around the center.
+    pub fn query(
+        &self,
+        center: Vector3<f32>,
+        radius: f32,
+    ) -> impl Iterator<Item = Handle<Node>> + '_ {
+        let radius = radius.max(0.0);
+        let sqr_radius = radius * radius;
+
+        let (min, max) = match self.bounds {
+            Some((bounds_min, bounds_max)) => {
+                let min = self.cell_of(center - Vector3::repeat(radius));
+                let max = self.cell_of(center + Vector3::repeat(radius));
+                (
+                    [
+                        min[0].max(bounds_min[0]),
+                        min[1].max(bounds_min[1]),
+                        min[2].max(bounds_min[2]),
+                    ],
+                    [
+                        max[0].min(bounds_max[0]),
+                        max[1].min(bounds_max[1]),
+                        max[2].min(bounds_max[2]),
+                    ],
+                )
+            }
+            // Empty range.
+            None => ([0; 3], [-1; 3]),
+        };
+
+        (min[2]..=max[2])
+            .flat_map(move |z| {
+                (min[1]..=max[1]).flat_map(move |y| (min[0]..=max[0]).map(move |x| [x, y, z]))
+            })
+            .filter_map(move |cell| self.cells.get(&cell))
+            .flat_map(|entries| entries.iter())
+            .filter(move |(_, position)| (position - center).norm_squared() <= sqr_radius)
+            .map(|(handle, _)| *handle)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector3,
+        scene::{
+            base::BaseBuilder, graph::Graph, pivot::PivotBuilder, transform::TransformBuilder,
+        },
+    };
+    use fxhash::FxHashSet;
+
+    #[test]
+    fn test_query_returns_only_nodes_within_radius() {
+        let mut graph = Graph::new();
+
+        let make = |graph: &mut Graph, x: f32, y: f32, z: f32| {
+            PivotBuilder::new(
+                BaseBuilder::new().with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(x, y, z))
+                        .build(),
+                ),
+            )
+            .build(graph)
+        };
+
+        // Cluster around the origin.
+        let center = make(&mut graph, 0.0, 0.0, 0.0);
+        let near = [
+            make(&mut graph, 0.5, 0.0, 0.0),
+            make(&mut graph, -0.5, 0.5, 0.0),
+            make(&mut graph, 0.0, -0.5, 0.9),
+        ];
+        // Nodes in the neighbor cells, but outside of the radius.
+        make(&mut graph, 1.5, 0.0, 0.0);
+        make(&mut graph, 0.9, 0.9, 0.9);
+        // Far cluster.
+        make(&mut graph, 50.0, 50.0, 50.0);
+        make(&mut graph, 50.5, 50.0, 50.0);
+
+        graph.update_hierarchical_data();
+        graph.spatial_hash.set_cell_size(1.0);
+
+        let found = graph
+            .spatial_hash()
+            .query(Vector3::default(), 1.0)
+            .collect::<FxHashSet<_>>();
+
+        let mut expected = near.iter().cloned().collect::<FxHashSet<_>>();
+        expected.insert(center);
+        assert_eq!(found, expected);
+    }
+}
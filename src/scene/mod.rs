@@ -7,11 +7,14 @@
 pub mod accel;
 pub mod animation;
 pub mod base;
+pub mod blackboard;
 pub mod camera;
 pub mod collider;
 pub mod debug;
 pub mod decal;
 pub mod dim2;
+pub mod environment;
+pub mod fog;
 pub mod graph;
 pub mod joint;
 pub mod light;
@@ -21,11 +24,16 @@ pub mod navmesh;
 pub mod node;
 pub mod particle_system;
 pub mod pivot;
+pub mod post_processing;
 pub mod rigidbody;
+pub mod rng;
 pub mod sound;
+pub mod spline;
 pub mod sprite;
 pub mod terrain;
 pub mod transform;
+pub mod trigger;
+pub mod water;
 
 use crate::{
     asset::{self, manager::ResourceManager, untyped::UntypedResource},
@@ -44,9 +52,12 @@ use crate::{
     renderer::framework::state::PolygonFillMode,
     resource::texture::TextureResource,
     scene::{
-        base::BaseBuilder,
+        base::{BaseBuilder, Mobility},
+        blackboard::SceneBlackboard,
         camera::Camera,
         debug::SceneDrawingContext,
+        environment::EnvironmentLighting,
+        fog::FogSettings,
         graph::{map::NodeHandleMap, Graph, GraphPerformanceStatistics, GraphUpdateSwitches},
         mesh::{
             buffer::{
@@ -57,14 +68,19 @@ use crate::{
         },
         navmesh::NavigationalMeshBuilder,
         node::Node,
+        post_processing::PostProcessingSettings,
+        rng::SceneRng,
         sound::SoundEngine,
     },
-    utils::{lightmap::Lightmap, navmesh::Navmesh},
+    utils::{
+        lightmap::{Lightmap, LightmapBakingSettings, LightmapGenerationError, LightmapResult},
+        navmesh::Navmesh,
+    },
 };
 use fxhash::{FxHashMap, FxHashSet};
 use std::path::PathBuf;
 use std::{
-    fmt::{Display, Formatter},
+    fmt::{Debug, Display, Formatter},
     ops::{Index, IndexMut},
     path::Path,
     sync::Arc,
@@ -190,6 +206,28 @@ pub struct Scene {
     /// Defines how polygons of the scene will be rasterized. By default it set to [`PolygonFillMode::Fill`],
     /// [`PolygonFillMode::Line`] could be used to render the scene in wireframe mode.
     pub polygon_rasterization_mode: PolygonFillMode,
+
+    /// Typed storage for arbitrary data, that is shared between scripts of the scene. It is saved together
+    /// with the scene. See [`SceneBlackboard`] docs for more info.
+    #[reflect(hidden)]
+    pub blackboard: SceneBlackboard,
+
+    /// Post-processing effects of the scene, see [`PostProcessingSettings`] docs for more info.
+    pub post_processing: PostProcessingSettings,
+
+    /// Atmospheric fog of the scene, see [`FogSettings`] docs for more info.
+    pub fog: FogSettings,
+
+    /// Image-based lighting of the scene, see [`EnvironmentLighting`] docs for more info.
+    pub environment: EnvironmentLighting,
+
+    /// Seeded pseudo-random numbers generator for gameplay randomness, see [`SceneRng`] docs for more info.
+    #[reflect(hidden)]
+    pub rng: SceneRng,
+
+    /// Scale of the time of the scene, see [`Self::set_time_scale`] docs for more info.
+    #[reflect(min_value = 0.0, step = 0.1, setter = "set_time_scale")]
+    time_scale: f32,
 }
 
 impl Default for Scene {
@@ -203,6 +241,12 @@ impl Default for Scene {
             ambient_lighting_color: Color::opaque(100, 100, 100),
             enabled: true,
             polygon_rasterization_mode: Default::default(),
+            blackboard: Default::default(),
+            post_processing: Default::default(),
+            fog: Default::default(),
+            environment: Default::default(),
+            rng: Default::default(),
+            time_scale: 1.0,
         }
     }
 }
@@ -364,6 +408,12 @@ impl Scene {
             ambient_lighting_color: Color::opaque(100, 100, 100),
             enabled: true,
             polygon_rasterization_mode: Default::default(),
+            blackboard: Default::default(),
+            post_processing: Default::default(),
+            fog: Default::default(),
+            environment: Default::default(),
+            rng: Default::default(),
+            time_scale: 1.0,
         }
     }
 
@@ -509,11 +559,60 @@ impl Scene {
         Ok(std::mem::replace(&mut self.lightmap, Some(lightmap)))
     }
 
+    /// Returns current lightmap of the scene (if any).
+    pub fn lightmap(&self) -> Option<&Lightmap> {
+        self.lightmap.as_ref()
+    }
+
+    /// Bakes lightmaps for the geometry of the scene and applies them to the scene. This method is
+    /// blocking and it may take a lot of time to finish, use [`LightmapBakingSettings::progress_indicator`]
+    /// to track progress and [`LightmapBakingSettings::cancellation_token`] to stop baking. Current
+    /// lightmap of the scene is left untouched if baking has failed or was cancelled.
+    pub fn bake_lightmaps(&mut self, settings: LightmapBakingSettings) -> LightmapResult {
+        let LightmapBakingSettings {
+            texels_per_unit,
+            static_geometry_only,
+            cancellation_token,
+            progress_indicator,
+        } = settings;
+
+        let lightmap = Lightmap::new(
+            self,
+            texels_per_unit,
+            |_, node| {
+                !static_geometry_only
+                    || node.cast::<Mesh>().is_none()
+                    || node.mobility() != Mobility::Dynamic
+            },
+            cancellation_token,
+            progress_indicator,
+        )?;
+
+        self.set_lightmap(lightmap)
+            .map_err(LightmapGenerationError::ApplyFailed)
+    }
+
+    /// Sets new scale of the time of the scene and returns the old one. The scale is applied to the
+    /// time step that is used to update the scene (including physics and animations) and its scripts,
+    /// so it could be used for slow-motion effects or to pause the scene: 0.0 pauses the scene, 0.5
+    /// slows it down twice, 2.0 speeds it up twice. Negative values are clamped to zero.
+    pub fn set_time_scale(&mut self, time_scale: f32) -> f32 {
+        std::mem::replace(&mut self.time_scale, time_scale.max(0.0))
+    }
+
+    /// Returns current scale of the time of the scene. See [`Self::set_time_scale`] for more info.
+    pub fn time_scale(&self) -> f32 {
+        self.time_scale
+    }
+
     /// Performs single update tick with given delta time from last frame. Internally
-    /// it updates physics, animations, and each graph node. In most cases there is
-    /// no need to call it directly, engine automatically updates all available scenes.
+    /// it updates physics, animations, and each graph node. The time step is scaled by
+    /// [`Self::time_scale`]. In most cases there is no need to call it directly, engine
+    /// automatically updates all available scenes.
     pub fn update(&mut self, frame_size: Vector2<f32>, dt: f32, switches: GraphUpdateSwitches) {
-        self.graph.update(frame_size, dt, switches);
+        self.graph
+            .update(frame_size, dt * self.time_scale, switches);
+        self.environment.update();
         self.performance_statistics.graph = self.graph.performance_statistics.clone();
     }
 
@@ -547,6 +646,12 @@ impl Scene {
                 ambient_lighting_color: self.ambient_lighting_color,
                 enabled: self.enabled,
                 polygon_rasterization_mode: self.polygon_rasterization_mode,
+                blackboard: self.blackboard.clone(),
+                post_processing: self.post_processing.clone(),
+                fog: self.fog.clone(),
+                environment: self.environment.clone(),
+                rng: self.rng.clone(),
+                time_scale: self.time_scale,
             },
             old_new_map,
         )
@@ -563,6 +668,12 @@ impl Scene {
         let _ = self
             .polygon_rasterization_mode
             .visit("PolygonRasterizationMode", &mut region);
+        let _ = self.blackboard.visit("Blackboard", &mut region);
+        let _ = self.post_processing.visit("PostProcessing", &mut region);
+        let _ = self.fog.visit("Fog", &mut region);
+        let _ = self.environment.visit("Environment", &mut region);
+        let _ = self.rng.visit("Rng", &mut region);
+        let _ = self.time_scale.visit("TimeScale", &mut region);
 
         // Backward compatibility.\
         let mut navmeshes = NavMeshContainer::default();
@@ -587,6 +698,61 @@ impl Scene {
 
         self.visit(region_name, visitor)
     }
+
+    /// Makes an in-memory snapshot of the scene. The snapshot could be used later on to restore
+    /// exact state of the scene (including the state of scripts) without a round-trip to disk,
+    /// for example to discard the changes made in the scene while a game was simulated in the
+    /// editor. See [`SceneSnapshot::restore`] for more info.
+    pub fn snapshot(&mut self) -> Result<SceneSnapshot, VisitError> {
+        let mut visitor = Visitor::new();
+        self.save("Scene", &mut visitor)?;
+        Ok(SceneSnapshot {
+            data: visitor.save_binary_to_vec()?,
+        })
+    }
+}
+
+/// Serialized state of a scene, that is stored in memory. See [`Scene::snapshot`].
+#[derive(Clone, PartialEq, Eq)]
+pub struct SceneSnapshot {
+    data: Vec<u8>,
+}
+
+impl Debug for SceneSnapshot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SceneSnapshot ({} bytes)", self.data.len())
+    }
+}
+
+impl SceneSnapshot {
+    /// Creates a new scene from the snapshot. Every node handle that was valid in the original
+    /// scene at the moment of the snapshot is valid in the restored scene and points to the same
+    /// node. Scripts are restored with their fields, so [`crate::engine::SerializationContext`] must
+    /// contain constructors for every script used in the scene.
+    ///
+    /// # Important notes
+    ///
+    /// The method does not wait until the resources used by the scene are loaded, it is assumed that
+    /// they're loaded already (which is always true for snapshots of the scenes that were loaded
+    /// normally). This makes restoration fast enough to be done interactively. The render target of
+    /// the scene is not saved in the snapshot, and it should be set again if needed.
+    pub fn restore(
+        &self,
+        serialization_context: Arc<SerializationContext>,
+        resource_manager: ResourceManager,
+    ) -> Result<Scene, VisitError> {
+        let mut visitor = Visitor::load_from_memory(self.data.clone())?;
+        let mut scene = SceneLoader::load(
+            "Scene",
+            serialization_context,
+            resource_manager,
+            &mut visitor,
+            None,
+        )?
+        .scene;
+        scene.resolve();
+        Ok(scene)
+    }
 }
 
 /// Container for scenes in the engine.
@@ -699,3 +865,187 @@ impl IndexMut<Handle<Scene>> for SceneContainer {
         &mut self.pool[index]
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        asset::manager::ResourceManager,
+        core::{
+            algebra::Vector3,
+            reflect::prelude::*,
+            uuid::{uuid, Uuid},
+            visitor::prelude::*,
+            TypeUuidProvider,
+        },
+        engine::SerializationContext,
+        impl_component_provider,
+        scene::{
+            base::{Base, BaseBuilder, NodeId},
+            pivot::PivotBuilder,
+            transform::TransformBuilder,
+            Scene,
+        },
+        script::{Script, ScriptTrait},
+    };
+    use std::sync::Arc;
+
+    #[derive(Reflect, Visit, Default, Debug, Clone)]
+    struct Door {
+        open_angle: f32,
+        locked: bool,
+    }
+
+    impl_component_provider!(Door);
+
+    impl TypeUuidProvider for Door {
+        fn type_uuid() -> Uuid {
+            uuid!("5c0e9a7d-2b4f-4e18-a3d6-81f2c4b7e059")
+        }
+    }
+
+    impl ScriptTrait for Door {
+        fn id(&self) -> Uuid {
+            Self::type_uuid()
+        }
+    }
+
+    #[test]
+    fn test_snapshot_restores_node_handles() {
+        let mut scene = Scene::new();
+        let position = Vector3::new(1.0, 2.0, 3.0);
+        let moved = PivotBuilder::new(
+            BaseBuilder::new().with_name("Moved").with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(position)
+                    .build(),
+            ),
+        )
+        .build(&mut scene.graph);
+        let removed =
+            PivotBuilder::new(BaseBuilder::new().with_name("Removed")).build(&mut scene.graph);
+        let node_count = scene.graph.node_count();
+
+        let snapshot = scene.snapshot().unwrap();
+
+        scene.graph[moved]
+            .local_transform_mut()
+            .set_position(Vector3::new(10.0, 0.0, 0.0));
+        scene.graph.remove_node(removed);
+        PivotBuilder::new(BaseBuilder::new().with_name("Spawned")).build(&mut scene.graph);
+
+        let scene = snapshot
+            .restore(
+                Arc::new(SerializationContext::new()),
+                ResourceManager::new(),
+            )
+            .unwrap();
+
+        assert_eq!(scene.graph.node_count(), node_count);
+        assert_eq!(scene.graph[moved].name(), "Moved");
+        assert_eq!(**scene.graph[moved].local_transform().position(), position);
+        assert_eq!(scene.graph[removed].name(), "Removed");
+        assert!(scene
+            .graph
+            .linear_iter()
+            .all(|node| node.name() != "Spawned"));
+    }
+
+    #[test]
+    fn test_node_id_survives_save_load() {
+        let mut scene = Scene::new();
+        let child =
+            PivotBuilder::new(BaseBuilder::new().with_name("Child")).build(&mut scene.graph);
+        let parent = PivotBuilder::new(
+            BaseBuilder::new()
+                .with_name("Parent")
+                .with_children(&[child]),
+        )
+        .build(&mut scene.graph);
+        let id = scene.graph[child].node_id();
+        assert_ne!(id, scene.graph[parent].node_id());
+
+        // Copies are new nodes, so they must have their own ids.
+        let (parent_copy, map) = scene.graph.copy_node_inplace(parent, &mut |_, _| true);
+        assert_ne!(
+            scene.graph[parent_copy].node_id(),
+            scene.graph[parent].node_id()
+        );
+        assert_ne!(scene.graph[map.map[&child]].node_id(), id);
+        assert_eq!(scene.graph.find_by_id(id).unwrap().0, child);
+
+        // While the copy of the entire graph is the same graph.
+        let root = scene.graph.get_root();
+        let (graph_copy, map) = scene.graph.clone(root, &mut |_, _| true);
+        assert_eq!(graph_copy.find_by_id(id).unwrap().0, map.map[&child]);
+
+        let scene = scene
+            .snapshot()
+            .unwrap()
+            .restore(
+                Arc::new(SerializationContext::new()),
+                ResourceManager::new(),
+            )
+            .unwrap();
+
+        let (handle, node) = scene.graph.find_by_id(id).unwrap();
+        assert_eq!(handle, child);
+        assert_eq!(node.name(), "Child");
+        assert!(scene.graph.find_by_id(NodeId::new()).is_none());
+    }
+
+    #[test]
+    fn test_unresolved_script_is_preserved() {
+        let mut scene = Scene::new();
+        let door = PivotBuilder::new(BaseBuilder::new().with_script(Script::new(Door {
+            open_angle: 90.0,
+            locked: true,
+        })))
+        .build(&mut scene.graph);
+
+        // The script type is not registered, so the node is loaded without it.
+        let empty_context = Arc::new(SerializationContext::new());
+        let mut scene = scene
+            .snapshot()
+            .unwrap()
+            .restore(empty_context.clone(), ResourceManager::new())
+            .unwrap();
+        assert!(scene.graph[door].script().is_none());
+        let unresolved = scene.graph[door].unresolved_script().unwrap();
+        assert_eq!(unresolved.type_uuid(), Door::type_uuid());
+        assert!(!unresolved.data().is_empty());
+
+        // The unresolved script is visible via reflection, but it cannot be edited.
+        let base: &Base = &scene.graph[door];
+        base.get_resolve_path::<Uuid>("unresolved_script.Some@0.type_uuid", &mut |result| {
+            assert_eq!(*result.unwrap(), Door::type_uuid())
+        });
+        base.fields_info(&mut |fields| {
+            let field = fields
+                .iter()
+                .find(|field| field.name == "unresolved_script")
+                .unwrap();
+            assert!(field.read_only);
+        });
+
+        // But the data is preserved when the scene is saved again.
+        let snapshot = scene.snapshot().unwrap();
+        let context = Arc::new(SerializationContext::new());
+        context.script_constructors.add::<Door>("Door");
+        let restored = snapshot.restore(context, ResourceManager::new()).unwrap();
+        let script = restored.graph[door].try_get_script::<Door>().unwrap();
+        assert_eq!(script.open_angle, 90.0);
+        assert!(script.locked);
+
+        // And the script could be reattached once its type is registered.
+        let mut scene = snapshot
+            .restore(empty_context.clone(), ResourceManager::new())
+            .unwrap();
+        assert_eq!(scene.graph.resolve_scripts(&empty_context), 0);
+        empty_context.script_constructors.add::<Door>("Door");
+        assert_eq!(scene.graph.resolve_scripts(&empty_context), 1);
+        assert!(scene.graph[door].unresolved_script().is_none());
+        let script = scene.graph[door].try_get_script::<Door>().unwrap();
+        assert_eq!(script.open_angle, 90.0);
+        assert!(script.locked);
+    }
+}
@@ -15,8 +15,11 @@ use crate::{
         particle_system::ParticleSystem,
         pivot::Pivot,
         sound::{listener::Listener, Sound},
+        spline::Spline,
         sprite::Sprite,
         terrain::Terrain,
+        trigger::TriggerVolume,
+        water::Water,
     },
 };
 use fxhash::FxHashMap;
@@ -38,6 +41,7 @@ impl NodeConstructorContainer {
         container.add::<dim2::collider::Collider>();
         container.add::<dim2::joint::Joint>();
         container.add::<Rectangle>();
+        container.add::<dim2::flipbook::Flipbook>();
         container.add::<dim2::rigidbody::RigidBody>();
         container.add::<DirectionalLight>();
         container.add::<PointLight>();
@@ -57,6 +61,9 @@ impl NodeConstructorContainer {
         container.add::<AnimationPlayer>();
         container.add::<AnimationBlendingStateMachine>();
         container.add::<NavigationalMesh>();
+        container.add::<TriggerVolume>();
+        container.add::<Spline>();
+        container.add::<Water>();
 
         container
     }
@@ -402,6 +402,30 @@ impl Node {
     ///     node.cast_mut::<Mesh>().expect("Expected to be an instance of Mesh")
     /// }
     /// ```
+    ///
+    /// # Borrowing
+    ///
+    /// The returned reference borrows the entire node, including its [`Base`] part (transform, name,
+    /// etc.), so it can never alias with a reference obtained via `node.local_transform_mut()` - the
+    /// borrow checker rejects such code at compile time and there are no runtime borrow checks that
+    /// could panic. Every node type dereferences to [`Base`], so there's no need to keep two borrows at
+    /// all - use the typed reference to access both the typed data and the transform:
+    ///
+    /// ```rust
+    /// # use fyrox::core::algebra::Vector3;
+    /// # use fyrox::scene::node::Node;
+    /// # use fyrox::scene::rigidbody::RigidBody;
+    ///
+    /// fn push_and_move(node: &mut Node) {
+    ///     if let Some(rigid_body) = node.cast_mut::<RigidBody>() {
+    ///         rigid_body.set_lin_vel(Vector3::new(0.0, 1.0, 0.0));
+    ///         // Transform is accessed through the same (typed) reference.
+    ///         rigid_body
+    ///             .local_transform_mut()
+    ///             .set_position(Vector3::new(1.0, 2.0, 3.0));
+    ///     }
+    /// }
+    /// ```
     #[inline]
     pub fn cast_mut<T: NodeTrait>(&mut self) -> Option<&mut T> {
         self.0.as_any_ref_mut().downcast_mut::<T>()
@@ -583,6 +607,7 @@ mod test {
                 MeshBuilder,
             },
             pivot::PivotBuilder,
+            rigidbody::{RigidBody, RigidBodyBuilder},
             transform::TransformBuilder,
             Scene,
         },
@@ -746,4 +771,32 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn test_typed_and_transform_access() {
+        let mut scene = Scene::new();
+        let handle = RigidBodyBuilder::new(BaseBuilder::new()).build(&mut scene.graph);
+
+        // The same thing that a script does in `on_update` with its own node.
+        let node = &mut scene.graph[handle];
+        let rigid_body = node.cast_mut::<RigidBody>().unwrap();
+        rigid_body.set_lin_vel(Vector3::new(0.0, 1.0, 0.0));
+        rigid_body
+            .local_transform_mut()
+            .set_position(Vector3::new(1.0, 2.0, 3.0));
+        rigid_body.set_ang_vel(Vector3::new(0.0, 0.0, 1.0));
+
+        // Untyped access first, then the typed one.
+        node.local_transform_mut()
+            .offset(Vector3::new(1.0, 0.0, 0.0));
+        let rigid_body = node.cast_mut::<RigidBody>().unwrap();
+        assert_eq!(rigid_body.lin_vel(), Vector3::new(0.0, 1.0, 0.0));
+        assert_eq!(rigid_body.ang_vel(), Vector3::new(0.0, 0.0, 1.0));
+
+        scene.graph.update_hierarchical_data();
+        assert_eq!(
+            scene.graph[handle].global_position(),
+            Vector3::new(2.0, 2.0, 3.0)
+        );
+    }
 }
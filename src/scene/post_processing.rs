@@ -0,0 +1,71 @@
+//! Per-scene post-processing stack. See [`PostProcessingSettings`] docs for more info.
+
+use crate::core::{reflect::prelude::*, visitor::prelude::*};
+
+/// Screen-space ambient occlusion settings.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct SsaoSettings {
+    /// Whether the effect is enabled or not.
+    pub enabled: bool,
+    /// Radius of sampling hemisphere, it defines how much ambient occlusion will be in the scene. If not
+    /// set, the radius from the quality settings of the renderer is used.
+    #[reflect(min_value = 0.0, step = 0.05)]
+    pub radius: Option<f32>,
+}
+
+impl Default for SsaoSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            radius: None,
+        }
+    }
+}
+
+/// Bloom (glow of bright pixels) settings. Emissive surfaces glow too if they're bright enough, emission of
+/// standard materials could be boosted by `emissionStrength` property of the materials.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct BloomSettings {
+    /// Whether the effect is enabled or not.
+    pub enabled: bool,
+    /// Min luminance of a pixel to make it glow.
+    #[reflect(min_value = 0.0, step = 0.05)]
+    pub threshold: f32,
+}
+
+impl Default for BloomSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            threshold: 1.0,
+        }
+    }
+}
+
+/// Tone mapping settings.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct ToneMappingSettings {
+    /// Whether the high dynamic range of the frame should be mapped to the low dynamic range of the
+    /// screen using exposure of the camera. If disabled, the colors are just clamped.
+    pub enabled: bool,
+}
+
+impl Default for ToneMappingSettings {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// A set of post-processing effects of a scene. Every effect could be toggled and configured separately,
+/// the settings are saved together with the scene. Effects are applied only if they're allowed by the
+/// [`crate::renderer::QualitySettings`] of the renderer, this way the global quality settings always
+/// have the final word - see [`crate::renderer::QualitySettings::with_post_processing`].
+#[derive(Clone, Debug, Default, PartialEq, Visit, Reflect)]
+pub struct PostProcessingSettings {
+    /// Screen-space ambient occlusion settings.
+    pub ssao: SsaoSettings,
+    /// Bloom settings.
+    pub bloom: BloomSettings,
+    /// Tone mapping settings.
+    pub tone_mapping: ToneMappingSettings,
+}
@@ -24,7 +24,10 @@ use crate::{
     scene::{
         base::{Base, BaseBuilder},
         collider::Collider,
-        graph::Graph,
+        graph::{
+            physics::{ContactInfo, PhysicsWorld},
+            Graph,
+        },
         node::{Node, NodeTrait, SyncContext, UpdateContext},
         Scene,
     },
@@ -38,6 +41,10 @@ use std::{
 };
 use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
 
+/// Default maximum slope (in radians) of a surface, that is still considered as ground by
+/// [`RigidBody::is_grounded`].
+pub const DEFAULT_MAX_GROUND_SLOPE: f32 = std::f32::consts::FRAC_PI_4;
+
 /// A set of possible types of rigid body.
 #[derive(
     Copy, Clone, Debug, Reflect, Visit, PartialEq, Eq, Hash, AsRefStr, EnumString, EnumVariantNames,
@@ -127,6 +134,13 @@ pub(crate) enum ApplyAction {
 ///
 /// Rigid body that does not move for some time will go asleep. This means that the body will not
 /// move unless it is woken up by some other moving body. This feature allows to save CPU resources.
+///
+/// # Speed limit
+///
+/// Linear speed of a dynamic rigid body could be limited using [`RigidBody::set_max_linear_speed`].
+/// The velocity is clamped right before and after each physics step, so huge forces or impulses (for
+/// example, the ones produced by movement code on tiny time steps) cannot make the body fly away. Zero
+/// limit (default) means that the speed is not limited.
 #[derive(Visit, Reflect)]
 pub struct RigidBody {
     base: Base,
@@ -162,6 +176,18 @@ pub struct RigidBody {
     #[reflect(setter = "lock_translation")]
     pub(crate) translation_locked: InheritableVariable<bool>,
 
+    #[visit(optional)]
+    #[reflect(setter = "lock_x_translation")]
+    pub(crate) x_translation_locked: InheritableVariable<bool>,
+
+    #[visit(optional)]
+    #[reflect(setter = "lock_y_translation")]
+    pub(crate) y_translation_locked: InheritableVariable<bool>,
+
+    #[visit(optional)]
+    #[reflect(setter = "lock_z_translation")]
+    pub(crate) z_translation_locked: InheritableVariable<bool>,
+
     #[reflect(setter = "enable_ccd")]
     pub(crate) ccd_enabled: InheritableVariable<bool>,
 
@@ -174,6 +200,11 @@ pub struct RigidBody {
     #[reflect(setter = "set_gravity_scale")]
     pub(crate) gravity_scale: InheritableVariable<f32>,
 
+    #[visit(optional)]
+    #[reflect(min_value = 0.0)]
+    #[reflect(setter = "set_max_linear_speed")]
+    pub(crate) max_linear_speed: InheritableVariable<f32>,
+
     #[visit(skip)]
     #[reflect(hidden)]
     pub(crate) sleeping: bool,
@@ -209,10 +240,14 @@ impl Default for RigidBody {
             y_rotation_locked: Default::default(),
             z_rotation_locked: Default::default(),
             translation_locked: Default::default(),
+            x_translation_locked: Default::default(),
+            y_translation_locked: Default::default(),
+            z_translation_locked: Default::default(),
             ccd_enabled: Default::default(),
             can_sleep: InheritableVariable::new_modified(true),
             dominance: Default::default(),
             gravity_scale: InheritableVariable::new_modified(1.0),
+            max_linear_speed: Default::default(),
             native: Cell::new(RigidBodyHandle::invalid()),
             actions: Default::default(),
             reset_forces: Default::default(),
@@ -249,10 +284,14 @@ impl Clone for RigidBody {
             y_rotation_locked: self.y_rotation_locked.clone(),
             z_rotation_locked: self.z_rotation_locked.clone(),
             translation_locked: self.translation_locked.clone(),
+            x_translation_locked: self.x_translation_locked.clone(),
+            y_translation_locked: self.y_translation_locked.clone(),
+            z_translation_locked: self.z_translation_locked.clone(),
             ccd_enabled: self.ccd_enabled.clone(),
             can_sleep: self.can_sleep.clone(),
             dominance: self.dominance.clone(),
             gravity_scale: self.gravity_scale.clone(),
+            max_linear_speed: self.max_linear_speed.clone(),
             // Do not copy. The copy will have its own native representation.
             native: Cell::new(RigidBodyHandle::invalid()),
             actions: Default::default(),
@@ -360,6 +399,15 @@ impl RigidBody {
         self.z_rotation_locked.set_value_and_mark_modified(locked);
     }
 
+    /// Locks or unlocks rotations around each axis (in world coordinates) separately. For example, locking
+    /// rotations around X and Z axes of a capsule-shaped character prevents it from tipping over, while still
+    /// allowing it to turn around Y axis.
+    pub fn set_rotation_locked(&mut self, x: bool, y: bool, z: bool) {
+        self.x_rotation_locked.set_value_and_mark_modified(x);
+        self.y_rotation_locked.set_value_and_mark_modified(y);
+        self.z_rotation_locked.set_value_and_mark_modified(z);
+    }
+
     /// Locks translation in world coordinates.
     pub fn lock_translation(&mut self, state: bool) -> bool {
         self.translation_locked.set_value_and_mark_modified(state)
@@ -370,6 +418,55 @@ impl RigidBody {
         *self.translation_locked
     }
 
+    /// Locks translation along X axis in world coordinates.
+    pub fn lock_x_translation(&mut self, state: bool) -> bool {
+        self.x_translation_locked.set_value_and_mark_modified(state)
+    }
+
+    /// Returns true if translation along X axis is locked, false - otherwise.
+    pub fn is_x_translation_locked(&self) -> bool {
+        *self.x_translation_locked
+    }
+
+    /// Locks translation along Y axis in world coordinates.
+    pub fn lock_y_translation(&mut self, state: bool) -> bool {
+        self.y_translation_locked.set_value_and_mark_modified(state)
+    }
+
+    /// Returns true if translation along Y axis is locked, false - otherwise.
+    pub fn is_y_translation_locked(&self) -> bool {
+        *self.y_translation_locked
+    }
+
+    /// Locks translation along Z axis in world coordinates.
+    pub fn lock_z_translation(&mut self, state: bool) -> bool {
+        self.z_translation_locked.set_value_and_mark_modified(state)
+    }
+
+    /// Returns true if translation along Z axis is locked, false - otherwise.
+    pub fn is_z_translation_locked(&self) -> bool {
+        *self.z_translation_locked
+    }
+
+    /// Locks or unlocks translation along each axis (in world coordinates) separately. For example, locking
+    /// translation along Z axis restricts the body to XY plane.
+    pub fn set_translation_locked(&mut self, x: bool, y: bool, z: bool) {
+        self.x_translation_locked.set_value_and_mark_modified(x);
+        self.y_translation_locked.set_value_and_mark_modified(y);
+        self.z_translation_locked.set_value_and_mark_modified(z);
+    }
+
+    /// Returns an actual lock state of translation along each axis, taking [`Self::is_translation_locked`]
+    /// into account.
+    pub(crate) fn translation_locks(&self) -> [bool; 3] {
+        let all = *self.translation_locked;
+        [
+            all || *self.x_translation_locked,
+            all || *self.y_translation_locked,
+            all || *self.z_translation_locked,
+        ]
+    }
+
     /// Sets new body type. See [`RigidBodyType`] for more info.
     pub fn set_body_type(&mut self, body_type: RigidBodyType) -> RigidBodyType {
         self.body_type.set_value_and_mark_modified(body_type)
@@ -407,6 +504,18 @@ impl RigidBody {
         *self.gravity_scale
     }
 
+    /// Sets max linear speed of the rigid body. Linear velocity of the body is clamped to this value
+    /// before and after each physics step. Zero can be used to disable the limit. Returns previous value.
+    pub fn set_max_linear_speed(&mut self, max_speed: f32) -> f32 {
+        self.max_linear_speed
+            .set_value_and_mark_modified(max_speed.max(0.0))
+    }
+
+    /// Returns current max linear speed of the rigid body. Zero means that the speed is not limited.
+    pub fn max_linear_speed(&self) -> f32 {
+        *self.max_linear_speed
+    }
+
     /// Sets dominance group of the rigid body. A rigid body with higher dominance group will not
     /// be affected by an object with lower dominance group (it will behave like it has an infinite
     /// mass). This is very importance feature for character physics in games, you can set highest
@@ -483,6 +592,54 @@ impl RigidBody {
         self.actions.get_mut().push_back(ApplyAction::WakeUp)
     }
 
+    /// Returns an iterator that yields contact points of every collider of the body with other colliders,
+    /// that were found on the last physics step. Contact normals point towards the body, so it is easy to
+    /// check whether a character stands on the ground:
+    ///
+    /// ```rust
+    /// # use fyrox::scene::{graph::Graph, rigidbody::RigidBody};
+    /// fn is_on_ground(body: &RigidBody, graph: &Graph) -> bool {
+    ///     body.contacts(&graph.physics)
+    ///         .any(|contact| contact.normal.y > 0.7)
+    /// }
+    /// ```
+    pub fn contacts<'a>(
+        &self,
+        physics: &'a PhysicsWorld,
+    ) -> impl Iterator<Item = ContactInfo> + 'a {
+        physics.body_contacts(self.native.get()).into_iter()
+    }
+
+    /// Checks whether there is a ground right below the body, within `max_distance` from the lowest point of its
+    /// colliders. The ground must not be steeper than [`DEFAULT_MAX_GROUND_SLOPE`], use
+    /// [`Self::is_grounded_with_max_slope`] to specify a different maximum slope. Typical usage is a character
+    /// controller, that should be able to jump only if it stands on the ground:
+    ///
+    /// ```rust
+    /// # use fyrox::scene::{graph::Graph, rigidbody::RigidBody};
+    /// fn can_jump(body: &RigidBody, graph: &Graph) -> bool {
+    ///     body.is_grounded(graph, 0.1)
+    /// }
+    /// ```
+    pub fn is_grounded(&self, graph: &Graph, max_distance: f32) -> bool {
+        self.is_grounded_with_max_slope(graph, max_distance, DEFAULT_MAX_GROUND_SLOPE)
+    }
+
+    /// Same as [`Self::is_grounded`], but allows you to specify the maximum slope (in radians) of the ground. Any
+    /// surface that is steeper than the given angle is not considered as ground.
+    pub fn is_grounded_with_max_slope(
+        &self,
+        graph: &Graph,
+        max_distance: f32,
+        max_slope: f32,
+    ) -> bool {
+        graph
+            .physics
+            .cast_ray_below_body(self.native.get(), max_distance)
+            .and_then(|hit| hit.normal.try_normalize(f32::EPSILON))
+            .map_or(false, |normal| normal.y >= max_slope.cos())
+    }
+
     pub(crate) fn need_sync_model(&self) -> bool {
         self.lin_vel.need_sync()
             || self.ang_vel.need_sync()
@@ -494,6 +651,9 @@ impl RigidBody {
             || self.y_rotation_locked.need_sync()
             || self.z_rotation_locked.need_sync()
             || self.translation_locked.need_sync()
+            || self.x_translation_locked.need_sync()
+            || self.y_translation_locked.need_sync()
+            || self.z_translation_locked.need_sync()
             || self.ccd_enabled.need_sync()
             || self.can_sleep.need_sync()
             || self.dominance.need_sync()
@@ -578,10 +738,14 @@ pub struct RigidBodyBuilder {
     y_rotation_locked: bool,
     z_rotation_locked: bool,
     translation_locked: bool,
+    x_translation_locked: bool,
+    y_translation_locked: bool,
+    z_translation_locked: bool,
     ccd_enabled: bool,
     can_sleep: bool,
     dominance: i8,
     gravity_scale: f32,
+    max_linear_speed: f32,
 }
 
 impl RigidBodyBuilder {
@@ -600,10 +764,14 @@ impl RigidBodyBuilder {
             y_rotation_locked: false,
             z_rotation_locked: false,
             translation_locked: false,
+            x_translation_locked: false,
+            y_translation_locked: false,
+            z_translation_locked: false,
             ccd_enabled: false,
             can_sleep: true,
             dominance: 0,
             gravity_scale: 1.0,
+            max_linear_speed: 0.0,
         }
     }
 
@@ -673,6 +841,14 @@ impl RigidBodyBuilder {
         self
     }
 
+    /// Sets whether the translation of the body along each axis should be locked or not.
+    pub fn with_translation_locked_axes(mut self, x: bool, y: bool, z: bool) -> Self {
+        self.x_translation_locked = x;
+        self.y_translation_locked = y;
+        self.z_translation_locked = z;
+        self
+    }
+
     /// Locks or unlocks rotations of the rigid body.
     pub fn with_locked_rotations(mut self, locked: bool) -> Self {
         self.x_rotation_locked = locked;
@@ -705,6 +881,12 @@ impl RigidBodyBuilder {
         self
     }
 
+    /// Sets desired max linear speed. Zero means that the speed is not limited.
+    pub fn with_max_linear_speed(mut self, max_linear_speed: f32) -> Self {
+        self.max_linear_speed = max_linear_speed;
+        self
+    }
+
     /// Creates RigidBody node but does not add it to the graph.
     pub fn build_rigid_body(self) -> RigidBody {
         RigidBody {
@@ -720,10 +902,14 @@ impl RigidBodyBuilder {
             y_rotation_locked: self.y_rotation_locked.into(),
             z_rotation_locked: self.z_rotation_locked.into(),
             translation_locked: self.translation_locked.into(),
+            x_translation_locked: self.x_translation_locked.into(),
+            y_translation_locked: self.y_translation_locked.into(),
+            z_translation_locked: self.z_translation_locked.into(),
             ccd_enabled: self.ccd_enabled.into(),
             can_sleep: self.can_sleep.into(),
             dominance: self.dominance.into(),
             gravity_scale: self.gravity_scale.into(),
+            max_linear_speed: self.max_linear_speed.into(),
             native: Cell::new(RigidBodyHandle::invalid()),
             actions: Default::default(),
             reset_forces: Default::default(),
@@ -740,3 +926,277 @@ impl RigidBodyBuilder {
         graph.add_node(self.build_node())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::{Vector2, Vector3},
+        scene::{
+            base::BaseBuilder,
+            collider::{ColliderBuilder, ColliderShape},
+            graph::Graph,
+            rigidbody::{RigidBodyBuilder, RigidBodyType},
+            transform::TransformBuilder,
+            trigger::{TriggerShape, TriggerVolumeBuilder},
+        },
+    };
+
+    #[test]
+    fn test_locked_rotation_keeps_body_upright() {
+        let mut graph = Graph::new();
+
+        let ground_collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(50.0, 0.5, 50.0))
+            .build(&mut graph);
+        RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_children(&[ground_collider])
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, -0.5, 0.0))
+                        .build(),
+                ),
+        )
+        .with_body_type(RigidBodyType::Static)
+        .build(&mut graph);
+
+        let capsule_collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::capsule_y(0.5, 0.3))
+            .build(&mut graph);
+        let capsule = RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_children(&[capsule_collider])
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, 0.8, 0.0))
+                        .build(),
+                ),
+        )
+        .with_can_sleep(false)
+        .build(&mut graph);
+
+        let body = graph[capsule].as_rigid_body_mut();
+        body.set_rotation_locked(true, false, true);
+        // Push the top of the capsule sideways, without the locks this would tip it over.
+        body.apply_impulse_at_point(Vector3::new(5.0, 0.0, 0.0), Vector3::new(0.0, 1.6, 0.0));
+
+        for _ in 0..120 {
+            graph.update(Vector2::new(800.0, 600.0), 1.0 / 60.0, Default::default());
+        }
+
+        let body = &graph[capsule];
+        assert!(body.global_position().x > 0.1);
+        let up = body.up_vector().normalize();
+        assert!(
+            up.dot(&Vector3::y()) > 0.999,
+            "the body tipped over: {up:?}"
+        );
+    }
+
+    #[test]
+    fn test_resting_body_has_upward_contact() {
+        let mut graph = Graph::new();
+
+        let ground_collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(50.0, 0.5, 50.0))
+            .build(&mut graph);
+        let ground = RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_children(&[ground_collider])
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, -0.5, 0.0))
+                        .build(),
+                ),
+        )
+        .with_body_type(RigidBodyType::Static)
+        .build(&mut graph);
+
+        let cube_collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(0.5, 0.5, 0.5))
+            .build(&mut graph);
+        let cube = RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_children(&[cube_collider])
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, 0.6, 0.0))
+                        .build(),
+                ),
+        )
+        .build(&mut graph);
+
+        for _ in 0..60 {
+            graph.update(Vector2::new(800.0, 600.0), 1.0 / 60.0, Default::default());
+        }
+
+        let contacts = graph[cube]
+            .as_rigid_body()
+            .contacts(&graph.physics)
+            .collect::<Vec<_>>();
+        assert!(!contacts.is_empty());
+        for contact in contacts.iter() {
+            assert_eq!(contact.collider, cube_collider);
+            assert_eq!(contact.other_collider, ground_collider);
+            assert_eq!(contact.other_body, ground);
+        }
+        assert!(contacts
+            .iter()
+            .any(|contact| contact.normal.dot(&Vector3::y()) > 0.99));
+
+        // The ground is touched from above, so its contacts look down.
+        assert!(graph[ground]
+            .as_rigid_body()
+            .contacts(&graph.physics)
+            .all(|contact| contact.normal.dot(&Vector3::y()) < -0.99));
+    }
+
+    #[test]
+    fn test_max_linear_speed() {
+        fn launch(max_linear_speed: f32) -> f32 {
+            let mut graph = Graph::new();
+
+            let collider = ColliderBuilder::new(BaseBuilder::new())
+                .with_shape(ColliderShape::ball(0.5))
+                .build(&mut graph);
+            let body = RigidBodyBuilder::new(BaseBuilder::new().with_children(&[collider]))
+                .with_gravity_scale(0.0)
+                .with_max_linear_speed(max_linear_speed)
+                .build(&mut graph);
+
+            graph[body]
+                .as_rigid_body_mut()
+                .apply_impulse(Vector3::new(10000.0, 0.0, 0.0));
+
+            for _ in 0..10 {
+                graph.update(Vector2::new(800.0, 600.0), 1.0 / 60.0, Default::default());
+            }
+
+            graph[body].as_rigid_body().lin_vel().norm()
+        }
+
+        assert!(launch(0.0) > 1000.0);
+
+        let speed = launch(20.0);
+        assert!((speed - 20.0).abs() < 1.0e-3, "{}", speed);
+    }
+
+    #[test]
+    fn test_is_grounded() {
+        let mut graph = Graph::new();
+
+        let ground_collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(50.0, 0.5, 50.0))
+            .build(&mut graph);
+        RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_children(&[ground_collider])
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, -0.5, 0.0))
+                        .build(),
+                ),
+        )
+        .with_body_type(RigidBodyType::Static)
+        .build(&mut graph);
+
+        let mut make_character = |position: Vector3<f32>| {
+            let collider = ColliderBuilder::new(BaseBuilder::new())
+                .with_shape(ColliderShape::capsule_y(0.5, 0.3))
+                .build(&mut graph);
+            RigidBodyBuilder::new(
+                BaseBuilder::new()
+                    .with_children(&[collider])
+                    .with_local_transform(
+                        TransformBuilder::new()
+                            .with_local_position(position)
+                            .build(),
+                    ),
+            )
+            .with_body_type(RigidBodyType::KinematicPositionBased)
+            .build(&mut graph)
+        };
+
+        let standing = make_character(Vector3::new(0.0, 0.8, 0.0));
+        let flying = make_character(Vector3::new(10.0, 5.0, 0.0));
+
+        // Colliders are built before their bodies, so the first update creates native bodies and the
+        // second one creates native colliders.
+        graph.update(Vector2::new(800.0, 600.0), 1.0 / 60.0, Default::default());
+        graph.update(Vector2::new(800.0, 600.0), 1.0 / 60.0, Default::default());
+
+        assert!(graph[standing].as_rigid_body().is_grounded(&graph, 0.1));
+        assert!(!graph[flying].as_rigid_body().is_grounded(&graph, 0.1));
+        // The ground is reachable from the flying body only with a very long probe.
+        assert!(graph[flying].as_rigid_body().is_grounded(&graph, 5.0));
+    }
+
+    #[test]
+    fn test_is_grounded_ignores_trigger_volumes() {
+        let mut graph = Graph::new();
+
+        let ground_collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(50.0, 0.5, 50.0))
+            .build(&mut graph);
+        RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_children(&[ground_collider])
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, -0.5, 0.0))
+                        .build(),
+                ),
+        )
+        .with_body_type(RigidBodyType::Static)
+        .build(&mut graph);
+
+        let mut make_trigger = |position: Vector3<f32>, half_extents: Vector3<f32>| {
+            TriggerVolumeBuilder::new(
+                BaseBuilder::new().with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(position)
+                        .build(),
+                ),
+            )
+            .with_shape(TriggerShape::cuboid(
+                half_extents.x,
+                half_extents.y,
+                half_extents.z,
+            ))
+            .build(&mut graph);
+        };
+
+        // A body that stands on the ground inside of a trigger volume.
+        make_trigger(Vector3::new(0.0, 1.0, 0.0), Vector3::new(2.0, 2.0, 2.0));
+        // A thin trigger volume right below the feet of a body in the air.
+        make_trigger(Vector3::new(10.0, 4.15, 0.0), Vector3::new(1.0, 0.05, 1.0));
+
+        let mut make_character = |position: Vector3<f32>| {
+            let collider = ColliderBuilder::new(BaseBuilder::new())
+                .with_shape(ColliderShape::capsule_y(0.5, 0.3))
+                .build(&mut graph);
+            RigidBodyBuilder::new(
+                BaseBuilder::new()
+                    .with_children(&[collider])
+                    .with_local_transform(
+                        TransformBuilder::new()
+                            .with_local_position(position)
+                            .build(),
+                    ),
+            )
+            .with_body_type(RigidBodyType::KinematicPositionBased)
+            .build(&mut graph)
+        };
+
+        let standing = make_character(Vector3::new(0.0, 0.8, 0.0));
+        let flying = make_character(Vector3::new(10.0, 5.0, 0.0));
+
+        // Colliders are built before their bodies, so the first update creates native bodies and the
+        // second one creates native colliders.
+        graph.update(Vector2::new(800.0, 600.0), 1.0 / 60.0, Default::default());
+        graph.update(Vector2::new(800.0, 600.0), 1.0 / 60.0, Default::default());
+
+        assert!(graph[standing].as_rigid_body().is_grounded(&graph, 0.1));
+        assert!(!graph[flying].as_rigid_body().is_grounded(&graph, 0.1));
+    }
+}
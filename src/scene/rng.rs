@@ -0,0 +1,92 @@
+//! Seeded pseudo-random numbers generator of a scene. See [`SceneRng`] docs for more info.
+
+use crate::core::{
+    rand::{prelude::StdRng, Error, RngCore, SeedableRng},
+    reflect::prelude::*,
+    visitor::prelude::*,
+};
+
+/// Seeded pseudo-random numbers generator of a scene. Use it for gameplay randomness (random pitch of
+/// footsteps, loot drops, etc.) instead of thread-local generators: the same seed produces the same
+/// sequence of numbers, which makes the game reproducible (for replays, tests, networking). The seed is
+/// saved together with the scene, the generator starts from the beginning of the sequence when the scene
+/// is loaded.
+#[derive(Debug, Clone, Reflect)]
+pub struct SceneRng {
+    seed: u64,
+
+    #[reflect(hidden)]
+    rng: StdRng,
+}
+
+impl Default for SceneRng {
+    fn default() -> Self {
+        Self::new(0xDEADBEEF)
+    }
+}
+
+impl SceneRng {
+    /// Creates new PRNG with a given seed.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Returns the seed of PRNG.
+    #[inline]
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Sets a new seed and resets the state of PRNG.
+    #[inline]
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.reset();
+    }
+
+    /// Resets the state of PRNG, so it will produce the same sequence of numbers again.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.rng = StdRng::seed_from_u64(self.seed);
+    }
+}
+
+impl RngCore for SceneRng {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+impl Visit for SceneRng {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut guard = visitor.enter_region(name)?;
+
+        self.seed.visit("Seed", &mut guard)?;
+
+        // Re-initialize the RNG to keep determinism.
+        if guard.is_reading() {
+            self.rng = StdRng::seed_from_u64(self.seed);
+        }
+
+        Ok(())
+    }
+}
@@ -2,11 +2,17 @@
 
 use crate::{
     core::{
+        algebra::Point3,
         log::{Log, MessageKind},
         pool::Handle,
         visitor::prelude::*,
     },
-    scene::{node::Node, sound::Sound},
+    scene::{
+        graph::{physics::PhysicsWorld, NodePool},
+        node::Node,
+        rigidbody::RigidBody,
+        sound::Sound,
+    },
 };
 use fxhash::FxHashSet;
 use fyrox_sound::{
@@ -22,6 +28,8 @@ use std::{sync::MutexGuard, time::Duration};
 pub struct SoundContext {
     #[visit(optional)]
     pub(crate) native: fyrox_sound::context::SoundContext,
+    #[visit(optional)]
+    occlusion_enabled: bool,
 }
 
 /// Proxy for guarded access to the sound context.
@@ -101,6 +109,7 @@ impl Default for SoundContext {
     fn default() -> Self {
         Self {
             native: fyrox_sound::context::SoundContext::new(),
+            occlusion_enabled: false,
         }
     }
 }
@@ -114,9 +123,24 @@ impl SoundContext {
     pub fn deep_clone(&self) -> Self {
         Self {
             native: self.native.deep_clone(),
+            occlusion_enabled: self.occlusion_enabled,
         }
     }
 
+    /// Enables or disables sound occlusion for every sound source in the scene. When enabled, the
+    /// engine casts a ray from each playing sound source to the listener and muffles the sound if
+    /// the ray hits any solid collider. Sensors (including trigger volumes) and colliders of the rigid
+    /// body the sound is attached to are ignored. Occlusion could also be disabled for particular sounds, see
+    /// [`Sound::set_occlusion_enabled`]. Disabled by default.
+    pub fn set_occlusion_enabled(&mut self, enabled: bool) {
+        self.occlusion_enabled = enabled;
+    }
+
+    /// Returns `true` if sound occlusion is enabled, `false` - otherwise.
+    pub fn is_occlusion_enabled(&self) -> bool {
+        self.occlusion_enabled
+    }
+
     /// Returns locked inner state of the sound context.
     pub fn state(&self) -> SoundContextGuard {
         SoundContextGuard {
@@ -150,6 +174,45 @@ impl SoundContext {
         }
     }
 
+    pub(crate) fn update_occlusion(&self, sound: &Sound, nodes: &NodePool, physics: &PhysicsWorld) {
+        let mut state = self.native.state();
+        let listener_position = state.listener().position();
+        if let Some(source) = state.try_get_source_mut(sound.native.get()) {
+            let mut occlusion = 0.0;
+            if self.occlusion_enabled
+                && sound.is_occlusion_enabled()
+                && sound.status() == Status::Playing
+            {
+                // A sound could be attached to a rigid body (for example, a character), it must not
+                // occlude its own sounds.
+                let mut parent = sound.parent();
+                let mut exclude_body = None;
+                while let Some(node) = nodes.try_borrow(parent) {
+                    if let Some(rigid_body) = node.cast::<RigidBody>() {
+                        exclude_body = Some(rigid_body.native.get());
+                        break;
+                    }
+                    parent = node.parent();
+                }
+
+                let origin = sound.global_position();
+                let direction = listener_position - origin;
+                if physics
+                    .cast_ray_closest_solid(
+                        Point3::from(origin),
+                        direction,
+                        direction.norm(),
+                        exclude_body,
+                    )
+                    .is_some()
+                {
+                    occlusion = 1.0;
+                }
+            }
+            source.set_occlusion(occlusion);
+        }
+    }
+
     pub(crate) fn sync_to_sound(
         &mut self,
         sound_handle: Handle<Node>,
@@ -188,6 +251,14 @@ impl SoundContext {
             sound.looping.try_sync_model(|v| {
                 source.set_looping(v);
             });
+            sound.loop_region.try_sync_model(|v| match v {
+                Some(region) => {
+                    source.set_loop_region(region.start, region.end);
+                }
+                None => {
+                    source.clear_loop_region();
+                }
+            });
             sound.panning.try_sync_model(|v| {
                 source.set_panning(v);
             });
@@ -216,6 +287,7 @@ impl SoundContext {
                 .with_gain(sound.gain())
                 .with_opt_buffer(sound.buffer())
                 .with_looping(sound.is_looping())
+                .with_opt_loop_region(sound.loop_region())
                 .with_panning(sound.panning())
                 .with_pitch(sound.pitch())
                 .with_status(sound.status())
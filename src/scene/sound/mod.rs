@@ -2,9 +2,11 @@
 
 use crate::{
     core::{
-        algebra::Matrix4,
+        algebra::{Matrix4, Vector3},
         math::{aabb::AxisAlignedBoundingBox, m4x4_approx_eq},
+        numeric_range::RangeExt,
         pool::Handle,
+        rand::Rng,
         reflect::prelude::*,
         uuid::{uuid, Uuid},
         variable::InheritableVariable,
@@ -16,6 +18,7 @@ use crate::{
         base::{Base, BaseBuilder},
         graph::Graph,
         node::{Node, NodeTrait, SyncContext, UpdateContext},
+        transform::TransformBuilder,
     },
 };
 
@@ -33,7 +36,7 @@ pub use fyrox_sound::{
     error::SoundError,
     hrtf::HrirSphere,
     renderer::{hrtf::HrtfRenderer, Renderer},
-    source::Status,
+    source::{LoopRegion, Status},
 };
 
 use crate::scene::Scene;
@@ -48,6 +51,37 @@ use std::{
 pub mod context;
 pub mod listener;
 
+/// A range of pitch, from which a random pitch is picked each time a sound is played with a random
+/// numbers generator (see [`Sound::play_with_rng`]). Use it to make repeated sounds (footsteps, gunshots,
+/// etc.) less monotonous.
+#[derive(Copy, Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct PitchRange {
+    /// Min pitch.
+    #[reflect(min_value = 0.0, step = 0.05)]
+    pub min: f64,
+    /// Max pitch.
+    #[reflect(min_value = 0.0, step = 0.05)]
+    pub max: f64,
+}
+
+impl Default for PitchRange {
+    fn default() -> Self {
+        Self { min: 0.9, max: 1.1 }
+    }
+}
+
+impl PitchRange {
+    /// Creates a new pitch range.
+    pub fn new(min: f64, max: f64) -> Self {
+        Self { min, max }
+    }
+
+    /// Picks a random pitch from the range.
+    pub fn random<R: Rng>(&self, rng: &mut R) -> f64 {
+        (self.min..self.max).random(rng).abs()
+    }
+}
+
 /// Sound source.
 #[derive(Visit, Reflect, Debug)]
 pub struct Sound {
@@ -73,10 +107,24 @@ pub struct Sound {
     #[reflect(setter = "set_looping")]
     looping: InheritableVariable<bool>,
 
+    #[visit(optional)]
+    #[reflect(
+        setter = "set_loop_region",
+        description = "A region of the sound that will be repeated when looping is enabled."
+    )]
+    loop_region: InheritableVariable<Option<LoopRegion>>,
+
     #[reflect(min_value = 0.0, step = 0.05)]
     #[reflect(setter = "set_pitch")]
     pitch: InheritableVariable<f64>,
 
+    #[visit(optional)]
+    #[reflect(
+        setter = "set_pitch_range",
+        description = "A range of pitch, from which a random pitch is picked each time the sound is played with a random numbers generator."
+    )]
+    pitch_range: InheritableVariable<Option<PitchRange>>,
+
     #[reflect(min_value = 0.0, step = 0.05)]
     #[reflect(setter = "set_radius")]
     radius: InheritableVariable<f32>,
@@ -101,6 +149,13 @@ pub struct Sound {
     )]
     audio_bus: InheritableVariable<String>,
 
+    #[visit(optional)]
+    #[reflect(
+        setter = "set_occlusion_enabled",
+        description = "Whether the sound should be muffled by obstacles between it and the listener. Works only if occlusion is enabled in the sound context."
+    )]
+    occlusion_enabled: InheritableVariable<bool>,
+
     #[reflect(hidden)]
     #[visit(skip)]
     pub(crate) native: Cell<Handle<SoundSource>>,
@@ -130,13 +185,16 @@ impl Default for Sound {
             panning: InheritableVariable::new_modified(0.0),
             status: InheritableVariable::new_modified(Status::Stopped),
             looping: InheritableVariable::new_modified(false),
+            loop_region: InheritableVariable::new_modified(None),
             pitch: InheritableVariable::new_modified(1.0),
+            pitch_range: InheritableVariable::new_modified(None),
             radius: InheritableVariable::new_modified(10.0),
             max_distance: InheritableVariable::new_modified(f32::MAX),
             rolloff_factor: InheritableVariable::new_modified(1.0),
             playback_time: Default::default(),
             spatial_blend: InheritableVariable::new_modified(1.0),
             audio_bus: InheritableVariable::new_modified(AudioBusGraph::PRIMARY_BUS.to_string()),
+            occlusion_enabled: InheritableVariable::new_modified(true),
             native: Default::default(),
         }
     }
@@ -152,13 +210,16 @@ impl Clone for Sound {
             panning: self.panning.clone(),
             status: self.status.clone(),
             looping: self.looping.clone(),
+            loop_region: self.loop_region.clone(),
             pitch: self.pitch.clone(),
+            pitch_range: self.pitch_range.clone(),
             radius: self.radius.clone(),
             max_distance: self.max_distance.clone(),
             rolloff_factor: self.rolloff_factor.clone(),
             playback_time: self.playback_time.clone(),
             spatial_blend: self.spatial_blend.clone(),
             audio_bus: self.audio_bus.clone(),
+            occlusion_enabled: self.occlusion_enabled.clone(),
             // Do not copy. The copy will have its own native representation.
             native: Default::default(),
         }
@@ -280,6 +341,17 @@ impl Sound {
         *self.looping
     }
 
+    /// Sets a region of the sound that will be played in a loop (see [`SoundSource::set_loop_region`]
+    /// for more info). `None` means that the entire sound will loop.
+    pub fn set_loop_region(&mut self, loop_region: Option<LoopRegion>) -> Option<LoopRegion> {
+        self.loop_region.set_value_and_mark_modified(loop_region)
+    }
+
+    /// Returns current loop region.
+    pub fn loop_region(&self) -> Option<LoopRegion> {
+        *self.loop_region
+    }
+
     /// Sets sound pitch. Defines "tone" of sounds. Default value is 1.0
     pub fn set_pitch(&mut self, pitch: f64) -> f64 {
         self.pitch.set_value_and_mark_modified(pitch.abs())
@@ -290,6 +362,27 @@ impl Sound {
         *self.pitch
     }
 
+    /// Sets a range of pitch, from which a random pitch will be picked by [`Self::play_with_rng`]. `None`
+    /// means that the pitch is not randomized.
+    pub fn set_pitch_range(&mut self, pitch_range: Option<PitchRange>) -> Option<PitchRange> {
+        self.pitch_range.set_value_and_mark_modified(pitch_range)
+    }
+
+    /// Returns current pitch range.
+    pub fn pitch_range(&self) -> Option<PitchRange> {
+        *self.pitch_range
+    }
+
+    /// Picks a random pitch from the pitch range (if any) using the given random numbers generator and
+    /// changes status to `Playing`. Use a seeded generator (for example [`crate::scene::rng::SceneRng`])
+    /// to keep the pitch reproducible.
+    pub fn play_with_rng<R: Rng>(&mut self, rng: &mut R) {
+        if let Some(pitch_range) = *self.pitch_range {
+            self.set_pitch(pitch_range.random(rng));
+        }
+        self.play();
+    }
+
     /// Stops sound source. Automatically rewinds streaming buffers.
     pub fn stop(&mut self) {
         self.status.set_value_and_mark_modified(Status::Stopped);
@@ -350,6 +443,19 @@ impl Sound {
     pub fn audio_bus(&self) -> &str {
         &self.audio_bus
     }
+
+    /// Enables or disables occlusion of the sound. Occluded sound is quieter and muffled, the engine
+    /// checks whether the sound is occluded by casting a ray from the sound to the listener. Keep in
+    /// mind, that occlusion must also be enabled in the sound context (see
+    /// [`context::SoundContext::set_occlusion_enabled`]). Enabled by default.
+    pub fn set_occlusion_enabled(&mut self, enabled: bool) -> bool {
+        self.occlusion_enabled.set_value_and_mark_modified(enabled)
+    }
+
+    /// Returns `true` if the sound could be occluded, `false` - otherwise.
+    pub fn is_occlusion_enabled(&self) -> bool {
+        *self.occlusion_enabled
+    }
 }
 
 impl NodeTrait for Sound {
@@ -399,6 +505,9 @@ impl NodeTrait for Sound {
 
     fn update(&mut self, context: &mut UpdateContext) {
         context.sound_context.sync_with_sound(self);
+        context
+            .sound_context
+            .update_occlusion(self, context.nodes, context.physics);
     }
 
     fn validate(&self, _scene: &Scene) -> Result<(), String> {
@@ -433,13 +542,16 @@ pub struct SoundBuilder {
     panning: f32,
     status: Status,
     looping: bool,
+    loop_region: Option<LoopRegion>,
     pitch: f64,
+    pitch_range: Option<PitchRange>,
     radius: f32,
     max_distance: f32,
     rolloff_factor: f32,
     playback_time: Duration,
     spatial_blend: f32,
     audio_bus: String,
+    occlusion_enabled: bool,
 }
 
 impl SoundBuilder {
@@ -453,13 +565,16 @@ impl SoundBuilder {
             panning: 0.0,
             status: Status::Stopped,
             looping: false,
+            loop_region: None,
             pitch: 1.0,
+            pitch_range: None,
             radius: 10.0,
             max_distance: f32::MAX,
             rolloff_factor: 1.0,
             spatial_blend: 1.0,
             playback_time: Default::default(),
             audio_bus: AudioBusGraph::PRIMARY_BUS.to_string(),
+            occlusion_enabled: true,
         }
     }
 
@@ -493,11 +608,21 @@ impl SoundBuilder {
         fn with_looping(looping: bool)
     );
 
+    define_with!(
+        /// Sets desired loop region. See [`Sound::set_loop_region`] for more info.
+        fn with_loop_region(loop_region: Option<LoopRegion>)
+    );
+
     define_with!(
         /// Sets desired pitch. See [`Sound::set_pitch`] for more info.
         fn with_pitch(pitch: f64)
     );
 
+    define_with!(
+        /// Sets desired pitch range. See [`Sound::set_pitch_range`] for more info.
+        fn with_pitch_range(pitch_range: Option<PitchRange>)
+    );
+
     define_with!(
         /// Sets desired radius. See [`Sound::set_radius`] for more info.
         fn with_radius(radius: f32)
@@ -528,6 +653,11 @@ impl SoundBuilder {
         fn with_audio_bus(audio_bus: String)
     );
 
+    define_with!(
+        /// Sets whether the sound could be occluded or not. See [`Sound::set_occlusion_enabled`] for more info.
+        fn with_occlusion_enabled(occlusion_enabled: bool)
+    );
+
     /// Creates a new [`Sound`] node.
     #[must_use]
     pub fn build_sound(self) -> Sound {
@@ -539,13 +669,16 @@ impl SoundBuilder {
             panning: self.panning.into(),
             status: self.status.into(),
             looping: self.looping.into(),
+            loop_region: self.loop_region.into(),
             pitch: self.pitch.into(),
+            pitch_range: self.pitch_range.into(),
             radius: self.radius.into(),
             max_distance: self.max_distance.into(),
             rolloff_factor: self.rolloff_factor.into(),
             playback_time: self.playback_time.into(),
             spatial_blend: self.spatial_blend.into(),
             audio_bus: self.audio_bus.into(),
+            occlusion_enabled: self.occlusion_enabled.into(),
             native: Default::default(),
         }
     }
@@ -561,3 +694,333 @@ impl SoundBuilder {
         graph.add_node(self.build_node())
     }
 }
+
+fn make_one_shot(
+    buffer: SoundBufferResource,
+    position: Option<Vector3<f32>>,
+    gain: f32,
+) -> SoundBuilder {
+    SoundBuilder::new(
+        BaseBuilder::new().with_local_transform(
+            TransformBuilder::new()
+                .with_local_position(position.unwrap_or_default())
+                .build(),
+        ),
+    )
+    .with_buffer(Some(buffer))
+    .with_spatial_blend_factor(if position.is_some() { 1.0 } else { 0.0 })
+    .with_gain(gain)
+    .with_play_once(true)
+    .with_status(Status::Playing)
+}
+
+/// Creates a new sound source, that plays the given buffer once and then removes itself from the graph. A sound with
+/// `position` is fully spatial and is emitted from the given point in world space, `None` creates a non-spatial (2D)
+/// sound, which is useful for UI sounds and music stingers. Returns a handle to the new sound node, it becomes invalid
+/// once the sound has stopped.
+pub fn play_one_shot(
+    graph: &mut Graph,
+    buffer: SoundBufferResource,
+    position: Option<Vector3<f32>>,
+    gain: f32,
+) -> Handle<Node> {
+    make_one_shot(buffer, position, gain).build(graph)
+}
+
+/// Same as [`play_one_shot`], but the sound is played with a random pitch from the given range. The pitch is picked
+/// using the given random numbers generator, use a seeded one (for example [`crate::scene::rng::SceneRng`]) to make
+/// the pitch reproducible.
+pub fn play_one_shot_with_pitch_range<R: Rng>(
+    graph: &mut Graph,
+    buffer: SoundBufferResource,
+    position: Option<Vector3<f32>>,
+    gain: f32,
+    pitch_range: PitchRange,
+    rng: &mut R,
+) -> Handle<Node> {
+    make_one_shot(buffer, position, gain)
+        .with_pitch_range(Some(pitch_range))
+        .with_pitch(pitch_range.random(rng))
+        .build(graph)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::{Vector2, Vector3},
+        scene::{
+            base::BaseBuilder,
+            collider::{ColliderBuilder, ColliderShape},
+            graph::{Graph, GraphUpdateSwitches},
+            rigidbody::{RigidBodyBuilder, RigidBodyType},
+            rng::SceneRng,
+            sound::{
+                play_one_shot, play_one_shot_with_pitch_range, DataSource, PitchRange,
+                SoundBufferResource, SoundBuilder, SoundEngine, Status,
+            },
+            transform::TransformBuilder,
+        },
+    };
+    use fyrox_sound::{buffer::SoundBufferResourceExtension, source::OCCLUDED_GAIN};
+    use rapier3d::geometry::ColliderHandle;
+
+    #[test]
+    fn test_one_shot_is_removed_after_playback() {
+        let mut graph = Graph::new();
+
+        let mut engine = SoundEngine::without_device();
+        engine.add_context(graph.sound_context.native.clone());
+
+        // A very short buffer, it will be fully played in a single render pass.
+        let buffer = SoundBufferResource::new_generic(DataSource::Raw {
+            sample_rate: 44100,
+            channel_count: 1,
+            samples: vec![0.5; 64],
+        })
+        .unwrap();
+
+        let sound = play_one_shot(&mut graph, buffer, Some(Vector3::new(1.0, 2.0, 3.0)), 0.5);
+        assert!(graph.is_valid_handle(sound));
+        assert_eq!(graph[sound].as_sound().spatial_blend(), 1.0);
+
+        let frame_size = Vector2::new(100.0, 100.0);
+
+        // Creates the native sound source.
+        graph.update(frame_size, 1.0 / 60.0, GraphUpdateSwitches::default());
+        assert!(graph.is_valid_handle(sound));
+
+        let mut output = vec![(0.0, 0.0); SoundEngine::render_buffer_len()];
+        engine.render(&mut output);
+
+        for _ in 0..3 {
+            graph.update(frame_size, 1.0 / 60.0, GraphUpdateSwitches::default());
+        }
+        assert!(!graph.is_valid_handle(sound));
+    }
+
+    #[test]
+    fn test_one_shot_random_pitch_is_reproducible() {
+        let mut graph = Graph::new();
+
+        let buffer = SoundBufferResource::new_generic(DataSource::Raw {
+            sample_rate: 44100,
+            channel_count: 1,
+            samples: vec![0.5; 64],
+        })
+        .unwrap();
+
+        let pitch_range = PitchRange::new(0.8, 1.2);
+        let mut play_footsteps = |rng: &mut SceneRng| {
+            [(); 2].map(|_| {
+                let sound = play_one_shot_with_pitch_range(
+                    &mut graph,
+                    buffer.clone(),
+                    None,
+                    1.0,
+                    pitch_range,
+                    rng,
+                );
+                graph[sound].as_sound().pitch()
+            })
+        };
+
+        let mut rng = SceneRng::new(42);
+        let [first, second] = play_footsteps(&mut rng);
+        assert_ne!(first, second);
+        for pitch in [first, second] {
+            assert!((0.8..=1.2).contains(&pitch));
+        }
+
+        // The same seed gives the same pitches.
+        rng.reset();
+        assert_eq!(play_footsteps(&mut rng), [first, second]);
+    }
+
+    #[test]
+    fn test_wall_occludes_sound() {
+        let mut graph = Graph::new();
+        graph.sound_context.set_occlusion_enabled(true);
+
+        let mut engine = SoundEngine::without_device();
+        engine.add_context(graph.sound_context.native.clone());
+
+        // Highest possible frequency - every sample flips its sign.
+        let buffer = SoundBufferResource::new_generic(DataSource::Raw {
+            sample_rate: 44100,
+            channel_count: 1,
+            samples: (0..44100)
+                .map(|i| if i % 2 == 0 { 0.5 } else { -0.5 })
+                .collect(),
+        })
+        .unwrap();
+
+        // The listener is at the origin.
+        let sound = SoundBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(0.0, 0.0, 5.0))
+                    .build(),
+            ),
+        )
+        .with_buffer(Some(buffer))
+        .with_looping(true)
+        .with_status(Status::Playing)
+        .build(&mut graph);
+
+        let render_peak = |graph: &mut Graph, engine: &mut SoundEngine| {
+            graph.update(
+                Vector2::new(100.0, 100.0),
+                1.0 / 60.0,
+                GraphUpdateSwitches::default(),
+            );
+            let mut output = vec![(0.0, 0.0); SoundEngine::render_buffer_len()];
+            // Gain is interpolated during the first pass, so check the second one.
+            engine.render(&mut output);
+            engine.render(&mut output);
+            output
+                .iter()
+                .fold(0.0f32, |peak, (left, _)| peak.max(left.abs()))
+        };
+        let occlusion = |graph: &Graph| {
+            graph
+                .sound_context
+                .native
+                .state()
+                .source(graph[sound].as_sound().native.get())
+                .occlusion()
+        };
+
+        let clear_peak = render_peak(&mut graph, &mut engine);
+        assert!(clear_peak > 0.0);
+        assert_eq!(occlusion(&graph), 0.0);
+
+        // Put a wall between the sound and the listener.
+        let collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(2.0, 2.0, 0.1))
+            .build(&mut graph);
+        RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, 0.0, 2.5))
+                        .build(),
+                )
+                .with_children(&[collider]),
+        )
+        .with_body_type(RigidBodyType::Static)
+        .build(&mut graph);
+        // The collider is built before its body, so the native body is created first and the native
+        // collider is created on the next update.
+        graph.update(
+            Vector2::new(100.0, 100.0),
+            1.0 / 60.0,
+            GraphUpdateSwitches::default(),
+        );
+
+        let occluded_peak = render_peak(&mut graph, &mut engine);
+        assert_eq!(occlusion(&graph), 1.0);
+        // The sound is both quieter and muffled.
+        assert!(occluded_peak < clear_peak * OCCLUDED_GAIN);
+
+        // Occlusion can be disabled per sound.
+        graph[sound].as_sound_mut().set_occlusion_enabled(false);
+        render_peak(&mut graph, &mut engine);
+        assert_eq!(occlusion(&graph), 0.0);
+    }
+
+    #[test]
+    fn test_own_body_and_sensors_do_not_occlude_sound() {
+        let mut graph = Graph::new();
+        graph.sound_context.set_occlusion_enabled(true);
+
+        let buffer = SoundBufferResource::new_generic(DataSource::Raw {
+            sample_rate: 44100,
+            channel_count: 1,
+            samples: vec![0.5; 64],
+        })
+        .unwrap();
+
+        // A character with a collider that encloses its own sound, the listener is at the origin.
+        let sound = SoundBuilder::new(BaseBuilder::new())
+            .with_buffer(Some(buffer))
+            .with_looping(true)
+            .with_status(Status::Playing)
+            .build(&mut graph);
+        let body_collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::ball(1.0))
+            .build(&mut graph);
+        RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, 0.0, 5.0))
+                        .build(),
+                )
+                .with_children(&[body_collider, sound]),
+        )
+        .with_body_type(RigidBodyType::KinematicPositionBased)
+        .build(&mut graph);
+
+        // A sensor between the sound and the listener.
+        let sensor = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(2.0, 2.0, 0.1))
+            .with_sensor(true)
+            .build(&mut graph);
+        RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, 0.0, 2.5))
+                        .build(),
+                )
+                .with_children(&[sensor]),
+        )
+        .with_body_type(RigidBodyType::Static)
+        .build(&mut graph);
+
+        // Colliders are built before their bodies, so it takes two updates to create native bodies
+        // and then native colliders.
+        let update_occlusion = |graph: &mut Graph| {
+            for _ in 0..2 {
+                graph.update(
+                    Vector2::new(100.0, 100.0),
+                    1.0 / 60.0,
+                    GraphUpdateSwitches::default(),
+                );
+            }
+            graph
+                .sound_context
+                .native
+                .state()
+                .source(graph[sound].as_sound().native.get())
+                .occlusion()
+        };
+
+        assert_eq!(update_occlusion(&mut graph), 0.0);
+        // Both the own body collider and the sensor are in the physics world.
+        for collider in [body_collider, sensor] {
+            assert_ne!(
+                graph[collider].as_collider().native.get(),
+                ColliderHandle::invalid()
+            );
+        }
+
+        // A solid wall still occludes the sound.
+        let wall = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(2.0, 2.0, 0.1))
+            .build(&mut graph);
+        RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, 0.0, 1.5))
+                        .build(),
+                )
+                .with_children(&[wall]),
+        )
+        .with_body_type(RigidBodyType::Static)
+        .build(&mut graph);
+
+        assert_eq!(update_occlusion(&mut graph), 1.0);
+    }
+}
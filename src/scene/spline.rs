@@ -0,0 +1,369 @@
+//! Spline is a smooth curve, that passes through a set of control points. See [`Spline`] docs for more
+//! info.
+
+use crate::{
+    core::{
+        algebra::Vector3,
+        color::Color,
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        variable::InheritableVariable,
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    scene::{
+        base::{Base, BaseBuilder},
+        debug::{Line, SceneDrawingContext},
+        graph::Graph,
+        node::{Node, NodeTrait},
+    },
+};
+use std::ops::{Deref, DerefMut};
+
+/// Spline is a smooth curve (Catmull-Rom spline), that passes through every control point. It could be
+/// used as a path for cutscene cameras, patrolling characters, moving platforms and so on.
+///
+/// Control points are defined in local coordinates of the node, so the whole path could be moved, rotated
+/// or scaled using node's transform. The spline could be sampled using normalized parameter `t` in
+/// `[0; 1]` range, where `0.0` is the first control point and `1.0` is the last one (or the first
+/// one again, if the spline is closed). Each segment between two adjacent control points takes the same
+/// portion of the parameter range, regardless of its length.
+///
+/// The tangent returned by the sampling methods is the derivative of the position with respect to `t`,
+/// it is continuous along the entire spline, so it could be used to orient objects moving along the path.
+///
+/// # Example
+///
+/// The following example creates a rail, and moves a camera along it.
+///
+/// ```rust
+/// use fyrox::{
+///     core::{algebra::{UnitQuaternion, Vector3}, pool::Handle},
+///     scene::{
+///         base::BaseBuilder,
+///         graph::Graph,
+///         node::Node,
+///         spline::{Spline, SplineBuilder},
+///     },
+/// };
+///
+/// fn create_rail(graph: &mut Graph) -> Handle<Node> {
+///     SplineBuilder::new(BaseBuilder::new().with_name("CameraRail"))
+///         .with_control_points(vec![
+///             Vector3::new(0.0, 2.0, 0.0),
+///             Vector3::new(5.0, 3.0, 2.0),
+///             Vector3::new(10.0, 2.0, 0.0),
+///         ])
+///         .build(graph)
+/// }
+///
+/// fn move_camera(graph: &mut Graph, rail: Handle<Node>, camera: Handle<Node>, t: f32) {
+///     if let Some((position, tangent)) = graph
+///         .try_get_of_type::<Spline>(rail)
+///         .and_then(|rail| rail.sample_global(t))
+///     {
+///         graph[camera]
+///             .local_transform_mut()
+///             .set_position(position)
+///             .set_rotation(UnitQuaternion::face_towards(&tangent, &Vector3::y()));
+///     }
+/// }
+/// ```
+#[derive(Reflect, Visit, Debug, Clone, Default)]
+pub struct Spline {
+    base: Base,
+
+    #[reflect(setter = "set_control_points")]
+    control_points: InheritableVariable<Vec<Vector3<f32>>>,
+
+    #[reflect(setter = "set_closed")]
+    closed: InheritableVariable<bool>,
+}
+
+impl Deref for Spline {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Spline {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl TypeUuidProvider for Spline {
+    fn type_uuid() -> Uuid {
+        uuid!("b6e1a1a2-52a4-4b0e-8f3e-6f7b1c4d9a35")
+    }
+}
+
+impl Spline {
+    /// Sets new control points of the spline (in local coordinates). Returns previous control points.
+    pub fn set_control_points(&mut self, points: Vec<Vector3<f32>>) -> Vec<Vector3<f32>> {
+        self.control_points.set_value_and_mark_modified(points)
+    }
+
+    /// Returns a slice with control points of the spline (in local coordinates).
+    pub fn control_points(&self) -> &[Vector3<f32>] {
+        &self.control_points
+    }
+
+    /// Sets whether the spline is closed or not. Closed spline connects the last control point with the
+    /// first one. Returns previous value.
+    pub fn set_closed(&mut self, closed: bool) -> bool {
+        self.closed.set_value_and_mark_modified(closed)
+    }
+
+    /// Returns `true` if the spline is closed, `false` - otherwise.
+    pub fn is_closed(&self) -> bool {
+        *self.closed
+    }
+
+    fn segment_count(&self) -> usize {
+        let count = self.control_points.len();
+        if *self.closed && count > 2 {
+            count
+        } else {
+            count.saturating_sub(1)
+        }
+    }
+
+    fn point(&self, index: isize) -> Vector3<f32> {
+        let count = self.control_points.len() as isize;
+        let index = if *self.closed && count > 2 {
+            index.rem_euclid(count)
+        } else {
+            index.clamp(0, count - 1)
+        };
+        self.control_points[index as usize]
+    }
+
+    /// Samples the spline at the given parameter `t` in `[0; 1]` range (the value is clamped). Returns a
+    /// pair of position and tangent in local coordinates of the node or [`None`] if the spline has no
+    /// control points. See [`Spline`] docs for more info about parametrization.
+    pub fn sample(&self, t: f32) -> Option<(Vector3<f32>, Vector3<f32>)> {
+        let segment_count = self.segment_count();
+        if segment_count == 0 {
+            return self
+                .control_points
+                .first()
+                .map(|point| (*point, Vector3::default()));
+        }
+
+        let scaled = t.clamp(0.0, 1.0) * segment_count as f32;
+        let segment = (scaled.floor() as usize).min(segment_count - 1);
+        let s = scaled - segment as f32;
+
+        let i = segment as isize;
+        let p0 = self.point(i - 1);
+        let p1 = self.point(i);
+        let p2 = self.point(i + 1);
+        let p3 = self.point(i + 2);
+
+        // Uniform Catmull-Rom spline in polynomial form.
+        let a = p1.scale(2.0);
+        let b = p2 - p0;
+        let c = p0.scale(2.0) - p1.scale(5.0) + p2.scale(4.0) - p3;
+        let d = -p0 + p1.scale(3.0) - p2.scale(3.0) + p3;
+
+        let position = (a + b.scale(s) + c.scale(s * s) + d.scale(s * s * s)).scale(0.5);
+        let tangent = (b + c.scale(2.0 * s) + d.scale(3.0 * s * s)).scale(0.5);
+
+        // Convert the tangent from segment's parameter to spline's parameter.
+        Some((position, tangent.scale(segment_count as f32)))
+    }
+
+    /// Same as [`Self::sample`], but returns position and tangent in world coordinates.
+    pub fn sample_global(&self, t: f32) -> Option<(Vector3<f32>, Vector3<f32>)> {
+        self.sample(t).map(|(position, tangent)| {
+            let transform = self.global_transform();
+            (
+                transform.transform_point(&position.into()).coords,
+                transform.transform_vector(&tangent),
+            )
+        })
+    }
+}
+
+impl NodeTrait for Spline {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        if self.control_points.is_empty() {
+            self.base.local_bounding_box()
+        } else {
+            AxisAlignedBoundingBox::from_points(&self.control_points)
+        }
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.local_bounding_box()
+            .transform(&self.global_transform())
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn debug_draw(&self, ctx: &mut SceneDrawingContext) {
+        const STEPS_PER_SEGMENT: usize = 16;
+
+        let steps = self.segment_count() * STEPS_PER_SEGMENT;
+        let mut previous = None;
+        for i in 0..=steps {
+            if let Some((position, _)) = self.sample_global(i as f32 / steps.max(1) as f32) {
+                if let Some(previous) = previous {
+                    ctx.add_line(Line {
+                        begin: previous,
+                        end: position,
+                        color: Color::ORANGE,
+                    });
+                }
+                previous = Some(position);
+            }
+        }
+    }
+}
+
+/// Allows you to create splines in declarative manner.
+pub struct SplineBuilder {
+    base_builder: BaseBuilder,
+    control_points: Vec<Vector3<f32>>,
+    closed: bool,
+}
+
+impl SplineBuilder {
+    /// Creates new spline builder.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            control_points: Default::default(),
+            closed: false,
+        }
+    }
+
+    /// Sets desired control points of the spline (in local coordinates).
+    pub fn with_control_points(mut self, control_points: Vec<Vector3<f32>>) -> Self {
+        self.control_points = control_points;
+        self
+    }
+
+    /// Sets whether the spline is closed or not.
+    pub fn with_closed(mut self, closed: bool) -> Self {
+        self.closed = closed;
+        self
+    }
+
+    /// Creates new spline node.
+    pub fn build_node(self) -> Node {
+        Node::new(Spline {
+            base: self.base_builder.build_base(),
+            control_points: self.control_points.into(),
+            closed: self.closed.into(),
+        })
+    }
+
+    /// Creates new spline node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector3,
+        scene::{
+            base::BaseBuilder,
+            graph::Graph,
+            spline::{Spline, SplineBuilder},
+            transform::TransformBuilder,
+        },
+    };
+
+    fn assert_near(a: Vector3<f32>, b: Vector3<f32>) {
+        assert!((a - b).norm() < 1.0e-4, "{:?} != {:?}", a, b);
+    }
+
+    fn spline(closed: bool) -> Spline {
+        let mut graph = Graph::new();
+        let node = SplineBuilder::new(BaseBuilder::new())
+            .with_control_points(vec![
+                Vector3::new(0.0, 0.0, 0.0),
+                Vector3::new(1.0, 1.0, 0.0),
+                Vector3::new(2.0, 0.0, 0.0),
+            ])
+            .with_closed(closed)
+            .build(&mut graph);
+        graph[node].cast::<Spline>().unwrap().clone()
+    }
+
+    #[test]
+    fn test_spline_sampling() {
+        let spline = spline(false);
+
+        let (begin, begin_tangent) = spline.sample(0.0).unwrap();
+        assert_near(begin, Vector3::new(0.0, 0.0, 0.0));
+        assert_near(begin_tangent, Vector3::new(1.0, 1.0, 0.0));
+
+        let (middle, middle_tangent) = spline.sample(0.5).unwrap();
+        assert_near(middle, Vector3::new(1.0, 1.0, 0.0));
+        assert_near(middle_tangent, Vector3::new(2.0, 0.0, 0.0));
+
+        let (end, end_tangent) = spline.sample(1.0).unwrap();
+        assert_near(end, Vector3::new(2.0, 0.0, 0.0));
+        assert_near(end_tangent, Vector3::new(1.0, -1.0, 0.0));
+
+        // Tangent must be continuous at the joint of two segments.
+        let (_, left) = spline.sample(0.5 - 1.0e-4).unwrap();
+        let (_, right) = spline.sample(0.5 + 1.0e-4).unwrap();
+        assert!((left - right).norm() < 1.0e-2);
+        assert!((left - middle_tangent).norm() < 1.0e-2);
+
+        // Out-of-range parameter is clamped.
+        assert_eq!(spline.sample(2.0), spline.sample(1.0));
+        assert!(Spline::default().sample(0.5).is_none());
+    }
+
+    #[test]
+    fn test_closed_spline_sampling() {
+        let spline = spline(true);
+
+        assert_near(spline.sample(0.0).unwrap().0, Vector3::new(0.0, 0.0, 0.0));
+        assert_near(spline.sample(1.0).unwrap().0, Vector3::new(0.0, 0.0, 0.0));
+        // Closed spline has the same tangent at both ends.
+        assert_near(spline.sample(0.0).unwrap().1, spline.sample(1.0).unwrap().1);
+    }
+
+    #[test]
+    fn test_spline_global_sampling() {
+        let mut graph = Graph::new();
+        let node = SplineBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(0.0, 0.0, 5.0))
+                    .with_local_scale(Vector3::new(2.0, 2.0, 2.0))
+                    .build(),
+            ),
+        )
+        .with_control_points(vec![
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+        ])
+        .build(&mut graph);
+        graph.update(Default::default(), 0.0, Default::default());
+
+        let (position, tangent) = graph[node]
+            .cast::<Spline>()
+            .unwrap()
+            .sample_global(1.0)
+            .unwrap();
+        assert_near(position, Vector3::new(2.0, 0.0, 5.0));
+        assert_near(tangent, Vector3::new(1.0, 0.0, 0.0));
+    }
+}
@@ -244,7 +244,8 @@ impl Transform {
             .set_value_and_mark_modified(local_rotation)
     }
 
-    /// Returns current scale factor of transform.
+    /// Returns current scale factor of transform. Use [`crate::scene::graph::Graph::global_scale`] to get
+    /// the scale in world coordinates.
     #[inline]
     pub fn scale(&self) -> &InheritableVariable<Vector3<f32>> {
         &self.local_scale
@@ -259,6 +260,12 @@ impl Transform {
         self
     }
 
+    /// Sets the same scale factor for every axis. Negative values mirror the object.
+    #[inline]
+    pub fn set_uniform_scale(&mut self, scale: f32) -> &mut Self {
+        self.set_scale(Vector3::repeat(scale))
+    }
+
     #[inline]
     fn set_scale_internal(&mut self, local_scale: Vector3<f32>) -> Vector3<f32> {
         self.dirty.set(true);
@@ -0,0 +1,428 @@
+//! Trigger volume is a region of space, that detects colliders entering and leaving it. See
+//! [`TriggerVolume`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::Vector3,
+        color::Color,
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        variable::InheritableVariable,
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    scene::{
+        base::{Base, BaseBuilder},
+        collider::{BallShape, CuboidShape},
+        debug::SceneDrawingContext,
+        graph::Graph,
+        node::{Node, NodeTrait, SyncContext, UpdateContext},
+        rigidbody::RigidBody,
+    },
+    script::ScriptMessageSender,
+};
+use rapier3d::geometry::ColliderHandle;
+use std::{
+    cell::Cell,
+    ops::{Deref, DerefMut},
+};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Possible shapes of a trigger volume.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect, AsRefStr, EnumString, EnumVariantNames)]
+pub enum TriggerShape {
+    /// See [`CuboidShape`] docs.
+    Cuboid(CuboidShape),
+    /// See [`BallShape`] docs.
+    Ball(BallShape),
+}
+
+impl Default for TriggerShape {
+    fn default() -> Self {
+        Self::Cuboid(Default::default())
+    }
+}
+
+impl TriggerShape {
+    /// Initializes a box shape defined by its half-extents.
+    pub fn cuboid(hx: f32, hy: f32, hz: f32) -> Self {
+        Self::Cuboid(CuboidShape {
+            half_extents: Vector3::new(hx, hy, hz),
+        })
+    }
+
+    /// Initializes a sphere shape defined by its radius.
+    pub fn ball(radius: f32) -> Self {
+        Self::Ball(BallShape { radius })
+    }
+}
+
+/// Kind of a trigger event.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TriggerEventKind {
+    /// A collider has started intersecting the volume.
+    Enter,
+    /// A collider has stopped intersecting the volume.
+    Exit,
+}
+
+/// An event, that is produced by a trigger volume when a collider enters or leaves it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TriggerEvent {
+    /// Kind of the event.
+    pub kind: TriggerEventKind,
+    /// A handle of the collider node, that has entered or left the volume.
+    pub collider: Handle<Node>,
+    /// A handle of the rigid body, the collider is attached to. Could be [`Handle::NONE`] if the
+    /// collider is not a child of a rigid body.
+    pub body: Handle<Node>,
+}
+
+/// A script message, that is sent by a trigger volume for each of its events. The message is
+/// targeted both to the volume itself and to the rigid body (or the collider, if it has no rigid
+/// body) that has entered or left the volume. Scripts must subscribe to it using
+/// [`crate::script::ScriptMessageDispatcher::subscribe_to`] with `TriggerMessage` type to receive
+/// it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TriggerMessage {
+    /// A handle of the trigger volume, that has produced the event.
+    pub trigger: Handle<Node>,
+    /// The event itself.
+    pub event: TriggerEvent,
+}
+
+/// Trigger volume is an invisible region of space, that tracks colliders entering and leaving it.
+/// Unlike sensor colliders, it does not need a rigid body and it detects colliders of any rigid
+/// body type, including static and kinematic ones. Each frame the volume produces a set of
+/// [`TriggerEvent`]s, that could be fetched using [`TriggerVolume::events`], and the engine
+/// delivers them to scripts as [`TriggerMessage`]s.
+///
+/// # Example
+///
+/// ```rust
+/// use fyrox::scene::{
+///     base::BaseBuilder,
+///     graph::Graph,
+///     trigger::{TriggerShape, TriggerVolumeBuilder},
+/// };
+///
+/// let mut graph = Graph::new();
+/// let trigger = TriggerVolumeBuilder::new(BaseBuilder::new().with_name("Finish"))
+///     .with_shape(TriggerShape::cuboid(2.0, 1.0, 2.0))
+///     .build(&mut graph);
+/// ```
+#[derive(Reflect, Visit, Debug)]
+pub struct TriggerVolume {
+    base: Base,
+
+    #[reflect(setter = "set_shape")]
+    pub(crate) shape: InheritableVariable<TriggerShape>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) native: Cell<ColliderHandle>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    touching: Vec<(Handle<Node>, Handle<Node>)>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    events: Vec<TriggerEvent>,
+}
+
+impl Default for TriggerVolume {
+    fn default() -> Self {
+        Self {
+            base: Default::default(),
+            shape: Default::default(),
+            native: Cell::new(ColliderHandle::invalid()),
+            touching: Default::default(),
+            events: Default::default(),
+        }
+    }
+}
+
+impl Clone for TriggerVolume {
+    fn clone(&self) -> Self {
+        Self {
+            base: self.base.clone(),
+            shape: self.shape.clone(),
+            // Do not copy. The copy will have its own native representation and its own set of
+            // intersecting colliders.
+            native: Cell::new(ColliderHandle::invalid()),
+            touching: Default::default(),
+            events: Default::default(),
+        }
+    }
+}
+
+impl Deref for TriggerVolume {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for TriggerVolume {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl TypeUuidProvider for TriggerVolume {
+    fn type_uuid() -> Uuid {
+        uuid!("0c6c4a04-3a39-4f0c-9b0c-1c5b8b7f2e61")
+    }
+}
+
+impl TriggerVolume {
+    /// Sets the new shape of the volume. Returns previous shape.
+    pub fn set_shape(&mut self, shape: TriggerShape) -> TriggerShape {
+        self.shape.set_value_and_mark_modified(shape)
+    }
+
+    /// Returns shared reference to the shape of the volume.
+    pub fn shape(&self) -> &TriggerShape {
+        &self.shape
+    }
+
+    /// Returns the events, that were produced by the volume during the last update of the graph.
+    pub fn events(&self) -> &[TriggerEvent] {
+        &self.events
+    }
+
+    /// Returns an iterator over handles of the colliders, that are currently inside the volume.
+    pub fn touching(&self) -> impl Iterator<Item = Handle<Node>> + '_ {
+        self.touching.iter().map(|(collider, _)| *collider)
+    }
+
+    /// Sends the events of the last update to scripts. See [`TriggerMessage`] docs for more info.
+    pub(crate) fn send_events(&self, self_handle: Handle<Node>, sender: &ScriptMessageSender) {
+        for event in self.events.iter() {
+            let message = TriggerMessage {
+                trigger: self_handle,
+                event: *event,
+            };
+            sender.send_to_target(self_handle, message);
+            sender.send_to_target(
+                if event.body.is_some() {
+                    event.body
+                } else {
+                    event.collider
+                },
+                message,
+            );
+        }
+    }
+}
+
+impl NodeTrait for TriggerVolume {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        match &*self.shape {
+            TriggerShape::Cuboid(cuboid) => {
+                AxisAlignedBoundingBox::from_min_max(-cuboid.half_extents, cuboid.half_extents)
+            }
+            TriggerShape::Ball(ball) => AxisAlignedBoundingBox::from_radius(ball.radius),
+        }
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.local_bounding_box()
+            .transform(&self.global_transform())
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn on_removed_from_graph(&mut self, graph: &mut Graph) {
+        graph.physics.remove_collider(self.native.get());
+        self.native.set(ColliderHandle::invalid());
+    }
+
+    fn sync_native(&self, self_handle: Handle<Node>, context: &mut SyncContext) {
+        context
+            .physics
+            .sync_to_trigger_volume_node(context.nodes, self_handle, self);
+    }
+
+    fn update(&mut self, context: &mut UpdateContext) {
+        self.events.clear();
+
+        let intersecting = context.physics.intersecting_colliders(self.native.get());
+
+        for (collider, body) in std::mem::take(&mut self.touching) {
+            if intersecting.contains(&collider) {
+                self.touching.push((collider, body));
+            } else {
+                self.events.push(TriggerEvent {
+                    kind: TriggerEventKind::Exit,
+                    collider,
+                    body,
+                });
+            }
+        }
+
+        for collider in intersecting {
+            if self.touching.iter().any(|(c, _)| *c == collider) {
+                continue;
+            }
+
+            let body = context
+                .nodes
+                .try_borrow(collider)
+                .map(|c| c.parent())
+                .filter(|parent| {
+                    context
+                        .nodes
+                        .try_borrow(*parent)
+                        .map_or(false, |p| p.cast::<RigidBody>().is_some())
+                })
+                .unwrap_or_default();
+
+            self.touching.push((collider, body));
+            self.events.push(TriggerEvent {
+                kind: TriggerEventKind::Enter,
+                collider,
+                body,
+            });
+        }
+    }
+
+    fn debug_draw(&self, ctx: &mut SceneDrawingContext) {
+        match &*self.shape {
+            TriggerShape::Cuboid(cuboid) => ctx.draw_oob(
+                &AxisAlignedBoundingBox::from_min_max(-cuboid.half_extents, cuboid.half_extents),
+                self.global_transform(),
+                Color::GREEN,
+            ),
+            TriggerShape::Ball(ball) => {
+                ctx.draw_wire_sphere(self.global_position(), ball.radius, 16, Color::GREEN)
+            }
+        }
+    }
+}
+
+/// Allows you to create trigger volumes in declarative manner.
+pub struct TriggerVolumeBuilder {
+    base_builder: BaseBuilder,
+    shape: TriggerShape,
+}
+
+impl TriggerVolumeBuilder {
+    /// Creates new trigger volume builder.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            shape: Default::default(),
+        }
+    }
+
+    /// Sets desired shape of the volume.
+    pub fn with_shape(mut self, shape: TriggerShape) -> Self {
+        self.shape = shape;
+        self
+    }
+
+    /// Creates new trigger volume node.
+    pub fn build_node(self) -> Node {
+        Node::new(TriggerVolume {
+            base: self.base_builder.build_base(),
+            shape: self.shape.into(),
+            ..Default::default()
+        })
+    }
+
+    /// Creates new trigger volume node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{algebra::Vector3, visitor::prelude::*},
+        scene::{
+            base::BaseBuilder,
+            collider::{ColliderBuilder, ColliderShape},
+            graph::Graph,
+            rigidbody::RigidBodyBuilder,
+            transform::TransformBuilder,
+            trigger::{
+                TriggerEventKind, TriggerMessage, TriggerShape, TriggerVolume, TriggerVolumeBuilder,
+            },
+        },
+        script::ScriptMessageSender,
+    };
+    use std::sync::mpsc::channel;
+
+    #[test]
+    fn test_trigger_volume_save_load() {
+        let mut volume = TriggerVolume::default();
+        volume.set_shape(TriggerShape::ball(1.5));
+
+        let mut visitor = Visitor::new();
+        volume.visit("Volume", &mut visitor).unwrap();
+        let data = visitor.save_binary_to_vec().unwrap();
+
+        let mut visitor = Visitor::load_from_memory(data).unwrap();
+        let mut loaded = TriggerVolume::default();
+        loaded.visit("Volume", &mut visitor).unwrap();
+
+        assert_eq!(loaded.shape(), &TriggerShape::ball(1.5));
+    }
+
+    #[test]
+    fn test_trigger_volume_enter_exit() {
+        let mut graph = Graph::new();
+
+        let trigger = TriggerVolumeBuilder::new(BaseBuilder::new())
+            .with_shape(TriggerShape::cuboid(1.0, 0.25, 1.0))
+            .build(&mut graph);
+
+        let collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::ball(0.25))
+            .build(&mut graph);
+        let body = RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, 1.0, 0.0))
+                        .build(),
+                )
+                .with_children(&[collider]),
+        )
+        .build(&mut graph);
+
+        let (tx, rx) = channel();
+        let sender = ScriptMessageSender { sender: tx };
+
+        let mut events = Vec::new();
+        for _ in 0..120 {
+            graph.update(Default::default(), 1.0 / 60.0, Default::default());
+            let volume = graph[trigger].cast::<TriggerVolume>().unwrap();
+            events.extend(volume.events().iter().cloned());
+            volume.send_events(trigger, &sender);
+        }
+
+        let kinds = events.iter().map(|e| e.kind).collect::<Vec<_>>();
+        assert_eq!(kinds, [TriggerEventKind::Enter, TriggerEventKind::Exit]);
+        assert!(events
+            .iter()
+            .all(|e| e.collider == collider && e.body == body));
+
+        // Each event is delivered to the volume and to the body.
+        let messages = rx
+            .try_iter()
+            .filter(|message| message.payload.downcast_ref::<TriggerMessage>().is_some())
+            .count();
+        assert_eq!(messages, 4);
+    }
+}
@@ -0,0 +1,257 @@
+//! Water is a flat reflective surface, that mirrors the scene above it. See [`Water`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Vector2, Vector3},
+        color::Color,
+        math::{aabb::AxisAlignedBoundingBox, plane::Plane},
+        pool::Handle,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        variable::InheritableVariable,
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    scene::{
+        base::{Base, BaseBuilder},
+        graph::Graph,
+        node::{Node, NodeTrait},
+    },
+};
+use std::ops::{Deref, DerefMut};
+
+/// Water is a flat rectangular surface, that renders a planar reflection of the scene. It does not require
+/// any custom shaders, the renderer renders the scene from a mirrored camera for every visible water surface
+/// and then draws the surface itself using the reflection, slightly distorted by animated waves and tinted
+/// by the color of the water.
+///
+/// The surface lies in XZ plane of the node and faces towards its Y axis, the size of the surface is defined
+/// in local coordinates, so it could also be changed by scaling the node.
+///
+/// # Performance
+///
+/// Each visible water surface requires the scene to be rendered one more time (without sprites, particles
+/// and other water surfaces), so keep the amount of simultaneously visible water surfaces low. The reflection
+/// is rendered in half resolution of the camera viewport.
+///
+/// # Example
+///
+/// ```rust
+/// use fyrox::{
+///     core::{algebra::Vector2, color::Color, pool::Handle},
+///     scene::{base::BaseBuilder, graph::Graph, node::Node, water::WaterBuilder},
+/// };
+///
+/// fn create_lake(graph: &mut Graph) -> Handle<Node> {
+///     WaterBuilder::new(BaseBuilder::new().with_name("Lake"))
+///         .with_size(Vector2::new(50.0, 30.0))
+///         .with_color(Color::from_rgba(20, 60, 80, 100))
+///         .with_distortion(0.02)
+///         .build(graph)
+/// }
+/// ```
+#[derive(Reflect, Visit, Debug, Clone)]
+pub struct Water {
+    base: Base,
+
+    #[reflect(setter = "set_size")]
+    size: InheritableVariable<Vector2<f32>>,
+
+    #[reflect(setter = "set_color")]
+    color: InheritableVariable<Color>,
+
+    #[reflect(min_value = 0.0, max_value = 0.5, step = 0.005)]
+    #[reflect(setter = "set_distortion")]
+    distortion: InheritableVariable<f32>,
+
+    #[reflect(min_value = 0.0, step = 0.1)]
+    #[reflect(setter = "set_wave_speed")]
+    wave_speed: InheritableVariable<f32>,
+}
+
+impl Deref for Water {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Water {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl Default for Water {
+    fn default() -> Self {
+        WaterBuilder::new(BaseBuilder::new()).build_water()
+    }
+}
+
+impl TypeUuidProvider for Water {
+    fn type_uuid() -> Uuid {
+        uuid!("3e4f7a1c-8b2d-4c6e-9f05-a7d1b3c5e924")
+    }
+}
+
+impl Water {
+    /// Sets new size of the surface (in local coordinates) along X and Z axes. Returns previous size.
+    pub fn set_size(&mut self, size: Vector2<f32>) -> Vector2<f32> {
+        self.size.set_value_and_mark_modified(size)
+    }
+
+    /// Returns current size of the surface (in local coordinates) along X and Z axes.
+    pub fn size(&self) -> Vector2<f32> {
+        *self.size
+    }
+
+    /// Sets new color of the water. Alpha channel defines how much the reflection is tinted by the color,
+    /// zero alpha means a clean mirror. Returns previous color.
+    pub fn set_color(&mut self, color: Color) -> Color {
+        self.color.set_value_and_mark_modified(color)
+    }
+
+    /// Returns current color of the water.
+    pub fn color(&self) -> Color {
+        *self.color
+    }
+
+    /// Sets new strength of the distortion of the reflection caused by waves. The value is a fraction of
+    /// the screen size, so small values (`0.01..0.05`) should be used. Returns previous value.
+    pub fn set_distortion(&mut self, distortion: f32) -> f32 {
+        self.distortion.set_value_and_mark_modified(distortion)
+    }
+
+    /// Returns current strength of the distortion of the reflection.
+    pub fn distortion(&self) -> f32 {
+        *self.distortion
+    }
+
+    /// Sets new speed of the waves. Returns previous value.
+    pub fn set_wave_speed(&mut self, speed: f32) -> f32 {
+        self.wave_speed.set_value_and_mark_modified(speed)
+    }
+
+    /// Returns current speed of the waves.
+    pub fn wave_speed(&self) -> f32 {
+        *self.wave_speed
+    }
+
+    /// Returns the plane of the surface in world coordinates. The normal of the plane points towards
+    /// the side that is reflected.
+    pub fn plane(&self) -> Plane {
+        Plane::from_normal_and_point(&self.up_vector(), &self.global_position()).unwrap_or_default()
+    }
+
+    /// Returns a matrix that mirrors world-space points relative to the plane of the surface.
+    pub fn reflection_matrix(&self) -> Matrix4<f32> {
+        let Plane { normal: n, d } = self.plane();
+
+        Matrix4::new(
+            1.0 - 2.0 * n.x * n.x,
+            -2.0 * n.x * n.y,
+            -2.0 * n.x * n.z,
+            -2.0 * d * n.x,
+            -2.0 * n.y * n.x,
+            1.0 - 2.0 * n.y * n.y,
+            -2.0 * n.y * n.z,
+            -2.0 * d * n.y,
+            -2.0 * n.z * n.x,
+            -2.0 * n.z * n.y,
+            1.0 - 2.0 * n.z * n.z,
+            -2.0 * d * n.z,
+            0.0,
+            0.0,
+            0.0,
+            1.0,
+        )
+    }
+}
+
+impl NodeTrait for Water {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        let half_size = self.size.scale(0.5);
+        AxisAlignedBoundingBox::from_min_max(
+            Vector3::new(-half_size.x, 0.0, -half_size.y),
+            Vector3::new(half_size.x, 0.0, half_size.y),
+        )
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.local_bounding_box()
+            .transform(&self.global_transform())
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+}
+
+/// Allows you to create water surfaces in declarative manner.
+pub struct WaterBuilder {
+    base_builder: BaseBuilder,
+    size: Vector2<f32>,
+    color: Color,
+    distortion: f32,
+    wave_speed: f32,
+}
+
+impl WaterBuilder {
+    /// Creates new water builder with default state (10x10 surface with a light blue tint).
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            size: Vector2::new(10.0, 10.0),
+            color: Color::from_rgba(40, 90, 120, 80),
+            distortion: 0.015,
+            wave_speed: 1.0,
+        }
+    }
+
+    /// Sets desired size of the surface along X and Z axes.
+    pub fn with_size(mut self, size: Vector2<f32>) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Sets desired color of the water.
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Sets desired strength of the distortion of the reflection.
+    pub fn with_distortion(mut self, distortion: f32) -> Self {
+        self.distortion = distortion;
+        self
+    }
+
+    /// Sets desired speed of the waves.
+    pub fn with_wave_speed(mut self, wave_speed: f32) -> Self {
+        self.wave_speed = wave_speed;
+        self
+    }
+
+    fn build_water(self) -> Water {
+        Water {
+            base: self.base_builder.build_base(),
+            size: self.size.into(),
+            color: self.color.into(),
+            distortion: self.distortion.into(),
+            wave_speed: self.wave_speed.into(),
+        }
+    }
+
+    /// Creates new water node.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_water())
+    }
+
+    /// Creates new water node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
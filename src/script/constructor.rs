@@ -37,21 +37,22 @@ impl ScriptConstructorContainer {
     ///
     /// # Panic
     ///
-    /// The method will panic if there is already a constructor for given type uuid.
+    /// The method will panic if there is already a constructor for given type uuid. It usually means
+    /// that two script types have the same type uuid (for example, after copy-pasting a script), which
+    /// breaks serialization. The panic message contains the names of both scripts.
     pub fn add<T>(&self, name: &str) -> &Self
     where
         T: TypeUuidProvider + ScriptTrait + Default,
     {
-        let old = self.map.lock().insert(
+        self.insert(
             T::type_uuid(),
             ScriptConstructor {
                 constructor: Box::new(|| Script::new(T::default())),
                 name: name.to_owned(),
             },
+            std::any::type_name::<T>(),
         );
 
-        assert!(old.is_none());
-
         self
     }
 
@@ -61,9 +62,21 @@ impl ScriptConstructorContainer {
     ///
     /// The method will panic if there is already a constructor for given type uuid.
     pub fn add_custom(&self, type_uuid: Uuid, constructor: ScriptConstructor) {
-        let old = self.map.lock().insert(type_uuid, constructor);
+        self.insert(type_uuid, constructor, "custom constructor");
+    }
+
+    fn insert(&self, type_uuid: Uuid, constructor: ScriptConstructor, type_name: &str) {
+        let mut map = self.map.lock();
+
+        if let Some(existing) = map.get(&type_uuid) {
+            panic!(
+                "Unable to register script {} ({}), because its type uuid {} is already used by \
+                 {} script! Each script type must have a unique type uuid.",
+                constructor.name, type_name, type_uuid, existing.name
+            );
+        }
 
-        assert!(old.is_none());
+        map.insert(type_uuid, constructor);
     }
 
     /// Unregisters type constructor.
@@ -5,26 +5,43 @@
 use crate::{
     asset::manager::ResourceManager,
     core::{
-        log::Log,
+        algebra::{Matrix4, Point3, Vector2, Vector3},
+        log::{Log, MessageKind},
         pool::Handle,
         reflect::{FieldInfo, Reflect, ReflectArray, ReflectList},
         uuid::Uuid,
-        visitor::{Visit, VisitResult, Visitor},
+        visitor::{Visit, VisitError, VisitResult, Visitor},
+        TypeUuidProvider,
     },
-    engine::ScriptMessageDispatcher,
+    engine::{window_info::WindowInfo, ScriptMessageDispatcher},
     event::Event,
-    plugin::Plugin,
-    scene::{node::Node, Scene},
+    plugin::{service::ServiceRegistry, Plugin},
+    scene::{
+        blackboard::BlackboardValue,
+        graph::Graph,
+        node::Node,
+        rng::SceneRng,
+        sound::{play_one_shot, play_one_shot_with_pitch_range, PitchRange, SoundBuffer},
+        Scene,
+    },
+    script::{
+        constructor::ScriptConstructorContainer,
+        task::{ScriptTaskPool, TaskContext},
+    },
     utils::component::ComponentProvider,
 };
 use std::{
     any::{Any, TypeId},
     fmt::{Debug, Formatter},
+    future::Future,
     ops::{Deref, DerefMut},
+    path::Path,
     sync::mpsc::Sender,
 };
 
 pub mod constructor;
+pub mod save_game;
+pub mod task;
 
 /// A script message's payload.
 pub trait ScriptMessagePayload: Any + Send {
@@ -33,6 +50,9 @@ pub trait ScriptMessagePayload: Any + Send {
 
     /// Returns `self` as `&dyn Any`
     fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    /// Converts boxed `self` into `Box<dyn Any>`.
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
 }
 
 impl dyn ScriptMessagePayload {
@@ -58,6 +78,10 @@ where
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self
     }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
 }
 
 /// Defines how a script message will be delivered for each node in a hierarchy.
@@ -97,6 +121,30 @@ pub enum ScriptMessageKind {
     Global,
 }
 
+impl ScriptMessageKind {
+    /// Checks whether a message of this kind is addressed to the given node.
+    pub(crate) fn is_routed_to(&self, node: Handle<Node>, graph: &Graph) -> bool {
+        let is_ancestor_or_self = |ancestor: Handle<Node>, mut handle: Handle<Node>| {
+            while let Some(node_ref) = graph.try_get(handle) {
+                if handle == ancestor {
+                    return true;
+                }
+                handle = node_ref.parent();
+            }
+            false
+        };
+
+        match self {
+            ScriptMessageKind::Targeted(target) => *target == node,
+            ScriptMessageKind::Hierarchical { root, routing } => match routing {
+                RoutingStrategy::Up => is_ancestor_or_self(node, *root),
+                RoutingStrategy::Down => is_ancestor_or_self(*root, node),
+            },
+            ScriptMessageKind::Global => true,
+        }
+    }
+}
+
 /// A script message sender.
 #[derive(Clone)]
 pub struct ScriptMessageSender {
@@ -180,11 +228,31 @@ where
     }
 }
 
+/// The closest intersection of a ray with a collider, see [`ScriptContext::ray_cast`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RayHit {
+    /// A handle of the collider node, that was hit by the ray.
+    pub node: Handle<Node>,
+    /// A position of the hit in world coordinates.
+    pub position: Vector3<f32>,
+    /// A normal of the collider surface at the hit position.
+    pub normal: Vector3<f32>,
+    /// Distance from the ray origin to the hit position.
+    pub distance: f32,
+}
+
 /// A set of data, that provides contextual information for script methods.
 pub struct ScriptContext<'a, 'b, 'c> {
     /// Amount of time that passed from last call. It has valid values only when called from `on_update`.
+    /// The value is clamped to [`crate::engine::Engine::max_dt`], so a frame spike won't cause objects
+    /// to teleport or physics to explode. Keep in mind, that it still could be zero (for example, if
+    /// the game is paused), so check it before dividing by it.
     pub dt: f32,
 
+    /// The same as [`Self::dt`], but in double precision. Use it for time accumulators, that are
+    /// updated for a long time and will lose precision with single-precision floats.
+    pub dt_f64: f64,
+
     /// Amount of time (in seconds) that passed from creation of the engine. Keep in mind, that
     /// this value is **not** guaranteed to match real time. A user can change delta time with
     /// which the engine "ticks" and this delta time affects elapsed time.
@@ -212,6 +280,10 @@ pub struct ScriptContext<'a, 'b, 'c> {
     /// A reference to resource manager, use it to load resources.
     pub resource_manager: &'a ResourceManager,
 
+    /// Actual parameters of the main window, they're updated when the window is resized or moved to a
+    /// monitor with different DPI. See [`WindowInfo`] docs for more info.
+    pub window_info: &'a WindowInfo,
+
     /// An message sender. Every message sent via this sender will be then passed to every [`ScriptTrait::on_message`]
     /// method of every script.
     pub message_sender: &'c ScriptMessageSender,
@@ -219,6 +291,251 @@ pub struct ScriptContext<'a, 'b, 'c> {
     /// A message dispatcher. If you need to receive messages of a particular type, you must subscribe to a type
     /// explicitly. See [`ScriptTrait::on_message`] for more examples.
     pub message_dispatcher: &'c mut ScriptMessageDispatcher,
+
+    /// A pool of tasks of the scene. Use [`Self::spawn_task`] to spawn a new task that belongs to the node of the
+    /// script instance.
+    pub task_pool: &'c mut ScriptTaskPool,
+
+    /// A registry of shared services, that were registered by plugins. See [`ServiceRegistry`] docs for more
+    /// info.
+    pub services: &'a mut ServiceRegistry,
+}
+
+impl<'a, 'b, 'c> ScriptContext<'a, 'b, 'c> {
+    /// Returns size of the client area of the main window in physical pixels.
+    pub fn window_size(&self) -> Vector2<f32> {
+        self.window_info.size
+    }
+
+    /// Returns DPI scale factor of the main window.
+    pub fn scale_factor(&self) -> f32 {
+        self.window_info.scale_factor
+    }
+
+    /// Drains every queued message of the given type, that is addressed to the node of the script, and returns
+    /// them in the order they were sent. Use it when order of processing or batching matters, for example to
+    /// process all hits of a frame at once. Messages that were not drained are removed one frame after they
+    /// were sent.
+    ///
+    /// A message is addressed to the node, if the node is the target of a targeted message, if it is on the
+    /// path of a hierarchical message, or if the message is global. Every message could be drained only once,
+    /// so a global message is taken by the first script that drains it.
+    ///
+    /// # Important notes
+    ///
+    /// Only messages of types, that no script is subscribed to (see [`ScriptTrait::on_message`]), are queued.
+    /// Subscription and draining are mutually exclusive for a message type - draining a type that has
+    /// subscribers yields nothing and logs a warning.
+    ///
+    /// ```rust
+    /// # use fyrox::script::ScriptContext;
+    /// struct Damage(f32);
+    ///
+    /// # fn on_update(ctx: &mut ScriptContext) {
+    /// let total_damage = ctx.messages::<Damage>().map(|damage| damage.0).sum::<f32>();
+    /// # }
+    /// ```
+    pub fn messages<T: 'static>(&mut self) -> impl Iterator<Item = T> {
+        self.message_dispatcher
+            .drain::<T>(self.handle, &self.scene.graph)
+    }
+
+    /// Returns a reference to a service of the given type, registered by a plugin (if any).
+    ///
+    /// ```rust
+    /// # use fyrox::script::ScriptContext;
+    /// struct Score(u32);
+    ///
+    /// # fn on_coin_collected(ctx: &mut ScriptContext) {
+    /// if let Some(score) = ctx.service_mut::<Score>() {
+    ///     score.0 += 1;
+    /// }
+    /// # }
+    /// ```
+    pub fn service<T: Any>(&self) -> Option<&T> {
+        self.services.get::<T>()
+    }
+
+    /// Returns a reference to a service of the given type, registered by a plugin (if any).
+    pub fn service_mut<T: Any>(&mut self) -> Option<&mut T> {
+        self.services.get_mut::<T>()
+    }
+
+    /// Plays a sound from the given path once at the given world-space position. The sound buffer is loaded
+    /// on first use and then reused from the resource manager's cache. The sound node removes itself when
+    /// the playback is finished. Returns a handle to the sound node.
+    ///
+    /// ```rust
+    /// # use fyrox::script::ScriptContext;
+    /// # fn on_jump(ctx: &mut ScriptContext) {
+    /// let position = ctx.scene.graph[ctx.handle].global_position();
+    /// ctx.play_sound_3d("data/sounds/boing.wav", position, 1.0);
+    /// # }
+    /// ```
+    pub fn play_sound_3d<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        position: Vector3<f32>,
+        gain: f32,
+    ) -> Handle<Node> {
+        let buffer = self.resource_manager.request::<SoundBuffer, _>(path);
+        play_one_shot(&mut self.scene.graph, buffer, Some(position), gain)
+    }
+
+    /// Plays a non-spatial sound from the given path once. It works the same as [`Self::play_sound_3d`], but
+    /// the sound is heard the same regardless of the listener position.
+    pub fn play_sound_2d<P: AsRef<Path>>(&mut self, path: P, gain: f32) -> Handle<Node> {
+        let buffer = self.resource_manager.request::<SoundBuffer, _>(path);
+        play_one_shot(&mut self.scene.graph, buffer, None, gain)
+    }
+
+    /// Same as [`Self::play_sound_3d`], but the sound is played with a random pitch from the given range. The pitch
+    /// is picked using the seeded generator of the scene (see [`Self::rng`]), so it is reproducible. Use it for
+    /// repeated sounds, such as footsteps, to make them less monotonous.
+    pub fn play_sound_3d_with_pitch_range<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        position: Vector3<f32>,
+        gain: f32,
+        pitch_range: PitchRange,
+    ) -> Handle<Node> {
+        let buffer = self.resource_manager.request::<SoundBuffer, _>(path);
+        play_one_shot_with_pitch_range(
+            &mut self.scene.graph,
+            buffer,
+            Some(position),
+            gain,
+            pitch_range,
+            &mut self.scene.rng,
+        )
+    }
+
+    /// Returns seeded pseudo-random numbers generator of the scene. Use it for gameplay randomness to keep the game
+    /// reproducible, see [`SceneRng`] docs for more info.
+    pub fn rng(&mut self) -> &mut SceneRng {
+        &mut self.scene.rng
+    }
+
+    /// Returns a reference to a value of the given type from the blackboard of the scene. If there's no such value,
+    /// a default one will be created. Use it to share mutable state (game score, team data, etc.) between scripts
+    /// of the scene. See [`crate::scene::blackboard::SceneBlackboard`] for more info.
+    pub fn scene_data<T>(&mut self) -> &mut T
+    where
+        T: BlackboardValue + TypeUuidProvider + Default,
+    {
+        self.scene.blackboard.get_or_default::<T>()
+    }
+
+    /// Spawns a new coroutine-style task, that belongs to the node of the script instance. The task will be resumed
+    /// once per frame until it is finished. If the node is removed, the task will be cancelled. See
+    /// [`ScriptTaskPool`] docs for more info.
+    pub fn spawn_task<F, Fut>(&mut self, func: F)
+    where
+        F: FnOnce(TaskContext) -> Fut,
+        Fut: Future<Output = ()> + 'static,
+    {
+        self.task_pool
+            .spawn(self.handle, self.message_sender.clone(), func)
+    }
+
+    /// Casts a ray in the 3D physics world of the scene and returns the closest hit, or `None` if the ray
+    /// didn't hit any collider within `max_len` distance. The direction does not need to be normalized.
+    ///
+    /// The ray ignores sensors (including trigger volumes) and the colliders of the rigid body the script
+    /// belongs to - it is either the node of the script itself or its closest rigid body ancestor. This way
+    /// a ray cast from the inside of a character won't hit the character. Use
+    /// [`crate::scene::graph::physics::PhysicsWorld::cast_ray`] directly, if you need every hit or a filter
+    /// by collision groups.
+    ///
+    /// ```rust
+    /// # use fyrox::{core::algebra::Vector3, script::ScriptContext};
+    /// // The script is assigned to a rigid body of a character with a capsule collider, whose half
+    /// // height is 1.0.
+    /// fn is_grounded(ctx: &ScriptContext) -> bool {
+    ///     let position = ctx.scene.graph[ctx.handle].global_position();
+    ///     ctx.ray_cast(position, -Vector3::y(), 1.1).is_some()
+    /// }
+    /// ```
+    pub fn ray_cast(
+        &self,
+        origin: Vector3<f32>,
+        direction: Vector3<f32>,
+        max_len: f32,
+    ) -> Option<RayHit> {
+        let exclude_body = self
+            .scene
+            .graph
+            .find_up(self.handle, &mut |node| node.is_rigid_body())
+            .map(|(_, node)| node.as_rigid_body().native.get());
+
+        self.scene
+            .graph
+            .physics
+            .cast_ray_closest_solid(Point3::from(origin), direction, max_len, exclude_body)
+            .map(|intersection| RayHit {
+                node: intersection.collider,
+                position: intersection.position.coords,
+                normal: intersection.normal,
+                distance: intersection.toi,
+            })
+    }
+
+    /// Returns global transform of the node of the script instance, blended between the two last physics
+    /// states. Use it instead of the node's global transform for visuals that follow physics (camera, UI
+    /// markers, etc.), when the physics is stepped with fixed time step - this way the motion will be smooth
+    /// regardless of the frame rate. See
+    /// [`crate::scene::graph::Graph::interpolated_global_transform`] for more info.
+    ///
+    /// ```rust
+    /// # use fyrox::script::ScriptContext;
+    /// # use fyrox::core::algebra::Vector3;
+    /// # fn camera_target(ctx: &ScriptContext) -> Vector3<f32> {
+    /// let transform = ctx.interpolated_transform();
+    /// Vector3::new(transform[12], transform[13], transform[14])
+    /// # }
+    /// ```
+    pub fn interpolated_transform(&self) -> Matrix4<f32> {
+        self.scene.graph.interpolated_global_transform(self.handle)
+    }
+
+    /// Returns an iterator over handles of the nodes whose world positions are within the given radius around
+    /// the node of the script instance (the node itself is excluded). The query uses spatial hash of the graph,
+    /// so it is cheap even for large scenes, see [`crate::scene::graph::spatial_hash::SpatialHash`] docs for
+    /// more info.
+    ///
+    /// ```rust
+    /// # use fyrox::script::ScriptContext;
+    /// # fn crowd_size(ctx: &mut ScriptContext) -> usize {
+    /// ctx.query_neighbors(5.0).count()
+    /// # }
+    /// ```
+    pub fn query_neighbors(&mut self, radius: f32) -> impl Iterator<Item = Handle<Node>> + '_ {
+        let handle = self.handle;
+        let center = self.scene.graph[handle].global_position();
+        self.scene
+            .graph
+            .spatial_hash()
+            .query(center, radius)
+            .filter(move |neighbor| *neighbor != handle)
+    }
+
+    /// Writes an information message to the log. The message is prefixed with the name of the node the script
+    /// instance belongs to, so it is easy to find the source of the message. The log is shown in the editor's
+    /// message log panel.
+    pub fn log<S: AsRef<str>>(&self, message: S) {
+        self.log_with_kind(MessageKind::Information, message)
+    }
+
+    /// Same as [`Self::log`], but allows you to specify the severity of the message.
+    pub fn log_with_kind<S: AsRef<str>>(&self, kind: MessageKind, message: S) {
+        let name = self
+            .scene
+            .graph
+            .try_get(self.handle)
+            .map(|node| node.name())
+            .unwrap_or_default();
+        Log::writeln(kind, format!("[{}] {}", name, message.as_ref()));
+    }
 }
 
 /// A set of data, that provides contextual information for script methods.
@@ -288,6 +605,40 @@ pub struct ScriptDeinitContext<'a, 'b, 'c> {
 
 /// Script is a set predefined methods that are called on various stages by the engine. It is used to add
 /// custom behaviour to game entities.
+///
+/// # Serialization and editing
+///
+/// Every field of a script is saved and shown in the editor's inspector by default. `#[visit(skip)]`
+/// excludes a field from serialization, `#[reflect(hidden)]` hides it from the inspector. A field
+/// with `#[reflect(hidden)]` only is still saved and loaded, which is useful for internal state,
+/// that must survive save/load, but must not be edited by hand:
+///
+/// ```rust
+/// # use fyrox::{
+/// #     core::{reflect::prelude::*, uuid::Uuid, visitor::prelude::*},
+/// #     impl_component_provider,
+/// #     script::ScriptTrait,
+/// # };
+/// #[derive(Reflect, Visit, Debug, Clone, Default)]
+/// struct Player {
+///     // Saved and editable.
+///     speed: f32,
+///     // Saved, but hidden from the inspector.
+///     #[reflect(hidden)]
+///     jump_count: u32,
+///     // Neither saved nor editable.
+///     #[visit(skip)]
+///     #[reflect(hidden)]
+///     is_jumping: bool,
+/// }
+///
+/// # impl_component_provider!(Player);
+/// # impl ScriptTrait for Player {
+/// #     fn id(&self) -> Uuid {
+/// #         Uuid::default()
+/// #     }
+/// # }
+/// ```
 pub trait ScriptTrait: BaseScript + ComponentProvider {
     /// The method is called when the script wasn't initialized yet. It is guaranteed to be called once,
     /// and before any other methods of the script.
@@ -319,9 +670,23 @@ pub trait ScriptTrait: BaseScript + ComponentProvider {
     ) {
     }
 
+    /// Performs a single fixed update tick of the script. The method is aligned with the physics of the
+    /// scene: when its fixed time step is enabled (see [`crate::scene::graph::Graph::physics_time_step`]),
+    /// the method is called once per physics step, that was performed on the current frame (it could be
+    /// zero or multiple times per frame) and [`ScriptContext::dt`] is equal to the duration of the step.
+    /// Otherwise, the physics is stepped once per frame and so is the method, with the frame time step.
+    /// All physics steps of a frame are performed before the fixed updates, which are performed right
+    /// before [`Self::on_update`]. Use this method for gameplay code that applies forces or impulses, so
+    /// it does not depend on frame rate.
+    fn on_fixed_update(&mut self, #[allow(unused_variables)] ctx: &mut ScriptContext) {}
+
     /// Performs a single update tick of the script. The method may be called multiple times per
     /// frame, but it is guaranteed that the rate of call is stable and usually it will be called
     /// 60 times per second (this may change in future releases).
+    ///
+    /// A typical update changes both the typed data and the transform of the node of the script.
+    /// Cast the node once (`ctx.scene.graph[ctx.handle].cast_mut::<RigidBody>()`) and access the
+    /// transform through the typed reference, see [`crate::scene::node::Node::cast_mut`] for details.
     fn on_update(&mut self, #[allow(unused_variables)] ctx: &mut ScriptContext) {}
 
     /// Allows you to react to certain script messages. It could be used for communication between scripts; to
@@ -600,17 +965,81 @@ impl Script {
     }
 }
 
+/// Serialized state of a script instance. It is used to carry the state of scripts through code reloading
+/// (for example, when a game plugin is recompiled): capture the state of every live script before the code
+/// is unloaded and restore it after the new code is loaded. Restoration is done field-by-field, a field
+/// keeps its value only if the new version of the script has a field with the same name and type.
+#[derive(Debug, Clone)]
+pub struct ScriptState {
+    type_uuid: Uuid,
+    data: Vec<u8>,
+}
+
+impl ScriptState {
+    const DATA: &'static str = "Data";
+
+    /// Serializes current state of the given script.
+    pub fn capture(script: &mut Script) -> Result<Self, VisitError> {
+        let mut visitor = Visitor::new();
+        script.instance.visit(Self::DATA, &mut visitor)?;
+        Ok(Self {
+            type_uuid: script.id(),
+            data: visitor.save_binary_to_vec()?,
+        })
+    }
+
+    /// Returns type UUID of the script the state was captured from.
+    pub fn type_uuid(&self) -> Uuid {
+        self.type_uuid
+    }
+
+    /// Creates new instance of the script using the given set of constructors and restores the values of
+    /// its fields from the captured state. Fields that were added or changed their types will have default
+    /// values. Returns `Ok(None)` if there is no constructor for the script type anymore.
+    pub fn restore(
+        &self,
+        constructors: &ScriptConstructorContainer,
+    ) -> Result<Option<Script>, VisitError> {
+        let mut script = match constructors.try_create(&self.type_uuid) {
+            Some(script) => script,
+            None => return Ok(None),
+        };
+
+        let old_state = Visitor::load_from_memory(self.data.clone())?;
+
+        // Write default state of the new version of the script first and then patch it with compatible
+        // data from the old state, this way missing or changed fields will keep their default values.
+        let mut new_state = Visitor::new();
+        script.instance.visit(Self::DATA, &mut new_state)?;
+        new_state.merge_compatible(&old_state);
+
+        let mut new_state = Visitor::load_from_memory(new_state.save_binary_to_vec()?)?;
+        script.instance.visit(Self::DATA, &mut new_state)?;
+
+        Ok(Some(script))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::{
         core::{
-            reflect::prelude::*, uuid::Uuid, variable::try_inherit_properties,
-            variable::InheritableVariable, visitor::prelude::*,
+            reflect::prelude::*,
+            uuid::{uuid, Uuid},
+            variable::try_inherit_properties,
+            variable::InheritableVariable,
+            visitor::prelude::*,
+            TypeUuidProvider,
         },
         impl_component_provider,
-        scene::base::Base,
-        script::{Script, ScriptTrait},
+        scene::{
+            base::{Base, BaseBuilder},
+            graph::{event::GraphEvent, Graph},
+            pivot::PivotBuilder,
+        },
+        script::{constructor::ScriptConstructorContainer, Script, ScriptState, ScriptTrait},
     };
+    use std::sync::mpsc::channel;
 
     #[derive(Reflect, Visit, Debug, Clone, Default)]
     struct MyScript {
@@ -691,4 +1120,187 @@ mod test {
             3.21
         );
     }
+
+    const PLAYER_UUID: Uuid = uuid!("e9d2d1a4-1f2a-4c27-9b6e-6a3f3fbd2a0c");
+
+    #[derive(Reflect, Visit, Debug, Clone, Default)]
+    struct Player {
+        speed: f32,
+        yaw: f32,
+    }
+
+    impl_component_provider!(Player);
+
+    impl TypeUuidProvider for Player {
+        fn type_uuid() -> Uuid {
+            PLAYER_UUID
+        }
+    }
+
+    impl ScriptTrait for Player {
+        fn id(&self) -> Uuid {
+            Self::type_uuid()
+        }
+    }
+
+    // "Recompiled" version of the player script: `yaw` changed its type and there is a new field.
+    #[derive(Reflect, Visit, Debug, Clone)]
+    struct ReloadedPlayer {
+        speed: f32,
+        yaw: String,
+        jump_height: f32,
+    }
+
+    impl Default for ReloadedPlayer {
+        fn default() -> Self {
+            Self {
+                speed: 0.2,
+                yaw: "default".to_string(),
+                jump_height: 1.0,
+            }
+        }
+    }
+
+    impl_component_provider!(ReloadedPlayer);
+
+    impl TypeUuidProvider for ReloadedPlayer {
+        fn type_uuid() -> Uuid {
+            PLAYER_UUID
+        }
+    }
+
+    impl ScriptTrait for ReloadedPlayer {
+        fn id(&self) -> Uuid {
+            Self::type_uuid()
+        }
+    }
+
+    #[test]
+    fn test_script_state_is_preserved_on_reload() {
+        let mut script = Script::new(Player {
+            speed: 0.75,
+            yaw: 1.5,
+        });
+
+        let state = ScriptState::capture(&mut script).unwrap();
+        assert_eq!(state.type_uuid(), PLAYER_UUID);
+
+        // Simulate code reloading by registering the new version of the script using the same UUID.
+        let constructors = ScriptConstructorContainer::new();
+        constructors.add::<ReloadedPlayer>("Player");
+
+        let restored = state.restore(&constructors).unwrap().unwrap();
+        let restored = restored.cast::<ReloadedPlayer>().unwrap();
+        assert_eq!(restored.speed, 0.75);
+        assert_eq!(restored.yaw, "default");
+        assert_eq!(restored.jump_height, 1.0);
+
+        assert!(state
+            .restore(&ScriptConstructorContainer::new())
+            .unwrap()
+            .is_none());
+    }
+
+    const JUMPER_UUID: Uuid = uuid!("2d7ec5ab-3a6b-4b3f-8d8e-0c5a6f0e9b41");
+
+    #[derive(Reflect, Visit, Debug, Clone, Default)]
+    struct Jumper {
+        period: f32,
+    }
+
+    impl_component_provider!(Jumper);
+
+    impl ScriptTrait for Jumper {
+        fn id(&self) -> Uuid {
+            JUMPER_UUID
+        }
+    }
+
+    #[test]
+    fn test_script_changed_event() {
+        let mut graph = Graph::new();
+        let node =
+            PivotBuilder::new(BaseBuilder::new().with_script(Script::new(Player::default())))
+                .build(&mut graph);
+
+        let (tx, rx) = channel();
+        graph.event_broadcaster.subscribe(tx);
+
+        graph[node].set_script(Some(Script::new(Jumper::default())));
+        assert_eq!(
+            rx.try_recv(),
+            Ok(GraphEvent::ScriptChanged {
+                node,
+                old_uuid: Some(PLAYER_UUID),
+                new_uuid: Some(JUMPER_UUID),
+            })
+        );
+
+        graph[node].set_script(None);
+        assert_eq!(
+            rx.try_recv(),
+            Ok(GraphEvent::ScriptChanged {
+                node,
+                old_uuid: Some(JUMPER_UUID),
+                new_uuid: None,
+            })
+        );
+
+        // Nothing has changed.
+        graph[node].set_script(None);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "Unable to register script Jumper")]
+    fn test_duplicate_script_uuid_is_rejected() {
+        let constructors = ScriptConstructorContainer::new();
+        constructors.add::<Player>("Player");
+        // Copy-pasted script with the same type uuid.
+        constructors.add::<ReloadedPlayer>("Jumper");
+    }
+
+    #[derive(Reflect, Visit, Debug, Clone, Default)]
+    struct Latch {
+        speed: f32,
+        #[reflect(hidden)]
+        latched: bool,
+    }
+
+    impl_component_provider!(Latch);
+
+    impl TypeUuidProvider for Latch {
+        fn type_uuid() -> Uuid {
+            uuid!("7b1f0c9e-64a5-4d0e-a0a6-3b0c8e1f2d57")
+        }
+    }
+
+    impl ScriptTrait for Latch {
+        fn id(&self) -> Uuid {
+            Self::type_uuid()
+        }
+    }
+
+    #[test]
+    fn test_hidden_script_field_is_serialized() {
+        let mut script = Script::new(Latch {
+            speed: 2.0,
+            latched: true,
+        });
+
+        // Hidden from the inspector...
+        script.fields_info(&mut |fields| {
+            assert!(fields.iter().any(|f| f.name == "speed"));
+            assert!(fields.iter().all(|f| f.name != "latched"));
+        });
+
+        // ...but still saved and loaded.
+        let state = ScriptState::capture(&mut script).unwrap();
+        let constructors = ScriptConstructorContainer::new();
+        constructors.add::<Latch>("Latch");
+        let restored = state.restore(&constructors).unwrap().unwrap();
+        let restored = restored.cast::<Latch>().unwrap();
+        assert_eq!(restored.speed, 2.0);
+        assert!(restored.latched);
+    }
 }
@@ -0,0 +1,318 @@
+//! Save games, see [`SaveGame`] docs for more info.
+
+use crate::{
+    core::{
+        pool::Handle,
+        visitor::{Visit, VisitError, VisitResult, Visitor},
+    },
+    scene::{graph::Graph, node::Node},
+};
+use std::path::{Path, PathBuf};
+
+/// Save game is a set of values that persist between game sessions, such as the state of the player,
+/// the state of scripts of some nodes or any other data. The values are stored in named slots on disk,
+/// each slot is a separate file in a directory.
+///
+/// # Usage
+///
+/// Save game uses [`Visit`] trait for serialization, so the same code is used to save and to load
+/// the state: create an empty save game with [`Self::new`] and visit the values to save them, or
+/// load an existing save game with [`Self::load`] and visit the same values to restore them.
+///
+/// ```rust
+/// use fyrox::{
+///     core::{pool::Handle, visitor::prelude::*},
+///     scene::{graph::Graph, node::Node},
+///     script::save_game::SaveGame,
+/// };
+///
+/// fn visit_state(
+///     save_game: &mut SaveGame,
+///     level: &mut u32,
+///     graph: &mut Graph,
+///     player: Handle<Node>,
+/// ) -> VisitResult {
+///     save_game.visit_value("Level", level)?;
+///     save_game.visit_node(graph, player)
+/// }
+///
+/// fn save(level: &mut u32, graph: &mut Graph, player: Handle<Node>) -> VisitResult {
+///     let mut save_game = SaveGame::new();
+///     visit_state(&mut save_game, level, graph, player)?;
+///     save_game.save("saves", "quick")
+/// }
+///
+/// fn load(level: &mut u32, graph: &mut Graph, player: Handle<Node>) -> VisitResult {
+///     // Missing slot is not an error, there is just nothing to load.
+///     if let Some(mut save_game) = SaveGame::load("saves", "quick")? {
+///         visit_state(&mut save_game, level, graph, player)?;
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct SaveGame {
+    visitor: Visitor,
+}
+
+impl Default for SaveGame {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SaveGame {
+    /// Extension of save game files.
+    pub const EXTENSION: &'static str = "save";
+
+    /// Creates new empty save game, every visited value will be written into it.
+    pub fn new() -> Self {
+        Self {
+            visitor: Visitor::new(),
+        }
+    }
+
+    /// Returns `true` if the save game was loaded from a slot and visited values are read from it.
+    pub fn is_loading(&self) -> bool {
+        self.visitor.is_reading()
+    }
+
+    /// Writes the value to the save game or reads it back, depending on the mode of the save game.
+    pub fn visit_value<T: Visit + ?Sized>(&mut self, name: &str, value: &mut T) -> VisitResult {
+        value.visit(name, &mut self.visitor)
+    }
+
+    /// Writes the local transform and the state of the script of the given node to the save game or
+    /// reads them back, depending on the mode of the save game. The node is identified by its stable
+    /// id (see [`crate::scene::base::NodeId`]), so the state could be loaded into another instance of
+    /// the same scene (for example, when a level is loaded from its file again). Names of the nodes
+    /// do not matter.
+    pub fn visit_node(&mut self, graph: &mut Graph, handle: Handle<Node>) -> VisitResult {
+        let node = graph
+            .try_get_mut(handle)
+            .ok_or_else(|| VisitError::User(format!("Node {} does not exist!", handle)))?;
+
+        let mut region = self.visitor.enter_region(&node.node_id().0.to_string())?;
+
+        let transform = node.local_transform_mut();
+        let mut position = **transform.position();
+        let mut rotation = **transform.rotation();
+        let mut scale = **transform.scale();
+        position.visit("Position", &mut region)?;
+        rotation.visit("Rotation", &mut region)?;
+        scale.visit("Scale", &mut region)?;
+        if region.is_reading() {
+            transform
+                .set_position(position)
+                .set_rotation(rotation)
+                .set_scale(scale);
+        }
+
+        if let Some(script) = node.script_mut() {
+            // Visit the instance only, initialization flags of the script must not be restored.
+            script.instance.visit("Script", &mut region)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns a path of the file of the given slot in the given directory.
+    pub fn slot_path<P: AsRef<Path>>(directory: P, slot: &str) -> PathBuf {
+        directory
+            .as_ref()
+            .join(slot)
+            .with_extension(Self::EXTENSION)
+    }
+
+    /// Returns `true` if the given slot exists in the given directory.
+    pub fn has_slot<P: AsRef<Path>>(directory: P, slot: &str) -> bool {
+        Self::slot_path(directory, slot).exists()
+    }
+
+    /// Writes the save game to the given slot, the directory will be created if it does not exist.
+    /// Existing slot will be overwritten.
+    pub fn save<P: AsRef<Path>>(&self, directory: P, slot: &str) -> VisitResult {
+        if self.is_loading() {
+            return Err(VisitError::User(
+                "Save game must be in write mode!".to_string(),
+            ));
+        }
+
+        std::fs::create_dir_all(directory.as_ref())?;
+        self.visitor.save_binary(Self::slot_path(directory, slot))
+    }
+
+    /// Loads the save game from the given slot. Returns `Ok(None)` if there is no such slot.
+    pub fn load<P: AsRef<Path>>(directory: P, slot: &str) -> Result<Option<Self>, VisitError> {
+        let path = Self::slot_path(directory, slot);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        Ok(Some(Self {
+            visitor: Visitor::load_from_memory(std::fs::read(path)?)?,
+        }))
+    }
+
+    /// Deletes the given slot. Does nothing if there is no such slot.
+    pub fn delete_slot<P: AsRef<Path>>(directory: P, slot: &str) -> std::io::Result<()> {
+        let path = Self::slot_path(directory, slot);
+        if path.exists() {
+            std::fs::remove_file(path)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            algebra::Vector3,
+            pool::Handle,
+            reflect::prelude::*,
+            uuid::{uuid, Uuid},
+            visitor::prelude::*,
+            TypeUuidProvider,
+        },
+        impl_component_provider,
+        scene::{
+            base::BaseBuilder, graph::Graph, node::Node, pivot::PivotBuilder,
+            transform::TransformBuilder,
+        },
+        script::{save_game::SaveGame, Script, ScriptTrait},
+    };
+    use std::path::Path;
+
+    #[derive(Reflect, Visit, Debug, Clone, Default, PartialEq)]
+    struct Player {
+        health: f32,
+        coins: u32,
+        weapons: Vec<String>,
+    }
+
+    impl_component_provider!(Player);
+
+    impl TypeUuidProvider for Player {
+        fn type_uuid() -> Uuid {
+            uuid!("3b8c8d6c-52a3-4b6a-9a43-7c1ef3a0b3d1")
+        }
+    }
+
+    impl ScriptTrait for Player {
+        fn id(&self) -> Uuid {
+            Self::type_uuid()
+        }
+    }
+
+    fn make_node(graph: &mut Graph, player: Player, position: Vector3<f32>) -> Handle<Node> {
+        // The same name for every node, nodes are identified by their ids.
+        PivotBuilder::new(
+            BaseBuilder::new()
+                .with_name("Player")
+                .with_script(Script::new(player))
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(position)
+                        .build(),
+                ),
+        )
+        .build(graph)
+    }
+
+    #[test]
+    fn test_save_and_load_player_state() {
+        let directory = Path::new("test_output/save_games");
+
+        // Missing slots are handled gracefully.
+        SaveGame::delete_slot(directory, "slot1").unwrap();
+        assert!(!SaveGame::has_slot(directory, "slot1"));
+        assert!(SaveGame::load(directory, "slot1").unwrap().is_none());
+
+        let saved_player = Player {
+            health: 42.5,
+            coins: 123,
+            weapons: vec!["Sword".to_string(), "Bow".to_string()],
+        };
+        let saved_position = Vector3::new(1.0, 2.0, 3.0);
+        let mut saved_level = 7u32;
+
+        // A level as it is stored in its file.
+        let mut level_file = Graph::new();
+        let player = make_node(&mut level_file, Player::default(), Vector3::default());
+        let other_player = make_node(&mut level_file, Player::default(), Vector3::default());
+        let load_level = || {
+            let root = level_file.get_root();
+            let (graph, map) = level_file.clone(root, &mut |_, _| true);
+            (graph, map.map[&player], map.map[&other_player])
+        };
+
+        let (mut graph, player_instance, other_player_instance) = load_level();
+        *graph[player_instance]
+            .script_mut()
+            .unwrap()
+            .cast_mut::<Player>()
+            .unwrap() = saved_player.clone();
+        graph[player_instance]
+            .local_transform_mut()
+            .set_position(saved_position);
+
+        let mut save_game = SaveGame::new();
+        save_game.visit_value("Level", &mut saved_level).unwrap();
+        save_game.visit_node(&mut graph, player_instance).unwrap();
+        save_game
+            .visit_node(&mut graph, other_player_instance)
+            .unwrap();
+        save_game.save(directory, "slot1").unwrap();
+        assert!(SaveGame::has_slot(directory, "slot1"));
+
+        // Load the state into a fresh instance of the level.
+        let (mut graph, player_instance, other_player_instance) = load_level();
+        let mut level = 0u32;
+
+        let mut save_game = SaveGame::load(directory, "slot1").unwrap().unwrap();
+        assert!(save_game.is_loading());
+        save_game.visit_value("Level", &mut level).unwrap();
+        // The order of nodes does not matter.
+        save_game
+            .visit_node(&mut graph, other_player_instance)
+            .unwrap();
+        save_game.visit_node(&mut graph, player_instance).unwrap();
+
+        assert_eq!(level, saved_level);
+        assert_eq!(
+            **graph[player_instance].local_transform().position(),
+            saved_position
+        );
+        assert_eq!(
+            graph[player_instance]
+                .script()
+                .unwrap()
+                .cast::<Player>()
+                .unwrap(),
+            &saved_player
+        );
+        assert!(!graph[player_instance].script().unwrap().initialized);
+        // The node with the same name keeps its own state.
+        assert_eq!(
+            **graph[other_player_instance].local_transform().position(),
+            Vector3::default()
+        );
+        assert_eq!(
+            graph[other_player_instance]
+                .script()
+                .unwrap()
+                .cast::<Player>()
+                .unwrap(),
+            &Player::default()
+        );
+
+        // A node of another scene is not in the save game.
+        let mut other_graph = Graph::new();
+        let stranger = make_node(&mut other_graph, Player::default(), Vector3::default());
+        assert!(save_game.visit_node(&mut other_graph, stranger).is_err());
+
+        SaveGame::delete_slot(directory, "slot1").unwrap();
+        assert!(!SaveGame::has_slot(directory, "slot1"));
+    }
+}
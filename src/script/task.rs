@@ -0,0 +1,188 @@
+//! Lightweight coroutine-style tasks for scripts. See [`ScriptTaskPool`] docs for more info.
+
+use crate::{
+    core::{futures::task::noop_waker_ref, pool::Handle},
+    scene::{graph::Graph, node::Node},
+    script::ScriptMessageSender,
+};
+use std::{
+    cell::Cell,
+    fmt::{Debug, Formatter},
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll},
+};
+
+#[derive(Default)]
+struct FrameState {
+    dt: Cell<f32>,
+    elapsed_time: Cell<f32>,
+    frame: Cell<u64>,
+}
+
+/// A limited context, that is available to script tasks. Tasks cannot borrow the scene across `.await`
+/// points, so the only way for a task to affect the scene is to send a message to a script using
+/// [`Self::message_sender`].
+#[derive(Clone)]
+pub struct TaskContext {
+    node: Handle<Node>,
+    state: Rc<FrameState>,
+    message_sender: ScriptMessageSender,
+}
+
+impl TaskContext {
+    /// Returns a handle of the node, that owns the task.
+    pub fn node(&self) -> Handle<Node> {
+        self.node
+    }
+
+    /// Returns amount of time that passed from the last frame.
+    pub fn dt(&self) -> f32 {
+        self.state.dt.get()
+    }
+
+    /// Returns amount of time (in seconds) that passed from creation of the engine.
+    pub fn elapsed_time(&self) -> f32 {
+        self.state.elapsed_time.get()
+    }
+
+    /// Returns a message sender, that could be used to send messages to scripts. See
+    /// [`crate::script::ScriptTrait::on_message`] for more info.
+    pub fn message_sender(&self) -> &ScriptMessageSender {
+        &self.message_sender
+    }
+
+    /// Suspends the task until the next frame.
+    pub fn next_frame(&self) -> NextFrame {
+        NextFrame {
+            state: self.state.clone(),
+            frame: self.state.frame.get(),
+        }
+    }
+
+    /// Suspends the task for the given amount of seconds. The task will be resumed on the first frame
+    /// after the time has passed.
+    pub async fn wait(&self, seconds: f32) {
+        let end = self.elapsed_time() + seconds;
+        while self.elapsed_time() < end {
+            self.next_frame().await;
+        }
+    }
+}
+
+/// A future, that resolves on the next frame. See [`TaskContext::next_frame`].
+pub struct NextFrame {
+    state: Rc<FrameState>,
+    frame: u64,
+}
+
+impl Future for NextFrame {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.state.frame.get() > self.frame {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+struct ScriptTask {
+    node: Handle<Node>,
+    future: Pin<Box<dyn Future<Output = ()>>>,
+}
+
+/// Task pool holds every script task of a scene. A task is an `async` block, that is resumed once per frame
+/// after every script of the scene was updated. Tasks are bound to the node, that spawned them - if the node
+/// is removed, all of its tasks are cancelled (dropped) on the next frame. Tasks are not serialized.
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox::script::ScriptContext;
+///
+/// struct OpenDoor;
+///
+/// fn on_start(ctx: &mut ScriptContext) {
+///     let door = ctx.handle;
+///     ctx.spawn_task(|task| async move {
+///         // Wait 2 seconds and then open the door.
+///         task.wait(2.0).await;
+///         task.message_sender().send_to_target(door, OpenDoor);
+///     });
+/// }
+/// ```
+#[derive(Default)]
+pub struct ScriptTaskPool {
+    tasks: Vec<ScriptTask>,
+    state: Rc<FrameState>,
+}
+
+impl Debug for ScriptTaskPool {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ScriptTaskPool ({} tasks)", self.tasks.len())
+    }
+}
+
+impl ScriptTaskPool {
+    /// Spawns a new task that belongs to the given node. The task will be polled for the first time at the
+    /// end of the current frame.
+    pub fn spawn<F, Fut>(
+        &mut self,
+        node: Handle<Node>,
+        message_sender: ScriptMessageSender,
+        func: F,
+    ) where
+        F: FnOnce(TaskContext) -> Fut,
+        Fut: Future<Output = ()> + 'static,
+    {
+        let context = TaskContext {
+            node,
+            state: self.state.clone(),
+            message_sender,
+        };
+        self.tasks.push(ScriptTask {
+            node,
+            future: Box::pin(func(context)),
+        });
+    }
+
+    /// Returns total amount of running tasks.
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Returns `true` if there's no running tasks.
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// Cancels every task of the given node.
+    pub fn cancel_tasks_of(&mut self, node: Handle<Node>) {
+        self.tasks.retain(|task| task.node != node);
+    }
+
+    /// Resumes every task once. Tasks of dead nodes are cancelled, finished tasks are removed.
+    pub(crate) fn update(&mut self, graph: &Graph, dt: f32, elapsed_time: f32) {
+        self.state.dt.set(dt);
+        self.state.elapsed_time.set(elapsed_time);
+
+        // Tasks can spawn other tasks only via the script context, so the list cannot be modified while
+        // polling.
+        let mut cx = Context::from_waker(noop_waker_ref());
+        let mut i = 0;
+        while i < self.tasks.len() {
+            let task = &mut self.tasks[i];
+            if graph.is_valid_handle(task.node) && task.future.as_mut().poll(&mut cx).is_pending() {
+                i += 1;
+            } else {
+                // Order of tasks does not matter.
+                self.tasks.swap_remove(i);
+            }
+        }
+
+        self.state.frame.set(self.state.frame.get() + 1);
+    }
+}
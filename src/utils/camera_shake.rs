@@ -0,0 +1,227 @@
+//! Contains a helper that shakes a camera for a short period of time.
+//!
+//! Camera shake is a cheap but very effective way to make impacts, explosions, landings, etc. feel
+//! heavier. [`CameraShake`] perturbs local transform of a camera node with decaying noise and
+//! restores the original transform when the shake is over.
+
+#![warn(missing_docs)]
+
+use crate::{
+    core::{
+        algebra::{UnitQuaternion, Vector3},
+        pool::Handle,
+    },
+    scene::{graph::Graph, node::Node},
+};
+
+/// Base frequency of the noise (in radians per second) that is used to offset the camera.
+const FREQUENCY: f32 = 25.0;
+
+/// Max camera roll (in radians) per unit of intensity.
+const ROLL_SCALE: f32 = 0.05;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Pose {
+    position: Vector3<f32>,
+    rotation: UnitQuaternion<f32>,
+}
+
+/// Shakes a node (usually a camera) by adding decaying noise to its local position and rotation.
+///
+/// # Usage
+///
+/// Keep an instance of the helper in your script, call [`Self::shake`] when something heavy
+/// happens and call [`Self::update`] every frame. The shake does not prevent other code from
+/// moving the camera: if the local transform was changed by someone else between two updates,
+/// the new transform is used as a base for the rest of the shake. When the shake is over, the
+/// original (or the most recent external) transform is restored exactly.
+///
+/// ```rust
+/// use fyrox::{
+///     core::pool::Handle,
+///     scene::{graph::Graph, node::Node},
+///     utils::camera_shake::CameraShake,
+/// };
+///
+/// fn on_landing(shake: &mut CameraShake) {
+///     shake.shake(0.2, 0.3);
+/// }
+///
+/// fn on_update(shake: &mut CameraShake, camera: Handle<Node>, graph: &mut Graph, dt: f32) {
+///     shake.update(camera, graph, dt);
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CameraShake {
+    intensity: f32,
+    duration: f32,
+    elapsed: f32,
+    original: Option<Pose>,
+    applied: Option<Pose>,
+}
+
+fn noise(t: f32, seed: f32) -> f32 {
+    // Sum of two sines with incommensurate frequencies looks random enough for a short shake.
+    0.6 * (t * FREQUENCY * (1.0 + 0.37 * seed) + 1.7 * seed).sin()
+        + 0.4 * (t * FREQUENCY * 2.3 + 3.1 * seed).sin()
+}
+
+impl CameraShake {
+    /// Creates new camera shake helper that does nothing until [`Self::shake`] is called.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts a new shake with the given intensity (max offset in local units) and duration (in
+    /// seconds). If the camera is already shaking, the shake is restarted with the largest of the
+    /// two intensities.
+    pub fn shake(&mut self, intensity: f32, duration: f32) {
+        self.intensity = intensity.max(self.current_intensity());
+        self.duration = duration.max(0.0);
+        self.elapsed = 0.0;
+    }
+
+    /// Returns `true` if the shake is in progress.
+    pub fn is_shaking(&self) -> bool {
+        self.elapsed < self.duration
+    }
+
+    /// Returns current (decayed) intensity of the shake.
+    pub fn current_intensity(&self) -> f32 {
+        if self.is_shaking() {
+            let decay = 1.0 - self.elapsed / self.duration;
+            self.intensity * decay * decay
+        } else {
+            0.0
+        }
+    }
+
+    /// Stops the shake immediately and restores the original transform of the camera.
+    pub fn stop(&mut self, camera: Handle<Node>, graph: &mut Graph) {
+        self.elapsed = self.duration;
+        self.update(camera, graph, 0.0);
+    }
+
+    /// Advances the shake by `dt` seconds and applies the offset to the local transform of the
+    /// given camera. Must be called every frame, it does nothing if there is no active shake.
+    pub fn update(&mut self, camera: Handle<Node>, graph: &mut Graph, dt: f32) {
+        if !self.is_shaking() && self.original.is_none() {
+            return;
+        }
+
+        let transform = match graph.try_get_mut(camera) {
+            Some(node) => node.local_transform_mut(),
+            None => {
+                self.original = None;
+                self.applied = None;
+                return;
+            }
+        };
+
+        let current = Pose {
+            position: **transform.position(),
+            rotation: **transform.rotation(),
+        };
+
+        let mut original = self.original.unwrap_or(current);
+        if let Some(applied) = self.applied {
+            // Someone else has moved the camera since the last update, respect the new transform.
+            if applied.position != current.position {
+                original.position = current.position;
+            }
+            if applied.rotation != current.rotation {
+                original.rotation = current.rotation;
+            }
+        }
+
+        self.elapsed += dt;
+
+        if !self.is_shaking() {
+            transform
+                .set_position(original.position)
+                .set_rotation(original.rotation);
+            self.original = None;
+            self.applied = None;
+            return;
+        }
+
+        let amplitude = self.current_intensity();
+        let t = self.elapsed;
+        let offset = Vector3::new(noise(t, 0.0), noise(t, 1.0), noise(t, 2.0)).scale(amplitude);
+        let roll = UnitQuaternion::from_axis_angle(
+            &Vector3::z_axis(),
+            noise(t, 3.0) * amplitude * ROLL_SCALE,
+        );
+
+        let applied = Pose {
+            position: original.position + offset,
+            rotation: original.rotation * roll,
+        };
+
+        transform
+            .set_position(applied.position)
+            .set_rotation(applied.rotation);
+
+        self.original = Some(original);
+        self.applied = Some(applied);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::{UnitQuaternion, Vector3},
+        scene::{
+            base::BaseBuilder, camera::CameraBuilder, graph::Graph, transform::TransformBuilder,
+        },
+        utils::camera_shake::CameraShake,
+    };
+
+    #[test]
+    fn test_shake_perturbs_and_restores_camera_transform() {
+        let mut graph = Graph::new();
+
+        let position = Vector3::new(1.0, 2.0, 3.0);
+        let rotation = UnitQuaternion::from_axis_angle(&Vector3::y_axis(), 0.5);
+        let camera = CameraBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(position)
+                    .with_local_rotation(rotation)
+                    .build(),
+            ),
+        )
+        .build(&mut graph);
+
+        let mut shake = CameraShake::new();
+        assert!(!shake.is_shaking());
+
+        shake.shake(0.5, 1.0);
+        assert!(shake.is_shaking());
+
+        let dt = 1.0 / 60.0;
+        let mut perturbed = false;
+        let mut steps = 0;
+        while shake.is_shaking() {
+            shake.update(camera, &mut graph, dt);
+            let transform = graph[camera].local_transform();
+            if shake.is_shaking() && **transform.position() != position {
+                perturbed = true;
+                assert!((**transform.position() - position).norm() <= 0.5 * 3.0f32.sqrt());
+            }
+            steps += 1;
+            assert!(steps < 1000);
+        }
+
+        assert!(perturbed);
+        assert!(steps as f32 * dt >= 1.0);
+
+        let transform = graph[camera].local_transform();
+        assert_eq!(**transform.position(), position);
+        assert_eq!(**transform.rotation(), rotation);
+
+        // Idle updates must not touch the camera.
+        shake.update(camera, &mut graph, dt);
+        assert_eq!(**graph[camera].local_transform().position(), position);
+    }
+}
@@ -0,0 +1,146 @@
+//! Contains helpers to track state of user-defined input actions across frames.
+//!
+//! OS events only tell when a key or a button changes its state, which is not enough for game logic
+//! in most cases. For example, a jump must be performed only once per key press and must not repeat
+//! while the key is held. [`InputActions`] tracks state of every action and allows you to detect
+//! the exact frame when an action was pressed or released.
+
+#![warn(missing_docs)]
+
+use fxhash::FxHashSet;
+use std::hash::Hash;
+
+/// Tracks state of user-defined input actions with edge detection. Actions could be anything
+/// hashable, usually it is a simple enumeration (`Jump`, `WalkForward`, etc.).
+///
+/// # Usage
+///
+/// Call [`Self::set_down`] when an input event for an action is received, query the state of the
+/// actions in your update logic and then call [`Self::update`] once per frame, at the end of the
+/// update.
+///
+/// ```rust
+/// use fyrox::utils::input::InputActions;
+///
+/// #[derive(Copy, Clone, PartialEq, Eq, Hash)]
+/// enum Action {
+///     Jump,
+/// }
+///
+/// let mut actions = InputActions::new();
+///
+/// // A key was pressed.
+/// actions.set_down(Action::Jump, true);
+/// assert!(actions.just_pressed(Action::Jump));
+/// actions.update();
+///
+/// // The key is still held on the next frame.
+/// assert!(actions.is_down(Action::Jump));
+/// assert!(!actions.just_pressed(Action::Jump));
+/// ```
+#[derive(Clone, Debug)]
+pub struct InputActions<A> {
+    down: FxHashSet<A>,
+    previously_down: FxHashSet<A>,
+}
+
+impl<A> Default for InputActions<A> {
+    fn default() -> Self {
+        Self {
+            down: Default::default(),
+            previously_down: Default::default(),
+        }
+    }
+}
+
+impl<A> InputActions<A>
+where
+    A: Copy + Hash + Eq,
+{
+    /// Creates new action tracker with all actions released.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets current state of the given action.
+    pub fn set_down(&mut self, action: A, down: bool) {
+        if down {
+            self.down.insert(action);
+        } else {
+            self.down.remove(&action);
+        }
+    }
+
+    /// Returns `true` if the action is held down at the current frame.
+    pub fn is_down(&self, action: A) -> bool {
+        self.down.contains(&action)
+    }
+
+    /// Returns `true` only at the frame when the action went down.
+    pub fn just_pressed(&self, action: A) -> bool {
+        self.down.contains(&action) && !self.previously_down.contains(&action)
+    }
+
+    /// Returns `true` only at the frame when the action went up.
+    pub fn just_released(&self, action: A) -> bool {
+        !self.down.contains(&action) && self.previously_down.contains(&action)
+    }
+
+    /// Finishes current frame. Must be called once per frame after all the actions were handled,
+    /// otherwise [`Self::just_pressed`] and [`Self::just_released`] will report wrong state.
+    pub fn update(&mut self) {
+        self.previously_down.clone_from(&self.down);
+    }
+
+    /// Releases every action, it could be useful when a window loses focus.
+    pub fn release_all(&mut self) {
+        self.down.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::utils::input::InputActions;
+
+    #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+    enum Action {
+        Jump,
+        Fire,
+    }
+
+    #[test]
+    fn test_just_pressed_only_on_transition_frame() {
+        let mut actions = InputActions::new();
+
+        // Frame 1: nothing is pressed.
+        assert!(!actions.is_down(Action::Jump));
+        assert!(!actions.just_pressed(Action::Jump));
+        actions.update();
+
+        // Frame 2: jump goes down.
+        actions.set_down(Action::Jump, true);
+        assert!(actions.is_down(Action::Jump));
+        assert!(actions.just_pressed(Action::Jump));
+        assert!(!actions.just_pressed(Action::Fire));
+        actions.update();
+
+        // Frame 3 and 4: jump is held, repeated OS events must not retrigger it.
+        for _ in 0..2 {
+            actions.set_down(Action::Jump, true);
+            assert!(actions.is_down(Action::Jump));
+            assert!(!actions.just_pressed(Action::Jump));
+            assert!(!actions.just_released(Action::Jump));
+            actions.update();
+        }
+
+        // Frame 5: jump goes up.
+        actions.set_down(Action::Jump, false);
+        assert!(!actions.is_down(Action::Jump));
+        assert!(!actions.just_pressed(Action::Jump));
+        assert!(actions.just_released(Action::Jump));
+        actions.update();
+
+        // Frame 6: released state is reported only once.
+        assert!(!actions.just_released(Action::Jump));
+    }
+}
@@ -202,6 +202,36 @@ impl Deref for ProgressIndicator {
     }
 }
 
+/// Settings of lightmap baking, see [`Scene::bake_lightmaps`] for more info.
+#[derive(Clone)]
+pub struct LightmapBakingSettings {
+    /// Defines resolution of lightmaps, the higher value is, the more quality lightmap will be
+    /// generated, but also it will be slow to generate.
+    pub texels_per_unit: u32,
+    /// If set, only meshes with [`crate::scene::base::Mobility::Static`] or
+    /// [`crate::scene::base::Mobility::Stationary`] mobility will receive lightmaps. Lights are
+    /// used regardless of their mobility.
+    pub static_geometry_only: bool,
+    /// Allows you to stop baking in any time.
+    pub cancellation_token: CancellationToken,
+    /// Allows you to get info about current progress.
+    pub progress_indicator: ProgressIndicator,
+}
+
+impl Default for LightmapBakingSettings {
+    fn default() -> Self {
+        Self {
+            texels_per_unit: 64,
+            static_geometry_only: true,
+            cancellation_token: Default::default(),
+            progress_indicator: Default::default(),
+        }
+    }
+}
+
+/// Result of lightmap baking. On success, it contains previous lightmap of the scene (if any).
+pub type LightmapResult = Result<Option<Lightmap>, LightmapGenerationError>;
+
 /// An error that may occur during ligthmap generation.
 #[derive(Debug)]
 pub enum LightmapGenerationError {
@@ -209,6 +239,8 @@ pub enum LightmapGenerationError {
     Cancelled,
     /// Vertex buffer of a mesh lacks required data.
     InvalidData(VertexFetchError),
+    /// Generated lightmap could not be applied to the scene.
+    ApplyFailed(&'static str),
 }
 
 impl Display for LightmapGenerationError {
@@ -220,6 +252,9 @@ impl Display for LightmapGenerationError {
             LightmapGenerationError::InvalidData(v) => {
                 write!(f, "Vertex buffer of a mesh lacks required data {v}.")
             }
+            LightmapGenerationError::ApplyFailed(reason) => {
+                write!(f, "Unable to apply the lightmap to the scene: {reason}.")
+            }
         }
     }
 }
@@ -928,7 +963,7 @@ mod test {
     use crate::{
         core::algebra::{Matrix4, Vector3},
         scene::{
-            base::BaseBuilder,
+            base::{BaseBuilder, Mobility},
             light::{point::PointLightBuilder, BaseLightBuilder},
             mesh::{
                 surface::{SurfaceBuilder, SurfaceData},
@@ -937,7 +972,7 @@ mod test {
             transform::TransformBuilder,
             Scene,
         },
-        utils::lightmap::Lightmap,
+        utils::lightmap::{Lightmap, LightmapBakingSettings},
     };
 
     #[test]
@@ -986,4 +1021,58 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_bake_lightmaps() {
+        let mut scene = Scene::new();
+
+        let plane = MeshBuilder::new(BaseBuilder::new().with_mobility(Mobility::Static))
+            .with_surfaces(vec![SurfaceBuilder::new(SurfaceSharedData::new(
+                SurfaceData::make_quad(&Matrix4::new_scaling(4.0)),
+            ))
+            .build()])
+            .build(&mut scene.graph);
+
+        PointLightBuilder::new(BaseLightBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(0.0, 0.0, -2.0))
+                    .build(),
+            ),
+        ))
+        .with_radius(4.0)
+        .build(&mut scene.graph);
+
+        let previous = scene
+            .bake_lightmaps(LightmapBakingSettings {
+                texels_per_unit: 16,
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(previous.is_none());
+
+        let lightmap = scene.lightmap().unwrap();
+        let entries = lightmap.map.get(&plane).unwrap();
+        assert_eq!(entries.len(), 1);
+        let texture = entries[0].texture.as_ref().unwrap().data_ref();
+        assert!(!texture.data().is_empty());
+        assert!(texture.data().iter().any(|b| *b != 0));
+    }
+
+    #[test]
+    fn test_bake_lightmaps_cancelled() {
+        let mut scene = Scene::new();
+
+        MeshBuilder::new(BaseBuilder::new().with_mobility(Mobility::Static))
+            .with_surfaces(vec![SurfaceBuilder::new(SurfaceSharedData::new(
+                SurfaceData::make_quad(&Matrix4::identity()),
+            ))
+            .build()])
+            .build(&mut scene.graph);
+
+        let settings = LightmapBakingSettings::default();
+        settings.cancellation_token.cancel();
+        assert!(scene.bake_lightmaps(settings).is_err());
+        assert!(scene.lightmap().is_none());
+    }
 }
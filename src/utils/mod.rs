@@ -4,7 +4,9 @@
 
 pub mod astar;
 pub mod behavior;
+pub mod camera_shake;
 pub mod component;
+pub mod input;
 pub mod lightmap;
 pub mod navmesh;
 pub mod raw_mesh;